@@ -40,6 +40,9 @@ fn create_entity(name: &str, label: EntityLabel, salience: f32) -> EntityNode {
         name_embedding: None,
         salience,
         is_proper_noun: true,
+        aliases: Vec::new(),
+        centrality: 0.0,
+        community: None,
     }
 }
 
@@ -54,6 +54,9 @@ fn create_entity(
         name_embedding: None,
         salience,
         is_proper_noun: is_proper,
+        aliases: Vec::new(),
+        centrality: 0.0,
+        community: None,
     }
 }
 
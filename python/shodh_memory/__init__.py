@@ -15,6 +15,7 @@ Features:
 - Position(x, y, z) - Local robot coordinates in meters
 - GeoLocation(lat, lon, alt) - GPS for drones & outdoor robots
 - GeoFilter - Spatial queries by radius
+- LocalFilter - Radius, bounding-box and k-nearest queries in a robot's map frame
 - DecisionContext - For action-outcome learning (what conditions -> what action)
 - Outcome - Result of decisions (success/failure/partial + reward signal)
 - Environment - Weather, terrain, lighting, nearby agents
@@ -47,6 +48,7 @@ from .shodh_memory import (
     Position,
     GeoLocation,
     GeoFilter,
+    LocalFilter,
     # Decision & Learning types
     DecisionContext,
     Outcome,
@@ -66,6 +68,7 @@ __all__ = [
     "Position",
     "GeoLocation",
     "GeoFilter",
+    "LocalFilter",
     # Decision & Learning types
     "DecisionContext",
     "Outcome",
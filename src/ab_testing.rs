@@ -11,6 +11,7 @@
 //! - Statistical significance testing (chi-squared, confidence intervals)
 //! - Metric tracking (impressions, clicks, success rate, latency)
 //! - Automatic winner detection with configurable significance threshold
+//! - Optional RocksDB persistence: tests, metrics and assignments survive restarts
 //!
 //! # Example
 //!
@@ -46,10 +47,13 @@
 
 use std::collections::HashMap;
 use std::hash::{Hash, Hasher};
+use std::path::Path;
 use std::sync::Arc;
 
+use anyhow::Context;
 use chrono::{DateTime, Duration, Utc};
 use parking_lot::RwLock;
+use rocksdb::{Options, WriteBatch, DB};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
@@ -1441,11 +1445,30 @@ pub struct ComprehensiveAnalysis {
 // =============================================================================
 
 /// Manager for multiple A/B tests
+///
+/// Optionally backed by RocksDB (`{base_path}/ab_tests`) so experiments survive
+/// restarts. Storage layout:
+/// - `test:{test_id}`     → JSON-encoded active [`ABTest`] (config, status, metrics)
+/// - `archived:{test_id}` → JSON-encoded archived [`ABTest`]
+/// - `assign:{test_id}:{user_id}` → JSON-encoded [`StoredAssignment`]
 pub struct ABTestManager {
     /// Active tests by ID
     tests: Arc<RwLock<HashMap<String, ABTest>>>,
     /// Archived tests (for historical analysis)
     archived: Arc<RwLock<Vec<ABTest>>>,
+    /// Persistent storage (None = in-memory only)
+    db: Option<Arc<DB>>,
+}
+
+/// Persisted user → variant assignment
+///
+/// Assignments are stored as separate keys so a new user costs one small
+/// write instead of re-serializing the whole assignment map.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct StoredAssignment {
+    test_id: String,
+    user_id: String,
+    variant: ABTestVariant,
 }
 
 impl Default for ABTestManager {
@@ -1455,14 +1478,170 @@ impl Default for ABTestManager {
 }
 
 impl ABTestManager {
-    /// Create a new test manager
+    /// Create a new in-memory test manager
     pub fn new() -> Self {
         Self {
             tests: Arc::new(RwLock::new(HashMap::new())),
             archived: Arc::new(RwLock::new(Vec::new())),
+            db: None,
         }
     }
 
+    /// Open (or create) the persistent test store at `{storage_path}/ab_tests`
+    /// and reload every experiment with its metrics, assignments and status
+    pub fn with_persistence(storage_path: &Path) -> anyhow::Result<Self> {
+        let path = storage_path.join("ab_tests");
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        opts.set_compression_type(rocksdb::DBCompressionType::Lz4);
+        let db = Arc::new(DB::open(&opts, &path).context("Failed to open A/B test store")?);
+
+        let mut tests = HashMap::new();
+        let mut archived = Vec::new();
+        let mut assignments = Vec::new();
+
+        for item in db.iterator(rocksdb::IteratorMode::Start) {
+            let (key, value) = item.context("Failed to iterate A/B test store")?;
+            let Ok(key_str) = std::str::from_utf8(&key) else {
+                continue;
+            };
+
+            if key_str.starts_with("test:") || key_str.starts_with("archived:") {
+                match serde_json::from_slice::<ABTest>(&value) {
+                    Ok(mut test) => {
+                        test.id = test.config.id.clone();
+                        if key_str.starts_with("test:") {
+                            tests.insert(test.id.clone(), test);
+                        } else {
+                            archived.push(test);
+                        }
+                    }
+                    Err(e) => tracing::warn!("Skipping corrupt A/B test record {}: {}", key_str, e),
+                }
+            } else if key_str.starts_with("assign:") {
+                match serde_json::from_slice::<StoredAssignment>(&value) {
+                    Ok(assignment) => assignments.push(assignment),
+                    Err(e) => tracing::warn!("Skipping corrupt A/B assignment {}: {}", key_str, e),
+                }
+            }
+        }
+
+        for assignment in assignments {
+            if let Some(test) = tests.get_mut(&assignment.test_id) {
+                test.user_assignments
+                    .insert(assignment.user_id, assignment.variant);
+            }
+        }
+
+        archived.sort_by_key(|t: &ABTest| t.completed_at.unwrap_or(t.created_at));
+
+        tracing::info!(
+            "A/B test store loaded: {} active, {} archived",
+            tests.len(),
+            archived.len()
+        );
+
+        Ok(Self {
+            tests: Arc::new(RwLock::new(tests)),
+            archived: Arc::new(RwLock::new(archived)),
+            db: Some(db),
+        })
+    }
+
+    /// Get the underlying database (for backup), if persistent
+    pub fn database(&self) -> Option<&Arc<DB>> {
+        self.db.as_ref()
+    }
+
+    /// Flush persisted tests to disk
+    pub fn flush(&self) -> anyhow::Result<()> {
+        if let Some(ref db) = self.db {
+            db.flush()
+                .map_err(|e| anyhow::anyhow!("Failed to flush ab_tests db: {e}"))?;
+        }
+        Ok(())
+    }
+
+    /// Write an active test (and optionally a new user assignment) to disk
+    fn persist_test(
+        &self,
+        test: &ABTest,
+        new_assignment: Option<(&str, ABTestVariant)>,
+    ) -> Result<(), ABTestError> {
+        let Some(ref db) = self.db else {
+            return Ok(());
+        };
+
+        let mut batch = WriteBatch::default();
+        batch.put(format!("test:{}", test.id).as_bytes(), encode(test)?);
+        if let Some((user_id, variant)) = new_assignment {
+            let assignment = StoredAssignment {
+                test_id: test.id.clone(),
+                user_id: user_id.to_string(),
+                variant,
+            };
+            batch.put(
+                format!("assign:{}:{}", test.id, user_id).as_bytes(),
+                encode(&assignment)?,
+            );
+        }
+
+        db.write(batch)
+            .map_err(|e| ABTestError::Storage(e.to_string()))
+    }
+
+    /// Remove an active test and its assignments from disk, optionally
+    /// re-writing it under the archived prefix
+    fn persist_removal(&self, test: &ABTest, archive: bool) -> Result<(), ABTestError> {
+        let Some(ref db) = self.db else {
+            return Ok(());
+        };
+
+        let mut batch = WriteBatch::default();
+        batch.delete(format!("test:{}", test.id).as_bytes());
+        for user_id in test.user_assignments.keys() {
+            batch.delete(format!("assign:{}:{}", test.id, user_id).as_bytes());
+        }
+        if archive {
+            batch.put(format!("archived:{}", test.id).as_bytes(), encode(test)?);
+        }
+
+        db.write(batch)
+            .map_err(|e| ABTestError::Storage(e.to_string()))
+    }
+
+    /// Apply `update` to a test under the write lock and persist the result
+    ///
+    /// When `user_id` is given and the update assigns that user a variant for
+    /// the first time, the assignment is written in the same batch.
+    fn update_test<T>(
+        &self,
+        test_id: &str,
+        user_id: Option<&str>,
+        require_running: bool,
+        update: impl FnOnce(&mut ABTest) -> T,
+    ) -> Result<T, ABTestError> {
+        let mut tests = self.tests.write();
+        let test = tests
+            .get_mut(test_id)
+            .ok_or_else(|| ABTestError::TestNotFound(test_id.to_string()))?;
+
+        if require_running && test.status != ABTestStatus::Running {
+            return Err(ABTestError::TestNotRunning(test_id.to_string()));
+        }
+
+        let was_assigned = user_id.is_some_and(|u| test.user_assignments.contains_key(u));
+        let result = update(test);
+
+        let new_assignment = match user_id {
+            Some(u) if !was_assigned => test.user_assignments.get(u).map(|&v| (u, v)),
+            _ => None,
+        };
+        self.persist_test(test, new_assignment)?;
+
+        Ok(result)
+    }
+
     /// Create a new A/B test
     pub fn create_test(&self, test: ABTest) -> Result<String, ABTestError> {
         let id = test.config.id.clone();
@@ -1472,6 +1651,7 @@ impl ABTestManager {
             return Err(ABTestError::TestAlreadyExists(id));
         }
 
+        self.persist_test(&test, None)?;
         tests.insert(id.clone(), test);
         Ok(id)
     }
@@ -1498,42 +1678,27 @@ impl ABTestManager {
 
     /// Start a test
     pub fn start_test(&self, test_id: &str) -> Result<(), ABTestError> {
-        let mut tests = self.tests.write();
-        let test = tests
-            .get_mut(test_id)
-            .ok_or_else(|| ABTestError::TestNotFound(test_id.to_string()))?;
-
-        if test.status != ABTestStatus::Draft {
-            return Err(ABTestError::InvalidState(format!(
-                "Cannot start test in {:?} state",
-                test.status
-            )));
-        }
+        self.update_test(test_id, None, false, |test| {
+            if test.status != ABTestStatus::Draft {
+                return Err(ABTestError::InvalidState(format!(
+                    "Cannot start test in {:?} state",
+                    test.status
+                )));
+            }
 
-        test.start();
-        Ok(())
+            test.start();
+            Ok(())
+        })?
     }
 
     /// Pause a test
     pub fn pause_test(&self, test_id: &str) -> Result<(), ABTestError> {
-        let mut tests = self.tests.write();
-        let test = tests
-            .get_mut(test_id)
-            .ok_or_else(|| ABTestError::TestNotFound(test_id.to_string()))?;
-
-        test.pause();
-        Ok(())
+        self.update_test(test_id, None, false, ABTest::pause)
     }
 
     /// Resume a test
     pub fn resume_test(&self, test_id: &str) -> Result<(), ABTestError> {
-        let mut tests = self.tests.write();
-        let test = tests
-            .get_mut(test_id)
-            .ok_or_else(|| ABTestError::TestNotFound(test_id.to_string()))?;
-
-        test.resume();
-        Ok(())
+        self.update_test(test_id, None, false, ABTest::resume)
     }
 
     /// Complete a test
@@ -1547,10 +1712,7 @@ impl ABTestManager {
             ABTestAnalyzer::analyze(test)
         };
 
-        let mut tests = self.tests.write();
-        if let Some(test) = tests.get_mut(test_id) {
-            test.complete();
-        }
+        self.update_test(test_id, None, false, ABTest::complete)?;
 
         Ok(results)
     }
@@ -1563,6 +1725,10 @@ impl ABTestManager {
             .ok_or_else(|| ABTestError::TestNotFound(test_id.to_string()))?;
 
         test.archive();
+        if let Err(e) = self.persist_removal(&test, true) {
+            tests.insert(test_id.to_string(), test);
+            return Err(e);
+        }
         self.archived.write().push(test);
 
         Ok(())
@@ -1571,24 +1737,19 @@ impl ABTestManager {
     /// Delete a test (permanent)
     pub fn delete_test(&self, test_id: &str) -> Result<(), ABTestError> {
         let mut tests = self.tests.write();
-        tests
-            .remove(test_id)
+        let test = tests
+            .get(test_id)
             .ok_or_else(|| ABTestError::TestNotFound(test_id.to_string()))?;
+        self.persist_removal(test, false)?;
+        tests.remove(test_id);
         Ok(())
     }
 
     /// Get variant for a user in a specific test
     pub fn get_variant(&self, test_id: &str, user_id: &str) -> Result<ABTestVariant, ABTestError> {
-        let mut tests = self.tests.write();
-        let test = tests
-            .get_mut(test_id)
-            .ok_or_else(|| ABTestError::TestNotFound(test_id.to_string()))?;
-
-        if test.status != ABTestStatus::Running {
-            return Err(ABTestError::TestNotRunning(test_id.to_string()));
-        }
-
-        Ok(test.get_variant(user_id))
+        self.update_test(test_id, Some(user_id), true, |test| {
+            test.get_variant(user_id)
+        })
     }
 
     /// Get weights for a user (handles test assignment)
@@ -1597,17 +1758,10 @@ impl ABTestManager {
         test_id: &str,
         user_id: &str,
     ) -> Result<LearnedWeights, ABTestError> {
-        let mut tests = self.tests.write();
-        let test = tests
-            .get_mut(test_id)
-            .ok_or_else(|| ABTestError::TestNotFound(test_id.to_string()))?;
-
-        if test.status != ABTestStatus::Running {
-            return Err(ABTestError::TestNotRunning(test_id.to_string()));
-        }
-
-        let variant = test.get_variant(user_id);
-        Ok(test.get_weights(variant).clone())
+        self.update_test(test_id, Some(user_id), true, |test| {
+            let variant = test.get_variant(user_id);
+            test.get_weights(variant).clone()
+        })
     }
 
     /// Record an impression
@@ -1618,17 +1772,9 @@ impl ABTestManager {
         relevance_score: f64,
         latency_us: u64,
     ) -> Result<(), ABTestError> {
-        let mut tests = self.tests.write();
-        let test = tests
-            .get_mut(test_id)
-            .ok_or_else(|| ABTestError::TestNotFound(test_id.to_string()))?;
-
-        if test.status != ABTestStatus::Running {
-            return Err(ABTestError::TestNotRunning(test_id.to_string()));
-        }
-
-        test.record_impression(user_id, relevance_score, latency_us);
-        Ok(())
+        self.update_test(test_id, Some(user_id), true, |test| {
+            test.record_impression(user_id, relevance_score, latency_us)
+        })
     }
 
     /// Record a click
@@ -1638,17 +1784,9 @@ impl ABTestManager {
         user_id: &str,
         memory_id: Uuid,
     ) -> Result<(), ABTestError> {
-        let mut tests = self.tests.write();
-        let test = tests
-            .get_mut(test_id)
-            .ok_or_else(|| ABTestError::TestNotFound(test_id.to_string()))?;
-
-        if test.status != ABTestStatus::Running {
-            return Err(ABTestError::TestNotRunning(test_id.to_string()));
-        }
-
-        test.record_click(user_id, memory_id);
-        Ok(())
+        self.update_test(test_id, Some(user_id), true, |test| {
+            test.record_click(user_id, memory_id)
+        })
     }
 
     /// Record explicit feedback
@@ -1658,17 +1796,9 @@ impl ABTestManager {
         user_id: &str,
         positive: bool,
     ) -> Result<(), ABTestError> {
-        let mut tests = self.tests.write();
-        let test = tests
-            .get_mut(test_id)
-            .ok_or_else(|| ABTestError::TestNotFound(test_id.to_string()))?;
-
-        if test.status != ABTestStatus::Running {
-            return Err(ABTestError::TestNotRunning(test_id.to_string()));
-        }
-
-        test.record_feedback(user_id, positive);
-        Ok(())
+        self.update_test(test_id, Some(user_id), true, |test| {
+            test.record_feedback(user_id, positive)
+        })
     }
 
     /// Analyze a test
@@ -1694,6 +1824,9 @@ impl ABTestManager {
         for (id, test) in tests.iter_mut() {
             if test.status == ABTestStatus::Running && test.is_expired() {
                 test.complete();
+                if let Err(e) = self.persist_test(test, None) {
+                    tracing::warn!("Failed to persist expired A/B test {}: {}", id, e);
+                }
                 expired.push(id.clone());
             }
         }
@@ -1764,6 +1897,14 @@ pub enum ABTestError {
 
     #[error("Insufficient data for analysis")]
     InsufficientData,
+
+    #[error("Storage error: {0}")]
+    Storage(String),
+}
+
+/// JSON-encode a record for the A/B test store
+fn encode<T: Serialize>(value: &T) -> Result<Vec<u8>, ABTestError> {
+    serde_json::to_vec(value).map_err(|e| ABTestError::Storage(e.to_string()))
 }
 
 // =============================================================================
@@ -2569,4 +2710,87 @@ mod tests {
         // O'Brien-Fleming is very conservative early
         assert!(seq.alpha_spent < 0.01);
     }
+
+    #[test]
+    fn test_persistence_survives_restart() {
+        let dir = tempfile::tempdir().unwrap();
+
+        let (running_id, paused_id, variant) = {
+            let manager = ABTestManager::with_persistence(dir.path()).unwrap();
+
+            let running_id = manager
+                .create_test(ABTest::builder("running").build())
+                .unwrap();
+            manager.start_test(&running_id).unwrap();
+            let variant = manager.get_variant(&running_id, "user_1").unwrap();
+            manager
+                .record_impression(&running_id, "user_1", 0.8, 1500)
+                .unwrap();
+            manager
+                .record_feedback(&running_id, "user_1", true)
+                .unwrap();
+
+            let paused_id = manager
+                .create_test(ABTest::builder("paused").build())
+                .unwrap();
+            manager.start_test(&paused_id).unwrap();
+            manager.pause_test(&paused_id).unwrap();
+
+            let archived_id = manager
+                .create_test(ABTest::builder("archived").build())
+                .unwrap();
+            manager.archive_test(&archived_id).unwrap();
+
+            (running_id, paused_id, variant)
+        };
+
+        let manager = ABTestManager::with_persistence(dir.path()).unwrap();
+
+        let running = manager.get_test(&running_id).unwrap();
+        assert_eq!(running.id, running_id);
+        assert_eq!(running.status, ABTestStatus::Running);
+        assert!(running.started_at.is_some());
+        let metrics = running.get_metrics(variant);
+        assert_eq!(metrics.impressions, 1);
+        assert_eq!(metrics.positive_feedback, 1);
+        assert_eq!(metrics.unique_users, 1);
+
+        // Assignment is restored, so the user is not counted twice
+        assert_eq!(manager.get_variant(&running_id, "user_1").unwrap(), variant);
+        let running = manager.get_test(&running_id).unwrap();
+        assert_eq!(running.get_metrics(variant).unique_users, 1);
+
+        assert_eq!(
+            manager.get_test(&paused_id).unwrap().status,
+            ABTestStatus::Paused
+        );
+        assert_eq!(manager.list_archived().len(), 1);
+        assert_eq!(manager.summary().total_active, 2);
+    }
+
+    #[test]
+    fn test_deleted_test_stays_deleted() {
+        let dir = tempfile::tempdir().unwrap();
+
+        let id = {
+            let manager = ABTestManager::with_persistence(dir.path()).unwrap();
+            let id = manager
+                .create_test(ABTest::builder("gone").build())
+                .unwrap();
+            manager.start_test(&id).unwrap();
+            manager.get_variant(&id, "user_1").unwrap();
+            manager.delete_test(&id).unwrap();
+            id
+        };
+
+        let manager = ABTestManager::with_persistence(dir.path()).unwrap();
+        assert!(manager.get_test(&id).is_none());
+        let leftover = manager
+            .database()
+            .unwrap()
+            .prefix_iterator(b"assign:")
+            .filter_map(|item| item.ok())
+            .count();
+        assert_eq!(leftover, 0);
+    }
 }
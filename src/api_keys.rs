@@ -0,0 +1,423 @@
+//! Persistent API Key Store - per-key tenant scoping and roles
+//!
+//! Replaces the flat `SHODH_API_KEYS` list as the source of truth for
+//! multi-tenant deployments. Every key is bound to:
+//! - a role (`read_only`, `writer`, `admin`) checked against the route
+//! - a set of allowed `user_id`s (or `*` for every user)
+//!
+//! Only the SHA-256 hash of a key is persisted. The plaintext secret is
+//! returned exactly once, on create or rotate.
+//!
+//! Storage layout (RocksDB at `{base_path}/api_keys`):
+//! - `key:{key_id}`    → JSON-encoded [`ApiKeyRecord`]
+//! - `hash:{sha256}`   → `key_id` (lookup index for authentication)
+//!
+//! Keys from `SHODH_API_KEYS` / `SHODH_DEV_API_KEY` keep working as
+//! bootstrap admin keys so an operator can create the first scoped keys.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use rand::RngCore;
+use rocksdb::{Options, WriteBatch, DB};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::path::Path;
+use std::sync::Arc;
+
+/// Prefix for generated API key secrets
+pub const API_KEY_SECRET_PREFIX: &str = "sk-shodh-";
+
+/// Wildcard entry in `allowed_user_ids` granting access to every user
+pub const ALL_USERS_WILDCARD: &str = "*";
+
+/// Number of leading secret characters kept for display (e.g. `sk-shodh-3fa9`)
+const DISPLAY_PREFIX_LEN: usize = 13;
+
+/// Role attached to an API key.
+///
+/// Roles are ordered: `Admin` can do everything `Writer` can,
+/// `Writer` can do everything `ReadOnly` can.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ApiKeyRole {
+    /// Recall, list, search and export only
+    ReadOnly,
+    /// Everything read-only can do, plus remember/update/forget
+    Writer,
+    /// Full access including key management and destructive bulk operations
+    Admin,
+}
+
+impl ApiKeyRole {
+    /// Whether this role satisfies the required role
+    pub fn allows(&self, required: ApiKeyRole) -> bool {
+        *self >= required
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::ReadOnly => "read_only",
+            Self::Writer => "writer",
+            Self::Admin => "admin",
+        }
+    }
+}
+
+impl std::fmt::Display for ApiKeyRole {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Persisted API key metadata (never contains the plaintext secret)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApiKeyRecord {
+    /// Stable key identifier (survives rotation)
+    pub id: String,
+    /// Human-readable label, e.g. "planner-agent" or "alice laptop"
+    pub name: String,
+    pub role: ApiKeyRole,
+    /// User IDs this key may act on. `["*"]` grants access to all users.
+    pub allowed_user_ids: Vec<String>,
+    /// First characters of the secret, for identifying keys in listings
+    pub key_prefix: String,
+    /// Hex-encoded SHA-256 of the current secret
+    #[serde(skip_serializing_if = "String::is_empty", default)]
+    pub key_hash: String,
+    pub created_at: DateTime<Utc>,
+    #[serde(default)]
+    pub rotated_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub revoked_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub last_used_at: Option<DateTime<Utc>>,
+}
+
+impl ApiKeyRecord {
+    pub fn is_revoked(&self) -> bool {
+        self.revoked_at.is_some()
+    }
+
+    /// Whether the key spans all users (`*` in `allowed_user_ids`)
+    pub fn is_unscoped(&self) -> bool {
+        self.allowed_user_ids
+            .iter()
+            .any(|u| u == ALL_USERS_WILDCARD)
+    }
+
+    /// Copy of the record safe to return from the API (hash stripped)
+    pub fn redacted(&self) -> Self {
+        Self {
+            key_hash: String::new(),
+            ..self.clone()
+        }
+    }
+}
+
+/// A freshly issued secret together with its record
+#[derive(Debug, Clone, Serialize)]
+pub struct IssuedApiKey {
+    /// Plaintext secret - shown once, never stored
+    pub key: String,
+    pub record: ApiKeyRecord,
+}
+
+/// Hash a plaintext secret for storage / lookup
+pub fn hash_api_key(secret: &str) -> String {
+    hex::encode(Sha256::digest(secret.as_bytes()))
+}
+
+/// Generate a new random secret: `sk-shodh-` followed by 64 hex chars
+fn generate_secret() -> String {
+    let mut bytes = [0u8; 32];
+    rand::thread_rng().fill_bytes(&mut bytes);
+    format!("{}{}", API_KEY_SECRET_PREFIX, hex::encode(bytes))
+}
+
+fn display_prefix(secret: &str) -> String {
+    secret.chars().take(DISPLAY_PREFIX_LEN).collect()
+}
+
+/// Storage for scoped API keys
+pub struct ApiKeyStore {
+    db: Arc<DB>,
+}
+
+impl ApiKeyStore {
+    /// Open (or create) the key store under `{storage_path}/api_keys`
+    pub fn new(storage_path: &Path) -> Result<Self> {
+        let path = storage_path.join("api_keys");
+        std::fs::create_dir_all(&path)?;
+
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        opts.set_compression_type(rocksdb::DBCompressionType::Lz4);
+        // Tiny dataset - keep memtables small
+        opts.set_max_write_buffer_number(2);
+        opts.set_write_buffer_size(4 * 1024 * 1024);
+
+        let db = Arc::new(DB::open(&opts, &path).context("Failed to open API key store")?);
+
+        Ok(Self { db })
+    }
+
+    /// Flush to disk (graceful shutdown)
+    pub fn flush(&self) -> Result<()> {
+        self.db
+            .flush()
+            .map_err(|e| anyhow::anyhow!("Failed to flush api_keys db: {e}"))
+    }
+
+    /// Database reference for comprehensive backups
+    pub fn database(&self) -> &Arc<DB> {
+        &self.db
+    }
+
+    /// Issue a new key. Returns the plaintext secret exactly once.
+    pub fn create(
+        &self,
+        name: &str,
+        role: ApiKeyRole,
+        allowed_user_ids: Vec<String>,
+    ) -> Result<IssuedApiKey> {
+        let secret = generate_secret();
+        let record = ApiKeyRecord {
+            id: format!("key_{}", uuid::Uuid::new_v4().simple()),
+            name: name.to_string(),
+            role,
+            allowed_user_ids,
+            key_prefix: display_prefix(&secret),
+            key_hash: hash_api_key(&secret),
+            created_at: Utc::now(),
+            rotated_at: None,
+            revoked_at: None,
+            last_used_at: None,
+        };
+
+        let mut batch = WriteBatch::default();
+        batch.put(
+            format!("key:{}", record.id).as_bytes(),
+            serde_json::to_vec(&record)?,
+        );
+        batch.put(
+            format!("hash:{}", record.key_hash).as_bytes(),
+            record.id.as_bytes(),
+        );
+        self.db
+            .write(batch)
+            .context("Failed to persist new API key")?;
+
+        tracing::info!(key_id = %record.id, role = %role, "Created API key");
+
+        Ok(IssuedApiKey {
+            key: secret,
+            record: record.redacted(),
+        })
+    }
+
+    /// Get a key record by ID
+    pub fn get(&self, key_id: &str) -> Result<Option<ApiKeyRecord>> {
+        match self.db.get(format!("key:{key_id}").as_bytes())? {
+            Some(bytes) => Ok(Some(
+                serde_json::from_slice(&bytes).context("Corrupt API key record")?,
+            )),
+            None => Ok(None),
+        }
+    }
+
+    /// List all key records (including revoked ones), oldest first
+    pub fn list(&self) -> Result<Vec<ApiKeyRecord>> {
+        let mut records = Vec::new();
+        for item in self.db.prefix_iterator(b"key:") {
+            let (key, value) = item?;
+            if !key.starts_with(b"key:") {
+                break;
+            }
+            match serde_json::from_slice::<ApiKeyRecord>(&value) {
+                Ok(record) => records.push(record),
+                Err(e) => tracing::warn!("Skipping corrupt API key record: {}", e),
+            }
+        }
+        records.sort_by_key(|r| r.created_at);
+        Ok(records)
+    }
+
+    /// Whether any non-revoked key exists
+    pub fn has_active_keys(&self) -> bool {
+        self.list()
+            .map(|records| records.iter().any(|r| !r.is_revoked()))
+            .unwrap_or(false)
+    }
+
+    /// Look up the active record for a plaintext secret
+    ///
+    /// Returns `None` for unknown or revoked keys. Lookup goes through the
+    /// SHA-256 index, so no secret material is compared byte-by-byte.
+    pub fn authenticate(&self, secret: &str) -> Result<Option<ApiKeyRecord>> {
+        let hash = hash_api_key(secret);
+        let key_id = match self.db.get(format!("hash:{hash}").as_bytes())? {
+            Some(id) => String::from_utf8_lossy(&id).to_string(),
+            None => return Ok(None),
+        };
+
+        let Some(mut record) = self.get(&key_id)? else {
+            return Ok(None);
+        };
+        if record.is_revoked() || record.key_hash != hash {
+            return Ok(None);
+        }
+
+        // Best-effort usage tracking, throttled to one write per minute per key.
+        // Auth must never fail on this write.
+        let now = Utc::now();
+        let stale = record
+            .last_used_at
+            .is_none_or(|t| now - t > chrono::Duration::seconds(60));
+        if stale {
+            record.last_used_at = Some(now);
+            if let Ok(bytes) = serde_json::to_vec(&record) {
+                let _ = self.db.put(format!("key:{key_id}").as_bytes(), bytes);
+            }
+        }
+
+        Ok(Some(record))
+    }
+
+    /// Revoke a key. Revoked keys stay listed for audit purposes.
+    pub fn revoke(&self, key_id: &str) -> Result<Option<ApiKeyRecord>> {
+        let Some(mut record) = self.get(key_id)? else {
+            return Ok(None);
+        };
+        if record.revoked_at.is_none() {
+            record.revoked_at = Some(Utc::now());
+        }
+
+        let mut batch = WriteBatch::default();
+        batch.delete(format!("hash:{}", record.key_hash).as_bytes());
+        batch.put(
+            format!("key:{}", record.id).as_bytes(),
+            serde_json::to_vec(&record)?,
+        );
+        self.db
+            .write(batch)
+            .context("Failed to persist API key revocation")?;
+
+        tracing::info!(key_id = %key_id, "Revoked API key");
+        Ok(Some(record.redacted()))
+    }
+
+    /// Replace a key's secret, keeping its ID, role and scope.
+    ///
+    /// The previous secret stops working immediately.
+    pub fn rotate(&self, key_id: &str) -> Result<Option<IssuedApiKey>> {
+        let Some(mut record) = self.get(key_id)? else {
+            return Ok(None);
+        };
+        if record.is_revoked() {
+            anyhow::bail!("API key {key_id} is revoked and cannot be rotated");
+        }
+
+        let secret = generate_secret();
+        let old_hash = std::mem::replace(&mut record.key_hash, hash_api_key(&secret));
+        record.key_prefix = display_prefix(&secret);
+        record.rotated_at = Some(Utc::now());
+
+        let mut batch = WriteBatch::default();
+        batch.delete(format!("hash:{old_hash}").as_bytes());
+        batch.put(
+            format!("hash:{}", record.key_hash).as_bytes(),
+            record.id.as_bytes(),
+        );
+        batch.put(
+            format!("key:{}", record.id).as_bytes(),
+            serde_json::to_vec(&record)?,
+        );
+        self.db
+            .write(batch)
+            .context("Failed to persist API key rotation")?;
+
+        tracing::info!(key_id = %key_id, "Rotated API key");
+        Ok(Some(IssuedApiKey {
+            key: secret,
+            record: record.redacted(),
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn store() -> (ApiKeyStore, TempDir) {
+        let dir = TempDir::new().unwrap();
+        (ApiKeyStore::new(dir.path()).unwrap(), dir)
+    }
+
+    #[test]
+    fn role_ordering() {
+        assert!(ApiKeyRole::Admin.allows(ApiKeyRole::Writer));
+        assert!(ApiKeyRole::Writer.allows(ApiKeyRole::ReadOnly));
+        assert!(!ApiKeyRole::ReadOnly.allows(ApiKeyRole::Writer));
+        assert!(!ApiKeyRole::Writer.allows(ApiKeyRole::Admin));
+    }
+
+    #[test]
+    fn create_and_authenticate() {
+        let (store, _dir) = store();
+        let issued = store
+            .create("agent", ApiKeyRole::Writer, vec!["alice".to_string()])
+            .unwrap();
+
+        assert!(issued.key.starts_with(API_KEY_SECRET_PREFIX));
+        assert!(issued.record.key_hash.is_empty(), "hash must not leak");
+
+        let record = store.authenticate(&issued.key).unwrap().unwrap();
+        assert_eq!(record.id, issued.record.id);
+        assert_eq!(record.role, ApiKeyRole::Writer);
+        assert_eq!(record.allowed_user_ids, vec!["alice".to_string()]);
+        assert!(store.authenticate("sk-shodh-wrong").unwrap().is_none());
+    }
+
+    #[test]
+    fn revoke_disables_key() {
+        let (store, _dir) = store();
+        let issued = store.create("tmp", ApiKeyRole::ReadOnly, vec![]).unwrap();
+        assert!(store.has_active_keys());
+
+        let revoked = store.revoke(&issued.record.id).unwrap().unwrap();
+        assert!(revoked.is_revoked());
+        assert!(store.authenticate(&issued.key).unwrap().is_none());
+        assert!(!store.has_active_keys());
+        assert_eq!(store.list().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn rotate_replaces_secret() {
+        let (store, _dir) = store();
+        let issued = store
+            .create("ci", ApiKeyRole::Admin, vec!["*".to_string()])
+            .unwrap();
+        let rotated = store.rotate(&issued.record.id).unwrap().unwrap();
+
+        assert_ne!(issued.key, rotated.key);
+        assert_eq!(rotated.record.id, issued.record.id);
+        assert!(store.authenticate(&issued.key).unwrap().is_none());
+        assert!(store.authenticate(&rotated.key).unwrap().is_some());
+    }
+
+    #[test]
+    fn keys_survive_reopen() {
+        let dir = TempDir::new().unwrap();
+        let secret = {
+            let store = ApiKeyStore::new(dir.path()).unwrap();
+            store
+                .create("persisted", ApiKeyRole::Writer, vec!["bob".to_string()])
+                .unwrap()
+                .key
+        };
+        let store = ApiKeyStore::new(dir.path()).unwrap();
+        let record = store.authenticate(&secret).unwrap().unwrap();
+        assert_eq!(record.name, "persisted");
+    }
+}
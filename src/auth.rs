@@ -1,14 +1,23 @@
 use axum::{
-    extract::Request,
-    http::StatusCode,
+    body::Body,
+    extract::{FromRequestParts, Query, Request, State},
+    http::{request::Parts, Method, StatusCode},
     middleware::Next,
     response::{IntoResponse, Response},
     Json,
 };
+use std::collections::HashSet;
 use std::env;
+use std::sync::Arc;
 
+use crate::api_keys::{ApiKeyRecord, ApiKeyRole, ApiKeyStore};
 use crate::errors::ErrorResponse;
 
+/// Maximum request body size buffered by the auth middleware to find the
+/// target `user_id` of a tenant-scoped key. Matches axum's default `Json`
+/// extractor limit, so nothing the handlers accept is rejected here.
+const MAX_SCOPED_BODY_BYTES: usize = 2 * 1024 * 1024;
+
 /// Default API key for development when no key env vars are configured.
 /// Visibility is crate-only — not exposed in the public API surface.
 pub(crate) const DEFAULT_DEV_API_KEY: &str = "sk-shodh-dev-default";
@@ -63,6 +72,17 @@ pub enum AuthError {
     MissingApiKey,
     InvalidApiKey,
     NotConfigured,
+    /// Key is valid but its role is below what the route requires
+    InsufficientRole {
+        required: ApiKeyRole,
+        actual: ApiKeyRole,
+    },
+    /// Key is valid but not scoped to the requested user_id
+    UserNotPermitted(String),
+    /// Key is scoped to some users but the route spans all of them (key management)
+    ScopedKeyNotPermitted,
+    /// Request body could not be read to determine the target user
+    UnreadableBody,
 }
 
 impl AuthError {
@@ -71,6 +91,10 @@ impl AuthError {
             Self::MissingApiKey => "MISSING_API_KEY",
             Self::InvalidApiKey => "INVALID_API_KEY",
             Self::NotConfigured => "AUTH_NOT_CONFIGURED",
+            Self::InsufficientRole { .. } => "INSUFFICIENT_ROLE",
+            Self::UserNotPermitted(_) => "USER_NOT_PERMITTED",
+            Self::ScopedKeyNotPermitted => "SCOPED_KEY_NOT_PERMITTED",
+            Self::UnreadableBody => "UNREADABLE_BODY",
         }
     }
 
@@ -78,6 +102,10 @@ impl AuthError {
         match self {
             Self::MissingApiKey | Self::InvalidApiKey => StatusCode::UNAUTHORIZED,
             Self::NotConfigured => StatusCode::SERVICE_UNAVAILABLE,
+            Self::InsufficientRole { .. }
+            | Self::UserNotPermitted(_)
+            | Self::ScopedKeyNotPermitted => StatusCode::FORBIDDEN,
+            Self::UnreadableBody => StatusCode::PAYLOAD_TOO_LARGE,
         }
     }
 }
@@ -114,6 +142,20 @@ impl IntoResponse for AuthError {
             AuthError::NotConfigured => {
                 "API keys not configured. Set SHODH_API_KEYS environment variable.".to_string()
             }
+            AuthError::InsufficientRole { required, actual } => {
+                format!(
+                    "API key role '{actual}' cannot perform this operation (requires '{required}')"
+                )
+            }
+            AuthError::UserNotPermitted(user_id) => {
+                format!("API key is not permitted to access user '{user_id}'")
+            }
+            AuthError::ScopedKeyNotPermitted => {
+                "Only API keys scoped to all users (\"*\") can manage API keys".to_string()
+            }
+            AuthError::UnreadableBody => {
+                "Request body too large or unreadable for tenant authorization".to_string()
+            }
         };
 
         let body = ErrorResponse {
@@ -205,9 +247,330 @@ pub fn validate_api_key(provided_key: &str) -> Result<(), AuthError> {
     }
 }
 
-/// Authentication middleware
-pub async fn auth_middleware(request: Request, next: Next) -> Response {
-    let path = request.uri().path();
+/// Authenticated caller, inserted into request extensions by [`auth_middleware`].
+///
+/// Handlers take it as an extractor to filter multi-user listings or to
+/// check the target user of requests the middleware cannot see into
+/// (WebSocket handshakes, for example).
+#[derive(Debug, Clone)]
+pub struct AuthContext {
+    /// Key ID from the key store, or `"bootstrap"` for env-configured keys
+    pub key_id: String,
+    pub role: ApiKeyRole,
+    /// `None` means the key may act on every user
+    pub allowed_user_ids: Option<HashSet<String>>,
+}
+
+impl AuthContext {
+    /// Context for keys from `SHODH_API_KEYS` / `SHODH_DEV_API_KEY`:
+    /// admin role, unscoped. Used to bootstrap the key store.
+    pub fn bootstrap() -> Self {
+        Self {
+            key_id: "bootstrap".to_string(),
+            role: ApiKeyRole::Admin,
+            allowed_user_ids: None,
+        }
+    }
+
+    pub fn from_record(record: &ApiKeyRecord) -> Self {
+        let allowed_user_ids = if record.is_unscoped() {
+            None
+        } else {
+            Some(record.allowed_user_ids.iter().cloned().collect())
+        };
+        Self {
+            key_id: record.id.clone(),
+            role: record.role,
+            allowed_user_ids,
+        }
+    }
+
+    /// Whether this key may act on `user_id`
+    pub fn can_access_user(&self, user_id: &str) -> bool {
+        match &self.allowed_user_ids {
+            None => true,
+            Some(allowed) => allowed.contains(user_id),
+        }
+    }
+
+    pub fn authorize_user(&self, user_id: &str) -> Result<(), AuthError> {
+        if self.can_access_user(user_id) {
+            Ok(())
+        } else {
+            Err(AuthError::UserNotPermitted(user_id.to_string()))
+        }
+    }
+
+    /// Require a key spanning all users
+    ///
+    /// Key management can mint, rotate and revoke keys for any tenant, so an
+    /// admin key scoped to some users must not reach it.
+    pub fn require_unscoped(&self) -> Result<(), AuthError> {
+        if self.allowed_user_ids.is_none() {
+            Ok(())
+        } else {
+            Err(AuthError::ScopedKeyNotPermitted)
+        }
+    }
+
+    pub fn require_role(&self, required: ApiKeyRole) -> Result<(), AuthError> {
+        if self.role.allows(required) {
+            Ok(())
+        } else {
+            Err(AuthError::InsufficientRole {
+                required,
+                actual: self.role,
+            })
+        }
+    }
+}
+
+impl<S: Send + Sync> FromRequestParts<S> for AuthContext {
+    type Rejection = AuthError;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        parts
+            .extensions
+            .get::<AuthContext>()
+            .cloned()
+            .ok_or(AuthError::MissingApiKey)
+    }
+}
+
+/// Resolve a presented key to its [`AuthContext`].
+///
+/// Scoped keys from the key store are checked first; env-configured keys
+/// fall back to [`validate_api_key`] and act as unscoped admins. When the
+/// store holds active keys, a missing `SHODH_API_KEYS` in production is no
+/// longer a configuration error.
+pub fn resolve_api_key(store: &ApiKeyStore, provided_key: &str) -> Result<AuthContext, AuthError> {
+    match store.authenticate(provided_key) {
+        Ok(Some(record)) => return Ok(AuthContext::from_record(&record)),
+        Ok(None) => {}
+        Err(e) => tracing::error!("API key store lookup failed: {}", e),
+    }
+
+    match validate_api_key(provided_key) {
+        Ok(()) => Ok(AuthContext::bootstrap()),
+        Err(AuthError::NotConfigured) if store.has_active_keys() => Err(AuthError::InvalidApiKey),
+        Err(e) => Err(e),
+    }
+}
+
+/// Match a route pattern like `/api/users/{}/stats` against a concrete path.
+/// `{}` matches exactly one segment.
+fn route_matches(pattern: &str, path: &str) -> bool {
+    let mut pattern_segments = pattern.trim_end_matches('/').split('/');
+    let mut path_segments = path.trim_end_matches('/').split('/');
+    loop {
+        match (pattern_segments.next(), path_segments.next()) {
+            (None, None) => return true,
+            (Some("{}"), Some(seg)) if !seg.is_empty() => continue,
+            (Some(p), Some(seg)) if p == seg => continue,
+            _ => return false,
+        }
+    }
+}
+
+/// API key management routes: admin keys spanning all users only
+const KEY_MANAGEMENT_ROUTES: &[&str] = &[
+    "/api/keys",
+    "/api/keys/{}",
+    "/api/keys/{}/revoke",
+    "/api/keys/{}/rotate",
+];
+
+/// Routes restricted to admin keys regardless of method
+const ADMIN_ROUTES: &[&str] = &[
+    "/api/memories/clear",
+    "/api/graph/{}/clear",
+    "/api/backup/purge",
+    "/api/backups/purge",
+    "/api/backup/restore",
+    "/api/index/rebuild",
+    "/api/index/reembed",
+    "/api/storage/cleanup",
+    "/api/storage/migrate",
+];
+
+/// Whether `path` is one of the `/api/keys` management routes
+fn is_key_management_route(path: &str) -> bool {
+    KEY_MANAGEMENT_ROUTES.iter().any(|r| route_matches(r, path))
+}
+
+/// POST routes that only read state (GET routes are read-only by default)
+const READ_ONLY_POST_ROUTES: &[&str] = &[
+    "/api/recall",
+    "/api/recall/tracked",
+    "/api/recall/tags",
+    "/api/recall/by-tags",
+    "/api/recall/date",
+    "/api/context_summary",
+    "/api/relevant",
+    "/api/memories",
+    "/api/search/advanced",
+    "/api/search/multimodal",
+    "/api/search/robotics",
+    "/api/export/mif",
+    "/api/export/stream",
+    "/api/export/ics",
+    "/api/facts/list",
+    "/api/facts/search",
+    "/api/facts/by-entity",
+    "/api/facts/stats",
+    "/api/lineage/trace",
+    "/api/lineage/edges",
+    "/api/lineage/stats",
+    "/api/lineage/branches",
+    "/api/graph/entity/find",
+    "/api/graph/entities/all",
+    "/api/graph/traverse",
+    "/api/graph/episode/get",
+    "/api/graph/entity/merges",
+    "/api/graph/query",
+    "/api/graph/analytics",
+    "/api/visualization/build",
+    "/api/todos",
+    "/api/todos/list",
+    "/api/todos/due",
+    "/api/todos/next",
+    "/api/todos/stats",
+    "/api/projects/list",
+    "/api/projects/{}/files",
+    "/api/projects/{}/files/search",
+    "/api/reminders",
+    "/api/reminders/due",
+    "/api/reminders/check",
+    "/api/reminders/context",
+    "/api/sessions",
+    "/api/consolidation/report",
+    "/api/backup/list",
+    "/api/backups",
+    "/api/backup/verify",
+];
+
+/// Routes that carry the target user in a path segment (index = segment position)
+const USER_PATH_ROUTES: &[(&str, usize)] = &[
+    ("/api/list/{}", 3),
+    ("/api/users/{}", 3),
+    ("/api/users/{}/stats", 3),
+    ("/api/graph/{}/stats", 3),
+    ("/api/graph/{}/universe", 3),
+    ("/api/graph/{}/clear", 3),
+    ("/api/graph/{}/rebuild", 3),
+    ("/api/graph/data/{}", 4),
+    ("/api/brain/{}", 3),
+    ("/api/visualization/{}/stats", 3),
+    ("/api/visualization/{}/dot", 3),
+];
+
+/// Minimum role needed to call `method path`
+pub fn required_role(method: &Method, path: &str) -> ApiKeyRole {
+    if is_key_management_route(path)
+        || ADMIN_ROUTES.iter().any(|r| route_matches(r, path))
+        || (method == Method::DELETE && route_matches("/api/users/{}", path))
+    {
+        return ApiKeyRole::Admin;
+    }
+
+    // The streaming WebSocket is a GET upgrade but ingests memories
+    if path == "/api/stream" {
+        return ApiKeyRole::Writer;
+    }
+
+    // MCP transports check each tool call against the REST route it mirrors
+    if path == "/mcp" || path.starts_with("/mcp/") {
+        return ApiKeyRole::ReadOnly;
+    }
+
+    if method == Method::GET
+        || method == Method::HEAD
+        || (method == Method::POST && READ_ONLY_POST_ROUTES.iter().any(|r| route_matches(r, path)))
+    {
+        ApiKeyRole::ReadOnly
+    } else {
+        ApiKeyRole::Writer
+    }
+}
+
+/// Extract the target user from the path (e.g. `/api/users/{user_id}/stats`)
+fn user_id_from_path(path: &str) -> Option<String> {
+    // `/api/graph/data/{user_id}` must win over `/api/graph/{user_id}/...`
+    let mut matches: Vec<&(&str, usize)> = USER_PATH_ROUTES
+        .iter()
+        .filter(|(pattern, _)| route_matches(pattern, path))
+        .collect();
+    matches.sort_by_key(|(_, idx)| std::cmp::Reverse(*idx));
+    let (_, idx) = matches.first()?;
+    path.split('/').nth(*idx).map(percent_decode)
+}
+
+/// Decode `%XX` escapes in a path segment (user IDs may contain `@`)
+fn percent_decode(segment: &str) -> String {
+    let bytes = segment.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%' && i + 2 < bytes.len() {
+            let hex = std::str::from_utf8(&bytes[i + 1..i + 3]).ok();
+            if let Some(b) = hex.and_then(|h| u8::from_str_radix(h, 16).ok()) {
+                out.push(b);
+                i += 3;
+                continue;
+            }
+        }
+        out.push(bytes[i]);
+        i += 1;
+    }
+    String::from_utf8_lossy(&out).into_owned()
+}
+
+/// Extract `user_id` from the query string
+pub(crate) fn user_id_from_query(uri: &axum::http::Uri) -> Option<String> {
+    let Query(mut params) =
+        Query::<std::collections::HashMap<String, String>>::try_from_uri(uri).ok()?;
+    params.remove("user_id")
+}
+
+/// Extract a top-level `user_id` from a JSON body
+fn user_id_from_body(bytes: &[u8]) -> Option<String> {
+    if bytes.is_empty() {
+        return None;
+    }
+    let value: serde_json::Value = serde_json::from_slice(bytes).ok()?;
+    value.get("user_id")?.as_str().map(|s| s.to_string())
+}
+
+/// Whether axum's `Json` extractor would accept a body with this content type
+///
+/// Matches `application/json` and any `application/*+json` subtype, ignoring
+/// case and parameters, so scoped keys cannot dodge the body check with
+/// types like `application/vnd.api+json`.
+fn is_json_content_type(content_type: &str) -> bool {
+    let essence = content_type
+        .split(';')
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_ascii_lowercase();
+    match essence.split_once('/') {
+        Some(("application", subtype)) => subtype == "json" || subtype.ends_with("+json"),
+        _ => false,
+    }
+}
+
+/// Authentication and authorization middleware
+///
+/// 1. Resolves `X-API-Key` to an [`AuthContext`] (key store, then env keys)
+/// 2. Enforces the route's minimum role (see [`required_role`])
+/// 3. For tenant-scoped keys, rejects requests whose `user_id` (path, query
+///    or JSON body) is outside the key's allowed users
+pub async fn auth_middleware(
+    State(key_store): State<Arc<ApiKeyStore>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let path = request.uri().path().to_string();
 
     // Skip auth for health endpoint
     if path == "/health" {
@@ -230,12 +593,63 @@ pub async fn auth_middleware(request: Request, next: Next) -> Response {
         None => return AuthError::MissingApiKey.into_response(),
     };
 
-    // Validate the cloned key
-    if let Err(e) = validate_api_key(&api_key_value) {
+    let auth = match resolve_api_key(&key_store, &api_key_value) {
+        Ok(auth) => auth,
+        Err(e) => return e.into_response(),
+    };
+
+    if let Err(e) = auth.require_role(required_role(request.method(), &path)) {
         return e.into_response();
     }
 
-    // Now we can move request to next layer
+    if is_key_management_route(&path) {
+        if let Err(e) = auth.require_unscoped() {
+            tracing::warn!(
+                key_id = %auth.key_id,
+                path = %path,
+                "Rejected key management by a scoped key"
+            );
+            return e.into_response();
+        }
+    }
+
+    let mut request = request;
+
+    // Tenant scoping: only scoped keys pay for body inspection
+    if auth.allowed_user_ids.is_some() {
+        let mut targets = Vec::new();
+        targets.extend(user_id_from_path(&path));
+        targets.extend(user_id_from_query(request.uri()));
+
+        let is_json = request
+            .headers()
+            .get(axum::http::header::CONTENT_TYPE)
+            .and_then(|v| v.to_str().ok())
+            .is_some_and(is_json_content_type);
+        if is_json {
+            let (parts, body) = request.into_parts();
+            let bytes = match axum::body::to_bytes(body, MAX_SCOPED_BODY_BYTES).await {
+                Ok(bytes) => bytes,
+                Err(_) => return AuthError::UnreadableBody.into_response(),
+            };
+            targets.extend(user_id_from_body(&bytes));
+            request = Request::from_parts(parts, Body::from(bytes));
+        }
+
+        for user_id in &targets {
+            if let Err(e) = auth.authorize_user(user_id) {
+                tracing::warn!(
+                    key_id = %auth.key_id,
+                    user_id = %user_id,
+                    path = %path,
+                    "Rejected cross-tenant request"
+                );
+                return e.into_response();
+            }
+        }
+    }
+
+    request.extensions_mut().insert(auth);
     next.run(request).await
 }
 
@@ -567,4 +981,162 @@ mod tests {
         assert_eq!(parsed.code, "AUTH_NOT_CONFIGURED");
         assert!(parsed.message.contains("SHODH_API_KEYS"));
     }
+
+    // ── route classification & tenant extraction ──
+
+    #[test]
+    fn route_matching_wildcards() {
+        assert!(route_matches(
+            "/api/users/{}/stats",
+            "/api/users/alice/stats"
+        ));
+        assert!(route_matches(
+            "/api/keys/{}/revoke",
+            "/api/keys/key_1/revoke/"
+        ));
+        assert!(!route_matches("/api/users/{}/stats", "/api/users/alice"));
+        assert!(!route_matches("/api/users/{}", "/api/users//"));
+    }
+
+    #[test]
+    fn required_roles_by_route() {
+        assert_eq!(
+            required_role(&Method::POST, "/api/recall"),
+            ApiKeyRole::ReadOnly
+        );
+        assert_eq!(
+            required_role(&Method::GET, "/api/users/alice/stats"),
+            ApiKeyRole::ReadOnly
+        );
+        assert_eq!(
+            required_role(&Method::POST, "/api/remember"),
+            ApiKeyRole::Writer
+        );
+        assert_eq!(
+            required_role(&Method::DELETE, "/api/memory/abc"),
+            ApiKeyRole::Writer
+        );
+        assert_eq!(
+            required_role(&Method::GET, "/api/stream"),
+            ApiKeyRole::Writer
+        );
+        assert_eq!(
+            required_role(&Method::POST, "/api/memories/clear"),
+            ApiKeyRole::Admin
+        );
+        assert_eq!(
+            required_role(&Method::DELETE, "/api/users/alice"),
+            ApiKeyRole::Admin
+        );
+        assert_eq!(required_role(&Method::GET, "/api/keys"), ApiKeyRole::Admin);
+        assert_eq!(
+            required_role(&Method::POST, "/api/backup/restore"),
+            ApiKeyRole::Admin
+        );
+    }
+
+    #[test]
+    fn user_id_extraction() {
+        assert_eq!(
+            user_id_from_path("/api/users/alice/stats").as_deref(),
+            Some("alice")
+        );
+        assert_eq!(
+            user_id_from_path("/api/graph/data/bob").as_deref(),
+            Some("bob")
+        );
+        assert_eq!(
+            user_id_from_path("/api/list/a%40b.com").as_deref(),
+            Some("a@b.com")
+        );
+        assert_eq!(user_id_from_path("/api/recall"), None);
+
+        let uri: axum::http::Uri = "/api/memory/x?user_id=carol&limit=5".parse().unwrap();
+        assert_eq!(user_id_from_query(&uri).as_deref(), Some("carol"));
+
+        assert_eq!(
+            user_id_from_body(br#"{"user_id":"dave","query":"q"}"#).as_deref(),
+            Some("dave")
+        );
+        assert_eq!(user_id_from_body(b"not json"), None);
+    }
+
+    #[test]
+    fn json_content_types_match_axum() {
+        assert!(is_json_content_type("application/json"));
+        assert!(is_json_content_type("application/json; charset=utf-8"));
+        assert!(is_json_content_type("Application/JSON"));
+        assert!(is_json_content_type("application/vnd.x+json"));
+        assert!(is_json_content_type(
+            "application/problem+json;charset=utf-8"
+        ));
+        assert!(!is_json_content_type("text/json"));
+        assert!(!is_json_content_type("application/jsonl"));
+        assert!(!is_json_content_type("multipart/form-data; boundary=x"));
+    }
+
+    #[test]
+    fn scoped_context_checks() {
+        let record = ApiKeyRecord {
+            id: "key_1".to_string(),
+            name: "agent".to_string(),
+            role: ApiKeyRole::ReadOnly,
+            allowed_user_ids: vec!["alice".to_string()],
+            key_prefix: String::new(),
+            key_hash: String::new(),
+            created_at: chrono::Utc::now(),
+            rotated_at: None,
+            revoked_at: None,
+            last_used_at: None,
+        };
+        let ctx = AuthContext::from_record(&record);
+        assert!(ctx.authorize_user("alice").is_ok());
+        assert!(matches!(
+            ctx.authorize_user("bob"),
+            Err(AuthError::UserNotPermitted(_))
+        ));
+        assert!(ctx.require_role(ApiKeyRole::ReadOnly).is_ok());
+        assert!(matches!(
+            ctx.require_role(ApiKeyRole::Writer),
+            Err(AuthError::InsufficientRole { .. })
+        ));
+
+        let bootstrap = AuthContext::bootstrap();
+        assert!(bootstrap.can_access_user("anyone"));
+        assert!(bootstrap.require_role(ApiKeyRole::Admin).is_ok());
+        assert!(bootstrap.require_unscoped().is_ok());
+    }
+
+    #[test]
+    fn key_management_requires_unscoped_key() {
+        assert!(is_key_management_route("/api/keys"));
+        assert!(is_key_management_route("/api/keys/key_1/rotate"));
+        assert!(!is_key_management_route("/api/memories/clear"));
+
+        let scoped_admin = AuthContext {
+            key_id: "key_1".to_string(),
+            role: ApiKeyRole::Admin,
+            allowed_user_ids: Some(HashSet::from(["alice".to_string()])),
+        };
+        assert!(matches!(
+            scoped_admin.require_unscoped(),
+            Err(AuthError::ScopedKeyNotPermitted)
+        ));
+    }
+
+    #[test]
+    fn forbidden_error_status_codes() {
+        assert_eq!(
+            AuthError::UserNotPermitted("bob".to_string()).status_code(),
+            StatusCode::FORBIDDEN
+        );
+        assert_eq!(
+            AuthError::InsufficientRole {
+                required: ApiKeyRole::Admin,
+                actual: ApiKeyRole::Writer,
+            }
+            .status_code(),
+            StatusCode::FORBIDDEN
+        );
+    }
 }
@@ -2,7 +2,7 @@
 //!
 //! Provides production-grade backup and restore capabilities:
 //! - Incremental backups using RocksDB checkpoints
-//! - Point-in-time recovery (PITR)
+//! - Point-in-time recovery (PITR) by replaying archived WAL batches
 //! - Export to JSON/Parquet formats
 //! - Backup verification and integrity checks
 //! - Automated scheduling support
@@ -12,14 +12,44 @@ use chrono::{DateTime, Utc};
 use rocksdb::{
     backup::{BackupEngine, BackupEngineOptions},
     checkpoint::Checkpoint,
-    Env, DB,
+    Env, IteratorMode, Options, WriteBatch, DB,
 };
 use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
+use std::collections::{HashMap, VecDeque};
 use std::fs;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
+/// Store name of the main memories database in WAL archives and restore reports
+pub const MAIN_STORE: &str = "memories";
+
+/// How long RocksDB keeps obsolete WAL files around for the archiver
+///
+/// Must comfortably exceed the archive interval; batches that age out before
+/// being archived leave a gap that blocks PITR until the next backup.
+pub const WAL_RETENTION_SECS: u64 = 6 * 60 * 60;
+
+/// How often each open store's latest sequence number is sampled
+///
+/// The WAL holds no timestamps, so a batch's commit time is only known to be
+/// before the first sample that covers it. This is the granularity of PITR.
+pub const WAL_CLOCK_INTERVAL_SECS: u64 = 1;
+
+/// Sequence clock samples kept per store between archiver runs
+///
+/// Dropping the oldest sample only loosens the commit time of the batches
+/// it covered, so this bounds memory without making restores wrong.
+const WAL_CLOCK_MAX_SAMPLES: usize = 512;
+
+/// Directory under `{backup}/{user}` holding archived WAL segments
+const WAL_DIR: &str = "wal";
+
+/// Per-store file recording the next sequence number to archive
+const WAL_CURSOR_FILE: &str = "cursor";
+
+const WAL_SEGMENT_EXT: &str = "wal";
+
 /// Backup metadata for tracking and verification
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BackupMetadata {
@@ -37,7 +67,7 @@ pub struct BackupMetadata {
     pub checksum: String,
     /// Number of memories included in backup
     pub memory_count: usize,
-    /// RocksDB sequence number (for PITR)
+    /// RocksDB sequence number taken just before the backup (for PITR)
     pub sequence_number: u64,
     /// Secondary stores included in this backup
     #[serde(default)]
@@ -45,6 +75,49 @@ pub struct BackupMetadata {
     /// Total size of secondary store backups in bytes
     #[serde(default)]
     pub secondary_size_bytes: u64,
+    /// Sequence number of each checkpointed store, taken just before its checkpoint
+    #[serde(default)]
+    pub store_sequence_numbers: HashMap<String, u64>,
+}
+
+/// Outcome of restoring a user from a backup
+#[derive(Debug, Clone, Serialize)]
+pub struct RestoreReport {
+    pub user_id: String,
+    /// Backup used as the base of the restore
+    pub backup_id: u32,
+    pub backup_created_at: DateTime<Utc>,
+    /// Point in time the user was rolled forward to (None = backup point)
+    pub target_time: Option<DateTime<Utc>>,
+    /// Per-user stores replaced (memories, memory_index, graph_*)
+    pub restored_stores: Vec<String>,
+    /// Shared stores rolled back in place
+    pub shared_stores: Vec<String>,
+    /// Archived write batches replayed on top of the backup
+    pub replayed_batches: usize,
+    /// Latest commit time bound among the replayed batches
+    pub replayed_through: Option<DateTime<Utc>>,
+    /// Precision of `target_time` in seconds: a batch committed up to this
+    /// long before it may be left out (None = restored to the backup point)
+    pub granularity_secs: Option<u64>,
+}
+
+/// Outcome of replaying one store's WAL archive
+#[derive(Debug, Clone, Copy, Default)]
+pub struct WalReplay {
+    /// Write batches applied
+    pub batches: usize,
+    /// Commit time bound of the last batch applied
+    pub replayed_through: Option<DateTime<Utc>>,
+}
+
+/// One archived write batch
+struct WalRecord {
+    seq: u64,
+    /// Time by which the batch was committed: the first sequence clock sample
+    /// covering it, or the end of the archiver run when none did
+    committed_by_ms: i64,
+    batch: WriteBatch,
 }
 
 /// Named reference to a RocksDB database for backup
@@ -62,6 +135,11 @@ pub enum BackupType {
 /// Backup engine for creating and managing backups
 pub struct ShodhBackupEngine {
     backup_path: PathBuf,
+    /// Serializes WAL archiving: the periodic archiver and cache evictions
+    /// can reach the same store at once
+    wal_archive_lock: parking_lot::Mutex<()>,
+    /// (millis, latest sequence number) samples per (user, store), oldest first
+    sequence_clock: parking_lot::Mutex<HashMap<(String, String), VecDeque<(i64, u64)>>>,
 }
 
 impl ShodhBackupEngine {
@@ -71,7 +149,11 @@ impl ShodhBackupEngine {
     /// * `backup_path` - Directory to store backups
     pub fn new(backup_path: PathBuf) -> Result<Self> {
         fs::create_dir_all(&backup_path)?;
-        Ok(Self { backup_path })
+        Ok(Self {
+            backup_path,
+            wal_archive_lock: parking_lot::Mutex::new(()),
+            sequence_clock: parking_lot::Mutex::new(HashMap::new()),
+        })
     }
 
     /// Create a full backup of a RocksDB database
@@ -91,6 +173,10 @@ impl ShodhBackupEngine {
         let env = Env::new()?;
         let mut backup_engine = BackupEngine::open(&backup_opts, &env)?;
 
+        // Taken before the backup so WAL replay starts at or before its contents;
+        // re-applying batches the backup already holds is idempotent
+        let sequence_number = db.latest_sequence_number();
+
         // Create backup
         let before_count = backup_engine.get_backup_info().len();
         backup_engine.create_new_backup(db)?;
@@ -103,9 +189,6 @@ impl ShodhBackupEngine {
         let backup_id = latest_backup.backup_id;
         let size_bytes = latest_backup.size;
 
-        // Get latest sequence number from DB
-        let sequence_number = db.latest_sequence_number();
-
         // Count memories (estimate from DB size)
         let memory_count = self.estimate_memory_count(db)?;
 
@@ -130,6 +213,7 @@ impl ShodhBackupEngine {
             sequence_number,
             secondary_stores: Vec::new(),
             secondary_size_bytes: 0,
+            store_sequence_numbers: HashMap::new(),
         };
 
         // Save metadata
@@ -148,7 +232,7 @@ impl ShodhBackupEngine {
     /// Create a comprehensive backup of the main database and all secondary stores.
     ///
     /// Uses RocksDB BackupEngine for the main memories DB and Checkpoint API
-    /// for secondary stores (todos, reminders, facts, files, feedback, audit).
+    /// for secondary stores (todos, reminders, facts, files, feedback, A/B tests, sessions, audit).
     pub fn create_comprehensive_backup(
         &self,
         db: &DB,
@@ -182,6 +266,7 @@ impl ShodhBackupEngine {
 
             match Checkpoint::new(store_ref.db) {
                 Ok(checkpoint) => {
+                    let sequence_number = store_ref.db.latest_sequence_number();
                     if let Err(e) = checkpoint.create_checkpoint(&store_checkpoint_dir) {
                         tracing::warn!(
                             store = store_ref.name,
@@ -202,6 +287,9 @@ impl ShodhBackupEngine {
                     let store_size = dir_size(&store_checkpoint_dir).unwrap_or(0);
                     total_secondary_bytes += store_size;
                     backed_up_stores.push(store_ref.name.to_string());
+                    metadata
+                        .store_sequence_numbers
+                        .insert(store_ref.name.to_string(), sequence_number);
 
                     tracing::debug!(
                         store = store_ref.name,
@@ -430,6 +518,323 @@ impl ShodhBackupEngine {
         Ok(restored_stores)
     }
 
+    /// Pick the backup a restore starts from
+    ///
+    /// `backup_id` selects a specific backup; otherwise the newest backup taken
+    /// at or before `target_time` (or the newest overall) is used.
+    pub fn resolve_restore_point(
+        &self,
+        user_id: &str,
+        backup_id: Option<u32>,
+        target_time: Option<DateTime<Utc>>,
+    ) -> Result<Option<BackupMetadata>> {
+        Ok(self
+            .list_backups(user_id)?
+            .into_iter()
+            .filter(|b| backup_id.is_none_or(|id| b.backup_id == id))
+            .filter(|b| target_time.is_none_or(|t| b.created_at <= t))
+            .max_by_key(|b| b.backup_id))
+    }
+
+    /// Roll a live database back to its checkpoint in a backup
+    ///
+    /// Used for shared stores that cannot be closed while the server runs.
+    /// All live keys are deleted and the checkpoint's keys written in a single
+    /// atomic `WriteBatch`, so readers see either the old or the restored
+    /// contents. Returns false if the backup has no checkpoint for `store`.
+    pub fn restore_store_in_place(
+        &self,
+        user_id: &str,
+        backup_id: u32,
+        store: &str,
+        db: &DB,
+    ) -> Result<bool> {
+        let checkpoint_dir = self
+            .backup_path
+            .join(user_id)
+            .join(format!("secondary_{backup_id}"))
+            .join(store);
+        if !checkpoint_dir.exists() {
+            return Ok(false);
+        }
+
+        let snapshot = DB::open_for_read_only(&Options::default(), &checkpoint_dir, false)?;
+        let mut batch = WriteBatch::default();
+        for item in db.iterator(IteratorMode::Start) {
+            let (key, _) = item?;
+            batch.delete(key);
+        }
+        for item in snapshot.iterator(IteratorMode::Start) {
+            let (key, value) = item?;
+            batch.put(key, value);
+        }
+        db.write(batch)?;
+
+        tracing::info!(
+            store = store,
+            backup_id = backup_id,
+            "Store restored in place from checkpoint"
+        );
+        Ok(true)
+    }
+
+    // ========================================================================
+    // Point-in-time recovery (WAL archive)
+    // ========================================================================
+
+    /// Record the time at which a store reached its latest sequence number
+    ///
+    /// Called every `WAL_CLOCK_INTERVAL_SECS` for open stores; `archive_wal`
+    /// uses the samples to date each batch it archives.
+    pub fn sample_sequence(&self, user_id: &str, store: &str, db: &DB) {
+        // Sequence first: every batch up to it is committed by the timestamp
+        let seq = db.latest_sequence_number();
+        let now_ms = Utc::now().timestamp_millis();
+        let mut clock = self.sequence_clock.lock();
+        let samples = clock
+            .entry((user_id.to_string(), store.to_string()))
+            .or_default();
+        if samples.back().is_some_and(|&(_, last)| last == seq) {
+            return;
+        }
+        if samples.len() == WAL_CLOCK_MAX_SAMPLES {
+            samples.pop_front();
+        }
+        samples.push_back((now_ms, seq));
+    }
+
+    /// Copy write batches committed since the last run into the WAL archive
+    ///
+    /// Segments live at `{backup}/{user}/wal/{store}/{first_seq}.wal`; each
+    /// record is `seq (u64) | committed_by millis (i64) | len (u32) | batch`.
+    /// Archiving starts from the store's sequence number in the latest backup,
+    /// so stores that have never been backed up are skipped.
+    ///
+    /// Returns the number of batches archived.
+    pub fn archive_wal(&self, user_id: &str, store: &str, db: &DB) -> Result<usize> {
+        let _archiving = self.wal_archive_lock.lock();
+        let store_dir = self.backup_path.join(user_id).join(WAL_DIR).join(store);
+        let cursor_path = store_dir.join(WAL_CURSOR_FILE);
+        let backup_floor = self.latest_store_sequence(user_id, store)?.map(|s| s + 1);
+
+        let Some(next_seq) = fs::read_to_string(&cursor_path)
+            .ok()
+            .and_then(|s| s.trim().parse::<u64>().ok())
+            .or(backup_floor)
+        else {
+            return Ok(0);
+        };
+        if next_seq > db.latest_sequence_number() {
+            return Ok(0);
+        }
+
+        // The iterator yields batches after the given sequence number
+        let (updates, mut cursor) = match db.get_updates_since(next_seq - 1) {
+            Ok(updates) => (updates, next_seq),
+            // WAL aged out before it was archived; resume from the newest backup
+            Err(e) => match backup_floor.filter(|floor| *floor > next_seq) {
+                Some(floor) => {
+                    tracing::warn!(
+                        user_id = user_id,
+                        store = store,
+                        missing_from = next_seq,
+                        resume_at = floor,
+                        error = %e,
+                        "WAL no longer available, PITR before the latest backup is limited"
+                    );
+                    (db.get_updates_since(floor - 1)?, floor)
+                }
+                None => return Err(e.into()),
+            },
+        };
+
+        let clock_key = (user_id.to_string(), store.to_string());
+        let samples: Vec<(i64, u64)> = self
+            .sequence_clock
+            .lock()
+            .get(&clock_key)
+            .map(|samples| samples.iter().copied().collect())
+            .unwrap_or_default();
+
+        // (seq, commit time bound from the clock, batch data)
+        let mut records: Vec<(u64, Option<i64>, Vec<u8>)> = Vec::new();
+        for item in updates {
+            let (seq, batch) = item?;
+            let count = batch.len() as u64;
+            if count == 0 {
+                continue;
+            }
+            if seq > cursor {
+                tracing::warn!(
+                    user_id = user_id,
+                    store = store,
+                    expected = cursor,
+                    found = seq,
+                    "Gap in archived WAL, PITR across it will fail until the next backup"
+                );
+            }
+            let last_seq = seq + count - 1;
+            let committed_by = samples
+                .iter()
+                .find(|(_, sampled)| *sampled >= last_seq)
+                .map(|(at_ms, _)| *at_ms);
+            records.push((seq, committed_by, batch.data().to_vec()));
+            cursor = seq + count;
+        }
+        // Every batch iterated above was committed by now
+        let run_end_ms = Utc::now().timestamp_millis();
+
+        let Some(first_seq) = records.first().map(|(seq, _, _)| *seq) else {
+            return Ok(0);
+        };
+        let archived = records.len();
+        let mut segment = Vec::new();
+        for (seq, committed_by, data) in records {
+            segment.extend_from_slice(&seq.to_le_bytes());
+            segment.extend_from_slice(&committed_by.unwrap_or(run_end_ms).to_le_bytes());
+            segment.extend_from_slice(&(data.len() as u32).to_le_bytes());
+            segment.extend_from_slice(&data);
+        }
+        fs::create_dir_all(&store_dir)?;
+        let segment_path = store_dir.join(format!("{first_seq:020}.{WAL_SEGMENT_EXT}"));
+        let temp_path = segment_path.with_extension("tmp");
+        fs::write(&temp_path, &segment)?;
+        fs::rename(&temp_path, &segment_path)?;
+        fs::write(&cursor_path, cursor.to_string())?;
+
+        // Only samples at or past the cursor can date batches still to come
+        if let Some(samples) = self.sequence_clock.lock().get_mut(&clock_key) {
+            samples.retain(|(_, sampled)| *sampled >= cursor);
+        }
+
+        tracing::debug!(
+            user_id = user_id,
+            store = store,
+            batches = archived,
+            "Archived WAL segment"
+        );
+        Ok(archived)
+    }
+
+    /// Apply archived write batches following `after_seq` to `db`, in order,
+    /// up to the last batch known to be committed at or before `until`
+    ///
+    /// Batches committed within `WAL_CLOCK_INTERVAL_SECS` before `until` may
+    /// be dated after it and left out. Fails if sequence numbers are missing
+    /// from the archive in that range.
+    pub fn replay_wal(
+        &self,
+        user_id: &str,
+        store: &str,
+        db: &DB,
+        after_seq: u64,
+        until: DateTime<Utc>,
+    ) -> Result<WalReplay> {
+        let until_ms = until.timestamp_millis();
+        let mut expected = after_seq + 1;
+        let mut replay = WalReplay::default();
+
+        for segment_path in self.wal_segments(user_id, store)? {
+            for record in read_wal_segment(&segment_path)? {
+                if record.committed_by_ms > until_ms {
+                    return Ok(replay);
+                }
+                let count = record.batch.len() as u64;
+                if record.seq + count <= expected {
+                    continue;
+                }
+                if record.seq > expected {
+                    return Err(anyhow!(
+                        "WAL archive for {store} is missing sequence numbers {expected}..{}",
+                        record.seq
+                    ));
+                }
+                db.write(record.batch)?;
+                expected = record.seq + count;
+                replay.batches += 1;
+                replay.replayed_through = DateTime::from_timestamp_millis(record.committed_by_ms);
+            }
+        }
+
+        Ok(replay)
+    }
+
+    /// Drop the WAL archive of a user (their stores start a new history)
+    pub fn clear_wal_archive(&self, user_id: &str) -> Result<()> {
+        self.sequence_clock
+            .lock()
+            .retain(|(sampled_user, _), _| sampled_user != user_id);
+        let wal_dir = self.backup_path.join(user_id).join(WAL_DIR);
+        if wal_dir.exists() {
+            fs::remove_dir_all(&wal_dir)?;
+        }
+        Ok(())
+    }
+
+    /// Remove WAL segments that only hold batches older than the oldest backup
+    fn prune_wal_archive(&self, user_id: &str) -> Result<usize> {
+        let wal_dir = self.backup_path.join(user_id).join(WAL_DIR);
+        if !wal_dir.exists() {
+            return Ok(0);
+        }
+        let backups = self.list_backups(user_id)?;
+        let Some(oldest) = backups.iter().min_by_key(|b| b.backup_id) else {
+            return Ok(0);
+        };
+
+        let mut removed = 0;
+        for entry in fs::read_dir(&wal_dir)? {
+            let store = entry?.file_name().to_string_lossy().to_string();
+            let floor = if store == MAIN_STORE {
+                Some(oldest.sequence_number)
+            } else {
+                oldest.store_sequence_numbers.get(&store).copied()
+            };
+            let Some(floor) = floor else {
+                continue;
+            };
+            // A segment is obsolete once the next one starts at or before the floor
+            let segments = self.wal_segments(user_id, &store)?;
+            for pair in segments.windows(2) {
+                if segment_first_seq(&pair[1]).is_some_and(|seq| seq <= floor + 1) {
+                    fs::remove_file(&pair[0])?;
+                    removed += 1;
+                }
+            }
+        }
+        Ok(removed)
+    }
+
+    /// Archived segments of a store, oldest first
+    fn wal_segments(&self, user_id: &str, store: &str) -> Result<Vec<PathBuf>> {
+        let store_dir = self.backup_path.join(user_id).join(WAL_DIR).join(store);
+        if !store_dir.exists() {
+            return Ok(Vec::new());
+        }
+        let mut segments: Vec<PathBuf> = fs::read_dir(&store_dir)?
+            .filter_map(|entry| entry.ok().map(|e| e.path()))
+            .filter(|path| path.extension().is_some_and(|ext| ext == WAL_SEGMENT_EXT))
+            .collect();
+        // Zero-padded first sequence numbers sort numerically
+        segments.sort();
+        Ok(segments)
+    }
+
+    /// Sequence number of `store` in the user's newest backup
+    fn latest_store_sequence(&self, user_id: &str, store: &str) -> Result<Option<u64>> {
+        let latest = self
+            .list_backups(user_id)?
+            .into_iter()
+            .max_by_key(|b| b.backup_id);
+        Ok(latest.and_then(|b| {
+            if store == MAIN_STORE {
+                Some(b.sequence_number)
+            } else {
+                b.store_sequence_numbers.get(store).copied()
+            }
+        }))
+    }
+
     /// Delete old backups, keeping only the most recent N backups
     pub fn purge_old_backups(&self, user_id: &str, keep_count: usize) -> Result<usize> {
         let backup_dir = self.backup_path.join(user_id);
@@ -482,6 +887,20 @@ impl ShodhBackupEngine {
             }
         }
 
+        match self.prune_wal_archive(user_id) {
+            Ok(0) => {}
+            Ok(removed) => tracing::debug!(
+                user_id = user_id,
+                segments = removed,
+                "Pruned WAL segments older than the oldest backup"
+            ),
+            Err(e) => tracing::warn!(
+                user_id = user_id,
+                error = %e,
+                "Failed to prune WAL archive"
+            ),
+        }
+
         tracing::info!(
             purged_count = to_delete,
             kept_count = keep_count,
@@ -561,6 +980,40 @@ impl ShodhBackupEngine {
     }
 }
 
+/// First sequence number of a WAL segment, from its file name
+fn segment_first_seq(path: &Path) -> Option<u64> {
+    path.file_stem()?.to_str()?.parse().ok()
+}
+
+/// Parse the records of a WAL segment written by `archive_wal`
+fn read_wal_segment(path: &Path) -> Result<Vec<WalRecord>> {
+    const HEADER_LEN: usize = 8 + 8 + 4;
+
+    let data = fs::read(path)?;
+    let mut records = Vec::new();
+    let mut offset = 0;
+    while offset < data.len() {
+        let header = data
+            .get(offset..offset + HEADER_LEN)
+            .ok_or_else(|| anyhow!("Truncated WAL segment {}", path.display()))?;
+        let seq = u64::from_le_bytes(header[0..8].try_into()?);
+        let committed_by_ms = i64::from_le_bytes(header[8..16].try_into()?);
+        let len = u32::from_le_bytes(header[16..20].try_into()?) as usize;
+        offset += HEADER_LEN;
+
+        let batch_data = data
+            .get(offset..offset + len)
+            .ok_or_else(|| anyhow!("Truncated WAL segment {}", path.display()))?;
+        records.push(WalRecord {
+            seq,
+            committed_by_ms,
+            batch: WriteBatch::from_data(batch_data),
+        });
+        offset += len;
+    }
+    Ok(records)
+}
+
 /// Calculate total size of a directory recursively
 fn dir_size(path: &Path) -> Result<u64> {
     let mut total = 0u64;
@@ -619,6 +1072,7 @@ mod tests {
             sequence_number: 42,
             secondary_stores: vec!["todo_items".to_string(), "prospective_tasks".to_string()],
             secondary_size_bytes: 2048,
+            store_sequence_numbers: HashMap::from([("todo_items".to_string(), 7)]),
         };
 
         let json = serde_json::to_string(&metadata).unwrap();
@@ -626,5 +1080,128 @@ mod tests {
 
         assert_eq!(metadata.backup_id, deserialized.backup_id);
         assert_eq!(metadata.user_id, deserialized.user_id);
+        assert_eq!(deserialized.store_sequence_numbers["todo_items"], 7);
+    }
+
+    fn open_db(path: &Path) -> DB {
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        opts.set_compression_type(rocksdb::DBCompressionType::Lz4);
+        opts.set_wal_ttl_seconds(WAL_RETENTION_SECS);
+        DB::open(&opts, path).unwrap()
+    }
+
+    #[test]
+    fn test_wal_archive_replays_to_point_in_time() {
+        let temp_dir = TempDir::new().unwrap();
+        let engine = ShodhBackupEngine::new(temp_dir.path().join("backups")).unwrap();
+        let db = open_db(&temp_dir.path().join("live"));
+
+        // Nothing is archived before the first backup
+        db.put(b"a", b"1").unwrap();
+        assert_eq!(engine.archive_wal("u", MAIN_STORE, &db).unwrap(), 0);
+        let backup = engine.create_backup(&db, "u").unwrap();
+
+        db.put(b"b", b"2").unwrap();
+        db.delete(b"a").unwrap();
+        assert_eq!(engine.archive_wal("u", MAIN_STORE, &db).unwrap(), 2);
+        let cutoff = Utc::now();
+        std::thread::sleep(std::time::Duration::from_millis(5));
+        db.put(b"c", b"3").unwrap();
+        assert_eq!(engine.archive_wal("u", MAIN_STORE, &db).unwrap(), 1);
+        assert_eq!(engine.archive_wal("u", MAIN_STORE, &db).unwrap(), 0);
+
+        let restore_path = temp_dir.path().join("restored");
+        engine
+            .restore_backup("u", Some(backup.backup_id), &restore_path)
+            .unwrap();
+        let restored = open_db(&restore_path);
+        assert_eq!(restored.get(b"a").unwrap(), Some(b"1".to_vec()));
+
+        let replayed = engine
+            .replay_wal("u", MAIN_STORE, &restored, backup.sequence_number, cutoff)
+            .unwrap();
+        assert_eq!(replayed.batches, 2);
+        assert!(replayed.replayed_through.is_some_and(|t| t <= cutoff));
+        assert_eq!(restored.get(b"a").unwrap(), None);
+        assert_eq!(restored.get(b"b").unwrap(), Some(b"2".to_vec()));
+        assert_eq!(restored.get(b"c").unwrap(), None, "written after cutoff");
+
+        // Replaying from the backup point again is idempotent
+        engine
+            .replay_wal(
+                "u",
+                MAIN_STORE,
+                &restored,
+                backup.sequence_number,
+                Utc::now(),
+            )
+            .unwrap();
+        assert_eq!(restored.get(b"a").unwrap(), None);
+        assert_eq!(restored.get(b"c").unwrap(), Some(b"3".to_vec()));
+    }
+
+    #[test]
+    fn test_wal_replay_keeps_batches_committed_before_target_but_archived_after() {
+        let temp_dir = TempDir::new().unwrap();
+        let engine = ShodhBackupEngine::new(temp_dir.path().join("backups")).unwrap();
+        let db = open_db(&temp_dir.path().join("live"));
+        let backup = engine.create_backup(&db, "u").unwrap();
+
+        db.put(b"before", b"1").unwrap();
+        engine.sample_sequence("u", MAIN_STORE, &db);
+        std::thread::sleep(std::time::Duration::from_millis(5));
+        let target = Utc::now();
+        std::thread::sleep(std::time::Duration::from_millis(5));
+        db.put(b"after", b"2").unwrap();
+        // One archiver run after the target picks up both writes
+        assert_eq!(engine.archive_wal("u", MAIN_STORE, &db).unwrap(), 2);
+
+        let restore_path = temp_dir.path().join("restored");
+        engine
+            .restore_backup("u", Some(backup.backup_id), &restore_path)
+            .unwrap();
+        let restored = open_db(&restore_path);
+        let replayed = engine
+            .replay_wal("u", MAIN_STORE, &restored, backup.sequence_number, target)
+            .unwrap();
+        assert_eq!(replayed.batches, 1);
+        assert_eq!(restored.get(b"before").unwrap(), Some(b"1".to_vec()));
+        assert_eq!(restored.get(b"after").unwrap(), None);
+    }
+
+    #[test]
+    fn test_restore_store_in_place() {
+        let temp_dir = TempDir::new().unwrap();
+        let engine = ShodhBackupEngine::new(temp_dir.path().join("backups")).unwrap();
+        let main = Arc::new(open_db(&temp_dir.path().join("main")));
+        let shared = Arc::new(open_db(&temp_dir.path().join("shared")));
+
+        shared.put(b"kept", b"old").unwrap();
+        shared.put(b"changed", b"old").unwrap();
+        let backup = engine
+            .create_comprehensive_backup(
+                &main,
+                "u",
+                &[SecondaryStoreRef {
+                    name: "shared",
+                    db: &shared,
+                }],
+            )
+            .unwrap();
+        assert!(backup.store_sequence_numbers.contains_key("shared"));
+
+        shared.put(b"changed", b"new").unwrap();
+        shared.put(b"added", b"new").unwrap();
+        assert!(engine
+            .restore_store_in_place("u", backup.backup_id, "shared", &shared)
+            .unwrap());
+        assert!(!engine
+            .restore_store_in_place("u", backup.backup_id, "missing", &shared)
+            .unwrap());
+
+        assert_eq!(shared.get(b"kept").unwrap(), Some(b"old".to_vec()));
+        assert_eq!(shared.get(b"changed").unwrap(), Some(b"old".to_vec()));
+        assert_eq!(shared.get(b"added").unwrap(), None);
     }
 }
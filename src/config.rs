@@ -1,15 +1,35 @@
 //! Configuration management for Shodh-Memory
 //!
-//! All configurable parameters in one place with environment variable overrides.
+//! All configurable parameters in one place, resolved in layers:
+//! defaults → config file (`shodh_config.toml` / `shodh_config.json`) →
+//! environment variables → CLI flags (exported as env vars by the binary).
 //! Follows the principle: sensible defaults, configurable in production.
+//!
+//! Hot-reloadable settings (rate limits, CORS, maintenance interval, backup
+//! schedule) are re-read on SIGHUP or when the config file changes, see
+//! [`spawn_config_watcher`].
 
+use anyhow::{Context, Result};
+use serde::Deserialize;
 use std::env;
-use std::path::PathBuf;
-use tracing::info;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+use tokio::sync::watch;
+use tracing::{info, warn};
+
+use crate::memory::MemoryConfig;
+
+/// Config file names searched in the working directory when no explicit path is given
+pub const DEFAULT_CONFIG_FILES: &[&str] = &["shodh_config.toml", "shodh_config.json"];
+
+/// How often the config watcher checks the file's modification time
+const CONFIG_POLL_INTERVAL_SECS: u64 = 2;
 
 /// CORS configuration
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct CorsConfig {
+    /// Send CORS headers at all (false = browsers block every cross-origin request)
+    pub enabled: bool,
     /// Allowed origins (empty = allow all)
     pub allowed_origins: Vec<String>,
     /// Allowed HTTP methods
@@ -25,6 +45,7 @@ pub struct CorsConfig {
 impl Default for CorsConfig {
     fn default() -> Self {
         Self {
+            enabled: true,
             allowed_origins: Vec::new(), // Empty = allow all origins
             allowed_methods: vec![
                 "GET".to_string(),
@@ -51,9 +72,27 @@ impl CorsConfig {
     /// This prevents accidentally running in production with permissive CORS.
     pub fn from_env() -> Self {
         let mut config = Self::default();
+        config.apply_env();
+
+        let is_production = env::var("SHODH_ENV")
+            .map(|v| {
+                let v = v.to_lowercase();
+                v == "production" || v == "prod"
+            })
+            .unwrap_or(false);
+        config.warn_if_permissive(is_production);
+
+        config
+    }
+
+    /// Override fields with any `SHODH_CORS_*` environment variables that are set
+    pub fn apply_env(&mut self) {
+        if let Ok(val) = env::var("SHODH_CORS_ENABLED") {
+            self.enabled = val.to_lowercase() == "true" || val == "1";
+        }
 
         if let Ok(origins) = env::var("SHODH_CORS_ORIGINS") {
-            config.allowed_origins = origins
+            self.allowed_origins = origins
                 .split(',')
                 .map(|s| s.trim().to_string())
                 .filter(|s| !s.is_empty())
@@ -61,7 +100,7 @@ impl CorsConfig {
         }
 
         if let Ok(methods) = env::var("SHODH_CORS_METHODS") {
-            config.allowed_methods = methods
+            self.allowed_methods = methods
                 .split(',')
                 .map(|s| s.trim().to_uppercase())
                 .filter(|s| !s.is_empty())
@@ -69,7 +108,7 @@ impl CorsConfig {
         }
 
         if let Ok(headers) = env::var("SHODH_CORS_HEADERS") {
-            config.allowed_headers = headers
+            self.allowed_headers = headers
                 .split(',')
                 .map(|s| s.trim().to_string())
                 .filter(|s| !s.is_empty())
@@ -77,35 +116,28 @@ impl CorsConfig {
         }
 
         if let Ok(val) = env::var("SHODH_CORS_CREDENTIALS") {
-            config.allow_credentials = val.to_lowercase() == "true" || val == "1";
+            self.allow_credentials = val.to_lowercase() == "true" || val == "1";
         }
 
         if let Ok(val) = env::var("SHODH_CORS_MAX_AGE") {
             if let Ok(n) = val.parse() {
-                config.max_age_seconds = n;
+                self.max_age_seconds = n;
             }
         }
+    }
 
-        // Production safety check: warn if CORS is permissive in production
-        let is_production = env::var("SHODH_ENV")
-            .map(|v| {
-                let v = v.to_lowercase();
-                v == "production" || v == "prod"
-            })
-            .unwrap_or(false);
-
-        if is_production && config.allowed_origins.is_empty() {
+    /// Production safety check: warn if CORS is permissive in production
+    fn warn_if_permissive(&self, is_production: bool) {
+        if is_production && self.enabled && self.allowed_origins.is_empty() {
             tracing::warn!(
                 "⚠️  PRODUCTION WARNING: CORS allows all origins. Set SHODH_CORS_ORIGINS for security."
             );
         }
-
-        config
     }
 
     /// Check if any origin restrictions are configured
     pub fn is_restricted(&self) -> bool {
-        !self.allowed_origins.is_empty()
+        !self.enabled || !self.allowed_origins.is_empty()
     }
 
     /// Convert to tower-http CorsLayer
@@ -114,6 +146,11 @@ impl CorsConfig {
 
         let mut layer = CorsLayer::new();
 
+        // A layer with no allowed origins adds no CORS headers
+        if !self.enabled {
+            return layer;
+        }
+
         // Configure allowed origins
         if self.allowed_origins.is_empty() {
             // Intentionally permissive - no origins configured
@@ -219,6 +256,15 @@ pub struct ServerConfig {
     /// Audit log retention days (default: 30)
     pub audit_retention_days: u64,
 
+    /// Whether memory operations are written to the audit log (default: true)
+    pub audit_enabled: bool,
+
+    /// Session history retention days, 0 = keep forever (default: 365)
+    pub session_retention_days: u32,
+
+    /// Maximum persisted sessions per user, 0 = unlimited (default: 1000)
+    pub session_max_per_user: usize,
+
     /// Rate limit: requests per second (default: 4000 - LLM-friendly)
     pub rate_limit_per_second: u64,
 
@@ -262,6 +308,33 @@ pub struct ServerConfig {
     /// Caps the number of NER/tag/regex entities to prevent O(n²) edge explosion
     /// in the knowledge graph. 10 entities → max 45 co-occurrence edges.
     pub max_entities_per_memory: usize,
+
+    /// Confidence needed to merge two graph entities automatically (default: 0.85)
+    /// Lower values fold more aliases ("Postgres" → "PostgreSQL") at the risk
+    /// of merging distinct entities; merges can be undone.
+    pub entity_merge_confidence: f32,
+
+    /// Seconds between graph analytics runs per user (default: 21600 = 6 hours)
+    /// Recomputes entity centrality, communities and bridges during maintenance.
+    /// 0 disables scheduled runs; `/api/graph/analytics` can still refresh on demand.
+    pub graph_analytics_interval_secs: u64,
+
+    /// Template for each user's memory system (tier sizes, compression,
+    /// importance threshold). `storage_path` is replaced per user.
+    pub memory: MemoryConfig,
+
+    /// `[embeddings]` as written in the config file
+    ///
+    /// The embedder reads these through env vars exported at startup, so this
+    /// copy only exists to flag edits as needing a restart.
+    pub embeddings: EmbeddingsSection,
+
+    /// `[vector_index]` as written in the config file (exported like `embeddings`)
+    pub vector_index: VectorIndexSection,
+
+    /// Hosts outbound webhooks may target even if they resolve to loopback,
+    /// private or link-local addresses (default: none)
+    pub webhook_allowed_hosts: Vec<String>,
 }
 
 impl Default for ServerConfig {
@@ -274,6 +347,9 @@ impl Default for ServerConfig {
             audit_max_entries_per_user: 10_000,
             audit_rotation_check_interval: 100,
             audit_retention_days: 30,
+            audit_enabled: true,
+            session_retention_days: 365,
+            session_max_per_user: 1000,
             rate_limit_per_second: 4000,
             rate_limit_burst: 8000,
             max_concurrent_requests: 200,
@@ -286,133 +362,377 @@ impl Default for ServerConfig {
             backup_max_count: 7,            // Keep 7 backups (1 week of daily backups)
             backup_enabled: false,          // Disabled by default, auto-enabled in production
             max_entities_per_memory: 10,    // Cap entities per memory (10 → max 45 edges)
+            entity_merge_confidence: crate::constants::ENTITY_CONCEPT_MERGE_THRESHOLD,
+            graph_analytics_interval_secs: 21600, // 6 hours
+            memory: MemoryConfig::default(),
+            embeddings: EmbeddingsSection::default(),
+            vector_index: VectorIndexSection::default(),
+            webhook_allowed_hosts: Vec::new(),
         }
     }
 }
 
 impl ServerConfig {
     /// Load configuration from environment variables with defaults
-    #[allow(clippy::field_reassign_with_default)] // Environment overrides require mutable config
     pub fn from_env() -> Self {
+        Self::from_layers(&ConfigFile::default())
+    }
+
+    /// Load configuration from defaults, an optional config file, then environment
+    ///
+    /// Unknown keys in the file are reported as warnings; malformed files and
+    /// invalid values are errors.
+    pub fn load(config_path: Option<&Path>) -> Result<Self> {
+        let file = match config_path {
+            Some(path) => {
+                let (file, unknown_keys) = ConfigFile::load(path)?;
+                for key in &unknown_keys {
+                    warn!(
+                        "Config file {}: unknown key '{}' (ignored)",
+                        path.display(),
+                        key
+                    );
+                }
+                for key in file.inert_keys() {
+                    warn!(
+                        "Config file {}: '{}' is not supported by this version (ignored)",
+                        path.display(),
+                        key
+                    );
+                }
+                file
+            }
+            None => ConfigFile::default(),
+        };
+
+        let config = Self::from_layers(&file);
+        config.validate()?;
+        Ok(config)
+    }
+
+    fn from_layers(file: &ConfigFile) -> Self {
         let mut config = Self::default();
+        file.apply_to(&mut config);
+        config.apply_env();
+
+        // Auto-enable backups in production mode unless explicitly configured
+        if config.is_production
+            && file.backup.enabled.is_none()
+            && env::var("SHODH_BACKUP_ENABLED").is_err()
+        {
+            config.backup_enabled = true;
+        }
 
-        // Check production mode first
-        config.is_production = env::var("SHODH_ENV")
-            .map(|v| {
-                let v = v.to_lowercase();
-                v == "production" || v == "prod"
-            })
-            .unwrap_or(false);
+        config.cors.warn_if_permissive(config.is_production);
+        config
+    }
+
+    /// Override fields with any `SHODH_*` environment variables that are set
+    fn apply_env(&mut self) {
+        // Production mode
+        if let Ok(val) = env::var("SHODH_ENV") {
+            let val = val.to_lowercase();
+            self.is_production = val == "production" || val == "prod";
+        }
 
         // Host (bind address)
         if let Ok(val) = env::var("SHODH_HOST") {
-            config.host = val;
+            self.host = val;
         }
 
         // Port
         if let Ok(val) = env::var("SHODH_PORT") {
             if let Ok(port) = val.parse() {
-                config.port = port;
+                self.port = port;
             }
         }
 
         // Storage path
         if let Ok(val) = env::var("SHODH_MEMORY_PATH") {
-            config.storage_path = PathBuf::from(val);
+            self.storage_path = PathBuf::from(val);
         }
 
         // Max users in memory
         if let Ok(val) = env::var("SHODH_MAX_USERS") {
             if let Ok(n) = val.parse() {
-                config.max_users_in_memory = n;
+                self.max_users_in_memory = n;
             }
         }
 
         // Audit settings
         if let Ok(val) = env::var("SHODH_AUDIT_MAX_ENTRIES") {
             if let Ok(n) = val.parse() {
-                config.audit_max_entries_per_user = n;
+                self.audit_max_entries_per_user = n;
             }
         }
 
         if let Ok(val) = env::var("SHODH_AUDIT_RETENTION_DAYS") {
             if let Ok(n) = val.parse() {
-                config.audit_retention_days = n;
+                self.audit_retention_days = n;
+            }
+        }
+
+        if let Ok(val) = env::var("SHODH_AUDIT_ENABLED") {
+            self.audit_enabled = val.to_lowercase() == "true" || val == "1";
+        }
+
+        // Session history
+        if let Ok(val) = env::var("SHODH_SESSION_RETENTION_DAYS") {
+            if let Ok(n) = val.parse() {
+                self.session_retention_days = n;
+            }
+        }
+
+        if let Ok(val) = env::var("SHODH_SESSION_MAX_PER_USER") {
+            if let Ok(n) = val.parse() {
+                self.session_max_per_user = n;
             }
         }
 
         // Rate limiting
         if let Ok(val) = env::var("SHODH_RATE_LIMIT") {
             if let Ok(n) = val.parse() {
-                config.rate_limit_per_second = n;
+                self.rate_limit_per_second = n;
             }
         }
 
         if let Ok(val) = env::var("SHODH_RATE_BURST") {
             if let Ok(n) = val.parse() {
-                config.rate_limit_burst = n;
+                self.rate_limit_burst = n;
             }
         }
 
         // Concurrency
         if let Ok(val) = env::var("SHODH_MAX_CONCURRENT") {
             if let Ok(n) = val.parse() {
-                config.max_concurrent_requests = n;
+                self.max_concurrent_requests = n;
             }
         }
 
         // Request timeout
         if let Ok(val) = env::var("SHODH_REQUEST_TIMEOUT") {
             if let Ok(n) = val.parse() {
-                config.request_timeout_secs = n;
+                self.request_timeout_secs = n;
             }
         }
 
         // CORS configuration
-        config.cors = CorsConfig::from_env();
+        self.cors.apply_env();
 
         // Memory maintenance settings
         if let Ok(val) = env::var("SHODH_MAINTENANCE_INTERVAL") {
             if let Ok(n) = val.parse() {
-                config.maintenance_interval_secs = n;
+                self.maintenance_interval_secs = n;
             }
         }
 
         if let Ok(val) = env::var("SHODH_ACTIVATION_DECAY") {
             if let Ok(n) = val.parse::<f32>() {
-                config.activation_decay_factor = n.clamp(0.5, 0.99);
+                self.activation_decay_factor = n.clamp(0.5, 0.99);
             }
         }
 
         // Backup configuration
         if let Ok(val) = env::var("SHODH_BACKUP_INTERVAL") {
             if let Ok(n) = val.parse() {
-                config.backup_interval_secs = n;
+                self.backup_interval_secs = n;
             }
         }
 
         if let Ok(val) = env::var("SHODH_BACKUP_MAX_COUNT") {
             if let Ok(n) = val.parse() {
-                config.backup_max_count = n;
+                self.backup_max_count = n;
             }
         }
 
-        // Auto-enable backups in production mode unless explicitly disabled
         if let Ok(val) = env::var("SHODH_BACKUP_ENABLED") {
-            config.backup_enabled = val.to_lowercase() == "true" || val == "1";
-        } else if config.is_production {
-            // Auto-enable in production
-            config.backup_enabled = true;
+            self.backup_enabled = val.to_lowercase() == "true" || val == "1";
         }
 
         // Entity extraction cap
         if let Ok(val) = env::var("SHODH_MAX_ENTITIES") {
             if let Ok(n) = val.parse::<usize>() {
-                config.max_entities_per_memory = n.clamp(1, 50);
+                self.max_entities_per_memory = n.clamp(1, 50);
             }
         }
 
-        config
+        // Entity resolution
+        if let Ok(val) = env::var("SHODH_ENTITY_MERGE_CONFIDENCE") {
+            if let Ok(n) = val.parse::<f32>() {
+                self.entity_merge_confidence = n.clamp(0.5, 1.0);
+            }
+        }
+
+        // Graph analytics schedule
+        if let Ok(val) = env::var("SHODH_GRAPH_ANALYTICS_INTERVAL") {
+            if let Ok(n) = val.parse::<u64>() {
+                self.graph_analytics_interval_secs = n;
+            }
+        }
+
+        // Internal hosts outbound webhooks may reach
+        if let Ok(val) = env::var("SHODH_WEBHOOK_ALLOWED_HOSTS") {
+            self.webhook_allowed_hosts = val
+                .split(',')
+                .map(|s| s.trim().to_string())
+                .filter(|s| !s.is_empty())
+                .collect();
+        }
+    }
+
+    /// Reject values that would break the server at runtime
+    pub fn validate(&self) -> Result<()> {
+        let mut problems = Vec::new();
+
+        if self.port == 0 {
+            problems.push("port must be non-zero");
+        }
+        if self.max_users_in_memory == 0 {
+            problems.push("max_users_in_memory must be at least 1");
+        }
+        if self.max_concurrent_requests == 0 {
+            problems.push("max_concurrent_requests must be at least 1");
+        }
+        if self.request_timeout_secs == 0 {
+            problems.push("request_timeout_secs must be at least 1");
+        }
+        if self.audit_rotation_check_interval == 0 {
+            problems.push("audit rotation_check_interval must be at least 1");
+        }
+        if self.maintenance_interval_secs == 0 {
+            problems.push("maintenance interval_secs must be at least 1");
+        }
+        if self.rate_limit_per_second > 0 && self.rate_limit_burst == 0 {
+            problems.push("rate_limit burst_size must be at least 1 when rate limiting is on");
+        }
+        if self.backup_enabled && self.backup_max_count == 0 {
+            problems.push("backup max_count must be at least 1 when backups are enabled");
+        }
+        if self
+            .vector_index
+            .index_type
+            .as_deref()
+            .is_some_and(|t| !matches!(t, "vamana" | "vamana_hnsw"))
+        {
+            problems.push("vector_index type must be \"vamana_hnsw\" (the only supported index)");
+        }
+        if self.vector_index.max_degree == Some(0) {
+            problems.push("vector_index max_degree must be at least 1");
+        }
+        if self.vector_index.search_list_size == Some(0) {
+            problems.push("vector_index search_list_size must be at least 1");
+        }
+
+        if problems.is_empty() {
+            Ok(())
+        } else {
+            anyhow::bail!("Invalid configuration: {}", problems.join("; "))
+        }
+    }
+
+    /// Copy the hot-reloadable settings from `next` into a copy of `self`
+    ///
+    /// Returns the merged config and the names of the settings that changed.
+    /// Changes to settings that need a restart are logged and left untouched.
+    pub fn merge_reloadable(&self, next: &ServerConfig) -> (ServerConfig, Vec<&'static str>) {
+        let mut merged = self.clone();
+        let mut changed = Vec::new();
+
+        if self.rate_limit_per_second != next.rate_limit_per_second
+            || self.rate_limit_burst != next.rate_limit_burst
+        {
+            merged.rate_limit_per_second = next.rate_limit_per_second;
+            merged.rate_limit_burst = next.rate_limit_burst;
+            changed.push("rate_limit");
+        }
+        if self.cors != next.cors {
+            merged.cors = next.cors.clone();
+            changed.push("cors");
+        }
+        if self.maintenance_interval_secs != next.maintenance_interval_secs {
+            merged.maintenance_interval_secs = next.maintenance_interval_secs;
+            changed.push("maintenance");
+        }
+        if self.backup_enabled != next.backup_enabled
+            || self.backup_interval_secs != next.backup_interval_secs
+            || self.backup_max_count != next.backup_max_count
+        {
+            merged.backup_enabled = next.backup_enabled;
+            merged.backup_interval_secs = next.backup_interval_secs;
+            merged.backup_max_count = next.backup_max_count;
+            changed.push("backup");
+        }
+
+        let restart_required = [
+            ("host", self.host != next.host),
+            ("port", self.port != next.port),
+            ("storage path", self.storage_path != next.storage_path),
+            (
+                "max_users_in_memory",
+                self.max_users_in_memory != next.max_users_in_memory,
+            ),
+            (
+                "max_concurrent_requests",
+                self.max_concurrent_requests != next.max_concurrent_requests,
+            ),
+            (
+                "request_timeout_secs",
+                self.request_timeout_secs != next.request_timeout_secs,
+            ),
+            ("production", self.is_production != next.is_production),
+            (
+                "audit",
+                self.audit_enabled != next.audit_enabled
+                    || self.audit_max_entries_per_user != next.audit_max_entries_per_user
+                    || self.audit_rotation_check_interval != next.audit_rotation_check_interval
+                    || self.audit_retention_days != next.audit_retention_days,
+            ),
+            (
+                "sessions",
+                self.session_retention_days != next.session_retention_days
+                    || self.session_max_per_user != next.session_max_per_user,
+            ),
+            (
+                "activation_decay_factor",
+                self.activation_decay_factor != next.activation_decay_factor,
+            ),
+            (
+                "max_entities_per_memory",
+                self.max_entities_per_memory != next.max_entities_per_memory,
+            ),
+            (
+                "entity_merge_confidence",
+                self.entity_merge_confidence != next.entity_merge_confidence,
+            ),
+            (
+                "graph_analytics_interval_secs",
+                self.graph_analytics_interval_secs != next.graph_analytics_interval_secs,
+            ),
+            (
+                "webhook_allowed_hosts",
+                self.webhook_allowed_hosts != next.webhook_allowed_hosts,
+            ),
+            (
+                "memory",
+                self.memory.working_memory_size != next.memory.working_memory_size
+                    || self.memory.session_memory_size_mb != next.memory.session_memory_size_mb
+                    || self.memory.auto_compress != next.memory.auto_compress
+                    || self.memory.compression_age_days != next.memory.compression_age_days
+                    || self.memory.importance_threshold != next.memory.importance_threshold,
+            ),
+            ("embeddings", self.embeddings != next.embeddings),
+            ("vector_index", self.vector_index != next.vector_index),
+        ];
+        for (name, differs) in restart_required {
+            if differs {
+                warn!(
+                    "Config change to '{}' requires a restart to take effect",
+                    name
+                );
+            }
+        }
+
+        (merged, changed)
     }
 
     /// Log the current configuration
@@ -440,7 +760,13 @@ impl ServerConfig {
         info!("   Max concurrent: {}", self.max_concurrent_requests);
         info!("   Request timeout: {}s", self.request_timeout_secs);
         info!("   Audit retention: {} days", self.audit_retention_days);
-        if self.cors.is_restricted() {
+        info!(
+            "   Session retention: {} days, {} per user",
+            self.session_retention_days, self.session_max_per_user
+        );
+        if !self.cors.enabled {
+            info!("   CORS: disabled (cross-origin requests blocked)");
+        } else if self.cors.is_restricted() {
             info!("   CORS origins: {:?}", self.cors.allowed_origins);
         } else {
             info!("   CORS: Permissive (all origins allowed)");
@@ -461,6 +787,594 @@ impl ServerConfig {
     }
 }
 
+// =============================================================================
+// Config file
+// =============================================================================
+
+/// Server settings read from `shodh_config.toml` or `shodh_config.json`
+///
+/// Every field is optional: anything left out keeps its default, and
+/// environment variables still override whatever the file sets. TOML is
+/// used for `.toml` files, JSON for everything else.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct ConfigFile {
+    pub server: ServerSection,
+    pub storage: StorageSection,
+    pub rate_limit: RateLimitSection,
+    pub cors: CorsSection,
+    pub audit: AuditSection,
+    pub sessions: SessionsSection,
+    pub maintenance: MaintenanceSection,
+    pub backup: BackupSection,
+    pub memory: MemorySection,
+    pub embeddings: EmbeddingsSection,
+    pub vector_index: VectorIndexSection,
+    pub api: ApiSection,
+    pub deployment: DeploymentSection,
+}
+
+/// `[server]` section
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct ServerSection {
+    pub host: Option<String>,
+    pub port: Option<u16>,
+    pub production: Option<bool>,
+    pub max_users_in_memory: Option<usize>,
+    pub max_concurrent_requests: Option<usize>,
+    pub request_timeout_secs: Option<u64>,
+    /// Same as `[cors] enabled`; false sends no CORS headers (hot-reloadable)
+    pub cors_enabled: Option<bool>,
+}
+
+/// `[storage]` section
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct StorageSection {
+    #[serde(alias = "base_path")]
+    pub path: Option<PathBuf>,
+    pub compression: CompressionSection,
+    pub retention: RetentionSection,
+}
+
+/// `[storage.compression]` section
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct CompressionSection {
+    pub enabled: Option<bool>,
+    pub age_days: Option<u32>,
+}
+
+/// `[storage.retention]` section
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct RetentionSection {
+    pub working_memory_size: Option<usize>,
+    pub session_memory_size_mb: Option<usize>,
+    pub importance_threshold: Option<f32>,
+}
+
+/// `[rate_limit]` section (hot-reloadable)
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct RateLimitSection {
+    pub requests_per_second: Option<u64>,
+    pub burst_size: Option<u32>,
+}
+
+/// `[cors]` section (hot-reloadable)
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct CorsSection {
+    pub allowed_origins: Option<Vec<String>>,
+    pub allowed_methods: Option<Vec<String>>,
+    pub allowed_headers: Option<Vec<String>>,
+    pub allow_credentials: Option<bool>,
+    pub max_age_seconds: Option<u64>,
+}
+
+/// `[audit]` section
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct AuditSection {
+    pub enabled: Option<bool>,
+    pub max_entries_per_user: Option<usize>,
+    pub rotation_check_interval: Option<usize>,
+    pub retention_days: Option<u64>,
+}
+
+/// `[sessions]` section
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct SessionsSection {
+    pub retention_days: Option<u32>,
+    pub max_per_user: Option<usize>,
+}
+
+/// `[maintenance]` section (`interval_secs` is hot-reloadable)
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct MaintenanceSection {
+    pub interval_secs: Option<u64>,
+    pub activation_decay_factor: Option<f32>,
+    pub max_entities_per_memory: Option<usize>,
+    pub entity_merge_confidence: Option<f32>,
+    pub graph_analytics_interval_secs: Option<u64>,
+}
+
+/// `[backup]` section (hot-reloadable)
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct BackupSection {
+    pub enabled: Option<bool>,
+    pub interval_secs: Option<u64>,
+    pub max_count: Option<usize>,
+}
+
+/// `[memory]` section, overriding `[storage.compression]` / `[storage.retention]`
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct MemorySection {
+    pub auto_compress: Option<bool>,
+    pub compression_age_days: Option<u32>,
+    pub importance_threshold: Option<f32>,
+    /// Not supported: the knowledge graph is always on
+    pub enable_graph_memory: Option<bool>,
+    /// Not supported: visualization stats are always collected
+    pub enable_visualization: Option<bool>,
+}
+
+/// `[embeddings]` section (restart only)
+///
+/// The embedder reads its settings from the environment, so these values are
+/// exported as `SHODH_EMBEDDING_*` etc. at startup (see [`ConfigFile::export_env`]).
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default)]
+pub struct EmbeddingsSection {
+    /// `minilm` (default), `onnx` or `openai`
+    pub provider: Option<String>,
+    /// Directory containing `model_quantized.onnx` / `model.onnx` and `tokenizer.json`
+    pub model_dir: Option<PathBuf>,
+    /// ONNX model file; for `minilm` its directory is used as `model_dir`
+    pub model_path: Option<PathBuf>,
+    pub tokenizer_path: Option<PathBuf>,
+    pub dimension: Option<usize>,
+    pub max_length: Option<usize>,
+    pub use_quantized: Option<bool>,
+    /// Use hash-based embeddings when the model can't be loaded (default: true)
+    pub fallback_to_simplified: Option<bool>,
+    pub timeout_ms: Option<u64>,
+}
+
+/// `[vector_index]` section (restart only, exported as `SHODH_VECTOR_*`)
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default)]
+pub struct VectorIndexSection {
+    /// Only `vamana_hnsw` is supported
+    #[serde(rename = "type")]
+    pub index_type: Option<String>,
+    pub max_degree: Option<usize>,
+    pub search_list_size: Option<usize>,
+    pub alpha: Option<f32>,
+    /// Not supported: the memory index stores full-precision vectors
+    pub use_pq_compression: Option<bool>,
+    pub use_mmap: Option<bool>,
+    pub save_on_shutdown: Option<bool>,
+    pub rebuild_on_startup: Option<bool>,
+}
+
+/// `[api]` section
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct ApiSection {
+    /// Development key, exported as `SHODH_DEV_API_KEY` (never used in production)
+    pub default_api_key: Option<String>,
+    /// Fallback for the top-level `[rate_limit]` section
+    pub rate_limit: RateLimitSection,
+}
+
+/// `[deployment]` section
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct DeploymentSection {
+    /// `production` / `prod` enables production mode unless `[server] production` is set
+    pub environment: Option<String>,
+    /// Not supported
+    pub target: Option<String>,
+    /// Not supported
+    pub optimize_for: Option<String>,
+}
+
+impl ConfigFile {
+    /// Parse a config file, returning it along with the dotted paths of any unknown keys
+    pub fn load(path: &Path) -> Result<(Self, Vec<String>)> {
+        let text = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read config file {}", path.display()))?;
+
+        let is_toml = path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("toml"));
+
+        Self::parse(&text, is_toml)
+            .with_context(|| format!("Failed to parse config file {}", path.display()))
+    }
+
+    /// Parse config text as TOML or JSON, collecting unknown keys
+    pub fn parse(text: &str, is_toml: bool) -> Result<(Self, Vec<String>)> {
+        let mut unknown_keys = Vec::new();
+        let file = if is_toml {
+            serde_ignored::deserialize(toml::Deserializer::new(text), |path| {
+                unknown_keys.push(path.to_string())
+            })?
+        } else {
+            let mut de = serde_json::Deserializer::from_str(text);
+            let file =
+                serde_ignored::deserialize(&mut de, |path| unknown_keys.push(path.to_string()))?;
+            de.end()?;
+            file
+        };
+        Ok((file, unknown_keys))
+    }
+
+    /// Overlay every value set in the file onto `config`
+    pub fn apply_to(&self, config: &mut ServerConfig) {
+        let server = &self.server;
+        if let Some(host) = &server.host {
+            config.host = host.clone();
+        }
+        if let Some(port) = server.port {
+            config.port = port;
+        }
+        if let Some(production) = server.production {
+            config.is_production = production;
+        }
+        if let Some(n) = server.max_users_in_memory {
+            config.max_users_in_memory = n;
+        }
+        if let Some(n) = server.max_concurrent_requests {
+            config.max_concurrent_requests = n;
+        }
+        if let Some(n) = server.request_timeout_secs {
+            config.request_timeout_secs = n;
+        }
+        if let Some(enabled) = server.cors_enabled {
+            config.cors.enabled = enabled;
+        }
+        if server.production.is_none() {
+            if let Some(environment) = &self.deployment.environment {
+                let environment = environment.to_lowercase();
+                config.is_production = environment == "production" || environment == "prod";
+            }
+        }
+
+        let storage = &self.storage;
+        if let Some(path) = &storage.path {
+            config.storage_path = path.clone();
+        }
+        if let Some(enabled) = storage.compression.enabled {
+            config.memory.auto_compress = enabled;
+        }
+        if let Some(n) = storage.compression.age_days {
+            config.memory.compression_age_days = n;
+        }
+        if let Some(n) = storage.retention.working_memory_size {
+            config.memory.working_memory_size = n;
+        }
+        if let Some(n) = storage.retention.session_memory_size_mb {
+            config.memory.session_memory_size_mb = n;
+        }
+        if let Some(n) = storage.retention.importance_threshold {
+            config.memory.importance_threshold = n.clamp(0.0, 1.0);
+        }
+
+        let memory = &self.memory;
+        if let Some(enabled) = memory.auto_compress {
+            config.memory.auto_compress = enabled;
+        }
+        if let Some(n) = memory.compression_age_days {
+            config.memory.compression_age_days = n;
+        }
+        if let Some(n) = memory.importance_threshold {
+            config.memory.importance_threshold = n.clamp(0.0, 1.0);
+        }
+
+        for rate_limit in [&self.api.rate_limit, &self.rate_limit] {
+            if let Some(n) = rate_limit.requests_per_second {
+                config.rate_limit_per_second = n;
+            }
+            if let Some(n) = rate_limit.burst_size {
+                config.rate_limit_burst = n;
+            }
+        }
+
+        let cors = &self.cors;
+        if let Some(origins) = &cors.allowed_origins {
+            config.cors.allowed_origins = origins.clone();
+        }
+        if let Some(methods) = &cors.allowed_methods {
+            config.cors.allowed_methods = methods.iter().map(|m| m.to_uppercase()).collect();
+        }
+        if let Some(headers) = &cors.allowed_headers {
+            config.cors.allowed_headers = headers.clone();
+        }
+        if let Some(allow) = cors.allow_credentials {
+            config.cors.allow_credentials = allow;
+        }
+        if let Some(n) = cors.max_age_seconds {
+            config.cors.max_age_seconds = n;
+        }
+
+        if let Some(enabled) = self.audit.enabled {
+            config.audit_enabled = enabled;
+        }
+        if let Some(n) = self.audit.max_entries_per_user {
+            config.audit_max_entries_per_user = n;
+        }
+        if let Some(n) = self.audit.rotation_check_interval {
+            config.audit_rotation_check_interval = n;
+        }
+        if let Some(n) = self.audit.retention_days {
+            config.audit_retention_days = n;
+        }
+
+        if let Some(n) = self.sessions.retention_days {
+            config.session_retention_days = n;
+        }
+        if let Some(n) = self.sessions.max_per_user {
+            config.session_max_per_user = n;
+        }
+
+        if let Some(n) = self.maintenance.interval_secs {
+            config.maintenance_interval_secs = n;
+        }
+        if let Some(n) = self.maintenance.activation_decay_factor {
+            config.activation_decay_factor = n.clamp(0.5, 0.99);
+        }
+        if let Some(n) = self.maintenance.max_entities_per_memory {
+            config.max_entities_per_memory = n.clamp(1, 50);
+        }
+        if let Some(n) = self.maintenance.entity_merge_confidence {
+            config.entity_merge_confidence = n.clamp(0.5, 1.0);
+        }
+        if let Some(n) = self.maintenance.graph_analytics_interval_secs {
+            config.graph_analytics_interval_secs = n;
+        }
+
+        if let Some(enabled) = self.backup.enabled {
+            config.backup_enabled = enabled;
+        }
+        if let Some(n) = self.backup.interval_secs {
+            config.backup_interval_secs = n;
+        }
+        if let Some(n) = self.backup.max_count {
+            config.backup_max_count = n;
+        }
+
+        config.embeddings = self.embeddings.clone();
+        config.vector_index = self.vector_index.clone();
+    }
+
+    /// Dotted paths of settings that are accepted for compatibility but do nothing
+    pub fn inert_keys(&self) -> Vec<&'static str> {
+        [
+            (
+                "memory.enable_graph_memory",
+                self.memory.enable_graph_memory.is_some(),
+            ),
+            (
+                "memory.enable_visualization",
+                self.memory.enable_visualization.is_some(),
+            ),
+            (
+                "vector_index.use_pq_compression",
+                self.vector_index.use_pq_compression == Some(true),
+            ),
+            ("deployment.target", self.deployment.target.is_some()),
+            (
+                "deployment.optimize_for",
+                self.deployment.optimize_for.is_some(),
+            ),
+        ]
+        .into_iter()
+        .filter_map(|(key, set)| set.then_some(key))
+        .collect()
+    }
+
+    /// Export `[embeddings]`, `[vector_index]` and `[api]` settings as env vars,
+    /// without overriding ones already set
+    ///
+    /// The embedder, vector index and auth layer read these from the
+    /// environment. Must be called before any threads are spawned (same rule
+    /// as the CLI flag export in `main`).
+    pub fn export_env(&self) {
+        let path = |p: &PathBuf| p.to_string_lossy().to_string();
+        let embeddings = &self.embeddings;
+        let is_onnx = embeddings
+            .provider
+            .as_deref()
+            .is_some_and(|p| p.eq_ignore_ascii_case("onnx"));
+        // MiniLM takes a model directory; a model file path points into it
+        let model_dir = embeddings.model_dir.clone().or_else(|| {
+            embeddings
+                .model_path
+                .as_ref()
+                .filter(|_| !is_onnx)
+                .and_then(|p| p.parent().map(Path::to_path_buf))
+        });
+        let vector_index = &self.vector_index;
+
+        let exports = [
+            ("SHODH_EMBEDDING_BACKEND", embeddings.provider.clone()),
+            ("SHODH_MODEL_PATH", model_dir.as_ref().map(path)),
+            (
+                "SHODH_EMBEDDING_MODEL_PATH",
+                embeddings.model_path.as_ref().filter(|_| is_onnx).map(path),
+            ),
+            (
+                "SHODH_EMBEDDING_TOKENIZER_PATH",
+                embeddings.tokenizer_path.as_ref().map(path),
+            ),
+            (
+                "SHODH_EMBEDDING_DIMENSION",
+                embeddings.dimension.map(|n| n.to_string()),
+            ),
+            (
+                "SHODH_EMBEDDING_MAX_LENGTH",
+                embeddings.max_length.map(|n| n.to_string()),
+            ),
+            (
+                "SHODH_USE_QUANTIZED_MODEL",
+                embeddings.use_quantized.map(|b| b.to_string()),
+            ),
+            (
+                "SHODH_EMBEDDING_FALLBACK",
+                embeddings.fallback_to_simplified.map(|b| b.to_string()),
+            ),
+            (
+                "SHODH_EMBED_TIMEOUT_MS",
+                embeddings.timeout_ms.map(|n| n.to_string()),
+            ),
+            (
+                "SHODH_VECTOR_MAX_DEGREE",
+                vector_index.max_degree.map(|n| n.to_string()),
+            ),
+            (
+                "SHODH_VECTOR_SEARCH_LIST_SIZE",
+                vector_index.search_list_size.map(|n| n.to_string()),
+            ),
+            (
+                "SHODH_VECTOR_ALPHA",
+                vector_index.alpha.map(|n| n.to_string()),
+            ),
+            (
+                "SHODH_VECTOR_USE_MMAP",
+                vector_index.use_mmap.map(|b| b.to_string()),
+            ),
+            (
+                "SHODH_VECTOR_SAVE_ON_SHUTDOWN",
+                vector_index.save_on_shutdown.map(|b| b.to_string()),
+            ),
+            (
+                "SHODH_VECTOR_REBUILD_ON_STARTUP",
+                vector_index.rebuild_on_startup.map(|b| b.to_string()),
+            ),
+            ("SHODH_DEV_API_KEY", self.api.default_api_key.clone()),
+        ];
+        for (var, value) in exports {
+            if let Some(value) = value {
+                if env::var(var).is_err() {
+                    env::set_var(var, value);
+                }
+            }
+        }
+    }
+}
+
+/// Resolve which config file to use: an explicit path, or the first default that exists
+pub fn find_config_file(explicit: Option<&Path>) -> Option<PathBuf> {
+    if let Some(path) = explicit {
+        return Some(path.to_path_buf());
+    }
+    DEFAULT_CONFIG_FILES
+        .iter()
+        .map(PathBuf::from)
+        .find(|path| path.is_file())
+}
+
+// =============================================================================
+// Hot reload
+// =============================================================================
+
+/// Watch `path` and publish hot-reloadable changes on `tx`
+///
+/// Reloads on SIGHUP (Unix) and whenever the file's modification time
+/// changes. A file that fails to parse or validate is logged and the current
+/// settings are kept.
+pub fn spawn_config_watcher(path: PathBuf, tx: watch::Sender<ServerConfig>) {
+    info!(
+        "Config hot reload enabled for {} (SIGHUP or file change)",
+        path.display()
+    );
+
+    tokio::spawn(async move {
+        let mut last_modified = modified_time(&path);
+        let mut poll = tokio::time::interval(Duration::from_secs(CONFIG_POLL_INTERVAL_SECS));
+
+        #[cfg(unix)]
+        let mut hangup = match tokio::signal::unix::signal(tokio::signal::unix::SignalKind::hangup())
+        {
+            Ok(signal) => Some(signal),
+            Err(e) => {
+                warn!(
+                    "Failed to install SIGHUP handler, using file polling only: {}",
+                    e
+                );
+                None
+            }
+        };
+
+        loop {
+            #[cfg(unix)]
+            let sighup = async {
+                match hangup.as_mut() {
+                    Some(signal) => {
+                        signal.recv().await;
+                    }
+                    None => std::future::pending::<()>().await,
+                }
+            };
+            #[cfg(not(unix))]
+            let sighup = std::future::pending::<()>();
+
+            let trigger = tokio::select! {
+                _ = poll.tick() => {
+                    let modified = modified_time(&path);
+                    if modified == last_modified {
+                        continue;
+                    }
+                    last_modified = modified;
+                    "file change"
+                }
+                _ = sighup => "SIGHUP",
+            };
+
+            reload_config(&path, &tx, trigger);
+        }
+    });
+}
+
+fn modified_time(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+fn reload_config(path: &Path, tx: &watch::Sender<ServerConfig>, trigger: &str) {
+    let next = match ServerConfig::load(Some(path)) {
+        Ok(next) => next,
+        Err(e) => {
+            warn!(
+                "Config reload ({}) failed, keeping current settings: {:#}",
+                trigger, e
+            );
+            return;
+        }
+    };
+
+    let (merged, changed) = tx.borrow().merge_reloadable(&next);
+    if changed.is_empty() {
+        info!("Config reloaded ({}): no hot-reloadable changes", trigger);
+        return;
+    }
+
+    info!(
+        "Config reloaded ({}): updated {}",
+        trigger,
+        changed.join(", ")
+    );
+    tx.send_replace(merged);
+}
+
 /// Environment variable documentation
 #[allow(unused)] // Public API - available for CLI help output
 pub fn print_env_help() {
@@ -470,6 +1384,7 @@ pub fn print_env_help() {
     println!(
         "  SHODH_HOST             - Bind address (default: 127.0.0.1, use 0.0.0.0 for Docker)"
     );
+    println!("  SHODH_CONFIG           - Config file path (default: ./shodh_config.toml or ./shodh_config.json)");
     println!("  SHODH_PORT             - Server port (default: 3030)");
     println!("  SHODH_MEMORY_PATH      - Storage directory (default: ./shodh_memory_data)");
     println!("  SHODH_API_KEYS         - Comma-separated API keys (required in production)");
@@ -481,14 +1396,19 @@ pub fn print_env_help() {
     println!("  SHODH_REQUEST_TIMEOUT  - Request timeout in seconds (default: 60)");
     println!("  SHODH_AUDIT_MAX_ENTRIES    - Max audit entries per user (default: 10000)");
     println!("  SHODH_AUDIT_RETENTION_DAYS - Audit log retention days (default: 30)");
+    println!("  SHODH_AUDIT_ENABLED        - Write memory operations to the audit log true/false (default: true)");
+    println!("  SHODH_SESSION_RETENTION_DAYS - Session history retention days, 0 = forever (default: 365)");
+    println!("  SHODH_SESSION_MAX_PER_USER   - Max stored sessions per user, 0 = unlimited (default: 1000)");
     println!();
     println!("Integration APIs:");
     println!("  LINEAR_API_URL         - Linear GraphQL API URL (default: https://api.linear.app/graphql)");
     println!("  LINEAR_WEBHOOK_SECRET  - Linear webhook signing secret for HMAC verification");
     println!("  GITHUB_API_URL         - GitHub REST API URL (default: https://api.github.com)");
     println!("  GITHUB_WEBHOOK_SECRET  - GitHub webhook secret for HMAC verification");
+    println!("  SHODH_WEBHOOK_ALLOWED_HOSTS - Comma-separated internal hosts outbound webhooks may target (default: none)");
     println!();
     println!("CORS Configuration:");
+    println!("  SHODH_CORS_ENABLED     - Send CORS headers true/false (default: true)");
     println!("  SHODH_CORS_ORIGINS     - Comma-separated allowed origins (default: all)");
     println!("  SHODH_CORS_METHODS     - Comma-separated allowed methods (default: GET,POST,PUT,DELETE,OPTIONS)");
     println!("  SHODH_CORS_HEADERS     - Comma-separated allowed headers (default: Content-Type,Authorization,X-Request-ID)");
@@ -553,6 +1473,199 @@ mod tests {
         let _layer = cors.to_layer(); // Should not panic
     }
 
+    #[test]
+    fn test_config_file_layers_over_defaults() {
+        let toml = r#"
+            [maintenance]
+            interval_secs = 120
+
+            [backup]
+            max_count = 3
+
+            [cors]
+            allowed_origins = ["https://app.example.com"]
+            allowed_methods = ["get", "post"]
+        "#;
+        let (file, unknown) = ConfigFile::parse(toml, true).unwrap();
+        assert!(unknown.is_empty());
+
+        let config = ServerConfig::from_layers(&file);
+        assert_eq!(config.maintenance_interval_secs, 120);
+        assert_eq!(config.backup_max_count, 3);
+        assert_eq!(config.cors.allowed_origins, vec!["https://app.example.com"]);
+        assert_eq!(config.cors.allowed_methods, vec!["GET", "POST"]);
+        // Untouched sections keep their defaults
+        assert_eq!(config.audit_rotation_check_interval, 100);
+    }
+
+    #[test]
+    fn test_config_file_reports_unknown_keys() {
+        let json = r#"{
+            "server": { "prot": 8080 },
+            "audit": { "retention_days": 14 },
+            "logging": { "level": "debug" },
+            "deployment": { "target": "robotics" }
+        }"#;
+        let (file, mut unknown) = ConfigFile::parse(json, false).unwrap();
+        unknown.sort();
+        assert_eq!(unknown, vec!["logging", "server.prot"]);
+        assert_eq!(file.audit.retention_days, Some(14));
+        assert_eq!(file.inert_keys(), vec!["deployment.target"]);
+    }
+
+    #[test]
+    fn test_example_config_loads_every_section() {
+        let example = include_str!("../shodh_config.example.json");
+        let (file, unknown) = ConfigFile::parse(example, false).unwrap();
+        assert!(unknown.is_empty(), "unknown keys in example: {unknown:?}");
+
+        assert_eq!(file.embeddings.provider.as_deref(), Some("minilm"));
+        assert_eq!(file.embeddings.dimension, Some(384));
+        assert_eq!(file.embeddings.fallback_to_simplified, Some(true));
+        assert_eq!(file.vector_index.index_type.as_deref(), Some("vamana_hnsw"));
+        assert_eq!(file.vector_index.max_degree, Some(24));
+        assert_eq!(file.vector_index.rebuild_on_startup, Some(false));
+
+        let config = ServerConfig::from_layers(&file);
+        assert!(config.validate().is_ok());
+        assert_eq!(config.storage_path, PathBuf::from("./shodh_memory_data"));
+        assert_eq!(config.memory.compression_age_days, 7);
+        assert_eq!(config.vector_index.search_list_size, Some(50));
+        // `[rate_limit]` wins over the legacy `[api.rate_limit]`
+        assert_eq!(config.rate_limit_per_second, 4000);
+        // `[server] production` wins over `[deployment] environment`
+        assert!(!config.is_production);
+    }
+
+    #[test]
+    fn test_legacy_sections_fill_in_settings() {
+        let toml = r#"
+            [server]
+            cors_enabled = false
+
+            [storage.compression]
+            enabled = false
+            age_days = 30
+
+            [storage.retention]
+            working_memory_size = 50
+
+            [memory]
+            importance_threshold = 0.4
+
+            [api.rate_limit]
+            requests_per_second = 2
+            burst_size = 10
+
+            [audit]
+            enabled = false
+
+            [deployment]
+            environment = "prod"
+        "#;
+        let (file, unknown) = ConfigFile::parse(toml, true).unwrap();
+        assert!(unknown.is_empty());
+
+        let config = ServerConfig::from_layers(&file);
+        assert!(!config.cors.enabled);
+        assert!(config.cors.is_restricted());
+        assert!(!config.memory.auto_compress);
+        assert_eq!(config.memory.compression_age_days, 30);
+        assert_eq!(config.memory.working_memory_size, 50);
+        assert_eq!(config.memory.importance_threshold, 0.4);
+        assert_eq!(config.rate_limit_per_second, 2);
+        assert_eq!(config.rate_limit_burst, 10);
+        assert!(!config.audit_enabled);
+        assert!(config.is_production);
+    }
+
+    #[test]
+    fn test_validate_rejects_unknown_vector_index_type() {
+        let (file, _) = ConfigFile::parse("[vector_index]\ntype = \"faiss\"", true).unwrap();
+        assert!(ServerConfig::from_layers(&file).validate().is_err());
+
+        let (file, _) = ConfigFile::parse("[vector_index]\nmax_degree = 0", true).unwrap();
+        assert!(ServerConfig::from_layers(&file).validate().is_err());
+    }
+
+    #[test]
+    fn test_config_file_rejects_wrong_types() {
+        assert!(ConfigFile::parse("[server]\nport = \"high\"", true).is_err());
+        assert!(ConfigFile::parse(r#"{"backup": {"enabled": "yes"}}"#, false).is_err());
+    }
+
+    #[test]
+    fn test_env_overrides_config_file() {
+        env::set_var("SHODH_AUDIT_RETENTION_DAYS", "90");
+
+        let (file, _) = ConfigFile::parse("[audit]\nretention_days = 10", true).unwrap();
+        let config = ServerConfig::from_layers(&file);
+        assert_eq!(config.audit_retention_days, 90);
+
+        env::remove_var("SHODH_AUDIT_RETENTION_DAYS");
+    }
+
+    #[test]
+    fn test_validate_rejects_unusable_values() {
+        assert!(ServerConfig::default().validate().is_ok());
+
+        let config = ServerConfig {
+            maintenance_interval_secs: 0,
+            ..Default::default()
+        };
+        assert!(config.validate().is_err());
+
+        let config = ServerConfig {
+            rate_limit_per_second: 10,
+            rate_limit_burst: 0,
+            ..Default::default()
+        };
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_merge_reloadable_only_copies_hot_settings() {
+        let current = ServerConfig::default();
+        let next = ServerConfig {
+            port: 9999,
+            rate_limit_per_second: 10,
+            backup_enabled: true,
+            cors: CorsConfig {
+                allowed_origins: vec!["https://example.com".to_string()],
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        let (merged, changed) = current.merge_reloadable(&next);
+        assert_eq!(changed, vec!["rate_limit", "cors", "backup"]);
+        assert_eq!(merged.rate_limit_per_second, 10);
+        assert!(merged.backup_enabled);
+        assert!(merged.cors.is_restricted());
+        // Port needs a restart, so the running value is kept
+        assert_eq!(merged.port, current.port);
+
+        let (_, changed) = merged.merge_reloadable(&next);
+        assert!(changed.is_empty());
+    }
+
+    #[test]
+    fn test_merge_reloadable_keeps_model_and_index_settings() {
+        let current = ServerConfig::default();
+        let (file, _) = ConfigFile::parse(
+            "[embeddings]\nprovider = \"onnx\"\n\n[vector_index]\nmax_degree = 48",
+            true,
+        )
+        .unwrap();
+        let next = ServerConfig::from_layers(&file);
+
+        // Embedder and index settings are only read at startup
+        let (merged, changed) = current.merge_reloadable(&next);
+        assert!(changed.is_empty());
+        assert_eq!(merged.embeddings, current.embeddings);
+        assert_eq!(merged.vector_index, current.vector_index);
+    }
+
     #[test]
     fn test_cors_to_layer_restricted() {
         let cors = CorsConfig {
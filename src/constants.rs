@@ -115,6 +115,29 @@ pub const EDGE_HALF_LIFE_HOURS: f64 = 24.0;
 /// Reference: Reimers & Gurevych (2019) "Sentence-BERT"
 pub const ENTITY_CONCEPT_MERGE_THRESHOLD: f32 = 0.85;
 
+/// Cosine similarity below which two entity names are never considered
+/// aliases of each other by the entity resolver.
+///
+/// Justification:
+/// - Sits between unrelated pairs (< 0.60) and synonyms (0.82+) for MiniLM-L6-v2
+/// - Lets abbreviations like "pg" ↔ "Postgres" (~0.75) through as candidates,
+///   which then need shared neighbours to reach the merge confidence
+pub const ENTITY_RESOLVER_CANDIDATE_SIMILARITY: f32 = 0.70;
+
+/// Weight of shared-neighbour overlap (Jaccard) in entity resolver confidence.
+///
+/// Two names that co-occur with the same other entities ("Postgres" and
+/// "PostgreSQL" both next to "migrations", "pgvector") are likely the same
+/// thing. At 0.15, a full overlap lifts a 0.72 similarity over the 0.85 bar.
+pub const ENTITY_RESOLVER_COOCCURRENCE_WEIGHT: f32 = 0.15;
+
+/// Penalty on entity resolver confidence when both names appear in the
+/// same episode.
+///
+/// A memory that mentions "Postgres" and "MySQL" together is evidence that
+/// they are different things, however similar their embeddings are.
+pub const ENTITY_RESOLVER_COMENTION_PENALTY: f32 = 0.20;
+
 /// Floor multiplier for semantic edge weighting.
 ///
 /// Initial edge weight = L1_INITIAL_WEIGHT × (floor + (1 − floor) × cosine_sim).
@@ -1711,6 +1734,117 @@ pub const TIER_RETRIEVAL_SUCCESS_BOOST: f32 = 0.25;
 /// Edges above 0.8 weight are considered "potentiated" and decay even slower.
 pub const TIER_LTP_THRESHOLD: f32 = 0.8;
 
+// =============================================================================
+// GRAPH QUERY LANGUAGE LIMITS
+// Bounds on the Cypher-like MATCH queries served by /api/graph/query
+// =============================================================================
+
+/// Rows returned when a graph query has no LIMIT
+pub const GRAPH_QUERY_DEFAULT_LIMIT: usize = 100;
+
+/// Largest LIMIT a graph query may ask for
+///
+/// Also caps the rows collected before ORDER BY / DISTINCT are applied.
+pub const GRAPH_QUERY_MAX_ROWS: usize = 1000;
+
+/// Upper bound on hops in a variable-length relationship (`*1..n`)
+///
+/// Justification:
+/// - Spreading activation already stops at 3-4 hops; paths longer than 6
+///   connect almost everything to everything in a co-occurrence graph
+pub const GRAPH_QUERY_MAX_HOPS: usize = 6;
+
+/// Strongest edges considered per node during expansion
+///
+/// Matches the per-node cap of `GraphMemory::match_pattern`, so hub entities
+/// with thousands of weak L1 edges cannot blow up a query.
+pub const GRAPH_QUERY_EDGES_PER_NODE: usize = 100;
+
+/// Edge visits after which a graph query stops and reports `truncated`
+pub const GRAPH_QUERY_MAX_EXPANSIONS: usize = 100_000;
+
+// =============================================================================
+// GRAPH ANALYTICS
+// Centrality, communities and bridge entities computed during maintenance
+// =============================================================================
+
+/// PageRank damping factor (probability of following an edge vs. jumping)
+///
+/// Justification:
+/// - 0.85 is the standard value from Brin & Page (1998); lower values flatten
+///   scores toward uniform, higher values converge slowly on sparse graphs
+pub const GRAPH_PAGERANK_DAMPING: f64 = 0.85;
+
+/// PageRank power iterations before giving up on convergence
+pub const GRAPH_PAGERANK_MAX_ITERATIONS: usize = 100;
+
+/// PageRank stops once the L1 change between iterations drops below this
+pub const GRAPH_PAGERANK_TOLERANCE: f64 = 1e-6;
+
+/// Louvain aggregation levels (each level merges communities into super-nodes)
+pub const GRAPH_LOUVAIN_MAX_LEVELS: usize = 10;
+
+/// Local-moving passes over all nodes per Louvain level
+pub const GRAPH_LOUVAIN_MAX_PASSES: usize = 20;
+
+/// Smallest community reported as a topic cluster
+pub const GRAPH_COMMUNITY_MIN_SIZE: usize = 2;
+
+/// Participation coefficient at which an entity counts as a bridge
+///
+/// The coefficient is 1 - Σ(k_c / k)², where k_c is the edge weight to
+/// community c. 0.0 means all edges stay inside one community; an entity
+/// split evenly between two communities scores 0.5.
+pub const GRAPH_BRIDGE_MIN_PARTICIPATION: f32 = 0.3;
+
+/// Entities listed per ranking (central entities, bridges, cluster members)
+pub const GRAPH_ANALYTICS_TOP_K: usize = 20;
+
+/// Share of an entity's structural salience that comes from centrality
+///
+/// Structural salience = (1 - w) × salience + w × centrality once analytics
+/// have run. Spreading activation seeds and the memory universe use it, so
+/// hub entities gain weight without drowning out mention-based salience.
+pub const GRAPH_CENTRALITY_SALIENCE_WEIGHT: f32 = 0.3;
+
+// =============================================================================
+// LINEAGE POST-MORTEMS
+// Post-mortems generated when a todo or project is finished
+// =============================================================================
+
+/// Lineage hops followed in each direction from a post-mortem's seed memories
+pub const POSTMORTEM_TRACE_DEPTH: usize = 5;
+
+/// Memories looked up by tag (`todo:BOLT-1`, `project:bolt`) as trace seeds
+pub const POSTMORTEM_MAX_TAGGED_SEEDS: usize = 100;
+
+/// Memories summarized in one post-mortem
+///
+/// Bounds both the trace and the `ResolvedBy` edges written for it, so a
+/// long-running project cannot produce an unbounded markdown document.
+pub const POSTMORTEM_MAX_MEMORIES: usize = 200;
+
+// =============================================================================
+// LOCAL-FRAME SPATIAL INDEX
+// Voxel index over `local_position`, keyed by (robot_id, map frame)
+// =============================================================================
+
+/// Edge length of one voxel cell in the local-frame index (meters)
+///
+/// Matches the ~1.2m geohash cells used for `geo_location`: one cell holds a
+/// shelf or doorway, so waypoint-radius queries touch only a handful of cells.
+pub const LOCAL_SPATIAL_CELL_SIZE_METERS: f32 = 1.0;
+
+/// Cells probed one by one before a query scans the whole frame instead
+///
+/// Large radii and boxes cover more empty cells than stored points; past this
+/// bound a single prefix scan over the frame is cheaper than per-cell seeks.
+/// Also caps how far k-nearest searches grow their shell of cells.
+pub const LOCAL_SPATIAL_MAX_CELL_PROBES: usize = 4096;
+
+/// Heading cone half-angle used when a query gives a heading but no tolerance
+pub const DEFAULT_HEADING_TOLERANCE_DEGREES: f32 = 45.0;
+
 // =============================================================================
 // CONSTANTS USAGE DOCUMENTATION
 // =============================================================================
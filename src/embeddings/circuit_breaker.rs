@@ -22,7 +22,7 @@ use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 
-use super::{minilm::MiniLMEmbedder, Embedder};
+use super::Embedder;
 
 /// Circuit breaker states
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -90,15 +90,13 @@ impl CircuitBreakerState {
     }
 }
 
-/// Circuit breaker wrapper for embedding service
+/// Circuit breaker state machine shared by ONNX-backed services
 ///
-/// Provides resilience by:
-/// 1. Tracking failure rates
-/// 2. Opening circuit when failures exceed threshold
-/// 3. Automatically testing recovery after cooldown
-/// 4. Falling back to simplified embeddings when circuit is open
-pub struct ResilientEmbedder {
-    inner: Arc<MiniLMEmbedder>,
+/// Only decides whether a call should be attempted and tracks the outcome;
+/// the owning service supplies its own fallback when a call is rejected.
+pub struct CircuitBreaker {
+    /// Service label recorded on state-change metrics (e.g. "embedding")
+    service: &'static str,
     config: CircuitBreakerConfig,
     state: Mutex<CircuitBreakerState>,
     // Atomic counters for metrics (lock-free)
@@ -107,11 +105,11 @@ pub struct ResilientEmbedder {
     total_fallbacks: AtomicU64,
 }
 
-impl ResilientEmbedder {
-    /// Create a new resilient embedder wrapping the given MiniLM embedder
-    pub fn new(embedder: Arc<MiniLMEmbedder>, config: CircuitBreakerConfig) -> Self {
+impl CircuitBreaker {
+    /// Create a closed circuit breaker for the named service
+    pub fn new(service: &'static str, config: CircuitBreakerConfig) -> Self {
         Self {
-            inner: embedder,
+            service,
             config,
             state: Mutex::new(CircuitBreakerState::new()),
             total_calls: AtomicU64::new(0),
@@ -120,11 +118,6 @@ impl ResilientEmbedder {
         }
     }
 
-    /// Create with default configuration
-    pub fn with_defaults(embedder: Arc<MiniLMEmbedder>) -> Self {
-        Self::new(embedder, CircuitBreakerConfig::default())
-    }
-
     /// Get current circuit state
     pub fn state(&self) -> CircuitState {
         self.state.lock().state
@@ -144,8 +137,23 @@ impl ResilientEmbedder {
         }
     }
 
+    /// Count calls made through the breaker
+    pub fn record_calls(&self, count: u64) {
+        self.total_calls.fetch_add(count, Ordering::Relaxed);
+    }
+
+    /// Count calls rejected because the circuit was open
+    pub fn record_rejections(&self, count: u64) {
+        self.total_rejections.fetch_add(count, Ordering::Relaxed);
+    }
+
+    /// Count results served by the fallback path
+    pub fn record_fallbacks(&self, count: u64) {
+        self.total_fallbacks.fetch_add(count, Ordering::Relaxed);
+    }
+
     /// Check if circuit allows requests and update state if needed
-    fn should_allow_request(&self) -> bool {
+    pub fn allow_request(&self) -> bool {
         let mut state = self.state.lock();
 
         match state.state {
@@ -154,7 +162,8 @@ impl ResilientEmbedder {
                 // Check if enough time has passed to try recovery
                 if state.last_state_change.elapsed() >= self.config.open_duration {
                     tracing::info!(
-                        "Circuit breaker transitioning from Open to HalfOpen after {:?}",
+                        "Circuit breaker ({}) transitioning from Open to HalfOpen after {:?}",
+                        self.service,
                         self.config.open_duration
                     );
                     state.state = CircuitState::HalfOpen;
@@ -171,7 +180,7 @@ impl ResilientEmbedder {
     }
 
     /// Record a successful operation
-    fn record_success(&self) {
+    pub fn record_success(&self) {
         let mut state = self.state.lock();
         state.consecutive_failures = 0;
         state.consecutive_successes += 1;
@@ -180,7 +189,8 @@ impl ResilientEmbedder {
             && state.consecutive_successes >= self.config.success_threshold
         {
             tracing::info!(
-                "Circuit breaker closing after {} consecutive successes",
+                "Circuit breaker ({}) closing after {} consecutive successes",
+                self.service,
                 state.consecutive_successes
             );
             state.state = CircuitState::Closed;
@@ -190,7 +200,7 @@ impl ResilientEmbedder {
     }
 
     /// Record a failed operation
-    fn record_failure(&self) {
+    pub fn record_failure(&self) {
         let mut state = self.state.lock();
         state.consecutive_successes = 0;
         state.consecutive_failures += 1;
@@ -200,7 +210,8 @@ impl ResilientEmbedder {
             CircuitState::Closed => {
                 if state.consecutive_failures >= self.config.failure_threshold {
                     tracing::warn!(
-                        "Circuit breaker opening after {} consecutive failures",
+                        "Circuit breaker ({}) opening after {} consecutive failures",
+                        self.service,
                         state.consecutive_failures
                     );
                     state.state = CircuitState::Open;
@@ -210,7 +221,10 @@ impl ResilientEmbedder {
             }
             CircuitState::HalfOpen => {
                 // Single failure in half-open returns to open
-                tracing::warn!("Circuit breaker returning to Open after failure in HalfOpen state");
+                tracing::warn!(
+                    "Circuit breaker ({}) returning to Open after failure in HalfOpen state",
+                    self.service
+                );
                 state.state = CircuitState::Open;
                 state.last_state_change = Instant::now();
                 self.record_state_change(CircuitState::Open);
@@ -224,15 +238,52 @@ impl ResilientEmbedder {
     /// Record state change to metrics
     fn record_state_change(&self, new_state: CircuitState) {
         let label1 = format!("circuit_breaker_{new_state}");
-        let label2 = String::from("embedding");
+        let label2 = String::from(self.service);
         crate::metrics::ERRORS_TOTAL
             .with_label_values(&[&label1, &label2])
             .inc();
     }
+}
+
+/// Circuit breaker wrapper for embedding service
+///
+/// Provides resilience by:
+/// 1. Tracking failure rates
+/// 2. Opening circuit when failures exceed threshold
+/// 3. Automatically testing recovery after cooldown
+/// 4. Falling back to simplified embeddings when circuit is open
+pub struct ResilientEmbedder {
+    inner: Arc<dyn Embedder>,
+    breaker: CircuitBreaker,
+}
+
+impl ResilientEmbedder {
+    /// Create a new resilient embedder wrapping the given embedder
+    pub fn new(embedder: Arc<dyn Embedder>, config: CircuitBreakerConfig) -> Self {
+        Self {
+            inner: embedder,
+            breaker: CircuitBreaker::new("embedding", config),
+        }
+    }
+
+    /// Create with default configuration
+    pub fn with_defaults(embedder: Arc<dyn Embedder>) -> Self {
+        Self::new(embedder, CircuitBreakerConfig::default())
+    }
+
+    /// Get current circuit state
+    pub fn state(&self) -> CircuitState {
+        self.breaker.state()
+    }
+
+    /// Get metrics for monitoring
+    pub fn metrics(&self) -> CircuitBreakerMetrics {
+        self.breaker.metrics()
+    }
 
     /// Generate fallback embedding using simplified hash-based approach
     fn generate_fallback(&self, text: &str) -> Vec<f32> {
-        self.total_fallbacks.fetch_add(1, Ordering::Relaxed);
+        self.breaker.record_fallbacks(1);
 
         // Use the same hash-based approach as simplified mode
         use std::collections::hash_map::DefaultHasher;
@@ -272,15 +323,15 @@ impl ResilientEmbedder {
 
 impl Embedder for ResilientEmbedder {
     fn encode(&self, text: &str) -> Result<Vec<f32>> {
-        self.total_calls.fetch_add(1, Ordering::Relaxed);
+        self.breaker.record_calls(1);
 
         if text.is_empty() {
             return Ok(vec![0.0; self.inner.dimension()]);
         }
 
         // Check circuit state
-        if !self.should_allow_request() {
-            self.total_rejections.fetch_add(1, Ordering::Relaxed);
+        if !self.breaker.allow_request() {
+            self.breaker.record_rejections(1);
             tracing::debug!("Circuit breaker open, using fallback embedding");
             return Ok(self.generate_fallback(text));
         }
@@ -288,11 +339,11 @@ impl Embedder for ResilientEmbedder {
         // Try the actual embedding
         match self.inner.encode(text) {
             Ok(embedding) => {
-                self.record_success();
+                self.breaker.record_success();
                 Ok(embedding)
             }
             Err(e) => {
-                self.record_failure();
+                self.breaker.record_failure();
                 tracing::warn!("Embedding failed (circuit breaker tracking): {}", e);
                 // Return fallback instead of error to maintain availability
                 Ok(self.generate_fallback(text))
@@ -304,11 +355,14 @@ impl Embedder for ResilientEmbedder {
         self.inner.dimension()
     }
 
+    fn model_id(&self) -> String {
+        self.inner.model_id()
+    }
+
     fn encode_batch(&self, texts: &[&str]) -> Result<Vec<Vec<f32>>> {
         // For batch operations, check circuit once and apply consistently
-        if !self.should_allow_request() {
-            self.total_rejections
-                .fetch_add(texts.len() as u64, Ordering::Relaxed);
+        if !self.breaker.allow_request() {
+            self.breaker.record_rejections(texts.len() as u64);
             return Ok(texts.iter().map(|t| self.generate_fallback(t)).collect());
         }
 
@@ -318,7 +372,7 @@ impl Embedder for ResilientEmbedder {
         let mut any_failure = false;
 
         for text in texts {
-            self.total_calls.fetch_add(1, Ordering::Relaxed);
+            self.breaker.record_calls(1);
             match self.inner.encode(text) {
                 Ok(embedding) => {
                     any_success = true;
@@ -333,9 +387,9 @@ impl Embedder for ResilientEmbedder {
 
         // Update circuit state based on batch results
         if any_failure && !any_success {
-            self.record_failure();
+            self.breaker.record_failure();
         } else if any_success {
-            self.record_success();
+            self.breaker.record_success();
         }
 
         Ok(results)
@@ -356,17 +410,13 @@ pub struct CircuitBreakerMetrics {
 
 #[cfg(test)]
 mod tests {
+    use super::super::minilm::{EmbeddingConfig, MiniLMEmbedder};
     use super::*;
     use std::path::PathBuf;
 
     fn create_test_embedder() -> Arc<MiniLMEmbedder> {
-        let config = super::super::minilm::EmbeddingConfig {
-            model_path: PathBuf::from("dummy.onnx"),
-            tokenizer_path: PathBuf::from("dummy.json"),
-            max_length: 256,
-            use_quantized: true,
-            embed_timeout_ms: 5000,
-        };
+        let config =
+            EmbeddingConfig::with_paths(PathBuf::from("dummy.onnx"), PathBuf::from("dummy.json"));
         Arc::new(MiniLMEmbedder::new_simplified(config).unwrap())
     }
 
@@ -0,0 +1,494 @@
+//! Cross-encoder relevance scoring using ONNX Runtime
+//!
+//! Scores (query, document) pairs jointly for hybrid search reranking:
+//! - Model: ms-marco-MiniLM-L6-v2 (ONNX exported, ~23MB quantized)
+//! - Output: one relevance logit per pair, squashed to 0.0-1.0
+//! - Latency: ~2-4ms per pair on CPU, batched to amortize session overhead
+//!
+//! Unlike the bi-encoder path (separate query/doc embeddings + cosine), the
+//! query and document attend to each other inside the model, which is what
+//! makes cross-encoders accurate rerankers.
+//!
+//! # Loading
+//! - `preload()` downloads (unless `SHODH_OFFLINE=true`) and loads the model;
+//!   the server runs it in the background at startup
+//! - `score()` never downloads: it loads files already on disk, or errors
+//!   while they are missing so callers can fall back
+//! - A failed load is not remembered; the next call tries again
+//!
+//! # Resilience
+//! Inference runs behind a `CircuitBreaker` (same policy as `ResilientEmbedder`):
+//! repeated failures open the circuit and calls are rejected until it recovers.
+
+use anyhow::{Context, Result};
+use ort::session::Session;
+use ort::value::Value;
+use parking_lot::Mutex;
+use std::path::PathBuf;
+use std::sync::{Arc, OnceLock};
+use tokenizers::{Tokenizer, TruncationParams, TruncationStrategy};
+
+use super::circuit_breaker::{
+    CircuitBreaker, CircuitBreakerConfig, CircuitBreakerMetrics, CircuitState,
+};
+
+/// Configuration for the cross-encoder model
+#[derive(Debug, Clone)]
+pub struct CrossEncoderConfig {
+    /// Path to ONNX model file
+    pub model_path: PathBuf,
+    /// Path to tokenizer file
+    pub tokenizer_path: PathBuf,
+    /// Maximum sequence length for the joint (query, document) input
+    pub max_length: usize,
+    /// Number of pairs scored per ONNX inference call
+    pub batch_size: usize,
+    /// Download the model on first use when it is not found locally
+    pub auto_download: bool,
+}
+
+impl Default for CrossEncoderConfig {
+    fn default() -> Self {
+        Self::from_env()
+    }
+}
+
+impl CrossEncoderConfig {
+    /// Create configuration from environment variables
+    ///
+    /// Search order for model files:
+    /// 1. SHODH_CROSS_ENCODER_PATH environment variable
+    /// 2. Bundled in Python package (SHODH_PACKAGE_DIR/models/ms-marco-minilm-l6)
+    /// 3. ./models/ms-marco-minilm-l6 and ../models/ms-marco-minilm-l6
+    /// 4. ~/.cache/shodh-memory/models/ms-marco-minilm-l6 (auto-download location)
+    pub fn from_env() -> Self {
+        let base_path = std::env::var("SHODH_CROSS_ENCODER_PATH")
+            .map(PathBuf::from)
+            .unwrap_or_else(|_| {
+                let candidates: Vec<Option<PathBuf>> = vec![
+                    std::env::var("SHODH_PACKAGE_DIR")
+                        .ok()
+                        .map(|p| PathBuf::from(p).join("models/ms-marco-minilm-l6")),
+                    Some(PathBuf::from("./models/ms-marco-minilm-l6")),
+                    Some(PathBuf::from("../models/ms-marco-minilm-l6")),
+                    Some(super::downloader::get_cross_encoder_models_dir()),
+                ];
+
+                candidates
+                    .into_iter()
+                    .flatten()
+                    .find(|p| p.join("model.onnx").exists())
+                    .unwrap_or_else(super::downloader::get_cross_encoder_models_dir)
+            });
+
+        let batch_size = std::env::var("SHODH_CROSS_ENCODER_BATCH")
+            .ok()
+            .and_then(|s| s.parse().ok())
+            .filter(|&n: &usize| n > 0)
+            .unwrap_or(16);
+
+        let offline_mode = std::env::var("SHODH_OFFLINE")
+            .map(|v| v == "true" || v == "1")
+            .unwrap_or(false);
+
+        Self {
+            model_path: base_path.join("model.onnx"),
+            tokenizer_path: base_path.join("tokenizer.json"),
+            // MS MARCO passages are short; 256 covers query + typical memory content
+            max_length: 256,
+            batch_size,
+            auto_download: !offline_mode,
+        }
+    }
+
+    /// Configuration pointing at explicit model files, without auto-download
+    pub fn with_paths(model_path: PathBuf, tokenizer_path: PathBuf) -> Self {
+        Self {
+            model_path,
+            tokenizer_path,
+            max_length: 256,
+            batch_size: 16,
+            auto_download: false,
+        }
+    }
+
+    fn files_exist(&self) -> bool {
+        self.model_path.exists() && self.tokenizer_path.exists()
+    }
+
+    /// This configuration, or the auto-download location once files are there
+    fn resolve(&self) -> Option<Self> {
+        if self.files_exist() {
+            return Some(self.clone());
+        }
+        if !self.auto_download {
+            return None;
+        }
+        let dir = super::downloader::get_cross_encoder_models_dir();
+        let downloaded = Self {
+            model_path: dir.join("model.onnx"),
+            tokenizer_path: dir.join("tokenizer.json"),
+            ..self.clone()
+        };
+        downloaded.files_exist().then_some(downloaded)
+    }
+}
+
+/// Loaded cross-encoder session and tokenizer
+struct LazyCrossEncoderModel {
+    session: Mutex<Session>,
+    tokenizer: Tokenizer,
+}
+
+impl LazyCrossEncoderModel {
+    fn new(config: &CrossEncoderConfig) -> Result<Self> {
+        // macOS ARM64: default to 1 thread to avoid Eigen thread pool
+        // spin-to-block deadlock on heterogeneous P/E cores.
+        #[cfg(all(target_os = "macos", target_arch = "aarch64"))]
+        let default_threads = 1;
+        #[cfg(not(all(target_os = "macos", target_arch = "aarch64")))]
+        let default_threads = 2;
+
+        let num_threads = std::env::var("SHODH_ONNX_THREADS")
+            .ok()
+            .and_then(|s| s.parse().ok())
+            .unwrap_or(default_threads);
+
+        tracing::info!(
+            "Loading cross-encoder model from {:?} with {} threads",
+            config.model_path,
+            num_threads
+        );
+
+        let builder = Session::builder()
+            .context("Failed to create cross-encoder session builder")?
+            .with_intra_threads(num_threads)
+            .context("Failed to set cross-encoder intra thread count")?
+            .with_inter_threads(1)
+            .context("Failed to set cross-encoder inter thread count")?;
+
+        // Disable thread pool spinning (see microsoft/onnxruntime#10270, pykeio/ort#516)
+        let builder = builder
+            .with_intra_op_spinning(false)
+            .context("Failed to disable cross-encoder intra-op spinning")?
+            .with_inter_op_spinning(false)
+            .context("Failed to disable cross-encoder inter-op spinning")?;
+
+        let session = builder
+            .commit_from_file(&config.model_path)
+            .context("Failed to load cross-encoder ONNX model")?;
+
+        let mut tokenizer = Tokenizer::from_file(&config.tokenizer_path)
+            .map_err(|e| anyhow::anyhow!("Failed to load cross-encoder tokenizer: {e}"))?;
+
+        // Truncate the longer side of the pair first so the query survives
+        tokenizer
+            .with_truncation(Some(TruncationParams {
+                max_length: config.max_length,
+                strategy: TruncationStrategy::LongestFirst,
+                ..Default::default()
+            }))
+            .map_err(|e| anyhow::anyhow!("Failed to configure cross-encoder truncation: {e}"))?;
+        tokenizer.with_padding(None);
+
+        tracing::info!("Cross-encoder model loaded successfully");
+
+        Ok(Self {
+            session: Mutex::new(session),
+            tokenizer,
+        })
+    }
+}
+
+/// Cross-encoder relevance model (ms-marco-MiniLM style)
+pub struct CrossEncoder {
+    config: CrossEncoderConfig,
+    lazy_model: OnceLock<Arc<LazyCrossEncoderModel>>,
+    /// Serializes load attempts; only successful loads fill `lazy_model`
+    load_lock: Mutex<()>,
+    breaker: CircuitBreaker,
+}
+
+impl CrossEncoder {
+    /// Create cross-encoder with lazy loading and default circuit breaker
+    pub fn new(config: CrossEncoderConfig) -> Self {
+        Self::with_circuit_breaker(config, CircuitBreakerConfig::default())
+    }
+
+    /// Create cross-encoder with an explicit circuit breaker policy
+    pub fn with_circuit_breaker(
+        config: CrossEncoderConfig,
+        breaker_config: CircuitBreakerConfig,
+    ) -> Self {
+        Self {
+            config,
+            lazy_model: OnceLock::new(),
+            load_lock: Mutex::new(()),
+            breaker: CircuitBreaker::new("cross_encoder", breaker_config),
+        }
+    }
+
+    /// Whether the model can be used: loaded, or its files are on disk
+    pub fn is_available(&self) -> bool {
+        self.lazy_model.get().is_some() || self.config.resolve().is_some()
+    }
+
+    /// Check if the ONNX model has been loaded
+    pub fn is_model_loaded(&self) -> bool {
+        self.lazy_model.get().is_some()
+    }
+
+    /// Get current circuit state
+    pub fn circuit_state(&self) -> CircuitState {
+        self.breaker.state()
+    }
+
+    /// Get circuit breaker metrics for monitoring
+    pub fn metrics(&self) -> CircuitBreakerMetrics {
+        self.breaker.metrics()
+    }
+
+    /// Record that the caller served results from its fallback path
+    pub fn record_fallback(&self) {
+        self.breaker.record_fallbacks(1);
+    }
+
+    /// Download the model if allowed and missing, then load it
+    ///
+    /// Blocking; meant for a background task so the first reranked recall
+    /// does not wait on the download.
+    pub fn preload(&self) -> Result<()> {
+        if self.lazy_model.get().is_some() {
+            return Ok(());
+        }
+        let config = match self.config.resolve() {
+            Some(config) => config,
+            None if self.config.auto_download => {
+                let dir = super::downloader::download_cross_encoder_models(None)?;
+                CrossEncoderConfig {
+                    model_path: dir.join("model.onnx"),
+                    tokenizer_path: dir.join("tokenizer.json"),
+                    ..self.config.clone()
+                }
+            }
+            None => anyhow::bail!(
+                "Cross-encoder model not found at {:?}",
+                self.config.model_path
+            ),
+        };
+        let _guard = self.load_lock.lock();
+        self.load(&config).map(|_| ())
+    }
+
+    /// Load the model from files already on disk
+    ///
+    /// Does not wait for a load running elsewhere: callers fall back instead.
+    fn ensure_model_loaded(&self) -> Result<&Arc<LazyCrossEncoderModel>> {
+        if let Some(model) = self.lazy_model.get() {
+            return Ok(model);
+        }
+        let config = self.config.resolve().ok_or_else(|| {
+            anyhow::anyhow!(
+                "Cross-encoder unavailable: model not found at {:?}",
+                self.config.model_path
+            )
+        })?;
+        let _guard = self
+            .load_lock
+            .try_lock()
+            .ok_or_else(|| anyhow::anyhow!("Cross-encoder unavailable: model is still loading"))?;
+        self.load(&config)
+    }
+
+    /// Load under `load_lock`, keeping the model only if loading succeeds
+    fn load(&self, config: &CrossEncoderConfig) -> Result<&Arc<LazyCrossEncoderModel>> {
+        if let Some(model) = self.lazy_model.get() {
+            return Ok(model);
+        }
+        let model = LazyCrossEncoderModel::new(config)
+            .map(Arc::new)
+            .context("Cross-encoder unavailable")?;
+        Ok(self.lazy_model.get_or_init(|| model))
+    }
+
+    /// Score each document against the query
+    ///
+    /// Returns one relevance score (0.0-1.0) per document, in input order.
+    /// Errors when the model is unavailable, the circuit is open, or
+    /// inference fails; callers are expected to fall back.
+    pub fn score(&self, query: &str, documents: &[&str]) -> Result<Vec<f32>> {
+        if documents.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        // Model absence is a setup problem, not a service failure
+        let model = self.ensure_model_loaded()?;
+
+        self.breaker.record_calls(documents.len() as u64);
+        if !self.breaker.allow_request() {
+            self.breaker.record_rejections(documents.len() as u64);
+            anyhow::bail!("Cross-encoder circuit breaker is open");
+        }
+
+        let mut scores = Vec::with_capacity(documents.len());
+        for batch in documents.chunks(self.config.batch_size) {
+            match self.score_batch(model, query, batch) {
+                Ok(batch_scores) => scores.extend(batch_scores),
+                Err(e) => {
+                    self.breaker.record_failure();
+                    return Err(e);
+                }
+            }
+        }
+
+        self.breaker.record_success();
+        Ok(scores)
+    }
+
+    /// Run one ONNX inference over a batch of (query, document) pairs
+    fn score_batch(
+        &self,
+        model: &LazyCrossEncoderModel,
+        query: &str,
+        documents: &[&str],
+    ) -> Result<Vec<f32>> {
+        let pairs: Vec<(&str, &str)> = documents.iter().map(|doc| (query, *doc)).collect();
+        let encodings = model
+            .tokenizer
+            .encode_batch(pairs, true)
+            .map_err(|e| anyhow::anyhow!("Cross-encoder tokenization failed: {e}"))?;
+
+        // Pad to the longest pair in this batch rather than max_length
+        let batch_size = encodings.len();
+        let seq_len = encodings
+            .iter()
+            .map(|e| e.get_ids().len())
+            .max()
+            .unwrap_or(0)
+            .max(1);
+
+        let mut input_ids = vec![0i64; batch_size * seq_len];
+        let mut attention_mask = vec![0i64; batch_size * seq_len];
+        let mut token_type_ids = vec![0i64; batch_size * seq_len];
+
+        for (batch_idx, encoding) in encodings.iter().enumerate() {
+            let base = batch_idx * seq_len;
+            for (i, &token) in encoding.get_ids().iter().enumerate() {
+                input_ids[base + i] = token as i64;
+            }
+            for (i, &mask) in encoding.get_attention_mask().iter().enumerate() {
+                attention_mask[base + i] = mask as i64;
+            }
+            // Segment ids distinguish query (0) from document (1)
+            for (i, &type_id) in encoding.get_type_ids().iter().enumerate() {
+                token_type_ids[base + i] = type_id as i64;
+            }
+        }
+
+        let input_ids_value = Value::from_array((vec![batch_size, seq_len], input_ids))
+            .context("Failed to create cross-encoder input_ids tensor")?;
+        let attention_mask_value =
+            Value::from_array((vec![batch_size, seq_len], attention_mask))
+                .context("Failed to create cross-encoder attention_mask tensor")?;
+        let token_type_ids_value =
+            Value::from_array((vec![batch_size, seq_len], token_type_ids))
+                .context("Failed to create cross-encoder token_type_ids tensor")?;
+
+        let mut session = model
+            .session
+            .try_lock_for(std::time::Duration::from_secs(30))
+            .ok_or_else(|| anyhow::anyhow!("Cross-encoder session lock timeout after 30s"))?;
+
+        let outputs = session
+            .run(ort::inputs![
+                "input_ids" => &input_ids_value,
+                "attention_mask" => &attention_mask_value,
+                "token_type_ids" => &token_type_ids_value,
+            ])
+            .context("Cross-encoder inference failed")?;
+
+        // Logits - shape: [batch_size, num_labels]; ms-marco models have one label
+        let (_shape, logits) = outputs[0]
+            .try_extract_tensor::<f32>()
+            .context("Failed to extract cross-encoder output tensor")?;
+
+        if logits.len() < batch_size || logits.len() % batch_size != 0 {
+            anyhow::bail!(
+                "Unexpected cross-encoder output size {} for batch of {}",
+                logits.len(),
+                batch_size
+            );
+        }
+        let num_labels = logits.len() / batch_size;
+
+        // Multi-label heads put "relevant" last
+        Ok((0..batch_size)
+            .map(|i| sigmoid(logits[i * num_labels + num_labels - 1]))
+            .collect())
+    }
+}
+
+/// Logistic squashing of a relevance logit into 0.0-1.0
+fn sigmoid(x: f32) -> f32 {
+    1.0 / (1.0 + (-x).exp())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn missing_model_config() -> CrossEncoderConfig {
+        CrossEncoderConfig::with_paths(
+            PathBuf::from("/nonexistent/cross-encoder/model.onnx"),
+            PathBuf::from("/nonexistent/cross-encoder/tokenizer.json"),
+        )
+    }
+
+    #[test]
+    fn test_missing_model_is_unavailable() {
+        let encoder = CrossEncoder::new(missing_model_config());
+        assert!(!encoder.is_available());
+        assert!(encoder.score("query", &["document"]).is_err());
+        assert!(!encoder.is_model_loaded());
+    }
+
+    #[test]
+    fn test_missing_model_does_not_trip_circuit() {
+        let encoder = CrossEncoder::new(missing_model_config());
+        for _ in 0..10 {
+            let _ = encoder.score("query", &["document"]);
+        }
+        assert_eq!(encoder.circuit_state(), CircuitState::Closed);
+        assert_eq!(encoder.metrics().total_calls, 0);
+    }
+
+    #[test]
+    fn test_failed_load_is_retried() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let encoder = CrossEncoder::new(CrossEncoderConfig::with_paths(
+            dir.path().join("model.onnx"),
+            dir.path().join("tokenizer.json"),
+        ));
+        assert!(encoder.score("query", &["document"]).is_err());
+        assert!(encoder.preload().is_err());
+
+        // Files arriving later make the model available again
+        std::fs::write(dir.path().join("model.onnx"), b"onnx").unwrap();
+        std::fs::write(dir.path().join("tokenizer.json"), b"{}").unwrap();
+        assert!(encoder.is_available());
+        assert!(!encoder.is_model_loaded());
+    }
+
+    #[test]
+    fn test_empty_documents() {
+        let encoder = CrossEncoder::new(missing_model_config());
+        assert!(encoder.score("query", &[]).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_sigmoid_range() {
+        assert!((sigmoid(0.0) - 0.5).abs() < 1e-6);
+        assert!(sigmoid(10.0) > 0.99);
+        assert!(sigmoid(-10.0) < 0.01);
+    }
+}
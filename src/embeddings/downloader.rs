@@ -40,6 +40,14 @@ const NER_MODEL_URL: &str =
 const NER_TOKENIZER_URL: &str =
     "https://huggingface.co/onnx-community/TinyBERT-finetuned-NER-ONNX/resolve/9b03777d9832105fbe419f258127fb2ec3eb09d7/tokenizer.json";
 
+/// URLs for cross-encoder reranker files (ms-marco-MiniLM-L6-v2, ~23MB quantized)
+/// Scores (query, document) pairs jointly; used by hybrid search reranking
+/// Source: cross-encoder/ms-marco-MiniLM-L6-v2 (trained on MS MARCO passage ranking)
+const CROSS_ENCODER_MODEL_URL: &str =
+    "https://huggingface.co/cross-encoder/ms-marco-MiniLM-L6-v2/resolve/main/onnx/model_quint8_avx2.onnx";
+const CROSS_ENCODER_TOKENIZER_URL: &str =
+    "https://huggingface.co/cross-encoder/ms-marco-MiniLM-L6-v2/resolve/main/tokenizer.json";
+
 /// SHA-256 checksums for model integrity verification
 /// Verified against pinned commit hashes above — these will not drift
 struct ModelChecksums;
@@ -69,6 +77,13 @@ impl ModelChecksums {
     /// Pinned: onnx-community/TinyBERT-finetuned-NER-ONNX @ 9b03777d
     const NER_TOKENIZER: Option<&'static str> =
         Some("d241a60d5e8f04cc1b2b3e9ef7a4921b27bf526d9f6050ab90f9267a1f9e5c66");
+
+    /// Cross-encoder model checksum (model_quint8_avx2.onnx)
+    /// Not pinned yet: the cross-encoder URLs track `main`
+    const CROSS_ENCODER_MODEL: Option<&'static str> = None;
+
+    /// Cross-encoder tokenizer checksum (tokenizer.json)
+    const CROSS_ENCODER_TOKENIZER: Option<&'static str> = None;
 }
 
 /// ONNX Runtime download URLs by platform (v1.23.2 required by ort 2.0.0-rc.11)
@@ -105,6 +120,11 @@ pub fn get_ner_models_dir() -> PathBuf {
     get_cache_dir().join("models").join("bert-tiny-ner")
 }
 
+/// Get the models directory for the cross-encoder reranker (ms-marco-MiniLM-L6)
+pub fn get_cross_encoder_models_dir() -> PathBuf {
+    get_cache_dir().join("models").join("ms-marco-minilm-l6")
+}
+
 /// Get the ONNX Runtime directory
 pub fn get_onnx_runtime_dir() -> PathBuf {
     get_cache_dir().join("onnxruntime")
@@ -175,6 +195,38 @@ pub fn are_ner_models_downloaded() -> bool {
     true
 }
 
+/// Check if cross-encoder model files are downloaded and valid
+pub fn are_cross_encoder_models_downloaded() -> bool {
+    let models_dir = get_cross_encoder_models_dir();
+    let model_path = models_dir.join("model.onnx");
+    let tokenizer_path = models_dir.join("tokenizer.json");
+
+    if !model_path.exists() || !tokenizer_path.exists() {
+        return false;
+    }
+
+    if let Some(expected) = ModelChecksums::CROSS_ENCODER_MODEL {
+        if let Ok(valid) = verify_checksum(&model_path, expected) {
+            if !valid {
+                tracing::warn!("Cross-encoder model checksum mismatch — will re-download");
+                let _ = fs::remove_file(&model_path);
+                return false;
+            }
+        }
+    }
+    if let Some(expected) = ModelChecksums::CROSS_ENCODER_TOKENIZER {
+        if let Ok(valid) = verify_checksum(&tokenizer_path, expected) {
+            if !valid {
+                tracing::warn!("Cross-encoder tokenizer checksum mismatch — will re-download");
+                let _ = fs::remove_file(&tokenizer_path);
+                return false;
+            }
+        }
+    }
+
+    true
+}
+
 /// Check if ONNX Runtime is downloaded
 pub fn is_onnx_runtime_downloaded() -> bool {
     let onnx_dir = get_onnx_runtime_dir();
@@ -464,6 +516,49 @@ pub fn download_ner_models(progress: Option<ProgressCallback>) -> Result<PathBuf
     Ok(models_dir)
 }
 
+/// Download cross-encoder reranker files (ms-marco-MiniLM-L6-v2, ~23MB quantized)
+/// Run in the background at server startup when cross-encoder reranking is enabled
+pub fn download_cross_encoder_models(progress: Option<ProgressCallback>) -> Result<PathBuf> {
+    let models_dir = get_cross_encoder_models_dir();
+
+    if are_cross_encoder_models_downloaded() {
+        tracing::info!(
+            "Cross-encoder models already downloaded at {:?}",
+            models_dir
+        );
+        return Ok(models_dir);
+    }
+
+    tracing::info!(
+        "Downloading ms-marco-MiniLM-L6-v2 cross-encoder to {:?} (~23MB)",
+        models_dir
+    );
+
+    let model_path = models_dir.join("model.onnx");
+    tracing::info!("Downloading cross-encoder model_quint8_avx2.onnx (~23MB)");
+    download_file_with_checksum(
+        CROSS_ENCODER_MODEL_URL,
+        &model_path,
+        progress.as_ref().map(|p| p.as_ref()),
+        ModelChecksums::CROSS_ENCODER_MODEL,
+    )?;
+
+    let tokenizer_path = models_dir.join("tokenizer.json");
+    tracing::info!("Downloading cross-encoder tokenizer.json");
+    download_file_with_checksum(
+        CROSS_ENCODER_TOKENIZER_URL,
+        &tokenizer_path,
+        progress.as_ref().map(|p| p.as_ref()),
+        ModelChecksums::CROSS_ENCODER_TOKENIZER,
+    )?;
+
+    tracing::info!(
+        "Cross-encoder model downloaded successfully to {:?}",
+        models_dir
+    );
+    Ok(models_dir)
+}
+
 /// Download ONNX Runtime
 pub fn download_onnx_runtime(progress: Option<ProgressCallback>) -> Result<PathBuf> {
     let onnx_dir = get_onnx_runtime_dir();
@@ -591,12 +686,14 @@ pub fn print_status() {
     let cache_dir = get_cache_dir();
     let models_downloaded = are_models_downloaded();
     let ner_models_downloaded = are_ner_models_downloaded();
+    let cross_encoder_downloaded = are_cross_encoder_models_downloaded();
     let onnx_downloaded = is_onnx_runtime_downloaded();
 
     println!("Shodh-Memory Cache Status:");
     println!("  Cache directory: {cache_dir:?}");
     println!("  Embedding models downloaded: {models_downloaded}");
     println!("  NER models downloaded: {ner_models_downloaded}");
+    println!("  Cross-encoder models downloaded: {cross_encoder_downloaded}");
     println!("  ONNX Runtime downloaded: {onnx_downloaded}");
 
     if models_downloaded {
@@ -609,6 +706,11 @@ pub fn print_status() {
         println!("  NER model path: {ner_dir:?}");
     }
 
+    if cross_encoder_downloaded {
+        let cross_encoder_dir = get_cross_encoder_models_dir();
+        println!("  Cross-encoder model path: {cross_encoder_dir:?}");
+    }
+
     if onnx_downloaded {
         if let Some(path) = get_onnx_runtime_path() {
             println!("  ONNX Runtime path: {path:?}");
@@ -13,6 +13,10 @@
 //! - SHODH_EMBED_TIMEOUT_MS: Embedding timeout in ms (default: 5000)
 //! - SHODH_LAZY_LOAD: Set to "false" to load model at startup (default: true)
 //! - SHODH_ONNX_THREADS: Number of ONNX threads (default: 1 on macOS ARM64, 2 elsewhere)
+//!
+//! The same ONNX path also serves other sentence-transformer exports: set
+//! `dimension`, `pooling` and `model_id` in [`EmbeddingConfig`] (see
+//! `embeddings::registry` for the `onnx` backend).
 
 use anyhow::{Context, Result};
 use ort::session::Session;
@@ -24,6 +28,12 @@ use tokenizers::Tokenizer;
 
 use super::Embedder;
 
+/// Output dimension of all-MiniLM-L6-v2
+pub const MINILM_DIMENSION: usize = 384;
+
+/// Model ID recorded for the default MiniLM backend
+pub const MINILM_MODEL_ID: &str = "minilm-l6-v2";
+
 /// Thread-safe guard for ORT_DYLIB_PATH initialization.
 /// Using OnceLock ensures set_var is called exactly once.
 static ORT_PATH_INIT: OnceLock<Result<PathBuf, String>> = OnceLock::new();
@@ -43,6 +53,8 @@ pub fn pre_init_ort_runtime(offline_mode: bool) {
 struct LazyModel {
     session: Mutex<Session>,
     tokenizer: Tokenizer,
+    /// BERT-style exports take `token_type_ids`; many others (MPNet, E5) don't
+    uses_token_type_ids: bool,
 }
 
 impl LazyModel {
@@ -61,7 +73,8 @@ impl LazyModel {
             .unwrap_or(default_threads);
 
         tracing::info!(
-            "Loading MiniLM-L6-v2 model from {:?} with {} threads",
+            "Loading {} model from {:?} with {} threads",
+            config.model_id,
             config.model_path,
             num_threads
         );
@@ -89,15 +102,49 @@ impl LazyModel {
         let tokenizer = Tokenizer::from_file(&config.tokenizer_path)
             .map_err(|e| anyhow::anyhow!("Failed to load tokenizer: {e}"))?;
 
-        tracing::info!("MiniLM-L6-v2 model loaded successfully");
+        let uses_token_type_ids = session
+            .inputs()
+            .iter()
+            .any(|input| input.name() == "token_type_ids");
+
+        tracing::info!("{} model loaded successfully", config.model_id);
 
         Ok(Self {
             session: Mutex::new(session),
             tokenizer,
+            uses_token_type_ids,
         })
     }
 }
 
+/// How token embeddings are reduced to one sentence vector
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PoolingStrategy {
+    /// Attention-masked mean over tokens (sentence-transformers default)
+    #[default]
+    Mean,
+    /// First token (`[CLS]`), used by BGE/GTE-style models
+    Cls,
+    /// Element-wise max over attended tokens
+    Max,
+}
+
+impl std::str::FromStr for PoolingStrategy {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_ascii_lowercase().as_str() {
+            "mean" => Ok(Self::Mean),
+            "cls" => Ok(Self::Cls),
+            "max" => Ok(Self::Max),
+            other => {
+                anyhow::bail!("Unknown pooling strategy '{other}' (expected mean, cls or max)")
+            }
+        }
+    }
+}
+
 /// Configuration for MiniLM embedder
 #[derive(Debug, Clone)]
 pub struct EmbeddingConfig {
@@ -115,6 +162,30 @@ pub struct EmbeddingConfig {
 
     /// Timeout for embedding generation in milliseconds
     pub embed_timeout_ms: u64,
+
+    /// Output dimension (MiniLM: 384)
+    pub dimension: usize,
+
+    /// Token pooling applied to the model output
+    pub pooling: PoolingStrategy,
+
+    /// Stable model name, persisted with the vector indices to detect model changes
+    pub model_id: String,
+
+    /// Download the MiniLM files when `model_path` is missing. Disabled for
+    /// custom ONNX models, which must already be on disk.
+    pub auto_download: bool,
+
+    /// Fall back to hash-based embeddings when the ONNX model can't be loaded
+    /// or inference fails. When false those cases are errors instead.
+    pub fallback_to_simplified: bool,
+}
+
+/// `SHODH_EMBEDDING_FALLBACK` (default: true)
+fn simplified_fallback_from_env() -> bool {
+    std::env::var("SHODH_EMBEDDING_FALLBACK")
+        .map(|v| v != "0" && v.to_lowercase() != "false")
+        .unwrap_or(true)
 }
 
 impl Default for EmbeddingConfig {
@@ -132,6 +203,9 @@ impl EmbeddingConfig {
     /// 3. ./models/minilm-l6 (local)
     /// 4. ../models/minilm-l6 (parent)
     /// 5. ~/.cache/shodh-memory/models/minilm-l6 (auto-download location)
+    ///
+    /// `SHODH_EMBEDDING_MAX_LENGTH` overrides the token limit and
+    /// `SHODH_EMBEDDING_FALLBACK=false` disables the simplified fallback.
     pub fn from_env() -> Self {
         let base_path = std::env::var("SHODH_MODEL_PATH")
             .map(PathBuf::from)
@@ -167,6 +241,11 @@ impl EmbeddingConfig {
             .map(|v| v != "0" && v.to_lowercase() != "false")
             .unwrap_or(true);
 
+        let max_length = std::env::var("SHODH_EMBEDDING_MAX_LENGTH")
+            .ok()
+            .and_then(|s| s.parse().ok())
+            .unwrap_or(256);
+
         let model_filename = if use_quantized {
             "model_quantized.onnx"
         } else {
@@ -176,9 +255,14 @@ impl EmbeddingConfig {
         Self {
             model_path: base_path.join(model_filename),
             tokenizer_path: base_path.join("tokenizer.json"),
-            max_length: 256,
+            max_length,
             use_quantized,
             embed_timeout_ms,
+            dimension: MINILM_DIMENSION,
+            pooling: PoolingStrategy::Mean,
+            model_id: MINILM_MODEL_ID.to_string(),
+            auto_download: true,
+            fallback_to_simplified: simplified_fallback_from_env(),
         }
     }
 
@@ -190,6 +274,11 @@ impl EmbeddingConfig {
             max_length: 256,
             use_quantized: true,
             embed_timeout_ms: 5000,
+            dimension: MINILM_DIMENSION,
+            pooling: PoolingStrategy::Mean,
+            model_id: MINILM_MODEL_ID.to_string(),
+            auto_download: true,
+            fallback_to_simplified: simplified_fallback_from_env(),
         }
     }
 }
@@ -347,6 +436,9 @@ impl MiniLMEmbedder {
         // CRITICAL: Ensure ORT_DYLIB_PATH is set BEFORE any ort code runs
         // This prevents ort from picking up system DLLs with wrong versions
         if let Err(e) = Self::ensure_onnx_runtime_available(offline_mode) {
+            if !config.auto_download || !config.fallback_to_simplified {
+                anyhow::bail!("ONNX Runtime unavailable for {}: {e}", config.model_id);
+            }
             tracing::warn!(
                 "Failed to set up ONNX Runtime: {}. Using simplified embeddings.",
                 e
@@ -358,7 +450,21 @@ impl MiniLMEmbedder {
         let model_available = config.model_path.exists() && config.tokenizer_path.exists();
 
         if !model_available {
+            if !config.auto_download {
+                anyhow::bail!(
+                    "ONNX model files for {} not found ({:?}, {:?})",
+                    config.model_id,
+                    config.model_path,
+                    config.tokenizer_path
+                );
+            }
             if offline_mode {
+                if !config.fallback_to_simplified {
+                    anyhow::bail!(
+                        "Model files for {} not found and SHODH_OFFLINE=true",
+                        config.model_id
+                    );
+                }
                 tracing::warn!(
                     "Model files not found and SHODH_OFFLINE=true. Using simplified embeddings.",
                 );
@@ -405,6 +511,9 @@ impl MiniLMEmbedder {
                     return Self::new(updated_config);
                 }
                 Err(e) => {
+                    if !config.fallback_to_simplified {
+                        return Err(e.context("Failed to download embedding models"));
+                    }
                     tracing::warn!(
                         "Failed to download models: {}. Using simplified embeddings.",
                         e
@@ -418,7 +527,7 @@ impl MiniLMEmbedder {
             config: config.clone(),
             lazy_model: OnceLock::new(),
             simplified_mode: false,
-            dimension: 384,
+            dimension: config.dimension,
         };
 
         // If not lazy loading, initialize now
@@ -465,11 +574,12 @@ impl MiniLMEmbedder {
         tracing::warn!("    Model: {:?}", config.model_path);
         tracing::warn!("    Tokenizer: {:?}", config.tokenizer_path);
 
+        let dimension = config.dimension;
         Ok(Self {
             config,
             lazy_model: OnceLock::new(),
             simplified_mode: true,
-            dimension: 384,
+            dimension,
         })
     }
 
@@ -558,99 +668,9 @@ impl MiniLMEmbedder {
     ///
     /// Lazily loads the model on first call if not already loaded.
     fn generate_embedding_onnx(&self, text: &str) -> Result<Vec<f32>> {
-        // Lazy load model on first use
-        tracing::debug!("ONNX: ensuring model loaded...");
-        let model = self.ensure_model_loaded()?;
-        tracing::debug!("ONNX: model ready, acquiring session lock...");
-
-        let lock_timeout = std::time::Duration::from_secs(30);
-        let mut session = model.session.try_lock_for(lock_timeout).ok_or_else(|| {
-            tracing::error!(
-                "ONNX session lock acquisition timed out after {}s — a previous inference \
-                     call is likely stuck. Falling back to simplified embeddings.",
-                lock_timeout.as_secs()
-            );
-            anyhow::anyhow!("ONNX session lock timeout ({}s)", lock_timeout.as_secs())
-        })?;
-        tracing::debug!("ONNX: session lock acquired, tokenizing...");
-
-        // Tokenize input text
-        let encoding = model
-            .tokenizer
-            .encode(text, true)
-            .map_err(|e| anyhow::anyhow!("Tokenization failed: {e}"))?;
-
-        let tokens = encoding.get_ids();
-        let attention_mask = encoding.get_attention_mask();
-        let max_length = self.config.max_length;
-        tracing::debug!("ONNX: tokenized {} tokens", tokens.len());
-
-        // Truncate or pad to max_length
-        let mut input_ids = vec![0i64; max_length];
-        let mut attention = vec![0i64; max_length];
-        let token_type_ids = vec![0i64; max_length];
-
-        for (i, &token) in tokens.iter().take(max_length).enumerate() {
-            input_ids[i] = token as i64;
-        }
-        for (i, &mask) in attention_mask.iter().take(max_length).enumerate() {
-            attention[i] = mask as i64;
-        }
-
-        // Create input tensors
-        let input_ids_value = Value::from_array((vec![1, max_length], input_ids))?;
-        let attention_mask_value = Value::from_array((vec![1, max_length], attention.clone()))?;
-        let token_type_ids_value = Value::from_array((vec![1, max_length], token_type_ids))?;
-
-        // Run inference
-        tracing::debug!("ONNX: running inference...");
-        let outputs = session.run(ort::inputs![
-            "input_ids" => &input_ids_value,
-            "attention_mask" => &attention_mask_value,
-            "token_type_ids" => &token_type_ids_value,
-        ])?;
-        tracing::debug!("ONNX: inference complete");
-
-        // Extract embeddings
-        let output_tensor = outputs[0].try_extract_tensor::<f32>()?;
-        let (_shape, output_data) = output_tensor;
-
-        // Mean pooling over sequence dimension
-        let mut pooled = vec![0.0; self.dimension];
-        let mut mask_sum = 0.0;
-
-        for (seq_idx, &att) in attention.iter().enumerate() {
-            if att == 1 {
-                for (dim_idx, pooled_val) in pooled.iter_mut().enumerate() {
-                    let idx = seq_idx * self.dimension + dim_idx;
-                    *pooled_val += output_data[idx];
-                }
-                mask_sum += 1.0;
-            }
-        }
-
-        // Average and L2 normalize
-        if mask_sum > 0.0 {
-            for val in &mut pooled {
-                *val /= mask_sum;
-            }
-        }
-
-        // Handle NaN/Inf values that may come from model output
-        for val in pooled.iter_mut() {
-            if val.is_nan() || val.is_infinite() {
-                *val = 0.0;
-            }
-        }
-
-        let norm: f32 = pooled.iter().map(|x| x * x).sum::<f32>().sqrt();
-        if norm > f32::EPSILON && !norm.is_nan() {
-            for val in &mut pooled {
-                *val /= norm;
-            }
-        }
-
-        Ok(pooled)
+        self.generate_embeddings_batch_onnx(&[text])?
+            .pop()
+            .context("ONNX returned no embedding")
     }
 
     /// Generate embeddings for multiple texts in a single ONNX batch
@@ -671,18 +691,16 @@ impl MiniLMEmbedder {
         }
 
         // Lazy load model on first use
+        tracing::debug!("ONNX: ensuring model loaded...");
         let model = self.ensure_model_loaded()?;
         let lock_timeout = std::time::Duration::from_secs(30);
         let mut session = model.session.try_lock_for(lock_timeout).ok_or_else(|| {
             tracing::error!(
-                "ONNX session lock timed out after {}s in batch embed — \
-                 a previous inference call is likely stuck.",
+                "ONNX session lock acquisition timed out after {}s — a previous inference \
+                 call is likely stuck. Falling back to simplified embeddings.",
                 lock_timeout.as_secs()
             );
-            anyhow::anyhow!(
-                "ONNX session lock timeout ({}s) in batch embed",
-                lock_timeout.as_secs()
-            )
+            anyhow::anyhow!("ONNX session lock timeout ({}s)", lock_timeout.as_secs())
         })?;
 
         let batch_size = texts.len();
@@ -725,46 +743,44 @@ impl MiniLMEmbedder {
         let token_type_ids_value =
             Value::from_array((vec![batch_size, max_length], token_type_ids))?;
 
-        // Run batch inference
-        let outputs = session.run(ort::inputs![
+        let mut inputs = ort::inputs![
             "input_ids" => &input_ids_value,
             "attention_mask" => &attention_mask_value,
-            "token_type_ids" => &token_type_ids_value,
-        ])?;
+        ];
+        if model.uses_token_type_ids {
+            inputs.push(("token_type_ids".into(), (&token_type_ids_value).into()));
+        }
 
-        // Extract embeddings - output shape is [batch_size, seq_length, hidden_size]
-        let output_tensor = outputs[0].try_extract_tensor::<f32>()?;
-        let (_shape, output_data) = output_tensor;
+        // Run batch inference
+        tracing::debug!("ONNX: running inference on {} texts...", batch_size);
+        let outputs = session.run(inputs)?;
+
+        // Token embeddings [batch, seq, hidden], or already pooled [batch, hidden]
+        let (shape, output_data) = outputs[0].try_extract_tensor::<f32>()?;
+        let hidden = shape.last().copied().unwrap_or_default() as usize;
+        if hidden != self.dimension {
+            anyhow::bail!(
+                "{} produced {}-dim output but is configured for {} dimensions",
+                self.config.model_id,
+                hidden,
+                self.dimension
+            );
+        }
+        let pre_pooled = shape.len() == 2;
+        let seq_len = if pre_pooled { 1 } else { shape[1] as usize };
 
-        // Mean pooling for each item in batch
         let mut results = Vec::with_capacity(batch_size);
-
         for batch_idx in 0..batch_size {
-            let mut pooled = vec![0.0; self.dimension];
-            let mut mask_sum = 0.0;
-
-            let batch_offset = batch_idx * max_length * self.dimension;
-            let attention_offset = batch_idx * max_length;
-
-            for seq_idx in 0..max_length {
-                if attention_masks[attention_offset + seq_idx] == 1 {
-                    for (dim_idx, pooled_val) in pooled.iter_mut().enumerate().take(self.dimension)
-                    {
-                        let idx = batch_offset + seq_idx * self.dimension + dim_idx;
-                        *pooled_val += output_data[idx];
-                    }
-                    mask_sum += 1.0;
-                }
-            }
-
-            // Average
-            if mask_sum > 0.0 {
-                for val in &mut pooled {
-                    *val /= mask_sum;
-                }
-            }
+            let batch_data =
+                &output_data[batch_idx * seq_len * hidden..(batch_idx + 1) * seq_len * hidden];
+            let mut pooled = if pre_pooled {
+                batch_data.to_vec()
+            } else {
+                let mask = &attention_masks[batch_idx * max_length..(batch_idx + 1) * max_length];
+                self.pool(batch_data, mask, seq_len)
+            };
 
-            // Handle NaN/Inf values
+            // Handle NaN/Inf values that may come from model output
             for val in pooled.iter_mut() {
                 if val.is_nan() || val.is_infinite() {
                     *val = 0.0;
@@ -784,6 +800,44 @@ impl MiniLMEmbedder {
 
         Ok(results)
     }
+
+    /// Reduce one item's token embeddings `[seq_len, dimension]` to a single vector
+    fn pool(&self, tokens: &[f32], attention: &[i64], seq_len: usize) -> Vec<f32> {
+        let dim = self.dimension;
+        let attended = (0..seq_len.min(attention.len())).filter(|&i| attention[i] == 1);
+
+        match self.config.pooling {
+            PoolingStrategy::Cls => tokens[..dim].to_vec(),
+            PoolingStrategy::Mean => {
+                let mut pooled = vec![0.0; dim];
+                let mut mask_sum = 0.0;
+                for seq_idx in attended {
+                    let row = &tokens[seq_idx * dim..(seq_idx + 1) * dim];
+                    for (pooled_val, &v) in pooled.iter_mut().zip(row) {
+                        *pooled_val += v;
+                    }
+                    mask_sum += 1.0;
+                }
+                if mask_sum > 0.0 {
+                    for val in &mut pooled {
+                        *val /= mask_sum;
+                    }
+                }
+                pooled
+            }
+            PoolingStrategy::Max => {
+                let mut pooled = vec![f32::NEG_INFINITY; dim];
+                for seq_idx in attended {
+                    let row = &tokens[seq_idx * dim..(seq_idx + 1) * dim];
+                    for (pooled_val, &v) in pooled.iter_mut().zip(row) {
+                        *pooled_val = pooled_val.max(v);
+                    }
+                }
+                // No attended tokens leaves -inf, zeroed by the NaN/Inf pass
+                pooled
+            }
+        }
+    }
 }
 
 impl Embedder for MiniLMEmbedder {
@@ -842,6 +896,9 @@ impl Embedder for MiniLMEmbedder {
                 crate::metrics::EMBEDDING_GENERATE_TOTAL
                     .with_label_values(&["onnx", "failure"])
                     .inc();
+                if !self.config.fallback_to_simplified {
+                    return Err(e.context("ONNX inference failed"));
+                }
                 tracing::warn!("ONNX inference failed: {}. Falling back to simplified.", e);
 
                 // Fallback to simplified
@@ -854,6 +911,15 @@ impl Embedder for MiniLMEmbedder {
         self.dimension
     }
 
+    fn model_id(&self) -> String {
+        // Hash fallback vectors are not comparable with real model output
+        if self.simplified_mode {
+            format!("{}-simplified", self.config.model_id)
+        } else {
+            self.config.model_id.clone()
+        }
+    }
+
     fn encode_batch(&self, texts: &[&str]) -> Result<Vec<Vec<f32>>> {
         if texts.is_empty() {
             return Ok(Vec::new());
@@ -939,6 +1005,9 @@ impl Embedder for MiniLMEmbedder {
                 crate::metrics::EMBEDDING_GENERATE_TOTAL
                     .with_label_values(&["onnx_batch", "failure"])
                     .inc();
+                if !self.config.fallback_to_simplified {
+                    return Err(e.context("Batch ONNX inference failed"));
+                }
                 tracing::warn!(
                     "Batch ONNX inference failed: {}. Falling back to sequential simplified.",
                     e
@@ -976,13 +1045,8 @@ mod tests {
     #[test]
     fn test_embedding_generation_simplified() {
         // Create embedder in simplified mode (no ONNX model needed)
-        let config = EmbeddingConfig {
-            model_path: PathBuf::from("dummy.onnx"),
-            tokenizer_path: PathBuf::from("dummy.json"),
-            max_length: 256,
-            use_quantized: true,
-            embed_timeout_ms: 5000,
-        };
+        let config =
+            EmbeddingConfig::with_paths(PathBuf::from("dummy.onnx"), PathBuf::from("dummy.json"));
         let embedder = MiniLMEmbedder::new_simplified(config).unwrap();
 
         let text = "Hello world";
@@ -998,13 +1062,8 @@ mod tests {
     #[test]
     fn test_batch_encoding_simplified() {
         // Create embedder in simplified mode
-        let config = EmbeddingConfig {
-            model_path: PathBuf::from("dummy.onnx"),
-            tokenizer_path: PathBuf::from("dummy.json"),
-            max_length: 256,
-            use_quantized: true,
-            embed_timeout_ms: 5000,
-        };
+        let config =
+            EmbeddingConfig::with_paths(PathBuf::from("dummy.onnx"), PathBuf::from("dummy.json"));
         let embedder = MiniLMEmbedder::new_simplified(config).unwrap();
 
         let texts = vec!["Hello", "World", "Test"];
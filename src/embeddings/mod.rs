@@ -1,7 +1,9 @@
 //! Embedding generation module
 //!
 //! Provides semantic embedding generation for memory retrieval.
-//! Uses ONNX Runtime with MiniLM-L6-v2 for 384-dimensional embeddings.
+//! Uses ONNX Runtime with MiniLM-L6-v2 for 384-dimensional embeddings by
+//! default; other ONNX models and OpenAI-compatible HTTP endpoints can be
+//! selected through [`registry::EmbeddingBackend`].
 //!
 //! # Features
 //! - **Auto-download**: Model files downloaded on first use to ~/.cache/shodh-memory/
@@ -15,10 +17,13 @@
 
 pub mod chunking;
 pub mod circuit_breaker;
+pub mod cross_encoder;
 pub mod downloader;
 pub mod keywords;
 pub mod minilm;
 pub mod ner;
+pub mod openai;
+pub mod registry;
 
 // Re-export chunking types
 pub use chunking::{chunk_text, ChunkConfig, ChunkResult};
@@ -27,8 +32,9 @@ use anyhow::Result;
 
 // Re-export downloader functions for convenience
 pub use downloader::{
-    are_models_downloaded, are_ner_models_downloaded, download_ner_models, ensure_downloaded,
-    get_cache_dir, get_models_dir, get_ner_models_dir, get_onnx_runtime_path,
+    are_cross_encoder_models_downloaded, are_models_downloaded, are_ner_models_downloaded,
+    download_cross_encoder_models, download_ner_models, ensure_downloaded, get_cache_dir,
+    get_cross_encoder_models_dir, get_models_dir, get_ner_models_dir, get_onnx_runtime_path,
     is_onnx_runtime_downloaded, print_status,
 };
 
@@ -40,9 +46,15 @@ pub use keywords::{Keyword, KeywordConfig, KeywordExtractor};
 
 // Re-export circuit breaker types
 pub use circuit_breaker::{
-    CircuitBreakerConfig, CircuitBreakerMetrics, CircuitState, ResilientEmbedder,
+    CircuitBreaker, CircuitBreakerConfig, CircuitBreakerMetrics, CircuitState, ResilientEmbedder,
 };
 
+// Re-export cross-encoder types
+pub use cross_encoder::{CrossEncoder, CrossEncoderConfig};
+
+// Re-export embedding backend registry
+pub use registry::{EmbeddingBackend, EmbeddingFingerprint};
+
 /// Trait for embedding generation
 pub trait Embedder: Send + Sync {
     /// Generate embedding for text
@@ -51,6 +63,12 @@ pub trait Embedder: Send + Sync {
     /// Get embedding dimension
     fn dimension(&self) -> usize;
 
+    /// Stable identifier of the model producing the vectors. Stored next to
+    /// the vector indices so a model change triggers a re-embed.
+    fn model_id(&self) -> String {
+        format!("custom-{}", self.dimension())
+    }
+
     /// Batch encode multiple texts (default: sequential)
     fn encode_batch(&self, texts: &[&str]) -> Result<Vec<Vec<f32>>> {
         texts.iter().map(|text| self.encode(text)).collect()
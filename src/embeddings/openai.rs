@@ -0,0 +1,282 @@
+//! OpenAI-compatible HTTP embedding backend
+//!
+//! Sends `POST {base_url}/embeddings` with `{"model": .., "input": [..]}` and
+//! reads `data[].embedding`. Works with OpenAI itself and with local servers
+//! exposing the same API (llama.cpp, vLLM, Ollama, text-embeddings-inference).
+//!
+//! Uses ureq (blocking, no async runtime needed) because `Embedder` is a
+//! synchronous trait called from both blocking tasks and plain threads.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+use super::Embedder;
+
+/// Configuration for an OpenAI-compatible embedding endpoint
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OpenAiEmbeddingConfig {
+    /// Base URL including the API version, e.g. `http://localhost:8080/v1`
+    pub base_url: String,
+
+    /// Model name sent in each request
+    pub model: String,
+
+    /// Dimension of the returned vectors
+    pub dimension: usize,
+
+    /// Bearer token, if the endpoint requires one
+    #[serde(default, skip_serializing)]
+    pub api_key: Option<String>,
+
+    /// Per-request timeout in milliseconds
+    #[serde(default = "default_timeout_ms")]
+    pub timeout_ms: u64,
+
+    /// Maximum inputs per request
+    #[serde(default = "default_batch_size")]
+    pub batch_size: usize,
+}
+
+fn default_timeout_ms() -> u64 {
+    30_000
+}
+
+fn default_batch_size() -> usize {
+    64
+}
+
+impl OpenAiEmbeddingConfig {
+    pub fn new(base_url: impl Into<String>, model: impl Into<String>, dimension: usize) -> Self {
+        Self {
+            base_url: base_url.into(),
+            model: model.into(),
+            dimension,
+            api_key: None,
+            timeout_ms: default_timeout_ms(),
+            batch_size: default_batch_size(),
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct EmbeddingRequest<'a> {
+    model: &'a str,
+    input: &'a [&'a str],
+}
+
+#[derive(Deserialize)]
+struct EmbeddingResponse {
+    data: Vec<EmbeddingData>,
+}
+
+#[derive(Deserialize)]
+struct EmbeddingData {
+    embedding: Vec<f32>,
+    /// Position in the request; servers may return items out of order
+    #[serde(default)]
+    index: Option<usize>,
+}
+
+/// Embedder backed by an OpenAI-compatible `/embeddings` endpoint
+pub struct OpenAiEmbedder {
+    config: OpenAiEmbeddingConfig,
+    agent: ureq::Agent,
+    endpoint: String,
+}
+
+impl OpenAiEmbedder {
+    pub fn new(config: OpenAiEmbeddingConfig) -> Result<Self> {
+        if config.dimension == 0 {
+            anyhow::bail!("OpenAI embedding backend requires a non-zero dimension");
+        }
+        let endpoint = format!("{}/embeddings", config.base_url.trim_end_matches('/'));
+        let agent = ureq::Agent::config_builder()
+            .timeout_global(Some(Duration::from_millis(config.timeout_ms)))
+            .http_status_as_error(false)
+            .build()
+            .into();
+
+        tracing::info!(
+            endpoint = %endpoint,
+            model = %config.model,
+            dimension = config.dimension,
+            "Using OpenAI-compatible embedding backend"
+        );
+
+        Ok(Self {
+            config,
+            agent,
+            endpoint,
+        })
+    }
+
+    /// Embed one request's worth of texts
+    fn request(&self, texts: &[&str]) -> Result<Vec<Vec<f32>>> {
+        let mut request = self.agent.post(&self.endpoint);
+        if let Some(key) = &self.config.api_key {
+            request = request.header("Authorization", &format!("Bearer {key}"));
+        }
+
+        let mut response = request
+            .send_json(EmbeddingRequest {
+                model: &self.config.model,
+                input: texts,
+            })
+            .with_context(|| format!("Embedding request to {} failed", self.endpoint))?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.body_mut().read_to_string().unwrap_or_default();
+            anyhow::bail!(
+                "Embedding endpoint returned {}: {}",
+                status,
+                body.chars().take(200).collect::<String>()
+            );
+        }
+
+        let mut parsed: EmbeddingResponse = response
+            .body_mut()
+            .read_json()
+            .context("Failed to parse embedding response")?;
+        if parsed.data.len() != texts.len() {
+            anyhow::bail!(
+                "Embedding endpoint returned {} vectors for {} inputs",
+                parsed.data.len(),
+                texts.len()
+            );
+        }
+        parsed.data.sort_by_key(|d| d.index.unwrap_or(usize::MAX));
+
+        parsed
+            .data
+            .into_iter()
+            .map(|d| {
+                if d.embedding.len() != self.config.dimension {
+                    anyhow::bail!(
+                        "Embedding endpoint returned {}-dim vector, expected {}",
+                        d.embedding.len(),
+                        self.config.dimension
+                    );
+                }
+                Ok(d.embedding)
+            })
+            .collect()
+    }
+}
+
+impl Embedder for OpenAiEmbedder {
+    fn encode(&self, text: &str) -> Result<Vec<f32>> {
+        if text.is_empty() {
+            return Ok(vec![0.0; self.config.dimension]);
+        }
+        self.request(&[text])?
+            .pop()
+            .context("Embedding endpoint returned no vectors")
+    }
+
+    fn dimension(&self) -> usize {
+        self.config.dimension
+    }
+
+    fn model_id(&self) -> String {
+        format!("openai:{}", self.config.model)
+    }
+
+    fn encode_batch(&self, texts: &[&str]) -> Result<Vec<Vec<f32>>> {
+        let mut results = vec![vec![0.0; self.config.dimension]; texts.len()];
+
+        // Empty strings are rejected by most servers; they keep zero vectors
+        let non_empty: Vec<(usize, &str)> = texts
+            .iter()
+            .enumerate()
+            .filter(|(_, t)| !t.is_empty())
+            .map(|(i, t)| (i, *t))
+            .collect();
+
+        for chunk in non_empty.chunks(self.config.batch_size.max(1)) {
+            let inputs: Vec<&str> = chunk.iter().map(|(_, t)| *t).collect();
+            for ((i, _), embedding) in chunk.iter().zip(self.request(&inputs)?) {
+                results[*i] = embedding;
+            }
+        }
+        Ok(results)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Local stand-in for `/v1/embeddings`: vector = [len, words, 1, 0...]
+    async fn serve_stand_in() -> String {
+        use axum::{routing::post, Json, Router};
+
+        let app = Router::new().route(
+            "/v1/embeddings",
+            post(|Json(body): Json<serde_json::Value>| async move {
+                let data: Vec<serde_json::Value> = body["input"]
+                    .as_array()
+                    .unwrap()
+                    .iter()
+                    .enumerate()
+                    .rev()
+                    .map(|(i, text)| {
+                        let text = text.as_str().unwrap();
+                        serde_json::json!({
+                            "index": i,
+                            "embedding": [
+                                text.len() as f32,
+                                text.split_whitespace().count() as f32,
+                                1.0,
+                                0.0
+                            ],
+                        })
+                    })
+                    .collect();
+                Json(serde_json::json!({ "data": data }))
+            }),
+        );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await });
+        format!("http://{addr}/v1")
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn encodes_against_local_stand_in() {
+        let base_url = serve_stand_in().await;
+        let embedder = OpenAiEmbedder::new(OpenAiEmbeddingConfig {
+            batch_size: 2,
+            ..OpenAiEmbeddingConfig::new(base_url, "stand-in", 4)
+        })
+        .unwrap();
+
+        let result = tokio::task::spawn_blocking(move || {
+            let single = embedder.encode("hello world").unwrap();
+            let batch = embedder.encode_batch(&["a", "", "bb cc", "ddd"]).unwrap();
+            (single, batch, embedder.model_id())
+        })
+        .await
+        .unwrap();
+
+        let (single, batch, model_id) = result;
+        assert_eq!(single, vec![11.0, 2.0, 1.0, 0.0]);
+        assert_eq!(batch[0][0], 1.0);
+        assert_eq!(batch[1], vec![0.0; 4], "empty input keeps a zero vector");
+        assert_eq!(batch[2][0], 5.0, "out-of-order response reordered by index");
+        assert_eq!(batch[3][0], 3.0);
+        assert_eq!(model_id, "openai:stand-in");
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn rejects_dimension_mismatch() {
+        let base_url = serve_stand_in().await;
+        let embedder =
+            OpenAiEmbedder::new(OpenAiEmbeddingConfig::new(base_url, "stand-in", 8)).unwrap();
+        let err = tokio::task::spawn_blocking(move || embedder.encode("hello").unwrap_err())
+            .await
+            .unwrap();
+        assert!(err.to_string().contains("expected 8"), "{err}");
+    }
+}
@@ -0,0 +1,285 @@
+//! Embedding backend registry
+//!
+//! Selects the embedder used by memories, todos and files:
+//! - `minilm` (default): bundled MiniLM-L6-v2 ONNX model, auto-downloaded
+//! - `onnx`: any local sentence-transformer ONNX export with its own
+//!   tokenizer, dimension and pooling
+//! - `openai`: an OpenAI-compatible `/v1/embeddings` HTTP endpoint
+//!
+//! The active model is recorded next to the vector indices as an
+//! [`EmbeddingFingerprint`]; a mismatch on startup triggers a re-embed.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use super::circuit_breaker::ResilientEmbedder;
+use super::minilm::{
+    EmbeddingConfig, MiniLMEmbedder, PoolingStrategy, MINILM_DIMENSION, MINILM_MODEL_ID,
+};
+use super::openai::{OpenAiEmbedder, OpenAiEmbeddingConfig};
+use super::Embedder;
+
+/// File written next to the vector indices recording which model built them
+const FINGERPRINT_FILE: &str = "embedding_model.json";
+
+/// Local ONNX sentence-transformer model
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OnnxBackendConfig {
+    /// Path to the `.onnx` model file
+    pub model_path: PathBuf,
+    /// Path to `tokenizer.json`; defaults to the model's directory
+    #[serde(default)]
+    pub tokenizer_path: Option<PathBuf>,
+    /// Output dimension
+    pub dimension: usize,
+    #[serde(default)]
+    pub pooling: PoolingStrategy,
+    /// Model name recorded in the fingerprint; defaults to the file stem
+    #[serde(default)]
+    pub model_id: Option<String>,
+    #[serde(default = "default_max_length")]
+    pub max_length: usize,
+}
+
+fn default_max_length() -> usize {
+    256
+}
+
+/// Configured embedding backend
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+#[serde(tag = "backend", rename_all = "lowercase")]
+pub enum EmbeddingBackend {
+    /// Bundled MiniLM-L6-v2 (384 dims)
+    #[default]
+    MiniLm,
+    /// Local ONNX sentence-transformer
+    Onnx(OnnxBackendConfig),
+    /// OpenAI-compatible HTTP endpoint
+    OpenAi(OpenAiEmbeddingConfig),
+}
+
+impl EmbeddingBackend {
+    /// Read the backend from environment variables
+    ///
+    /// - `SHODH_EMBEDDING_BACKEND`: `minilm` (default), `onnx` or `openai`
+    /// - `SHODH_EMBEDDING_MODEL_PATH`, `SHODH_EMBEDDING_TOKENIZER_PATH`: ONNX files
+    /// - `SHODH_EMBEDDING_DIMENSION`: output dimension (required for onnx/openai)
+    /// - `SHODH_EMBEDDING_POOLING`: `mean` (default), `cls` or `max`
+    /// - `SHODH_EMBEDDING_MAX_LENGTH`: token limit for onnx (default: 256)
+    /// - `SHODH_EMBEDDING_MODEL`: model name (openai request model / onnx model id)
+    /// - `SHODH_EMBEDDING_URL`, `SHODH_EMBEDDING_API_KEY`: HTTP endpoint and token
+    pub fn from_env() -> Result<Self> {
+        let backend = std::env::var("SHODH_EMBEDDING_BACKEND").unwrap_or_default();
+        let var = |name: &str| std::env::var(name).ok().filter(|v| !v.trim().is_empty());
+        let dimension = || -> Result<usize> {
+            var("SHODH_EMBEDDING_DIMENSION")
+                .context("SHODH_EMBEDDING_DIMENSION is required for this backend")?
+                .parse()
+                .context("SHODH_EMBEDDING_DIMENSION must be a positive integer")
+        };
+
+        match backend.trim().to_ascii_lowercase().as_str() {
+            "" | "minilm" => Ok(Self::MiniLm),
+            "onnx" => Ok(Self::Onnx(OnnxBackendConfig {
+                model_path: var("SHODH_EMBEDDING_MODEL_PATH")
+                    .context("SHODH_EMBEDDING_MODEL_PATH is required for the onnx backend")?
+                    .into(),
+                tokenizer_path: var("SHODH_EMBEDDING_TOKENIZER_PATH").map(PathBuf::from),
+                dimension: dimension()?,
+                pooling: var("SHODH_EMBEDDING_POOLING")
+                    .map(|p| p.parse())
+                    .transpose()?
+                    .unwrap_or_default(),
+                model_id: var("SHODH_EMBEDDING_MODEL"),
+                max_length: var("SHODH_EMBEDDING_MAX_LENGTH")
+                    .map(|n| n.parse())
+                    .transpose()
+                    .context("SHODH_EMBEDDING_MAX_LENGTH must be a positive integer")?
+                    .unwrap_or_else(default_max_length),
+            })),
+            "openai" => Ok(Self::OpenAi(OpenAiEmbeddingConfig {
+                api_key: var("SHODH_EMBEDDING_API_KEY"),
+                ..OpenAiEmbeddingConfig::new(
+                    var("SHODH_EMBEDDING_URL")
+                        .unwrap_or_else(|| "https://api.openai.com/v1".to_string()),
+                    var("SHODH_EMBEDDING_MODEL")
+                        .context("SHODH_EMBEDDING_MODEL is required for the openai backend")?,
+                    dimension()?,
+                )
+            })),
+            other => {
+                anyhow::bail!(
+                    "Unknown SHODH_EMBEDDING_BACKEND '{other}' (expected minilm, onnx or openai)"
+                )
+            }
+        }
+    }
+
+    /// Construct the embedder for this backend
+    pub fn build(&self) -> Result<Arc<dyn Embedder>> {
+        match self {
+            Self::MiniLm => Ok(Arc::new(
+                MiniLMEmbedder::new(EmbeddingConfig::default())
+                    .context("Failed to initialize MiniLM embedder (ONNX model)")?,
+            )),
+            Self::Onnx(onnx) => {
+                let tokenizer_path = onnx.tokenizer_path.clone().unwrap_or_else(|| {
+                    onnx.model_path
+                        .parent()
+                        .unwrap_or_else(|| Path::new("."))
+                        .join("tokenizer.json")
+                });
+                let model_id = onnx.model_id.clone().unwrap_or_else(|| {
+                    onnx.model_path
+                        .file_stem()
+                        .map(|s| s.to_string_lossy().to_string())
+                        .unwrap_or_else(|| "onnx".to_string())
+                });
+                let config = EmbeddingConfig {
+                    max_length: onnx.max_length,
+                    dimension: onnx.dimension,
+                    pooling: onnx.pooling,
+                    model_id: format!("onnx:{model_id}"),
+                    auto_download: false,
+                    ..EmbeddingConfig::with_paths(onnx.model_path.clone(), tokenizer_path)
+                };
+                Ok(Arc::new(MiniLMEmbedder::new(config).with_context(
+                    || format!("Failed to load ONNX embedder {}", onnx.model_path.display()),
+                )?))
+            }
+            Self::OpenAi(config) => Ok(Arc::new(ResilientEmbedder::with_defaults(Arc::new(
+                OpenAiEmbedder::new(config.clone())?,
+            )))),
+        }
+    }
+}
+
+/// Model identity recorded alongside persisted vectors
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EmbeddingFingerprint {
+    pub model_id: String,
+    pub dimension: usize,
+}
+
+impl EmbeddingFingerprint {
+    pub fn of(embedder: &dyn Embedder) -> Self {
+        Self {
+            model_id: embedder.model_id(),
+            dimension: embedder.dimension(),
+        }
+    }
+
+    /// Read the fingerprint stored in `dir`, if any
+    pub fn load(dir: &Path) -> Result<Option<Self>> {
+        let path = dir.join(FINGERPRINT_FILE);
+        if !path.exists() {
+            return Ok(None);
+        }
+        let data =
+            std::fs::read(&path).with_context(|| format!("Failed to read {}", path.display()))?;
+        Ok(Some(serde_json::from_slice(&data).with_context(|| {
+            format!("Failed to parse {}", path.display())
+        })?))
+    }
+
+    pub fn save(&self, dir: &Path) -> Result<()> {
+        std::fs::create_dir_all(dir)?;
+        std::fs::write(dir.join(FINGERPRINT_FILE), serde_json::to_vec_pretty(self)?)?;
+        Ok(())
+    }
+
+    /// Whether vectors stored in `dir` were built by a different model
+    ///
+    /// Stores without a fingerprint predate pluggable backends, so any data
+    /// they hold came from MiniLM (full or simplified, both 384 dims).
+    /// `has_data` is only consulted for such legacy stores.
+    pub fn requires_reembed(&self, dir: &Path, has_data: impl FnOnce() -> bool) -> Result<bool> {
+        match Self::load(dir)? {
+            Some(stored) => Ok(stored != *self),
+            None => Ok(!self.is_minilm() && has_data()),
+        }
+    }
+
+    fn is_minilm(&self) -> bool {
+        self.model_id.starts_with(MINILM_MODEL_ID) && self.dimension == MINILM_DIMENSION
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_backend_serde_tagging() {
+        let backend: EmbeddingBackend = serde_json::from_value(serde_json::json!({
+            "backend": "onnx",
+            "model_path": "/models/bge-small/model.onnx",
+            "dimension": 384,
+            "pooling": "cls"
+        }))
+        .unwrap();
+        match backend {
+            EmbeddingBackend::Onnx(cfg) => {
+                assert_eq!(cfg.pooling, PoolingStrategy::Cls);
+                assert_eq!(cfg.max_length, 256);
+                assert!(cfg.tokenizer_path.is_none());
+            }
+            other => panic!("unexpected backend {other:?}"),
+        }
+
+        let openai = EmbeddingBackend::OpenAi(OpenAiEmbeddingConfig {
+            api_key: Some("sk-secret".to_string()),
+            ..OpenAiEmbeddingConfig::new("http://localhost:8080/v1", "nomic", 768)
+        });
+        let json = serde_json::to_string(&openai).unwrap();
+        assert!(json.contains("\"backend\":\"openai\""));
+        assert!(
+            !json.contains("sk-secret"),
+            "api key must not be serialized"
+        );
+    }
+
+    #[test]
+    fn test_onnx_backend_requires_model_files() {
+        let dir = TempDir::new().unwrap();
+        let backend = EmbeddingBackend::Onnx(OnnxBackendConfig {
+            model_path: dir.path().join("missing.onnx"),
+            tokenizer_path: None,
+            dimension: 768,
+            pooling: PoolingStrategy::Mean,
+            model_id: None,
+            max_length: 256,
+        });
+        // Custom models are never replaced by the hash fallback
+        assert!(backend.build().is_err());
+    }
+
+    #[test]
+    fn test_fingerprint_reembed_rules() {
+        let dir = TempDir::new().unwrap();
+        let minilm = EmbeddingFingerprint {
+            model_id: MINILM_MODEL_ID.to_string(),
+            dimension: MINILM_DIMENSION,
+        };
+        let other = EmbeddingFingerprint {
+            model_id: "openai:text-embedding-3-small".to_string(),
+            dimension: 1536,
+        };
+
+        // Legacy store: data without a fingerprint is MiniLM
+        assert!(!minilm.requires_reembed(dir.path(), || true).unwrap());
+        assert!(other.requires_reembed(dir.path(), || true).unwrap());
+        assert!(!other.requires_reembed(dir.path(), || false).unwrap());
+
+        other.save(dir.path()).unwrap();
+        assert_eq!(
+            EmbeddingFingerprint::load(dir.path()).unwrap(),
+            Some(other.clone())
+        );
+        assert!(!other.requires_reembed(dir.path(), || true).unwrap());
+        assert!(minilm.requires_reembed(dir.path(), || true).unwrap());
+    }
+}
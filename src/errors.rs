@@ -43,6 +43,9 @@ pub enum AppError {
         max: usize,
     },
 
+    // Permission Errors (403)
+    Forbidden(String),
+
     // Resource Limit Errors (429)
     ResourceLimit {
         resource: String,
@@ -61,6 +64,10 @@ pub enum AppError {
     UserNotFound(String),
     TodoNotFound(String),
     ProjectNotFound(String),
+    ApiKeyNotFound(String),
+    WebhookNotFound(String),
+    SpaceNotFound(String),
+    SessionNotFound(String),
 
     // Conflict Errors (409)
     MemoryAlreadyExists(String),
@@ -114,11 +121,16 @@ impl AppError {
             Self::InvalidEmbeddings(_) => "INVALID_EMBEDDINGS",
             Self::ContentTooLarge { .. } => "CONTENT_TOO_LARGE",
             Self::AmbiguousMemoryId { .. } => "AMBIGUOUS_MEMORY_ID",
+            Self::Forbidden(_) => "FORBIDDEN",
             Self::ResourceLimit { .. } => "RESOURCE_LIMIT",
             Self::MemoryNotFound(_) => "MEMORY_NOT_FOUND",
             Self::UserNotFound(_) => "USER_NOT_FOUND",
             Self::TodoNotFound(_) => "TODO_NOT_FOUND",
             Self::ProjectNotFound(_) => "PROJECT_NOT_FOUND",
+            Self::ApiKeyNotFound(_) => "API_KEY_NOT_FOUND",
+            Self::WebhookNotFound(_) => "WEBHOOK_NOT_FOUND",
+            Self::SpaceNotFound(_) => "SPACE_NOT_FOUND",
+            Self::SessionNotFound(_) => "SESSION_NOT_FOUND",
             Self::MemoryAlreadyExists(_) => "MEMORY_ALREADY_EXISTS",
             Self::StorageError(_) => "STORAGE_ERROR",
             Self::DatabaseError(_) => "DATABASE_ERROR",
@@ -141,12 +153,18 @@ impl AppError {
             | Self::ContentTooLarge { .. }
             | Self::AmbiguousMemoryId { .. } => StatusCode::BAD_REQUEST,
 
+            Self::Forbidden(_) => StatusCode::FORBIDDEN,
+
             Self::ResourceLimit { .. } => StatusCode::TOO_MANY_REQUESTS,
 
             Self::MemoryNotFound(_)
             | Self::UserNotFound(_)
             | Self::TodoNotFound(_)
-            | Self::ProjectNotFound(_) => StatusCode::NOT_FOUND,
+            | Self::ProjectNotFound(_)
+            | Self::ApiKeyNotFound(_)
+            | Self::WebhookNotFound(_)
+            | Self::SpaceNotFound(_)
+            | Self::SessionNotFound(_) => StatusCode::NOT_FOUND,
 
             Self::MemoryAlreadyExists(_) => StatusCode::CONFLICT,
 
@@ -177,6 +195,7 @@ impl AppError {
             Self::AmbiguousMemoryId { prefix, count } => {
                 format!("Ambiguous memory ID prefix '{prefix}': matches {count} memories. Use a longer prefix or full UUID.")
             }
+            Self::Forbidden(msg) => format!("Forbidden: {msg}"),
             Self::ResourceLimit {
                 resource,
                 current,
@@ -188,6 +207,10 @@ impl AppError {
             Self::UserNotFound(id) => format!("User not found: {id}"),
             Self::TodoNotFound(id) => format!("Todo not found: {id}"),
             Self::ProjectNotFound(id) => format!("Project not found: {id}"),
+            Self::ApiKeyNotFound(id) => format!("API key not found: {id}"),
+            Self::WebhookNotFound(id) => format!("Webhook not found: {id}"),
+            Self::SpaceNotFound(id) => format!("Space not found: {id}"),
+            Self::SessionNotFound(id) => format!("Session not found: {id}"),
             Self::MemoryAlreadyExists(id) => format!("Memory already exists: {id}"),
             Self::StorageError(msg) => format!("Storage error: {msg}"),
             Self::DatabaseError(msg) => format!("Database error: {msg}"),
@@ -293,6 +316,10 @@ mod tests {
             AppError::MemoryNotFound("123".to_string()).status_code(),
             StatusCode::NOT_FOUND
         );
+        assert_eq!(
+            AppError::Forbidden("read-only".to_string()).status_code(),
+            StatusCode::FORBIDDEN
+        );
         assert_eq!(
             AppError::StorageError("failed".to_string()).status_code(),
             StatusCode::INTERNAL_SERVER_ERROR
@@ -0,0 +1,978 @@
+//! Streaming export and import of every per-user store
+//!
+//! Memories, todo projects, todos, knowledge graph entities and
+//! relationships, semantic facts, temporal facts, lineage edges and file
+//! memories are exported as one ordered stream of [`ExportRecord`]s, either
+//! as newline-delimited JSON or as Parquet with one row group per page.
+//!
+//! Stores are read a page at a time straight from RocksDB, so memory use is
+//! bounded by the page size however much a user has stored. Every record
+//! carries an opaque cursor: handing the last cursor received back to the
+//! exporter resumes right after that record, and handing it to the importer
+//! skips everything an interrupted import already applied.
+
+pub mod parquet;
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, TimeZone, Utc};
+use parking_lot::RwLock;
+use rocksdb::{Direction, IteratorMode, DB};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::str::FromStr;
+use std::sync::Arc;
+use uuid::Uuid;
+
+use self::parquet::{ColumnSpec, ColumnType, ColumnValues, ParquetReader, ParquetWriter};
+use crate::graph_memory::{decode_entity, EntityNode, GraphMemory, RelationshipEdge};
+use crate::memory::facts::decode_fact;
+use crate::memory::{
+    FileMemory, FileMemoryStore, LineageEdge, Memory, MemorySystem, Project, SemanticFact,
+    TemporalFact, Todo, TodoStore,
+};
+use ::parquet::file::reader::ChunkReader;
+
+/// Version of the record layout, stored in Parquet footers
+pub const SCHEMA_VERSION: &str = "1";
+
+/// Parquet footer key holding [`SCHEMA_VERSION`]
+const SCHEMA_VERSION_KEY: &str = "shodh.export.schema_version";
+
+/// Default records per page (and per Parquet row group)
+pub const DEFAULT_PAGE_SIZE: usize = 500;
+
+/// Import errors kept in the report; the rest are only counted
+const MAX_REPORTED_ERRORS: usize = 20;
+
+/// Column layout of Parquet exports
+pub const PARQUET_SCHEMA: [ColumnSpec; 6] = [
+    ColumnSpec {
+        name: "store",
+        column_type: ColumnType::Utf8,
+        optional: false,
+    },
+    ColumnSpec {
+        name: "id",
+        column_type: ColumnType::Utf8,
+        optional: false,
+    },
+    ColumnSpec {
+        name: "cursor",
+        column_type: ColumnType::Utf8,
+        optional: false,
+    },
+    ColumnSpec {
+        name: "content",
+        column_type: ColumnType::Utf8,
+        optional: true,
+    },
+    ColumnSpec {
+        name: "created_at",
+        column_type: ColumnType::TimestampMillis,
+        optional: true,
+    },
+    ColumnSpec {
+        name: "data",
+        column_type: ColumnType::Json,
+        optional: false,
+    },
+];
+
+/// Exportable store, in export order
+///
+/// Entities precede relationships so an import can resolve edge endpoints.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ExportStore {
+    Memories,
+    Projects,
+    Todos,
+    Entities,
+    Relationships,
+    Facts,
+    TemporalFacts,
+    Lineage,
+    Files,
+}
+
+impl ExportStore {
+    pub const ALL: [ExportStore; 9] = [
+        Self::Memories,
+        Self::Projects,
+        Self::Todos,
+        Self::Entities,
+        Self::Relationships,
+        Self::Facts,
+        Self::TemporalFacts,
+        Self::Lineage,
+        Self::Files,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Memories => "memories",
+            Self::Projects => "projects",
+            Self::Todos => "todos",
+            Self::Entities => "entities",
+            Self::Relationships => "relationships",
+            Self::Facts => "facts",
+            Self::TemporalFacts => "temporal_facts",
+            Self::Lineage => "lineage",
+            Self::Files => "files",
+        }
+    }
+}
+
+impl fmt::Display for ExportStore {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for ExportStore {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|store| store.as_str() == s)
+            .with_context(|| format!("Unknown store '{s}'"))
+    }
+}
+
+/// Position just after one record: its store and raw RocksDB key
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct ExportCursor {
+    pub store: ExportStore,
+    key: Vec<u8>,
+}
+
+impl ExportCursor {
+    pub fn encode(&self) -> String {
+        format!("{}:{}", self.store, hex::encode(&self.key))
+    }
+
+    pub fn parse(cursor: &str) -> Result<Self> {
+        let (store, key) = cursor
+            .split_once(':')
+            .context("Cursor must be '<store>:<hex key>'")?;
+        Ok(Self {
+            store: store.parse()?,
+            key: hex::decode(key).context("Cursor key is not hex")?,
+        })
+    }
+}
+
+/// Output encoding of an export
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportFormat {
+    /// One JSON record per line
+    #[default]
+    Jsonl,
+    /// Parquet with the [`PARQUET_SCHEMA`] columns
+    Parquet,
+}
+
+impl ExportFormat {
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            Self::Jsonl => "application/x-ndjson",
+            Self::Parquet => "application/vnd.apache.parquet",
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Self::Jsonl => "jsonl",
+            Self::Parquet => "parquet",
+        }
+    }
+}
+
+/// One exported item, identical in shape across stores
+///
+/// `content` and `created_at` are lifted out of `data` (the store's native
+/// JSON form) so exports can be queried without parsing every document.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExportRecord {
+    pub store: ExportStore,
+    pub id: String,
+    pub cursor: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub content: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub created_at: Option<DateTime<Utc>>,
+    pub data: serde_json::Value,
+}
+
+/// RocksDB handles an export reads from
+///
+/// Cloned out of the live stores up front, so no store lock is held while
+/// the export streams.
+#[derive(Clone)]
+pub struct ExportSources {
+    pub user_id: String,
+    /// Main memory DB, which also holds facts, temporal facts and lineage
+    pub memory_db: Arc<DB>,
+    pub entities_db: Arc<DB>,
+    pub relationships_db: Arc<DB>,
+    pub project_db: Arc<DB>,
+    pub todo_db: Arc<DB>,
+    pub file_db: Arc<DB>,
+}
+
+impl ExportSources {
+    /// Database and key prefix holding `store`'s records for this user
+    fn range(&self, store: ExportStore) -> (&Arc<DB>, Vec<u8>) {
+        let user = &self.user_id;
+        match store {
+            ExportStore::Memories => (&self.memory_db, Vec::new()),
+            ExportStore::Projects => (&self.project_db, format!("{user}:").into_bytes()),
+            ExportStore::Todos => (&self.todo_db, format!("{user}:").into_bytes()),
+            ExportStore::Entities => (&self.entities_db, Vec::new()),
+            ExportStore::Relationships => (&self.relationships_db, Vec::new()),
+            ExportStore::Facts => (&self.memory_db, format!("facts:{user}:").into_bytes()),
+            ExportStore::TemporalFacts => (
+                &self.memory_db,
+                format!("temporal_facts:{user}:").into_bytes(),
+            ),
+            ExportStore::Lineage => (
+                &self.memory_db,
+                format!("lineage:edges:{user}:").into_bytes(),
+            ),
+            ExportStore::Files => (&self.file_db, format!("{user}:").into_bytes()),
+        }
+    }
+}
+
+/// Decoded record fields: id, content, created_at, data
+type Decoded = (
+    String,
+    Option<String>,
+    Option<DateTime<Utc>>,
+    serde_json::Value,
+);
+
+fn decode_bincode<T: serde::de::DeserializeOwned>(value: &[u8]) -> Result<T> {
+    Ok(bincode::serde::decode_from_slice(value, bincode::config::standard())?.0)
+}
+
+/// Decode one stored value; `None` for keys in the range that are not records
+fn decode(store: ExportStore, key: &[u8], value: &[u8]) -> Result<Option<Decoded>> {
+    let decoded = match store {
+        ExportStore::Memories => {
+            // The main DB also holds prefixed fact/lineage/stats keys
+            if key.len() != 16 {
+                return Ok(None);
+            }
+            let (memory, _) = crate::memory::storage::deserialize_memory(value)?;
+            (
+                memory.id.0.to_string(),
+                Some(memory.experience.content.clone()),
+                Some(memory.created_at),
+                serde_json::to_value(&memory)?,
+            )
+        }
+        ExportStore::Projects => {
+            let project: Project = serde_json::from_slice(value)?;
+            (
+                project.id.0.to_string(),
+                Some(project.name.clone()),
+                Some(project.created_at),
+                serde_json::to_value(&project)?,
+            )
+        }
+        ExportStore::Todos => {
+            let todo: Todo = serde_json::from_slice(value)?;
+            (
+                todo.id.0.to_string(),
+                Some(todo.content.clone()),
+                Some(todo.created_at),
+                serde_json::to_value(&todo)?,
+            )
+        }
+        ExportStore::Entities => {
+            let entity = decode_entity(value)?;
+            (
+                entity.uuid.to_string(),
+                Some(entity.name.clone()),
+                Some(entity.created_at),
+                serde_json::to_value(&entity)?,
+            )
+        }
+        ExportStore::Relationships => {
+            let edge: RelationshipEdge = decode_bincode(value)?;
+            (
+                edge.uuid.to_string(),
+                Some(edge.context.clone()).filter(|c| !c.is_empty()),
+                Some(edge.created_at),
+                serde_json::to_value(&edge)?,
+            )
+        }
+        ExportStore::Facts => {
+            let fact = decode_fact(value)?;
+            (
+                fact.id.clone(),
+                Some(fact.fact.clone()),
+                Some(fact.created_at),
+                serde_json::to_value(&fact)?,
+            )
+        }
+        ExportStore::TemporalFacts => {
+            let fact: TemporalFact = decode_bincode(value)?;
+            (
+                fact.id.clone(),
+                Some(fact.source_text.clone()),
+                Some(fact.conversation_date),
+                serde_json::to_value(&fact)?,
+            )
+        }
+        ExportStore::Lineage => {
+            let edge: LineageEdge = decode_bincode(value)?;
+            (
+                edge.id.clone(),
+                None,
+                Some(edge.created_at),
+                serde_json::to_value(&edge)?,
+            )
+        }
+        ExportStore::Files => {
+            let file: FileMemory = serde_json::from_slice(value)?;
+            (
+                file.id.0.to_string(),
+                Some(file.path.clone()),
+                Some(file.created_at),
+                serde_json::to_value(&file)?,
+            )
+        }
+    };
+    Ok(Some(decoded))
+}
+
+/// Result of scanning one page of a store
+struct ScanPage {
+    records: Vec<ExportRecord>,
+    last_key: Option<Vec<u8>>,
+    exhausted: bool,
+}
+
+/// Pages through the selected stores in export order
+pub struct Exporter {
+    sources: ExportSources,
+    stores: Vec<ExportStore>,
+    position: Option<ExportCursor>,
+    page_size: usize,
+}
+
+impl Exporter {
+    /// Export `stores`, starting right after `after` when resuming
+    ///
+    /// Fails if `after` does not point into the user's own range of its
+    /// store, e.g. a cursor from another user's export.
+    pub fn new(
+        sources: ExportSources,
+        stores: &[ExportStore],
+        after: Option<ExportCursor>,
+    ) -> Result<Self> {
+        if let Some(cursor) = &after {
+            let (_, prefix) = sources.range(cursor.store);
+            if !cursor.key.starts_with(&prefix) {
+                anyhow::bail!(
+                    "Cursor does not belong to this user's {} store",
+                    cursor.store
+                );
+            }
+        }
+        let mut stores = stores.to_vec();
+        stores.sort();
+        stores.dedup();
+        Ok(Self {
+            sources,
+            stores,
+            position: after,
+            page_size: DEFAULT_PAGE_SIZE,
+        })
+    }
+
+    pub fn with_page_size(mut self, page_size: usize) -> Self {
+        self.page_size = page_size.max(1);
+        self
+    }
+
+    /// Next page of records, or `None` once every store is exhausted
+    ///
+    /// Each page opens a fresh iterator, so writes made while an export
+    /// runs never block on it (and may or may not be included).
+    pub fn next_page(&mut self) -> Result<Option<Vec<ExportRecord>>> {
+        for store in self.stores.clone() {
+            let mut after = match &self.position {
+                Some(cursor) if cursor.store > store => continue,
+                Some(cursor) if cursor.store == store => Some(cursor.key.clone()),
+                _ => None,
+            };
+            loop {
+                let page = self.scan(store, after.as_deref())?;
+                if let Some(key) = page.last_key {
+                    after = Some(key.clone());
+                    self.position = Some(ExportCursor { store, key });
+                }
+                if !page.records.is_empty() {
+                    return Ok(Some(page.records));
+                }
+                if page.exhausted {
+                    break;
+                }
+            }
+        }
+        Ok(None)
+    }
+
+    /// Read up to `page_size` keys of `store` after `after`
+    fn scan(&self, store: ExportStore, after: Option<&[u8]>) -> Result<ScanPage> {
+        let (db, prefix) = self.sources.range(store);
+        let start = after.unwrap_or(&prefix);
+        let mut page = ScanPage {
+            records: Vec::new(),
+            last_key: None,
+            exhausted: true,
+        };
+
+        let mut scanned = 0;
+        for item in db.iterator(IteratorMode::From(start, Direction::Forward)) {
+            let (key, value) = item?;
+            if !key.starts_with(&prefix) {
+                return Ok(page);
+            }
+            if after == Some(&key[..]) {
+                continue;
+            }
+
+            let cursor = ExportCursor {
+                store,
+                key: key.to_vec(),
+            };
+            match decode(store, &key, &value) {
+                Ok(Some((id, content, created_at, data))) => page.records.push(ExportRecord {
+                    store,
+                    id,
+                    cursor: cursor.encode(),
+                    content,
+                    created_at,
+                    data,
+                }),
+                Ok(None) => {}
+                Err(e) => tracing::warn!(
+                    store = %store,
+                    key = %hex::encode(&key),
+                    error = %e,
+                    "Skipping undecodable record during export"
+                ),
+            }
+            page.last_key = Some(cursor.key);
+
+            scanned += 1;
+            if scanned >= self.page_size {
+                page.exhausted = false;
+                return Ok(page);
+            }
+        }
+        Ok(page)
+    }
+}
+
+/// Serializes pages of records into the chosen format
+pub enum RecordEncoder {
+    Jsonl,
+    Parquet(Box<ParquetWriter>),
+}
+
+impl RecordEncoder {
+    pub fn new(format: ExportFormat) -> Self {
+        match format {
+            ExportFormat::Jsonl => Self::Jsonl,
+            ExportFormat::Parquet => Self::Parquet(Box::new(
+                ParquetWriter::new(&PARQUET_SCHEMA)
+                    .with_key_value(SCHEMA_VERSION_KEY, SCHEMA_VERSION),
+            )),
+        }
+    }
+
+    /// Bytes for one page (a Parquet row group)
+    pub fn encode(&mut self, records: &[ExportRecord]) -> Result<Vec<u8>> {
+        match self {
+            Self::Jsonl => {
+                let mut out = Vec::new();
+                for record in records {
+                    serde_json::to_writer(&mut out, record)?;
+                    out.push(b'\n');
+                }
+                Ok(out)
+            }
+            Self::Parquet(writer) => writer.write_row_group(&records_to_columns(records)),
+        }
+    }
+
+    /// Trailing bytes that complete the output
+    pub fn finish(self) -> Result<Vec<u8>> {
+        match self {
+            Self::Jsonl => Ok(Vec::new()),
+            Self::Parquet(writer) => writer.finish(),
+        }
+    }
+}
+
+fn records_to_columns(records: &[ExportRecord]) -> Vec<ColumnValues> {
+    let strings = |f: &dyn Fn(&ExportRecord) -> Option<String>| {
+        ColumnValues::Strings(records.iter().map(f).collect())
+    };
+    vec![
+        strings(&|r| Some(r.store.to_string())),
+        strings(&|r| Some(r.id.clone())),
+        strings(&|r| Some(r.cursor.clone())),
+        strings(&|r| r.content.clone()),
+        ColumnValues::Int64(
+            records
+                .iter()
+                .map(|r| r.created_at.map(|t| t.timestamp_millis()))
+                .collect(),
+        ),
+        strings(&|r| Some(r.data.to_string())),
+    ]
+}
+
+fn records_from_columns(columns: Vec<ColumnValues>) -> Result<Vec<ExportRecord>> {
+    let mut columns = columns.into_iter();
+    let mut strings = || match columns.next() {
+        Some(ColumnValues::Strings(values)) => Ok(values),
+        _ => anyhow::bail!("Unexpected Parquet column layout"),
+    };
+    let (stores, ids, cursors, contents) = (strings()?, strings()?, strings()?, strings()?);
+    let created = match columns.next() {
+        Some(ColumnValues::Int64(values)) => values,
+        _ => anyhow::bail!("Unexpected Parquet column layout"),
+    };
+    let data = match columns.next() {
+        Some(ColumnValues::Strings(values)) => values,
+        _ => anyhow::bail!("Unexpected Parquet column layout"),
+    };
+
+    let mut records = Vec::with_capacity(stores.len());
+    for (i, store) in stores.into_iter().enumerate() {
+        records.push(ExportRecord {
+            store: store.unwrap_or_default().parse()?,
+            id: ids[i].clone().unwrap_or_default(),
+            cursor: cursors[i].clone().unwrap_or_default(),
+            content: contents[i].clone(),
+            created_at: created[i].and_then(|ms| Utc.timestamp_millis_opt(ms).single()),
+            data: serde_json::from_str(data[i].as_deref().unwrap_or("null"))
+                .context("Invalid JSON in data column")?,
+        });
+    }
+    Ok(records)
+}
+
+/// Reads a Parquet export back one row group at a time
+pub struct ParquetRecordReader<R: ChunkReader> {
+    reader: ParquetReader<R>,
+    next_group: usize,
+}
+
+impl<R: ChunkReader + 'static> ParquetRecordReader<R> {
+    pub fn open(input: R) -> Result<Self> {
+        let reader = ParquetReader::open(input, &PARQUET_SCHEMA)?;
+        match reader.key_value(SCHEMA_VERSION_KEY) {
+            Some(SCHEMA_VERSION) | None => {}
+            Some(other) => anyhow::bail!("Unsupported export schema version {other}"),
+        }
+        Ok(Self {
+            reader,
+            next_group: 0,
+        })
+    }
+
+    /// Records of the next row group, or `None` at the end of the file
+    pub fn next_group(&mut self) -> Result<Option<Vec<ExportRecord>>> {
+        if self.next_group >= self.reader.num_row_groups() {
+            return Ok(None);
+        }
+        let columns = self.reader.read_row_group(self.next_group)?;
+        self.next_group += 1;
+        records_from_columns(columns).map(Some)
+    }
+}
+
+/// Stores an import writes into
+pub struct ImportTargets {
+    pub user_id: String,
+    pub memory: Arc<RwLock<MemorySystem>>,
+    pub graph: Arc<RwLock<GraphMemory>>,
+    pub todos: Arc<TodoStore>,
+    pub files: Arc<FileMemoryStore>,
+}
+
+/// Outcome of an import
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ImportReport {
+    /// Records written, per store
+    pub imported: BTreeMap<ExportStore, usize>,
+    /// Records at or before `resume_after`
+    pub skipped: usize,
+    pub failed: usize,
+    /// First few failures, for diagnosis
+    pub errors: Vec<String>,
+    /// Cursor of the last record processed; pass as `resume_after` to continue
+    pub last_cursor: Option<String>,
+}
+
+impl ImportReport {
+    pub fn total_imported(&self) -> usize {
+        self.imported.values().sum()
+    }
+}
+
+/// Applies exported records to the live stores, preserving IDs
+///
+/// Re-importing a record overwrites the stored copy, so replaying part of
+/// an export after an interruption is harmless.
+pub struct Importer {
+    targets: ImportTargets,
+    resume_after: Option<ExportCursor>,
+    /// Exported entity UUIDs that merged into a differently-keyed entity
+    entity_remap: HashMap<Uuid, Uuid>,
+    report: ImportReport,
+}
+
+impl Importer {
+    pub fn new(targets: ImportTargets, resume_after: Option<ExportCursor>) -> Self {
+        Self {
+            targets,
+            resume_after,
+            entity_remap: HashMap::new(),
+            report: ImportReport::default(),
+        }
+    }
+
+    /// Count a record that could not be parsed at all
+    pub fn record_failure(&mut self, error: impl fmt::Display) {
+        self.report.failed += 1;
+        if self.report.errors.len() < MAX_REPORTED_ERRORS {
+            self.report.errors.push(error.to_string());
+        }
+    }
+
+    /// Apply a batch of records in order
+    pub fn apply(&mut self, records: Vec<ExportRecord>) -> Result<()> {
+        let mut memories: Vec<(Memory, String)> = Vec::new();
+        for record in records {
+            let cursor = match ExportCursor::parse(&record.cursor) {
+                Ok(cursor) => cursor,
+                Err(e) => {
+                    self.record_failure(format!("{} {}: {e}", record.store, record.id));
+                    continue;
+                }
+            };
+            if self.resume_after.as_ref().is_some_and(|r| cursor <= *r) {
+                self.report.skipped += 1;
+                continue;
+            }
+
+            if record.store == ExportStore::Memories {
+                match serde_json::from_value::<Memory>(record.data) {
+                    Ok(memory) => memories.push((memory, record.cursor)),
+                    Err(e) => self.record_failure(format!("memories {}: {e}", record.id)),
+                }
+                continue;
+            }
+
+            self.flush_memories(&mut memories)?;
+            match self.apply_one(&record) {
+                Ok(()) => *self.report.imported.entry(record.store).or_default() += 1,
+                Err(e) => self.record_failure(format!("{} {}: {e:#}", record.store, record.id)),
+            }
+            self.report.last_cursor = Some(record.cursor);
+        }
+        self.flush_memories(&mut memories)
+    }
+
+    pub fn finish(self) -> ImportReport {
+        self.report
+    }
+
+    fn flush_memories(&mut self, memories: &mut Vec<(Memory, String)>) -> Result<()> {
+        if memories.is_empty() {
+            return Ok(());
+        }
+        let batch: Vec<Memory> = memories.iter().map(|(m, _)| m.clone()).collect();
+        let imported = self.targets.memory.read().import_memories(&batch)?;
+        *self
+            .report
+            .imported
+            .entry(ExportStore::Memories)
+            .or_default() += imported;
+        self.report.last_cursor = memories.pop().map(|(_, cursor)| cursor);
+        memories.clear();
+        Ok(())
+    }
+
+    fn apply_one(&mut self, record: &ExportRecord) -> Result<()> {
+        let user_id = &self.targets.user_id;
+        let data = record.data.clone();
+        match record.store {
+            ExportStore::Memories => unreachable!("memories are imported in batches"),
+            ExportStore::Projects => {
+                let mut project: Project = serde_json::from_value(data)?;
+                project.user_id = user_id.clone();
+                self.targets.todos.store_project(&project)
+            }
+            ExportStore::Todos => {
+                let mut todo: Todo = serde_json::from_value(data)?;
+                todo.user_id = user_id.clone();
+                self.targets.todos.import_todo(todo).map(|_| ())
+            }
+            ExportStore::Entities => {
+                let entity: EntityNode = serde_json::from_value(data)?;
+                let exported = entity.uuid;
+                let stored = self.targets.graph.read().import_entity(entity)?;
+                if stored != exported {
+                    self.entity_remap.insert(exported, stored);
+                }
+                Ok(())
+            }
+            ExportStore::Relationships => {
+                let mut edge: RelationshipEdge = serde_json::from_value(data)?;
+                for endpoint in [&mut edge.from_entity, &mut edge.to_entity] {
+                    if let Some(stored) = self.entity_remap.get(endpoint) {
+                        *endpoint = *stored;
+                    }
+                }
+                self.targets
+                    .graph
+                    .read()
+                    .import_relationship(edge)
+                    .map(|_| ())
+            }
+            ExportStore::Facts => {
+                let fact: SemanticFact = serde_json::from_value(data)?;
+                self.targets
+                    .memory
+                    .read()
+                    .fact_store()
+                    .store(user_id, &fact)
+            }
+            ExportStore::TemporalFacts => {
+                let fact: TemporalFact = serde_json::from_value(data)?;
+                self.targets
+                    .memory
+                    .read()
+                    .temporal_fact_store()
+                    .store(user_id, &fact)
+            }
+            ExportStore::Lineage => {
+                let edge: LineageEdge = serde_json::from_value(data)?;
+                self.targets
+                    .memory
+                    .read()
+                    .lineage_graph()
+                    .store_edge(user_id, &edge)
+            }
+            ExportStore::Files => {
+                let mut file: FileMemory = serde_json::from_value(data)?;
+                file.user_id = user_id.clone();
+                self.targets.files.store(&file)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rocksdb::Options;
+    use tempfile::TempDir;
+
+    fn open(dir: &TempDir, name: &str) -> Arc<DB> {
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        opts.set_compression_type(rocksdb::DBCompressionType::Lz4);
+        Arc::new(DB::open(&opts, dir.path().join(name)).unwrap())
+    }
+
+    fn fact(id: &str) -> SemanticFact {
+        SemanticFact {
+            id: id.to_string(),
+            fact: format!("fact {id}"),
+            confidence: 0.9,
+            support_count: 1,
+            source_memories: Vec::new(),
+            related_entities: vec!["rust".to_string()],
+            created_at: Utc::now(),
+            last_reinforced: Utc::now(),
+            fact_type: Default::default(),
+            valid_from: None,
+            valid_to: None,
+            superseded_by: None,
+        }
+    }
+
+    fn sources(dir: &TempDir) -> ExportSources {
+        ExportSources {
+            user_id: "alice".to_string(),
+            memory_db: open(dir, "memories"),
+            entities_db: open(dir, "entities"),
+            relationships_db: open(dir, "relationships"),
+            project_db: open(dir, "projects"),
+            todo_db: open(dir, "todos"),
+            file_db: open(dir, "files"),
+        }
+    }
+
+    #[test]
+    fn test_cursor_roundtrip_and_order() {
+        let a = ExportCursor {
+            store: ExportStore::Todos,
+            key: b"alice:zz".to_vec(),
+        };
+        let b = ExportCursor {
+            store: ExportStore::Entities,
+            key: vec![0],
+        };
+        assert_eq!(ExportCursor::parse(&a.encode()).unwrap(), a);
+        assert!(a < b, "store order wins over key order");
+        assert!(ExportCursor::parse("nope:00").is_err());
+        assert!(ExportCursor::parse("facts:not-hex").is_err());
+    }
+
+    #[test]
+    fn test_paged_export_resumes_from_cursor() {
+        let dir = TempDir::new().unwrap();
+        let sources = sources(&dir);
+        let facts = crate::memory::SemanticFactStore::new(sources.memory_db.clone());
+        for i in 0..7 {
+            facts.store("alice", &fact(&format!("f{i}"))).unwrap();
+        }
+        facts.store("bob", &fact("other-user")).unwrap();
+
+        let mut exporter = Exporter::new(sources.clone(), &ExportStore::ALL, None)
+            .unwrap()
+            .with_page_size(3);
+        let mut ids = Vec::new();
+        let mut resume = None;
+        while let Some(page) = exporter.next_page().unwrap() {
+            assert!(page.len() <= 3);
+            if ids.len() == 3 {
+                resume = Some(page[0].cursor.clone());
+            }
+            ids.extend(page.into_iter().map(|r| r.id));
+        }
+        assert_eq!(ids, (0..7).map(|i| format!("f{i}")).collect::<Vec<_>>());
+
+        // Resuming after the 4th record yields only the remaining three
+        let after = ExportCursor::parse(&resume.unwrap()).unwrap();
+        let mut exporter = Exporter::new(sources, &[ExportStore::Facts], Some(after)).unwrap();
+        let rest: Vec<String> = exporter
+            .next_page()
+            .unwrap()
+            .unwrap()
+            .into_iter()
+            .map(|r| r.id)
+            .collect();
+        assert_eq!(rest, vec!["f4", "f5", "f6"]);
+        assert!(exporter.next_page().unwrap().is_none());
+    }
+
+    #[test]
+    fn test_foreign_cursor_is_rejected() {
+        let dir = TempDir::new().unwrap();
+        let sources = sources(&dir);
+        let foreign = ExportCursor {
+            store: ExportStore::Facts,
+            key: b"facts:bob:f0".to_vec(),
+        };
+        assert!(Exporter::new(sources, &[ExportStore::Facts], Some(foreign)).is_err());
+    }
+
+    #[test]
+    fn test_parquet_and_jsonl_encoding_roundtrip() {
+        let dir = TempDir::new().unwrap();
+        let sources = sources(&dir);
+        let facts = crate::memory::SemanticFactStore::new(sources.memory_db.clone());
+        for i in 0..5 {
+            facts.store("alice", &fact(&format!("f{i}"))).unwrap();
+        }
+        let mut exporter = Exporter::new(sources, &[ExportStore::Facts], None)
+            .unwrap()
+            .with_page_size(2);
+
+        let mut parquet = RecordEncoder::new(ExportFormat::Parquet);
+        let mut jsonl = RecordEncoder::new(ExportFormat::Jsonl);
+        let (mut parquet_bytes, mut jsonl_bytes, mut exported) =
+            (Vec::new(), Vec::new(), Vec::new());
+        while let Some(page) = exporter.next_page().unwrap() {
+            parquet_bytes.extend(parquet.encode(&page).unwrap());
+            jsonl_bytes.extend(jsonl.encode(&page).unwrap());
+            exported.extend(page);
+        }
+        parquet_bytes.extend(parquet.finish().unwrap());
+
+        let mut reader = ParquetRecordReader::open(bytes::Bytes::from(parquet_bytes)).unwrap();
+        let mut from_parquet = Vec::new();
+        while let Some(group) = reader.next_group().unwrap() {
+            from_parquet.extend(group);
+        }
+        let from_jsonl: Vec<ExportRecord> = jsonl_bytes
+            .split(|b| *b == b'\n')
+            .filter(|line| !line.is_empty())
+            .map(|line| serde_json::from_slice(line).unwrap())
+            .collect();
+
+        assert_eq!(exported.len(), 5);
+        // Parquet timestamps are millisecond precision
+        for (original, parsed) in exported.iter().zip(&from_parquet) {
+            assert_eq!(original.cursor, parsed.cursor);
+            assert_eq!(original.data, parsed.data);
+            assert_eq!(
+                original.created_at.map(|t| t.timestamp_millis()),
+                parsed.created_at.map(|t| t.timestamp_millis())
+            );
+        }
+        assert_eq!(from_jsonl, exported);
+    }
+
+    #[test]
+    fn test_legacy_fact_is_exported() {
+        let dir = TempDir::new().unwrap();
+        let sources = sources(&dir);
+        let created = Utc::now();
+        // SemanticFact as stored before validity tracking
+        let legacy = (
+            "legacy".to_string(),
+            "Rust is fast".to_string(),
+            0.7f32,
+            2usize,
+            Vec::<crate::memory::MemoryId>::new(),
+            vec!["rust".to_string()],
+            created,
+            created,
+            crate::memory::FactType::Definition,
+        );
+        let value = bincode::serde::encode_to_vec(&legacy, bincode::config::standard()).unwrap();
+        sources
+            .memory_db
+            .put(b"facts:alice:legacy", &value)
+            .unwrap();
+
+        let mut exporter = Exporter::new(sources, &[ExportStore::Facts], None).unwrap();
+        let page = exporter.next_page().unwrap().unwrap();
+        assert_eq!(page.len(), 1);
+        assert_eq!(page[0].id, "legacy");
+        assert_eq!(page[0].data["fact"], "Rust is fast");
+        assert!(page[0].data["valid_to"].is_null());
+
+        let mut jsonl = RecordEncoder::new(ExportFormat::Jsonl);
+        let line = jsonl.encode(&page).unwrap();
+        let parsed: ExportRecord = serde_json::from_slice(&line).unwrap();
+        assert_eq!(parsed, page[0]);
+    }
+}
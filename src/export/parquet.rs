@@ -0,0 +1,589 @@
+//! Apache Parquet writer and reader for flat exports
+//!
+//! A thin layer over the `parquet` crate for the streaming export's flat
+//! schema of `BYTE_ARRAY` and `INT64` columns. Files are produced one row
+//! group at a time (Snappy-compressed, dictionary-encoded where it pays) so
+//! a writer never holds more than a single group, and are read back the
+//! same way. Reading accepts whatever encodings and compression codecs
+//! other writers chose, nullable columns where the schema has required ones
+//! (failing only on an actual null), and timestamps in any unit including
+//! legacy `INT96`.
+
+use ::parquet::basic::{Compression, LogicalType, Repetition, TimeUnit, Type as PhysicalType};
+use ::parquet::column::reader::ColumnReader;
+use ::parquet::column::writer::ColumnWriter;
+use ::parquet::data_type::ByteArray;
+use ::parquet::file::metadata::KeyValue;
+use ::parquet::file::properties::WriterProperties;
+use ::parquet::file::reader::{ChunkReader, FileReader};
+use ::parquet::file::serialized_reader::SerializedFileReader;
+use ::parquet::file::writer::SerializedFileWriter;
+use ::parquet::schema::types::{ColumnDescriptor, Type};
+use anyhow::{Context, Result};
+use std::sync::Arc;
+
+/// Logical column type
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColumnType {
+    /// UTF-8 string (`BYTE_ARRAY` annotated `STRING`)
+    Utf8,
+    /// JSON document (`BYTE_ARRAY` annotated `JSON`)
+    Json,
+    /// Milliseconds since the Unix epoch (`INT64` annotated `TIMESTAMP(MILLIS)`)
+    TimestampMillis,
+}
+
+impl ColumnType {
+    fn physical(self) -> PhysicalType {
+        match self {
+            Self::Utf8 | Self::Json => PhysicalType::BYTE_ARRAY,
+            Self::TimestampMillis => PhysicalType::INT64,
+        }
+    }
+
+    fn logical(self) -> LogicalType {
+        match self {
+            Self::Utf8 => LogicalType::String,
+            Self::Json => LogicalType::Json,
+            Self::TimestampMillis => LogicalType::Timestamp {
+                is_adjusted_to_u_t_c: true,
+                unit: TimeUnit::MILLIS(Default::default()),
+            },
+        }
+    }
+}
+
+/// One column of a flat schema
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ColumnSpec {
+    pub name: &'static str,
+    pub column_type: ColumnType,
+    pub optional: bool,
+}
+
+/// Values of one column within a row group
+#[derive(Debug, Clone, PartialEq)]
+pub enum ColumnValues {
+    Strings(Vec<Option<String>>),
+    Int64(Vec<Option<i64>>),
+}
+
+impl ColumnValues {
+    pub fn len(&self) -> usize {
+        match self {
+            Self::Strings(v) => v.len(),
+            Self::Int64(v) => v.len(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn is_present(&self, index: usize) -> bool {
+        match self {
+            Self::Strings(v) => v[index].is_some(),
+            Self::Int64(v) => v[index].is_some(),
+        }
+    }
+}
+
+/// Parquet message type for a flat schema
+fn message_type(schema: &[ColumnSpec]) -> Result<Arc<Type>> {
+    let mut fields = Vec::with_capacity(schema.len());
+    for spec in schema {
+        let repetition = if spec.optional {
+            Repetition::OPTIONAL
+        } else {
+            Repetition::REQUIRED
+        };
+        let field = Type::primitive_type_builder(spec.name, spec.column_type.physical())
+            .with_repetition(repetition)
+            .with_logical_type(Some(spec.column_type.logical()))
+            .build()?;
+        fields.push(Arc::new(field));
+    }
+    Ok(Arc::new(
+        Type::group_type_builder("schema")
+            .with_fields(fields)
+            .build()?,
+    ))
+}
+
+/// Incremental Parquet writer
+///
+/// Each call returns the bytes to append to the output, so the caller can
+/// stream them out without the writer owning a sink. The underlying writer
+/// buffers internally, so a row group's tail may only be returned by the
+/// next call.
+pub struct ParquetWriter {
+    schema: Vec<ColumnSpec>,
+    key_values: Vec<KeyValue>,
+    inner: Option<SerializedFileWriter<Vec<u8>>>,
+}
+
+impl ParquetWriter {
+    pub fn new(schema: &[ColumnSpec]) -> Self {
+        Self {
+            schema: schema.to_vec(),
+            key_values: Vec::new(),
+            inner: None,
+        }
+    }
+
+    /// Add a key/value pair to the file footer
+    pub fn with_key_value(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.key_values
+            .push(KeyValue::new(key.into(), value.into()));
+        self
+    }
+
+    /// The file writer, created (emitting the leading magic) on first use
+    fn writer(&mut self) -> Result<&mut SerializedFileWriter<Vec<u8>>> {
+        if self.inner.is_none() {
+            let properties = WriterProperties::builder()
+                .set_compression(Compression::SNAPPY)
+                .set_created_by(format!(
+                    "shodh-memory version {}",
+                    env!("CARGO_PKG_VERSION")
+                ))
+                .set_key_value_metadata(Some(self.key_values.clone()))
+                .build();
+            self.inner = Some(SerializedFileWriter::new(
+                Vec::new(),
+                message_type(&self.schema)?,
+                Arc::new(properties),
+            )?);
+        }
+        Ok(self.inner.as_mut().expect("writer was just created"))
+    }
+
+    /// Encode one row group; `columns` follow the schema order
+    pub fn write_row_group(&mut self, columns: &[ColumnValues]) -> Result<Vec<u8>> {
+        if columns.len() != self.schema.len() {
+            anyhow::bail!(
+                "Row group has {} columns, schema has {}",
+                columns.len(),
+                self.schema.len()
+            );
+        }
+        let num_rows = columns.first().map_or(0, ColumnValues::len);
+        if columns.iter().any(|c| c.len() != num_rows) {
+            anyhow::bail!("Row group columns have different lengths");
+        }
+        for (spec, values) in self.schema.iter().zip(columns) {
+            let matches = matches!(
+                (spec.column_type, values),
+                (
+                    ColumnType::Utf8 | ColumnType::Json,
+                    ColumnValues::Strings(_)
+                ) | (ColumnType::TimestampMillis, ColumnValues::Int64(_))
+            );
+            if !matches {
+                anyhow::bail!("Column '{}' has values of the wrong type", spec.name);
+            }
+            if !spec.optional && (0..num_rows).any(|i| !values.is_present(i)) {
+                anyhow::bail!("Column '{}' is required but has nulls", spec.name);
+            }
+        }
+
+        let optional: Vec<bool> = self.schema.iter().map(|spec| spec.optional).collect();
+        let writer = self.writer()?;
+        let mut row_group = writer.next_row_group()?;
+        for (values, optional) in columns.iter().zip(optional) {
+            let mut column = row_group
+                .next_column()?
+                .context("Schema has fewer columns than the row group")?;
+            let levels: Vec<i16> = (0..num_rows).map(|i| values.is_present(i) as i16).collect();
+            let levels = optional.then_some(levels.as_slice());
+            match (column.untyped(), values) {
+                (ColumnWriter::ByteArrayColumnWriter(w), ColumnValues::Strings(v)) => {
+                    let data: Vec<ByteArray> =
+                        v.iter().flatten().map(|s| s.as_str().into()).collect();
+                    w.write_batch(&data, levels, None)?;
+                }
+                (ColumnWriter::Int64ColumnWriter(w), ColumnValues::Int64(v)) => {
+                    let data: Vec<i64> = v.iter().flatten().copied().collect();
+                    w.write_batch(&data, levels, None)?;
+                }
+                _ => anyhow::bail!("Column writer does not match the schema"),
+            }
+            column.close()?;
+        }
+        row_group.close()?;
+        Ok(std::mem::take(writer.inner_mut()))
+    }
+
+    /// Remaining bytes, including the footer, that complete the file
+    pub fn finish(mut self) -> Result<Vec<u8>> {
+        let writer = self.writer()?;
+        writer.finish()?;
+        Ok(std::mem::take(writer.inner_mut()))
+    }
+}
+
+/// Converts one file column's stored timestamps to milliseconds
+#[derive(Debug, Clone, Copy)]
+enum TimestampScale {
+    Millis,
+    Micros,
+    Nanos,
+}
+
+impl TimestampScale {
+    fn of(column: &ColumnDescriptor) -> Result<Self> {
+        use ::parquet::basic::ConvertedType;
+        Ok(match (column.logical_type(), column.converted_type()) {
+            (Some(LogicalType::Timestamp { unit, .. }), _) => match unit {
+                TimeUnit::MILLIS(_) => Self::Millis,
+                TimeUnit::MICROS(_) => Self::Micros,
+                TimeUnit::NANOS(_) => Self::Nanos,
+            },
+            (None, ConvertedType::TIMESTAMP_MICROS) => Self::Micros,
+            (None, ConvertedType::TIMESTAMP_MILLIS | ConvertedType::NONE) => Self::Millis,
+            _ => anyhow::bail!("Column '{}' is not a timestamp", column.name()),
+        })
+    }
+
+    fn to_millis(self, value: i64) -> i64 {
+        match self {
+            Self::Millis => value,
+            Self::Micros => value.div_euclid(1_000),
+            Self::Nanos => value.div_euclid(1_000_000),
+        }
+    }
+}
+
+/// Row-group-at-a-time Parquet reader for files with a known flat schema
+pub struct ParquetReader<R: ChunkReader> {
+    reader: SerializedFileReader<R>,
+    schema: Vec<ColumnSpec>,
+}
+
+impl<R: ChunkReader + 'static> ParquetReader<R> {
+    /// Open a file, checking that its columns are exactly `expected`
+    pub fn open(input: R, expected: &[ColumnSpec]) -> Result<Self> {
+        let reader = SerializedFileReader::new(input).context("Not a readable Parquet file")?;
+        let schema = reader.metadata().file_metadata().schema_descr();
+        let fields = schema.root_schema().get_fields();
+        if fields.len() != expected.len() || schema.num_columns() != expected.len() {
+            anyhow::bail!(
+                "Parquet file has {} columns, expected {}",
+                fields.len(),
+                expected.len()
+            );
+        }
+        for (i, spec) in expected.iter().enumerate() {
+            let column = schema.column(i);
+            if !fields[i].is_primitive() || column.name() != spec.name {
+                anyhow::bail!(
+                    "Parquet column {i} is '{}', expected '{}'",
+                    fields[i].name(),
+                    spec.name
+                );
+            }
+            if column.max_rep_level() > 0 {
+                anyhow::bail!("Parquet column '{}' is repeated", spec.name);
+            }
+            let physical = column.physical_type();
+            let compatible = match spec.column_type {
+                ColumnType::Utf8 | ColumnType::Json => physical == PhysicalType::BYTE_ARRAY,
+                ColumnType::TimestampMillis => {
+                    physical == PhysicalType::INT96
+                        || (physical == PhysicalType::INT64 && TimestampScale::of(&column).is_ok())
+                }
+            };
+            if !compatible {
+                anyhow::bail!(
+                    "Parquet column '{}' has type {physical}, expected {:?}",
+                    spec.name,
+                    spec.column_type
+                );
+            }
+        }
+        Ok(Self {
+            reader,
+            schema: expected.to_vec(),
+        })
+    }
+
+    pub fn num_row_groups(&self) -> usize {
+        self.reader.num_row_groups()
+    }
+
+    /// Value of a footer key/value pair
+    pub fn key_value(&self, key: &str) -> Option<&str> {
+        self.reader
+            .metadata()
+            .file_metadata()
+            .key_value_metadata()?
+            .iter()
+            .find(|kv| kv.key == key)?
+            .value
+            .as_deref()
+    }
+
+    /// Decode one row group into columns in schema order
+    pub fn read_row_group(&mut self, index: usize) -> Result<Vec<ColumnValues>> {
+        let row_group = self.reader.get_row_group(index)?;
+        let num_rows = usize::try_from(row_group.metadata().num_rows())
+            .context("Negative row count in row group")?;
+        let schema = self.reader.metadata().file_metadata().schema_descr();
+
+        let mut columns = Vec::with_capacity(self.schema.len());
+        for (i, spec) in self.schema.iter().enumerate() {
+            let mut levels = Vec::new();
+            let (present, read) = match row_group.get_column_reader(i)? {
+                ColumnReader::ByteArrayColumnReader(mut r) => {
+                    let mut data = Vec::new();
+                    let (read, _, _) =
+                        r.read_records(num_rows, Some(&mut levels), None, &mut data)?;
+                    let values = data
+                        .iter()
+                        .map(|v| {
+                            std::str::from_utf8(v.data())
+                                .map(str::to_string)
+                                .with_context(|| format!("Invalid UTF-8 in column '{}'", spec.name))
+                        })
+                        .collect::<Result<Vec<_>>>()?;
+                    (
+                        ColumnValues::Strings(values.into_iter().map(Some).collect()),
+                        read,
+                    )
+                }
+                ColumnReader::Int64ColumnReader(mut r) => {
+                    let scale = TimestampScale::of(&schema.column(i))?;
+                    let mut data = Vec::new();
+                    let (read, _, _) =
+                        r.read_records(num_rows, Some(&mut levels), None, &mut data)?;
+                    let values = data.into_iter().map(|v| Some(scale.to_millis(v)));
+                    (ColumnValues::Int64(values.collect()), read)
+                }
+                ColumnReader::Int96ColumnReader(mut r) => {
+                    let mut data = Vec::new();
+                    let (read, _, _) =
+                        r.read_records(num_rows, Some(&mut levels), None, &mut data)?;
+                    let values = data.iter().map(|v| Some(v.to_i64()));
+                    (ColumnValues::Int64(values.collect()), read)
+                }
+                _ => anyhow::bail!("Unsupported physical type in column '{}'", spec.name),
+            };
+            if read != num_rows {
+                anyhow::bail!(
+                    "Column '{}' has {read} values, row group has {num_rows}",
+                    spec.name
+                );
+            }
+            columns.push(spread_nulls(spec, present, &levels, num_rows)?);
+        }
+        Ok(columns)
+    }
+}
+
+/// Place the non-null `values` read from a column at the rows whose
+/// definition level marks them present
+fn spread_nulls(
+    spec: &ColumnSpec,
+    values: ColumnValues,
+    levels: &[i16],
+    num_rows: usize,
+) -> Result<ColumnValues> {
+    // Required columns carry no levels: every row is present
+    if levels.is_empty() {
+        return Ok(values);
+    }
+    if !spec.optional && levels.contains(&0) {
+        anyhow::bail!("Column '{}' is required but has nulls", spec.name);
+    }
+    fn spread<T>(values: Vec<Option<T>>, levels: &[i16], num_rows: usize) -> Vec<Option<T>> {
+        let mut values = values.into_iter();
+        let mut out = Vec::with_capacity(num_rows);
+        for level in levels {
+            out.push(if *level > 0 {
+                values.next().flatten()
+            } else {
+                None
+            });
+        }
+        out
+    }
+    Ok(match values {
+        ColumnValues::Strings(v) => ColumnValues::Strings(spread(v, levels, num_rows)),
+        ColumnValues::Int64(v) => ColumnValues::Int64(spread(v, levels, num_rows)),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ::parquet::data_type::{ByteArrayType, Int64Type};
+    use ::parquet::file::properties::WriterVersion;
+    use ::parquet::record::RowAccessor;
+    use bytes::Bytes;
+
+    const SCHEMA: [ColumnSpec; 3] = [
+        ColumnSpec {
+            name: "id",
+            column_type: ColumnType::Utf8,
+            optional: false,
+        },
+        ColumnSpec {
+            name: "at",
+            column_type: ColumnType::TimestampMillis,
+            optional: true,
+        },
+        ColumnSpec {
+            name: "data",
+            column_type: ColumnType::Json,
+            optional: true,
+        },
+    ];
+
+    fn group(start: usize, rows: usize) -> Vec<ColumnValues> {
+        vec![
+            ColumnValues::Strings(
+                (start..start + rows)
+                    .map(|i| Some(format!("row-{i}")))
+                    .collect(),
+            ),
+            ColumnValues::Int64(
+                (start..start + rows)
+                    .map(|i| (i % 3 != 0).then_some(i as i64 * 1000))
+                    .collect(),
+            ),
+            ColumnValues::Strings(
+                (start..start + rows)
+                    .map(|i| (i % 2 == 0).then(|| format!("{{\"n\":{i}}}")))
+                    .collect(),
+            ),
+        ]
+    }
+
+    fn written(groups: &[Vec<ColumnValues>]) -> Bytes {
+        let mut writer = ParquetWriter::new(&SCHEMA).with_key_value("schema_version", "1");
+        let mut file = Vec::new();
+        for group in groups {
+            file.extend(writer.write_row_group(group).unwrap());
+        }
+        file.extend(writer.finish().unwrap());
+        file.into()
+    }
+
+    #[test]
+    fn test_roundtrip_row_groups() {
+        let file = written(&[group(0, 20), group(20, 3)]);
+
+        let mut reader = ParquetReader::open(file, &SCHEMA).unwrap();
+        assert_eq!(reader.num_row_groups(), 2);
+        assert_eq!(reader.key_value("schema_version"), Some("1"));
+        assert_eq!(reader.read_row_group(0).unwrap(), group(0, 20));
+        assert_eq!(reader.read_row_group(1).unwrap(), group(20, 3));
+    }
+
+    #[test]
+    fn test_output_is_standard_parquet() {
+        // Read back through the parquet crate's generic record API, with no
+        // knowledge of our schema
+        let file = written(&[group(0, 4)]);
+        let reader = SerializedFileReader::new(file).unwrap();
+        let metadata = reader.metadata();
+        assert_eq!(metadata.file_metadata().num_rows(), 4);
+        assert_eq!(
+            metadata.row_group(0).column(0).compression(),
+            Compression::SNAPPY
+        );
+        let rows: Vec<_> = reader
+            .get_row_iter(None)
+            .unwrap()
+            .map(|row| row.unwrap())
+            .collect();
+        assert_eq!(rows[1].get_string(0).unwrap(), "row-1");
+        assert_eq!(rows[1].get_timestamp_millis(1).unwrap(), 1000);
+        assert_eq!(rows[2].get_string(2).unwrap(), "{\"n\":2}");
+    }
+
+    #[test]
+    fn test_reads_files_from_other_writers() {
+        // Mirrors pyarrow/pandas defaults: every column nullable, dictionary
+        // pages, v2 data pages, zstd and microsecond timestamps
+        let message = "message schema {
+            optional binary id (STRING);
+            optional int64 at (TIMESTAMP(MICROS,true));
+            optional binary data (STRING);
+        }";
+        let schema = Arc::new(::parquet::schema::parser::parse_message_type(message).unwrap());
+        let properties = WriterProperties::builder()
+            .set_writer_version(WriterVersion::PARQUET_2_0)
+            .set_compression(Compression::ZSTD(Default::default()))
+            .set_dictionary_enabled(true)
+            .build();
+        let mut writer =
+            SerializedFileWriter::new(Vec::new(), schema, Arc::new(properties)).unwrap();
+        let mut row_group = writer.next_row_group().unwrap();
+
+        let mut column = row_group.next_column().unwrap().unwrap();
+        let ids: Vec<ByteArray> = ["a", "b", "a"].map(ByteArray::from).to_vec();
+        column
+            .typed::<ByteArrayType>()
+            .write_batch(&ids, Some(&[1, 1, 1]), None)
+            .unwrap();
+        column.close().unwrap();
+        let mut column = row_group.next_column().unwrap().unwrap();
+        column
+            .typed::<Int64Type>()
+            .write_batch(&[1_500_000, 2_000], Some(&[1, 0, 1]), None)
+            .unwrap();
+        column.close().unwrap();
+        let mut column = row_group.next_column().unwrap().unwrap();
+        column
+            .typed::<ByteArrayType>()
+            .write_batch(&[ByteArray::from("{}")], Some(&[0, 1, 0]), None)
+            .unwrap();
+        column.close().unwrap();
+        row_group.close().unwrap();
+        let file = Bytes::from(writer.into_inner().unwrap());
+
+        let mut reader = ParquetReader::open(file, &SCHEMA).unwrap();
+        assert_eq!(
+            reader.read_row_group(0).unwrap(),
+            vec![
+                ColumnValues::Strings(vec![Some("a".into()), Some("b".into()), Some("a".into())]),
+                ColumnValues::Int64(vec![Some(1_500), None, Some(2)]),
+                ColumnValues::Strings(vec![None, Some("{}".into()), None]),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_empty_file_and_schema_mismatch() {
+        let file = written(&[]);
+        let reader = ParquetReader::open(file.clone(), &SCHEMA).unwrap();
+        assert_eq!(reader.num_row_groups(), 0);
+
+        let mut other = SCHEMA;
+        other[0].name = "uuid";
+        assert!(ParquetReader::open(file.clone(), &other).is_err());
+        let mut other = SCHEMA;
+        other[0].column_type = ColumnType::TimestampMillis;
+        assert!(ParquetReader::open(file, &other).is_err());
+        assert!(ParquetReader::open(Bytes::from_static(b"PAR1 not parquet"), &SCHEMA).is_err());
+    }
+
+    #[test]
+    fn test_required_column_rejects_nulls() {
+        let mut writer = ParquetWriter::new(&SCHEMA);
+        let mut columns = group(0, 2);
+        columns[0] = ColumnValues::Strings(vec![Some("a".into()), None]);
+        assert!(writer.write_row_group(&columns).is_err());
+
+        // A nullable file column is accepted for a required spec column
+        // until it actually holds a null
+        let mut nullable = SCHEMA;
+        nullable[0].optional = true;
+        let mut writer = ParquetWriter::new(&nullable);
+        let mut file = writer.write_row_group(&columns).unwrap();
+        file.extend(writer.finish().unwrap());
+        let mut reader = ParquetReader::open(Bytes::from(file), &SCHEMA).unwrap();
+        assert!(reader.read_row_group(0).is_err());
+    }
+}
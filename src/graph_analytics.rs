@@ -0,0 +1,618 @@
+//! Structural analytics over a user's entity graph
+//!
+//! Mention counts say how often an entity comes up, not how much of the graph
+//! hangs off it. This module computes, over the valid (non-invalidated) edges:
+//!
+//! - **Centrality**: weighted PageRank, with each edge weighted by its
+//!   decay-adjusted `effective_strength`. Scaled so the top entity scores 1.0.
+//! - **Communities**: Louvain modularity optimisation (Blondel et al. 2008).
+//!   Each community is reported as a topic cluster named after its most
+//!   central members.
+//! - **Bridges**: entities whose edges are spread across communities, scored by
+//!   the participation coefficient (Guimerà & Amaral 2005).
+//!
+//! Relationship direction carries meaning for queries but not for structure,
+//! so the graph is treated as undirected and parallel edges are summed.
+//! Results are written back onto `EntityNode::centrality` and
+//! `EntityNode::community` by `GraphMemory::run_analytics`.
+
+use std::collections::HashMap;
+use std::time::Instant;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::constants::{
+    GRAPH_ANALYTICS_TOP_K, GRAPH_BRIDGE_MIN_PARTICIPATION, GRAPH_COMMUNITY_MIN_SIZE,
+    GRAPH_LOUVAIN_MAX_LEVELS, GRAPH_LOUVAIN_MAX_PASSES, GRAPH_PAGERANK_DAMPING,
+    GRAPH_PAGERANK_MAX_ITERATIONS, GRAPH_PAGERANK_TOLERANCE,
+};
+use crate::graph_memory::{EntityNode, RelationshipEdge};
+
+/// Entity with its structural scores
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RankedEntity {
+    pub uuid: Uuid,
+    pub name: String,
+    /// PageRank scaled to 0.0 - 1.0 (1.0 = most central entity)
+    pub centrality: f32,
+    pub community: Option<u32>,
+}
+
+/// A community of densely connected entities
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TopicCluster {
+    pub id: u32,
+    pub size: usize,
+    /// Names of the most central members, e.g. "Rust / Tokio / async"
+    pub topic: String,
+    /// Most central members first
+    pub members: Vec<RankedEntity>,
+    /// Entity labels in the cluster, most common first
+    pub labels: Vec<String>,
+    /// Sum of effective strength over edges inside the cluster
+    pub internal_strength: f32,
+    /// Share of possible member pairs that are connected (0.0 - 1.0)
+    pub density: f32,
+}
+
+/// An entity linking otherwise separate communities
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BridgeEntity {
+    pub uuid: Uuid,
+    pub name: String,
+    pub centrality: f32,
+    pub community: Option<u32>,
+    /// Participation coefficient: 0.0 = edges in one community, higher = spread out
+    pub participation: f32,
+    /// Communities this entity has edges into, its own first
+    pub communities: Vec<u32>,
+}
+
+/// Result of one analytics run over a user's graph
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GraphAnalytics {
+    pub computed_at: DateTime<Utc>,
+    pub entity_count: usize,
+    /// Distinct connected entity pairs (parallel edges count once)
+    pub edge_count: usize,
+    pub community_count: usize,
+    /// Newman modularity of the community partition (-0.5 to 1.0)
+    pub modularity: f64,
+    pub pagerank_iterations: usize,
+    pub central_entities: Vec<RankedEntity>,
+    pub communities: Vec<TopicCluster>,
+    pub bridges: Vec<BridgeEntity>,
+    pub elapsed_ms: f64,
+}
+
+/// Per-entity scores to store back on the graph
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EntityScores {
+    pub centrality: f32,
+    pub community: Option<u32>,
+}
+
+/// Undirected weighted graph in adjacency-list form
+struct WeightedGraph {
+    /// Neighbour index and summed edge weight, excluding self-loops
+    adjacency: Vec<Vec<(usize, f64)>>,
+    /// Self-loop weight, counted twice as in a node's degree
+    self_loops: Vec<f64>,
+}
+
+impl WeightedGraph {
+    fn len(&self) -> usize {
+        self.adjacency.len()
+    }
+
+    /// Weighted degree of a node
+    fn degree(&self, node: usize) -> f64 {
+        self.adjacency[node].iter().map(|(_, w)| w).sum::<f64>() + self.self_loops[node]
+    }
+
+    /// Build from entities and edges; edges to unknown entities are ignored
+    fn from_edges(index: &HashMap<Uuid, usize>, edges: &[RelationshipEdge]) -> Self {
+        let n = index.len();
+        let mut weights: Vec<HashMap<usize, f64>> = vec![HashMap::new(); n];
+        for edge in edges {
+            if edge.invalidated_at.is_some() || edge.from_entity == edge.to_entity {
+                continue;
+            }
+            let (Some(&a), Some(&b)) = (index.get(&edge.from_entity), index.get(&edge.to_entity))
+            else {
+                continue;
+            };
+            let weight = edge.effective_strength().max(0.0) as f64;
+            if weight <= 0.0 {
+                continue;
+            }
+            *weights[a].entry(b).or_insert(0.0) += weight;
+            *weights[b].entry(a).or_insert(0.0) += weight;
+        }
+
+        let adjacency = weights
+            .into_iter()
+            .map(|map| {
+                let mut neighbours: Vec<(usize, f64)> = map.into_iter().collect();
+                // Deterministic order keeps community assignment stable between runs
+                neighbours.sort_by_key(|(j, _)| *j);
+                neighbours
+            })
+            .collect();
+        Self {
+            adjacency,
+            self_loops: vec![0.0; n],
+        }
+    }
+}
+
+/// Compute centrality, communities and bridges
+///
+/// Returns the report and the scores to store on each entity (keyed by UUID).
+pub fn analyze(
+    entities: &[EntityNode],
+    edges: &[RelationshipEdge],
+) -> (GraphAnalytics, HashMap<Uuid, EntityScores>) {
+    let started = Instant::now();
+    let index: HashMap<Uuid, usize> = entities
+        .iter()
+        .enumerate()
+        .map(|(i, e)| (e.uuid, i))
+        .collect();
+    let graph = WeightedGraph::from_edges(&index, edges);
+    let n = graph.len();
+
+    // Centrality
+    let (ranks, pagerank_iterations) = weighted_pagerank(&graph);
+    let max_rank = ranks.iter().cloned().fold(0.0_f64, f64::max);
+    let centrality: Vec<f32> = ranks
+        .iter()
+        .map(|r| {
+            if max_rank > 0.0 {
+                (r / max_rank) as f32
+            } else {
+                0.0
+            }
+        })
+        .collect();
+
+    // Communities, numbered largest first; isolated entities get none
+    let partition = louvain(&graph);
+    let modularity = modularity(&graph, &partition);
+    let mut sizes: HashMap<usize, usize> = HashMap::new();
+    for (i, c) in partition.iter().enumerate() {
+        if !graph.adjacency[i].is_empty() {
+            *sizes.entry(*c).or_insert(0) += 1;
+        }
+    }
+    let mut ordered: Vec<(usize, usize)> = sizes.into_iter().collect();
+    ordered.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+    let renumber: HashMap<usize, u32> = ordered
+        .iter()
+        .enumerate()
+        .map(|(id, (c, _))| (*c, id as u32))
+        .collect();
+    let community: Vec<Option<u32>> = (0..n)
+        .map(|i| {
+            if graph.adjacency[i].is_empty() {
+                None
+            } else {
+                renumber.get(&partition[i]).copied()
+            }
+        })
+        .collect();
+
+    let ranked = |i: usize| RankedEntity {
+        uuid: entities[i].uuid,
+        name: entities[i].name.clone(),
+        centrality: centrality[i],
+        community: community[i],
+    };
+    let mut by_centrality: Vec<usize> = (0..n).collect();
+    by_centrality.sort_by(|a, b| {
+        centrality[*b]
+            .partial_cmp(&centrality[*a])
+            .unwrap_or(std::cmp::Ordering::Equal)
+            .then_with(|| entities[*a].name.cmp(&entities[*b].name))
+    });
+
+    let central_entities: Vec<RankedEntity> = by_centrality
+        .iter()
+        .filter(|i| !graph.adjacency[**i].is_empty())
+        .take(GRAPH_ANALYTICS_TOP_K)
+        .map(|i| ranked(*i))
+        .collect();
+
+    // Topic clusters
+    let mut members: HashMap<u32, Vec<usize>> = HashMap::new();
+    for &i in &by_centrality {
+        if let Some(c) = community[i] {
+            members.entry(c).or_default().push(i);
+        }
+    }
+    let mut communities: Vec<TopicCluster> = members
+        .iter()
+        .filter(|(_, nodes)| nodes.len() >= GRAPH_COMMUNITY_MIN_SIZE)
+        .map(|(id, nodes)| {
+            let mut internal_strength = 0.0;
+            let mut internal_pairs = 0usize;
+            let mut label_counts: HashMap<String, usize> = HashMap::new();
+            for &i in nodes {
+                for &(j, w) in &graph.adjacency[i] {
+                    if i < j && community[j] == Some(*id) {
+                        internal_strength += w;
+                        internal_pairs += 1;
+                    }
+                }
+                for label in &entities[i].labels {
+                    *label_counts.entry(label.as_str().to_string()).or_insert(0) += 1;
+                }
+            }
+            let possible = nodes.len() * (nodes.len() - 1) / 2;
+            let mut labels: Vec<(String, usize)> = label_counts.into_iter().collect();
+            labels.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+
+            TopicCluster {
+                id: *id,
+                size: nodes.len(),
+                topic: nodes
+                    .iter()
+                    .take(3)
+                    .map(|i| entities[*i].name.as_str())
+                    .collect::<Vec<_>>()
+                    .join(" / "),
+                members: nodes
+                    .iter()
+                    .take(GRAPH_ANALYTICS_TOP_K)
+                    .map(|i| ranked(*i))
+                    .collect(),
+                labels: labels.into_iter().map(|(label, _)| label).collect(),
+                internal_strength: internal_strength as f32,
+                density: if possible > 0 {
+                    internal_pairs as f32 / possible as f32
+                } else {
+                    0.0
+                },
+            }
+        })
+        .collect();
+    communities.sort_by_key(|c| c.id);
+
+    // Bridges
+    let mut bridges: Vec<BridgeEntity> = (0..n)
+        .filter_map(|i| {
+            let own = community[i]?;
+            let degree = graph.degree(i);
+            if degree <= 0.0 {
+                return None;
+            }
+            let mut by_community: HashMap<u32, f64> = HashMap::new();
+            for &(j, w) in &graph.adjacency[i] {
+                if let Some(c) = community[j] {
+                    *by_community.entry(c).or_insert(0.0) += w;
+                }
+            }
+            if by_community.len() < 2 {
+                return None;
+            }
+            let participation = 1.0
+                - by_community
+                    .values()
+                    .map(|w| (w / degree).powi(2))
+                    .sum::<f64>();
+            let participation = participation as f32;
+            if participation < GRAPH_BRIDGE_MIN_PARTICIPATION {
+                return None;
+            }
+            let mut linked: Vec<u32> = by_community.keys().copied().filter(|c| *c != own).collect();
+            linked.sort_unstable();
+            linked.insert(0, own);
+            Some(BridgeEntity {
+                uuid: entities[i].uuid,
+                name: entities[i].name.clone(),
+                centrality: centrality[i],
+                community: Some(own),
+                participation,
+                communities: linked,
+            })
+        })
+        .collect();
+    bridges.sort_by(|a, b| {
+        (b.participation * (1.0 + b.centrality))
+            .partial_cmp(&(a.participation * (1.0 + a.centrality)))
+            .unwrap_or(std::cmp::Ordering::Equal)
+            .then_with(|| a.name.cmp(&b.name))
+    });
+    bridges.truncate(GRAPH_ANALYTICS_TOP_K);
+
+    let scores = (0..n)
+        .map(|i| {
+            (
+                entities[i].uuid,
+                EntityScores {
+                    centrality: centrality[i],
+                    community: community[i],
+                },
+            )
+        })
+        .collect();
+
+    let report = GraphAnalytics {
+        computed_at: Utc::now(),
+        entity_count: n,
+        edge_count: graph.adjacency.iter().map(Vec::len).sum::<usize>() / 2,
+        community_count: ordered.len(),
+        modularity,
+        pagerank_iterations,
+        central_entities,
+        communities,
+        bridges,
+        elapsed_ms: started.elapsed().as_secs_f64() * 1000.0,
+    };
+    (report, scores)
+}
+
+/// Weighted PageRank by power iteration
+///
+/// A node passes rank to each neighbour in proportion to the edge weight.
+/// Rank held by isolated nodes is spread evenly, so scores always sum to 1.
+/// Returns the scores and the number of iterations run.
+fn weighted_pagerank(graph: &WeightedGraph) -> (Vec<f64>, usize) {
+    let n = graph.len();
+    if n == 0 {
+        return (Vec::new(), 0);
+    }
+    let degrees: Vec<f64> = (0..n).map(|i| graph.degree(i)).collect();
+    let base = (1.0 - GRAPH_PAGERANK_DAMPING) / n as f64;
+    let mut ranks = vec![1.0 / n as f64; n];
+    let mut iterations = 0;
+
+    while iterations < GRAPH_PAGERANK_MAX_ITERATIONS {
+        iterations += 1;
+        let dangling: f64 = (0..n)
+            .filter(|i| degrees[*i] <= 0.0)
+            .map(|i| ranks[i])
+            .sum();
+        let mut next = vec![base + GRAPH_PAGERANK_DAMPING * dangling / n as f64; n];
+        for i in 0..n {
+            if degrees[i] <= 0.0 {
+                continue;
+            }
+            let share = GRAPH_PAGERANK_DAMPING * ranks[i] / degrees[i];
+            for &(j, w) in &graph.adjacency[i] {
+                next[j] += share * w;
+            }
+        }
+        let delta: f64 = next.iter().zip(&ranks).map(|(a, b)| (a - b).abs()).sum();
+        ranks = next;
+        if delta < GRAPH_PAGERANK_TOLERANCE {
+            break;
+        }
+    }
+    (ranks, iterations)
+}
+
+/// Louvain community detection
+///
+/// Alternates local moving (each node joins the neighbouring community with
+/// the best modularity gain) with aggregation (communities become nodes),
+/// until a level moves nothing. Returns a community index per node.
+fn louvain(graph: &WeightedGraph) -> Vec<usize> {
+    let n = graph.len();
+    let mut assignment: Vec<usize> = (0..n).collect();
+    let total: f64 = (0..n).map(|i| graph.degree(i)).sum();
+    if total <= 0.0 {
+        return assignment;
+    }
+
+    let mut level_graph = WeightedGraph {
+        adjacency: graph.adjacency.clone(),
+        self_loops: graph.self_loops.clone(),
+    };
+    for _ in 0..GRAPH_LOUVAIN_MAX_LEVELS {
+        let (communities, moved) = local_moving(&level_graph, total);
+        if !moved {
+            break;
+        }
+
+        // Renumber to 0..k and fold the original nodes into the new communities
+        let mut ids: HashMap<usize, usize> = HashMap::new();
+        for c in &communities {
+            let next = ids.len();
+            ids.entry(*c).or_insert(next);
+        }
+        let communities: Vec<usize> = communities.iter().map(|c| ids[c]).collect();
+        for c in assignment.iter_mut() {
+            *c = communities[*c];
+        }
+        level_graph = aggregate(&level_graph, &communities, ids.len());
+    }
+    assignment
+}
+
+/// One Louvain level of local moving; returns each node's community and
+/// whether any node changed community
+fn local_moving(graph: &WeightedGraph, total: f64) -> (Vec<usize>, bool) {
+    let n = graph.len();
+    let degrees: Vec<f64> = (0..n).map(|i| graph.degree(i)).collect();
+    let mut community: Vec<usize> = (0..n).collect();
+    let mut community_total = degrees.clone();
+    let mut moved_any = false;
+
+    for _ in 0..GRAPH_LOUVAIN_MAX_PASSES {
+        let mut moved = false;
+        for i in 0..n {
+            if graph.adjacency[i].is_empty() {
+                continue;
+            }
+            let current = community[i];
+            let mut links: Vec<(usize, f64)> = Vec::new();
+            for &(j, w) in &graph.adjacency[i] {
+                let c = community[j];
+                match links.iter_mut().find(|(lc, _)| *lc == c) {
+                    Some(entry) => entry.1 += w,
+                    None => links.push((c, w)),
+                }
+            }
+
+            community_total[current] -= degrees[i];
+            let gain = |c: usize, weight: f64| weight - community_total[c] * degrees[i] / total;
+            let current_links = links
+                .iter()
+                .find(|(c, _)| *c == current)
+                .map_or(0.0, |(_, w)| *w);
+            // Staying put wins ties, so a pass without real gains ends the level
+            let mut best = (current, gain(current, current_links));
+            for &(c, w) in &links {
+                let g = gain(c, w);
+                if g > best.1 + 1e-12 {
+                    best = (c, g);
+                }
+            }
+            community_total[best.0] += degrees[i];
+            if best.0 != current {
+                community[i] = best.0;
+                moved = true;
+                moved_any = true;
+            }
+        }
+        if !moved {
+            break;
+        }
+    }
+    (community, moved_any)
+}
+
+/// Collapse each community into a single node
+fn aggregate(graph: &WeightedGraph, community: &[usize], count: usize) -> WeightedGraph {
+    let mut weights: Vec<HashMap<usize, f64>> = vec![HashMap::new(); count];
+    let mut self_loops = vec![0.0; count];
+    for (i, neighbours) in graph.adjacency.iter().enumerate() {
+        let ci = community[i];
+        self_loops[ci] += graph.self_loops[i];
+        for &(j, w) in neighbours {
+            let cj = community[j];
+            if ci == cj {
+                // Each internal edge is seen from both ends, matching the degree convention
+                self_loops[ci] += w;
+            } else {
+                *weights[ci].entry(cj).or_insert(0.0) += w;
+            }
+        }
+    }
+    let adjacency = weights
+        .into_iter()
+        .map(|map| {
+            let mut neighbours: Vec<(usize, f64)> = map.into_iter().collect();
+            neighbours.sort_by_key(|(j, _)| *j);
+            neighbours
+        })
+        .collect();
+    WeightedGraph {
+        adjacency,
+        self_loops,
+    }
+}
+
+/// Newman modularity of a partition
+fn modularity(graph: &WeightedGraph, community: &[usize]) -> f64 {
+    let total: f64 = (0..graph.len()).map(|i| graph.degree(i)).sum();
+    if total <= 0.0 {
+        return 0.0;
+    }
+    let mut internal: HashMap<usize, f64> = HashMap::new();
+    let mut degree: HashMap<usize, f64> = HashMap::new();
+    for i in 0..graph.len() {
+        let c = community[i];
+        *degree.entry(c).or_insert(0.0) += graph.degree(i);
+        let inside: f64 = graph.adjacency[i]
+            .iter()
+            .filter(|(j, _)| community[*j] == c)
+            .map(|(_, w)| w)
+            .sum();
+        *internal.entry(c).or_insert(0.0) += inside + graph.self_loops[i];
+    }
+    degree
+        .iter()
+        .map(|(c, d)| internal.get(c).copied().unwrap_or(0.0) / total - (d / total).powi(2))
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn graph(n: usize, edges: &[(usize, usize, f64)]) -> WeightedGraph {
+        let mut adjacency = vec![Vec::new(); n];
+        for &(a, b, w) in edges {
+            adjacency[a].push((b, w));
+            adjacency[b].push((a, w));
+        }
+        WeightedGraph {
+            adjacency,
+            self_loops: vec![0.0; n],
+        }
+    }
+
+    /// Two triangles joined by a single weak edge between nodes 2 and 3
+    fn barbell() -> WeightedGraph {
+        graph(
+            6,
+            &[
+                (0, 1, 1.0),
+                (1, 2, 1.0),
+                (0, 2, 1.0),
+                (3, 4, 1.0),
+                (4, 5, 1.0),
+                (3, 5, 1.0),
+                (2, 3, 0.2),
+            ],
+        )
+    }
+
+    #[test]
+    fn test_pagerank_sums_to_one_and_favours_hubs() {
+        // Star: node 0 connected to 1..=4
+        let star = graph(5, &[(0, 1, 1.0), (0, 2, 1.0), (0, 3, 1.0), (0, 4, 1.0)]);
+        let (ranks, iterations) = weighted_pagerank(&star);
+        assert!(iterations > 0);
+        assert!((ranks.iter().sum::<f64>() - 1.0).abs() < 1e-6);
+        assert!(ranks[1..].iter().all(|r| ranks[0] > *r));
+    }
+
+    #[test]
+    fn test_pagerank_uses_edge_weights() {
+        // Node 0 links strongly to 1 and weakly to 2
+        let g = graph(3, &[(0, 1, 1.0), (0, 2, 0.1)]);
+        let (ranks, _) = weighted_pagerank(&g);
+        assert!(ranks[1] > ranks[2]);
+    }
+
+    #[test]
+    fn test_louvain_splits_barbell() {
+        let g = barbell();
+        let partition = louvain(&g);
+        assert_eq!(partition[0], partition[1]);
+        assert_eq!(partition[1], partition[2]);
+        assert_eq!(partition[3], partition[4]);
+        assert_eq!(partition[4], partition[5]);
+        assert_ne!(partition[0], partition[3]);
+        assert!(modularity(&g, &partition) > 0.3);
+    }
+
+    #[test]
+    fn test_modularity_of_single_community_is_zero() {
+        let g = barbell();
+        assert!(modularity(&g, &[0; 6]).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_empty_graph() {
+        let g = graph(0, &[]);
+        assert!(weighted_pagerank(&g).0.is_empty());
+        assert!(louvain(&g).is_empty());
+        assert_eq!(modularity(&g, &[]), 0.0);
+    }
+}
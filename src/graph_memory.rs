@@ -6,9 +6,9 @@
 use anyhow::Result;
 use chrono::{DateTime, Utc};
 use rocksdb::{Options, WriteBatch, DB};
-use rust_stemmers::{Algorithm, Stemmer};
 use serde::{Deserialize, Serialize};
 use std::cmp::Ordering as CmpOrdering;
+use std::collections::hash_map::Entry;
 use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
 use std::path::Path;
 use std::sync::atomic::{AtomicUsize, Ordering};
@@ -16,6 +16,8 @@ use std::sync::Arc;
 use uuid::Uuid;
 
 use crate::constants::{ENTITY_CONCEPT_MERGE_THRESHOLD, LTP_MIN_STRENGTH};
+use crate::graph_analytics::GraphAnalytics;
+use crate::memory::language::Language;
 
 /// Entity node in the knowledge graph
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -57,12 +59,138 @@ pub struct EntityNode {
     /// Proper nouns have higher base salience than common nouns
     #[serde(default)]
     pub is_proper_noun: bool,
+
+    /// Other names that resolve to this entity ("PostgreSQL", "pg" for "Postgres")
+    /// Indexed like the canonical name, so lookups and dedup by any alias land here.
+    #[serde(default)]
+    pub aliases: Vec<String>,
+
+    /// Structural importance from the last graph analytics run: weighted
+    /// PageRank scaled so the most central entity scores 1.0
+    #[serde(default)]
+    pub centrality: f32,
+
+    /// Community assigned by the last graph analytics run (None = not yet
+    /// analyzed, or no edges)
+    #[serde(default)]
+    pub community: Option<u32>,
+}
+
+impl EntityNode {
+    /// Salience blended with graph centrality once analytics have run
+    ///
+    /// Mention-driven salience says how often an entity comes up; centrality
+    /// says how much of the graph hangs off it. Entities never analyzed keep
+    /// their plain salience.
+    pub fn structural_salience(&self) -> f32 {
+        if self.community.is_none() && self.centrality == 0.0 {
+            return self.salience;
+        }
+        let weight = crate::constants::GRAPH_CENTRALITY_SALIENCE_WEIGHT;
+        ((1.0 - weight) * self.salience + weight * self.centrality).clamp(0.0, 1.0)
+    }
 }
 
 fn default_salience() -> f32 {
     0.5 // Default middle salience
 }
 
+/// EntityNode layout before aliases and graph analytics fields existed
+///
+/// bincode encodes structs positionally, so entities written by older
+/// versions end before the newer fields and fail to decode as `EntityNode`.
+#[derive(Deserialize)]
+struct LegacyEntityNodeV1 {
+    uuid: Uuid,
+    name: String,
+    labels: Vec<EntityLabel>,
+    created_at: DateTime<Utc>,
+    last_seen_at: DateTime<Utc>,
+    mention_count: usize,
+    summary: String,
+    attributes: HashMap<String, String>,
+    name_embedding: Option<Vec<f32>>,
+    salience: f32,
+    is_proper_noun: bool,
+}
+
+impl From<LegacyEntityNodeV1> for EntityNode {
+    fn from(legacy: LegacyEntityNodeV1) -> Self {
+        Self {
+            uuid: legacy.uuid,
+            name: legacy.name,
+            labels: legacy.labels,
+            created_at: legacy.created_at,
+            last_seen_at: legacy.last_seen_at,
+            mention_count: legacy.mention_count,
+            summary: legacy.summary,
+            attributes: legacy.attributes,
+            name_embedding: legacy.name_embedding,
+            salience: legacy.salience,
+            is_proper_noun: legacy.is_proper_noun,
+            aliases: Vec::new(),
+            centrality: 0.0,
+            community: None,
+        }
+    }
+}
+
+/// EntityNode layout with aliases, before graph analytics fields existed
+#[derive(Deserialize)]
+struct LegacyEntityNodeV2 {
+    uuid: Uuid,
+    name: String,
+    labels: Vec<EntityLabel>,
+    created_at: DateTime<Utc>,
+    last_seen_at: DateTime<Utc>,
+    mention_count: usize,
+    summary: String,
+    attributes: HashMap<String, String>,
+    name_embedding: Option<Vec<f32>>,
+    salience: f32,
+    is_proper_noun: bool,
+    aliases: Vec<String>,
+}
+
+impl From<LegacyEntityNodeV2> for EntityNode {
+    fn from(legacy: LegacyEntityNodeV2) -> Self {
+        Self {
+            uuid: legacy.uuid,
+            name: legacy.name,
+            labels: legacy.labels,
+            created_at: legacy.created_at,
+            last_seen_at: legacy.last_seen_at,
+            mention_count: legacy.mention_count,
+            summary: legacy.summary,
+            attributes: legacy.attributes,
+            name_embedding: legacy.name_embedding,
+            salience: legacy.salience,
+            is_proper_noun: legacy.is_proper_noun,
+            aliases: legacy.aliases,
+            centrality: 0.0,
+            community: None,
+        }
+    }
+}
+
+/// Decode a stored entity, falling back through the older layouts
+///
+/// Newest first: a shorter legacy layout would also accept a newer record
+/// and silently drop its trailing fields.
+pub(crate) fn decode_entity(data: &[u8]) -> Result<EntityNode> {
+    let config = bincode::config::standard();
+    match bincode::serde::decode_from_slice::<EntityNode, _>(data, config) {
+        Ok((entity, _)) => Ok(entity),
+        Err(e) => bincode::serde::decode_from_slice::<LegacyEntityNodeV2, _>(data, config)
+            .map(|(legacy, _)| legacy.into())
+            .or_else(|_| {
+                bincode::serde::decode_from_slice::<LegacyEntityNodeV1, _>(data, config)
+                    .map(|(legacy, _)| legacy.into())
+            })
+            .map_err(|_| e.into()),
+    }
+}
+
 /// Entity labels following Graphiti's categorization
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
 pub enum EntityLabel {
@@ -906,6 +1034,102 @@ pub enum EpisodeSource {
     Observation,
 }
 
+/// Tuning for entity resolution (alias detection and automatic merging)
+///
+/// Confidence = name-embedding similarity × label compatibility, plus
+/// shared-neighbour overlap, minus a penalty when both names appear in the
+/// same episode. Pairs at or above `min_confidence` are merged.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct EntityResolverConfig {
+    /// Confidence needed to merge two entities automatically
+    pub min_confidence: f32,
+    /// Name similarity below which a pair is not considered at all
+    pub candidate_similarity: f32,
+    /// Weight of shared-neighbour overlap (Jaccard, 0.0-1.0)
+    pub cooccurrence_weight: f32,
+    /// Subtracted when both entities are mentioned in the same episode
+    pub comention_penalty: f32,
+}
+
+impl Default for EntityResolverConfig {
+    fn default() -> Self {
+        use crate::constants::{
+            ENTITY_RESOLVER_CANDIDATE_SIMILARITY, ENTITY_RESOLVER_COMENTION_PENALTY,
+            ENTITY_RESOLVER_COOCCURRENCE_WEIGHT,
+        };
+        Self {
+            min_confidence: ENTITY_CONCEPT_MERGE_THRESHOLD,
+            candidate_similarity: ENTITY_RESOLVER_CANDIDATE_SIMILARITY,
+            cooccurrence_weight: ENTITY_RESOLVER_COOCCURRENCE_WEIGHT,
+            comention_penalty: ENTITY_RESOLVER_COMENTION_PENALTY,
+        }
+    }
+}
+
+/// A pair of entities the resolver thinks name the same thing
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EntityMergeCandidate {
+    /// Entity that would be kept (more mentions, then older)
+    pub survivor: Uuid,
+    pub survivor_name: String,
+    /// Entity that would be folded into the survivor
+    pub duplicate: Uuid,
+    pub duplicate_name: String,
+    /// Cosine similarity of the name embeddings
+    pub similarity: f32,
+    /// 1.0 for matching labels, 0.0 for conflicting ones (Person vs Technology)
+    pub label_compatibility: f32,
+    /// Jaccard overlap of the two entities' neighbours
+    pub shared_neighbors: f32,
+    /// Whether both names appear in the same episode
+    pub comentioned: bool,
+    pub confidence: f32,
+}
+
+/// An edge of a merged entity that now points at the survivor
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RewiredEdge {
+    pub edge: Uuid,
+    /// Whether the merged entity was the edge's `from_entity` (else `to_entity`)
+    pub from_side: bool,
+}
+
+/// An edge of a merged entity folded into the survivor's edge to the same neighbour
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CombinedEdge {
+    /// The merged entity's edge as it was before the merge
+    pub original: RelationshipEdge,
+    /// The survivor's edge that absorbed it
+    pub into: Uuid,
+    /// Strength and activations added to `into`
+    pub added_strength: f32,
+    pub added_activations: u32,
+}
+
+/// Everything needed to undo an entity merge
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EntityMergeRecord {
+    pub id: Uuid,
+    pub survivor: Uuid,
+    /// The merged entity as it was before the merge
+    pub merged: EntityNode,
+    /// The survivor as it was before the merge
+    pub survivor_before: EntityNode,
+    /// Names and labels the survivor gained
+    pub added_aliases: Vec<String>,
+    pub added_labels: Vec<EntityLabel>,
+    pub rewired_edges: Vec<RewiredEdge>,
+    pub combined_edges: Vec<CombinedEdge>,
+    /// Edges between the two entities, dropped rather than turned into self-loops
+    pub dropped_edges: Vec<RelationshipEdge>,
+    /// Episodes that referenced the merged entity, and whether each already
+    /// referenced the survivor
+    pub episodes: Vec<(Uuid, bool)>,
+    /// Resolver confidence for automatic merges (None when requested explicitly)
+    pub confidence: Option<f32>,
+    pub merged_at: DateTime<Utc>,
+}
+
 /// Graph memory storage and operations
 pub struct GraphMemory {
     /// RocksDB storage for entities
@@ -948,7 +1172,7 @@ pub struct GraphMemory {
     entity_stemmed_index: Arc<parking_lot::RwLock<HashMap<String, Uuid>>>,
 
     // === Atomic counters for O(1) stats (P1 fix) ===
-    /// Entity count - initialized from entity_name_index.len(), updated on add
+    /// Entity count - initialized from the distinct UUIDs in entity_name_index, updated on add
     entity_count: Arc<AtomicUsize>,
 
     /// Relationship count - initialized on startup, updated on add
@@ -966,6 +1190,17 @@ pub struct GraphMemory {
     /// Used when string-based dedup (exact/case/stemmed) fails — catches synonyms
     /// like "authentication" ↔ "auth" via cosine similarity.
     entity_embedding_cache: Arc<parking_lot::RwLock<Vec<(Uuid, Vec<f32>)>>>,
+
+    /// RocksDB storage for entity merge records (merge ID -> EntityMergeRecord),
+    /// kept so merges can be undone
+    entity_merges_db: Arc<DB>,
+
+    /// Entity resolution thresholds used by `add_entity` and `resolve_entities`
+    resolver_config: Arc<parking_lot::RwLock<EntityResolverConfig>>,
+
+    /// Report from the last `run_analytics` (per-entity scores are persisted
+    /// on the entities themselves; the report is recomputed after a restart)
+    last_analytics: Arc<parking_lot::RwLock<Option<GraphAnalytics>>>,
 }
 
 impl GraphMemory {
@@ -976,6 +1211,8 @@ impl GraphMemory {
         let mut opts = Options::default();
         opts.create_if_missing(true);
         opts.set_compression_type(rocksdb::DBCompressionType::Lz4);
+        // Keep obsolete WAL files until the backup archiver has copied them (PITR)
+        opts.set_wal_ttl_seconds(crate::backup::WAL_RETENTION_SECS);
 
         let entities_db = Arc::new(DB::open(&opts, path.join("graph_entities"))?);
         let relationships_db = Arc::new(DB::open(&opts, path.join("graph_relationships"))?);
@@ -988,6 +1225,7 @@ impl GraphMemory {
             Arc::new(DB::open(&opts, path.join("graph_entity_lowercase_index"))?);
         let entity_stemmed_index_db =
             Arc::new(DB::open(&opts, path.join("graph_entity_stemmed_index"))?);
+        let entity_merges_db = Arc::new(DB::open(&opts, path.join("graph_entity_merges"))?);
 
         // Load entity name index from persisted DB (O(n) but faster than deserializing entities)
         // If empty, migrate from entities_db (one-time migration for existing data)
@@ -1002,7 +1240,8 @@ impl GraphMemory {
         let entity_stemmed_index =
             Self::load_or_migrate_stemmed_index(&entity_stemmed_index_db, &entity_name_index)?;
 
-        let entity_count = entity_name_index.len();
+        // Aliases share their entity's UUID, so count distinct UUIDs
+        let entity_count = entity_name_index.values().collect::<HashSet<_>>().len();
 
         // Count relationships and episodes during startup (one-time cost)
         // This is O(n) at startup, but get_stats() will be O(1) at runtime
@@ -1033,6 +1272,9 @@ impl GraphMemory {
             episode_count: Arc::new(AtomicUsize::new(episode_count)),
             synapse_update_lock: Arc::new(parking_lot::Mutex::new(())),
             entity_embedding_cache: Arc::new(parking_lot::RwLock::new(entity_embedding_cache)),
+            entity_merges_db,
+            resolver_config: Arc::new(parking_lot::RwLock::new(EntityResolverConfig::default())),
+            last_analytics: Arc::new(parking_lot::RwLock::new(None)),
         };
 
         if entity_count > 0 || relationship_count > 0 || episode_count > 0 {
@@ -1071,12 +1313,7 @@ impl GraphMemory {
             let entity_iter = entities_db.iterator(rocksdb::IteratorMode::Start);
             let mut migrated_count = 0;
             for (_, value) in entity_iter.flatten() {
-                if let Ok(entity) = bincode::serde::decode_from_slice::<EntityNode, _>(
-                    &value,
-                    bincode::config::standard(),
-                )
-                .map(|(v, _)| v)
-                {
+                if let Ok(entity) = decode_entity(&value) {
                     // Store in index DB: name -> UUID bytes
                     index_db.put(entity.name.as_bytes(), entity.uuid.as_bytes())?;
                     index.insert(entity.name.clone(), entity.uuid);
@@ -1130,7 +1367,7 @@ impl GraphMemory {
     /// Load stemmed name->UUID index, or migrate from name_index if empty
     ///
     /// This enables O(1) linguistic entity lookup: "running" matches "run"
-    /// Uses Porter2 stemmer for English language stemming.
+    /// Uses the stemmer of the name's detected language (Snowball or Hindi suffix rules).
     fn load_or_migrate_stemmed_index(
         stemmed_db: &DB,
         name_index: &HashMap<String, Uuid>,
@@ -1150,9 +1387,8 @@ impl GraphMemory {
 
         // If empty but name_index has data, migrate (one-time operation)
         if index.is_empty() && !name_index.is_empty() {
-            let stemmer = Stemmer::create(Algorithm::English);
             for (name, uuid) in name_index {
-                let stemmed_name = Self::stem_entity_name(&stemmer, name);
+                let stemmed_name = Self::stem_entity_name(name);
                 stemmed_db.put(stemmed_name.as_bytes(), uuid.as_bytes())?;
                 index.insert(stemmed_name, *uuid);
             }
@@ -1166,13 +1402,33 @@ impl GraphMemory {
     ///
     /// For multi-word names (e.g., "New York City"), stems each word and joins.
     /// Returns lowercase stemmed version for consistent matching.
-    fn stem_entity_name(stemmer: &Stemmer, name: &str) -> String {
+    fn stem_entity_name(name: &str) -> String {
+        let stemmer = Language::detect(name).stemmer();
         name.split_whitespace()
-            .map(|word| stemmer.stem(&word.to_lowercase()).to_string())
+            .map(|word| stemmer.stem(&word.to_lowercase()))
             .collect::<Vec<_>>()
             .join(" ")
     }
 
+    /// Stemmed lookup keys for a name: its own language first, then English
+    ///
+    /// Entities indexed before language detection were stemmed in English.
+    fn stemmed_name_candidates(name: &str) -> Vec<String> {
+        let mut candidates = vec![Self::stem_entity_name(name)];
+        if Language::detect(name) != Language::English {
+            let english = Language::English.stemmer();
+            let stemmed = name
+                .split_whitespace()
+                .map(|word| english.stem(&word.to_lowercase()))
+                .collect::<Vec<_>>()
+                .join(" ");
+            if !candidates.contains(&stemmed) {
+                candidates.push(stemmed);
+            }
+        }
+        candidates
+    }
+
     /// Count entries in a RocksDB database (one-time startup cost)
     fn count_db_entries(db: &DB) -> usize {
         db.iterator(rocksdb::IteratorMode::Start).count()
@@ -1189,13 +1445,11 @@ impl GraphMemory {
         name_index: &HashMap<String, Uuid>,
     ) -> Vec<(Uuid, Vec<f32>)> {
         let mut cache = Vec::new();
-        for uuid in name_index.values() {
+        let uuids: HashSet<&Uuid> = name_index.values().collect();
+        for uuid in uuids {
             let key = uuid.as_bytes();
             if let Ok(Some(value)) = entities_db.get(key) {
-                if let Ok((entity, _)) = bincode::serde::decode_from_slice::<EntityNode, _>(
-                    &value,
-                    bincode::config::standard(),
-                ) {
+                if let Ok(entity) = decode_entity(&value) {
                     if let Some(emb) = entity.name_embedding {
                         cache.push((*uuid, emb));
                     }
@@ -1229,35 +1483,30 @@ impl GraphMemory {
 
         // Tier 3: Stemmed match (O(1)) — "running" matches "run"
         if existing_uuid.is_none() {
-            let stemmer = Stemmer::create(Algorithm::English);
-            let stemmed_name = Self::stem_entity_name(&stemmer, &entity.name);
             let index = self.entity_stemmed_index.read();
-            existing_uuid = index.get(&stemmed_name).cloned();
+            existing_uuid = Self::stemmed_name_candidates(&entity.name)
+                .iter()
+                .find_map(|stemmed_name| index.get(stemmed_name).cloned());
         }
 
         // Tier 4: Embedding-based concept merge (O(n) over cache)
         // Catches synonyms like "authentication" ↔ "auth" that string matching misses.
         // Only runs when the entity carries a name_embedding (populated by caller).
+        // The incoming name is kept as an alias, so the next mention hits tier 1.
+        let mut new_alias = false;
         if existing_uuid.is_none() {
             if let Some(ref new_emb) = entity.name_embedding {
-                let cache = self.entity_embedding_cache.read();
-                let mut best_match: Option<(Uuid, f32)> = None;
-                for (uuid, existing_emb) in cache.iter() {
-                    let sim = crate::similarity::cosine_similarity(new_emb, existing_emb);
-                    if sim >= ENTITY_CONCEPT_MERGE_THRESHOLD {
-                        if best_match.map_or(true, |(_, best_sim)| sim > best_sim) {
-                            best_match = Some((*uuid, sim));
-                        }
-                    }
-                }
-                if let Some((matched_uuid, sim)) = best_match {
+                if let Some((matched_uuid, confidence)) =
+                    self.resolve_by_embedding(new_emb, &entity.labels)?
+                {
                     tracing::debug!(
-                        "Concept merge: '{}' matched existing entity {} (cosine={:.3})",
+                        "Concept merge: '{}' matched existing entity {} (confidence={:.3})",
                         entity.name,
                         matched_uuid,
-                        sim
+                        confidence
                     );
                     existing_uuid = Some(matched_uuid);
+                    new_alias = true;
                 }
             }
         }
@@ -1273,14 +1522,22 @@ impl GraphMemory {
                 entity.created_at = existing.created_at;
                 entity.is_proper_noun = existing.is_proper_noun || entity.is_proper_noun;
 
-                // Preserve the canonical name (first-seen name wins)
-                entity.name = existing.name.clone();
+                // Preserve the canonical name (first-seen name wins) and its aliases
+                let incoming_name = std::mem::replace(&mut entity.name, existing.name.clone());
+                entity.aliases = existing.aliases;
+                if new_alias && !Self::names_entity(&entity, &incoming_name) {
+                    entity.aliases.push(incoming_name);
+                }
 
                 // Preserve existing embedding if the incoming one is None
                 if entity.name_embedding.is_none() {
                     entity.name_embedding = existing.name_embedding;
                 }
 
+                // Structural scores belong to the graph, not to the mention
+                entity.centrality = existing.centrality;
+                entity.community = existing.community;
+
                 // Update salience with frequency boost
                 // Formula: salience = base_salience * (1 + 0.1 * ln(mention_count))
                 // This caps at about 1.3x boost at 20 mentions
@@ -1309,23 +1566,16 @@ impl GraphMemory {
             is_new_entity = true;
         }
 
-        // BUG-002 FIX: Write index FIRST, then entity
-        let lowercase_name = entity.name.to_lowercase();
-        let stemmer = Stemmer::create(Algorithm::English);
-        let stemmed_name = Self::stem_entity_name(&stemmer, &entity.name);
+        self.persist_entity(&entity, is_new_entity)?;
+        Ok(entity.uuid)
+    }
 
-        // Update in-memory indices
-        {
-            let mut index = self.entity_name_index.write();
-            index.insert(entity.name.clone(), entity.uuid);
-        }
-        {
-            let mut lowercase_index = self.entity_lowercase_index.write();
-            lowercase_index.insert(lowercase_name.clone(), entity.uuid);
-        }
-        {
-            let mut stemmed_index = self.entity_stemmed_index.write();
-            stemmed_index.insert(stemmed_name.clone(), entity.uuid);
+    /// Write an entity and its name indices, updating caches and counters
+    fn persist_entity(&self, entity: &EntityNode, is_new_entity: bool) -> Result<()> {
+        // BUG-002 FIX: Write index FIRST, then entity
+        // The canonical name and every alias resolve to this entity
+        for name in std::iter::once(&entity.name).chain(&entity.aliases) {
+            self.index_entity_name(name, &entity.uuid)?;
         }
 
         // Update entity embedding cache for future concept merges
@@ -1341,17 +1591,9 @@ impl GraphMemory {
             }
         }
 
-        // Persist name->UUID mappings
-        self.entity_name_index_db
-            .put(entity.name.as_bytes(), entity.uuid.as_bytes())?;
-        self.entity_lowercase_index_db
-            .put(lowercase_name.as_bytes(), entity.uuid.as_bytes())?;
-        self.entity_stemmed_index_db
-            .put(stemmed_name.as_bytes(), entity.uuid.as_bytes())?;
-
         // Store entity in database
         let key = entity.uuid.as_bytes();
-        let value = bincode::serde::encode_to_vec(&entity, bincode::config::standard())?;
+        let value = bincode::serde::encode_to_vec(entity, bincode::config::standard())?;
         self.entities_db.put(key, value)?;
 
         // Increment counter only for truly new entities
@@ -1359,6 +1601,64 @@ impl GraphMemory {
             self.entity_count.fetch_add(1, Ordering::Relaxed);
         }
 
+        Ok(())
+    }
+
+    /// Point a name at an entity in the exact, lowercase and stemmed indices
+    fn index_entity_name(&self, name: &str, uuid: &Uuid) -> Result<()> {
+        let lowercase_name = name.to_lowercase();
+        let stemmed_name = Self::stem_entity_name(name);
+
+        // Update in-memory indices
+        self.entity_name_index
+            .write()
+            .insert(name.to_string(), *uuid);
+        self.entity_lowercase_index
+            .write()
+            .insert(lowercase_name.clone(), *uuid);
+        self.entity_stemmed_index
+            .write()
+            .insert(stemmed_name.clone(), *uuid);
+
+        // Persist name->UUID mappings
+        self.entity_name_index_db
+            .put(name.as_bytes(), uuid.as_bytes())?;
+        self.entity_lowercase_index_db
+            .put(lowercase_name.as_bytes(), uuid.as_bytes())?;
+        self.entity_stemmed_index_db
+            .put(stemmed_name.as_bytes(), uuid.as_bytes())?;
+        Ok(())
+    }
+
+    /// Import an exported entity, preserving its UUID and history
+    ///
+    /// An entity with the same UUID is overwritten. If the name already
+    /// belongs to a different entity, the import merges into it exactly as
+    /// `add_entity` would. Returns the UUID relationships must reference.
+    pub fn import_entity(&self, entity: EntityNode) -> Result<Uuid> {
+        if self.get_entity(&entity.uuid)?.is_some() {
+            self.persist_entity(&entity, false)?;
+            return Ok(entity.uuid);
+        }
+
+        let existing = self
+            .entity_name_index
+            .read()
+            .get(&entity.name)
+            .cloned()
+            .or_else(|| {
+                self.entity_lowercase_index
+                    .read()
+                    .get(&entity.name.to_lowercase())
+                    .cloned()
+            });
+        if let Some(uuid) = existing {
+            if self.get_entity(&uuid)?.is_some() {
+                return self.add_entity(entity);
+            }
+        }
+
+        self.persist_entity(&entity, true)?;
         Ok(entity.uuid)
     }
 
@@ -1366,11 +1666,7 @@ impl GraphMemory {
     pub fn get_entity(&self, uuid: &Uuid) -> Result<Option<EntityNode>> {
         let key = uuid.as_bytes();
         match self.entities_db.get(key)? {
-            Some(value) => {
-                let (entity, _): (EntityNode, _) =
-                    bincode::serde::decode_from_slice(&value, bincode::config::standard())?;
-                Ok(Some(entity))
-            }
+            Some(value) => Ok(Some(decode_entity(&value)?)),
             None => Ok(None),
         }
     }
@@ -1406,11 +1702,11 @@ impl GraphMemory {
         }
 
         // Tier 3: Stemmed match (O(1)) - "running" matches "run", "conversations" matches "conversation"
-        let stemmer = Stemmer::create(Algorithm::English);
-        let stemmed_name = Self::stem_entity_name(&stemmer, name);
         let uuid = {
             let stemmed_index = self.entity_stemmed_index.read();
-            stemmed_index.get(&stemmed_name).copied()
+            Self::stemmed_name_candidates(name)
+                .iter()
+                .find_map(|stemmed_name| stemmed_index.get(stemmed_name).copied())
         };
 
         if let Some(uuid) = uuid {
@@ -1462,8 +1758,8 @@ impl GraphMemory {
 
         let lowercase_index = self.entity_lowercase_index.read();
 
-        // Score and collect matches
-        let mut scored: Vec<(Uuid, f32)> = Vec::new();
+        // Score and collect matches (best score per entity; aliases share a UUID)
+        let mut best: HashMap<Uuid, f32> = HashMap::new();
 
         for (entity_name, uuid) in lowercase_index.iter() {
             let score = if entity_name == &name_lower {
@@ -1491,9 +1787,11 @@ impl GraphMemory {
             };
 
             if score > 0.0 {
-                scored.push((*uuid, score));
+                let entry = best.entry(*uuid).or_insert(0.0);
+                *entry = entry.max(score);
             }
         }
+        let mut scored: Vec<(Uuid, f32)> = best.into_iter().collect();
 
         // Sort by score descending
         scored.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
@@ -1508,6 +1806,639 @@ impl GraphMemory {
         Ok(results)
     }
 
+    /// Current entity resolution thresholds
+    pub fn resolver_config(&self) -> EntityResolverConfig {
+        *self.resolver_config.read()
+    }
+
+    /// Replace the entity resolution thresholds
+    pub fn set_resolver_config(&self, config: EntityResolverConfig) {
+        *self.resolver_config.write() = config;
+    }
+
+    /// Whether `name` is the entity's canonical name or one of its aliases (case-insensitive)
+    fn names_entity(entity: &EntityNode, name: &str) -> bool {
+        let name_lower = name.to_lowercase();
+        std::iter::once(&entity.name)
+            .chain(&entity.aliases)
+            .any(|n| n.to_lowercase() == name_lower)
+    }
+
+    /// How compatible two label sets are for a merge (0.0 = conflicting, 1.0 = identical)
+    ///
+    /// Labels in the same family (Organization/Technology/Product/Skill) or a
+    /// generic label (Concept/Keyword/Other) on one side are a soft match.
+    fn label_compatibility(a: &[EntityLabel], b: &[EntityLabel]) -> f32 {
+        fn family(label: &EntityLabel) -> Option<u8> {
+            match label {
+                EntityLabel::Person => Some(0),
+                EntityLabel::Location => Some(1),
+                EntityLabel::Date => Some(2),
+                EntityLabel::Event => Some(3),
+                EntityLabel::Organization
+                | EntityLabel::Technology
+                | EntityLabel::Product
+                | EntityLabel::Skill => Some(4),
+                EntityLabel::Concept | EntityLabel::Keyword | EntityLabel::Other(_) => None,
+            }
+        }
+
+        if a.is_empty() || b.is_empty() || a.iter().any(|label| b.contains(label)) {
+            return 1.0;
+        }
+        let families_a: HashSet<u8> = a.iter().filter_map(family).collect();
+        let families_b: HashSet<u8> = b.iter().filter_map(family).collect();
+        if families_a.is_empty() || families_b.is_empty() || !families_a.is_disjoint(&families_b) {
+            0.95
+        } else {
+            0.0
+        }
+    }
+
+    /// Find the existing entity a new name embedding most likely refers to
+    ///
+    /// A brand-new name has no neighbours or episodes yet, so only name
+    /// similarity and label compatibility count towards the confidence.
+    fn resolve_by_embedding(
+        &self,
+        embedding: &[f32],
+        labels: &[EntityLabel],
+    ) -> Result<Option<(Uuid, f32)>> {
+        let config = self.resolver_config();
+        let mut candidates: Vec<(Uuid, f32)> = {
+            let cache = self.entity_embedding_cache.read();
+            cache
+                .iter()
+                .map(|(uuid, existing)| {
+                    (
+                        *uuid,
+                        crate::similarity::cosine_similarity(embedding, existing),
+                    )
+                })
+                .filter(|(_, sim)| *sim >= config.candidate_similarity)
+                .collect()
+        };
+        candidates.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+
+        for (uuid, sim) in candidates.into_iter().take(5) {
+            let Some(existing) = self.get_entity(&uuid)? else {
+                continue;
+            };
+            let confidence = sim * Self::label_compatibility(labels, &existing.labels);
+            if confidence >= config.min_confidence {
+                return Ok(Some((uuid, confidence)));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Record an extra name for an entity, so lookups by that name resolve to it
+    pub fn add_alias(&self, entity_uuid: &Uuid, alias: &str) -> Result<EntityNode> {
+        let alias = alias.trim();
+        if alias.is_empty() {
+            anyhow::bail!("alias must not be empty");
+        }
+        let mut entity = self
+            .get_entity(entity_uuid)?
+            .ok_or_else(|| anyhow::anyhow!("entity {entity_uuid} not found"))?;
+        if Self::names_entity(&entity, alias) {
+            return Ok(entity);
+        }
+
+        let owner = self
+            .entity_lowercase_index
+            .read()
+            .get(&alias.to_lowercase())
+            .copied();
+        if let Some(owner) = owner.filter(|uuid| uuid != entity_uuid) {
+            if let Some(other) = self.get_entity(&owner)? {
+                anyhow::bail!(
+                    "'{alias}' already names entity '{}' ({owner}); merge the entities instead",
+                    other.name
+                );
+            }
+        }
+
+        entity.aliases.push(alias.to_string());
+        self.persist_entity(&entity, false)?;
+        Ok(entity)
+    }
+
+    /// Remove an alias from an entity (the canonical name cannot be removed)
+    pub fn remove_alias(&self, entity_uuid: &Uuid, alias: &str) -> Result<EntityNode> {
+        let mut entity = self
+            .get_entity(entity_uuid)?
+            .ok_or_else(|| anyhow::anyhow!("entity {entity_uuid} not found"))?;
+        let alias_lower = alias.trim().to_lowercase();
+        let (removed, kept): (Vec<String>, Vec<String>) = std::mem::take(&mut entity.aliases)
+            .into_iter()
+            .partition(|a| a.to_lowercase() == alias_lower);
+        entity.aliases = kept;
+        if removed.is_empty() {
+            anyhow::bail!("'{}' is not an alias of '{}'", alias.trim(), entity.name);
+        }
+
+        for name in &removed {
+            self.unindex_entity_name(name, &entity)?;
+        }
+        self.persist_entity(&entity, false)?;
+        Ok(entity)
+    }
+
+    /// Drop a former name of `entity` from the name indices
+    ///
+    /// Keys still produced by one of its remaining names, or owned by another
+    /// entity, are left alone.
+    fn unindex_entity_name(&self, name: &str, entity: &EntityNode) -> Result<()> {
+        let remaining: Vec<&String> = std::iter::once(&entity.name)
+            .chain(&entity.aliases)
+            .collect();
+
+        if !remaining.iter().any(|n| n.as_str() == name) {
+            let mut index = self.entity_name_index.write();
+            if index.get(name) == Some(&entity.uuid) {
+                index.remove(name);
+                self.entity_name_index_db.delete(name.as_bytes())?;
+            }
+        }
+
+        let lowercase_name = name.to_lowercase();
+        if !remaining.iter().any(|n| n.to_lowercase() == lowercase_name) {
+            let mut index = self.entity_lowercase_index.write();
+            if index.get(&lowercase_name) == Some(&entity.uuid) {
+                index.remove(&lowercase_name);
+                self.entity_lowercase_index_db
+                    .delete(lowercase_name.as_bytes())?;
+            }
+        }
+
+        let stemmed_name = Self::stem_entity_name(name);
+        if !remaining
+            .iter()
+            .any(|n| Self::stem_entity_name(n) == stemmed_name)
+        {
+            let mut index = self.entity_stemmed_index.write();
+            if index.get(&stemmed_name) == Some(&entity.uuid) {
+                index.remove(&stemmed_name);
+                self.entity_stemmed_index_db
+                    .delete(stemmed_name.as_bytes())?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Score every pair of entities whose name embeddings are similar enough
+    /// to be the same thing, best candidates first
+    pub fn find_merge_candidates(
+        &self,
+        config: &EntityResolverConfig,
+        limit: usize,
+    ) -> Result<Vec<EntityMergeCandidate>> {
+        let cache = self.entity_embedding_cache.read().clone();
+
+        let mut pairs = Vec::new();
+        for (i, (uuid_a, emb_a)) in cache.iter().enumerate() {
+            for (uuid_b, emb_b) in &cache[i + 1..] {
+                let sim = crate::similarity::cosine_similarity(emb_a, emb_b);
+                if sim >= config.candidate_similarity {
+                    pairs.push((*uuid_a, *uuid_b, sim));
+                }
+            }
+        }
+
+        let mut entities: HashMap<Uuid, EntityNode> = HashMap::new();
+        let mut neighbors: HashMap<Uuid, HashSet<Uuid>> = HashMap::new();
+        let mut episodes: HashMap<Uuid, HashSet<Uuid>> = HashMap::new();
+        let mut candidates = Vec::new();
+
+        for (uuid_a, uuid_b, similarity) in pairs {
+            for uuid in [uuid_a, uuid_b] {
+                if let Entry::Vacant(slot) = neighbors.entry(uuid) {
+                    if let Some(entity) = self.get_entity(&uuid)? {
+                        entities.insert(uuid, entity);
+                    }
+                    let adjacent = self
+                        .get_entity_relationships(&uuid)?
+                        .into_iter()
+                        .map(|edge| {
+                            if edge.from_entity == uuid {
+                                edge.to_entity
+                            } else {
+                                edge.from_entity
+                            }
+                        })
+                        .collect();
+                    slot.insert(adjacent);
+                    episodes.insert(uuid, self.entity_episode_uuids(&uuid).into_iter().collect());
+                }
+            }
+            let (Some(a), Some(b)) = (entities.get(&uuid_a), entities.get(&uuid_b)) else {
+                continue;
+            };
+
+            let label_compatibility = Self::label_compatibility(&a.labels, &b.labels);
+            if label_compatibility == 0.0 {
+                continue;
+            }
+
+            let mut neighbors_a = neighbors[&uuid_a].clone();
+            let mut neighbors_b = neighbors[&uuid_b].clone();
+            neighbors_a.remove(&uuid_b);
+            neighbors_b.remove(&uuid_a);
+            let union = neighbors_a.union(&neighbors_b).count();
+            let shared_neighbors = if union == 0 {
+                0.0
+            } else {
+                neighbors_a.intersection(&neighbors_b).count() as f32 / union as f32
+            };
+            let comentioned = !episodes[&uuid_a].is_disjoint(&episodes[&uuid_b]);
+
+            let mut confidence =
+                similarity * label_compatibility + config.cooccurrence_weight * shared_neighbors;
+            if comentioned {
+                confidence -= config.comention_penalty;
+            }
+
+            // Keep the better-established entity: more mentions, then older
+            let a_survives = a.mention_count > b.mention_count
+                || (a.mention_count == b.mention_count && a.created_at <= b.created_at);
+            let (survivor, duplicate) = if a_survives { (a, b) } else { (b, a) };
+
+            candidates.push(EntityMergeCandidate {
+                survivor: survivor.uuid,
+                survivor_name: survivor.name.clone(),
+                duplicate: duplicate.uuid,
+                duplicate_name: duplicate.name.clone(),
+                similarity,
+                label_compatibility,
+                shared_neighbors,
+                comentioned,
+                confidence: confidence.clamp(0.0, 1.0),
+            });
+        }
+
+        candidates.sort_by(|a, b| {
+            b.confidence
+                .partial_cmp(&a.confidence)
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+        candidates.truncate(limit);
+        Ok(candidates)
+    }
+
+    /// Merge every candidate pair at or above `config.min_confidence`
+    ///
+    /// Returns the scored candidates and the merges performed (none when
+    /// `dry_run` is set). An entity absorbed by one merge is skipped by later ones.
+    pub fn resolve_entities(
+        &self,
+        config: &EntityResolverConfig,
+        limit: usize,
+        dry_run: bool,
+    ) -> Result<(Vec<EntityMergeCandidate>, Vec<EntityMergeRecord>)> {
+        let candidates = self.find_merge_candidates(config, limit)?;
+        let mut merges = Vec::new();
+        if dry_run {
+            return Ok((candidates, merges));
+        }
+
+        let mut absorbed: HashSet<Uuid> = HashSet::new();
+        for candidate in &candidates {
+            if candidate.confidence < config.min_confidence
+                || absorbed.contains(&candidate.survivor)
+                || absorbed.contains(&candidate.duplicate)
+            {
+                continue;
+            }
+            let record = self.merge_entities(
+                &candidate.survivor,
+                &candidate.duplicate,
+                Some(candidate.confidence),
+            )?;
+            absorbed.insert(candidate.duplicate);
+            merges.push(record);
+        }
+        Ok((candidates, merges))
+    }
+
+    /// Fold `duplicate` into `survivor`
+    ///
+    /// The survivor takes over the duplicate's edges and episodes and keeps its
+    /// names as aliases. Where both entities have an edge to the same neighbour
+    /// the strengths and activation counts are summed; edges between the two
+    /// are dropped. Everything changed is recorded so the merge can be undone.
+    pub fn merge_entities(
+        &self,
+        survivor_uuid: &Uuid,
+        duplicate_uuid: &Uuid,
+        confidence: Option<f32>,
+    ) -> Result<EntityMergeRecord> {
+        if survivor_uuid == duplicate_uuid {
+            anyhow::bail!("cannot merge an entity into itself");
+        }
+        let mut survivor = self
+            .get_entity(survivor_uuid)?
+            .ok_or_else(|| anyhow::anyhow!("entity {survivor_uuid} not found"))?;
+        let duplicate = self
+            .get_entity(duplicate_uuid)?
+            .ok_or_else(|| anyhow::anyhow!("entity {duplicate_uuid} not found"))?;
+
+        let mut record = EntityMergeRecord {
+            id: Uuid::new_v4(),
+            survivor: *survivor_uuid,
+            merged: duplicate.clone(),
+            survivor_before: survivor.clone(),
+            added_aliases: Vec::new(),
+            added_labels: Vec::new(),
+            rewired_edges: Vec::new(),
+            combined_edges: Vec::new(),
+            dropped_edges: Vec::new(),
+            episodes: Vec::new(),
+            confidence,
+            merged_at: Utc::now(),
+        };
+
+        // Edges: rewire, combine with the survivor's parallel edge, or drop
+        for edge in self.get_entity_relationships(duplicate_uuid)? {
+            let from_side = edge.from_entity == *duplicate_uuid;
+            let other = if from_side {
+                edge.to_entity
+            } else {
+                edge.from_entity
+            };
+
+            if other == *survivor_uuid || other == *duplicate_uuid {
+                self.delete_relationship(&edge.uuid)?;
+                record.dropped_edges.push(edge);
+            } else if let Some(mut into) = self.find_relationship_between(survivor_uuid, &other)? {
+                let before = into.strength;
+                into.strength = (into.strength + edge.strength).min(1.0);
+                into.activation_count = into.activation_count.saturating_add(edge.activation_count);
+                into.last_activated = into.last_activated.max(edge.last_activated);
+                self.write_relationship(&into)?;
+                self.delete_relationship(&edge.uuid)?;
+                record.combined_edges.push(CombinedEdge {
+                    into: into.uuid,
+                    added_strength: into.strength - before,
+                    added_activations: edge.activation_count,
+                    original: edge,
+                });
+            } else {
+                record.rewired_edges.push(RewiredEdge {
+                    edge: edge.uuid,
+                    from_side,
+                });
+                self.move_edge_endpoint(edge, duplicate_uuid, survivor_uuid)?;
+            }
+        }
+
+        // Episodes: reference the survivor instead
+        for episode_uuid in self.entity_episode_uuids(duplicate_uuid) {
+            let key = format!("{duplicate_uuid}:{episode_uuid}");
+            let Some(mut episode) = self.get_episode(&episode_uuid)? else {
+                self.entity_episodes_db.delete(key.as_bytes())?;
+                continue;
+            };
+            let had_survivor = episode.entity_refs.contains(survivor_uuid);
+            if had_survivor {
+                episode.entity_refs.retain(|uuid| uuid != duplicate_uuid);
+            } else {
+                for uuid in episode.entity_refs.iter_mut() {
+                    if uuid == duplicate_uuid {
+                        *uuid = *survivor_uuid;
+                    }
+                }
+            }
+            self.write_episode(&episode)?;
+            self.entity_episodes_db.delete(key.as_bytes())?;
+            self.index_entity_episode(survivor_uuid, &episode_uuid)?;
+            record.episodes.push((episode_uuid, had_survivor));
+        }
+
+        // Survivor absorbs the duplicate's names, labels and mentions
+        for name in std::iter::once(&duplicate.name).chain(&duplicate.aliases) {
+            if !Self::names_entity(&survivor, name) {
+                survivor.aliases.push(name.clone());
+                record.added_aliases.push(name.clone());
+            }
+        }
+        for label in &duplicate.labels {
+            if !survivor.labels.contains(label) {
+                survivor.labels.push(label.clone());
+                record.added_labels.push(label.clone());
+            }
+        }
+        survivor.mention_count += duplicate.mention_count;
+        survivor.created_at = survivor.created_at.min(duplicate.created_at);
+        survivor.last_seen_at = survivor.last_seen_at.max(duplicate.last_seen_at);
+        survivor.salience = survivor.salience.max(duplicate.salience);
+        survivor.is_proper_noun |= duplicate.is_proper_noun;
+
+        self.entities_db.delete(duplicate_uuid.as_bytes())?;
+        self.entity_embedding_cache
+            .write()
+            .retain(|(uuid, _)| uuid != duplicate_uuid);
+        self.entity_count.fetch_sub(1, Ordering::Relaxed);
+        self.persist_entity(&survivor, false)?;
+        // Names that differ from a survivor name only by case were not added
+        // as aliases, but their exact-match keys must stop pointing at the duplicate
+        for name in std::iter::once(&duplicate.name).chain(&duplicate.aliases) {
+            self.index_entity_name(name, survivor_uuid)?;
+        }
+
+        let value = bincode::serde::encode_to_vec(&record, bincode::config::standard())?;
+        self.entity_merges_db.put(record.id.as_bytes(), value)?;
+
+        tracing::info!(
+            "Merged entity '{}' ({}) into '{}' ({}): {} rewired, {} combined, {} dropped edges",
+            duplicate.name,
+            duplicate_uuid,
+            survivor.name,
+            survivor_uuid,
+            record.rewired_edges.len(),
+            record.combined_edges.len(),
+            record.dropped_edges.len()
+        );
+        Ok(record)
+    }
+
+    /// Reverse an entity merge, restoring the merged entity with its edges and episodes
+    ///
+    /// Merges must be undone newest first when the survivor was itself merged
+    /// into another entity afterwards.
+    pub fn undo_entity_merge(&self, merge_id: &Uuid) -> Result<EntityMergeRecord> {
+        let record = self
+            .get_entity_merge(merge_id)?
+            .ok_or_else(|| anyhow::anyhow!("merge {merge_id} not found"))?;
+        let merged_uuid = record.merged.uuid;
+        let mut survivor = self.get_entity(&record.survivor)?.ok_or_else(|| {
+            anyhow::anyhow!(
+                "entity {} no longer exists; undo the merge that absorbed it first",
+                record.survivor
+            )
+        })?;
+        if self.get_entity(&merged_uuid)?.is_some() {
+            anyhow::bail!("entity {merged_uuid} already exists");
+        }
+
+        // Survivor gives back what the merge added. Mentions and sightings
+        // recorded since the merge stay with the survivor.
+        survivor
+            .aliases
+            .retain(|alias| !record.added_aliases.contains(alias));
+        survivor
+            .labels
+            .retain(|label| !record.added_labels.contains(label));
+        survivor.mention_count = survivor
+            .mention_count
+            .saturating_sub(record.merged.mention_count)
+            .max(1);
+        survivor.created_at = record.survivor_before.created_at;
+        survivor.salience = record.survivor_before.salience;
+        survivor.is_proper_noun = record.survivor_before.is_proper_noun;
+        for alias in &record.added_aliases {
+            self.unindex_entity_name(alias, &survivor)?;
+        }
+        self.persist_entity(&survivor, false)?;
+        self.persist_entity(&record.merged, true)?;
+
+        for rewired in &record.rewired_edges {
+            let Some(edge) = self.get_relationship(&rewired.edge)? else {
+                continue;
+            };
+            let endpoint = if rewired.from_side {
+                edge.from_entity
+            } else {
+                edge.to_entity
+            };
+            if endpoint == record.survivor {
+                self.move_edge_endpoint(edge, &record.survivor, &merged_uuid)?;
+            }
+        }
+        for combined in &record.combined_edges {
+            if let Some(mut into) = self.get_relationship(&combined.into)? {
+                into.strength = (into.strength - combined.added_strength).max(LTP_MIN_STRENGTH);
+                into.activation_count = into
+                    .activation_count
+                    .saturating_sub(combined.added_activations);
+                self.write_relationship(&into)?;
+            }
+            self.restore_relationship(&combined.original)?;
+        }
+        for edge in &record.dropped_edges {
+            self.restore_relationship(edge)?;
+        }
+
+        for (episode_uuid, had_survivor) in &record.episodes {
+            let Some(mut episode) = self.get_episode(episode_uuid)? else {
+                continue;
+            };
+            if *had_survivor {
+                if !episode.entity_refs.contains(&merged_uuid) {
+                    episode.entity_refs.push(merged_uuid);
+                }
+            } else {
+                for uuid in episode.entity_refs.iter_mut() {
+                    if *uuid == record.survivor {
+                        *uuid = merged_uuid;
+                    }
+                }
+                let key = format!("{}:{episode_uuid}", record.survivor);
+                self.entity_episodes_db.delete(key.as_bytes())?;
+            }
+            self.write_episode(&episode)?;
+            self.index_entity_episode(&merged_uuid, episode_uuid)?;
+        }
+
+        self.entity_merges_db.delete(merge_id.as_bytes())?;
+        tracing::info!(
+            "Undid merge {}: restored entity '{}' ({})",
+            merge_id,
+            record.merged.name,
+            merged_uuid
+        );
+        Ok(record)
+    }
+
+    /// Get a stored entity merge record
+    pub fn get_entity_merge(&self, merge_id: &Uuid) -> Result<Option<EntityMergeRecord>> {
+        match self.entity_merges_db.get(merge_id.as_bytes())? {
+            Some(value) => {
+                let (record, _): (EntityMergeRecord, _) =
+                    bincode::serde::decode_from_slice(&value, bincode::config::standard())?;
+                Ok(Some(record))
+            }
+            None => Ok(None),
+        }
+    }
+
+    /// All entity merges that can still be undone, newest first
+    pub fn list_entity_merges(&self) -> Result<Vec<EntityMergeRecord>> {
+        let mut records = Vec::new();
+        for (_, value) in self
+            .entity_merges_db
+            .iterator(rocksdb::IteratorMode::Start)
+            .flatten()
+        {
+            if let Ok((record, _)) = bincode::serde::decode_from_slice::<EntityMergeRecord, _>(
+                &value,
+                bincode::config::standard(),
+            ) {
+                records.push(record);
+            }
+        }
+        records.sort_by_key(|r| std::cmp::Reverse(r.merged_at));
+        Ok(records)
+    }
+
+    /// Move one endpoint of an edge from `from` to `to`, keeping its indices in step
+    fn move_edge_endpoint(&self, mut edge: RelationshipEdge, from: &Uuid, to: &Uuid) -> Result<()> {
+        let other = if edge.from_entity == *from {
+            edge.from_entity = *to;
+            edge.to_entity
+        } else {
+            edge.to_entity = *to;
+            edge.from_entity
+        };
+        let old_key = format!("{from}:{}", edge.uuid);
+        self.entity_edges_db.delete(old_key.as_bytes())?;
+        self.remove_entity_pair_index(from, &other)?;
+
+        self.write_relationship(&edge)?;
+        self.index_entity_edge(to, &edge.uuid)?;
+        self.index_entity_pair(to, &other, &edge.uuid)?;
+        Ok(())
+    }
+
+    /// Recreate an edge removed by a merge, unless either endpoint is gone
+    /// or the pair has been connected again since
+    fn restore_relationship(&self, edge: &RelationshipEdge) -> Result<()> {
+        if self.get_relationship(&edge.uuid)?.is_some()
+            || self.get_entity(&edge.from_entity)?.is_none()
+            || self.get_entity(&edge.to_entity)?.is_none()
+            || self
+                .find_relationship_between(&edge.from_entity, &edge.to_entity)?
+                .is_some()
+        {
+            return Ok(());
+        }
+        self.persist_new_relationship(edge)
+    }
+
+    /// Overwrite a stored edge (no index or counter changes)
+    fn write_relationship(&self, edge: &RelationshipEdge) -> Result<()> {
+        let value = bincode::serde::encode_to_vec(edge, bincode::config::standard())?;
+        self.relationships_db.put(edge.uuid.as_bytes(), value)?;
+        Ok(())
+    }
+
+    /// Overwrite a stored episode (no index or counter changes)
+    fn write_episode(&self, episode: &EpisodicNode) -> Result<()> {
+        let value = bincode::serde::encode_to_vec(episode, bincode::config::standard())?;
+        self.episodes_db.put(episode.uuid.as_bytes(), value)?;
+        Ok(())
+    }
+
     /// Canonical pair key for the entity-pair index.
     /// Uses min/max UUID ordering so A→B and B→A produce the same key.
     fn pair_key(entity_a: &Uuid, entity_b: &Uuid) -> String {
@@ -1604,9 +2535,36 @@ impl GraphMemory {
         edge.uuid = Uuid::new_v4();
         edge.created_at = Utc::now();
 
+        self.persist_new_relationship(&edge)?;
+        Ok(edge.uuid)
+    }
+
+    /// Import an exported relationship, preserving its UUID and strength
+    ///
+    /// An edge with the same UUID is overwritten. If the two entities are
+    /// already connected by another edge, that edge is kept untouched (an
+    /// import is not a co-activation) and its UUID returned.
+    pub fn import_relationship(&self, edge: RelationshipEdge) -> Result<Uuid> {
+        if self.get_relationship(&edge.uuid)?.is_some() {
+            let value = bincode::serde::encode_to_vec(&edge, bincode::config::standard())?;
+            self.relationships_db.put(edge.uuid.as_bytes(), value)?;
+            return Ok(edge.uuid);
+        }
+        if let Some(existing) =
+            self.find_relationship_between(&edge.from_entity, &edge.to_entity)?
+        {
+            return Ok(existing.uuid);
+        }
+
+        self.persist_new_relationship(&edge)?;
+        Ok(edge.uuid)
+    }
+
+    /// Store a new edge with its entity indices, then enforce the degree cap
+    fn persist_new_relationship(&self, edge: &RelationshipEdge) -> Result<()> {
         // Store relationship
         let key = edge.uuid.as_bytes();
-        let value = bincode::serde::encode_to_vec(&edge, bincode::config::standard())?;
+        let value = bincode::serde::encode_to_vec(edge, bincode::config::standard())?;
         self.relationships_db.put(key, value)?;
 
         // Increment relationship counter
@@ -1625,7 +2583,7 @@ impl GraphMemory {
         self.prune_entity_if_over_degree(&edge.from_entity)?;
         self.prune_entity_if_over_degree(&edge.to_entity)?;
 
-        Ok(edge.uuid)
+        Ok(())
     }
 
     /// Index an edge for an entity
@@ -2039,6 +2997,7 @@ impl GraphMemory {
             &self.entity_name_index_db,
             &self.entity_lowercase_index_db,
             &self.entity_stemmed_index_db,
+            &self.entity_merges_db,
         ] {
             let mut batch = rocksdb::WriteBatch::default();
             let iter = db.iterator(rocksdb::IteratorMode::Start);
@@ -2052,6 +3011,7 @@ impl GraphMemory {
         self.entity_name_index.write().clear();
         self.entity_lowercase_index.write().clear();
         self.entity_stemmed_index.write().clear();
+        self.entity_embedding_cache.write().clear();
 
         // Reset counters
         self.entity_count.store(0, Ordering::Relaxed);
@@ -2112,17 +3072,10 @@ impl GraphMemory {
         }
     }
 
-    /// Get all episodes that contain a specific entity
-    ///
-    /// Uses inverted index for O(k) lookup instead of O(n) full scan.
-    /// Collects episode UUIDs first, then batch-reads them using multi_get.
-    /// Crucial for spreading activation algorithm.
-    pub fn get_episodes_by_entity(&self, entity_uuid: &Uuid) -> Result<Vec<EpisodicNode>> {
+    /// UUIDs of the episodes indexed under an entity (prefix scan, no episode reads)
+    fn entity_episode_uuids(&self, entity_uuid: &Uuid) -> Vec<Uuid> {
         let prefix = format!("{entity_uuid}:");
-        tracing::debug!("get_episodes_by_entity: prefix {}", &prefix[..12]);
-
-        // Phase 1: Collect episode UUIDs from index (fast prefix scan, no data transfer)
-        let mut episode_uuids: Vec<Uuid> = Vec::new();
+        let mut episode_uuids = Vec::new();
         let iter = self.entity_episodes_db.prefix_iterator(prefix.as_bytes());
         for (key, _) in iter.flatten() {
             if let Ok(key_str) = std::str::from_utf8(&key) {
@@ -2136,6 +3089,20 @@ impl GraphMemory {
                 }
             }
         }
+        episode_uuids
+    }
+
+    /// Get all episodes that contain a specific entity
+    ///
+    /// Uses inverted index for O(k) lookup instead of O(n) full scan.
+    /// Collects episode UUIDs first, then batch-reads them using multi_get.
+    /// Crucial for spreading activation algorithm.
+    pub fn get_episodes_by_entity(&self, entity_uuid: &Uuid) -> Result<Vec<EpisodicNode>> {
+        let prefix = format!("{entity_uuid}:");
+        tracing::debug!("get_episodes_by_entity: prefix {}", &prefix[..12]);
+
+        // Phase 1: Collect episode UUIDs from index (fast prefix scan, no data transfer)
+        let episode_uuids = self.entity_episode_uuids(entity_uuid);
 
         if episode_uuids.is_empty() {
             return Ok(Vec::new());
@@ -2848,8 +3815,7 @@ impl GraphMemory {
             }
 
             let (_, value) = result?;
-            let (entity, _): (EntityNode, _) =
-                bincode::serde::decode_from_slice(&value, bincode::config::standard())?;
+            let entity = decode_entity(&value)?;
 
             let entity_matches = self.match_pattern(&entity.uuid, pattern, min_strength)?;
             for m in entity_matches {
@@ -3380,6 +4346,27 @@ impl GraphMemory {
         Ok(pruned_count)
     }
 
+    /// Get references to all RocksDB databases for backup
+    ///
+    /// Names match the directories under the graph path.
+    pub fn databases(&self) -> Vec<(&str, &Arc<DB>)> {
+        vec![
+            ("graph_entities", &self.entities_db),
+            ("graph_relationships", &self.relationships_db),
+            ("graph_episodes", &self.episodes_db),
+            ("graph_entity_edges", &self.entity_edges_db),
+            ("graph_entity_pair_index", &self.entity_pair_index_db),
+            ("graph_entity_episodes", &self.entity_episodes_db),
+            ("graph_entity_name_index", &self.entity_name_index_db),
+            (
+                "graph_entity_lowercase_index",
+                &self.entity_lowercase_index_db,
+            ),
+            ("graph_entity_stemmed_index", &self.entity_stemmed_index_db),
+            ("graph_entity_merges", &self.entity_merges_db),
+        ]
+    }
+
     /// Get graph statistics - O(1) using atomic counters
     pub fn get_stats(&self) -> Result<GraphStats> {
         Ok(GraphStats {
@@ -3389,18 +4376,61 @@ impl GraphMemory {
         })
     }
 
+    /// Compute centrality, communities and bridges over the whole graph
+    ///
+    /// Each entity's `centrality` and `community` are written back (only where
+    /// they changed), and the report is kept for `last_analytics`.
+    pub fn run_analytics(&self) -> Result<GraphAnalytics> {
+        let entities = self.get_all_entities()?;
+        let relationships = self.get_all_relationships()?;
+        let (report, scores) = crate::graph_analytics::analyze(&entities, &relationships);
+
+        let mut batch = WriteBatch::default();
+        let mut updated = 0;
+        for entity in &entities {
+            let Some(score) = scores.get(&entity.uuid) else {
+                continue;
+            };
+            if entity.centrality == score.centrality && entity.community == score.community {
+                continue;
+            }
+            // Re-read so a mention recorded during the analysis is not lost
+            let Some(mut current) = self.get_entity(&entity.uuid)? else {
+                continue;
+            };
+            current.centrality = score.centrality;
+            current.community = score.community;
+            let value = bincode::serde::encode_to_vec(&current, bincode::config::standard())?;
+            batch.put(current.uuid.as_bytes(), value);
+            updated += 1;
+        }
+        if updated > 0 {
+            self.entities_db.write(batch)?;
+        }
+
+        tracing::debug!(
+            "Graph analytics: {} entities ({} updated), {} communities, modularity {:.3}",
+            report.entity_count,
+            updated,
+            report.community_count,
+            report.modularity
+        );
+        *self.last_analytics.write() = Some(report.clone());
+        Ok(report)
+    }
+
+    /// Report from the last analytics run since this graph was opened
+    pub fn last_analytics(&self) -> Option<GraphAnalytics> {
+        self.last_analytics.read().clone()
+    }
+
     /// Get all entities in the graph
     pub fn get_all_entities(&self) -> Result<Vec<EntityNode>> {
         let mut entities = Vec::new();
 
         let iter = self.entities_db.iterator(rocksdb::IteratorMode::Start);
         for (_, value) in iter.flatten() {
-            if let Ok(entity) = bincode::serde::decode_from_slice::<EntityNode, _>(
-                &value,
-                bincode::config::standard(),
-            )
-            .map(|(v, _)| v)
-            {
+            if let Ok(entity) = decode_entity(&value) {
                 entities.push(entity);
             }
         }
@@ -3454,6 +4484,16 @@ impl GraphMemory {
             .map(|(i, e)| (e.uuid, i))
             .collect();
 
+        // Communities from the last analytics run each get an arm of the galaxy,
+        // so topic clusters sit together; unanalyzed entities are spread evenly
+        let community_count = entities
+            .iter()
+            .filter_map(|e| e.community)
+            .max()
+            .map_or(0, |c| c as usize + 1);
+        let arm_width = std::f32::consts::TAU / community_count.max(1) as f32;
+        let mut arm_positions: HashMap<u32, usize> = HashMap::new();
+
         // Calculate 3D positions using a force-directed layout approximation
         // High-salience entities are positioned more centrally
         let mut stars: Vec<UniverseStar> = entities
@@ -3462,8 +4502,17 @@ impl GraphMemory {
             .map(|(i, entity)| {
                 // Use a spiral galaxy layout with salience affecting radius
                 // Higher salience = closer to center
-                let angle = (i as f32) * 2.4; // Golden angle for even distribution
-                let base_radius = 1.0 - entity.salience; // High salience = small radius
+                let angle = match entity.community {
+                    Some(community) if community_count > 1 => {
+                        let slot = arm_positions.entry(community).or_insert(0);
+                        *slot += 1;
+                        let offset = ((*slot as f32) * 2.4) % arm_width;
+                        (community as f32) * arm_width + offset * 0.8
+                    }
+                    _ => (i as f32) * 2.4, // Golden angle for even distribution
+                };
+                let salience = entity.structural_salience();
+                let base_radius = 1.0 - salience; // High salience = small radius
                 let radius = base_radius * 100.0 + 10.0; // 10-110 range
 
                 let x = radius * angle.cos();
@@ -3477,9 +4526,11 @@ impl GraphMemory {
                     salience: entity.salience,
                     mention_count: entity.mention_count,
                     is_proper_noun: entity.is_proper_noun,
+                    centrality: entity.centrality,
+                    community: entity.community,
                     position: Position3D { x, y, z },
                     color: entity_type_color(entity.labels.first()),
-                    size: 5.0 + entity.salience * 20.0, // Size 5-25 based on salience
+                    size: 5.0 + salience * 20.0, // Size 5-25 based on salience
                 }
             })
             .collect();
@@ -3602,6 +4653,12 @@ pub struct UniverseStar {
     pub salience: f32,
     pub mention_count: usize,
     pub is_proper_noun: bool,
+    /// Graph centrality from the last analytics run (0.0 if never analyzed)
+    #[serde(default)]
+    pub centrality: f32,
+    /// Community from the last analytics run
+    #[serde(default)]
+    pub community: Option<u32>,
     pub position: Position3D,
     pub color: String,
     pub size: f32,
@@ -4514,6 +5571,13 @@ impl EntityExtractor {
         }
     }
 
+    /// Check a lowercase word against the stop words, including those of a
+    /// detected non-English language ("Der", "Los" at sentence start)
+    fn is_stop_word(&self, lower: &str, language: Language) -> bool {
+        self.stop_words.contains(lower)
+            || (language != Language::English && language.is_stopword(lower))
+    }
+
     /// Check if a word is likely a proper noun (not just capitalized at sentence start)
     fn is_likely_proper_noun(&self, word: &str, position: usize, prev_char: Option<char>) -> bool {
         // If it's not at position 0 and is capitalized, it's likely a proper noun
@@ -4541,6 +5605,7 @@ impl EntityExtractor {
         let mut entities = Vec::new();
         let mut seen = HashSet::new();
         let mut skip_until_index = 0; // For skipping sub-spans of multi-word entities
+        let language = Language::detect(text);
 
         // Split into words and detect capitalized sequences
         let words: Vec<&str> = text.split_whitespace().collect();
@@ -4560,7 +5625,7 @@ impl EntityExtractor {
             let lower = clean_word.to_lowercase();
 
             // Skip common stop words
-            if self.stop_words.contains(&lower) {
+            if self.is_stop_word(&lower, language) {
                 continue;
             }
 
@@ -4639,7 +5704,7 @@ impl EntityExtractor {
                 {
                     let next_word = words[j].trim_matches(|c: char| !c.is_alphanumeric());
                     // Skip stop words in multi-word sequences
-                    if !self.stop_words.contains(&next_word.to_lowercase()) {
+                    if !self.is_stop_word(&next_word.to_lowercase(), language) {
                         entity_name.push(' ');
                         entity_name.push_str(next_word);
                     }
@@ -4757,7 +5822,7 @@ impl EntityExtractor {
                 }
 
                 // Skip stop words
-                if self.stop_words.contains(&term_lower) {
+                if self.is_stop_word(&term_lower, language) {
                     continue;
                 }
 
@@ -4806,6 +5871,7 @@ impl EntityExtractor {
         use crate::memory::query_parser::extract_chunks;
 
         let chunk_extraction = extract_chunks(text);
+        let language = Language::detect(text);
         let mut pairs = Vec::new();
 
         // Get all co-occurrence pairs from chunks (same sentence)
@@ -4821,8 +5887,8 @@ impl EntityExtractor {
                     // Skip very short words and stop words
                     if w1.len() >= 3
                         && w2.len() >= 3
-                        && !self.stop_words.contains(&w1)
-                        && !self.stop_words.contains(&w2)
+                        && !self.is_stop_word(&w1, language)
+                        && !self.is_stop_word(&w2, language)
                     {
                         pairs.push((w1, w2));
                     }
@@ -5484,6 +6550,9 @@ mod tests {
             name_embedding: None,
             salience: 0.5,
             is_proper_noun: false,
+            aliases: Vec::new(),
+            centrality: 0.0,
+            community: None,
         };
         let entity2 = EntityNode {
             uuid: Uuid::new_v4(),
@@ -5497,6 +6566,9 @@ mod tests {
             name_embedding: None,
             salience: 0.5,
             is_proper_noun: false,
+            aliases: Vec::new(),
+            centrality: 0.0,
+            community: None,
         };
 
         let entity1_uuid = graph.add_entity(entity1.clone()).unwrap();
@@ -5546,6 +6618,9 @@ mod tests {
             name_embedding: None,
             salience: 0.5,
             is_proper_noun: false,
+            aliases: Vec::new(),
+            centrality: 0.0,
+            community: None,
         };
         let entity2 = EntityNode {
             uuid: entity2_uuid,
@@ -5559,6 +6634,9 @@ mod tests {
             name_embedding: None,
             salience: 0.5,
             is_proper_noun: false,
+            aliases: Vec::new(),
+            centrality: 0.0,
+            community: None,
         };
 
         graph.add_entity(entity1).unwrap();
@@ -5605,4 +6683,73 @@ mod tests {
         let s = strength.unwrap();
         assert!(s > 0.75 && s <= 0.8, "Strength should be ~0.8, got {}", s);
     }
+
+    #[test]
+    fn test_decode_legacy_entity() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let graph = GraphMemory::new(temp_dir.path()).unwrap();
+
+        let uuid = Uuid::new_v4();
+        let seen = Utc::now();
+        let legacy = (
+            uuid,
+            "Postgres".to_string(),
+            vec![EntityLabel::Technology],
+            seen,
+            seen,
+            3usize,
+            String::new(),
+            HashMap::<String, String>::new(),
+            Some(vec![0.25f32; 4]),
+            0.7f32,
+            true,
+        );
+        let value = bincode::serde::encode_to_vec(&legacy, bincode::config::standard()).unwrap();
+        graph.entities_db.put(uuid.as_bytes(), &value).unwrap();
+
+        let entity = graph.get_entity(&uuid).unwrap().unwrap();
+        assert_eq!(entity.name, "Postgres");
+        assert_eq!(entity.mention_count, 3);
+        assert_eq!(entity.name_embedding, Some(vec![0.25; 4]));
+        assert!(entity.is_proper_noun);
+        assert!(entity.aliases.is_empty());
+        assert!(entity.community.is_none());
+
+        let all = graph.get_all_entities().unwrap();
+        assert_eq!(all.len(), 1);
+        assert_eq!(all[0].uuid, uuid);
+    }
+
+    #[test]
+    fn test_decode_entity_before_analytics() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let graph = GraphMemory::new(temp_dir.path()).unwrap();
+
+        let uuid = Uuid::new_v4();
+        let seen = Utc::now();
+        let with_aliases = (
+            uuid,
+            "Postgres".to_string(),
+            vec![EntityLabel::Technology],
+            seen,
+            seen,
+            3usize,
+            String::new(),
+            HashMap::<String, String>::new(),
+            None::<Vec<f32>>,
+            0.7f32,
+            true,
+            vec!["PostgreSQL".to_string(), "pg".to_string()],
+        );
+        let value =
+            bincode::serde::encode_to_vec(&with_aliases, bincode::config::standard()).unwrap();
+        graph.entities_db.put(uuid.as_bytes(), &value).unwrap();
+
+        let entity = graph.get_entity(&uuid).unwrap().unwrap();
+        assert_eq!(entity.name, "Postgres");
+        assert_eq!(entity.aliases, vec!["PostgreSQL", "pg"]);
+        assert_eq!(entity.centrality, 0.0);
+        assert!(entity.community.is_none());
+        assert_eq!(graph.get_all_entities().unwrap().len(), 1);
+    }
 }
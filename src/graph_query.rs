@@ -0,0 +1,2222 @@
+//! Declarative graph queries over [`GraphMemory`]
+//!
+//! A small Cypher-like language, so agents can ask multi-hop questions in one
+//! request instead of chaining `find_entity` and `traverse` calls:
+//!
+//! ```text
+//! MATCH (p:Person)-[:WorksAt|EmployedBy]->(o:Organization {name: "Acme"})
+//! WHERE p.salience > 0.4 AND NOT p.name STARTS WITH "Bot"
+//! RETURN p.name, o.name AS employer
+//! ORDER BY p.name
+//! LIMIT 10
+//! ```
+//!
+//! - Nodes: `(var:Label|Label {key: value})`. Labels are alternatives. In a node
+//!   map, `name` matches the canonical name or an alias, ignoring case.
+//! - Relationships: `-[var:Type|Type*min..max {key: value}]->`, `<-[...]-` or
+//!   `-[...]-` (either direction), and the short forms `-->`, `<--`, `--`.
+//!   `*` alone means 1 to [`GRAPH_QUERY_MAX_HOPS`] hops. In a relationship map,
+//!   `min_strength` means "strength at least" and a list means "one of"; the
+//!   map applies to every hop of a variable-length relationship.
+//! - `WHERE` supports `AND`/`OR`/`NOT`, `= <> < <= > >=`, `CONTAINS`,
+//!   `STARTS WITH`, `ENDS WITH`, `IN [...]` and `IS [NOT] NULL`.
+//! - `RETURN [DISTINCT] expr [AS name], ...` or `RETURN *`, then optional
+//!   `ORDER BY expr [ASC|DESC], ...` and `LIMIT n`.
+//!
+//! Node properties: `uuid`, `name`, `labels`, `aliases`, `summary`, `salience`,
+//! `mention_count`, `is_proper_noun`, `centrality`, `community`, `created_at` and
+//! `last_seen_at`; any other key reads the entity's `attributes`. Relationship properties: `uuid`, `type`,
+//! `from`, `to`, `strength` (decay-adjusted), `raw_strength`, `tier` (`"L1"`,
+//! `"L2"` or `"L3"`), `activation_count`, `context`, `created_at`, `valid_at`,
+//! `last_activated` and `invalidated_at`. Invalidated edges are skipped unless
+//! the query constrains that relationship's `invalidated_at`.
+//!
+//! A query is parsed and compiled once. The most selective node (by UUID, then
+//! the name index, then label) anchors the match, the pattern expands outwards
+//! over the entity→edge index, and each `WHERE` conjunct is checked as soon as
+//! the variables it mentions are bound.
+
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::sync::Arc;
+use std::time::Instant;
+
+use anyhow::{anyhow, bail, Result};
+use chrono::{DateTime, NaiveDate, NaiveDateTime, Utc};
+use serde::Serialize;
+use uuid::Uuid;
+
+use crate::constants::{
+    GRAPH_QUERY_DEFAULT_LIMIT, GRAPH_QUERY_EDGES_PER_NODE, GRAPH_QUERY_MAX_EXPANSIONS,
+    GRAPH_QUERY_MAX_HOPS, GRAPH_QUERY_MAX_ROWS,
+};
+use crate::graph_memory::{EdgeTier, EntityNode, GraphMemory, RelationshipEdge};
+
+/// Relationship properties a query may read
+const EDGE_PROPERTIES: &[&str] = &[
+    "uuid",
+    "type",
+    "from",
+    "to",
+    "strength",
+    "raw_strength",
+    "tier",
+    "activation_count",
+    "context",
+    "created_at",
+    "valid_at",
+    "last_activated",
+    "invalidated_at",
+];
+
+// =============================================================================
+// LEXER
+// =============================================================================
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Ident(String),
+    Str(String),
+    Int(i64),
+    Float(f64),
+    LParen,
+    RParen,
+    LBracket,
+    RBracket,
+    LBrace,
+    RBrace,
+    Colon,
+    Comma,
+    Dot,
+    DotDot,
+    Pipe,
+    Star,
+    Dash,
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+/// Split a query into tokens, each with its byte offset for error messages
+fn tokenize(input: &str) -> Result<Vec<(Token, usize)>> {
+    let chars: Vec<(usize, char)> = input.char_indices().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        let (pos, c) = chars[i];
+        let next = chars.get(i + 1).map(|(_, c)| *c);
+
+        if c.is_whitespace() {
+            i += 1;
+            continue;
+        }
+
+        // Quoted strings and `backtick` identifiers
+        if c == '"' || c == '\'' || c == '`' {
+            let mut text = String::new();
+            let mut j = i + 1;
+            loop {
+                let Some(&(_, ch)) = chars.get(j) else {
+                    bail!("unterminated quote starting at position {pos}");
+                };
+                if ch == c {
+                    break;
+                }
+                if ch == '\\' && c != '`' {
+                    j += 1;
+                    let Some(&(_, escaped)) = chars.get(j) else {
+                        bail!("unterminated quote starting at position {pos}");
+                    };
+                    text.push(match escaped {
+                        'n' => '\n',
+                        't' => '\t',
+                        other => other,
+                    });
+                } else {
+                    text.push(ch);
+                }
+                j += 1;
+            }
+            tokens.push((
+                if c == '`' {
+                    Token::Ident(text)
+                } else {
+                    Token::Str(text)
+                },
+                pos,
+            ));
+            i = j + 1;
+            continue;
+        }
+
+        if c.is_ascii_digit() {
+            let mut j = i;
+            while chars.get(j).is_some_and(|(_, ch)| ch.is_ascii_digit()) {
+                j += 1;
+            }
+            // A '.' followed by a digit is a decimal point; `1..3` is a range
+            let is_float = chars.get(j).is_some_and(|(_, ch)| *ch == '.')
+                && chars.get(j + 1).is_some_and(|(_, ch)| ch.is_ascii_digit());
+            if is_float {
+                j += 1;
+                while chars.get(j).is_some_and(|(_, ch)| ch.is_ascii_digit()) {
+                    j += 1;
+                }
+            }
+            let end = chars.get(j).map_or(input.len(), |(p, _)| *p);
+            let text = &input[pos..end];
+            let token = if is_float {
+                Token::Float(
+                    text.parse()
+                        .map_err(|_| anyhow!("invalid number '{text}' at position {pos}"))?,
+                )
+            } else {
+                Token::Int(
+                    text.parse()
+                        .map_err(|_| anyhow!("invalid number '{text}' at position {pos}"))?,
+                )
+            };
+            tokens.push((token, pos));
+            i = j;
+            continue;
+        }
+
+        if c.is_alphabetic() || c == '_' {
+            let mut j = i;
+            while chars
+                .get(j)
+                .is_some_and(|(_, ch)| ch.is_alphanumeric() || *ch == '_')
+            {
+                j += 1;
+            }
+            let end = chars.get(j).map_or(input.len(), |(p, _)| *p);
+            tokens.push((Token::Ident(input[pos..end].to_string()), pos));
+            i = j;
+            continue;
+        }
+
+        let (token, width) = match (c, next) {
+            ('.', Some('.')) => (Token::DotDot, 2),
+            ('!', Some('=')) | ('<', Some('>')) => (Token::Ne, 2),
+            ('<', Some('=')) => (Token::Le, 2),
+            ('>', Some('=')) => (Token::Ge, 2),
+            ('(', _) => (Token::LParen, 1),
+            (')', _) => (Token::RParen, 1),
+            ('[', _) => (Token::LBracket, 1),
+            (']', _) => (Token::RBracket, 1),
+            ('{', _) => (Token::LBrace, 1),
+            ('}', _) => (Token::RBrace, 1),
+            (':', _) => (Token::Colon, 1),
+            (',', _) => (Token::Comma, 1),
+            ('.', _) => (Token::Dot, 1),
+            ('|', _) => (Token::Pipe, 1),
+            ('*', _) => (Token::Star, 1),
+            ('-', _) => (Token::Dash, 1),
+            ('=', _) => (Token::Eq, 1),
+            ('<', _) => (Token::Lt, 1),
+            ('>', _) => (Token::Gt, 1),
+            _ => bail!("unexpected character '{c}' at position {pos}"),
+        };
+        tokens.push((token, pos));
+        i += width;
+    }
+
+    Ok(tokens)
+}
+
+// =============================================================================
+// AST
+// =============================================================================
+
+/// A value produced while evaluating a query
+#[derive(Debug, Clone)]
+enum Value {
+    Null,
+    Bool(bool),
+    Int(i64),
+    Float(f64),
+    Str(String),
+    Time(DateTime<Utc>),
+    List(Vec<Value>),
+    Node(Arc<EntityNode>),
+    Edge(Arc<RelationshipEdge>),
+    Path(Vec<Arc<RelationshipEdge>>),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CmpOp {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    Contains,
+    StartsWith,
+    EndsWith,
+    In,
+}
+
+impl CmpOp {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Eq => "=",
+            Self::Ne => "<>",
+            Self::Lt => "<",
+            Self::Le => "<=",
+            Self::Gt => ">",
+            Self::Ge => ">=",
+            Self::Contains => "CONTAINS",
+            Self::StartsWith => "STARTS WITH",
+            Self::EndsWith => "ENDS WITH",
+            Self::In => "IN",
+        }
+    }
+}
+
+/// Slot of a variable not yet resolved by the compiler
+const UNRESOLVED: usize = usize::MAX;
+
+#[derive(Debug, Clone)]
+enum Expr {
+    Literal(Value),
+    Variable {
+        name: String,
+        slot: usize,
+    },
+    Property {
+        name: String,
+        slot: usize,
+        key: String,
+    },
+    List(Vec<Expr>),
+    Not(Box<Expr>),
+    And(Box<Expr>, Box<Expr>),
+    Or(Box<Expr>, Box<Expr>),
+    Compare(CmpOp, Box<Expr>, Box<Expr>),
+    IsNull(Box<Expr>, bool),
+    /// Node carries one of these labels (from a `(n:Label|Label)` pattern)
+    HasLabel {
+        name: String,
+        slot: usize,
+        labels: Vec<String>,
+    },
+    /// Node's name or an alias equals this, ignoring case (from `{name: ...}`)
+    Named {
+        name: String,
+        slot: usize,
+        value: String,
+    },
+}
+
+impl Expr {
+    fn variable(name: String) -> Self {
+        Self::Variable {
+            name,
+            slot: UNRESOLVED,
+        }
+    }
+
+    fn property(name: &str, key: &str) -> Self {
+        Self::Property {
+            name: name.to_string(),
+            slot: UNRESOLVED,
+            key: key.to_string(),
+        }
+    }
+
+    fn compare(op: CmpOp, left: Expr, right: Expr) -> Self {
+        Self::Compare(op, Box::new(left), Box::new(right))
+    }
+
+    /// Visit every variable reference (name, slot and property key, if any)
+    fn visit_vars<'a>(&'a self, f: &mut dyn FnMut(&'a str, usize, Option<&'a str>)) {
+        match self {
+            Self::Literal(_) => {}
+            Self::Variable { name, slot } => f(name, *slot, None),
+            Self::Property { name, slot, key } => f(name, *slot, Some(key)),
+            Self::HasLabel { name, slot, .. } | Self::Named { name, slot, .. } => {
+                f(name, *slot, None)
+            }
+            Self::List(items) => items.iter().for_each(|item| item.visit_vars(f)),
+            Self::Not(inner) | Self::IsNull(inner, _) => inner.visit_vars(f),
+            Self::And(a, b) | Self::Or(a, b) | Self::Compare(_, a, b) => {
+                a.visit_vars(f);
+                b.visit_vars(f);
+            }
+        }
+    }
+
+    /// Resolve variable names to binding slots
+    fn resolve(&mut self, slots: &HashMap<String, usize>) -> Result<()> {
+        match self {
+            Self::Literal(_) => Ok(()),
+            Self::Variable { name, slot }
+            | Self::Property { name, slot, .. }
+            | Self::HasLabel { name, slot, .. }
+            | Self::Named { name, slot, .. } => {
+                *slot = *slots
+                    .get(name.as_str())
+                    .ok_or_else(|| anyhow!("variable '{name}' is not defined in MATCH"))?;
+                Ok(())
+            }
+            Self::List(items) => items.iter_mut().try_for_each(|item| item.resolve(slots)),
+            Self::Not(inner) | Self::IsNull(inner, _) => inner.resolve(slots),
+            Self::And(a, b) | Self::Or(a, b) | Self::Compare(_, a, b) => {
+                a.resolve(slots)?;
+                b.resolve(slots)
+            }
+        }
+    }
+
+    /// Split a conjunction into its parts
+    fn into_conjuncts(self, out: &mut Vec<Expr>) {
+        match self {
+            Self::And(a, b) => {
+                a.into_conjuncts(out);
+                b.into_conjuncts(out);
+            }
+            other => out.push(other),
+        }
+    }
+}
+
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Null => write!(f, "null"),
+            Self::Bool(b) => write!(f, "{b}"),
+            Self::Int(n) => write!(f, "{n}"),
+            Self::Float(x) => write!(f, "{x}"),
+            Self::Str(s) => write!(f, "{s:?}"),
+            Self::Time(t) => write!(f, "{:?}", t.to_rfc3339()),
+            Self::List(items) => {
+                write!(f, "[")?;
+                for (i, item) in items.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{item}")?;
+                }
+                write!(f, "]")
+            }
+            Self::Node(node) => write!(f, "({})", node.name),
+            Self::Edge(edge) => write!(f, "[{}]", edge.uuid),
+            Self::Path(edges) => write!(f, "<path of {}>", edges.len()),
+        }
+    }
+}
+
+impl fmt::Display for Expr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Literal(value) => write!(f, "{value}"),
+            Self::Variable { name, .. } => write!(f, "{name}"),
+            Self::Property { name, key, .. } => write!(f, "{name}.{key}"),
+            Self::List(items) => {
+                write!(f, "[")?;
+                for (i, item) in items.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{item}")?;
+                }
+                write!(f, "]")
+            }
+            Self::Not(inner) => write!(f, "NOT {inner}"),
+            Self::And(a, b) => write!(f, "({a} AND {b})"),
+            Self::Or(a, b) => write!(f, "({a} OR {b})"),
+            Self::Compare(op, a, b) => write!(f, "{a} {} {b}", op.as_str()),
+            Self::IsNull(inner, false) => write!(f, "{inner} IS NULL"),
+            Self::IsNull(inner, true) => write!(f, "{inner} IS NOT NULL"),
+            Self::HasLabel { name, labels, .. } => write!(f, "{name}:{}", labels.join("|")),
+            Self::Named { name, value, .. } => write!(f, "{name} named {value:?}"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Direction {
+    Outgoing,
+    Incoming,
+    Either,
+}
+
+impl Direction {
+    fn reversed(self) -> Self {
+        match self {
+            Self::Outgoing => Self::Incoming,
+            Self::Incoming => Self::Outgoing,
+            Self::Either => Self::Either,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+struct NodePattern {
+    var: Option<String>,
+    labels: Vec<String>,
+    props: Vec<(String, Value)>,
+}
+
+#[derive(Debug, Clone)]
+struct RelPattern {
+    var: Option<String>,
+    types: Vec<String>,
+    direction: Direction,
+    /// Hop range for variable-length relationships (`*min..max`)
+    hops: Option<(usize, usize)>,
+    props: Vec<(String, Value)>,
+}
+
+#[derive(Debug, Clone)]
+struct ReturnItem {
+    expr: Expr,
+    column: String,
+}
+
+#[derive(Debug, Clone)]
+struct OrderItem {
+    expr: Expr,
+    descending: bool,
+}
+
+#[derive(Debug, Clone)]
+struct ParsedQuery {
+    nodes: Vec<NodePattern>,
+    rels: Vec<RelPattern>,
+    filter: Option<Expr>,
+    distinct: bool,
+    /// Empty for `RETURN *`
+    returns: Vec<ReturnItem>,
+    order_by: Vec<OrderItem>,
+    limit: Option<usize>,
+}
+
+// =============================================================================
+// PARSER
+// =============================================================================
+
+struct Parser {
+    tokens: Vec<(Token, usize)>,
+    pos: usize,
+    end: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos).map(|(token, _)| token)
+    }
+
+    fn offset(&self) -> usize {
+        self.tokens.get(self.pos).map_or(self.end, |(_, pos)| *pos)
+    }
+
+    fn error(&self, message: &str) -> anyhow::Error {
+        match self.peek() {
+            Some(token) => anyhow!(
+                "{message} at position {}, found {}",
+                self.offset(),
+                describe_token(token)
+            ),
+            None => anyhow!("{message} at end of query"),
+        }
+    }
+
+    fn eat(&mut self, token: &Token) -> bool {
+        if self.peek() == Some(token) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, token: &Token) -> Result<()> {
+        if self.eat(token) {
+            Ok(())
+        } else {
+            Err(self.error(&format!("expected {}", describe_token(token))))
+        }
+    }
+
+    fn is_keyword(&self, keyword: &str) -> bool {
+        matches!(self.peek(), Some(Token::Ident(word)) if word.eq_ignore_ascii_case(keyword))
+    }
+
+    fn eat_keyword(&mut self, keyword: &str) -> bool {
+        if self.is_keyword(keyword) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect_keyword(&mut self, keyword: &str) -> Result<()> {
+        if self.eat_keyword(keyword) {
+            Ok(())
+        } else {
+            Err(self.error(&format!("expected {keyword}")))
+        }
+    }
+
+    fn ident(&mut self, what: &str) -> Result<String> {
+        match self.peek() {
+            Some(Token::Ident(name)) => {
+                let name = name.clone();
+                self.pos += 1;
+                Ok(name)
+            }
+            _ => Err(self.error(&format!("expected {what}"))),
+        }
+    }
+
+    fn integer(&mut self, what: &str) -> Result<usize> {
+        match self.peek() {
+            Some(Token::Int(n)) if *n >= 0 => {
+                let n = *n as usize;
+                self.pos += 1;
+                Ok(n)
+            }
+            _ => Err(self.error(&format!("expected {what}"))),
+        }
+    }
+
+    fn parse_query(&mut self) -> Result<ParsedQuery> {
+        self.expect_keyword("MATCH")?;
+        let mut nodes = vec![self.parse_node()?];
+        let mut rels = Vec::new();
+        while matches!(self.peek(), Some(Token::Dash) | Some(Token::Lt)) {
+            rels.push(self.parse_rel()?);
+            nodes.push(self.parse_node()?);
+        }
+
+        let filter = if self.eat_keyword("WHERE") {
+            Some(self.parse_or()?)
+        } else {
+            None
+        };
+
+        self.expect_keyword("RETURN")?;
+        let distinct = self.eat_keyword("DISTINCT");
+        let mut returns = Vec::new();
+        if !self.eat(&Token::Star) {
+            loop {
+                let expr = self.parse_or()?;
+                let column = if self.eat_keyword("AS") {
+                    self.ident("column name after AS")?
+                } else {
+                    expr.to_string()
+                };
+                returns.push(ReturnItem { expr, column });
+                if !self.eat(&Token::Comma) {
+                    break;
+                }
+            }
+        }
+
+        let mut order_by = Vec::new();
+        if self.eat_keyword("ORDER") {
+            self.expect_keyword("BY")?;
+            loop {
+                let expr = self.parse_or()?;
+                let descending = if self.eat_keyword("DESC") {
+                    true
+                } else {
+                    self.eat_keyword("ASC");
+                    false
+                };
+                order_by.push(OrderItem { expr, descending });
+                if !self.eat(&Token::Comma) {
+                    break;
+                }
+            }
+        }
+
+        let limit = if self.eat_keyword("LIMIT") {
+            Some(self.integer("a number after LIMIT")?)
+        } else {
+            None
+        };
+
+        if self.peek().is_some() {
+            return Err(self.error("unexpected input"));
+        }
+
+        Ok(ParsedQuery {
+            nodes,
+            rels,
+            filter,
+            distinct,
+            returns,
+            order_by,
+            limit,
+        })
+    }
+
+    fn parse_node(&mut self) -> Result<NodePattern> {
+        self.expect(&Token::LParen)?;
+        let mut node = NodePattern::default();
+        if let Some(Token::Ident(_)) = self.peek() {
+            node.var = Some(self.ident("variable")?);
+        }
+        if self.eat(&Token::Colon) {
+            node.labels.push(self.ident("label")?);
+            while self.eat(&Token::Pipe) {
+                node.labels.push(self.ident("label")?);
+            }
+            if self.peek() == Some(&Token::Colon) {
+                return Err(self.error("labels are alternatives; write :A|B"));
+            }
+        }
+        if self.peek() == Some(&Token::LBrace) {
+            node.props = self.parse_props()?;
+        }
+        self.expect(&Token::RParen)?;
+        Ok(node)
+    }
+
+    fn parse_rel(&mut self) -> Result<RelPattern> {
+        let incoming = self.eat(&Token::Lt);
+        self.expect(&Token::Dash)?;
+
+        let mut rel = RelPattern {
+            var: None,
+            types: Vec::new(),
+            direction: Direction::Either,
+            hops: None,
+            props: Vec::new(),
+        };
+        if self.eat(&Token::LBracket) {
+            if let Some(Token::Ident(_)) = self.peek() {
+                rel.var = Some(self.ident("variable")?);
+            }
+            if self.eat(&Token::Colon) {
+                rel.types.push(self.ident("relationship type")?);
+                while self.eat(&Token::Pipe) {
+                    self.eat(&Token::Colon);
+                    rel.types.push(self.ident("relationship type")?);
+                }
+            }
+            if self.eat(&Token::Star) {
+                rel.hops = Some(self.parse_hops()?);
+            }
+            if self.peek() == Some(&Token::LBrace) {
+                rel.props = self.parse_props()?;
+            }
+            self.expect(&Token::RBracket)?;
+        }
+        self.expect(&Token::Dash)?;
+        let outgoing = self.eat(&Token::Gt);
+
+        rel.direction = match (incoming, outgoing) {
+            (false, true) => Direction::Outgoing,
+            (true, false) => Direction::Incoming,
+            (false, false) => Direction::Either,
+            (true, true) => return Err(self.error("a relationship cannot point both ways")),
+        };
+        Ok(rel)
+    }
+
+    /// `*`, `*n`, `*n..m`, `*..m` or `*n..`
+    fn parse_hops(&mut self) -> Result<(usize, usize)> {
+        let min = match self.peek() {
+            Some(Token::Int(_)) => Some(self.integer("hop count")?),
+            _ => None,
+        };
+        if self.eat(&Token::DotDot) {
+            let max = match self.peek() {
+                Some(Token::Int(_)) => self.integer("hop count")?,
+                _ => GRAPH_QUERY_MAX_HOPS,
+            };
+            Ok((min.unwrap_or(1), max))
+        } else {
+            Ok(min.map_or((1, GRAPH_QUERY_MAX_HOPS), |n| (n, n)))
+        }
+    }
+
+    fn parse_props(&mut self) -> Result<Vec<(String, Value)>> {
+        self.expect(&Token::LBrace)?;
+        let mut props = Vec::new();
+        if !self.eat(&Token::RBrace) {
+            loop {
+                let key = self.ident("property name")?;
+                self.expect(&Token::Colon)?;
+                props.push((key, self.parse_literal()?));
+                if !self.eat(&Token::Comma) {
+                    break;
+                }
+            }
+            self.expect(&Token::RBrace)?;
+        }
+        Ok(props)
+    }
+
+    fn parse_literal(&mut self) -> Result<Value> {
+        match self.parse_operand()? {
+            Expr::Literal(value) => Ok(value),
+            Expr::List(items) => items
+                .into_iter()
+                .map(|item| match item {
+                    Expr::Literal(value) => Ok(value),
+                    other => Err(anyhow!("expected a literal value, found {other}")),
+                })
+                .collect::<Result<Vec<_>>>()
+                .map(Value::List),
+            other => Err(anyhow!("expected a literal value, found {other}")),
+        }
+    }
+
+    fn parse_or(&mut self) -> Result<Expr> {
+        let mut left = self.parse_and()?;
+        while self.eat_keyword("OR") {
+            left = Expr::Or(Box::new(left), Box::new(self.parse_and()?));
+        }
+        Ok(left)
+    }
+
+    fn parse_and(&mut self) -> Result<Expr> {
+        let mut left = self.parse_not()?;
+        while self.eat_keyword("AND") {
+            left = Expr::And(Box::new(left), Box::new(self.parse_not()?));
+        }
+        Ok(left)
+    }
+
+    fn parse_not(&mut self) -> Result<Expr> {
+        if self.eat_keyword("NOT") {
+            Ok(Expr::Not(Box::new(self.parse_not()?)))
+        } else {
+            self.parse_comparison()
+        }
+    }
+
+    fn parse_comparison(&mut self) -> Result<Expr> {
+        let left = self.parse_operand()?;
+        let op = match self.peek() {
+            Some(Token::Eq) => CmpOp::Eq,
+            Some(Token::Ne) => CmpOp::Ne,
+            Some(Token::Lt) => CmpOp::Lt,
+            Some(Token::Le) => CmpOp::Le,
+            Some(Token::Gt) => CmpOp::Gt,
+            Some(Token::Ge) => CmpOp::Ge,
+            _ if self.is_keyword("CONTAINS") => CmpOp::Contains,
+            _ if self.is_keyword("IN") => CmpOp::In,
+            _ if self.is_keyword("STARTS") || self.is_keyword("ENDS") => {
+                let starts = self.is_keyword("STARTS");
+                self.pos += 1;
+                self.expect_keyword("WITH")?;
+                let right = self.parse_operand()?;
+                let op = if starts {
+                    CmpOp::StartsWith
+                } else {
+                    CmpOp::EndsWith
+                };
+                return Ok(Expr::compare(op, left, right));
+            }
+            _ if self.is_keyword("IS") => {
+                self.pos += 1;
+                let negated = self.eat_keyword("NOT");
+                self.expect_keyword("NULL")?;
+                return Ok(Expr::IsNull(Box::new(left), negated));
+            }
+            _ => return Ok(left),
+        };
+        self.pos += 1;
+        let right = self.parse_operand()?;
+        Ok(Expr::compare(op, left, right))
+    }
+
+    fn parse_operand(&mut self) -> Result<Expr> {
+        let Some(token) = self.peek().cloned() else {
+            return Err(self.error("expected a value"));
+        };
+        match token {
+            Token::LParen => {
+                self.pos += 1;
+                let expr = self.parse_or()?;
+                self.expect(&Token::RParen)?;
+                Ok(expr)
+            }
+            Token::LBracket => {
+                self.pos += 1;
+                let mut items = Vec::new();
+                if !self.eat(&Token::RBracket) {
+                    loop {
+                        items.push(self.parse_operand()?);
+                        if !self.eat(&Token::Comma) {
+                            break;
+                        }
+                    }
+                    self.expect(&Token::RBracket)?;
+                }
+                Ok(Expr::List(items))
+            }
+            Token::Dash => {
+                self.pos += 1;
+                match self.peek() {
+                    Some(Token::Int(n)) => {
+                        let n = -*n;
+                        self.pos += 1;
+                        Ok(Expr::Literal(Value::Int(n)))
+                    }
+                    Some(Token::Float(x)) => {
+                        let x = -*x;
+                        self.pos += 1;
+                        Ok(Expr::Literal(Value::Float(x)))
+                    }
+                    _ => Err(self.error("expected a number after '-'")),
+                }
+            }
+            Token::Str(s) => {
+                self.pos += 1;
+                Ok(Expr::Literal(Value::Str(s)))
+            }
+            Token::Int(n) => {
+                self.pos += 1;
+                Ok(Expr::Literal(Value::Int(n)))
+            }
+            Token::Float(x) => {
+                self.pos += 1;
+                Ok(Expr::Literal(Value::Float(x)))
+            }
+            Token::Ident(word) => {
+                self.pos += 1;
+                if word.eq_ignore_ascii_case("true") {
+                    return Ok(Expr::Literal(Value::Bool(true)));
+                }
+                if word.eq_ignore_ascii_case("false") {
+                    return Ok(Expr::Literal(Value::Bool(false)));
+                }
+                if word.eq_ignore_ascii_case("null") {
+                    return Ok(Expr::Literal(Value::Null));
+                }
+                if !self.eat(&Token::Dot) {
+                    return Ok(Expr::variable(word));
+                }
+                let mut key = self.ident("property name")?;
+                while self.eat(&Token::Dot) {
+                    key.push('.');
+                    key.push_str(&self.ident("property name")?);
+                }
+                // `n.attributes.role` and `n.role` both read the attribute
+                let key = key
+                    .strip_prefix("attributes.")
+                    .map(str::to_string)
+                    .unwrap_or(key);
+                Ok(Expr::property(&word, &key))
+            }
+            _ => Err(self.error("expected a value")),
+        }
+    }
+}
+
+fn describe_token(token: &Token) -> String {
+    match token {
+        Token::Ident(name) => format!("'{name}'"),
+        Token::Str(s) => format!("{s:?}"),
+        Token::Int(n) => n.to_string(),
+        Token::Float(x) => x.to_string(),
+        Token::LParen => "'('".into(),
+        Token::RParen => "')'".into(),
+        Token::LBracket => "'['".into(),
+        Token::RBracket => "']'".into(),
+        Token::LBrace => "'{'".into(),
+        Token::RBrace => "'}'".into(),
+        Token::Colon => "':'".into(),
+        Token::Comma => "','".into(),
+        Token::Dot => "'.'".into(),
+        Token::DotDot => "'..'".into(),
+        Token::Pipe => "'|'".into(),
+        Token::Star => "'*'".into(),
+        Token::Dash => "'-'".into(),
+        Token::Eq => "'='".into(),
+        Token::Ne => "'<>'".into(),
+        Token::Lt => "'<'".into(),
+        Token::Le => "'<='".into(),
+        Token::Gt => "'>'".into(),
+        Token::Ge => "'>='".into(),
+    }
+}
+
+// =============================================================================
+// PLAN
+// =============================================================================
+
+/// How the anchor node's candidates are found
+#[derive(Debug, Clone)]
+enum Access {
+    ByUuid(Uuid),
+    ByName(String),
+    LabelScan(Vec<String>),
+    FullScan,
+}
+
+/// One relationship expansion, from a bound node to the next
+#[derive(Debug, Clone)]
+struct Step {
+    rel: usize,
+    from: usize,
+    to: usize,
+    /// Walking the pattern left to right (else right to left)
+    forward: bool,
+}
+
+#[derive(Debug, Clone)]
+enum OrderKey {
+    Column(usize),
+    Expr(Expr),
+}
+
+/// A parsed and compiled graph query, ready to run against any user's graph
+#[derive(Debug, Clone)]
+pub struct GraphQuery {
+    rels: Vec<RelPattern>,
+    /// Variable name of each pattern element (anonymous ones get `_nX` / `_rX`)
+    node_vars: Vec<String>,
+    rel_vars: Vec<String>,
+    node_slots: Vec<usize>,
+    rel_slots: Vec<usize>,
+    slot_count: usize,
+    anchor: usize,
+    access: Access,
+    steps: Vec<Step>,
+    /// Conditions on a single edge, checked on every hop of each relationship
+    hop_filters: Vec<Vec<Expr>>,
+    /// Whether each relationship may traverse invalidated edges
+    include_invalidated: Vec<bool>,
+    /// Conditions checked once their variables are bound: level 0 is the
+    /// anchor, level k+1 follows step k
+    checks: Vec<Vec<Expr>>,
+    distinct: bool,
+    returns: Vec<ReturnItem>,
+    order_by: Vec<(OrderKey, bool)>,
+    limit: usize,
+}
+
+impl GraphQuery {
+    /// Parse and compile a query
+    pub fn parse(query: &str) -> Result<Self> {
+        let tokens = tokenize(query)?;
+        let mut parser = Parser {
+            tokens,
+            pos: 0,
+            end: query.len(),
+        };
+        Self::compile(parser.parse_query()?)
+    }
+
+    fn compile(parsed: ParsedQuery) -> Result<Self> {
+        let ParsedQuery {
+            nodes,
+            rels,
+            filter,
+            distinct,
+            returns,
+            order_by,
+            limit,
+        } = parsed;
+
+        // Variables and binding slots
+        let mut slots: HashMap<String, usize> = HashMap::new();
+        let mut rel_names: HashSet<String> = HashSet::new();
+        let mut named: Vec<String> = Vec::new();
+        let mut node_vars = Vec::new();
+        let mut node_slots = Vec::new();
+        let mut rel_vars = Vec::new();
+        let mut rel_slots = Vec::new();
+
+        for i in 0..nodes.len() {
+            if i > 0 {
+                let rel = &rels[i - 1];
+                let name = rel.var.clone().unwrap_or_else(|| format!("_r{}", i - 1));
+                if slots.contains_key(&name) {
+                    bail!(
+                        "variable '{name}' is bound twice; relationship variables must be unique"
+                    );
+                }
+                if rel.var.is_some() {
+                    named.push(name.clone());
+                }
+                rel_names.insert(name.clone());
+                let slot = slots.len();
+                slots.insert(name.clone(), slot);
+                rel_vars.push(name);
+                rel_slots.push(slot);
+
+                if let Some((min, max)) = rel.hops {
+                    if min > max {
+                        bail!("invalid hop range *{min}..{max}");
+                    }
+                    if max > GRAPH_QUERY_MAX_HOPS {
+                        bail!("variable-length relationships are limited to {GRAPH_QUERY_MAX_HOPS} hops");
+                    }
+                }
+            }
+
+            let node = &nodes[i];
+            let name = node.var.clone().unwrap_or_else(|| format!("_n{i}"));
+            if rel_names.contains(&name) {
+                bail!("variable '{name}' is used for both a node and a relationship");
+            }
+            let slot = match slots.get(&name) {
+                Some(slot) => *slot,
+                None => {
+                    if node.var.is_some() {
+                        named.push(name.clone());
+                    }
+                    let slot = slots.len();
+                    slots.insert(name.clone(), slot);
+                    slot
+                }
+            };
+            node_vars.push(name);
+            node_slots.push(slot);
+        }
+        // A relationship bound to a node variable later in the pattern
+        for name in &rel_vars {
+            if node_vars.contains(name) {
+                bail!("variable '{name}' is used for both a node and a relationship");
+            }
+        }
+        let var_length: HashSet<usize> = rels
+            .iter()
+            .enumerate()
+            .filter(|(_, rel)| rel.hops.is_some())
+            .map(|(i, _)| rel_slots[i])
+            .collect();
+        let rel_slot_set: HashSet<usize> = rel_slots.iter().copied().collect();
+
+        let check_expr = |expr: &mut Expr| -> Result<()> {
+            expr.resolve(&slots)?;
+            let mut error = None;
+            expr.visit_vars(&mut |name, slot, key| {
+                let Some(key) = key else { return };
+                if var_length.contains(&slot) {
+                    error.get_or_insert_with(|| {
+                        anyhow!("'{name}' is a variable-length relationship; it has no property '{key}'")
+                    });
+                } else if rel_slot_set.contains(&slot) && !EDGE_PROPERTIES.contains(&key) {
+                    error.get_or_insert_with(|| {
+                        anyhow!(
+                            "unknown relationship property '{key}' (expected one of: {})",
+                            EDGE_PROPERTIES.join(", ")
+                        )
+                    });
+                }
+            });
+            error.map_or(Ok(()), Err)
+        };
+
+        // Pattern maps and WHERE conjuncts become filters
+        let mut conjuncts = Vec::new();
+        for (i, node) in nodes.iter().enumerate() {
+            let name = &node_vars[i];
+            if !node.labels.is_empty() {
+                conjuncts.push(Expr::HasLabel {
+                    name: name.clone(),
+                    slot: UNRESOLVED,
+                    labels: node.labels.clone(),
+                });
+            }
+            for (key, value) in &node.props {
+                conjuncts.push(match (key.as_str(), value) {
+                    ("name", Value::Str(s)) => Expr::Named {
+                        name: name.clone(),
+                        slot: UNRESOLVED,
+                        value: s.clone(),
+                    },
+                    _ => map_condition(name, key, value),
+                });
+            }
+        }
+        if let Some(filter) = filter {
+            filter.into_conjuncts(&mut conjuncts);
+        }
+
+        let mut hop_filters: Vec<Vec<Expr>> = vec![Vec::new(); rels.len()];
+        for (i, rel) in rels.iter().enumerate() {
+            for (key, value) in &rel.props {
+                let mut expr = if key == "min_strength" {
+                    Expr::compare(
+                        CmpOp::Ge,
+                        Expr::property(&rel_vars[i], "strength"),
+                        Expr::Literal(value.clone()),
+                    )
+                } else {
+                    map_condition(&rel_vars[i], key, value)
+                };
+                // Map conditions read the single hop edge, even on `*` relationships
+                expr.resolve(&slots)?;
+                let mut unknown = None;
+                expr.visit_vars(&mut |_, _, key| {
+                    if let Some(key) = key.filter(|key| !EDGE_PROPERTIES.contains(key)) {
+                        unknown.get_or_insert_with(|| key.to_string());
+                    }
+                });
+                if let Some(key) = unknown {
+                    bail!(
+                        "unknown relationship property '{key}' (expected one of: {}, min_strength)",
+                        EDGE_PROPERTIES.join(", ")
+                    );
+                }
+                hop_filters[i].push(expr);
+            }
+        }
+
+        // Binding level of each slot, for scheduling checks
+        let anchor = choose_anchor(&nodes, &node_vars, &conjuncts);
+        let mut steps = Vec::new();
+        for rel in anchor..rels.len() {
+            steps.push(Step {
+                rel,
+                from: rel,
+                to: rel + 1,
+                forward: true,
+            });
+        }
+        for rel in (0..anchor).rev() {
+            steps.push(Step {
+                rel,
+                from: rel + 1,
+                to: rel,
+                forward: false,
+            });
+        }
+        let mut level_of = vec![usize::MAX; slots.len()];
+        level_of[node_slots[anchor]] = 0;
+        for (k, step) in steps.iter().enumerate() {
+            let rel_slot = rel_slots[step.rel];
+            level_of[rel_slot] = level_of[rel_slot].min(k + 1);
+            let to_slot = node_slots[step.to];
+            level_of[to_slot] = level_of[to_slot].min(k + 1);
+        }
+
+        let mut checks: Vec<Vec<Expr>> = vec![Vec::new(); steps.len() + 1];
+        for mut expr in conjuncts {
+            check_expr(&mut expr)?;
+            let mut vars: HashSet<usize> = HashSet::new();
+            expr.visit_vars(&mut |_, slot, _| {
+                vars.insert(slot);
+            });
+            // A condition on a single-hop relationship alone is checked per edge
+            if vars.len() == 1 {
+                let slot = *vars.iter().next().unwrap_or(&UNRESOLVED);
+                if let Some(rel) = rel_slots.iter().position(|s| *s == slot) {
+                    if rels[rel].hops.is_none() {
+                        hop_filters[rel].push(expr);
+                        continue;
+                    }
+                }
+            }
+            let level = vars.iter().map(|slot| level_of[*slot]).max().unwrap_or(0);
+            checks[level].push(expr);
+        }
+
+        let include_invalidated = (0..rels.len())
+            .map(|i| {
+                let slot = rel_slots[i];
+                let mut mentioned = false;
+                let mut visit = |_: &str, s: usize, key: Option<&str>| {
+                    mentioned |= s == slot && key == Some("invalidated_at");
+                };
+                hop_filters[i].iter().for_each(|e| e.visit_vars(&mut visit));
+                checks
+                    .iter()
+                    .flatten()
+                    .for_each(|e| e.visit_vars(&mut visit));
+                mentioned
+            })
+            .collect();
+
+        let access = anchor_access(&nodes[anchor], &node_vars[anchor], &checks[0])?;
+
+        // Projection
+        let mut returns = if returns.is_empty() {
+            if named.is_empty() {
+                bail!("RETURN * needs at least one named variable in MATCH");
+            }
+            named
+                .iter()
+                .map(|name| ReturnItem {
+                    expr: Expr::variable(name.clone()),
+                    column: name.clone(),
+                })
+                .collect()
+        } else {
+            returns
+        };
+        let mut columns = HashSet::new();
+        for item in &mut returns {
+            check_expr(&mut item.expr)?;
+            if !columns.insert(item.column.clone()) {
+                bail!("duplicate column '{}'; name it with AS", item.column);
+            }
+        }
+
+        let mut order = Vec::new();
+        for item in order_by {
+            // ORDER BY may name a RETURN column (`RETURN p.name AS who ORDER BY who`)
+            let column = match &item.expr {
+                Expr::Variable { name, .. } if !slots.contains_key(name) => {
+                    returns.iter().position(|r| &r.column == name)
+                }
+                other => returns.iter().position(|r| r.column == other.to_string()),
+            };
+            let key = match column {
+                Some(index) => OrderKey::Column(index),
+                None => {
+                    let mut expr = item.expr;
+                    check_expr(&mut expr)?;
+                    OrderKey::Expr(expr)
+                }
+            };
+            order.push((key, item.descending));
+        }
+
+        let limit = limit.unwrap_or(GRAPH_QUERY_DEFAULT_LIMIT);
+        if limit > GRAPH_QUERY_MAX_ROWS {
+            bail!("LIMIT may be at most {GRAPH_QUERY_MAX_ROWS}");
+        }
+
+        Ok(Self {
+            rels,
+            node_vars,
+            rel_vars,
+            node_slots,
+            rel_slots,
+            slot_count: slots.len(),
+            anchor,
+            access,
+            steps,
+            hop_filters,
+            include_invalidated,
+            checks,
+            distinct,
+            returns,
+            order_by: order,
+            limit,
+        })
+    }
+
+    /// Names of the result columns, in order
+    pub fn columns(&self) -> Vec<String> {
+        self.returns.iter().map(|r| r.column.clone()).collect()
+    }
+
+    /// Human-readable description of the plan, one line per operation
+    pub fn explain(&self) -> Vec<String> {
+        let anchor = &self.node_vars[self.anchor];
+        let mut lines = vec![match &self.access {
+            Access::ByUuid(uuid) => format!("anchor ({anchor}) by uuid {uuid}"),
+            Access::ByName(name) => format!("anchor ({anchor}) by name index {name:?}"),
+            Access::LabelScan(labels) => {
+                format!("anchor ({anchor}) by entity scan for :{}", labels.join("|"))
+            }
+            Access::FullScan => format!("anchor ({anchor}) by full entity scan"),
+        }];
+        for check in &self.checks[0] {
+            lines.push(format!("  filter {check}"));
+        }
+
+        for (k, step) in self.steps.iter().enumerate() {
+            let rel = &self.rels[step.rel];
+            let direction = if step.forward {
+                rel.direction
+            } else {
+                rel.direction.reversed()
+            };
+            let types = if rel.types.is_empty() {
+                String::new()
+            } else {
+                format!(":{}", rel.types.join("|"))
+            };
+            let hops = match rel.hops {
+                Some((min, max)) => format!("*{min}..{max}"),
+                None => String::new(),
+            };
+            let body = format!("[{}{types}{hops}]", self.rel_vars[step.rel]);
+            let (from, to) = (&self.node_vars[step.from], &self.node_vars[step.to]);
+            lines.push(match direction {
+                Direction::Outgoing => format!("expand ({from})-{body}->({to})"),
+                Direction::Incoming => format!("expand ({from})<-{body}-({to})"),
+                Direction::Either => format!("expand ({from})-{body}-({to})"),
+            });
+            for filter in &self.hop_filters[step.rel] {
+                lines.push(format!("  edge filter {filter}"));
+            }
+            if self.include_invalidated[step.rel] {
+                lines.push("  including invalidated edges".to_string());
+            }
+            for check in &self.checks[k + 1] {
+                lines.push(format!("  filter {check}"));
+            }
+        }
+
+        let columns = self.columns().join(", ");
+        lines.push(if self.distinct {
+            format!("return distinct {columns}")
+        } else {
+            format!("return {columns}")
+        });
+        if !self.order_by.is_empty() {
+            let keys: Vec<String> = self
+                .order_by
+                .iter()
+                .map(|(key, descending)| {
+                    let key = match key {
+                        OrderKey::Column(index) => self.returns[*index].column.clone(),
+                        OrderKey::Expr(expr) => expr.to_string(),
+                    };
+                    if *descending {
+                        format!("{key} DESC")
+                    } else {
+                        key
+                    }
+                })
+                .collect();
+            lines.push(format!("order by {}", keys.join(", ")));
+        }
+        lines.push(format!("limit {}", self.limit));
+        lines
+    }
+
+    /// Run the query against a graph
+    pub fn execute(&self, graph: &GraphMemory) -> Result<QueryResult> {
+        let started = Instant::now();
+        let mut exec = Executor {
+            graph,
+            query: self,
+            binding: vec![Value::Null; self.slot_count],
+            used_edges: HashSet::new(),
+            entities: HashMap::new(),
+            edges: HashMap::new(),
+            rows: Vec::new(),
+            seen: HashSet::new(),
+            expansions: 0,
+            truncated: false,
+            done: false,
+            stats: QueryStats::default(),
+        };
+
+        for entity in exec.anchor_candidates()? {
+            if exec.done {
+                break;
+            }
+            exec.stats.anchors += 1;
+            let slot = self.node_slots[self.anchor];
+            exec.binding[slot] = Value::Node(entity);
+            if exec.passes(&self.checks[0]) {
+                exec.walk(0)?;
+            }
+            exec.binding[slot] = Value::Null;
+        }
+
+        let Executor {
+            mut rows,
+            truncated,
+            mut stats,
+            ..
+        } = exec;
+
+        if !self.order_by.is_empty() {
+            rows.sort_by(|(keys_a, _), (keys_b, _)| {
+                for ((a, b), (_, descending)) in keys_a.iter().zip(keys_b).zip(&self.order_by) {
+                    let ordering = order_values(a, b);
+                    let ordering = if *descending {
+                        ordering.reverse()
+                    } else {
+                        ordering
+                    };
+                    if ordering != Ordering::Equal {
+                        return ordering;
+                    }
+                }
+                Ordering::Equal
+            });
+            rows.truncate(self.limit);
+        }
+
+        let columns = self.columns();
+        let rows = rows
+            .into_iter()
+            .map(|(_, values)| {
+                let mut row = serde_json::Map::new();
+                for (column, value) in columns.iter().zip(values) {
+                    row.insert(column.clone(), value_to_json(&value));
+                }
+                serde_json::Value::Object(row)
+            })
+            .collect();
+
+        stats.elapsed_ms = started.elapsed().as_secs_f64() * 1000.0;
+        Ok(QueryResult {
+            columns,
+            rows,
+            truncated,
+            stats,
+        })
+    }
+}
+
+/// Condition for a `{key: value}` pattern map entry (a list means "one of")
+fn map_condition(var: &str, key: &str, value: &Value) -> Expr {
+    let op = if matches!(value, Value::List(_)) {
+        CmpOp::In
+    } else {
+        CmpOp::Eq
+    };
+    Expr::compare(op, Expr::property(var, key), Expr::Literal(value.clone()))
+}
+
+/// Literal a conjunct pins `var.key` to, if it is `var.key = literal`
+fn pinned_literal<'a>(expr: &'a Expr, var: &str, key: &str) -> Option<&'a Value> {
+    match expr {
+        Expr::Compare(CmpOp::Eq, a, b) => match (a.as_ref(), b.as_ref()) {
+            (Expr::Property { name, key: k, .. }, Expr::Literal(value))
+            | (Expr::Literal(value), Expr::Property { name, key: k, .. })
+                if name == var && k == key =>
+            {
+                Some(value)
+            }
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Pick the node to start from: pinned UUID, then name, then label, then the first
+fn choose_anchor(nodes: &[NodePattern], node_vars: &[String], conjuncts: &[Expr]) -> usize {
+    let rank = |i: usize| -> u8 {
+        let var = &node_vars[i];
+        let pinned = |key: &str| {
+            conjuncts.iter().any(|c| match c {
+                Expr::Named { name, .. } => key == "name" && name == var,
+                other => pinned_literal(other, var, key).is_some(),
+            })
+        };
+        if pinned("uuid") {
+            0
+        } else if pinned("name") {
+            1
+        } else if !nodes[i].labels.is_empty() {
+            2
+        } else {
+            3
+        }
+    };
+    (0..nodes.len()).min_by_key(|i| rank(*i)).unwrap_or(0)
+}
+
+fn anchor_access(node: &NodePattern, var: &str, checks: &[Expr]) -> Result<Access> {
+    for check in checks {
+        if let Some(value) = pinned_literal(check, var, "uuid") {
+            let Value::Str(s) = value else {
+                bail!("{var}.uuid must be compared with a string");
+            };
+            let uuid = Uuid::parse_str(s).map_err(|_| anyhow!("invalid UUID {s:?}"))?;
+            return Ok(Access::ByUuid(uuid));
+        }
+    }
+    for check in checks {
+        match check {
+            Expr::Named { name, value, .. } if name == var => {
+                return Ok(Access::ByName(value.clone()))
+            }
+            other => {
+                if let Some(Value::Str(s)) = pinned_literal(other, var, "name") {
+                    return Ok(Access::ByName(s.clone()));
+                }
+            }
+        }
+    }
+    if node.labels.is_empty() {
+        Ok(Access::FullScan)
+    } else {
+        Ok(Access::LabelScan(node.labels.clone()))
+    }
+}
+
+// =============================================================================
+// EXECUTION
+// =============================================================================
+
+/// Result of running a graph query
+#[derive(Debug, Clone, Serialize)]
+pub struct QueryResult {
+    pub columns: Vec<String>,
+    /// One object per match, keyed by column name
+    pub rows: Vec<serde_json::Value>,
+    /// The expansion or row budget ran out before the match was exhaustive
+    pub truncated: bool,
+    pub stats: QueryStats,
+}
+
+/// Work done by a graph query
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct QueryStats {
+    /// Candidate entities the match started from
+    pub anchors: usize,
+    /// Distinct entities loaded
+    pub entities_read: usize,
+    /// Edges examined during expansion
+    pub edges_scanned: usize,
+    pub elapsed_ms: f64,
+}
+
+type Row = (Vec<Value>, Vec<Value>);
+
+struct Executor<'a> {
+    graph: &'a GraphMemory,
+    query: &'a GraphQuery,
+    binding: Vec<Value>,
+    /// Edges already used by the current match (a path never reuses an edge)
+    used_edges: HashSet<Uuid>,
+    entities: HashMap<Uuid, Option<Arc<EntityNode>>>,
+    edges: HashMap<Uuid, Arc<Vec<Arc<RelationshipEdge>>>>,
+    /// (ORDER BY keys, column values)
+    rows: Vec<Row>,
+    /// Rendered rows, for DISTINCT
+    seen: HashSet<String>,
+    expansions: usize,
+    truncated: bool,
+    done: bool,
+    stats: QueryStats,
+}
+
+impl Executor<'_> {
+    fn entity(&mut self, uuid: &Uuid) -> Result<Option<Arc<EntityNode>>> {
+        if let Some(cached) = self.entities.get(uuid) {
+            return Ok(cached.clone());
+        }
+        let entity = self.graph.get_entity(uuid)?.map(Arc::new);
+        self.stats.entities_read += 1;
+        self.entities.insert(*uuid, entity.clone());
+        Ok(entity)
+    }
+
+    fn edges_of(&mut self, uuid: &Uuid) -> Result<Arc<Vec<Arc<RelationshipEdge>>>> {
+        if let Some(cached) = self.edges.get(uuid) {
+            return Ok(cached.clone());
+        }
+        let edges: Vec<Arc<RelationshipEdge>> = self
+            .graph
+            .get_entity_relationships_limited(uuid, Some(GRAPH_QUERY_EDGES_PER_NODE))?
+            .into_iter()
+            .map(Arc::new)
+            .collect();
+        let edges = Arc::new(edges);
+        self.edges.insert(*uuid, edges.clone());
+        Ok(edges)
+    }
+
+    fn anchor_candidates(&mut self) -> Result<Vec<Arc<EntityNode>>> {
+        let entities = match &self.query.access {
+            Access::ByUuid(uuid) => self.entity(uuid)?.into_iter().collect(),
+            Access::ByName(name) => match self.graph.find_entity_by_name(name)? {
+                Some(entity) => {
+                    let entity = Arc::new(entity);
+                    self.entities.insert(entity.uuid, Some(entity.clone()));
+                    self.stats.entities_read += 1;
+                    vec![entity]
+                }
+                None => Vec::new(),
+            },
+            Access::LabelScan(_) | Access::FullScan => {
+                let all = self.graph.get_all_entities()?;
+                self.stats.entities_read += all.len();
+                all.into_iter()
+                    .map(|entity| {
+                        let entity = Arc::new(entity);
+                        self.entities.insert(entity.uuid, Some(entity.clone()));
+                        entity
+                    })
+                    .collect()
+            }
+        };
+        Ok(entities)
+    }
+
+    fn passes(&self, checks: &[Expr]) -> bool {
+        checks
+            .iter()
+            .all(|check| matches!(eval(check, &self.binding), Value::Bool(true)))
+    }
+
+    /// Bind the remaining steps, recording a row when all are bound
+    fn walk(&mut self, step_index: usize) -> Result<()> {
+        let query = self.query;
+        let Some(step) = query.steps.get(step_index) else {
+            return self.emit();
+        };
+
+        let from_slot = query.node_slots[step.from];
+        let Value::Node(start) = self.binding[from_slot].clone() else {
+            return Ok(());
+        };
+        let (min, max) = query.rels[step.rel].hops.unwrap_or((1, 1));
+        let mut paths = Vec::new();
+        self.extend_paths(
+            step,
+            start.clone(),
+            min,
+            max,
+            &mut Vec::new(),
+            &mut vec![start.uuid],
+            &mut paths,
+        )?;
+
+        let rel_slot = query.rel_slots[step.rel];
+        let to_slot = query.node_slots[step.to];
+        let to_was_bound = !matches!(self.binding[to_slot], Value::Null);
+
+        for (path, end) in paths {
+            if self.done {
+                break;
+            }
+            if to_was_bound {
+                // Repeated node variable: the path must close on the same entity
+                match &self.binding[to_slot] {
+                    Value::Node(bound) if bound.uuid == end.uuid => {}
+                    _ => continue,
+                }
+            }
+            if path.iter().any(|edge| self.used_edges.contains(&edge.uuid)) {
+                continue;
+            }
+
+            self.binding[rel_slot] = if query.rels[step.rel].hops.is_some() {
+                Value::Path(path.clone())
+            } else {
+                Value::Edge(path[0].clone())
+            };
+            if !to_was_bound {
+                self.binding[to_slot] = Value::Node(end);
+            }
+            if self.passes(&query.checks[step_index + 1]) {
+                for edge in &path {
+                    self.used_edges.insert(edge.uuid);
+                }
+                self.walk(step_index + 1)?;
+                for edge in &path {
+                    self.used_edges.remove(&edge.uuid);
+                }
+            }
+            if !to_was_bound {
+                self.binding[to_slot] = Value::Null;
+            }
+            self.binding[rel_slot] = Value::Null;
+        }
+        Ok(())
+    }
+
+    /// Collect every path of `min..=max` matching hops from `current`
+    #[allow(clippy::too_many_arguments)]
+    fn extend_paths(
+        &mut self,
+        step: &Step,
+        current: Arc<EntityNode>,
+        min: usize,
+        max: usize,
+        path: &mut Vec<Arc<RelationshipEdge>>,
+        visited: &mut Vec<Uuid>,
+        out: &mut Vec<(Vec<Arc<RelationshipEdge>>, Arc<EntityNode>)>,
+    ) -> Result<()> {
+        if path.len() >= min {
+            out.push((path.clone(), current.clone()));
+        }
+        if path.len() == max || self.done {
+            return Ok(());
+        }
+
+        for (edge, next) in self.hops(step, &current.uuid)? {
+            // Variable-length matches never revisit a node; a single hop may self-loop
+            if max > 1 && visited.contains(&next) {
+                continue;
+            }
+            let Some(next_entity) = self.entity(&next)? else {
+                continue;
+            };
+            path.push(edge);
+            visited.push(next);
+            self.extend_paths(step, next_entity, min, max, path, visited, out)?;
+            visited.pop();
+            path.pop();
+        }
+        Ok(())
+    }
+
+    /// Edges leaving `node` that satisfy a step's type, direction and edge filters
+    fn hops(&mut self, step: &Step, node: &Uuid) -> Result<Vec<(Arc<RelationshipEdge>, Uuid)>> {
+        let query = self.query;
+        let rel = &query.rels[step.rel];
+        let direction = if step.forward {
+            rel.direction
+        } else {
+            rel.direction.reversed()
+        };
+        let rel_slot = query.rel_slots[step.rel];
+        let mut scratch = vec![Value::Null; query.slot_count];
+
+        let mut hops = Vec::new();
+        for edge in self.edges_of(node)?.iter() {
+            self.expansions += 1;
+            self.stats.edges_scanned += 1;
+            if self.expansions > GRAPH_QUERY_MAX_EXPANSIONS {
+                self.truncated = true;
+                self.done = true;
+                break;
+            }
+
+            if edge.invalidated_at.is_some() && !query.include_invalidated[step.rel] {
+                continue;
+            }
+            if !rel.types.is_empty()
+                && !rel
+                    .types
+                    .iter()
+                    .any(|t| same_name(t, edge.relation_type.as_str()))
+            {
+                continue;
+            }
+            let next = match direction {
+                Direction::Outgoing if edge.from_entity == *node => edge.to_entity,
+                Direction::Incoming if edge.to_entity == *node => edge.from_entity,
+                Direction::Either if edge.from_entity == *node => edge.to_entity,
+                Direction::Either => edge.from_entity,
+                _ => continue,
+            };
+
+            scratch[rel_slot] = Value::Edge(edge.clone());
+            if query.hop_filters[step.rel]
+                .iter()
+                .all(|filter| matches!(eval(filter, &scratch), Value::Bool(true)))
+            {
+                hops.push((edge.clone(), next));
+            }
+        }
+        Ok(hops)
+    }
+
+    fn emit(&mut self) -> Result<()> {
+        let query = self.query;
+        let values: Vec<Value> = query
+            .returns
+            .iter()
+            .map(|item| eval(&item.expr, &self.binding))
+            .collect();
+
+        if query.distinct {
+            let rendered =
+                serde_json::Value::Array(values.iter().map(value_to_json).collect()).to_string();
+            if !self.seen.insert(rendered) {
+                return Ok(());
+            }
+        }
+
+        let keys = query
+            .order_by
+            .iter()
+            .map(|(key, _)| match key {
+                OrderKey::Column(index) => values[*index].clone(),
+                OrderKey::Expr(expr) => eval(expr, &self.binding),
+            })
+            .collect();
+        self.rows.push((keys, values));
+
+        if query.order_by.is_empty() {
+            if self.rows.len() >= query.limit {
+                self.done = true;
+            }
+        } else if self.rows.len() >= GRAPH_QUERY_MAX_ROWS {
+            // Sorting needs every match; stop at the row budget and say so
+            self.truncated = true;
+            self.done = true;
+        }
+        Ok(())
+    }
+}
+
+/// Compare names ignoring case and underscores (`WORKS_AT` matches `WorksAt`)
+fn same_name(a: &str, b: &str) -> bool {
+    let normalize = |s: &str| -> String {
+        s.chars()
+            .filter(|c| *c != '_')
+            .flat_map(char::to_lowercase)
+            .collect()
+    };
+    normalize(a) == normalize(b)
+}
+
+// =============================================================================
+// EVALUATION
+// =============================================================================
+
+fn eval(expr: &Expr, binding: &[Value]) -> Value {
+    match expr {
+        Expr::Literal(value) => value.clone(),
+        Expr::Variable { slot, .. } => binding.get(*slot).cloned().unwrap_or(Value::Null),
+        Expr::Property { slot, key, .. } => match binding.get(*slot) {
+            Some(Value::Node(node)) => node_property(node, key),
+            Some(Value::Edge(edge)) => edge_property(edge, key),
+            _ => Value::Null,
+        },
+        Expr::List(items) => Value::List(items.iter().map(|item| eval(item, binding)).collect()),
+        Expr::Not(inner) => match eval(inner, binding) {
+            Value::Bool(b) => Value::Bool(!b),
+            _ => Value::Null,
+        },
+        Expr::And(a, b) => match (eval(a, binding), eval(b, binding)) {
+            (Value::Bool(false), _) | (_, Value::Bool(false)) => Value::Bool(false),
+            (Value::Bool(true), Value::Bool(true)) => Value::Bool(true),
+            _ => Value::Null,
+        },
+        Expr::Or(a, b) => match (eval(a, binding), eval(b, binding)) {
+            (Value::Bool(true), _) | (_, Value::Bool(true)) => Value::Bool(true),
+            (Value::Bool(false), Value::Bool(false)) => Value::Bool(false),
+            _ => Value::Null,
+        },
+        Expr::IsNull(inner, negated) => {
+            let is_null = matches!(eval(inner, binding), Value::Null);
+            Value::Bool(is_null != *negated)
+        }
+        Expr::Compare(op, a, b) => compare_op(*op, &eval(a, binding), &eval(b, binding)),
+        Expr::HasLabel { slot, labels, .. } => match binding.get(*slot) {
+            Some(Value::Node(node)) => Value::Bool(
+                node.labels
+                    .iter()
+                    .any(|label| labels.iter().any(|l| same_name(l, label.as_str()))),
+            ),
+            _ => Value::Null,
+        },
+        Expr::Named { slot, value, .. } => match binding.get(*slot) {
+            Some(Value::Node(node)) => {
+                let value = value.to_lowercase();
+                Value::Bool(
+                    std::iter::once(&node.name)
+                        .chain(&node.aliases)
+                        .any(|name| name.to_lowercase() == value),
+                )
+            }
+            _ => Value::Null,
+        },
+    }
+}
+
+fn compare_op(op: CmpOp, a: &Value, b: &Value) -> Value {
+    if matches!(a, Value::Null) || matches!(b, Value::Null) {
+        return Value::Null;
+    }
+    let result = match op {
+        CmpOp::Eq => values_equal(a, b),
+        CmpOp::Ne => !values_equal(a, b),
+        CmpOp::Lt | CmpOp::Le | CmpOp::Gt | CmpOp::Ge => {
+            let Some(ordering) = compare_values(a, b) else {
+                return Value::Null;
+            };
+            match op {
+                CmpOp::Lt => ordering == Ordering::Less,
+                CmpOp::Le => ordering != Ordering::Greater,
+                CmpOp::Gt => ordering == Ordering::Greater,
+                _ => ordering != Ordering::Less,
+            }
+        }
+        CmpOp::Contains | CmpOp::StartsWith | CmpOp::EndsWith => match (a, b) {
+            (Value::Str(s), Value::Str(pattern)) => match op {
+                CmpOp::Contains => s.contains(pattern.as_str()),
+                CmpOp::StartsWith => s.starts_with(pattern.as_str()),
+                _ => s.ends_with(pattern.as_str()),
+            },
+            (Value::List(items), item) if op == CmpOp::Contains => {
+                items.iter().any(|x| values_equal(x, item))
+            }
+            _ => return Value::Null,
+        },
+        CmpOp::In => match b {
+            Value::List(items) => items.iter().any(|x| values_equal(a, x)),
+            _ => return Value::Null,
+        },
+    };
+    Value::Bool(result)
+}
+
+fn values_equal(a: &Value, b: &Value) -> bool {
+    match (a, b) {
+        (Value::Node(x), Value::Node(y)) => x.uuid == y.uuid,
+        (Value::Edge(x), Value::Edge(y)) => x.uuid == y.uuid,
+        (Value::List(x), Value::List(y)) => {
+            x.len() == y.len() && x.iter().zip(y).all(|(a, b)| values_equal(a, b))
+        }
+        _ => compare_values(a, b) == Some(Ordering::Equal),
+    }
+}
+
+fn compare_values(a: &Value, b: &Value) -> Option<Ordering> {
+    match (a, b) {
+        (Value::Int(x), Value::Int(y)) => Some(x.cmp(y)),
+        (Value::Int(x), Value::Float(y)) => (*x as f64).partial_cmp(y),
+        (Value::Float(x), Value::Int(y)) => x.partial_cmp(&(*y as f64)),
+        (Value::Float(x), Value::Float(y)) => x.partial_cmp(y),
+        (Value::Str(x), Value::Str(y)) => Some(x.cmp(y)),
+        (Value::Bool(x), Value::Bool(y)) => Some(x.cmp(y)),
+        (Value::Time(x), Value::Time(y)) => Some(x.cmp(y)),
+        (Value::Time(x), Value::Str(y)) => parse_time(y).map(|y| x.cmp(&y)),
+        (Value::Str(x), Value::Time(y)) => parse_time(x).map(|x| x.cmp(y)),
+        _ => None,
+    }
+}
+
+/// Sort order: comparable values by value, then everything else, nulls last
+fn order_values(a: &Value, b: &Value) -> Ordering {
+    match (a, b) {
+        (Value::Null, Value::Null) => Ordering::Equal,
+        (Value::Null, _) => Ordering::Greater,
+        (_, Value::Null) => Ordering::Less,
+        _ => compare_values(a, b).unwrap_or(Ordering::Equal),
+    }
+}
+
+/// RFC 3339 timestamps, `YYYY-MM-DDTHH:MM:SS` (UTC) or `YYYY-MM-DD` (midnight UTC)
+fn parse_time(s: &str) -> Option<DateTime<Utc>> {
+    if let Ok(t) = DateTime::parse_from_rfc3339(s) {
+        return Some(t.with_timezone(&Utc));
+    }
+    if let Ok(t) = NaiveDateTime::parse_from_str(s, "%Y-%m-%dT%H:%M:%S") {
+        return Some(t.and_utc());
+    }
+    NaiveDate::parse_from_str(s, "%Y-%m-%d")
+        .ok()
+        .and_then(|d| d.and_hms_opt(0, 0, 0))
+        .map(|t| t.and_utc())
+}
+
+fn node_property(node: &EntityNode, key: &str) -> Value {
+    match key {
+        "uuid" => Value::Str(node.uuid.to_string()),
+        "name" => Value::Str(node.name.clone()),
+        "labels" => Value::List(
+            node.labels
+                .iter()
+                .map(|l| Value::Str(l.as_str().to_string()))
+                .collect(),
+        ),
+        "aliases" => Value::List(node.aliases.iter().cloned().map(Value::Str).collect()),
+        "summary" => Value::Str(node.summary.clone()),
+        "salience" => Value::Float(node.salience as f64),
+        "mention_count" => Value::Int(node.mention_count as i64),
+        "is_proper_noun" => Value::Bool(node.is_proper_noun),
+        "centrality" => Value::Float(node.centrality as f64),
+        "community" => node.community.map_or(Value::Null, |c| Value::Int(c as i64)),
+        "created_at" => Value::Time(node.created_at),
+        "last_seen_at" => Value::Time(node.last_seen_at),
+        other => node
+            .attributes
+            .get(other)
+            .map_or(Value::Null, |v| Value::Str(v.clone())),
+    }
+}
+
+fn tier_name(tier: EdgeTier) -> &'static str {
+    match tier {
+        EdgeTier::L1Working => "L1",
+        EdgeTier::L2Episodic => "L2",
+        EdgeTier::L3Semantic => "L3",
+    }
+}
+
+fn edge_property(edge: &RelationshipEdge, key: &str) -> Value {
+    match key {
+        "uuid" => Value::Str(edge.uuid.to_string()),
+        "type" => Value::Str(edge.relation_type.as_str().to_string()),
+        "from" => Value::Str(edge.from_entity.to_string()),
+        "to" => Value::Str(edge.to_entity.to_string()),
+        "strength" => Value::Float(edge.effective_strength() as f64),
+        "raw_strength" => Value::Float(edge.strength as f64),
+        "tier" => Value::Str(tier_name(edge.tier).to_string()),
+        "activation_count" => Value::Int(edge.activation_count as i64),
+        "context" => Value::Str(edge.context.clone()),
+        "created_at" => Value::Time(edge.created_at),
+        "valid_at" => Value::Time(edge.valid_at),
+        "last_activated" => Value::Time(edge.last_activated),
+        "invalidated_at" => edge.invalidated_at.map_or(Value::Null, Value::Time),
+        _ => Value::Null,
+    }
+}
+
+fn node_json(node: &EntityNode) -> serde_json::Value {
+    serde_json::json!({
+        "uuid": node.uuid,
+        "name": node.name,
+        "labels": node.labels.iter().map(|l| l.as_str()).collect::<Vec<_>>(),
+        "aliases": node.aliases,
+        "summary": node.summary,
+        "salience": node.salience,
+        "mention_count": node.mention_count,
+        "is_proper_noun": node.is_proper_noun,
+        "centrality": node.centrality,
+        "community": node.community,
+        "attributes": node.attributes,
+        "created_at": node.created_at,
+        "last_seen_at": node.last_seen_at,
+    })
+}
+
+fn edge_json(edge: &RelationshipEdge) -> serde_json::Value {
+    serde_json::json!({
+        "uuid": edge.uuid,
+        "type": edge.relation_type.as_str(),
+        "from": edge.from_entity,
+        "to": edge.to_entity,
+        "strength": edge.effective_strength(),
+        "raw_strength": edge.strength,
+        "tier": tier_name(edge.tier),
+        "activation_count": edge.activation_count,
+        "context": edge.context,
+        "created_at": edge.created_at,
+        "valid_at": edge.valid_at,
+        "last_activated": edge.last_activated,
+        "invalidated_at": edge.invalidated_at,
+    })
+}
+
+fn value_to_json(value: &Value) -> serde_json::Value {
+    match value {
+        Value::Null => serde_json::Value::Null,
+        Value::Bool(b) => serde_json::Value::Bool(*b),
+        Value::Int(n) => serde_json::Value::from(*n),
+        Value::Float(x) => serde_json::Number::from_f64(*x)
+            .map_or(serde_json::Value::Null, serde_json::Value::Number),
+        Value::Str(s) => serde_json::Value::String(s.clone()),
+        Value::Time(t) => serde_json::Value::String(t.to_rfc3339()),
+        Value::List(items) => serde_json::Value::Array(items.iter().map(value_to_json).collect()),
+        Value::Node(node) => node_json(node),
+        Value::Edge(edge) => edge_json(edge),
+        Value::Path(edges) => {
+            serde_json::Value::Array(edges.iter().map(|e| edge_json(e)).collect())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tokenize_range_and_arrows() {
+        let tokens: Vec<Token> = tokenize("(a)<-[:X*1..3]->(b) 2.5")
+            .unwrap()
+            .into_iter()
+            .map(|(t, _)| t)
+            .collect();
+        assert_eq!(
+            tokens,
+            vec![
+                Token::LParen,
+                Token::Ident("a".into()),
+                Token::RParen,
+                Token::Lt,
+                Token::Dash,
+                Token::LBracket,
+                Token::Colon,
+                Token::Ident("X".into()),
+                Token::Star,
+                Token::Int(1),
+                Token::DotDot,
+                Token::Int(3),
+                Token::RBracket,
+                Token::Dash,
+                Token::Gt,
+                Token::LParen,
+                Token::Ident("b".into()),
+                Token::RParen,
+                Token::Float(2.5),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_pattern_and_clauses() {
+        let query = GraphQuery::parse(
+            "MATCH (p:Person)-[r:WorksAt]->(o:Organization {name: 'Acme'}) \
+             WHERE p.salience >= 0.5 AND r.tier IN ['L2', 'L3'] \
+             RETURN p.name AS who, o ORDER BY who DESC LIMIT 5",
+        )
+        .unwrap();
+
+        assert_eq!(query.columns(), vec!["who".to_string(), "o".to_string()]);
+        assert_eq!(query.limit, 5);
+        // The named organization anchors the match; the edge is walked backwards
+        assert_eq!(query.anchor, 1);
+        assert!(matches!(query.access, Access::ByName(ref n) if n == "Acme"));
+        assert_eq!(query.steps.len(), 1);
+        assert!(!query.steps[0].forward);
+        // r.tier is checked per edge, p.salience once p is bound
+        assert_eq!(query.hop_filters[0].len(), 1);
+        assert_eq!(query.checks[1].len(), 2);
+        assert!(matches!(query.order_by[0], (OrderKey::Column(0), true)));
+    }
+
+    #[test]
+    fn test_variable_length_hops() {
+        let query = GraphQuery::parse("MATCH (a)-[*]-(b) RETURN b").unwrap();
+        assert_eq!(query.rels[0].hops, Some((1, GRAPH_QUERY_MAX_HOPS)));
+        let query = GraphQuery::parse("MATCH (a)-[*2]-(b) RETURN b").unwrap();
+        assert_eq!(query.rels[0].hops, Some((2, 2)));
+        let query = GraphQuery::parse("MATCH (a)-[*..3]-(b) RETURN b").unwrap();
+        assert_eq!(query.rels[0].hops, Some((1, 3)));
+
+        assert!(GraphQuery::parse("MATCH (a)-[*3..1]-(b) RETURN b").is_err());
+        assert!(GraphQuery::parse("MATCH (a)-[*1..99]-(b) RETURN b").is_err());
+    }
+
+    #[test]
+    fn test_invalidated_edges_only_when_asked() {
+        let query = GraphQuery::parse("MATCH (a)-[r]->(b) RETURN b").unwrap();
+        assert!(!query.include_invalidated[0]);
+        let query =
+            GraphQuery::parse("MATCH (a)-[r]->(b) WHERE r.invalidated_at IS NOT NULL RETURN b")
+                .unwrap();
+        assert!(query.include_invalidated[0]);
+    }
+
+    #[test]
+    fn test_compile_errors() {
+        for bad in [
+            "MATCH (a) RETURN b",
+            "MATCH (a)-[r*1..2]->(b) RETURN r.strength",
+            "MATCH (a)-[r]->(b) WHERE r.colour = 'red' RETURN a",
+            "MATCH (a)-[r]->(r) RETURN a",
+            "MATCH (a) RETURN a LIMIT 100000",
+            "MATCH () RETURN *",
+            "MATCH (a:X:Y) RETURN a",
+            "MATCH (a) WHERE a.name = 'x RETURN a",
+            "MATCH (a)<-[]->(b) RETURN a",
+        ] {
+            assert!(GraphQuery::parse(bad).is_err(), "should reject: {bad}");
+        }
+    }
+
+    #[test]
+    fn test_comparisons() {
+        let t = Value::Time(parse_time("2025-06-01").unwrap());
+        assert!(matches!(
+            compare_op(CmpOp::Gt, &t, &Value::Str("2025-01-01".into())),
+            Value::Bool(true)
+        ));
+        assert!(matches!(
+            compare_op(CmpOp::Eq, &Value::Int(2), &Value::Float(2.0)),
+            Value::Bool(true)
+        ));
+        assert!(matches!(
+            compare_op(CmpOp::Eq, &Value::Null, &Value::Int(1)),
+            Value::Null
+        ));
+        assert!(same_name("WORKS_AT", "WorksAt"));
+    }
+}
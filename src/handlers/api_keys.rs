@@ -0,0 +1,142 @@
+//! API Key Management Handlers
+//!
+//! Admin-only endpoints for issuing, listing, revoking and rotating scoped
+//! API keys. The auth middleware restricts every `/api/keys` route to admin
+//! keys spanning all users: an admin scoped to one tenant could otherwise mint
+//! an unscoped key or rotate another tenant's. Plaintext secrets are only ever
+//! returned by create and rotate.
+
+use axum::{
+    extract::{Path, State},
+    response::Json,
+};
+use serde::{Deserialize, Serialize};
+
+use super::state::MultiUserMemoryManager;
+use crate::api_keys::{ApiKeyRecord, ApiKeyRole, IssuedApiKey, ALL_USERS_WILDCARD};
+use crate::auth::AuthContext;
+use crate::errors::{AppError, ValidationErrorExt};
+use crate::validation;
+use std::sync::Arc;
+
+type AppState = Arc<MultiUserMemoryManager>;
+
+/// Request for POST /api/keys
+#[derive(Debug, Deserialize)]
+pub struct CreateApiKeyRequest {
+    pub name: String,
+    pub role: ApiKeyRole,
+    /// User IDs the key may act on. Use `["*"]` for all users.
+    pub allowed_user_ids: Vec<String>,
+}
+
+/// Response for GET /api/keys
+#[derive(Debug, Serialize)]
+pub struct ListApiKeysResponse {
+    pub keys: Vec<ApiKeyRecord>,
+    pub count: usize,
+}
+
+/// POST /api/keys - Issue a new scoped API key
+pub async fn create_api_key(
+    State(state): State<AppState>,
+    auth: AuthContext,
+    Json(req): Json<CreateApiKeyRequest>,
+) -> Result<Json<IssuedApiKey>, AppError> {
+    if req.name.trim().is_empty() {
+        return Err(AppError::InvalidInput {
+            field: "name".to_string(),
+            reason: "name cannot be empty".to_string(),
+        });
+    }
+    if req.allowed_user_ids.is_empty() {
+        return Err(AppError::InvalidInput {
+            field: "allowed_user_ids".to_string(),
+            reason: "at least one user_id (or \"*\") is required".to_string(),
+        });
+    }
+    for user_id in &req.allowed_user_ids {
+        if user_id != ALL_USERS_WILDCARD {
+            validation::validate_user_id(user_id).map_validation_err("allowed_user_ids")?;
+        }
+    }
+
+    let issued = state
+        .api_key_store
+        .create(req.name.trim(), req.role, req.allowed_user_ids)
+        .map_err(AppError::Internal)?;
+
+    state.log_event(
+        "_system",
+        "API_KEY_CREATE",
+        &issued.record.id,
+        &format!(
+            "Created {} key '{}' (by {})",
+            issued.record.role, issued.record.name, auth.key_id
+        ),
+    );
+
+    Ok(Json(issued))
+}
+
+/// GET /api/keys - List all keys (secrets and hashes are never returned)
+pub async fn list_api_keys(
+    State(state): State<AppState>,
+) -> Result<Json<ListApiKeysResponse>, AppError> {
+    let keys: Vec<ApiKeyRecord> = state
+        .api_key_store
+        .list()
+        .map_err(AppError::Internal)?
+        .iter()
+        .map(ApiKeyRecord::redacted)
+        .collect();
+    let count = keys.len();
+    Ok(Json(ListApiKeysResponse { keys, count }))
+}
+
+/// POST /api/keys/{key_id}/revoke - Revoke a key immediately
+pub async fn revoke_api_key(
+    State(state): State<AppState>,
+    auth: AuthContext,
+    Path(key_id): Path<String>,
+) -> Result<Json<ApiKeyRecord>, AppError> {
+    let record = state
+        .api_key_store
+        .revoke(&key_id)
+        .map_err(AppError::Internal)?
+        .ok_or_else(|| AppError::ApiKeyNotFound(key_id.clone()))?;
+
+    state.log_event(
+        "_system",
+        "API_KEY_REVOKE",
+        &key_id,
+        &format!("Revoked key '{}' (by {})", record.name, auth.key_id),
+    );
+
+    Ok(Json(record))
+}
+
+/// POST /api/keys/{key_id}/rotate - Issue a new secret for an existing key
+pub async fn rotate_api_key(
+    State(state): State<AppState>,
+    auth: AuthContext,
+    Path(key_id): Path<String>,
+) -> Result<Json<IssuedApiKey>, AppError> {
+    let issued = state
+        .api_key_store
+        .rotate(&key_id)
+        .map_err(|e| AppError::InvalidInput {
+            field: "key_id".to_string(),
+            reason: e.to_string(),
+        })?
+        .ok_or_else(|| AppError::ApiKeyNotFound(key_id.clone()))?;
+
+    state.log_event(
+        "_system",
+        "API_KEY_ROTATE",
+        &key_id,
+        &format!("Rotated key '{}' (by {})", issued.record.name, auth.key_id),
+    );
+
+    Ok(Json(issued))
+}
@@ -10,9 +10,11 @@ use super::types::{
     BackupResponse, CleanupCorruptedRequest, CleanupCorruptedResponse, ConsolidateRequest,
     ConsolidateResponse, CreateBackupRequest, ListBackupsRequest, ListBackupsResponse, MemoryEvent,
     MigrateLegacyRequest, MigrateLegacyResponse, PurgeBackupsRequest, PurgeBackupsResponse,
-    RebuildIndexRequest, RebuildIndexResponse, RepairIndexRequest, RepairIndexResponse,
-    VerifyBackupRequest, VerifyBackupResponse, VerifyIndexRequest,
+    RebuildIndexRequest, RebuildIndexResponse, ReembedRequest, ReembedResponse, RepairIndexRequest,
+    RepairIndexResponse, RestoreBackupRequest, RestoreBackupResponse, VerifyBackupRequest,
+    VerifyBackupResponse, VerifyIndexRequest,
 };
+use crate::auth::AuthContext;
 use crate::errors::{AppError, ValidationErrorExt};
 use crate::memory;
 use crate::metrics;
@@ -273,6 +275,38 @@ pub async fn rebuild_index(
     }))
 }
 
+/// Re-embed a user's memories and facts with the configured embedding model
+///
+/// Stores opened after an embedding model or dimension change keep serving
+/// (with degraded semantic recall) until this is run for them.
+pub async fn reembed_memories(
+    State(state): State<AppState>,
+    Json(req): Json<ReembedRequest>,
+) -> Result<Json<ReembedResponse>, AppError> {
+    validation::validate_user_id(&req.user_id).map_validation_err("user_id")?;
+
+    let memory_sys = state
+        .get_user_memory(&req.user_id)
+        .map_err(AppError::Internal)?;
+
+    let (reembedded, model, dimension) = tokio::task::spawn_blocking(move || {
+        let memory_guard = memory_sys.read();
+        let reembedded = memory_guard.reembed()?;
+        let embedder = memory_guard.get_embedder();
+        Ok::<_, anyhow::Error>((reembedded, embedder.model_id(), embedder.dimension()))
+    })
+    .await
+    .map_err(|e| AppError::Internal(anyhow::anyhow!("Re-embed task panicked: {e}")))?
+    .map_err(AppError::Internal)?;
+
+    Ok(Json(ReembedResponse {
+        success: true,
+        reembedded,
+        model,
+        dimension,
+    }))
+}
+
 // =============================================================================
 // BACKUP & RESTORE
 // =============================================================================
@@ -284,29 +318,8 @@ pub async fn create_backup(
 ) -> Result<Json<BackupResponse>, AppError> {
     validation::validate_user_id(&req.user_id).map_validation_err("user_id")?;
 
-    let memory_sys = state
-        .get_user_memory(&req.user_id)
-        .map_err(AppError::Internal)?;
-
-    let memory_guard = memory_sys.read();
-    let db = memory_guard.get_db();
-
-    // Collect secondary store DB references for comprehensive backup
-    let secondary_refs = state.collect_secondary_store_refs();
-    let store_refs: Vec<crate::backup::SecondaryStoreRef<'_>> = secondary_refs
-        .iter()
-        .map(|(name, db)| crate::backup::SecondaryStoreRef { name, db })
-        .collect();
-
-    let result = if store_refs.is_empty() {
-        state.backup_engine().create_backup(&db, &req.user_id)
-    } else {
-        state
-            .backup_engine()
-            .create_comprehensive_backup(&db, &req.user_id, &store_refs)
-    };
-
-    match result {
+    // Memories, per-user stores (index, graph) and shared stores
+    match state.create_user_backup(&req.user_id) {
         Ok(metadata) => {
             let secondary_count = metadata.secondary_stores.len();
             state.log_event(
@@ -409,6 +422,87 @@ pub async fn purge_backups(
     }
 }
 
+/// POST /api/backup/restore - Restore a user from a backup, optionally to a point in time
+///
+/// Without `target_time` the user is restored to the backup point; with it,
+/// archived WAL batches are replayed up to that time (granularity is the
+/// archive interval). Shared stores are only rolled back on request, and only
+/// by unscoped admin keys since they hold every user's data.
+pub async fn restore_backup(
+    State(state): State<AppState>,
+    auth: AuthContext,
+    Json(req): Json<RestoreBackupRequest>,
+) -> Result<Json<RestoreBackupResponse>, AppError> {
+    validation::validate_user_id(&req.user_id).map_validation_err("user_id")?;
+
+    if req.include_shared_stores && auth.allowed_user_ids.is_some() {
+        return Err(AppError::InvalidInput {
+            field: "include_shared_stores".to_string(),
+            reason: "shared stores hold every user's data; an unscoped admin key is required"
+                .to_string(),
+        });
+    }
+    if let Some(target_time) = req.target_time {
+        if target_time > chrono::Utc::now() {
+            return Err(AppError::InvalidInput {
+                field: "target_time".to_string(),
+                reason: "must not be in the future".to_string(),
+            });
+        }
+    }
+
+    let backup = state
+        .backup_engine()
+        .resolve_restore_point(&req.user_id, req.backup_id, req.target_time)
+        .map_err(AppError::Internal)?
+        .ok_or_else(|| AppError::InvalidInput {
+            field: if req.backup_id.is_some() {
+                "backup_id".to_string()
+            } else {
+                "target_time".to_string()
+            },
+            reason: format!("no matching backup for user '{}'", req.user_id),
+        })?;
+
+    let restore = {
+        let state = state.clone();
+        let user_id = req.user_id.clone();
+        tokio::task::spawn_blocking(move || {
+            state.restore_user(
+                &user_id,
+                &backup,
+                req.target_time,
+                req.include_shared_stores,
+            )
+        })
+        .await
+        .map_err(|e| AppError::Internal(anyhow::anyhow!("Blocking task panicked: {e}")))?
+        .map_err(AppError::Internal)?
+    };
+
+    state.log_event(
+        &req.user_id,
+        "BACKUP_RESTORE",
+        &restore.backup_id.to_string(),
+        &format!(
+            "Restored from backup {} (target: {}, {} WAL batches, shared stores: [{}]) by {}",
+            restore.backup_id,
+            restore
+                .target_time
+                .map(|t| t.to_rfc3339())
+                .unwrap_or_else(|| "backup point".to_string()),
+            restore.replayed_batches,
+            restore.shared_stores.join(", "),
+            auth.key_id
+        ),
+    );
+
+    Ok(Json(RestoreBackupResponse {
+        success: true,
+        restore,
+    }))
+}
+
 // =============================================================================
 // CONSOLIDATION INTROSPECTION
 // =============================================================================
@@ -48,6 +48,12 @@ pub struct ListQuery {
     pub memory_type: Option<String>,
     /// Text search query - filters by content or tags (case-insensitive)
     pub query: Option<String>,
+    /// Only memories created by this agent
+    pub agent_id: Option<String>,
+    /// Only memories from this agent run
+    pub run_id: Option<String>,
+    /// Only memories recorded for this actor
+    pub actor_id: Option<String>,
 }
 
 /// List response - simplified memory list
@@ -65,6 +71,12 @@ pub struct ListMemoriesRequest {
     #[serde(rename = "type")]
     pub memory_type: Option<String>,
     pub query: Option<String>,
+    /// Only memories created by this agent
+    pub agent_id: Option<String>,
+    /// Only memories from this agent run
+    pub run_id: Option<String>,
+    /// Only memories recorded for this actor
+    pub actor_id: Option<String>,
 }
 
 #[derive(Debug, Serialize)]
@@ -75,6 +87,12 @@ pub struct ListMemoryItem {
     pub importance: f32,
     pub tags: Vec<String>,
     pub created_at: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub agent_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub run_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub actor_id: Option<String>,
 }
 
 // =============================================================================
@@ -148,6 +166,18 @@ pub struct ForgetByDateRequest {
     pub end: chrono::DateTime<chrono::Utc>,
 }
 
+/// Forget an agent's memories, or a single run's
+#[derive(Debug, Deserialize)]
+pub struct ForgetByAgentRequest {
+    pub user_id: String,
+    /// Delete every memory created by this agent
+    #[serde(default)]
+    pub agent_id: Option<String>,
+    /// Delete every memory from this run (e.g. a failed run)
+    #[serde(default)]
+    pub run_id: Option<String>,
+}
+
 /// Bulk delete memories by filters
 #[derive(Debug, Deserialize)]
 pub struct BulkDeleteRequest {
@@ -283,6 +313,13 @@ pub async fn list_memories(
         });
     }
 
+    // Filter by agent/run/actor scope if specified
+    filtered.retain(|m| {
+        (query.agent_id.is_none() || m.agent_id == query.agent_id)
+            && (query.run_id.is_none() || m.run_id == query.run_id)
+            && (query.actor_id.is_none() || m.actor_id == query.actor_id)
+    });
+
     let total = filtered.len();
     let limit = query.limit.unwrap_or(100).min(1000);
 
@@ -296,6 +333,9 @@ pub async fn list_memories(
             importance: m.importance(),
             tags: m.experience.entities.clone(),
             created_at: m.created_at.to_rfc3339(),
+            agent_id: m.agent_id.clone(),
+            run_id: m.run_id.clone(),
+            actor_id: m.actor_id.clone(),
         })
         .collect();
 
@@ -355,6 +395,13 @@ pub async fn list_memories_post(
         });
     }
 
+    // Filter by agent/run/actor scope if specified
+    filtered.retain(|m| {
+        (req.agent_id.is_none() || m.agent_id == req.agent_id)
+            && (req.run_id.is_none() || m.run_id == req.run_id)
+            && (req.actor_id.is_none() || m.actor_id == req.actor_id)
+    });
+
     let total = filtered.len();
     let limit = req.limit.unwrap_or(100).min(1000);
 
@@ -368,6 +415,9 @@ pub async fn list_memories_post(
             importance: m.importance(),
             tags: m.experience.entities.clone(),
             created_at: m.created_at.to_rfc3339(),
+            agent_id: m.agent_id.clone(),
+            run_id: m.run_id.clone(),
+            actor_id: m.actor_id.clone(),
         })
         .collect();
 
@@ -781,6 +831,73 @@ pub async fn forget_by_date(
     })))
 }
 
+// =============================================================================
+// FORGET BY AGENT HANDLER
+// =============================================================================
+
+/// POST /api/forget/agent - Forget an agent's memories or one run's memories
+///
+/// Exactly one of `agent_id` or `run_id` must be given.
+#[tracing::instrument(skip(state), fields(user_id = %req.user_id))]
+pub async fn forget_by_agent(
+    State(state): State<AppState>,
+    Json(req): Json<ForgetByAgentRequest>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    validation::validate_user_id(&req.user_id).map_validation_err("user_id")?;
+
+    let (criteria, scope) = match (&req.agent_id, &req.run_id) {
+        (Some(agent_id), None) if !agent_id.is_empty() => (
+            memory::ForgetCriteria::ByAgent(agent_id.clone()),
+            format!("agent: {agent_id}"),
+        ),
+        (None, Some(run_id)) if !run_id.is_empty() => (
+            memory::ForgetCriteria::ByRun(run_id.clone()),
+            format!("run: {run_id}"),
+        ),
+        _ => {
+            return Err(AppError::InvalidInput {
+                field: "agent_id".to_string(),
+                reason: "Provide exactly one non-empty agent_id or run_id".to_string(),
+            });
+        }
+    };
+
+    let memory_sys = state
+        .get_user_memory(&req.user_id)
+        .map_err(AppError::Internal)?;
+
+    let deleted_count = {
+        let memory_sys = memory_sys.clone();
+        tokio::task::spawn_blocking(move || memory_sys.read().forget(criteria))
+            .await
+            .map_err(|e| AppError::Internal(anyhow::anyhow!("Blocking task panicked: {e}")))?
+            .map_err(AppError::Internal)?
+    };
+
+    info!(
+        "🤖 Forget by agent: user={}, {}, deleted={}",
+        req.user_id, scope, deleted_count
+    );
+
+    state.emit_event(MemoryEvent {
+        event_type: "DELETE".to_string(),
+        timestamp: chrono::Utc::now(),
+        user_id: req.user_id.clone(),
+        memory_id: None,
+        content_preview: Some(scope),
+        memory_type: None,
+        importance: None,
+        count: Some(deleted_count),
+    });
+
+    Ok(Json(serde_json::json!({
+        "success": true,
+        "deleted_count": deleted_count,
+        "agent_id": req.agent_id,
+        "run_id": req.run_id
+    })))
+}
+
 // =============================================================================
 // BULK DELETE HANDLER
 // =============================================================================
@@ -926,7 +1043,7 @@ fn resolve_memory(
 }
 
 /// Parse experience type from string
-fn parse_experience_type(type_str: &str) -> Result<ExperienceType, AppError> {
+pub(super) fn parse_experience_type(type_str: &str) -> Result<ExperienceType, AppError> {
     match type_str.to_lowercase().as_str() {
         "observation" => Ok(ExperienceType::Observation),
         "decision" => Ok(ExperienceType::Decision),
@@ -0,0 +1,384 @@
+//! Streaming Export/Import Handlers
+//!
+//! Bulk export of every per-user store as JSONL or Parquet, streamed page
+//! by page, and the matching importer. See [`crate::export`] for the
+//! record layout and cursor semantics.
+
+use axum::{
+    body::{Body, Bytes},
+    extract::{Query, State},
+    http::header,
+    response::{Json, Response},
+};
+use futures::StreamExt;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+use super::state::MultiUserMemoryManager;
+use crate::errors::{AppError, ValidationErrorExt};
+use crate::export::{
+    ExportCursor, ExportFormat, ExportRecord, ExportSources, ExportStore, Exporter, ImportReport,
+    ImportTargets, Importer, ParquetRecordReader, RecordEncoder, DEFAULT_PAGE_SIZE,
+};
+use crate::validation;
+
+/// Application state type alias
+pub type AppState = std::sync::Arc<MultiUserMemoryManager>;
+
+/// Encoded pages buffered between the store scanner and the response body
+const EXPORT_CHANNEL_DEPTH: usize = 4;
+
+/// Longest JSONL line accepted on import
+const MAX_IMPORT_LINE_BYTES: usize = 16 * 1024 * 1024;
+
+/// Directory under the storage root where Parquet uploads are spooled
+const IMPORT_STAGING_DIR: &str = ".import";
+
+// =============================================================================
+// TYPES
+// =============================================================================
+
+/// Request for a streaming export
+#[derive(Debug, Deserialize)]
+pub struct StreamExportRequest {
+    pub user_id: String,
+    #[serde(default)]
+    pub format: ExportFormat,
+    /// Stores to include (default: all)
+    #[serde(default)]
+    pub stores: Option<Vec<ExportStore>>,
+    /// Resume right after the record carrying this cursor
+    #[serde(default)]
+    pub cursor: Option<String>,
+    /// Stop after this many records
+    #[serde(default)]
+    pub limit: Option<usize>,
+}
+
+/// Query parameters of a streaming import (the body is the export itself)
+#[derive(Debug, Deserialize)]
+pub struct StreamImportQuery {
+    pub user_id: String,
+    #[serde(default)]
+    pub format: ExportFormat,
+    /// Skip records up to and including this cursor (from a partial import)
+    #[serde(default)]
+    pub resume_after: Option<String>,
+}
+
+/// Response for a streaming import
+#[derive(Debug, Serialize)]
+pub struct StreamImportResponse {
+    pub success: bool,
+    pub report: ImportReport,
+}
+
+fn parse_cursor(field: &str, cursor: Option<&str>) -> Result<Option<ExportCursor>, AppError> {
+    cursor
+        .map(ExportCursor::parse)
+        .transpose()
+        .map_err(|e| AppError::InvalidInput {
+            field: field.to_string(),
+            reason: e.to_string(),
+        })
+}
+
+/// Find a named database among a store's `databases()`
+fn named_db(
+    databases: Vec<(&str, &Arc<rocksdb::DB>)>,
+    name: &str,
+) -> anyhow::Result<Arc<rocksdb::DB>> {
+    databases
+        .into_iter()
+        .find(|(n, _)| *n == name)
+        .map(|(_, db)| Arc::clone(db))
+        .ok_or_else(|| anyhow::anyhow!("Store database '{name}' not found"))
+}
+
+fn export_sources(state: &AppState, user_id: &str) -> anyhow::Result<ExportSources> {
+    let memory_db = state.get_user_memory(user_id)?.read().get_db();
+    let graph = state.get_user_graph(user_id)?;
+    let graph = graph.read();
+    Ok(ExportSources {
+        user_id: user_id.to_string(),
+        memory_db,
+        entities_db: named_db(graph.databases(), "graph_entities")?,
+        relationships_db: named_db(graph.databases(), "graph_relationships")?,
+        project_db: named_db(state.todo_store.databases(), "todo_projects")?,
+        todo_db: named_db(state.todo_store.databases(), "todo_items")?,
+        file_db: named_db(state.file_store.databases(), "file_memories")?,
+    })
+}
+
+// =============================================================================
+// HANDLERS
+// =============================================================================
+
+/// POST /api/export/stream - Stream every store as JSONL or Parquet
+///
+/// The body is produced page by page from a blocking scanner; each record's
+/// `cursor` can be passed back as `cursor` to resume an interrupted export.
+pub async fn export_stream(
+    State(state): State<AppState>,
+    Json(req): Json<StreamExportRequest>,
+) -> Result<Response, AppError> {
+    validation::validate_user_id(&req.user_id).map_validation_err("user_id")?;
+    let cursor = parse_cursor("cursor", req.cursor.as_deref())?;
+    let stores = req.stores.unwrap_or_else(|| ExportStore::ALL.to_vec());
+
+    let sources = export_sources(&state, &req.user_id).map_err(AppError::Internal)?;
+    let mut exporter = Exporter::new(sources, &stores, cursor)
+        .map_err(|e| AppError::InvalidInput {
+            field: "cursor".to_string(),
+            reason: e.to_string(),
+        })?
+        .with_page_size(DEFAULT_PAGE_SIZE);
+    let mut encoder = RecordEncoder::new(req.format);
+    let mut remaining = req.limit.unwrap_or(usize::MAX);
+
+    let (tx, rx) =
+        tokio::sync::mpsc::channel::<Result<Bytes, std::io::Error>>(EXPORT_CHANNEL_DEPTH);
+    let user_id = req.user_id.clone();
+    tokio::task::spawn_blocking(move || {
+        let mut exported = 0usize;
+        let result = (|| -> anyhow::Result<bool> {
+            while remaining > 0 {
+                let Some(mut page) = exporter.next_page()? else {
+                    break;
+                };
+                page.truncate(remaining);
+                remaining -= page.len();
+                exported += page.len();
+                if tx.blocking_send(Ok(encoder.encode(&page)?.into())).is_err() {
+                    return Ok(false);
+                }
+            }
+            Ok(tx.blocking_send(Ok(encoder.finish()?.into())).is_ok())
+        })();
+        match result {
+            Ok(true) => tracing::debug!(user_id = %user_id, records = exported, "Export streamed"),
+            Ok(false) => tracing::debug!(
+                user_id = %user_id,
+                records = exported,
+                "Export client disconnected"
+            ),
+            Err(e) => {
+                tracing::warn!(user_id = %user_id, error = %e, "Export stream failed");
+                let _ = tx.blocking_send(Err(std::io::Error::other(e.to_string())));
+            }
+        }
+    });
+
+    state.log_event(
+        &req.user_id,
+        "EXPORT_STREAM",
+        &req.user_id,
+        &format!(
+            "Streaming {} export of [{}]",
+            req.format.extension(),
+            stores
+                .iter()
+                .map(ExportStore::as_str)
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+    );
+
+    Response::builder()
+        .header(header::CONTENT_TYPE, req.format.content_type())
+        .header(
+            header::CONTENT_DISPOSITION,
+            format!(
+                "attachment; filename=\"{}-export.{}\"",
+                req.user_id,
+                req.format.extension()
+            ),
+        )
+        .body(Body::from_stream(
+            tokio_stream::wrappers::ReceiverStream::new(rx),
+        ))
+        .map_err(|e| AppError::Internal(anyhow::anyhow!("Failed to build response: {e}")))
+}
+
+/// POST /api/import/stream - Import a JSONL or Parquet export
+///
+/// JSONL is applied as it arrives, in batches; Parquet is spooled to disk
+/// first (its index sits at the end of the file) and then read one row
+/// group at a time.
+pub async fn import_stream(
+    State(state): State<AppState>,
+    Query(query): Query<StreamImportQuery>,
+    body: Body,
+) -> Result<Json<StreamImportResponse>, AppError> {
+    validation::validate_user_id(&query.user_id).map_validation_err("user_id")?;
+    let resume_after = parse_cursor("resume_after", query.resume_after.as_deref())?;
+
+    let targets = ImportTargets {
+        user_id: query.user_id.clone(),
+        memory: state
+            .get_user_memory(&query.user_id)
+            .map_err(AppError::Internal)?,
+        graph: state
+            .get_user_graph(&query.user_id)
+            .map_err(AppError::Internal)?,
+        todos: state.todo_store.clone(),
+        files: state.file_store.clone(),
+    };
+    let importer = Importer::new(targets, resume_after);
+
+    let report = match query.format {
+        ExportFormat::Jsonl => import_jsonl(importer, body).await?,
+        ExportFormat::Parquet => import_parquet(&state, importer, body).await?,
+    };
+
+    state.log_event(
+        &query.user_id,
+        "IMPORT_STREAM",
+        &query.user_id,
+        &format!(
+            "Imported {} records from {} ({} skipped, {} failed)",
+            report.total_imported(),
+            query.format.extension(),
+            report.skipped,
+            report.failed
+        ),
+    );
+
+    Ok(Json(StreamImportResponse {
+        success: report.failed == 0,
+        report,
+    }))
+}
+
+/// Apply one batch on the blocking pool, handing the importer back
+async fn apply_batch(
+    mut importer: Importer,
+    records: Vec<ExportRecord>,
+) -> Result<Importer, AppError> {
+    tokio::task::spawn_blocking(move || importer.apply(records).map(|()| importer))
+        .await
+        .map_err(|e| AppError::Internal(anyhow::anyhow!("Blocking task panicked: {e}")))?
+        .map_err(AppError::Internal)
+}
+
+async fn import_jsonl(mut importer: Importer, body: Body) -> Result<ImportReport, AppError> {
+    let mut stream = body.into_data_stream();
+    let mut buffer: Vec<u8> = Vec::new();
+    let mut batch = Vec::with_capacity(DEFAULT_PAGE_SIZE);
+    let mut line_number = 0usize;
+
+    loop {
+        let chunk = stream
+            .next()
+            .await
+            .transpose()
+            .map_err(|e| AppError::InvalidInput {
+                field: "body".to_string(),
+                reason: format!("failed to read request body: {e}"),
+            })?;
+        let finished = chunk.is_none();
+        if let Some(chunk) = chunk {
+            buffer.extend_from_slice(&chunk);
+        }
+
+        // Split off every complete line; the final line needs no newline
+        let mut start = 0;
+        loop {
+            let end = match buffer[start..].iter().position(|b| *b == b'\n') {
+                Some(p) => start + p,
+                None if finished && start < buffer.len() => buffer.len(),
+                None => break,
+            };
+            line_number += 1;
+            let line = buffer[start..end].trim_ascii();
+            if !line.is_empty() {
+                match serde_json::from_slice::<ExportRecord>(line) {
+                    Ok(record) => batch.push(record),
+                    Err(e) => importer.record_failure(format!("line {line_number}: {e}")),
+                }
+            }
+            start = (end + 1).min(buffer.len());
+        }
+        buffer.drain(..start);
+        if buffer.len() > MAX_IMPORT_LINE_BYTES {
+            return Err(AppError::InvalidInput {
+                field: "body".to_string(),
+                reason: format!(
+                    "line {} exceeds {MAX_IMPORT_LINE_BYTES} bytes",
+                    line_number + 1
+                ),
+            });
+        }
+
+        if batch.len() >= DEFAULT_PAGE_SIZE || (finished && !batch.is_empty()) {
+            importer = apply_batch(importer, std::mem::take(&mut batch)).await?;
+        }
+        if finished {
+            return Ok(importer.finish());
+        }
+    }
+}
+
+/// Removes a spooled upload when the import ends, however it ends
+struct SpoolFile(std::path::PathBuf);
+
+impl Drop for SpoolFile {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.0);
+    }
+}
+
+async fn import_parquet(
+    state: &AppState,
+    importer: Importer,
+    body: Body,
+) -> Result<ImportReport, AppError> {
+    use tokio::io::AsyncWriteExt;
+
+    let staging = state.base_path.join(IMPORT_STAGING_DIR);
+    tokio::fs::create_dir_all(&staging)
+        .await
+        .map_err(|e| AppError::Internal(e.into()))?;
+    let spool = SpoolFile(staging.join(format!("{}.parquet", uuid::Uuid::new_v4())));
+
+    let mut file = tokio::fs::File::create(&spool.0)
+        .await
+        .map_err(|e| AppError::Internal(e.into()))?;
+    let mut stream = body.into_data_stream();
+    while let Some(chunk) = stream.next().await {
+        let chunk = chunk.map_err(|e| AppError::InvalidInput {
+            field: "body".to_string(),
+            reason: format!("failed to read request body: {e}"),
+        })?;
+        file.write_all(&chunk)
+            .await
+            .map_err(|e| AppError::Internal(e.into()))?;
+    }
+    file.flush()
+        .await
+        .map_err(|e| AppError::Internal(e.into()))?;
+    drop(file);
+
+    tokio::task::spawn_blocking(move || {
+        let mut importer = importer;
+        let file = std::fs::File::open(&spool.0).map_err(|e| AppError::Internal(e.into()))?;
+        let mut reader = ParquetRecordReader::open(file).map_err(|e| AppError::InvalidInput {
+            field: "body".to_string(),
+            reason: format!("{e:#}"),
+        })?;
+        loop {
+            match reader.next_group() {
+                Ok(Some(records)) => importer.apply(records).map_err(AppError::Internal)?,
+                Ok(None) => break,
+                // A damaged row group leaves earlier groups applied; report and stop
+                Err(e) => {
+                    importer.record_failure(format!("{e:#}"));
+                    break;
+                }
+            }
+        }
+        Ok(importer.finish())
+    })
+    .await
+    .map_err(|e| AppError::Internal(anyhow::anyhow!("Blocking task panicked: {e}")))?
+}
@@ -1,8 +1,12 @@
 //! Facts API Handlers
 //!
 //! Handlers for semantic facts extracted from episodic memories.
+//!
+//! Facts carry a validity interval; by default only facts that currently hold
+//! are returned. Pass `as_of` to query the facts that held at another time.
 
 use axum::{extract::State, response::Json};
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
 use super::state::MultiUserMemoryManager;
@@ -23,6 +27,9 @@ pub struct FactsListRequest {
     pub user_id: String,
     #[serde(default = "facts_default_limit")]
     pub limit: usize,
+    /// Return the facts that held at this time (default: now)
+    #[serde(default)]
+    pub as_of: Option<DateTime<Utc>>,
 }
 
 /// Request for searching facts
@@ -32,6 +39,9 @@ pub struct FactsSearchRequest {
     pub query: String,
     #[serde(default = "facts_default_limit")]
     pub limit: usize,
+    /// Search the facts that held at this time (default: now)
+    #[serde(default)]
+    pub as_of: Option<DateTime<Utc>>,
 }
 
 /// Request for facts by entity
@@ -41,6 +51,9 @@ pub struct FactsByEntityRequest {
     pub entity: String,
     #[serde(default = "facts_default_limit")]
     pub limit: usize,
+    /// Return the facts that held at this time (default: now)
+    #[serde(default)]
+    pub as_of: Option<DateTime<Utc>>,
 }
 
 /// Response containing facts
@@ -64,10 +77,11 @@ pub async fn list_facts(
 
     let user_id = req.user_id.clone();
     let limit = req.limit;
+    let as_of = req.as_of.unwrap_or_else(Utc::now);
 
     let facts = tokio::task::spawn_blocking(move || {
         let memory_guard = memory.read();
-        memory_guard.get_facts(&user_id, limit)
+        memory_guard.get_facts(&user_id, as_of, limit)
     })
     .await
     .map_err(|e| AppError::Internal(anyhow::anyhow!("Blocking task panicked: {e}")))?
@@ -92,10 +106,11 @@ pub async fn search_facts(
     let user_id = req.user_id.clone();
     let query = req.query.clone();
     let limit = req.limit;
+    let as_of = req.as_of.unwrap_or_else(Utc::now);
 
     let facts = tokio::task::spawn_blocking(move || {
         let memory_guard = memory.read();
-        memory_guard.search_facts(&user_id, &query, limit)
+        memory_guard.search_facts(&user_id, &query, as_of, limit)
     })
     .await
     .map_err(|e| AppError::Internal(anyhow::anyhow!("Blocking task panicked: {e}")))?
@@ -120,10 +135,11 @@ pub async fn facts_by_entity(
     let user_id = req.user_id.clone();
     let entity = req.entity.clone();
     let limit = req.limit;
+    let as_of = req.as_of.unwrap_or_else(Utc::now);
 
     let facts = tokio::task::spawn_blocking(move || {
         let memory_guard = memory.read();
-        memory_guard.get_facts_by_entity(&user_id, &entity, limit)
+        memory_guard.get_facts_by_entity(&user_id, &entity, as_of, limit)
     })
     .await
     .map_err(|e| AppError::Internal(anyhow::anyhow!("Blocking task panicked: {e}")))?
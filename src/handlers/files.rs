@@ -13,9 +13,15 @@ use super::state::MultiUserMemoryManager;
 use super::todos::TodoQuery;
 use super::types::MemoryEvent;
 use crate::errors::{AppError, ValidationErrorExt};
-use crate::memory::{FileMemoryStats, IndexingResult, ProjectId};
+use crate::memory::file_watcher::{
+    FileChange, WatchOptions, WatchStatus, WatchTarget, DEFAULT_DEBOUNCE_MS,
+    DEFAULT_POLL_INTERVAL_MS,
+};
+use crate::memory::symbols::SymbolMatch;
+use crate::memory::{FileMemoryStats, IndexingResult, Project, ProjectId};
 use crate::validation;
-use std::sync::Arc;
+use std::sync::{Arc, Weak};
+use std::time::Duration;
 
 type AppState = Arc<MultiUserMemoryManager>;
 
@@ -23,6 +29,18 @@ fn default_search_limit() -> usize {
     10
 }
 
+fn default_debounce_ms() -> u64 {
+    DEFAULT_DEBOUNCE_MS
+}
+
+fn default_poll_interval_ms() -> u64 {
+    DEFAULT_POLL_INTERVAL_MS
+}
+
+fn default_true() -> bool {
+    true
+}
+
 /// Request for listing files
 #[derive(Debug, Deserialize)]
 pub struct ListFilesRequest {
@@ -49,12 +67,44 @@ pub struct SearchFilesRequest {
     pub limit: usize,
 }
 
+/// Request to start watching a project's codebase
+#[derive(Debug, Deserialize)]
+pub struct WatchCodebaseRequest {
+    pub user_id: String,
+    /// Defaults to the path the project was indexed from
+    #[serde(default)]
+    pub codebase_path: Option<String>,
+    /// Quiet period before a burst of changes is re-indexed
+    #[serde(default = "default_debounce_ms")]
+    pub debounce_ms: u64,
+    /// Rescan interval when polling
+    #[serde(default = "default_poll_interval_ms")]
+    pub poll_interval_ms: u64,
+    /// Poll instead of using change notifications (e.g. for network filesystems)
+    #[serde(default)]
+    pub polling: bool,
+    /// Reconcile changes made since the last index before watching
+    #[serde(default = "default_true")]
+    pub initial_sync: bool,
+}
+
+/// Response for watch operations
+#[derive(Debug, Serialize)]
+pub struct WatchListResponse {
+    pub success: bool,
+    pub watches: Vec<WatchStatus>,
+    pub total: usize,
+}
+
 /// Response for file list operations
 #[derive(Debug, Serialize)]
 pub struct FileListResponse {
     pub success: bool,
     pub files: Vec<FileMemorySummary>,
     pub total: usize,
+    /// Matching functions, types and other symbols, best first (search only)
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub symbols: Vec<SymbolMatch>,
 }
 
 /// Summary of a file memory
@@ -152,6 +202,7 @@ pub async fn list_project_files(
         success: true,
         files: summaries,
         total,
+        symbols: Vec::new(),
     }))
 }
 
@@ -272,10 +323,17 @@ pub async fn index_project_codebase(
             .map_err(AppError::Internal)?;
     }
 
-    let result = state
-        .file_store
-        .index_codebase(codebase_path, &project.id, &req.user_id, None)
-        .map_err(AppError::Internal)?;
+    // Indexing reads every file and may call the embedding backend per file
+    let file_store = state.file_store.clone();
+    let codebase_root = codebase_path.to_path_buf();
+    let project_id = project.id.clone();
+    let user_id = req.user_id.clone();
+    let result = tokio::task::spawn_blocking(move || {
+        file_store.index_codebase(&codebase_root, &project_id, &user_id, None)
+    })
+    .await
+    .map_err(|e| AppError::Internal(anyhow::anyhow!("Indexing task panicked: {e}")))?
+    .map_err(AppError::Internal)?;
 
     project.codebase_path = Some(req.codebase_path.clone());
     project.codebase_indexed = true;
@@ -319,7 +377,7 @@ pub async fn index_project_codebase(
     }))
 }
 
-/// POST /api/projects/{project_id}/files/search - Search files
+/// POST /api/projects/{project_id}/files/search - Search files and the symbols they declare
 pub async fn search_project_files(
     State(state): State<AppState>,
     Path(project_id): Path<String>,
@@ -347,6 +405,21 @@ pub async fn search_project_files(
         .list_by_project(&req.user_id, &project.id, None)
         .map_err(AppError::Internal)?;
 
+    // Symbol search embeds the query
+    let symbols = {
+        let file_store = state.file_store.clone();
+        let user_id = req.user_id.clone();
+        let project_id = project.id.clone();
+        let query = req.query.clone();
+        let limit = req.limit;
+        tokio::task::spawn_blocking(move || {
+            file_store.search_symbols(&user_id, &project_id, &query, limit)
+        })
+        .await
+        .map_err(|e| AppError::Internal(anyhow::anyhow!("Blocking task panicked: {e}")))?
+        .map_err(AppError::Internal)?
+    };
+
     let query_lower = req.query.to_lowercase();
     let matching_files: Vec<_> = all_files
         .into_iter()
@@ -385,6 +458,172 @@ pub async fn search_project_files(
         success: true,
         files: summaries,
         total,
+        symbols,
+    }))
+}
+
+fn resolve_project(state: &AppState, user_id: &str, project_id: &str) -> Result<Project, AppError> {
+    state
+        .todo_store
+        .find_project_by_name(user_id, project_id)
+        .map_err(AppError::Internal)?
+        .or_else(|| {
+            uuid::Uuid::parse_str(project_id).ok().and_then(|uuid| {
+                state
+                    .todo_store
+                    .get_project(user_id, &ProjectId(uuid))
+                    .ok()
+                    .flatten()
+            })
+        })
+        .ok_or_else(|| AppError::ProjectNotFound(project_id.to_string()))
+}
+
+/// Publish a watcher's index change to the event stream
+fn emit_file_change(state: &AppState, user_id: &str, project_id: &ProjectId, change: &FileChange) {
+    let (event_type, preview) = match change {
+        FileChange::Added { path } => ("FILE_INDEXED", path.clone()),
+        FileChange::Modified { path } => ("FILE_UPDATED", path.clone()),
+        FileChange::Removed { path } => ("FILE_REMOVED", path.clone()),
+        FileChange::Renamed { from, to } => ("FILE_RENAMED", format!("{from} -> {to}")),
+    };
+    state.emit_event(MemoryEvent {
+        event_type: event_type.to_string(),
+        timestamp: chrono::Utc::now(),
+        user_id: user_id.to_string(),
+        memory_id: Some(project_id.0.to_string()),
+        content_preview: Some(preview),
+        memory_type: Some("Codebase".to_string()),
+        importance: None,
+        count: None,
+    });
+}
+
+/// POST /api/projects/{project_id}/watch - Keep a project's file index in sync
+///
+/// Replaces any watcher the project already has. Changed files are re-indexed
+/// after `debounce_ms` of quiet; each change is published as a FILE_* event.
+pub async fn watch_project_codebase(
+    State(state): State<AppState>,
+    Path(project_id): Path<String>,
+    Json(req): Json<WatchCodebaseRequest>,
+) -> Result<Json<WatchStatus>, AppError> {
+    validation::validate_user_id(&req.user_id).map_validation_err("user_id")?;
+
+    let project = resolve_project(&state, &req.user_id, &project_id)?;
+    let codebase_path = req
+        .codebase_path
+        .clone()
+        .or_else(|| project.codebase_path.clone())
+        .ok_or_else(|| AppError::InvalidInput {
+            field: "codebase_path".to_string(),
+            reason: "Project has no indexed codebase; pass codebase_path".to_string(),
+        })?;
+    if !std::path::Path::new(&codebase_path).is_dir() {
+        return Err(AppError::InvalidInput {
+            field: "codebase_path".to_string(),
+            reason: format!("Path is not a directory: {codebase_path}"),
+        });
+    }
+    if req.debounce_ms == 0 || req.poll_interval_ms == 0 {
+        return Err(AppError::InvalidInput {
+            field: "debounce_ms".to_string(),
+            reason: "debounce_ms and poll_interval_ms must be positive".to_string(),
+        });
+    }
+
+    let target = WatchTarget {
+        user_id: req.user_id.clone(),
+        project_id: project.id.clone(),
+        root: codebase_path.into(),
+        config: state.file_store.config().clone(),
+    };
+    let options = WatchOptions {
+        debounce: Duration::from_millis(req.debounce_ms),
+        poll_interval: Duration::from_millis(req.poll_interval_ms),
+        force_polling: req.polling,
+        initial_sync: req.initial_sync,
+    };
+
+    // The watcher must not keep the manager alive past shutdown
+    let weak_state: Weak<MultiUserMemoryManager> = Arc::downgrade(&state);
+    let user_id = req.user_id.clone();
+    let watched_project = project.id.clone();
+    let on_change = Arc::new(move |change: &FileChange| {
+        if let Some(state) = weak_state.upgrade() {
+            emit_file_change(&state, &user_id, &watched_project, change);
+        }
+    });
+
+    // Starting walks the tree to register watches, and replacing a watcher joins its thread
+    let status = {
+        let state = state.clone();
+        tokio::task::spawn_blocking(move || {
+            state
+                .file_watchers
+                .start(state.file_store.clone(), target, options, on_change)
+        })
+        .await
+        .map_err(|e| AppError::Internal(anyhow::anyhow!("Blocking task panicked: {e}")))?
+        .map_err(AppError::Internal)?
+    };
+
+    state.log_event(
+        &req.user_id,
+        "CODEBASE_WATCH",
+        &status.project_id,
+        &format!("Watching {} ({:?})", status.codebase_path, status.backend),
+    );
+
+    Ok(Json(status))
+}
+
+/// DELETE /api/projects/{project_id}/watch?user_id= - Stop watching a project's codebase
+pub async fn unwatch_project_codebase(
+    State(state): State<AppState>,
+    Path(project_id): Path<String>,
+    Query(query): Query<TodoQuery>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    validation::validate_user_id(&query.user_id).map_validation_err("user_id")?;
+
+    let project = resolve_project(&state, &query.user_id, &project_id)?;
+    let stopped = {
+        let state = state.clone();
+        let user_id = query.user_id.clone();
+        let project_id = project.id.clone();
+        tokio::task::spawn_blocking(move || state.file_watchers.stop(&user_id, &project_id))
+            .await
+            .map_err(|e| AppError::Internal(anyhow::anyhow!("Blocking task panicked: {e}")))?
+    };
+    if stopped {
+        state.log_event(
+            &query.user_id,
+            "CODEBASE_UNWATCH",
+            &project.id.0.to_string(),
+            "Stopped watching codebase",
+        );
+    }
+
+    Ok(Json(serde_json::json!({
+        "success": true,
+        "project_id": project.id.0.to_string(),
+        "stopped": stopped,
+    })))
+}
+
+/// GET /api/files/watches?user_id= - List the caller's codebase watchers
+pub async fn list_codebase_watches(
+    State(state): State<AppState>,
+    Query(query): Query<TodoQuery>,
+) -> Result<Json<WatchListResponse>, AppError> {
+    validation::validate_user_id(&query.user_id).map_validation_err("user_id")?;
+
+    let watches = state.file_watchers.list(&query.user_id);
+    let total = watches.len();
+    Ok(Json(WatchListResponse {
+        success: true,
+        watches,
+        total,
     }))
 }
 
@@ -13,7 +13,12 @@ use tracing::info;
 use super::state::MultiUserMemoryManager;
 use super::types::MemoryEvent;
 use crate::errors::{AppError, ValidationErrorExt};
-use crate::graph_memory::{EntityNode, EpisodicNode, GraphStats, GraphTraversal, MemoryUniverse};
+use crate::graph_analytics::GraphAnalytics;
+use crate::graph_memory::{
+    EntityMergeRecord, EntityNode, EpisodicNode, GraphMemory, GraphStats, GraphTraversal,
+    MemoryUniverse,
+};
+use crate::graph_query::GraphQuery;
 use crate::memory::{Experience, MemoryId};
 use crate::validation;
 use std::sync::Arc;
@@ -342,3 +347,358 @@ pub async fn invalidate_relationship(
         "message": "Relationship invalidated"
     })))
 }
+
+/// Look up an entity by UUID, or by its exact name or alias (case-insensitive)
+///
+/// Fuzzy matches are not accepted: merging "York" into "New York" by accident
+/// is worse than asking the caller for the UUID.
+fn resolve_entity_ref(
+    graph: &GraphMemory,
+    field: &str,
+    value: &str,
+) -> Result<EntityNode, AppError> {
+    let entity = match uuid::Uuid::parse_str(value) {
+        Ok(uuid) => graph
+            .get_entity(&uuid)
+            .map_err(|e| AppError::Internal(anyhow::anyhow!(e)))?,
+        Err(_) => graph
+            .find_entity_by_name(value)
+            .map_err(|e| AppError::Internal(anyhow::anyhow!(e)))?
+            .filter(|entity| {
+                let value = value.to_lowercase();
+                std::iter::once(&entity.name)
+                    .chain(&entity.aliases)
+                    .any(|name| name.to_lowercase() == value)
+            }),
+    };
+    entity.ok_or_else(|| AppError::MemoryNotFound(format!("Entity not found ({field}): {value}")))
+}
+
+/// Request to merge two entities
+#[derive(Debug, Deserialize)]
+pub struct MergeEntitiesRequest {
+    pub user_id: String,
+    /// Entity to keep (name, alias or UUID)
+    pub survivor: String,
+    /// Entity folded into the survivor (name, alias or UUID)
+    pub duplicate: String,
+}
+
+/// POST /api/graph/entity/merge - Merge a duplicate entity into another
+pub async fn merge_entities(
+    State(state): State<AppState>,
+    Json(req): Json<MergeEntitiesRequest>,
+) -> Result<Json<EntityMergeRecord>, AppError> {
+    validation::validate_user_id(&req.user_id).map_validation_err("user_id")?;
+
+    let graph = state
+        .get_user_graph(&req.user_id)
+        .map_err(AppError::Internal)?;
+    let graph_guard = graph.write();
+
+    let survivor = resolve_entity_ref(&graph_guard, "survivor", &req.survivor)?;
+    let duplicate = resolve_entity_ref(&graph_guard, "duplicate", &req.duplicate)?;
+    if survivor.uuid == duplicate.uuid {
+        return Err(AppError::InvalidInput {
+            field: "duplicate".to_string(),
+            reason: format!(
+                "'{}' and '{}' are the same entity",
+                req.survivor, req.duplicate
+            ),
+        });
+    }
+
+    let record = graph_guard
+        .merge_entities(&survivor.uuid, &duplicate.uuid, None)
+        .map_err(|e| AppError::Internal(anyhow::anyhow!(e)))?;
+
+    state.emit_event(MemoryEvent {
+        event_type: "ENTITY_MERGE".to_string(),
+        timestamp: chrono::Utc::now(),
+        user_id: req.user_id.clone(),
+        memory_id: Some(record.id.to_string()),
+        content_preview: Some(format!(
+            "'{}' merged into '{}'",
+            duplicate.name, survivor.name
+        )),
+        memory_type: Some("graph".to_string()),
+        importance: None,
+        count: None,
+    });
+
+    Ok(Json(record))
+}
+
+/// Request to undo an entity merge
+#[derive(Debug, Deserialize)]
+pub struct UndoEntityMergeRequest {
+    pub user_id: String,
+    pub merge_id: String,
+}
+
+/// POST /api/graph/entity/merge/undo - Restore an entity folded in by a merge
+pub async fn undo_entity_merge(
+    State(state): State<AppState>,
+    Json(req): Json<UndoEntityMergeRequest>,
+) -> Result<Json<EntityMergeRecord>, AppError> {
+    validation::validate_user_id(&req.user_id).map_validation_err("user_id")?;
+
+    let merge_id = uuid::Uuid::parse_str(&req.merge_id).map_err(|_| AppError::InvalidInput {
+        field: "merge_id".to_string(),
+        reason: "Invalid UUID format".to_string(),
+    })?;
+
+    let graph = state
+        .get_user_graph(&req.user_id)
+        .map_err(AppError::Internal)?;
+    let graph_guard = graph.write();
+
+    if graph_guard
+        .get_entity_merge(&merge_id)
+        .map_err(|e| AppError::Internal(anyhow::anyhow!(e)))?
+        .is_none()
+    {
+        return Err(AppError::MemoryNotFound(format!(
+            "Entity merge not found: {}",
+            req.merge_id
+        )));
+    }
+
+    let record = graph_guard
+        .undo_entity_merge(&merge_id)
+        .map_err(|e| AppError::InvalidInput {
+            field: "merge_id".to_string(),
+            reason: e.to_string(),
+        })?;
+
+    state.emit_event(MemoryEvent {
+        event_type: "ENTITY_MERGE_UNDO".to_string(),
+        timestamp: chrono::Utc::now(),
+        user_id: req.user_id.clone(),
+        memory_id: Some(record.id.to_string()),
+        content_preview: Some(format!("'{}' restored", record.merged.name)),
+        memory_type: Some("graph".to_string()),
+        importance: None,
+        count: None,
+    });
+
+    Ok(Json(record))
+}
+
+/// Request to list entity merges
+#[derive(Debug, Deserialize)]
+pub struct ListEntityMergesRequest {
+    pub user_id: String,
+}
+
+/// POST /api/graph/entity/merges - List merges that can still be undone, newest first
+pub async fn list_entity_merges(
+    State(state): State<AppState>,
+    Json(req): Json<ListEntityMergesRequest>,
+) -> Result<Json<Vec<EntityMergeRecord>>, AppError> {
+    validation::validate_user_id(&req.user_id).map_validation_err("user_id")?;
+
+    let graph = state
+        .get_user_graph(&req.user_id)
+        .map_err(AppError::Internal)?;
+    let merges = graph
+        .read()
+        .list_entity_merges()
+        .map_err(|e| AppError::Internal(anyhow::anyhow!(e)))?;
+
+    Ok(Json(merges))
+}
+
+/// Request to add or remove an entity alias
+#[derive(Debug, Deserialize)]
+pub struct EntityAliasRequest {
+    pub user_id: String,
+    /// Entity name, alias or UUID
+    pub entity: String,
+    pub alias: String,
+    /// Remove the alias instead of adding it
+    #[serde(default)]
+    pub remove: bool,
+}
+
+/// POST /api/graph/entity/alias - Add (or remove) an alternative name for an entity
+pub async fn set_entity_alias(
+    State(state): State<AppState>,
+    Json(req): Json<EntityAliasRequest>,
+) -> Result<Json<EntityNode>, AppError> {
+    validation::validate_user_id(&req.user_id).map_validation_err("user_id")?;
+
+    let graph = state
+        .get_user_graph(&req.user_id)
+        .map_err(AppError::Internal)?;
+    let graph_guard = graph.write();
+
+    let entity = resolve_entity_ref(&graph_guard, "entity", &req.entity)?;
+    let updated = if req.remove {
+        graph_guard.remove_alias(&entity.uuid, &req.alias)
+    } else {
+        graph_guard.add_alias(&entity.uuid, &req.alias)
+    }
+    .map_err(|e| AppError::InvalidInput {
+        field: "alias".to_string(),
+        reason: e.to_string(),
+    })?;
+
+    Ok(Json(updated))
+}
+
+/// Request to run entity resolution
+#[derive(Debug, Deserialize)]
+pub struct ResolveEntitiesRequest {
+    pub user_id: String,
+    /// Confidence needed to merge (default: server `entity_merge_confidence`)
+    pub min_confidence: Option<f32>,
+    /// Only report candidates, merge nothing
+    #[serde(default)]
+    pub dry_run: bool,
+    /// Maximum candidates to score and report (default: 50)
+    pub limit: Option<usize>,
+}
+
+/// POST /api/graph/entity/resolve - Find duplicate entities and merge the confident ones
+pub async fn resolve_entities(
+    State(state): State<AppState>,
+    Json(req): Json<ResolveEntitiesRequest>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    validation::validate_user_id(&req.user_id).map_validation_err("user_id")?;
+
+    let graph = state
+        .get_user_graph(&req.user_id)
+        .map_err(AppError::Internal)?;
+    let graph_guard = graph.write();
+
+    let mut config = graph_guard.resolver_config();
+    if let Some(min_confidence) = req.min_confidence {
+        if !(0.0..=1.0).contains(&min_confidence) {
+            return Err(AppError::InvalidInput {
+                field: "min_confidence".to_string(),
+                reason: "must be between 0.0 and 1.0".to_string(),
+            });
+        }
+        config.min_confidence = min_confidence;
+    }
+    let limit = req.limit.unwrap_or(50).clamp(1, 1000);
+
+    let (candidates, merges) = graph_guard
+        .resolve_entities(&config, limit, req.dry_run)
+        .map_err(|e| AppError::Internal(anyhow::anyhow!(e)))?;
+
+    if !merges.is_empty() {
+        info!(
+            "Entity resolution for user {}: merged {} of {} candidates",
+            req.user_id,
+            merges.len(),
+            candidates.len()
+        );
+        state.emit_event(MemoryEvent {
+            event_type: "ENTITY_MERGE".to_string(),
+            timestamp: chrono::Utc::now(),
+            user_id: req.user_id.clone(),
+            memory_id: None,
+            content_preview: Some(format!("Resolved {} duplicate entities", merges.len())),
+            memory_type: Some("graph".to_string()),
+            importance: None,
+            count: Some(merges.len()),
+        });
+    }
+
+    Ok(Json(serde_json::json!({
+        "dry_run": req.dry_run,
+        "min_confidence": config.min_confidence,
+        "candidates": candidates,
+        "merges": merges
+    })))
+}
+
+/// Request to run a graph query
+#[derive(Debug, Deserialize)]
+pub struct GraphQueryRequest {
+    pub user_id: String,
+    /// Cypher-like query, e.g. `MATCH (p:Person)-[:WorksAt]->(o) RETURN p.name, o.name`
+    pub query: String,
+    /// Include the compiled plan in the response
+    #[serde(default)]
+    pub explain: bool,
+}
+
+/// POST /api/graph/query - Run a declarative pattern query over the knowledge graph
+pub async fn graph_query(
+    State(state): State<AppState>,
+    Json(req): Json<GraphQueryRequest>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    validation::validate_user_id(&req.user_id).map_validation_err("user_id")?;
+    if req.query.trim().is_empty() || req.query.len() > 10_000 {
+        return Err(AppError::InvalidInput {
+            field: "query".to_string(),
+            reason: "must be between 1 and 10000 characters".to_string(),
+        });
+    }
+
+    let query = GraphQuery::parse(&req.query).map_err(|e| AppError::InvalidInput {
+        field: "query".to_string(),
+        reason: e.to_string(),
+    })?;
+
+    let graph = state
+        .get_user_graph(&req.user_id)
+        .map_err(AppError::Internal)?;
+    let (query, result) = tokio::task::spawn_blocking(move || {
+        let result = query.execute(&graph.read());
+        (query, result)
+    })
+    .await
+    .map_err(|e| AppError::Internal(anyhow::anyhow!("Graph query task panicked: {e}")))?;
+    let result = result.map_err(AppError::Internal)?;
+
+    let mut response =
+        serde_json::to_value(&result).map_err(|e| AppError::Internal(anyhow::anyhow!(e)))?;
+    if req.explain {
+        response["plan"] = serde_json::json!(query.explain());
+    }
+    Ok(Json(response))
+}
+
+/// Request for graph analytics
+#[derive(Debug, Deserialize)]
+pub struct GraphAnalyticsRequest {
+    pub user_id: String,
+    /// Recompute now instead of returning the last scheduled run
+    #[serde(default)]
+    pub refresh: bool,
+}
+
+/// POST /api/graph/analytics - Central entities, topic clusters and bridge entities
+///
+/// Returns the report from the last maintenance run, computing one first if
+/// the graph has not been analyzed since the server started.
+pub async fn graph_analytics(
+    State(state): State<AppState>,
+    Json(req): Json<GraphAnalyticsRequest>,
+) -> Result<Json<GraphAnalytics>, AppError> {
+    validation::validate_user_id(&req.user_id).map_validation_err("user_id")?;
+
+    let graph = state
+        .get_user_graph(&req.user_id)
+        .map_err(AppError::Internal)?;
+    if !req.refresh {
+        if let Some(report) = graph.read().last_analytics() {
+            return Ok(Json(report));
+        }
+    }
+
+    let report = tokio::task::spawn_blocking(move || graph.read().run_analytics())
+        .await
+        .map_err(|e| AppError::Internal(anyhow::anyhow!("Graph analytics task panicked: {e}")))?
+        .map_err(AppError::Internal)?;
+
+    info!(
+        "Graph analytics for user {}: {} entities, {} communities, modularity {:.3}",
+        req.user_id, report.entity_count, report.community_count, report.modularity
+    );
+    Ok(Json(report))
+}
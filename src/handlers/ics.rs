@@ -0,0 +1,362 @@
+//! iCalendar Export/Import Handlers
+//!
+//! `.ics` files of open todos (VTODO) and timed reminders (VTODO + VALARM)
+//! for keeping calendar apps in sync offline. See [`crate::memory::ics`] for
+//! the component mapping and how UIDs are matched on re-import.
+
+use axum::{
+    body::Body,
+    extract::{Query, State},
+    http::header,
+    response::{Json, Response},
+};
+use serde::{Deserialize, Serialize};
+
+use super::state::MultiUserMemoryManager;
+use super::todos::resolve_project;
+use super::utils::default_true;
+use crate::errors::{AppError, ValidationErrorExt};
+use crate::memory::ics::{self, IcsEntry, IcsEntryKind};
+use crate::memory::{
+    ProjectId, ProspectiveTask, ProspectiveTaskId, ProspectiveTaskStatus, ProspectiveTrigger, Todo,
+    TodoId, TodoStatus,
+};
+use crate::validation;
+
+/// Application state type alias
+pub type AppState = std::sync::Arc<MultiUserMemoryManager>;
+
+/// Tag that ties a reminder to the UID of a foreign calendar item
+fn reminder_uid_tag(uid: &str) -> String {
+    format!("ics:{uid}")
+}
+
+// =============================================================================
+// TYPES
+// =============================================================================
+
+/// Request for an iCalendar export
+#[derive(Debug, Deserialize)]
+pub struct IcsExportRequest {
+    pub user_id: String,
+    /// Only todos in this project (name or ID)
+    #[serde(default)]
+    pub project: Option<String>,
+    /// Also export open todos without a due date
+    #[serde(default)]
+    pub include_undated: bool,
+    /// Export pending timed reminders (default: true)
+    #[serde(default = "default_true")]
+    pub include_reminders: bool,
+}
+
+/// Query parameters of an iCalendar import (the body is the .ics file)
+#[derive(Debug, Deserialize)]
+pub struct IcsImportQuery {
+    pub user_id: String,
+    /// Project for newly created todos
+    #[serde(default)]
+    pub project: Option<String>,
+}
+
+/// Response for an iCalendar import
+#[derive(Debug, Default, Serialize)]
+pub struct IcsImportResponse {
+    pub success: bool,
+    pub todos_created: usize,
+    pub todos_updated: usize,
+    pub reminders_created: usize,
+    pub reminders_updated: usize,
+    /// Components that were not imported, with the reason
+    pub skipped: Vec<String>,
+}
+
+// =============================================================================
+// HANDLERS
+// =============================================================================
+
+/// POST /api/export/ics - Export open todos and pending reminders as .ics
+pub async fn export_ics(
+    State(state): State<AppState>,
+    Json(req): Json<IcsExportRequest>,
+) -> Result<Response, AppError> {
+    validation::validate_user_id(&req.user_id).map_validation_err("user_id")?;
+
+    let project_id = req
+        .project
+        .as_deref()
+        .map(|p| resolve_project(&state, &req.user_id, p).map(|p| p.id))
+        .transpose()?;
+
+    let open = [
+        TodoStatus::Backlog,
+        TodoStatus::Todo,
+        TodoStatus::InProgress,
+        TodoStatus::Blocked,
+    ];
+    let todos: Vec<Todo> = state
+        .todo_store
+        .list_todos_for_user(&req.user_id, Some(&open))
+        .map_err(AppError::Internal)?
+        .into_iter()
+        .filter(|t| project_id.is_none() || t.project_id == project_id)
+        .filter(|t| req.include_undated || t.due_date.is_some())
+        .collect();
+
+    let reminders = if req.include_reminders {
+        state
+            .prospective_store
+            .list_for_user(&req.user_id, Some(ProspectiveTaskStatus::Pending))
+            .map_err(AppError::Internal)?
+    } else {
+        Vec::new()
+    };
+
+    let calendar = ics::export_calendar(&todos, &reminders);
+
+    state.log_event(
+        &req.user_id,
+        "ICS_EXPORT",
+        &req.user_id,
+        &format!(
+            "Exported {} todos and {} reminders as iCalendar",
+            todos.len(),
+            reminders
+                .iter()
+                .filter(|r| r.trigger.due_at().is_some())
+                .count()
+        ),
+    );
+
+    Response::builder()
+        .header(header::CONTENT_TYPE, "text/calendar; charset=utf-8")
+        .header(
+            header::CONTENT_DISPOSITION,
+            format!("attachment; filename=\"{}-todos.ics\"", req.user_id),
+        )
+        .body(Body::from(calendar))
+        .map_err(|e| AppError::Internal(anyhow::anyhow!("Failed to build response: {e}")))
+}
+
+/// POST /api/import/ics - Import VTODOs and VEVENTs from an .ics file
+///
+/// Items exported by this server update the originals; other UIDs create
+/// new todos/reminders on first import and update them afterwards.
+pub async fn import_ics(
+    State(state): State<AppState>,
+    Query(query): Query<IcsImportQuery>,
+    body: String,
+) -> Result<Json<IcsImportResponse>, AppError> {
+    validation::validate_user_id(&query.user_id).map_validation_err("user_id")?;
+
+    let parsed = ics::parse_calendar(&body).map_err(|e| AppError::InvalidInput {
+        field: "body".to_string(),
+        reason: e.to_string(),
+    })?;
+
+    let project_id = match query.project.as_deref() {
+        Some(name) => Some(
+            state
+                .todo_store
+                .find_or_create_project(&query.user_id, name)
+                .map_err(AppError::Internal)?
+                .id,
+        ),
+        None => None,
+    };
+
+    let state_clone = state.clone();
+    let user_id = query.user_id.clone();
+    let mut response = tokio::task::spawn_blocking(move || {
+        let mut response = IcsImportResponse {
+            skipped: parsed.skipped,
+            ..Default::default()
+        };
+        for entry in parsed.entries {
+            let uid = entry.uid.clone();
+            let kind = entry.kind;
+            let result = match kind {
+                IcsEntryKind::Todo => {
+                    import_todo_entry(&state_clone, &user_id, project_id.as_ref(), entry)
+                }
+                IcsEntryKind::Reminder => import_reminder_entry(&state_clone, &user_id, entry),
+            };
+            match (kind, result) {
+                (IcsEntryKind::Todo, Ok(true)) => response.todos_created += 1,
+                (IcsEntryKind::Todo, Ok(false)) => response.todos_updated += 1,
+                (IcsEntryKind::Reminder, Ok(true)) => response.reminders_created += 1,
+                (IcsEntryKind::Reminder, Ok(false)) => response.reminders_updated += 1,
+                (_, Err(e)) => response.skipped.push(format!("{uid}: {e:#}")),
+            }
+        }
+        response
+    })
+    .await
+    .map_err(|e| AppError::Internal(anyhow::anyhow!("Blocking task panicked: {e}")))?;
+    response.success = response.skipped.is_empty();
+
+    state.log_event(
+        &query.user_id,
+        "ICS_IMPORT",
+        &query.user_id,
+        &format!(
+            "Imported iCalendar: {} todos created, {} updated; {} reminders created, {} updated; {} skipped",
+            response.todos_created,
+            response.todos_updated,
+            response.reminders_created,
+            response.reminders_updated,
+            response.skipped.len()
+        ),
+    );
+
+    Ok(Json(response))
+}
+
+// =============================================================================
+// IMPORT HELPERS
+// =============================================================================
+
+/// Create or update the todo for a VTODO. Returns true when created.
+fn import_todo_entry(
+    state: &AppState,
+    user_id: &str,
+    project_id: Option<&ProjectId>,
+    entry: IcsEntry,
+) -> anyhow::Result<bool> {
+    let store = &state.todo_store;
+    let existing = match ics::parse_export_uid(&entry.uid) {
+        Some(uuid) => store.get_todo(user_id, &TodoId(uuid))?,
+        None => None,
+    };
+    let existing = match existing {
+        Some(todo) => Some(todo),
+        None => store.find_by_external_id(user_id, &entry.uid)?,
+    };
+
+    let Some(mut todo) = existing else {
+        let mut todo = Todo::new(user_id.to_string(), entry.summary.clone());
+        todo.project_id = project_id.cloned();
+        todo.external_id = Some(entry.uid.clone());
+        apply_todo_entry(&mut todo, entry);
+        if todo.status.is_finished() {
+            todo.completed_at = Some(chrono::Utc::now());
+        }
+        store.import_todo(todo)?;
+        return Ok(true);
+    };
+
+    let was_finished = todo.status.is_finished();
+    let old_text = todo.embedding_text();
+    apply_todo_entry(&mut todo, entry);
+    if !was_finished && todo.status.is_finished() {
+        todo.completed_at = Some(chrono::Utc::now());
+    }
+    todo.updated_at = chrono::Utc::now();
+
+    if todo.embedding_text() != old_text {
+        if let Ok(memory) = state.get_user_memory(user_id) {
+            if let Ok(embedding) = memory.read().compute_embedding(&todo.embedding_text()) {
+                let vector_id = store.index_todo_embedding(user_id, &todo.id, &embedding)?;
+                store.store_vector_id_mapping(user_id, vector_id, &todo.id)?;
+                todo.embedding = Some(embedding);
+            }
+        }
+    }
+
+    store.update_todo(&todo)?;
+    if !was_finished && todo.status.is_finished() {
+        store.unblock_dependents(user_id, &todo)?;
+    }
+    Ok(false)
+}
+
+/// Copy the calendar-owned fields of a VTODO onto a todo
+fn apply_todo_entry(todo: &mut Todo, entry: IcsEntry) {
+    todo.content = entry.summary;
+    if entry.description.is_some() {
+        todo.notes = entry.description;
+    }
+    todo.due_date = entry.due;
+    todo.recurrence = entry.recurrence;
+    if let Some(status) = entry.status {
+        // NEEDS-ACTION doesn't distinguish backlog or blocked todos
+        let keep = status == TodoStatus::Todo
+            && matches!(todo.status, TodoStatus::Backlog | TodoStatus::Blocked);
+        if !keep {
+            todo.status = status;
+        }
+    }
+    if let Some(priority) = entry.priority {
+        todo.priority = ics::todo_priority_from_ical(priority);
+    }
+    if !entry.categories.is_empty() {
+        todo.tags = entry.categories;
+    }
+}
+
+/// Create or update the reminder for a VEVENT or reminder VTODO.
+/// Returns true when created.
+fn import_reminder_entry(state: &AppState, user_id: &str, entry: IcsEntry) -> anyhow::Result<bool> {
+    let store = &state.prospective_store;
+    let trigger = match (entry.recurrence, entry.due) {
+        (Some(recurrence), _) => {
+            let now = chrono::Utc::now();
+            let at = recurrence
+                .next_after(now - chrono::Duration::seconds(1))
+                .ok_or_else(|| anyhow::anyhow!("recurrence has no upcoming occurrences"))?;
+            ProspectiveTrigger::Recurring { at, recurrence }
+        }
+        (None, Some(at)) => {
+            validation::validate_reminder_timestamp(&at)?;
+            ProspectiveTrigger::AtTime { at }
+        }
+        (None, None) => anyhow::bail!("no start, due or alarm time"),
+    };
+
+    let own = match ics::parse_export_uid(&entry.uid) {
+        Some(uuid) => store.get(user_id, &ProspectiveTaskId(uuid))?,
+        None => None,
+    };
+    let uid_tag = reminder_uid_tag(&entry.uid);
+    let existing = match own {
+        Some(task) => Some(task),
+        None => store
+            .list_for_user(user_id, None)?
+            .into_iter()
+            .find(|t| t.tags.contains(&uid_tag)),
+    };
+
+    let priority = entry.priority.map(ics::reminder_priority_from_ical);
+    let mut tags = entry.categories;
+
+    match existing {
+        Some(mut task) => {
+            if task.trigger.due_at() != trigger.due_at() {
+                task.status = ProspectiveTaskStatus::Pending;
+            }
+            task.content = entry.summary;
+            task.trigger = trigger;
+            if let Some(priority) = priority {
+                task.priority = priority;
+            }
+            if !tags.is_empty() {
+                if task.tags.contains(&uid_tag) {
+                    tags.push(uid_tag);
+                }
+                task.tags = tags;
+            }
+            store.update(&task)?;
+            Ok(false)
+        }
+        None => {
+            let mut task = ProspectiveTask::new(user_id.to_string(), entry.summary, trigger);
+            tags.push(uid_tag);
+            task.tags = tags;
+            if let Some(priority) = priority {
+                task.priority = priority;
+            }
+            store.store(&task)?;
+            Ok(true)
+        }
+    }
+}
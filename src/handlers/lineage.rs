@@ -2,13 +2,18 @@
 //!
 //! Handlers for tracing decision lineage and causal relationships between memories.
 
-use axum::{extract::State, response::Json};
+use axum::{
+    extract::{Path, State},
+    response::Json,
+};
 use serde::{Deserialize, Serialize};
 
 use super::state::MultiUserMemoryManager;
+use super::todos::resolve_project;
 use crate::errors::{AppError, ValidationErrorExt};
 use crate::memory::{
-    CausalRelation, LineageBranch, LineageEdge, LineageStats, MemoryId, TraceDirection,
+    CausalRelation, LineageBranch, LineageEdge, LineageStats, MemoryId, PostMortem,
+    PostMortemSubject, Project, ProjectStatus, Todo, TodoStatus, TraceDirection,
 };
 use crate::validation;
 use std::sync::Arc;
@@ -27,6 +32,10 @@ fn lineage_list_limit_default() -> usize {
     50
 }
 
+fn post_mortem_format_default() -> String {
+    "json".to_string()
+}
+
 /// Request to trace lineage from a memory
 #[derive(Debug, Deserialize)]
 pub struct LineageTraceRequest {
@@ -72,6 +81,18 @@ pub struct LineageCreateBranchRequest {
     pub description: Option<String>,
 }
 
+/// Request to fetch or regenerate a post-mortem
+#[derive(Debug, Deserialize)]
+pub struct PostMortemRequest {
+    pub user_id: String,
+    /// "json" (default) or "markdown"
+    #[serde(default = "post_mortem_format_default")]
+    pub format: String,
+    /// Rebuild from the current lineage instead of returning the stored post-mortem
+    #[serde(default)]
+    pub regenerate: bool,
+}
+
 /// Response for lineage trace
 #[derive(Debug, Serialize)]
 pub struct LineageTraceResponse {
@@ -96,6 +117,88 @@ pub struct LineageBranchesResponse {
     pub total: usize,
 }
 
+/// Response for a post-mortem; exactly one of `post_mortem` and `markdown` is set
+#[derive(Debug, Serialize)]
+pub struct PostMortemResponse {
+    pub subject_id: String,
+    /// "todo" or "project"
+    pub subject_type: String,
+    /// Whether the post-mortem was generated by this request
+    pub regenerated: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub post_mortem: Option<PostMortem>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub markdown: Option<String>,
+}
+
+// =============================================================================
+// POST-MORTEMS
+// =============================================================================
+
+/// Post-mortem subject for a todo: its linked memories and those tagged with its short ID
+pub(crate) fn todo_post_mortem_subject(todo: &Todo) -> PostMortemSubject {
+    PostMortemSubject {
+        id: MemoryId(todo.id.0),
+        content: format!("[{}] {}", todo.short_id(), todo.content),
+        seeds: todo.related_memory_ids.clone(),
+        seed_tags: vec![format!("todo:{}", todo.short_id())],
+    }
+}
+
+/// Post-mortem subject for a project, seeded from the project and all of its todos
+pub(crate) fn project_post_mortem_subject(
+    state: &AppState,
+    user_id: &str,
+    project: &Project,
+) -> Result<PostMortemSubject, AppError> {
+    let todos = state
+        .todo_store
+        .list_todos_by_project(user_id, &project.id)
+        .map_err(AppError::Internal)?;
+
+    let mut seeds = project.related_memory_ids.clone();
+    let mut seed_tags = vec![format!("project:{}", project.name)];
+    for todo in &todos {
+        seeds.extend(todo.related_memory_ids.iter().cloned());
+        seed_tags.push(format!("todo:{}", todo.short_id()));
+    }
+
+    Ok(PostMortemSubject {
+        id: MemoryId(project.id.0),
+        content: format!("Project {}", project.name),
+        seeds,
+        seed_tags,
+    })
+}
+
+/// Generate and store a post-mortem, logging instead of failing
+///
+/// Used when a todo or project is finished; the completion itself must not
+/// fail because the post-mortem could not be written.
+pub(crate) async fn run_post_mortem(state: AppState, user_id: String, subject: PostMortemSubject) {
+    let subject_id = subject.id.0;
+    let result = tokio::task::spawn_blocking(move || {
+        let memory = state.get_user_memory(&user_id)?;
+        let memory_guard = memory.read();
+        memory_guard.generate_post_mortem(&user_id, &subject)
+    })
+    .await;
+
+    match result {
+        Ok(Ok(Some(post_mortem))) => tracing::debug!(
+            subject_id = %subject_id,
+            related = post_mortem.related_memories.len(),
+            "Stored post-mortem"
+        ),
+        Ok(Ok(None)) => tracing::debug!(
+            subject_id = %subject_id,
+            "No memories linked, skipped post-mortem"
+        ),
+        Ok(Err(e)) => tracing::warn!(subject_id = %subject_id, "Post-mortem failed: {e}"),
+        Err(e) => tracing::warn!(subject_id = %subject_id, "Post-mortem task panicked: {e}"),
+    }
+}
+
 /// POST /api/lineage/trace - Trace lineage from a memory
 #[tracing::instrument(skip(state), fields(user_id = %req.user_id, memory_id = %req.memory_id))]
 pub async fn lineage_trace(
@@ -356,3 +459,103 @@ pub async fn lineage_create_branch(
 
     Ok(Json(branch))
 }
+
+/// POST /api/lineage/postmortem/{subject_id} - Fetch or regenerate a post-mortem
+///
+/// `subject_id` is a todo (UUID or short ID such as BOLT-1) or a project
+/// (name or UUID). The stored post-mortem is returned unless `regenerate` is
+/// set; one is generated on first request for a finished todo or project.
+#[tracing::instrument(skip(state), fields(user_id = %req.user_id, subject_id = %subject_id))]
+pub async fn lineage_post_mortem(
+    State(state): State<AppState>,
+    Path(subject_id): Path<String>,
+    Json(req): Json<PostMortemRequest>,
+) -> Result<Json<PostMortemResponse>, AppError> {
+    validation::validate_user_id(&req.user_id).map_validation_err("user_id")?;
+
+    let as_markdown = match req.format.to_lowercase().as_str() {
+        "json" => false,
+        "markdown" | "md" => true,
+        other => {
+            return Err(AppError::InvalidInput {
+                field: "format".to_string(),
+                reason: format!("Unknown format '{other}', expected 'json' or 'markdown'"),
+            })
+        }
+    };
+
+    let todo = state
+        .todo_store
+        .find_todo_by_prefix(&req.user_id, &subject_id)
+        .map_err(AppError::Internal)?;
+    let (subject_type, finished, subject) = match todo {
+        Some(todo) => (
+            "todo",
+            todo.status == TodoStatus::Done,
+            todo_post_mortem_subject(&todo),
+        ),
+        None => {
+            let project = resolve_project(&state, &req.user_id, &subject_id)?;
+            (
+                "project",
+                project.status == ProjectStatus::Completed,
+                project_post_mortem_subject(&state, &req.user_id, &project)?,
+            )
+        }
+    };
+
+    let memory = state
+        .get_user_memory(&req.user_id)
+        .map_err(AppError::Internal)?;
+
+    let user_id = req.user_id.clone();
+    let regenerate = req.regenerate;
+    let subject_uuid = subject.id.0;
+
+    let (post_mortem, regenerated) = tokio::task::spawn_blocking(move || {
+        let memory_guard = memory.read();
+        if !regenerate {
+            if let Some(stored) = memory_guard
+                .lineage_graph()
+                .get_post_mortem(&user_id, &subject_uuid)?
+            {
+                return Ok((Some(stored), false));
+            }
+        }
+        if !finished {
+            return Ok((None, false));
+        }
+        memory_guard
+            .generate_post_mortem(&user_id, &subject)
+            .map(|p| (p, true))
+    })
+    .await
+    .map_err(|e| AppError::Internal(anyhow::anyhow!("Blocking task panicked: {e}")))?
+    .map_err(AppError::Internal)?;
+
+    let post_mortem = post_mortem.ok_or_else(|| AppError::InvalidInput {
+        field: "subject_id".to_string(),
+        reason: if finished {
+            format!("No memories are linked to {subject_type} '{subject_id}'")
+        } else {
+            format!("The {subject_type} '{subject_id}' is not finished yet")
+        },
+    })?;
+
+    if regenerated {
+        tracing::info!(
+            user_id = %req.user_id,
+            subject_id = %subject_uuid,
+            related = post_mortem.related_memories.len(),
+            "Generated post-mortem"
+        );
+    }
+
+    Ok(Json(PostMortemResponse {
+        subject_id: subject_uuid.to_string(),
+        subject_type: subject_type.to_string(),
+        regenerated,
+        markdown: as_markdown.then(|| post_mortem.to_markdown()),
+        post_mortem: (!as_markdown).then_some(post_mortem),
+    }))
+}
@@ -0,0 +1,794 @@
+//! MCP over HTTP - Streamable HTTP and legacy SSE transports
+//!
+//! Serves the same tool set as the stdio `shodh` binary directly from the
+//! memory server. Both build each tool's REST call from
+//! [`crate::mcp_params`]; here it runs through the API routes in-process
+//! instead of being proxied over HTTP.
+//!
+//! - `POST/GET/DELETE /mcp`  - Streamable HTTP (MCP 2025-03-26)
+//! - `GET /mcp/sse`          - Legacy HTTP+SSE stream (MCP 2024-11-05)
+//! - `POST /mcp/message`     - Legacy client → server messages
+//!
+//! All three sit behind the API-key middleware. Each tool call is checked
+//! against the role its REST equivalent requires, and acts for the user given
+//! by `?user_id=` on the MCP URL, the `X-User-Id` header, or - for keys scoped
+//! to exactly one user - that user.
+
+use axum::{
+    body::Body,
+    extract::{Query, Request, State},
+    http::{header, request::Parts, Method, StatusCode},
+    response::{
+        sse::{Event, KeepAlive, Sse},
+        IntoResponse, Response,
+    },
+    Router,
+};
+use futures::{channel::mpsc, SinkExt, StreamExt};
+use parking_lot::Mutex;
+use rmcp::{
+    handler::server::{router::tool::ToolRouter, wrapper::Parameters},
+    model::{
+        CallToolResult, ClientJsonRpcMessage, ErrorCode, Extensions, GetExtensions, Implementation,
+        ProtocolVersion, ServerCapabilities, ServerInfo,
+    },
+    tool, tool_handler, tool_router,
+    transport::streamable_http_server::{
+        session::local::LocalSessionManager, StreamableHttpServerConfig, StreamableHttpService,
+    },
+    ErrorData as McpError, ServerHandler, ServiceExt,
+};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::convert::Infallible;
+use std::sync::{Arc, LazyLock};
+use tower::ServiceExt as _;
+
+use super::router;
+use super::state::MultiUserMemoryManager;
+use crate::auth::{self, AuthContext, AuthError};
+use crate::mcp_params::*;
+
+/// Application state type alias
+pub type AppState = std::sync::Arc<MultiUserMemoryManager>;
+
+/// Max body size accepted on the legacy SSE message endpoint
+const MAX_LEGACY_MESSAGE_BYTES: usize = 4 * 1024 * 1024;
+
+// =============================================================================
+// MCP SERVER
+// =============================================================================
+
+/// MCP tool server backed directly by the [`MultiUserMemoryManager`]
+///
+/// Tool calls run through the protected API routes in-process, so each tool
+/// behaves exactly like the REST call the stdio `shodh` binary makes for it.
+#[derive(Clone)]
+pub struct MemoryMcpServer {
+    api: Router,
+    tool_router: ToolRouter<Self>,
+}
+
+#[tool_router]
+impl MemoryMcpServer {
+    pub fn new(state: AppState) -> Self {
+        Self {
+            api: router::build_protected_routes(state),
+            tool_router: describe_tools(Self::tool_router()),
+        }
+    }
+
+    #[tool]
+    async fn remember(
+        &self,
+        Parameters(params): Parameters<RememberParams>,
+        extensions: Extensions,
+    ) -> Result<CallToolResult, McpError> {
+        self.run(remember_call(&params), &extensions).await
+    }
+
+    #[tool]
+    async fn recall(
+        &self,
+        Parameters(params): Parameters<RecallParams>,
+        extensions: Extensions,
+    ) -> Result<CallToolResult, McpError> {
+        self.run(recall_call(&params), &extensions).await
+    }
+
+    #[tool]
+    async fn proactive_context(
+        &self,
+        Parameters(params): Parameters<ProactiveContextParams>,
+        extensions: Extensions,
+    ) -> Result<CallToolResult, McpError> {
+        self.run(proactive_context_call(&params), &extensions).await
+    }
+
+    // =========================================================================
+    // LINEAGE TOOLS - Causal Memory Tracking
+    // =========================================================================
+
+    #[tool]
+    async fn lineage_trace(
+        &self,
+        Parameters(params): Parameters<LineageTraceParams>,
+        extensions: Extensions,
+    ) -> Result<CallToolResult, McpError> {
+        self.run(lineage_trace_call(&params), &extensions).await
+    }
+
+    #[tool]
+    async fn lineage_confirm(
+        &self,
+        Parameters(params): Parameters<LineageConfirmParams>,
+        extensions: Extensions,
+    ) -> Result<CallToolResult, McpError> {
+        self.run(lineage_confirm_call(&params), &extensions).await
+    }
+
+    #[tool]
+    async fn lineage_reject(
+        &self,
+        Parameters(params): Parameters<LineageRejectParams>,
+        extensions: Extensions,
+    ) -> Result<CallToolResult, McpError> {
+        self.run(lineage_reject_call(&params), &extensions).await
+    }
+
+    #[tool]
+    async fn lineage_link(
+        &self,
+        Parameters(params): Parameters<LineageLinkParams>,
+        extensions: Extensions,
+    ) -> Result<CallToolResult, McpError> {
+        self.run(lineage_link_call(&params), &extensions).await
+    }
+
+    #[tool]
+    async fn lineage_stats(
+        &self,
+        Parameters(params): Parameters<LineageStatsParams>,
+        extensions: Extensions,
+    ) -> Result<CallToolResult, McpError> {
+        self.run(lineage_stats_call(&params), &extensions).await
+    }
+
+    #[tool]
+    async fn post_mortem(
+        &self,
+        Parameters(params): Parameters<PostMortemParams>,
+        extensions: Extensions,
+    ) -> Result<CallToolResult, McpError> {
+        self.run(post_mortem_call(&params), &extensions).await
+    }
+
+    // =========================================================================
+    // TODO / PROJECT TOOLS
+    // =========================================================================
+
+    #[tool]
+    async fn list_todos(
+        &self,
+        Parameters(params): Parameters<ListTodosParams>,
+        extensions: Extensions,
+    ) -> Result<CallToolResult, McpError> {
+        self.run(list_todos_call(&params), &extensions).await
+    }
+
+    #[tool]
+    async fn add_todo(
+        &self,
+        Parameters(params): Parameters<AddTodoParams>,
+        extensions: Extensions,
+    ) -> Result<CallToolResult, McpError> {
+        self.run(add_todo_call(&params), &extensions).await
+    }
+
+    #[tool]
+    async fn update_todo(
+        &self,
+        Parameters(params): Parameters<UpdateTodoParams>,
+        extensions: Extensions,
+    ) -> Result<CallToolResult, McpError> {
+        self.run(update_todo_call(&params), &extensions).await
+    }
+
+    #[tool]
+    async fn complete_todo(
+        &self,
+        Parameters(params): Parameters<TodoIdParams>,
+        extensions: Extensions,
+    ) -> Result<CallToolResult, McpError> {
+        self.run(complete_todo_call(&params), &extensions).await
+    }
+
+    #[tool]
+    async fn delete_todo(
+        &self,
+        Parameters(params): Parameters<TodoIdParams>,
+        extensions: Extensions,
+    ) -> Result<CallToolResult, McpError> {
+        self.run(delete_todo_call(&params), &extensions).await
+    }
+
+    #[tool]
+    async fn get_todo(
+        &self,
+        Parameters(params): Parameters<TodoIdParams>,
+        extensions: Extensions,
+    ) -> Result<CallToolResult, McpError> {
+        self.run(get_todo_call(&params), &extensions).await
+    }
+
+    #[tool]
+    async fn due_todos(
+        &self,
+        Parameters(params): Parameters<DueTodosParams>,
+        extensions: Extensions,
+    ) -> Result<CallToolResult, McpError> {
+        self.run(due_todos_call(&params), &extensions).await
+    }
+
+    #[tool]
+    async fn next_todos(
+        &self,
+        Parameters(params): Parameters<NextTodosParams>,
+        extensions: Extensions,
+    ) -> Result<CallToolResult, McpError> {
+        self.run(next_todos_call(&params), &extensions).await
+    }
+
+    #[tool]
+    async fn todo_stats(
+        &self,
+        Parameters(params): Parameters<EmptyParams>,
+        extensions: Extensions,
+    ) -> Result<CallToolResult, McpError> {
+        self.run(todo_stats_call(&params), &extensions).await
+    }
+
+    #[tool]
+    async fn list_projects(
+        &self,
+        Parameters(params): Parameters<EmptyParams>,
+        extensions: Extensions,
+    ) -> Result<CallToolResult, McpError> {
+        self.run(list_projects_call(&params), &extensions).await
+    }
+
+    #[tool]
+    async fn add_project(
+        &self,
+        Parameters(params): Parameters<AddProjectParams>,
+        extensions: Extensions,
+    ) -> Result<CallToolResult, McpError> {
+        self.run(add_project_call(&params), &extensions).await
+    }
+
+    #[tool]
+    async fn get_project(
+        &self,
+        Parameters(params): Parameters<ProjectParams>,
+        extensions: Extensions,
+    ) -> Result<CallToolResult, McpError> {
+        self.run(get_project_call(&params), &extensions).await
+    }
+
+    #[tool]
+    async fn project_dependencies(
+        &self,
+        Parameters(params): Parameters<ProjectParams>,
+        extensions: Extensions,
+    ) -> Result<CallToolResult, McpError> {
+        self.run(project_dependencies_call(&params), &extensions)
+            .await
+    }
+
+    #[tool]
+    async fn project_critical_path(
+        &self,
+        Parameters(params): Parameters<ProjectParams>,
+        extensions: Extensions,
+    ) -> Result<CallToolResult, McpError> {
+        self.run(project_critical_path_call(&params), &extensions)
+            .await
+    }
+
+    #[tool]
+    async fn update_project(
+        &self,
+        Parameters(params): Parameters<UpdateProjectParams>,
+        extensions: Extensions,
+    ) -> Result<CallToolResult, McpError> {
+        self.run(update_project_call(&params), &extensions).await
+    }
+
+    #[tool]
+    async fn delete_project(
+        &self,
+        Parameters(params): Parameters<DeleteProjectParams>,
+        extensions: Extensions,
+    ) -> Result<CallToolResult, McpError> {
+        self.run(delete_project_call(&params), &extensions).await
+    }
+
+    // =========================================================================
+    // REMINDER TOOLS
+    // =========================================================================
+
+    #[tool]
+    async fn set_reminder(
+        &self,
+        Parameters(params): Parameters<SetReminderParams>,
+        extensions: Extensions,
+    ) -> Result<CallToolResult, McpError> {
+        self.run(set_reminder_call(&params), &extensions).await
+    }
+
+    #[tool]
+    async fn list_reminders(
+        &self,
+        Parameters(params): Parameters<ListRemindersParams>,
+        extensions: Extensions,
+    ) -> Result<CallToolResult, McpError> {
+        self.run(list_reminders_call(&params), &extensions).await
+    }
+
+    #[tool]
+    async fn due_reminders(
+        &self,
+        Parameters(params): Parameters<DueRemindersParams>,
+        extensions: Extensions,
+    ) -> Result<CallToolResult, McpError> {
+        self.run(due_reminders_call(&params), &extensions).await
+    }
+
+    #[tool]
+    async fn check_reminders(
+        &self,
+        Parameters(params): Parameters<CheckRemindersParams>,
+        extensions: Extensions,
+    ) -> Result<CallToolResult, McpError> {
+        self.run(check_reminders_call(&params), &extensions).await
+    }
+
+    #[tool]
+    async fn dismiss_reminder(
+        &self,
+        Parameters(params): Parameters<ReminderIdParams>,
+        extensions: Extensions,
+    ) -> Result<CallToolResult, McpError> {
+        self.run(dismiss_reminder_call(&params), &extensions).await
+    }
+
+    #[tool]
+    async fn delete_reminder(
+        &self,
+        Parameters(params): Parameters<ReminderIdParams>,
+        extensions: Extensions,
+    ) -> Result<CallToolResult, McpError> {
+        self.run(delete_reminder_call(&params), &extensions).await
+    }
+
+    // =========================================================================
+    // FACTS / GRAPH TOOLS
+    // =========================================================================
+
+    #[tool]
+    async fn facts_list(
+        &self,
+        Parameters(params): Parameters<FactsListParams>,
+        extensions: Extensions,
+    ) -> Result<CallToolResult, McpError> {
+        self.run(facts_list_call(&params), &extensions).await
+    }
+
+    #[tool]
+    async fn facts_search(
+        &self,
+        Parameters(params): Parameters<FactsSearchParams>,
+        extensions: Extensions,
+    ) -> Result<CallToolResult, McpError> {
+        self.run(facts_search_call(&params), &extensions).await
+    }
+
+    #[tool]
+    async fn facts_by_entity(
+        &self,
+        Parameters(params): Parameters<FactsByEntityParams>,
+        extensions: Extensions,
+    ) -> Result<CallToolResult, McpError> {
+        self.run(facts_by_entity_call(&params), &extensions).await
+    }
+
+    #[tool]
+    async fn facts_stats(
+        &self,
+        Parameters(params): Parameters<EmptyParams>,
+        extensions: Extensions,
+    ) -> Result<CallToolResult, McpError> {
+        self.run(facts_stats_call(&params), &extensions).await
+    }
+
+    #[tool]
+    async fn find_entity(
+        &self,
+        Parameters(params): Parameters<FindEntityParams>,
+        extensions: Extensions,
+    ) -> Result<CallToolResult, McpError> {
+        self.run(find_entity_call(&params), &extensions).await
+    }
+
+    #[tool]
+    async fn graph_traverse(
+        &self,
+        Parameters(params): Parameters<GraphTraverseParams>,
+        extensions: Extensions,
+    ) -> Result<CallToolResult, McpError> {
+        self.run(graph_traverse_call(&params), &extensions).await
+    }
+
+    #[tool]
+    async fn merge_entities(
+        &self,
+        Parameters(params): Parameters<MergeEntitiesParams>,
+        extensions: Extensions,
+    ) -> Result<CallToolResult, McpError> {
+        self.run(merge_entities_call(&params), &extensions).await
+    }
+
+    #[tool]
+    async fn graph_query(
+        &self,
+        Parameters(params): Parameters<GraphQueryParams>,
+        extensions: Extensions,
+    ) -> Result<CallToolResult, McpError> {
+        self.run(graph_query_call(&params), &extensions).await
+    }
+
+    // =========================================================================
+    // FORGET / CONSOLIDATION TOOLS
+    // =========================================================================
+
+    #[tool]
+    async fn forget(
+        &self,
+        Parameters(params): Parameters<ForgetParams>,
+        extensions: Extensions,
+    ) -> Result<CallToolResult, McpError> {
+        self.run(forget_call(&params), &extensions).await
+    }
+
+    #[tool]
+    async fn forget_by_age(
+        &self,
+        Parameters(params): Parameters<ForgetByAgeParams>,
+        extensions: Extensions,
+    ) -> Result<CallToolResult, McpError> {
+        self.run(forget_by_age_call(&params), &extensions).await
+    }
+
+    #[tool]
+    async fn forget_by_importance(
+        &self,
+        Parameters(params): Parameters<ForgetByImportanceParams>,
+        extensions: Extensions,
+    ) -> Result<CallToolResult, McpError> {
+        self.run(forget_by_importance_call(&params), &extensions)
+            .await
+    }
+
+    #[tool]
+    async fn forget_by_pattern(
+        &self,
+        Parameters(params): Parameters<ForgetByPatternParams>,
+        extensions: Extensions,
+    ) -> Result<CallToolResult, McpError> {
+        self.run(forget_by_pattern_call(&params), &extensions).await
+    }
+
+    #[tool]
+    async fn forget_by_tags(
+        &self,
+        Parameters(params): Parameters<ForgetByTagsParams>,
+        extensions: Extensions,
+    ) -> Result<CallToolResult, McpError> {
+        self.run(forget_by_tags_call(&params), &extensions).await
+    }
+
+    #[tool]
+    async fn forget_by_date(
+        &self,
+        Parameters(params): Parameters<ForgetByDateParams>,
+        extensions: Extensions,
+    ) -> Result<CallToolResult, McpError> {
+        self.run(forget_by_date_call(&params), &extensions).await
+    }
+
+    #[tool]
+    async fn consolidation_report(
+        &self,
+        Parameters(params): Parameters<ConsolidationReportParams>,
+        extensions: Extensions,
+    ) -> Result<CallToolResult, McpError> {
+        self.run(consolidation_report_call(&params), &extensions)
+            .await
+    }
+}
+
+#[tool_handler]
+impl ServerHandler for MemoryMcpServer {
+    fn get_info(&self) -> ServerInfo {
+        ServerInfo {
+            protocol_version: ProtocolVersion::V_2025_03_26,
+            capabilities: ServerCapabilities::builder().enable_tools().build(),
+            server_info: Implementation::from_build_env(),
+            instructions: Some(SERVER_INSTRUCTIONS.to_string()),
+        }
+    }
+}
+
+// =============================================================================
+// CALLER RESOLUTION & REST BRIDGING
+// =============================================================================
+
+impl MemoryMcpServer {
+    /// Run a tool's REST call through the API routes as the MCP caller
+    async fn run(
+        &self,
+        call: RestCall,
+        extensions: &Extensions,
+    ) -> Result<CallToolResult, McpError> {
+        let method = match call.method {
+            RestMethod::Get => Method::GET,
+            RestMethod::Post => Method::POST,
+            RestMethod::Delete => Method::DELETE,
+        };
+        let (auth, user_id) = caller(extensions, &method, &call.path)?;
+
+        let body = call.body_for(&user_id);
+        let query_user = body.is_none().then_some(user_id.as_str());
+        let builder = axum::http::Request::builder()
+            .method(method)
+            .uri(local_uri(&call.path, query_user));
+        let request = match body {
+            Some(body) => builder
+                .header(header::CONTENT_TYPE, "application/json")
+                .body(Body::from(body.to_string())),
+            None => builder.body(Body::empty()),
+        };
+        let mut request = request.map_err(|e| McpError::invalid_params(e.to_string(), None))?;
+        request.extensions_mut().insert(auth);
+
+        let response = match self.api.clone().oneshot(request).await {
+            Ok(response) => response,
+            Err(never) => match never {},
+        };
+        let status = response.status();
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .map_err(|e| McpError::internal_error(e.to_string(), None))?;
+        let value: serde_json::Value = serde_json::from_slice(&bytes).unwrap_or_else(|_| {
+            serde_json::Value::String(String::from_utf8_lossy(&bytes).into_owned())
+        });
+
+        if status.is_success() {
+            return Ok(call.output(&value));
+        }
+        let message = match &value {
+            serde_json::Value::String(text) if !text.is_empty() => text.clone(),
+            _ => value
+                .get("message")
+                .and_then(|m| m.as_str())
+                .map(|m| m.to_string())
+                .unwrap_or_else(|| status.to_string()),
+        };
+        if status.is_client_error() {
+            Err(McpError {
+                code: ErrorCode::INVALID_PARAMS,
+                message: message.into(),
+                data: value
+                    .get("code")
+                    .map(|code| serde_json::json!({ "code": code })),
+            })
+        } else {
+            Err(McpError::internal_error(message, None))
+        }
+    }
+}
+
+/// Resolve the user a tool call acts for and check the API key may call the
+/// REST route `method path` the tool mirrors. Returns the key's auth context
+/// with the user, so the routed request carries the same identity.
+///
+/// The HTTP request parts (carrying the [`AuthContext`] set by the auth
+/// middleware) are attached to every MCP request by the transports.
+fn caller(
+    extensions: &Extensions,
+    method: &Method,
+    path: &str,
+) -> Result<(AuthContext, String), McpError> {
+    let parts = extensions.get::<Parts>().ok_or_else(|| {
+        McpError::invalid_request("MCP request is missing its HTTP request context", None)
+    })?;
+    let auth = parts
+        .extensions
+        .get::<AuthContext>()
+        .ok_or_else(|| auth_error(AuthError::MissingApiKey))?;
+    auth.require_role(auth::required_role(method, path))
+        .map_err(auth_error)?;
+
+    let user_id = auth::user_id_from_query(&parts.uri)
+        .or_else(|| {
+            parts
+                .headers
+                .get("X-User-Id")
+                .and_then(|v| v.to_str().ok())
+                .map(|s| s.to_string())
+        })
+        .or_else(|| match &auth.allowed_user_ids {
+            Some(allowed) if allowed.len() == 1 => allowed.iter().next().cloned(),
+            _ => None,
+        })
+        .ok_or_else(|| {
+            McpError::invalid_request(
+                "No user_id: add ?user_id= to the MCP URL, send an X-User-Id header, \
+                 or use an API key scoped to a single user",
+                None,
+            )
+        })?;
+    auth.authorize_user(&user_id).map_err(auth_error)?;
+    Ok((auth.clone(), user_id))
+}
+
+/// Origin-relative URI for `path`, percent-encoded, with `user_id` in the
+/// query when the call has no body
+fn local_uri(path: &str, user_id: Option<&str>) -> String {
+    let mut url = reqwest::Url::parse("http://localhost").expect("static base URL");
+    url.set_path(path);
+    if let Some(user_id) = user_id {
+        url.query_pairs_mut().append_pair("user_id", user_id);
+    }
+    match url.query() {
+        Some(query) => format!("{}?{query}", url.path()),
+        None => url.path().to_string(),
+    }
+}
+
+fn auth_error(e: AuthError) -> McpError {
+    let message = match e {
+        AuthError::InsufficientRole { required, actual } => format!(
+            "API key role '{}' cannot call this tool (requires '{}')",
+            actual.as_str(),
+            required.as_str()
+        ),
+        AuthError::UserNotPermitted(user_id) => {
+            format!("API key is not permitted to act on user '{user_id}'")
+        }
+        _ => "Missing or invalid API key".to_string(),
+    };
+    McpError::invalid_request(message, None)
+}
+
+// =============================================================================
+// STREAMABLE HTTP TRANSPORT
+// =============================================================================
+
+/// Streamable HTTP service for `/mcp`, one [`MemoryMcpServer`] per session
+pub fn streamable_http_service(
+    state: AppState,
+) -> StreamableHttpService<MemoryMcpServer, LocalSessionManager> {
+    StreamableHttpService::new(
+        move || Ok(MemoryMcpServer::new(Arc::clone(&state))),
+        Arc::new(LocalSessionManager::default()),
+        StreamableHttpServerConfig::default(),
+    )
+}
+
+// =============================================================================
+// LEGACY HTTP+SSE TRANSPORT
+// =============================================================================
+
+/// Open legacy sessions: session ID → channel into that session's MCP service
+static LEGACY_SSE_SESSIONS: LazyLock<Mutex<HashMap<String, mpsc::Sender<ClientJsonRpcMessage>>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// Unregisters a legacy session when its SSE stream is dropped. Dropping the
+/// last sender ends the service's input stream, which shuts the service down.
+struct LegacySessionGuard(String);
+
+impl Drop for LegacySessionGuard {
+    fn drop(&mut self) {
+        LEGACY_SSE_SESSIONS.lock().remove(&self.0);
+    }
+}
+
+/// GET /mcp/sse - Open a legacy HTTP+SSE MCP session
+///
+/// The first event (`endpoint`) tells the client where to POST its messages;
+/// server messages follow as `message` events.
+pub async fn legacy_sse(State(state): State<AppState>, request: Request) -> Response {
+    let session_id = uuid::Uuid::new_v4().to_string();
+    let (client_tx, client_rx) = mpsc::channel(64);
+    let (server_tx, server_rx) = mpsc::channel(64);
+    LEGACY_SSE_SESSIONS
+        .lock()
+        .insert(session_id.clone(), client_tx);
+
+    let server = MemoryMcpServer::new(state);
+    tokio::spawn(async move {
+        match server.serve((server_tx, client_rx)).await {
+            Ok(service) => {
+                let _ = service.waiting().await;
+            }
+            Err(e) => tracing::debug!("Legacy MCP session ended before initialization: {}", e),
+        }
+    });
+
+    // Keep this stream's query (and so its user_id) on the message endpoint
+    let mut endpoint = format!("/mcp/message?sessionId={session_id}");
+    if let Some(query) = request.uri().query() {
+        endpoint.push('&');
+        endpoint.push_str(query);
+    }
+
+    let guard = LegacySessionGuard(session_id);
+    let messages = server_rx.map(move |message| {
+        let _session = &guard;
+        let data = serde_json::to_string(&message).unwrap_or_default();
+        Ok::<_, Infallible>(Event::default().event("message").data(data))
+    });
+    let events = futures::stream::once(async move {
+        Ok::<_, Infallible>(Event::default().event("endpoint").data(endpoint))
+    })
+    .chain(messages);
+
+    Sse::new(events)
+        .keep_alive(KeepAlive::new().interval(std::time::Duration::from_secs(15)))
+        .into_response()
+}
+
+#[derive(Debug, Deserialize)]
+pub struct LegacyMessageQuery {
+    #[serde(rename = "sessionId")]
+    pub session_id: String,
+}
+
+/// POST /mcp/message - Deliver a client message to a legacy SSE session
+///
+/// Responses are sent on the session's SSE stream, so this only acknowledges.
+pub async fn legacy_message(
+    Query(query): Query<LegacyMessageQuery>,
+    request: Request<Body>,
+) -> Response {
+    let (parts, body) = request.into_parts();
+    let bytes = match axum::body::to_bytes(body, MAX_LEGACY_MESSAGE_BYTES).await {
+        Ok(bytes) => bytes,
+        Err(_) => return StatusCode::PAYLOAD_TOO_LARGE.into_response(),
+    };
+    let mut message: ClientJsonRpcMessage = match serde_json::from_slice(&bytes) {
+        Ok(message) => message,
+        Err(e) => return (StatusCode::BAD_REQUEST, e.to_string()).into_response(),
+    };
+
+    // Same as the Streamable HTTP transport: tools read auth from the parts
+    match &mut message {
+        ClientJsonRpcMessage::Request(req) => {
+            req.request.extensions_mut().insert(parts);
+        }
+        ClientJsonRpcMessage::Notification(not) => {
+            not.notification.extensions_mut().insert(parts);
+        }
+        _ => {}
+    }
+
+    let sender = LEGACY_SSE_SESSIONS.lock().get(&query.session_id).cloned();
+    let Some(mut sender) = sender else {
+        return (StatusCode::NOT_FOUND, "Unknown MCP session").into_response();
+    };
+    if sender.send(message).await.is_err() {
+        return (StatusCode::GONE, "MCP session closed").into_response();
+    }
+    StatusCode::ACCEPTED.into_response()
+}
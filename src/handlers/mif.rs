@@ -12,9 +12,12 @@ use std::collections::HashMap;
 
 use super::state::MultiUserMemoryManager;
 use super::types::RetrieveResponse;
+use crate::constants::DEFAULT_HEADING_TOLERANCE_DEGREES;
 use crate::errors::{AppError, ValidationErrorExt};
 use crate::graph_memory;
-use crate::memory::{self, Memory, Query as MemoryQuery};
+use crate::memory::{
+    self, Memory, Query as MemoryQuery, Session, SessionEvent, SessionQuery, SessionStats,
+};
 use crate::validation;
 
 /// Application state type alias
@@ -33,6 +36,8 @@ pub struct MifExportRequest {
     #[serde(default)]
     pub include_graph: bool,
     #[serde(default)]
+    pub include_sessions: bool,
+    #[serde(default)]
     pub since: Option<String>,
     #[serde(default)]
     pub redact_pii: bool,
@@ -203,6 +208,8 @@ pub struct MifTodo {
     pub subtask_ids: Vec<String>,
     #[serde(skip_serializing_if = "Option::is_none", default)]
     pub blocked_on: Option<String>,
+    #[serde(default)]
+    pub blocked_by: Vec<String>,
     #[serde(skip_serializing_if = "Option::is_none", default)]
     pub recurrence: Option<String>,
     #[serde(default)]
@@ -244,6 +251,31 @@ fn default_comment_type() -> String {
     "comment".to_string()
 }
 
+/// MIF Session object
+///
+/// Event previews are left out so sessions never carry memory or query text;
+/// the timeline is reduced to the memories, todos and projects it touched.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MifSession {
+    pub id: String,
+    pub status: String,
+    pub started_at: String,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub ended_at: Option<String>,
+    #[serde(default)]
+    pub duration_secs: i64,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub label: Option<String>,
+    #[serde(default)]
+    pub stats: SessionStats,
+    #[serde(default)]
+    pub memory_ids: Vec<String>,
+    #[serde(default)]
+    pub todo_ids: Vec<String>,
+    #[serde(default)]
+    pub projects: Vec<String>,
+}
+
 /// MIF Graph structure
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MifGraph {
@@ -329,6 +361,7 @@ fn default_max_weight() -> f32 {
 pub struct MifMetadata {
     pub total_memories: usize,
     pub total_todos: usize,
+    pub total_sessions: usize,
     pub date_range: MifDateRange,
     pub memory_types: HashMap<String, usize>,
     pub top_entities: Vec<MifTopEntity>,
@@ -374,6 +407,8 @@ pub struct MifExport {
     pub todos: Vec<MifTodo>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub graph: Option<MifGraph>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub sessions: Vec<MifSession>,
     pub metadata: MifMetadata,
 }
 
@@ -799,6 +834,11 @@ pub async fn export_mif(
                 parent_id: t.parent_id.map(|id| format!("todo_{}", id.0)),
                 subtask_ids: vec![],
                 blocked_on: t.blocked_on,
+                blocked_by: t
+                    .blocked_by
+                    .iter()
+                    .map(|id| format!("todo_{}", id.0))
+                    .collect(),
                 recurrence: t.recurrence.map(|r| format!("{:?}", r).to_lowercase()),
                 related_memory_ids: t
                     .related_memory_ids
@@ -879,6 +919,33 @@ pub async fn export_mif(
         None
     };
 
+    // Collect session history if requested
+    let sessions: Vec<MifSession> = if req.include_sessions {
+        let mut sessions = Vec::new();
+        let mut cursor = None;
+        loop {
+            let page = state.session_store.query_sessions(&SessionQuery {
+                user_id: user_id.clone(),
+                limit: 100,
+                cursor,
+                ..Default::default()
+            });
+            sessions.extend(
+                page.sessions
+                    .iter()
+                    .filter_map(|s| state.session_store.get_session(&s.id))
+                    .map(|s| session_to_mif(&s)),
+            );
+            match page.next_cursor {
+                Some(next) => cursor = Some(next),
+                None => break,
+            }
+        }
+        sessions
+    } else {
+        Vec::new()
+    };
+
     // Build metadata
     let mut memory_types: HashMap<String, usize> = HashMap::new();
     for m in &memories {
@@ -923,6 +990,7 @@ pub async fn export_mif(
     let metadata = MifMetadata {
         total_memories: memories.len(),
         total_todos: todos.len(),
+        total_sessions: sessions.len(),
         date_range: MifDateRange { earliest, latest },
         memory_types,
         top_entities: vec![],
@@ -966,6 +1034,7 @@ pub async fn export_mif(
         memories,
         todos,
         graph,
+        sessions,
         metadata,
     };
 
@@ -982,6 +1051,53 @@ pub async fn export_mif(
     Ok(Json(export))
 }
 
+/// Reduce a session to its MIF form (IDs and stats only, no event previews)
+fn session_to_mif(session: &Session) -> MifSession {
+    let mut memory_ids = Vec::new();
+    let mut todo_ids = Vec::new();
+    let mut projects: Vec<String> = Vec::new();
+    let mut add_memory = |id: &str| {
+        let id = format!("mem_{id}");
+        if !memory_ids.contains(&id) {
+            memory_ids.push(id);
+        }
+    };
+
+    for event in &session.timeline {
+        match event {
+            SessionEvent::MemoryCreated { memory_id, .. }
+            | SessionEvent::MemoryUsed { memory_id, .. } => add_memory(memory_id),
+            SessionEvent::MemoriesSurfaced {
+                memory_ids: ids, ..
+            } => ids.iter().for_each(|id| add_memory(id)),
+            SessionEvent::TodoCreated {
+                todo_id, project, ..
+            } => {
+                todo_ids.push(format!("todo_{todo_id}"));
+                if let Some(p) = project {
+                    if !projects.contains(p) {
+                        projects.push(p.clone());
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    MifSession {
+        id: format!("sess_{}", session.id.0),
+        status: format!("{:?}", session.status).to_lowercase(),
+        started_at: session.started_at.to_rfc3339(),
+        ended_at: session.ended_at.map(|d| d.to_rfc3339()),
+        duration_secs: session.duration().num_seconds(),
+        label: session.label.clone(),
+        stats: session.stats.clone(),
+        memory_ids,
+        todo_ids,
+        projects,
+    }
+}
+
 // =============================================================================
 // MIF IMPORT HANDLER
 // =============================================================================
@@ -1134,6 +1250,7 @@ pub async fn import_mif(
             tags: mif_todo.tags.clone(),
             notes: mif_todo.notes.clone(),
             blocked_on: mif_todo.blocked_on.clone(),
+            blocked_by: Vec::new(),
             recurrence: None,
             sort_order: 0,
             comments: Vec::new(),
@@ -1267,12 +1384,118 @@ pub struct RoboticsSearchRequest {
     pub lat: Option<f64>,
     pub lon: Option<f64>,
     pub radius_meters: Option<f64>,
+    /// Map frame for local-position queries (defaults to "map")
+    pub frame: Option<String>,
+    /// Center of a local radius or k-nearest query (x, y, z in meters)
+    pub position: Option<[f32; 3]>,
+    pub local_radius_meters: Option<f32>,
+    pub k_nearest: Option<usize>,
+    /// Corners of a local bounding-box query
+    pub bbox_min: Option<[f32; 3]>,
+    pub bbox_max: Option<[f32; 3]>,
+    /// Only match memories facing within `heading_tolerance_degrees` of this heading
+    pub heading: Option<f32>,
+    pub heading_tolerance_degrees: Option<f32>,
     pub action_type: Option<String>,
     pub min_reward: Option<f32>,
     pub max_reward: Option<f32>,
     pub limit: Option<usize>,
 }
 
+/// Build the local-frame filter of a robotics search, if it asks for one
+fn local_filter_from_request(
+    req: &RoboticsSearchRequest,
+) -> Result<Option<memory::LocalSpatialFilter>, AppError> {
+    let invalid = |field: &str, reason: &str| AppError::InvalidInput {
+        field: field.to_string(),
+        reason: reason.to_string(),
+    };
+
+    let region = match (req.position, req.bbox_min, req.bbox_max) {
+        (Some(_), Some(_), _) | (Some(_), _, Some(_)) => {
+            return Err(invalid(
+                "position/bbox_min/bbox_max",
+                "Use either position or bbox_min and bbox_max, not both",
+            ))
+        }
+        (None, Some(min), Some(max)) => {
+            if (0..3).any(|i| min[i] > max[i]) {
+                return Err(invalid("bbox_min", "bbox_min must not exceed bbox_max"));
+            }
+            memory::LocalRegion::BoundingBox { min, max }
+        }
+        (None, Some(_), None) | (None, None, Some(_)) => {
+            return Err(invalid(
+                "bbox_min/bbox_max",
+                "bbox_min and bbox_max must be provided together",
+            ))
+        }
+        (Some(center), None, None) => match (req.k_nearest, req.local_radius_meters) {
+            (Some(_), Some(_)) => {
+                return Err(invalid(
+                    "k_nearest/local_radius_meters",
+                    "Use either k_nearest or local_radius_meters, not both",
+                ))
+            }
+            (Some(k), None) => {
+                if k == 0 {
+                    return Err(invalid("k_nearest", "k_nearest must be positive"));
+                }
+                memory::LocalRegion::Nearest { center, k }
+            }
+            (None, Some(radius_meters)) => {
+                if radius_meters <= 0.0 {
+                    return Err(invalid(
+                        "local_radius_meters",
+                        "local_radius_meters must be positive",
+                    ));
+                }
+                memory::LocalRegion::Radius {
+                    center,
+                    radius_meters,
+                }
+            }
+            (None, None) => {
+                return Err(invalid(
+                    "local_radius_meters",
+                    "position requires local_radius_meters or k_nearest",
+                ))
+            }
+        },
+        (None, None, None) => {
+            if req.local_radius_meters.is_some() || req.k_nearest.is_some() {
+                return Err(invalid(
+                    "position",
+                    "local_radius_meters and k_nearest require position",
+                ));
+            }
+            if req.heading.is_some() {
+                return Err(invalid(
+                    "heading",
+                    "heading requires a position or bounding box",
+                ));
+            }
+            return Ok(None);
+        }
+    };
+
+    let frame = req.frame.as_deref().unwrap_or(memory::DEFAULT_LOCAL_FRAME);
+    let mut filter = memory::LocalSpatialFilter::new(frame, region);
+    if let Some(heading) = req.heading {
+        let tolerance = req
+            .heading_tolerance_degrees
+            .unwrap_or(DEFAULT_HEADING_TOLERANCE_DEGREES);
+        if !(0.0..=180.0).contains(&tolerance) {
+            return Err(invalid(
+                "heading_tolerance_degrees",
+                "heading_tolerance_degrees must be between 0 and 180",
+            ));
+        }
+        filter = filter.with_heading_cone(memory::HeadingCone::new(heading, tolerance));
+    }
+    Ok(Some(filter))
+}
+
 /// Robotics-specific memory search
 pub async fn robotics_search(
     State(state): State<AppState>,
@@ -1313,10 +1536,22 @@ pub async fn robotics_search(
         _ => None,
     };
 
-    if matches!(retrieval_mode, memory::RetrievalMode::Spatial) && geo_filter.is_none() {
+    let local_filter = local_filter_from_request(&req)?;
+
+    if matches!(retrieval_mode, memory::RetrievalMode::Spatial)
+        && geo_filter.is_none()
+        && local_filter.is_none()
+    {
         return Err(AppError::InvalidInput {
             field: "lat/lon/radius_meters".to_string(),
-            reason: "Spatial mode requires lat, lon, and radius_meters".to_string(),
+            reason: "Spatial mode requires lat, lon, and radius_meters, or a local position or bounding box".to_string(),
+        });
+    }
+
+    if local_filter.is_some() && req.robot_id.is_none() {
+        return Err(AppError::InvalidInput {
+            field: "robot_id".to_string(),
+            reason: "Local-frame queries require robot_id".to_string(),
         });
     }
 
@@ -1332,6 +1567,7 @@ pub async fn robotics_search(
         robot_id: req.robot_id.clone(),
         mission_id: req.mission_id.clone(),
         geo_filter,
+        local_filter,
         action_type: req.action_type.clone(),
         reward_range,
         max_results: req.limit.unwrap_or(10),
@@ -1427,6 +1663,9 @@ pub async fn add_entity(
         name_embedding: None,
         salience: 0.5,
         is_proper_noun: true,
+        aliases: Vec::new(),
+        centrality: 0.0,
+        community: None,
     };
 
     graph_guard.add_entity(entity).map_err(AppError::Internal)?;
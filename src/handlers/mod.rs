@@ -31,14 +31,20 @@ pub mod visualization;
 pub mod todos;
 
 // MCP and webhooks
+pub mod export;
+pub mod ics;
+pub mod mcp;
 pub mod mif;
 pub mod webhooks;
 
 // External integrations
 pub mod integrations;
 
-// Session and user management
+// Session, user, API key, shared space and outbound webhook management
+pub mod api_keys;
+pub mod outbound_webhooks;
 pub mod sessions;
+pub mod spaces;
 pub mod users;
 
 // File and codebase memory
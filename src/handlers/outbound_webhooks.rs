@@ -0,0 +1,200 @@
+//! Outbound Webhook Handlers
+//!
+//! Per-user subscription management plus the delivery log and dead-letter
+//! list. Signing secrets are only returned when a subscription is created.
+
+use axum::{
+    extract::{Path, Query, State},
+    response::Json,
+};
+use serde::{Deserialize, Serialize};
+
+use super::state::MultiUserMemoryManager;
+use crate::errors::{AppError, ValidationErrorExt};
+use crate::outbound_webhooks::{WebhookDelivery, WebhookEventType, WebhookSubscription};
+use crate::validation;
+use std::sync::Arc;
+
+type AppState = Arc<MultiUserMemoryManager>;
+
+/// Request for POST /api/webhooks
+#[derive(Debug, Deserialize)]
+pub struct CreateWebhookRequest {
+    pub user_id: String,
+    pub url: String,
+    /// Events to deliver. Omit or leave empty for all events.
+    #[serde(default)]
+    pub events: Vec<WebhookEventType>,
+    #[serde(default)]
+    pub description: Option<String>,
+    /// Signing secret; generated when omitted
+    #[serde(default)]
+    pub secret: Option<String>,
+}
+
+/// Query for webhook endpoints scoped to a user
+#[derive(Debug, Deserialize)]
+pub struct WebhookQuery {
+    pub user_id: String,
+}
+
+/// Query for GET /api/webhooks/deliveries
+#[derive(Debug, Deserialize)]
+pub struct DeliveryLogQuery {
+    pub user_id: String,
+    #[serde(default)]
+    pub subscription_id: Option<String>,
+    #[serde(default = "default_delivery_limit")]
+    pub limit: usize,
+}
+
+fn default_delivery_limit() -> usize {
+    50
+}
+
+/// Response for GET /api/webhooks
+#[derive(Debug, Serialize)]
+pub struct ListWebhooksResponse {
+    pub webhooks: Vec<WebhookSubscription>,
+    pub count: usize,
+}
+
+/// Response for the delivery log and dead-letter endpoints
+#[derive(Debug, Serialize)]
+pub struct DeliveriesResponse {
+    pub deliveries: Vec<WebhookDelivery>,
+    pub count: usize,
+}
+
+/// POST /api/webhooks - Register a webhook endpoint
+pub async fn create_webhook(
+    State(state): State<AppState>,
+    Json(req): Json<CreateWebhookRequest>,
+) -> Result<Json<WebhookSubscription>, AppError> {
+    validation::validate_user_id(&req.user_id).map_validation_err("user_id")?;
+
+    // Subscribing resolves the URL's host to reject internal addresses
+    let store = state.webhook_store.clone();
+    let user_id = req.user_id.clone();
+    let subscription = tokio::task::spawn_blocking(move || {
+        store.subscribe(
+            &user_id,
+            req.url.trim(),
+            req.events,
+            req.description,
+            req.secret,
+        )
+    })
+    .await
+    .map_err(|e| AppError::Internal(anyhow::anyhow!("Blocking task panicked: {e}")))?
+    .map_err(|e| AppError::InvalidInput {
+        field: "url".to_string(),
+        reason: format!("{e:#}"),
+    })?;
+
+    state.log_event(
+        &req.user_id,
+        "WEBHOOK_CREATE",
+        &subscription.id,
+        &format!("Registered webhook {}", subscription.url),
+    );
+
+    Ok(Json(subscription))
+}
+
+/// GET /api/webhooks?user_id= - List a user's webhooks (secrets redacted)
+pub async fn list_webhooks(
+    State(state): State<AppState>,
+    Query(query): Query<WebhookQuery>,
+) -> Result<Json<ListWebhooksResponse>, AppError> {
+    validation::validate_user_id(&query.user_id).map_validation_err("user_id")?;
+
+    let webhooks: Vec<WebhookSubscription> = state
+        .webhook_store
+        .subscriptions(&query.user_id)
+        .map_err(AppError::Internal)?
+        .iter()
+        .map(WebhookSubscription::redacted)
+        .collect();
+    let count = webhooks.len();
+    Ok(Json(ListWebhooksResponse { webhooks, count }))
+}
+
+/// DELETE /api/webhooks/{webhook_id}?user_id= - Remove a webhook
+pub async fn delete_webhook(
+    State(state): State<AppState>,
+    Path(webhook_id): Path<String>,
+    Query(query): Query<WebhookQuery>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    validation::validate_user_id(&query.user_id).map_validation_err("user_id")?;
+
+    let deleted = state
+        .webhook_store
+        .unsubscribe(&query.user_id, &webhook_id)
+        .map_err(AppError::Internal)?;
+    if !deleted {
+        return Err(AppError::WebhookNotFound(webhook_id));
+    }
+
+    state.log_event(
+        &query.user_id,
+        "WEBHOOK_DELETE",
+        &webhook_id,
+        "Deleted webhook",
+    );
+
+    Ok(Json(
+        serde_json::json!({ "success": true, "id": webhook_id }),
+    ))
+}
+
+/// GET /api/webhooks/deliveries?user_id= - Recent deliveries, newest first
+pub async fn list_deliveries(
+    State(state): State<AppState>,
+    Query(query): Query<DeliveryLogQuery>,
+) -> Result<Json<DeliveriesResponse>, AppError> {
+    validation::validate_user_id(&query.user_id).map_validation_err("user_id")?;
+
+    let deliveries = state
+        .webhook_store
+        .delivery_log(
+            &query.user_id,
+            query.subscription_id.as_deref(),
+            query.limit.clamp(1, 500),
+        )
+        .map_err(AppError::Internal)?;
+    let count = deliveries.len();
+    Ok(Json(DeliveriesResponse { deliveries, count }))
+}
+
+/// GET /api/webhooks/dead-letters?user_id= - Deliveries that exhausted their retries
+pub async fn list_dead_letters(
+    State(state): State<AppState>,
+    Query(query): Query<WebhookQuery>,
+) -> Result<Json<DeliveriesResponse>, AppError> {
+    validation::validate_user_id(&query.user_id).map_validation_err("user_id")?;
+
+    let deliveries = state
+        .webhook_store
+        .dead_letters(&query.user_id)
+        .map_err(AppError::Internal)?;
+    let count = deliveries.len();
+    Ok(Json(DeliveriesResponse { deliveries, count }))
+}
+
+/// POST /api/webhooks/dead-letters/{delivery_id}/retry?user_id= - Re-queue a dead letter
+pub async fn retry_dead_letter(
+    State(state): State<AppState>,
+    Path(delivery_id): Path<String>,
+    Query(query): Query<WebhookQuery>,
+) -> Result<Json<WebhookDelivery>, AppError> {
+    validation::validate_user_id(&query.user_id).map_validation_err("user_id")?;
+
+    let delivery = state
+        .webhook_store
+        .retry_dead_letter(&query.user_id, &delivery_id)
+        .map_err(AppError::Internal)?
+        .ok_or_else(|| AppError::WebhookNotFound(delivery_id.clone()))?;
+
+    Ok(Json(delivery))
+}
@@ -13,8 +13,8 @@ use tracing::info;
 
 use super::state::MultiUserMemoryManager;
 use super::types::{
-    MemoryEvent, RecallExperience, RecallFact, RecallMemory, RecallRequest, RecallResponse,
-    RecallTodo, ReinforceFeedbackRequest, RetrieveResponse, TrackedRetrieveRequest,
+    MemoryEvent, RecallExperience, RecallFact, RecallFilter, RecallMemory, RecallRequest,
+    RecallResponse, RecallTodo, ReinforceFeedbackRequest, RetrieveResponse, TrackedRetrieveRequest,
     TrackedRetrieveResponse,
 };
 use super::utils::{is_bare_question, is_boilerplate_response, strip_system_noise};
@@ -112,6 +112,9 @@ pub struct ProactiveContextRequest {
     /// User's followup message after agent response (for delayed signals)
     #[serde(default)]
     pub user_followup: Option<String>,
+    /// Shared spaces to surface memories from alongside the personal store
+    #[serde(default)]
+    pub spaces: Vec<String>,
 }
 
 fn default_proactive_max_results() -> usize {
@@ -153,6 +156,9 @@ pub struct ProactiveSurfacedMemory {
     /// Entities from this memory that matched the query context
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub matched_entities: Vec<String>,
+    /// Space the memory came from (absent for the personal store)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub space: Option<String>,
     /// Embedding for semantic feedback (not serialized to response)
     #[serde(skip)]
     pub embedding: Vec<f32>,
@@ -284,6 +290,141 @@ pub struct RecallByDateRequest {
 // MAIN RECALL HANDLER
 // =============================================================================
 
+/// Translate a recall filter into storage search criteria
+///
+/// Returns `None` when no field is set. Multiple fields combine with AND.
+fn recall_filter_criteria(filter: &RecallFilter) -> Result<Option<SearchCriteria>, AppError> {
+    let invalid = |field: &str, reason: &str| AppError::InvalidInput {
+        field: format!("filter.{field}"),
+        reason: reason.to_string(),
+    };
+    let mut criterias = Vec::new();
+
+    if let Some(tags) = &filter.tags {
+        if tags.is_empty() {
+            return Err(invalid("tags", "tags cannot be empty"));
+        }
+        criterias.push(SearchCriteria::ByTags(tags.clone()));
+    }
+
+    if let Some(exp_type) = &filter.experience_type {
+        let exp_type = super::crud::parse_experience_type(exp_type)
+            .map_err(|_| invalid("experience_type", &format!("unknown type: {exp_type}")))?;
+        criterias.push(SearchCriteria::ByType(exp_type));
+    }
+
+    if filter.start.is_some() || filter.end.is_some() {
+        // Open bounds stay within 4-digit years so the date index keys sort correctly
+        let start = filter.start.unwrap_or_else(|| {
+            chrono::NaiveDate::from_ymd_opt(1, 1, 1)
+                .and_then(|d| d.and_hms_opt(0, 0, 0))
+                .map(|dt| dt.and_utc())
+                .unwrap_or_default()
+        });
+        let end = filter.end.unwrap_or_else(|| {
+            chrono::NaiveDate::from_ymd_opt(9999, 12, 31)
+                .and_then(|d| d.and_hms_opt(23, 59, 59))
+                .map(|dt| dt.and_utc())
+                .unwrap_or_else(chrono::Utc::now)
+        });
+        if start > end {
+            return Err(invalid("start", "start must not be after end"));
+        }
+        criterias.push(SearchCriteria::ByDate { start, end });
+    }
+
+    if filter.min_importance.is_some() || filter.max_importance.is_some() {
+        let min = filter.min_importance.unwrap_or(0.0);
+        let max = filter.max_importance.unwrap_or(1.0);
+        validation::validate_importance_threshold(min)
+            .map_validation_err("filter.min_importance")?;
+        validation::validate_importance_threshold(max)
+            .map_validation_err("filter.max_importance")?;
+        if min > max {
+            return Err(invalid(
+                "min_importance",
+                "min_importance must not exceed max_importance",
+            ));
+        }
+        criterias.push(SearchCriteria::ByImportance { min, max });
+    }
+
+    if let Some(entity) = &filter.entity {
+        criterias.push(SearchCriteria::ByEntity(entity.clone()));
+    }
+    if let Some(episode_id) = &filter.episode_id {
+        criterias.push(SearchCriteria::ByEpisode(episode_id.clone()));
+    }
+    if let Some(robot_id) = &filter.robot_id {
+        criterias.push(SearchCriteria::ByRobot(robot_id.clone()));
+    }
+    if let Some(mission_id) = &filter.mission_id {
+        criterias.push(SearchCriteria::ByMission(mission_id.clone()));
+    }
+    if let Some(action_type) = &filter.action_type {
+        criterias.push(SearchCriteria::ByActionType(action_type.clone()));
+    }
+    if let Some(agent_id) = &filter.agent_id {
+        criterias.push(SearchCriteria::ByAgent(agent_id.clone()));
+    }
+    if let Some(run_id) = &filter.run_id {
+        criterias.push(SearchCriteria::ByRun(run_id.clone()));
+    }
+    if let Some(actor_id) = &filter.actor_id {
+        criterias.push(SearchCriteria::ByActor(actor_id.clone()));
+    }
+
+    match (filter.lat, filter.lon, filter.radius_meters) {
+        (Some(lat), Some(lon), Some(radius_meters)) => {
+            if !(-90.0..=90.0).contains(&lat) || !(-180.0..=180.0).contains(&lon) {
+                return Err(invalid("lat/lon", "coordinates out of range"));
+            }
+            if radius_meters.is_nan() || radius_meters <= 0.0 {
+                return Err(invalid("radius_meters", "radius_meters must be positive"));
+            }
+            criterias.push(SearchCriteria::ByLocation {
+                lat,
+                lon,
+                radius_meters,
+            });
+        }
+        (None, None, None) => {}
+        _ => {
+            return Err(invalid(
+                "lat/lon/radius_meters",
+                "lat, lon and radius_meters must be provided together",
+            ))
+        }
+    }
+
+    if filter.min_reward.is_some() || filter.max_reward.is_some() {
+        let min = filter.min_reward.unwrap_or(-1.0);
+        let max = filter.max_reward.unwrap_or(1.0);
+        if min > max {
+            return Err(invalid(
+                "min_reward",
+                "min_reward must not exceed max_reward",
+            ));
+        }
+        criterias.push(SearchCriteria::ByReward { min, max });
+    }
+
+    if let Some(parent_id) = &filter.parent_id {
+        let uuid =
+            validation::validate_memory_id(parent_id).map_validation_err("filter.parent_id")?;
+        criterias.push(SearchCriteria::ByParent(MemoryId(uuid)));
+    }
+    if filter.roots_only {
+        criterias.push(SearchCriteria::RootsOnly);
+    }
+
+    Ok(match criterias.len() {
+        0 => None,
+        1 => criterias.pop(),
+        _ => Some(SearchCriteria::Combined(criterias)),
+    })
+}
+
 /// POST /api/recall - Semantic + associative hybrid recall
 ///
 /// Uses a hybrid retrieval strategy:
@@ -298,6 +439,10 @@ pub async fn recall(
     let op_start = std::time::Instant::now();
     validation::validate_user_id(&req.user_id).map_validation_err("user_id")?;
     validation::validate_max_results(req.limit).map_validation_err("limit")?;
+    let filter = match &req.filter {
+        Some(filter) => recall_filter_criteria(filter)?,
+        None => None,
+    };
 
     let memory = state
         .get_user_memory(&req.user_id)
@@ -307,6 +452,8 @@ pub async fn recall(
         .get_user_graph(&req.user_id)
         .map_err(AppError::Internal)?;
 
+    let space_stores = super::spaces::readable_spaces(&state, &req.user_id, &req.spaces)?;
+
     let query_text = req.query.clone();
     let limit = req.limit;
     let mode = req.mode.clone();
@@ -380,14 +527,13 @@ pub async fn recall(
         let signals = prospective_signals.clone();
         let user_id = req.user_id.clone();
         tokio::task::spawn_blocking(move || {
-            let memory_guard = memory.read();
-
             // Build query with prospective signals and user_id for temporal fact lookup
             let query = MemoryQuery {
                 user_id: Some(user_id),
                 query_text: Some(query_text.clone()),
                 max_results: limit,
                 prospective_signals: signals,
+                filter,
                 ..Default::default()
             };
 
@@ -398,7 +544,8 @@ pub async fn recall(
             // 4. Applies prospective boost for future intention matches
             // 5. Records coactivation for Hebbian learning
             // 6. Returns ranked results
-            memory_guard.recall(&query).unwrap_or_default()
+            // Requested spaces are queried the same way and fused by rank.
+            crate::spaces::recall_across(&memory, &space_stores, &query)
         })
         .await
         .map_err(|e| AppError::Internal(anyhow::anyhow!("Blocking task panicked: {e}")))?
@@ -407,9 +554,10 @@ pub async fn recall(
     // Convert to response format
     let total = memories.len();
     let recall_memories: Vec<RecallMemory> = memories
-        .iter()
+        .into_iter()
         .enumerate()
-        .map(|(rank, m)| {
+        .map(|(rank, hit)| {
+            let m = hit.memory;
             // Score based on rank position and salience
             let rank_score = 1.0 - (rank as f32 / total.max(1) as f32);
             let salience = m.salience_score_with_access();
@@ -424,6 +572,7 @@ pub async fn recall(
                 importance: m.importance(),
                 created_at: m.created_at.to_rfc3339(),
                 score,
+                space: hit.space,
             }
         })
         .collect();
@@ -514,8 +663,10 @@ pub async fn recall(
             tokio::task::spawn_blocking(move || {
                 let memory_guard = memory.read();
                 let mut facts = Vec::new();
+                let now = chrono::Utc::now();
                 for entity in &entity_list {
-                    if let Ok(entity_facts) = memory_guard.get_facts_by_entity(&user_id, entity, 5)
+                    if let Ok(entity_facts) =
+                        memory_guard.get_facts_by_entity(&user_id, entity, now, 5)
                     {
                         for fact in entity_facts {
                             facts.push(RecallFact {
@@ -773,6 +924,8 @@ pub async fn proactive_context(
         .get_user_graph(&req.user_id)
         .map_err(AppError::Internal)?;
 
+    let space_stores = super::spaces::readable_spaces(&state, &req.user_id, &req.spaces)?;
+
     // 0. Process pending feedback if previous_response is provided
     let feedback_processed = if let Some(ref prev_response) = req.previous_response {
         let feedback_store = state.feedback_store.clone();
@@ -935,7 +1088,7 @@ pub async fn proactive_context(
             Ok(emb) => (emb, true),
             Err(e) => {
                 tracing::warn!("proactive_context: embedding computation failed: {e}, using zero vector — retrieval quality degraded");
-                (vec![0.0; 384], false)
+                (vec![0.0; memory_guard.get_embedder().dimension()], false)
             }
         }
     });
@@ -1004,8 +1157,6 @@ pub async fn proactive_context(
     let memories: Vec<ProactiveSurfacedMemory> = {
         let memory = memory_system.clone();
         tokio::task::spawn_blocking(move || {
-            let memory_guard = memory.read();
-
             // Build word set from query for anti-echo detection (borrows context_clone)
             let query_words: std::collections::HashSet<String> = context_clone
                 .split_whitespace()
@@ -1022,11 +1173,20 @@ pub async fn proactive_context(
                 max_results,
                 ..Default::default()
             };
-            let results = memory_guard.recall(&query).unwrap_or_default();
+            // With spaces requested, scores are the fused RRF scores
+            let results = crate::spaces::recall_across(&memory, &space_stores, &query);
+            let mut space_of: std::collections::HashMap<MemoryId, String> =
+                std::collections::HashMap::new();
 
             let mut candidates: Vec<(SharedMemory, f32)> = results
                 .into_iter()
-                .filter(|m| {
+                .map(|hit| {
+                    if let Some(space) = hit.space {
+                        space_of.insert(hit.memory.id.clone(), space);
+                    }
+                    (hit.memory, hit.score)
+                })
+                .filter(|(m, _)| {
                     // Quality gate: skip garbage/truncated memories
                     let content = m.experience.content.trim();
                     if content.len() < 30 {
@@ -1059,10 +1219,6 @@ pub async fn proactive_context(
                     }
                     true
                 })
-                .map(|m| {
-                    let score = m.get_score().unwrap_or(0.0);
-                    (m, score)
-                })
                 .collect();
 
             // Sort by score (highest first) - already mostly sorted from recall()
@@ -1153,6 +1309,7 @@ pub async fn proactive_context(
                         tags: m.experience.tags.clone(),
                         relevance_reason,
                         matched_entities: matched,
+                        space: space_of.remove(&m.id),
                         embedding: m.experience.embeddings.clone().unwrap_or_default(),
                     }
                 })
@@ -1164,10 +1321,12 @@ pub async fn proactive_context(
 
     // 2.5. Record coactivation - fire-and-forget (doesn't affect response)
     // When memories are retrieved together, their graph edges get stronger (Hebbian learning)
-    if memories.len() >= 2 {
+    // Space memories live in other graphs, so only personal ones are linked
+    if memories.iter().filter(|m| m.space.is_none()).count() >= 2 {
         let graph = graph_memory.clone();
         let memory_ids: Vec<uuid::Uuid> = memories
             .iter()
+            .filter(|m| m.space.is_none())
             .filter_map(|m| uuid::Uuid::parse_str(&m.id).ok())
             .collect();
         tokio::task::spawn(async move {
@@ -1181,8 +1340,10 @@ pub async fn proactive_context(
 
     // 3. Store pending feedback (fast, in-memory — do before parallel block)
     if embedding_valid {
+        // Feedback reinforces the personal store, so space memories are left out
         let surfaced_infos: Vec<feedback::SurfacedMemoryInfo> = memories
             .iter()
+            .filter(|m| m.space.is_none())
             .map(|m| {
                 let id = uuid::Uuid::parse_str(&m.id).unwrap_or_else(|_| uuid::Uuid::new_v4());
                 feedback::SurfacedMemoryInfo {
@@ -1349,6 +1510,7 @@ pub async fn proactive_context(
                         ProspectiveTrigger::AtTime { .. } => "time".to_string(),
                         ProspectiveTrigger::AfterDuration { .. } => "duration".to_string(),
                         ProspectiveTrigger::OnContext { .. } => "context".to_string(),
+                        ProspectiveTrigger::Recurring { .. } => "recurring".to_string(),
                     };
                     ReminderItem {
                         id: t.id.0.to_string(),
@@ -1490,8 +1652,10 @@ pub async fn proactive_context(
                 let memory_guard = fact_memory.read();
                 let mut found: std::collections::HashMap<String, ProactiveFact> =
                     std::collections::HashMap::new();
+                let now = chrono::Utc::now();
                 for entity in &fact_entity_list {
-                    if let Ok(entity_facts) = memory_guard.get_facts_by_entity(&fact_uid, entity, 5)
+                    if let Ok(entity_facts) =
+                        memory_guard.get_facts_by_entity(&fact_uid, entity, now, 5)
                     {
                         for fact in entity_facts {
                             // Quality gate: skip low-confidence facts
@@ -1761,6 +1925,7 @@ pub async fn recall_tracked(
                 importance: m.importance(),
                 created_at: m.created_at.to_rfc3339(),
                 score,
+                space: None,
             }
         })
         .collect();
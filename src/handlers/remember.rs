@@ -58,10 +58,17 @@ pub struct RememberRequest {
     pub parent_agent_id: Option<String>,
     #[serde(default)]
     pub run_id: Option<String>,
+    /// End user or caller the agent acted for
+    #[serde(default)]
+    pub actor_id: Option<String>,
     /// Parent memory ID for hierarchical organization
     /// Use this to create memory trees (e.g., "71-research" -> "algebraic" -> "21×27≡-1")
     #[serde(default)]
     pub parent_id: Option<String>,
+    /// Shared space to store into instead of the caller's personal store
+    /// (requires write membership)
+    #[serde(default)]
+    pub space: Option<String>,
 }
 
 /// Remember response
@@ -69,6 +76,9 @@ pub struct RememberRequest {
 pub struct RememberResponse {
     pub id: String,
     pub success: bool,
+    /// Space the memory was stored in (absent for the personal store)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub space: Option<String>,
 }
 
 /// Batch remember request
@@ -399,9 +409,19 @@ pub async fn remember(
         ..Default::default()
     };
 
-    let memory = state
-        .get_user_memory(&req.user_id)
-        .map_err(AppError::Internal)?;
+    // Graph and temporal facts live alongside whichever store receives the memory
+    let (memory, store_id) = match &req.space {
+        Some(space_id) => (
+            super::spaces::writable_space(&state, &req.user_id, space_id)?,
+            crate::spaces::storage_id(space_id),
+        ),
+        None => (
+            state
+                .get_user_memory(&req.user_id)
+                .map_err(AppError::Internal)?,
+            req.user_id.clone(),
+        ),
+    };
 
     let memory_id = {
         let memory = memory.clone();
@@ -409,11 +429,12 @@ pub async fn remember(
         let created_at = req.created_at;
         let agent_id = req.agent_id.clone();
         let run_id = req.run_id.clone();
+        let actor_id = req.actor_id.clone();
 
         tokio::task::spawn_blocking(move || {
             let memory_guard = memory.read();
-            if agent_id.is_some() || run_id.is_some() {
-                memory_guard.remember_with_agent(exp_clone, created_at, agent_id, run_id)
+            if agent_id.is_some() || run_id.is_some() || actor_id.is_some() {
+                memory_guard.remember_with_agent(exp_clone, created_at, agent_id, run_id, actor_id)
             } else {
                 memory_guard.remember(exp_clone, created_at)
             }
@@ -424,7 +445,7 @@ pub async fn remember(
     };
 
     // Build episodic graph: entities + episode + relationships for multi-hop retrieval
-    if let Err(e) = state.process_experience_into_graph(&req.user_id, &experience, &memory_id) {
+    if let Err(e) = state.process_experience_into_graph(&store_id, &experience, &memory_id) {
         tracing::debug!("Graph processing failed (non-fatal): {}", e);
     }
 
@@ -451,7 +472,7 @@ pub async fn remember(
     // E.g., "planning camping next month" → resolves "next month" to absolute date
     {
         let memory = memory.clone();
-        let user_id = req.user_id.clone();
+        let user_id = store_id.clone();
         let content = req.content.clone();
         let entities = experience.entities.clone();
         let created_at = req.created_at.unwrap_or_else(chrono::Utc::now);
@@ -510,6 +531,7 @@ pub async fn remember(
     Ok(Json(RememberResponse {
         id: memory_id.0.to_string(),
         success: true,
+        space: req.space,
     }))
 }
 
@@ -11,8 +11,9 @@ use std::sync::Arc;
 
 use super::state::MultiUserMemoryManager;
 use super::{
-    ab_testing, compression, consolidation, crud, facts, files, graph, health, integrations,
-    lineage, mif, recall, remember, search, sessions, todos, users, visualization, webhooks,
+    ab_testing, api_keys, compression, consolidation, crud, export, facts, files, graph, health,
+    ics, integrations, lineage, mcp, mif, outbound_webhooks, recall, remember, search, sessions,
+    spaces, todos, users, visualization, webhooks,
 };
 
 /// Application state type alias
@@ -64,7 +65,8 @@ pub fn build_public_routes(state: AppState) -> Router {
 /// Build the protected API routes (authentication required)
 ///
 /// These routes require API key authentication and are rate-limited.
-/// The auth middleware and rate limiter should be applied by the caller.
+/// The auth middleware and rate limiter should be applied by the caller:
+/// `axum::middleware::from_fn_with_state(manager.api_key_store.clone(), auth::auth_middleware)`.
 pub fn build_protected_routes(state: AppState) -> Router {
     Router::new()
         // =================================================================
@@ -109,6 +111,7 @@ pub fn build_protected_routes(state: AppState) -> Router {
         .route("/api/forget/pattern", post(crud::forget_by_pattern))
         .route("/api/forget/tags", post(crud::forget_by_tags))
         .route("/api/forget/date", post(crud::forget_by_date))
+        .route("/api/forget/agent", post(crud::forget_by_agent))
         // =================================================================
         // USER MANAGEMENT
         // =================================================================
@@ -117,6 +120,48 @@ pub fn build_protected_routes(state: AppState) -> Router {
         .route("/api/users/{user_id}", delete(users::delete_user))
         .route("/api/stats", get(users::get_stats_query))
         // =================================================================
+        // API KEY MANAGEMENT (ADMIN ROLE)
+        // =================================================================
+        .route("/api/keys", get(api_keys::list_api_keys))
+        .route("/api/keys", post(api_keys::create_api_key))
+        .route("/api/keys/{key_id}/revoke", post(api_keys::revoke_api_key))
+        .route("/api/keys/{key_id}/rotate", post(api_keys::rotate_api_key))
+        // =================================================================
+        // OUTBOUND WEBHOOKS
+        // =================================================================
+        .route("/api/webhooks", get(outbound_webhooks::list_webhooks))
+        .route("/api/webhooks", post(outbound_webhooks::create_webhook))
+        .route(
+            "/api/webhooks/{webhook_id}",
+            delete(outbound_webhooks::delete_webhook),
+        )
+        .route(
+            "/api/webhooks/deliveries",
+            get(outbound_webhooks::list_deliveries),
+        )
+        .route(
+            "/api/webhooks/dead-letters",
+            get(outbound_webhooks::list_dead_letters),
+        )
+        .route(
+            "/api/webhooks/dead-letters/{delivery_id}/retry",
+            post(outbound_webhooks::retry_dead_letter),
+        )
+        // =================================================================
+        // SHARED SPACES
+        // =================================================================
+        .route("/api/spaces", get(spaces::list_spaces))
+        .route("/api/spaces", post(spaces::create_space))
+        .route(
+            "/api/spaces/{space_id}",
+            get(spaces::get_space).delete(spaces::delete_space),
+        )
+        .route("/api/spaces/{space_id}/members", post(spaces::set_member))
+        .route(
+            "/api/spaces/{space_id}/members/{member_id}",
+            delete(spaces::remove_member),
+        )
+        // =================================================================
         // COMPRESSION
         // =================================================================
         .route("/api/memory/compress", post(compression::compress_memory))
@@ -142,6 +187,7 @@ pub fn build_protected_routes(state: AppState) -> Router {
             post(consolidation::repair_vector_index),
         )
         .route("/api/index/rebuild", post(consolidation::rebuild_index))
+        .route("/api/index/reembed", post(consolidation::reembed_memories))
         .route(
             "/api/storage/cleanup",
             post(consolidation::cleanup_corrupted),
@@ -167,6 +213,7 @@ pub fn build_protected_routes(state: AppState) -> Router {
         .route("/api/backups", post(consolidation::list_backups)) // MCP alias
         .route("/api/backup/verify", post(consolidation::verify_backup))
         .route("/api/backup/purge", post(consolidation::purge_backups))
+        .route("/api/backup/restore", post(consolidation::restore_backup))
         .route("/api/backups/purge", post(consolidation::purge_backups)) // MCP alias
         // =================================================================
         // FACTS
@@ -189,6 +236,10 @@ pub fn build_protected_routes(state: AppState) -> Router {
             post(lineage::lineage_list_branches),
         )
         .route("/api/lineage/branch", post(lineage::lineage_create_branch))
+        .route(
+            "/api/lineage/postmortem/{subject_id}",
+            post(lineage::lineage_post_mortem),
+        )
         // =================================================================
         // KNOWLEDGE GRAPH (ADVANCED)
         // =================================================================
@@ -213,6 +264,16 @@ pub fn build_protected_routes(state: AppState) -> Router {
         )
         .route("/api/graph/traverse", post(graph::traverse_graph))
         .route("/api/graph/episode/get", post(graph::get_episode))
+        .route("/api/graph/entity/merge", post(graph::merge_entities))
+        .route(
+            "/api/graph/entity/merge/undo",
+            post(graph::undo_entity_merge),
+        )
+        .route("/api/graph/entity/merges", post(graph::list_entity_merges))
+        .route("/api/graph/entity/alias", post(graph::set_entity_alias))
+        .route("/api/graph/entity/resolve", post(graph::resolve_entities))
+        .route("/api/graph/query", post(graph::graph_query))
+        .route("/api/graph/analytics", post(graph::graph_analytics))
         // =================================================================
         // KNOWLEDGE GRAPH (BASIC)
         // =================================================================
@@ -249,6 +310,7 @@ pub fn build_protected_routes(state: AppState) -> Router {
         .route("/api/todos/delete", post(todos::delete_todo))
         .route("/api/todos/reorder", post(todos::reorder_todo))
         .route("/api/todos/due", post(todos::list_due_todos))
+        .route("/api/todos/next", post(todos::list_next_todos))
         .route("/api/todos/{todo_id}", get(todos::get_todo))
         .route("/api/todos/{todo_id}", delete(todos::delete_todo)) // TUI uses DELETE
         .route("/api/todos/{todo_id}/update", post(todos::update_todo)) // TUI path style
@@ -291,6 +353,14 @@ pub fn build_protected_routes(state: AppState) -> Router {
             "/api/projects/{project_id}/delete",
             post(todos::delete_project),
         )
+        .route(
+            "/api/projects/{project_id}/dependencies",
+            get(todos::get_project_dependencies),
+        )
+        .route(
+            "/api/projects/{project_id}/critical-path",
+            get(todos::get_project_critical_path),
+        )
         // =================================================================
         // FILE MEMORY / CODEBASE INTEGRATION
         // =================================================================
@@ -310,7 +380,12 @@ pub fn build_protected_routes(state: AppState) -> Router {
             "/api/projects/{project_id}/files/search",
             post(files::search_project_files),
         )
+        .route(
+            "/api/projects/{project_id}/watch",
+            post(files::watch_project_codebase).delete(files::unwatch_project_codebase),
+        )
         .route("/api/files/stats", get(files::get_file_stats))
+        .route("/api/files/watches", get(files::list_codebase_watches))
         // =================================================================
         // REMINDERS
         // =================================================================
@@ -337,6 +412,7 @@ pub fn build_protected_routes(state: AppState) -> Router {
         .route("/api/sessions", post(sessions::list_sessions))
         .route("/api/sessions/stats", get(sessions::get_session_stats))
         .route("/api/sessions/end", post(sessions::end_session))
+        .route("/api/sessions/query", post(sessions::query_sessions))
         .route("/api/sessions/{session_id}", get(sessions::get_session))
         // =================================================================
         // A/B TESTING
@@ -400,6 +476,16 @@ pub fn build_protected_routes(state: AppState) -> Router {
         .route("/api/search/robotics", post(mif::robotics_search))
         .route("/api/export/mif", post(mif::export_mif))
         .route("/api/import/mif", post(mif::import_mif))
+        .route("/api/export/stream", post(export::export_stream))
+        .route("/api/import/stream", post(export::import_stream))
+        .route("/api/export/ics", post(ics::export_ics))
+        .route("/api/import/ics", post(ics::import_ics))
+        // =================================================================
+        // MCP (STREAMABLE HTTP + LEGACY SSE)
+        // =================================================================
+        .route_service("/mcp", mcp::streamable_http_service(state.clone()))
+        .route("/mcp/sse", get(mcp::legacy_sse))
+        .route("/mcp/message", post(mcp::legacy_message))
         // =================================================================
         // STATE
         // =================================================================
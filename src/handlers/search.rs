@@ -23,6 +23,9 @@ pub struct AdvancedSearchRequest {
     pub end_date: Option<String>,
     pub min_importance: Option<f32>,
     pub max_importance: Option<f32>,
+    pub agent_id: Option<String>,
+    pub run_id: Option<String>,
+    pub actor_id: Option<String>,
 }
 
 /// POST /api/search/advanced - Advanced memory search with entity filtering
@@ -70,6 +73,16 @@ pub async fn advanced_search(
         criterias.push(memory::storage::SearchCriteria::ByImportance { min, max });
     }
 
+    if let Some(agent_id) = req.agent_id {
+        criterias.push(memory::storage::SearchCriteria::ByAgent(agent_id));
+    }
+    if let Some(run_id) = req.run_id {
+        criterias.push(memory::storage::SearchCriteria::ByRun(run_id));
+    }
+    if let Some(actor_id) = req.actor_id {
+        criterias.push(memory::storage::SearchCriteria::ByActor(actor_id));
+    }
+
     // Execute combined search
     if criterias.is_empty() {
         return Err(AppError::InvalidInput {
@@ -10,7 +10,9 @@ use serde::{Deserialize, Serialize};
 
 use super::state::MultiUserMemoryManager;
 use crate::errors::{AppError, ValidationErrorExt};
-use crate::memory::{Session, SessionId, SessionStatus, SessionStoreStats, SessionSummary};
+use crate::memory::{
+    Session, SessionId, SessionQuery, SessionStatus, SessionStoreStats, SessionSummary,
+};
 use crate::validation;
 use std::sync::Arc;
 
@@ -40,6 +42,38 @@ pub struct ListSessionsResponse {
     pub count: usize,
 }
 
+/// Request for querying session history
+#[derive(Debug, Deserialize)]
+pub struct QuerySessionsRequest {
+    pub user_id: String,
+    /// Only sessions started at or after this time
+    #[serde(default)]
+    pub start: Option<chrono::DateTime<chrono::Utc>>,
+    /// Only sessions started before this time
+    #[serde(default)]
+    pub end: Option<chrono::DateTime<chrono::Utc>>,
+    /// Only sessions that worked on this project
+    #[serde(default)]
+    pub project: Option<String>,
+    /// Only sessions that created, surfaced or used this memory
+    #[serde(default)]
+    pub memory_id: Option<String>,
+    #[serde(default = "default_sessions_limit")]
+    pub limit: usize,
+    /// `next_cursor` from the previous page
+    #[serde(default)]
+    pub cursor: Option<String>,
+}
+
+/// Response for querying session history
+#[derive(Debug, Serialize)]
+pub struct QuerySessionsResponse {
+    pub success: bool,
+    pub sessions: Vec<SessionSummary>,
+    pub count: usize,
+    pub next_cursor: Option<String>,
+}
+
 /// Request for getting a specific session
 #[derive(Debug, Deserialize)]
 pub struct GetSessionRequest {
@@ -94,6 +128,36 @@ pub async fn list_sessions(
     }))
 }
 
+/// POST /api/sessions/query - Page through session history with filters
+pub async fn query_sessions(
+    State(state): State<AppState>,
+    Json(req): Json<QuerySessionsRequest>,
+) -> Result<Json<QuerySessionsResponse>, AppError> {
+    validation::validate_user_id(&req.user_id).map_validation_err("user_id")?;
+
+    let query = SessionQuery {
+        user_id: req.user_id,
+        start: req.start,
+        end: req.end,
+        project: req.project,
+        // Accept MIF-style "mem_<uuid>" IDs as well as bare UUIDs
+        memory_id: req
+            .memory_id
+            .map(|id| id.strip_prefix("mem_").unwrap_or(&id).to_string()),
+        limit: req.limit,
+        cursor: req.cursor,
+    };
+    let page = state.session_store.query_sessions(&query);
+    let count = page.sessions.len();
+
+    Ok(Json(QuerySessionsResponse {
+        success: true,
+        sessions: page.sessions,
+        count,
+        next_cursor: page.next_cursor,
+    }))
+}
+
 /// GET /api/sessions/{session_id} - Get a specific session
 pub async fn get_session(
     State(state): State<AppState>,
@@ -107,11 +171,17 @@ pub async fn get_session(
         reason: format!("Invalid UUID: {e}"),
     })?;
     let sid = SessionId(uuid);
-    let session = state.session_store.get_session(&sid);
+    // Scoped keys are only checked against req.user_id, so a session owned by
+    // another user must look exactly like a missing one
+    let session = state
+        .session_store
+        .get_session(&sid)
+        .filter(|session| session.user_id == req.user_id)
+        .ok_or_else(|| AppError::SessionNotFound(session_id.clone()))?;
 
     Ok(Json(GetSessionResponse {
-        success: session.is_some(),
-        session,
+        success: true,
+        session: Some(session),
     }))
 }
 
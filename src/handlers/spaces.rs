@@ -0,0 +1,291 @@
+//! Shared Space Handlers
+//!
+//! Space creation, membership management, and the access checks used by
+//! `remember`, `recall` and `proactive_context` when they target spaces.
+//! Spaces a caller doesn't belong to are reported as not found, so space ids
+//! can't be probed.
+
+use axum::{
+    extract::{Path, Query, State},
+    response::Json,
+};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+use super::state::MultiUserMemoryManager;
+use crate::errors::{AppError, ValidationErrorExt};
+use crate::memory::MemorySystem;
+use crate::spaces::{Space, SpaceRole, MAX_SPACES_PER_QUERY};
+use crate::validation;
+
+type AppState = Arc<MultiUserMemoryManager>;
+
+/// A space's memory system, paired with its id
+pub(crate) type SpaceMemory = (String, Arc<parking_lot::RwLock<MemorySystem>>);
+
+/// Request for POST /api/spaces
+#[derive(Debug, Deserialize)]
+pub struct CreateSpaceRequest {
+    /// Creator; becomes the space's first owner
+    pub user_id: String,
+    pub space_id: String,
+    /// Display name (defaults to the space id)
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(default)]
+    pub description: Option<String>,
+}
+
+/// Query for space endpoints scoped to the calling user
+#[derive(Debug, Deserialize)]
+pub struct SpaceQuery {
+    pub user_id: String,
+}
+
+/// Request for POST /api/spaces/{space_id}/members
+#[derive(Debug, Deserialize)]
+pub struct SetMemberRequest {
+    /// Caller; must own the space
+    pub user_id: String,
+    pub member_id: String,
+    pub role: SpaceRole,
+}
+
+/// Space summary in GET /api/spaces
+#[derive(Debug, Serialize)]
+pub struct SpaceMembership {
+    #[serde(flatten)]
+    pub space: Space,
+    /// The caller's role in this space
+    pub role: SpaceRole,
+}
+
+/// Response for GET /api/spaces
+#[derive(Debug, Serialize)]
+pub struct ListSpacesResponse {
+    pub spaces: Vec<SpaceMembership>,
+    pub count: usize,
+}
+
+/// The caller's role in a space, treating non-membership as not found
+fn require_role(state: &AppState, space_id: &str, user_id: &str) -> Result<SpaceRole, AppError> {
+    validation::validate_space_id(space_id).map_validation_err("space_id")?;
+    state
+        .space_store
+        .role_of(space_id, user_id)
+        .map_err(AppError::Internal)?
+        .ok_or_else(|| AppError::SpaceNotFound(space_id.to_string()))
+}
+
+fn require_owner(state: &AppState, space_id: &str, user_id: &str) -> Result<(), AppError> {
+    if require_role(state, space_id, user_id)?.can_manage() {
+        Ok(())
+    } else {
+        Err(AppError::Forbidden(format!(
+            "only owners can manage space '{space_id}'"
+        )))
+    }
+}
+
+/// Resolve the memory system `remember` should write to for a space.
+///
+/// The caller needs write (or owner) membership.
+pub(crate) fn writable_space(
+    state: &AppState,
+    user_id: &str,
+    space_id: &str,
+) -> Result<Arc<parking_lot::RwLock<MemorySystem>>, AppError> {
+    if !require_role(state, space_id, user_id)?.can_write() {
+        return Err(AppError::Forbidden(format!(
+            "read-only membership in space '{space_id}'"
+        )));
+    }
+    state.get_space_memory(space_id).map_err(AppError::Internal)
+}
+
+/// Resolve the spaces a recall fans out to, deduplicated in request order.
+///
+/// The caller needs at least read membership in each.
+pub(crate) fn readable_spaces(
+    state: &AppState,
+    user_id: &str,
+    space_ids: &[String],
+) -> Result<Vec<SpaceMemory>, AppError> {
+    let mut resolved: Vec<SpaceMemory> = Vec::new();
+    for space_id in space_ids {
+        if resolved.iter().any(|(id, _)| id == space_id) {
+            continue;
+        }
+        if resolved.len() == MAX_SPACES_PER_QUERY {
+            return Err(AppError::InvalidInput {
+                field: "spaces".to_string(),
+                reason: format!("at most {MAX_SPACES_PER_QUERY} spaces per query"),
+            });
+        }
+        require_role(state, space_id, user_id)?;
+        let memory = state
+            .get_space_memory(space_id)
+            .map_err(AppError::Internal)?;
+        resolved.push((space_id.clone(), memory));
+    }
+    Ok(resolved)
+}
+
+/// POST /api/spaces - Create a shared space owned by the caller
+pub async fn create_space(
+    State(state): State<AppState>,
+    Json(req): Json<CreateSpaceRequest>,
+) -> Result<Json<Space>, AppError> {
+    validation::validate_user_id(&req.user_id).map_validation_err("user_id")?;
+    validation::validate_space_id(&req.space_id).map_validation_err("space_id")?;
+
+    let space = state
+        .space_store
+        .create(&req.space_id, req.name, req.description, &req.user_id)
+        .map_err(|e| AppError::InvalidInput {
+            field: "space_id".to_string(),
+            reason: format!("{e:#}"),
+        })?;
+
+    state.log_event(
+        &req.user_id,
+        "SPACE_CREATE",
+        &space.id,
+        &format!("Created space '{}'", space.name),
+    );
+
+    Ok(Json(space))
+}
+
+/// GET /api/spaces?user_id= - List the spaces the caller belongs to
+pub async fn list_spaces(
+    State(state): State<AppState>,
+    Query(query): Query<SpaceQuery>,
+) -> Result<Json<ListSpacesResponse>, AppError> {
+    validation::validate_user_id(&query.user_id).map_validation_err("user_id")?;
+
+    let spaces: Vec<SpaceMembership> = state
+        .space_store
+        .spaces_for_user(&query.user_id)
+        .map_err(AppError::Internal)?
+        .into_iter()
+        .map(|(space, role)| SpaceMembership { space, role })
+        .collect();
+    let count = spaces.len();
+    Ok(Json(ListSpacesResponse { spaces, count }))
+}
+
+/// GET /api/spaces/{space_id}?user_id= - Get a space (members only)
+pub async fn get_space(
+    State(state): State<AppState>,
+    Path(space_id): Path<String>,
+    Query(query): Query<SpaceQuery>,
+) -> Result<Json<Space>, AppError> {
+    validation::validate_user_id(&query.user_id).map_validation_err("user_id")?;
+    require_role(&state, &space_id, &query.user_id)?;
+
+    state
+        .space_store
+        .get(&space_id)
+        .map_err(AppError::Internal)?
+        .map(Json)
+        .ok_or(AppError::SpaceNotFound(space_id))
+}
+
+/// DELETE /api/spaces/{space_id}?user_id= - Delete a space and all its memories (owners only)
+pub async fn delete_space(
+    State(state): State<AppState>,
+    Path(space_id): Path<String>,
+    Query(query): Query<SpaceQuery>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    validation::validate_user_id(&query.user_id).map_validation_err("user_id")?;
+    require_owner(&state, &space_id, &query.user_id)?;
+
+    let deleted = {
+        let state = state.clone();
+        let space_id = space_id.clone();
+        tokio::task::spawn_blocking(move || state.delete_space(&space_id))
+            .await
+            .map_err(|e| AppError::Internal(anyhow::anyhow!("Blocking task panicked: {e}")))?
+            .map_err(AppError::Internal)?
+    };
+    if !deleted {
+        return Err(AppError::SpaceNotFound(space_id));
+    }
+
+    state.log_event(&query.user_id, "SPACE_DELETE", &space_id, "Deleted space");
+
+    Ok(Json(serde_json::json!({
+        "success": true,
+        "space_id": space_id,
+    })))
+}
+
+/// POST /api/spaces/{space_id}/members - Add a member or change their role (owners only)
+pub async fn set_member(
+    State(state): State<AppState>,
+    Path(space_id): Path<String>,
+    Json(req): Json<SetMemberRequest>,
+) -> Result<Json<Space>, AppError> {
+    validation::validate_user_id(&req.user_id).map_validation_err("user_id")?;
+    validation::validate_user_id(&req.member_id).map_validation_err("member_id")?;
+    require_owner(&state, &space_id, &req.user_id)?;
+
+    let space = state
+        .space_store
+        .set_member(&space_id, &req.member_id, req.role)
+        .map_err(|e| AppError::InvalidInput {
+            field: "role".to_string(),
+            reason: format!("{e:#}"),
+        })?;
+
+    state.log_event(
+        &req.user_id,
+        "SPACE_MEMBER_SET",
+        &space_id,
+        &format!("Set {} to {:?}", req.member_id, req.role),
+    );
+
+    Ok(Json(space))
+}
+
+/// DELETE /api/spaces/{space_id}/members/{member_id}?user_id= - Remove a member
+///
+/// Owners can remove anyone; other members can only remove themselves.
+pub async fn remove_member(
+    State(state): State<AppState>,
+    Path((space_id, member_id)): Path<(String, String)>,
+    Query(query): Query<SpaceQuery>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    validation::validate_user_id(&query.user_id).map_validation_err("user_id")?;
+    validation::validate_user_id(&member_id).map_validation_err("member_id")?;
+    if member_id == query.user_id {
+        require_role(&state, &space_id, &query.user_id)?;
+    } else {
+        require_owner(&state, &space_id, &query.user_id)?;
+    }
+
+    let removed = state
+        .space_store
+        .remove_member(&space_id, &member_id)
+        .map_err(|e| AppError::InvalidInput {
+            field: "member_id".to_string(),
+            reason: format!("{e:#}"),
+        })?;
+    if !removed {
+        return Err(AppError::UserNotFound(member_id));
+    }
+
+    state.log_event(
+        &query.user_id,
+        "SPACE_MEMBER_REMOVE",
+        &space_id,
+        &format!("Removed {member_id}"),
+    );
+
+    Ok(Json(serde_json::json!({
+        "success": true,
+        "space_id": space_id,
+        "member_id": member_id,
+    })))
+}
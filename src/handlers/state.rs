@@ -5,8 +5,9 @@
 //! subsidiary stores (todos, reminders, files, etc.).
 
 use anyhow::{Context, Result};
-use dashmap::DashMap;
+use dashmap::{DashMap, DashSet};
 use std::collections::{HashMap, VecDeque};
+use std::path::{Path, PathBuf};
 use std::sync::{Arc, OnceLock};
 use tracing::info;
 
@@ -23,21 +24,26 @@ fn issue_regex() -> &'static regex::Regex {
 }
 
 use crate::ab_testing;
+use crate::api_keys::ApiKeyStore;
 use crate::backup;
 use crate::config::ServerConfig;
+use crate::embeddings::cross_encoder::{CrossEncoder, CrossEncoderConfig};
 use crate::embeddings::{
     are_ner_models_downloaded, download_ner_models, get_ner_models_dir, ner::NerEntityType,
-    KeywordExtractor, NerConfig, NeuralNer,
+    Embedder, EmbeddingBackend, KeywordExtractor, NerConfig, NeuralNer,
 };
 use crate::graph_memory::{
-    EdgeTier, EntityLabel, EntityNode, EpisodeSource, EpisodicNode, GraphMemory, GraphStats,
-    LtpStatus, RelationType, RelationshipEdge,
+    EdgeTier, EntityLabel, EntityNode, EntityResolverConfig, EpisodeSource, EpisodicNode,
+    GraphMemory, GraphStats, LtpStatus, RelationType, RelationshipEdge,
 };
+use crate::memory::file_watcher::FileWatcherRegistry;
 use crate::memory::{
     query_parser, Experience, FeedbackStore, FileMemoryStore, MemoryConfig, MemoryId, MemoryStats,
-    MemorySystem, ProspectiveStore, SessionStore, TodoStore,
+    MemorySystem, ProspectiveStore, SessionRetention, SessionStore, TodoStore,
 };
+use crate::outbound_webhooks::{WebhookEventType, WebhookStore};
 use crate::relevance::RelevanceEngine;
+use crate::spaces::{self, SpaceStore};
 use crate::streaming;
 
 use super::types::{AuditEvent, ContextStatus, MemoryEvent};
@@ -45,6 +51,140 @@ use super::types::{AuditEvent, ContextStatus, MemoryEvent};
 /// Type alias for context sessions map
 pub type ContextSessions = DashMap<String, ContextStatus>;
 
+/// Store name (and directory under the user path) of the secondary memory index
+const MEMORY_INDEX_STORE: &str = "memory_index";
+
+/// Directory under the user path holding the graph databases
+const GRAPH_DIR: &str = "graph";
+
+/// Directory under the base path where restores are staged
+const RESTORE_STAGING_DIR: &str = ".restore";
+
+/// How long a restore waits for in-flight requests to release a user's stores
+const RESTORE_DRAIN_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(30);
+
+/// Shared stores an online restore never rolls back: audit history is
+/// append-only evidence, undoing key revocations would be a security hole,
+/// and the rest are mirrored in memory at startup
+const RESTORE_EXCLUDED_STORES: &[&str] =
+    &["audit_logs", "api_keys", "ab_tests", "sessions", "feedback"];
+
+/// Location of a per-user store relative to the user directory (None for shared stores)
+fn user_store_path(name: &str) -> Option<PathBuf> {
+    if name == MEMORY_INDEX_STORE {
+        Some(PathBuf::from(name))
+    } else if name.starts_with("graph_") {
+        Some(Path::new(GRAPH_DIR).join(name))
+    } else {
+        None
+    }
+}
+
+/// Move staged per-user stores into the user directory, putting the
+/// originals back if any move fails
+fn swap_in_staged(staging: &Path, user_path: &Path) -> Result<()> {
+    let pre_restore = user_path.join(".pre_restore");
+    if pre_restore.exists() {
+        std::fs::remove_dir_all(&pre_restore)?;
+    }
+    std::fs::create_dir_all(&pre_restore)?;
+
+    let mut swapped = Vec::new();
+    let mut failure = None;
+    for name in [backup::MAIN_STORE, MEMORY_INDEX_STORE, GRAPH_DIR] {
+        let staged = staging.join(name);
+        if !staged.exists() {
+            continue;
+        }
+        let live = user_path.join(name);
+        if live.exists() {
+            if let Err(e) = std::fs::rename(&live, pre_restore.join(name)) {
+                failure = Some(e);
+                break;
+            }
+        }
+        swapped.push(name);
+        if let Err(e) = std::fs::rename(&staged, &live) {
+            failure = Some(e);
+            break;
+        }
+    }
+
+    if let Some(e) = failure {
+        for name in swapped.into_iter().rev() {
+            let live = user_path.join(name);
+            let original = pre_restore.join(name);
+            let rollback = (|| -> std::io::Result<()> {
+                if live.exists() {
+                    std::fs::remove_dir_all(&live)?;
+                }
+                if original.exists() {
+                    std::fs::rename(&original, &live)?;
+                }
+                Ok(())
+            })();
+            if let Err(rollback_err) = rollback {
+                tracing::error!(
+                    store = name,
+                    error = %rollback_err,
+                    "Failed to roll back store after failed restore, original kept in {:?}",
+                    original
+                );
+            }
+        }
+        return Err(anyhow::anyhow!(
+            "Failed to move restored stores into place: {e}"
+        ));
+    }
+
+    std::fs::remove_dir_all(&pre_restore)?;
+    Ok(())
+}
+
+/// Wait until `handle` is the last reference, then drop it
+fn wait_for_release<T>(handle: Arc<T>, deadline: std::time::Instant, what: &str) -> Result<()> {
+    while Arc::strong_count(&handle) > 1 {
+        if std::time::Instant::now() >= deadline {
+            anyhow::bail!("Timed out waiting for in-flight requests to release the {what}");
+        }
+        std::thread::sleep(std::time::Duration::from_millis(50));
+    }
+    Ok(())
+}
+
+/// Clears a user's restoring flag when the restore ends, including on error
+struct RestoringGuard<'a> {
+    users: &'a DashSet<String>,
+    user_id: &'a str,
+}
+
+impl Drop for RestoringGuard<'_> {
+    fn drop(&mut self) {
+        self.users.remove(self.user_id);
+    }
+}
+
+/// Archive the WAL of a user's stores as they leave the cache
+///
+/// The periodic archiver only visits cached users, so batches written since
+/// its last run would otherwise age out of the WAL and break PITR.
+fn archive_evicted_wal<'a>(
+    engine: &backup::ShodhBackupEngine,
+    user_id: &str,
+    stores: impl IntoIterator<Item = (&'a str, &'a Arc<rocksdb::DB>)>,
+) {
+    for (store, db) in stores {
+        if let Err(e) = engine.archive_wal(user_id, store, db) {
+            tracing::warn!(
+                user_id = %user_id,
+                store = store,
+                error = %e,
+                "Failed to archive WAL of evicted store"
+            );
+        }
+    }
+}
+
 /// Helper struct for audit log rotation (allows spawn_blocking with minimal clone)
 struct MultiUserMemoryManagerRotationHelper {
     audit_db: Arc<rocksdb::DB>,
@@ -170,6 +310,12 @@ pub struct MultiUserMemoryManager {
     /// Default config
     pub default_config: MemoryConfig,
 
+    /// Embedder shared by all users' memory systems, todos and files
+    pub embedder: Arc<dyn Embedder>,
+
+    /// Reranking cross-encoder shared by all users' hybrid search engines
+    pub cross_encoder: Arc<CrossEncoder>,
+
     /// Counter for audit log rotation checks
     pub audit_log_counter: Arc<std::sync::atomic::AtomicUsize>,
 
@@ -203,6 +349,9 @@ pub struct MultiUserMemoryManager {
     /// File memory store for codebase integration
     pub file_store: Arc<FileMemoryStore>,
 
+    /// Live watchers keeping indexed codebases in sync
+    pub file_watchers: Arc<FileWatcherRegistry>,
+
     /// Implicit feedback store for memory reinforcement
     pub feedback_store: Arc<parking_lot::RwLock<FeedbackStore>>,
 
@@ -223,6 +372,22 @@ pub struct MultiUserMemoryManager {
 
     /// Shared relevance engine for proactive memory surfacing (entity cache + learned weights persist)
     pub relevance_engine: Arc<RelevanceEngine>,
+
+    /// Scoped API keys (roles + allowed user_ids), consulted by the auth middleware
+    pub api_key_store: Arc<ApiKeyStore>,
+
+    /// Outbound webhook subscriptions, retry queue and delivery log
+    pub webhook_store: Arc<WebhookStore>,
+
+    /// Shared memory spaces and their memberships
+    pub space_store: Arc<SpaceStore>,
+
+    /// Users whose stores are being restored (requests for them fail until done)
+    pub restoring_users: Arc<DashSet<String>>,
+
+    /// Per-user lock: opening a user's stores holds it shared, a restore
+    /// holds it exclusively from eviction until the restored stores are swapped in
+    user_locks: Arc<DashMap<String, Arc<parking_lot::RwLock<()>>>>,
 }
 
 impl MultiUserMemoryManager {
@@ -300,6 +465,11 @@ impl MultiUserMemoryManager {
             }
         };
 
+        let backup_path = base_path.join("backups");
+        let backup_engine = Arc::new(backup::ShodhBackupEngine::new(backup_path)?);
+        let memory_eviction_backups = backup_engine.clone();
+        let graph_eviction_backups = backup_engine.clone();
+
         let user_evictions = Arc::new(std::sync::atomic::AtomicUsize::new(0));
         let evictions_clone = user_evictions.clone();
         let max_cache = server_config.max_users_in_memory;
@@ -328,6 +498,12 @@ impl MultiUserMemoryManager {
                                 );
                             }
                         }
+                        let (db, index_db) = (guard.get_db(), guard.get_index_db());
+                        archive_evicted_wal(
+                            &memory_eviction_backups,
+                            &key,
+                            [(backup::MAIN_STORE, &db), (MEMORY_INDEX_STORE, &index_db)],
+                        );
                     } else {
                         // Lock contention during eviction - unusual but possible
                         tracing::warn!(
@@ -341,23 +517,70 @@ impl MultiUserMemoryManager {
 
         let graph_memories = moka::sync::Cache::builder()
             .max_capacity(server_config.max_users_in_memory as u64)
-            .eviction_listener(move |key: Arc<String>, _value, _cause| {
-                info!("Evicted graph for user '{}' from memory cache (LRU)", key);
-            })
+            .eviction_listener(
+                move |key: Arc<String>, value: Arc<parking_lot::RwLock<GraphMemory>>, cause| {
+                    if cause == moka::notification::RemovalCause::Size {
+                        if let Some(graph) = value.try_read() {
+                            archive_evicted_wal(&graph_eviction_backups, &key, graph.databases());
+                        }
+                        info!("Evicted graph for user '{}' from memory cache (LRU)", key);
+                    }
+                },
+            )
             .build();
 
         let prospective_store = Arc::new(ProspectiveStore::new(&base_path)?);
         info!("Prospective memory store initialized");
 
-        let todo_store = Arc::new(TodoStore::new(&base_path)?);
+        // Load the embedding model once; every store shares it
+        let embedder = EmbeddingBackend::from_env()?
+            .build()
+            .context("Failed to initialize embedding backend")?;
+        info!(
+            "Embedding backend initialized ({}, {} dims)",
+            embedder.model_id(),
+            embedder.dimension()
+        );
+
+        // Loaded in the background at startup, then shared like the embedder
+        let cross_encoder = Arc::new(CrossEncoder::new(CrossEncoderConfig::default()));
+
+        let todo_store = Arc::new(TodoStore::new(&base_path)?.with_embedder(embedder.clone()));
         if let Err(e) = todo_store.load_vector_indices() {
             tracing::warn!("Failed to load todo vector indices: {}, semantic todo search will rebuild on first use", e);
         }
+        todo_store
+            .sync_embedding_model()
+            .context("Failed to re-embed todos for the configured embedding model")?;
         info!("Todo store initialized");
 
-        let file_store = Arc::new(FileMemoryStore::new(&base_path)?);
+        let file_store =
+            Arc::new(FileMemoryStore::new(&base_path)?.with_embedder(embedder.clone()));
         info!("File memory store initialized");
 
+        let api_key_store = Arc::new(ApiKeyStore::new(&base_path)?);
+        info!("API key store initialized");
+
+        let webhook_store = Arc::new(
+            WebhookStore::new(&base_path)?
+                .with_allowed_hosts(server_config.webhook_allowed_hosts.iter().cloned()),
+        );
+        info!("Webhook store initialized");
+
+        let space_store = Arc::new(SpaceStore::new(&base_path)?);
+        info!("Space registry initialized");
+
+        let ab_test_manager = Arc::new(ab_testing::ABTestManager::with_persistence(&base_path)?);
+
+        let session_store = Arc::new(SessionStore::with_persistence(
+            &base_path,
+            SessionRetention {
+                max_age_days: server_config.session_retention_days,
+                max_sessions_per_user: server_config.session_max_per_user,
+            },
+        )?);
+        info!("A/B test store initialized");
+
         let feedback_store = Arc::new(parking_lot::RwLock::new(
             FeedbackStore::with_persistence(base_path.join("feedback")).unwrap_or_else(|e| {
                 tracing::warn!("Failed to load feedback store: {}, using in-memory", e);
@@ -378,8 +601,6 @@ impl MultiUserMemoryManager {
         let relevance_engine = Arc::new(RelevanceEngine::new(neural_ner.clone()));
         info!("Relevance engine initialized (entity cache + learned weights)");
 
-        let backup_path = base_path.join("backups");
-        let backup_engine = Arc::new(backup::ShodhBackupEngine::new(backup_path)?);
         if server_config.backup_enabled {
             info!(
                 "Backup engine initialized (interval: {}h, keep: {})",
@@ -395,7 +616,9 @@ impl MultiUserMemoryManager {
             audit_logs: Arc::new(DashMap::new()),
             audit_db,
             base_path,
-            default_config: MemoryConfig::default(),
+            default_config: server_config.memory.clone(),
+            embedder,
+            cross_encoder,
             audit_log_counter: Arc::new(std::sync::atomic::AtomicUsize::new(0)),
             graph_memories,
             neural_ner,
@@ -407,6 +630,7 @@ impl MultiUserMemoryManager {
             prospective_store,
             todo_store,
             file_store,
+            file_watchers: Arc::new(FileWatcherRegistry::new()),
             feedback_store,
             backup_engine,
             context_sessions: Arc::new(DashMap::new()),
@@ -414,9 +638,14 @@ impl MultiUserMemoryManager {
                 let (tx, _) = tokio::sync::broadcast::channel(16);
                 tx
             },
-            ab_test_manager: Arc::new(ab_testing::ABTestManager::new()),
-            session_store: Arc::new(SessionStore::new()),
+            ab_test_manager,
+            session_store,
             relevance_engine,
+            api_key_store,
+            webhook_store,
+            space_store,
+            restoring_users: Arc::new(DashSet::new()),
+            user_locks: Arc::new(DashMap::new()),
         };
 
         info!("Running initial audit log rotation...");
@@ -429,6 +658,10 @@ impl MultiUserMemoryManager {
 
     /// Log audit event (non-blocking with background persistence)
     pub fn log_event(&self, user_id: &str, event_type: &str, memory_id: &str, details: &str) {
+        if !self.server_config.audit_enabled {
+            return;
+        }
+
         let event = AuditEvent {
             timestamp: chrono::Utc::now(),
             event_type: event_type.to_string(),
@@ -496,7 +729,17 @@ impl MultiUserMemoryManager {
     }
 
     /// Emit SSE event to all connected dashboard clients
+    /// and queue it for any matching outbound webhooks
     pub fn emit_event(&self, event: MemoryEvent) {
+        if let Some(webhook_event) = WebhookEventType::from_memory_event(&event.event_type) {
+            let data = serde_json::to_value(&event).unwrap_or_default();
+            if let Err(e) = self
+                .webhook_store
+                .enqueue(&event.user_id, webhook_event, data)
+            {
+                tracing::warn!("Failed to queue webhook deliveries: {}", e);
+            }
+        }
         let _ = self.event_broadcaster.send(event);
     }
 
@@ -556,8 +799,24 @@ impl MultiUserMemoryManager {
         }
     }
 
+    fn user_lock(&self, user_id: &str) -> Arc<parking_lot::RwLock<()>> {
+        self.user_locks
+            .entry(user_id.to_string())
+            .or_default()
+            .clone()
+    }
+
     /// Get or create memory system for a user
     pub fn get_user_memory(&self, user_id: &str) -> Result<Arc<parking_lot::RwLock<MemorySystem>>> {
+        // Held until the store is cached, so a restore cannot swap files
+        // underneath an open in progress
+        let lock = self.user_lock(user_id);
+        let Some(_open) = lock
+            .try_read()
+            .filter(|_| !self.restoring_users.contains(user_id))
+        else {
+            anyhow::bail!("User '{user_id}' is being restored from a backup, retry shortly");
+        };
         if let Some(memory) = self.user_memories.get(user_id) {
             return Ok(memory);
         }
@@ -568,8 +827,11 @@ impl MultiUserMemoryManager {
             ..self.default_config.clone()
         };
 
-        let mut memory_system = MemorySystem::new(config)
-            .with_context(|| format!("Failed to initialize memory system for user '{user_id}'"))?;
+        let mut memory_system =
+            MemorySystem::with_models(config, self.embedder.clone(), self.cross_encoder.clone())
+                .with_context(|| {
+                    format!("Failed to initialize memory system for user '{user_id}'")
+                })?;
         // Wire up GraphMemory for Layer 2 (spreading activation) and Layer 5 (Hebbian learning)
         let graph = self.get_user_graph(user_id)?;
         memory_system.set_graph_memory(graph);
@@ -647,6 +909,11 @@ impl MultiUserMemoryManager {
             }
         }
 
+        match memory_guard.agent_stats() {
+            Ok(agents) => stats.agents = agents,
+            Err(e) => tracing::warn!("Failed to read per-agent stats for {}: {}", user_id, e),
+        }
+
         Ok(stats)
     }
 
@@ -659,13 +926,8 @@ impl MultiUserMemoryManager {
                     if file_type.is_dir() {
                         if let Some(name) = entry.file_name().to_str() {
                             // Filter out system directories
-                            if name != "audit_logs"
-                                && name != "backups"
-                                && name != "feedback"
-                                && name != "semantic_facts"
-                                && name != "files"
-                                && name != "prospective"
-                                && name != "todos"
+                            if !crate::validation::RESERVED_USER_IDS.contains(&name)
+                                && !name.starts_with('.')
                             {
                                 users.push(name.to_string());
                             }
@@ -734,6 +996,36 @@ impl MultiUserMemoryManager {
             info!("  Feedback store flushed");
         }
 
+        if let Err(e) = self.api_key_store.flush() {
+            tracing::warn!("  Failed to flush API key store: {}", e);
+        } else {
+            info!("  API key store flushed");
+        }
+
+        if let Err(e) = self.webhook_store.flush() {
+            tracing::warn!("  Failed to flush webhook store: {}", e);
+        } else {
+            info!("  Webhook store flushed");
+        }
+
+        if let Err(e) = self.space_store.flush() {
+            tracing::warn!("  Failed to flush space registry: {}", e);
+        } else {
+            info!("  Space registry flushed");
+        }
+
+        if let Err(e) = self.ab_test_manager.flush() {
+            tracing::warn!("  Failed to flush A/B test store: {}", e);
+        } else {
+            info!("  A/B test store flushed");
+        }
+
+        if let Err(e) = self.session_store.flush() {
+            tracing::warn!("  Failed to flush session store: {}", e);
+        } else {
+            info!("  Session store flushed");
+        }
+
         let user_entries: Vec<(String, Arc<parking_lot::RwLock<MemorySystem>>)> = self
             .user_memories
             .iter()
@@ -754,7 +1046,7 @@ impl MultiUserMemoryManager {
         }
 
         info!(
-            "All databases flushed: audit, todos, files, prospective, feedback, {} user memories",
+            "All databases flushed: audit, todos, files, prospective, feedback, api keys, ab tests, {} user memories",
             flushed
         );
 
@@ -851,12 +1143,23 @@ impl MultiUserMemoryManager {
 
     /// Get or create graph memory for a user
     pub fn get_user_graph(&self, user_id: &str) -> Result<Arc<parking_lot::RwLock<GraphMemory>>> {
+        let lock = self.user_lock(user_id);
+        let Some(_open) = lock
+            .try_read()
+            .filter(|_| !self.restoring_users.contains(user_id))
+        else {
+            anyhow::bail!("User '{user_id}' is being restored from a backup, retry shortly");
+        };
         if let Some(graph) = self.graph_memories.get(user_id) {
             return Ok(graph);
         }
 
         let graph_path = self.base_path.join(user_id).join("graph");
         let graph_memory = GraphMemory::new(&graph_path)?;
+        graph_memory.set_resolver_config(EntityResolverConfig {
+            min_confidence: self.server_config.entity_merge_confidence,
+            ..Default::default()
+        });
         let graph_arc = Arc::new(parking_lot::RwLock::new(graph_memory));
 
         self.graph_memories
@@ -890,6 +1193,8 @@ impl MultiUserMemoryManager {
         let mut edges_strengthened = 0;
         let mut total_facts_extracted = 0;
         let mut total_facts_reinforced = 0;
+        let mut analytics_runs = 0;
+        let analytics_interval = self.server_config.graph_analytics_interval_secs;
 
         for user_id in user_ids {
             let maintenance_result = if let Ok(memory_lock) = self.get_user_memory(&user_id) {
@@ -899,6 +1204,7 @@ impl MultiUserMemoryManager {
                         total_processed += result.decayed_count;
                         total_facts_extracted += result.facts_extracted;
                         total_facts_reinforced += result.facts_reinforced;
+                        self.enqueue_consolidation_report(&user_id, &memory);
                         Some(result)
                     }
                     Err(e) => {
@@ -940,22 +1246,66 @@ impl MultiUserMemoryManager {
                         tracing::debug!("Graph decay failed for user {}: {}", user_id, e);
                     }
                 }
+
+                if Self::graph_analytics_due(&graph_guard, analytics_interval) {
+                    match graph_guard.run_analytics() {
+                        Ok(_) => analytics_runs += 1,
+                        Err(e) => {
+                            tracing::debug!("Graph analytics failed for user {}: {}", user_id, e);
+                        }
+                    }
+                }
             }
         }
 
         tracing::info!(
-            "Maintenance complete: {} memories processed, {} edges strengthened, {} weak edges pruned, {} facts extracted, {} facts reinforced across {} users",
+            "Maintenance complete: {} memories processed, {} edges strengthened, {} weak edges pruned, {} facts extracted, {} facts reinforced, {} graphs analyzed across {} users",
             total_processed,
             edges_strengthened,
             edges_decayed,
             total_facts_extracted,
             total_facts_reinforced,
+            analytics_runs,
             user_count
         );
 
         total_processed
     }
 
+    /// Whether a graph's analytics are older than `interval_secs` (0 = never scheduled)
+    fn graph_analytics_due(graph: &GraphMemory, interval_secs: u64) -> bool {
+        if interval_secs == 0 {
+            return false;
+        }
+        match graph.last_analytics() {
+            Some(report) => {
+                let age = chrono::Utc::now() - report.computed_at;
+                age.num_seconds() >= interval_secs as i64
+            }
+            None => true,
+        }
+    }
+
+    /// Queue the consolidation report covering the last maintenance window
+    /// for users with a `consolidation_report` webhook
+    fn enqueue_consolidation_report(&self, user_id: &str, memory: &MemorySystem) {
+        if !self
+            .webhook_store
+            .has_subscribers(user_id, WebhookEventType::ConsolidationReport)
+        {
+            return;
+        }
+        let window = chrono::Duration::seconds(self.server_config.maintenance_interval_secs as i64);
+        let report = memory.get_consolidation_report(chrono::Utc::now() - window, None);
+        let data = serde_json::to_value(&report).unwrap_or_default();
+        if let Err(e) =
+            self.webhook_store
+                .enqueue(user_id, WebhookEventType::ConsolidationReport, data)
+        {
+            tracing::warn!("Failed to queue consolidation report webhook: {}", e);
+        }
+    }
+
     /// Get the streaming extractor
     pub fn streaming_extractor(&self) -> &Arc<streaming::StreamingMemoryExtractor> {
         &self.streaming_extractor
@@ -986,6 +1336,11 @@ impl MultiUserMemoryManager {
         &self.file_store
     }
 
+    /// Get the codebase watcher registry
+    pub fn file_watchers(&self) -> &Arc<FileWatcherRegistry> {
+        &self.file_watchers
+    }
+
     /// Get the feedback store
     pub fn feedback_store(&self) -> &Arc<parking_lot::RwLock<FeedbackStore>> {
         &self.feedback_store
@@ -996,6 +1351,38 @@ impl MultiUserMemoryManager {
         &self.session_store
     }
 
+    /// Get the API key store
+    pub fn api_key_store(&self) -> &Arc<ApiKeyStore> {
+        &self.api_key_store
+    }
+
+    /// Get the outbound webhook store
+    pub fn webhook_store(&self) -> &Arc<WebhookStore> {
+        &self.webhook_store
+    }
+
+    /// Get the shared space registry
+    pub fn space_store(&self) -> &Arc<SpaceStore> {
+        &self.space_store
+    }
+
+    /// Get (or load) the memory system backing a shared space
+    pub fn get_space_memory(
+        &self,
+        space_id: &str,
+    ) -> Result<Arc<parking_lot::RwLock<MemorySystem>>> {
+        self.get_user_memory(&spaces::storage_id(space_id))
+    }
+
+    /// Delete a shared space: registry record plus all of its memory data
+    pub fn delete_space(&self, space_id: &str) -> Result<bool> {
+        if self.space_store.delete(space_id)?.is_none() {
+            return Ok(false);
+        }
+        self.forget_user(&spaces::storage_id(space_id))?;
+        Ok(true)
+    }
+
     /// Get context sessions
     pub fn context_sessions(&self) -> &Arc<ContextSessions> {
         &self.context_sessions
@@ -1057,6 +1444,34 @@ impl MultiUserMemoryManager {
             refs.push(("feedback".to_string(), std::sync::Arc::clone(db)));
         }
 
+        // API key store
+        refs.push((
+            "api_keys".to_string(),
+            std::sync::Arc::clone(self.api_key_store.database()),
+        ));
+
+        // Outbound webhooks (subscriptions, queue, delivery log)
+        refs.push((
+            "webhooks".to_string(),
+            std::sync::Arc::clone(self.webhook_store.database()),
+        ));
+
+        // Shared space registry (definitions and memberships)
+        refs.push((
+            "spaces".to_string(),
+            std::sync::Arc::clone(self.space_store.database()),
+        ));
+
+        // A/B test store (experiments, metrics, assignments)
+        if let Some(db) = self.ab_test_manager.database() {
+            refs.push(("ab_tests".to_string(), std::sync::Arc::clone(db)));
+        }
+
+        // Session history
+        if let Some(db) = self.session_store.database() {
+            refs.push(("sessions".to_string(), std::sync::Arc::clone(db)));
+        }
+
         // Audit log database
         refs.push((
             "audit_logs".to_string(),
@@ -1082,49 +1497,36 @@ impl MultiUserMemoryManager {
                     continue;
                 }
 
-                let db_path = path.join("memory.db");
+                let db_path = path.join(backup::MAIN_STORE);
                 if !db_path.exists() {
                     continue;
                 }
 
-                if let Ok(memory_lock) = self.get_user_memory(name) {
-                    let memory = memory_lock.read();
-                    let db = memory.get_db();
-                    let secondary_refs = self.collect_secondary_store_refs();
-                    let store_refs: Vec<crate::backup::SecondaryStoreRef<'_>> = secondary_refs
-                        .iter()
-                        .map(|(n, d)| crate::backup::SecondaryStoreRef { name: n, db: d })
-                        .collect();
-                    match self
-                        .backup_engine
-                        .create_comprehensive_backup(&db, name, &store_refs)
-                    {
-                        Ok(metadata) => {
-                            tracing::info!(
-                                user_id = name,
-                                backup_id = metadata.backup_id,
-                                size_mb = metadata.size_bytes / 1024 / 1024,
-                                "Backup created successfully"
-                            );
-                            backed_up += 1;
+                match self.create_user_backup(name) {
+                    Ok(metadata) => {
+                        tracing::info!(
+                            user_id = name,
+                            backup_id = metadata.backup_id,
+                            size_mb = metadata.size_bytes / 1024 / 1024,
+                            "Backup created successfully"
+                        );
+                        backed_up += 1;
 
-                            if let Err(e) = self.backup_engine.purge_old_backups(name, max_backups)
-                            {
-                                tracing::warn!(
-                                    user_id = name,
-                                    error = %e,
-                                    "Failed to purge old backups"
-                                );
-                            }
-                        }
-                        Err(e) => {
+                        if let Err(e) = self.backup_engine.purge_old_backups(name, max_backups) {
                             tracing::warn!(
                                 user_id = name,
                                 error = %e,
-                                "Failed to create backup"
+                                "Failed to purge old backups"
                             );
                         }
                     }
+                    Err(e) => {
+                        tracing::warn!(
+                            user_id = name,
+                            error = %e,
+                            "Failed to create backup"
+                        );
+                    }
                 }
             }
         }
@@ -1132,6 +1534,284 @@ impl MultiUserMemoryManager {
         backed_up
     }
 
+    /// Collect references to the per-user stores backed up alongside the main
+    /// memories database: the secondary memory index and every graph database.
+    pub fn collect_user_store_refs(
+        &self,
+        user_id: &str,
+    ) -> Result<Vec<(String, Arc<rocksdb::DB>)>> {
+        let index_db = self.get_user_memory(user_id)?.read().get_index_db();
+        let mut refs = vec![(MEMORY_INDEX_STORE.to_string(), index_db)];
+
+        let graph = self.get_user_graph(user_id)?;
+        for (name, db) in graph.read().databases() {
+            refs.push((name.to_string(), Arc::clone(db)));
+        }
+        Ok(refs)
+    }
+
+    /// Create a comprehensive backup of a user: memories, per-user stores and shared stores
+    pub fn create_user_backup(&self, user_id: &str) -> Result<backup::BackupMetadata> {
+        let db = self.get_user_memory(user_id)?.read().get_db();
+        let mut refs = self.collect_user_store_refs(user_id)?;
+        refs.extend(self.collect_secondary_store_refs());
+        let store_refs: Vec<backup::SecondaryStoreRef<'_>> = refs
+            .iter()
+            .map(|(name, db)| backup::SecondaryStoreRef { name, db })
+            .collect();
+        self.backup_engine
+            .create_comprehensive_backup(&db, user_id, &store_refs)
+    }
+
+    /// Archive new WAL batches of every cached user (point-in-time recovery)
+    ///
+    /// Users leaving the cache are archived by the eviction listeners instead.
+    /// Returns the number of write batches archived.
+    pub fn archive_wal_all_users(&self) -> usize {
+        let user_ids: Vec<String> = self
+            .user_memories
+            .iter()
+            .map(|(user_id, _)| user_id.to_string())
+            .collect();
+
+        let mut archived = 0;
+        for user_id in user_ids {
+            if self.restoring_users.contains(&user_id) {
+                continue;
+            }
+            match self.archive_user_wal(&user_id) {
+                Ok(count) => archived += count,
+                Err(e) => tracing::warn!(
+                    user_id = %user_id,
+                    error = %e,
+                    "Failed to archive WAL"
+                ),
+            }
+        }
+        archived
+    }
+
+    /// Sample the latest sequence number of every cached user's stores
+    ///
+    /// Dates archived WAL batches to within `backup::WAL_CLOCK_INTERVAL_SECS`
+    /// of their commit, rather than to the next archiver run.
+    pub fn sample_wal_clock_all_users(&self) {
+        let user_ids: Vec<String> = self
+            .user_memories
+            .iter()
+            .map(|(user_id, _)| user_id.to_string())
+            .collect();
+
+        for user_id in user_ids {
+            if self.restoring_users.contains(&user_id) {
+                continue;
+            }
+            match self.user_wal_stores(&user_id) {
+                Ok(stores) => {
+                    for (name, db) in &stores {
+                        self.backup_engine.sample_sequence(&user_id, name, db);
+                    }
+                }
+                Err(e) => tracing::warn!(
+                    user_id = %user_id,
+                    error = %e,
+                    "Failed to sample WAL sequence numbers"
+                ),
+            }
+        }
+    }
+
+    /// Archive new WAL batches of all per-user stores of one user
+    fn archive_user_wal(&self, user_id: &str) -> Result<usize> {
+        let mut archived = 0;
+        for (name, db) in &self.user_wal_stores(user_id)? {
+            archived += self.backup_engine.archive_wal(user_id, name, db)?;
+        }
+        Ok(archived)
+    }
+
+    /// Per-user stores whose WAL is archived, main store first
+    fn user_wal_stores(&self, user_id: &str) -> Result<Vec<(String, Arc<rocksdb::DB>)>> {
+        let db = self.get_user_memory(user_id)?.read().get_db();
+        let mut stores = vec![(backup::MAIN_STORE.to_string(), db)];
+        stores.extend(self.collect_user_store_refs(user_id)?);
+        Ok(stores)
+    }
+
+    /// Restore a user from a backup, optionally rolling forward to `target_time`
+    ///
+    /// The user is evicted from the caches and requests for them fail until
+    /// the restore finishes. Per-user stores are rebuilt in a staging
+    /// directory, rolled forward from the WAL archive, then swapped in together
+    /// (and put back if any swap fails); vector and BM25 indices are rebuilt
+    /// when the user reopens. Shared stores hold every user's data, so
+    /// `include_shared_stores` rolls them back for everyone, to the backup point.
+    ///
+    /// The restored stores start a new history, so the user's WAL archive is
+    /// reset and a fresh backup taken as the base for later PITR.
+    pub fn restore_user(
+        &self,
+        user_id: &str,
+        backup: &backup::BackupMetadata,
+        target_time: Option<chrono::DateTime<chrono::Utc>>,
+        include_shared_stores: bool,
+    ) -> Result<backup::RestoreReport> {
+        if target_time.is_some() {
+            // Capture writes made since the last archiver run
+            self.archive_user_wal(user_id)?;
+        }
+
+        if !self.restoring_users.insert(user_id.to_string()) {
+            anyhow::bail!("A restore is already running for user '{user_id}'");
+        }
+        let restoring = RestoringGuard {
+            users: &self.restoring_users,
+            user_id,
+        };
+        // Opens that passed the restoring check finish first; later ones fail
+        // until the swapped-in stores are in place
+        let lock = self.user_lock(user_id);
+        let exclusive = lock.write();
+        self.evict_user_for_restore(user_id)?;
+
+        let engine = &self.backup_engine;
+        let user_path = self.base_path.join(user_id);
+        let staging = self.base_path.join(RESTORE_STAGING_DIR).join(user_id);
+        if staging.exists() {
+            std::fs::remove_dir_all(&staging)?;
+        }
+        std::fs::create_dir_all(&staging)?;
+
+        // Per-user checkpoints are staged where the live stores would be
+        let mut staged: Vec<(String, PathBuf)> = backup
+            .secondary_stores
+            .iter()
+            .filter_map(|name| user_store_path(name).map(|rel| (name.clone(), staging.join(rel))))
+            .collect();
+        let secondary_paths: Vec<(&str, &Path)> = staged
+            .iter()
+            .map(|(name, path)| (name.as_str(), path.as_path()))
+            .collect();
+        let restored = engine.restore_comprehensive_backup(
+            user_id,
+            Some(backup.backup_id),
+            &staging.join(backup::MAIN_STORE),
+            &secondary_paths,
+        )?;
+        if restored.len() != staged.len() {
+            anyhow::bail!(
+                "Backup {} is missing per-user store checkpoints",
+                backup.backup_id
+            );
+        }
+        staged.insert(
+            0,
+            (
+                backup::MAIN_STORE.to_string(),
+                staging.join(backup::MAIN_STORE),
+            ),
+        );
+
+        let mut replayed_batches = 0;
+        let mut replayed_through = None;
+        if let Some(until) = target_time {
+            let mut opts = rocksdb::Options::default();
+            opts.set_compression_type(rocksdb::DBCompressionType::Lz4);
+            for (name, path) in &staged {
+                let after_seq = if name == backup::MAIN_STORE {
+                    Some(backup.sequence_number)
+                } else {
+                    backup.store_sequence_numbers.get(name).copied()
+                };
+                let Some(after_seq) = after_seq else {
+                    tracing::warn!(
+                        store = %name,
+                        "Backup has no sequence number for store, restoring it to the backup point"
+                    );
+                    continue;
+                };
+                let db = rocksdb::DB::open(&opts, path)?;
+                let replay = engine.replay_wal(user_id, name, &db, after_seq, until)?;
+                replayed_batches += replay.batches;
+                replayed_through = replayed_through.max(replay.replayed_through);
+            }
+        }
+
+        swap_in_staged(&staging, &user_path)?;
+        for derived in ["vector_index", "bm25_index"] {
+            let path = user_path.join(derived);
+            if path.exists() {
+                std::fs::remove_dir_all(&path)?;
+            }
+        }
+        if let Err(e) = std::fs::remove_dir_all(&staging) {
+            tracing::warn!(error = %e, "Failed to remove restore staging directory");
+        }
+
+        let mut shared_stores = Vec::new();
+        if include_shared_stores {
+            for (name, db) in self.collect_secondary_store_refs() {
+                if RESTORE_EXCLUDED_STORES.contains(&name.as_str()) {
+                    continue;
+                }
+                if engine.restore_store_in_place(user_id, backup.backup_id, &name, &db)? {
+                    shared_stores.push(name);
+                }
+            }
+            if shared_stores.iter().any(|name| name.starts_with("todo_")) {
+                self.todo_store.rebuild_vector_indices()?;
+            }
+        }
+
+        engine.clear_wal_archive(user_id)?;
+        drop(exclusive);
+        drop(restoring);
+        self.create_user_backup(user_id).context(
+            "User restored, but the follow-up backup failed; create a backup before relying on PITR",
+        )?;
+
+        info!(
+            "Restored user '{}' from backup {} ({} WAL batches replayed, {} shared stores)",
+            user_id,
+            backup.backup_id,
+            replayed_batches,
+            shared_stores.len()
+        );
+
+        Ok(backup::RestoreReport {
+            user_id: user_id.to_string(),
+            backup_id: backup.backup_id,
+            backup_created_at: backup.created_at,
+            target_time,
+            restored_stores: staged.into_iter().map(|(name, _)| name).collect(),
+            shared_stores,
+            replayed_batches,
+            replayed_through,
+            granularity_secs: target_time.map(|_| backup::WAL_CLOCK_INTERVAL_SECS),
+        })
+    }
+
+    /// Drop a user's cached memory and graph and wait for in-flight requests
+    /// to release them, so their databases are closed
+    fn evict_user_for_restore(&self, user_id: &str) -> Result<()> {
+        let memory = self.user_memories.get(user_id);
+        let graph = self.graph_memories.get(user_id);
+        self.user_memories.invalidate(user_id);
+        self.graph_memories.invalidate(user_id);
+        self.user_memories.run_pending_tasks();
+        self.graph_memories.run_pending_tasks();
+
+        // The memory system holds a graph handle, so it has to close first
+        let deadline = std::time::Instant::now() + RESTORE_DRAIN_TIMEOUT;
+        if let Some(memory) = memory {
+            wait_for_release(memory, deadline, "memory system")?;
+        }
+        if let Some(graph) = graph {
+            wait_for_release(graph, deadline, "graph")?;
+        }
+        Ok(())
+    }
+
     /// Process an experience and extract entities/relationships into the graph
     ///
     /// SHO-102: Improved graph building with:
@@ -1156,14 +1836,18 @@ impl MultiUserMemoryManager {
 
         let now = chrono::Utc::now();
 
-        // Stop words for filtering
-        let stop_words: std::collections::HashSet<&str> = [
+        // Stop words for filtering (plus those of the memory's language)
+        let mut stop_words: std::collections::HashSet<&str> = [
             "the", "and", "for", "that", "this", "with", "from", "have", "been", "are", "was",
             "were", "will", "would", "could", "should", "may", "might",
         ]
         .iter()
         .cloned()
         .collect();
+        let language = experience.detected_language();
+        if language != crate::memory::Language::English {
+            stop_words.extend(language.stopwords().iter().copied());
+        }
 
         // Use pre-extracted NER records for proper entity labels when available
         // This avoids redundant NER inference — the handler already ran NER in Pass 1
@@ -1273,6 +1957,9 @@ impl MultiUserMemoryManager {
                     name_embedding: None,
                     salience: ner_entity.confidence,
                     is_proper_noun: true,
+                    aliases: Vec::new(),
+                    centrality: 0.0,
+                    community: None,
                 };
                 (ner_entity.text, node)
             })
@@ -1299,6 +1986,9 @@ impl MultiUserMemoryManager {
                             name_embedding: None,
                             salience: 0.6,
                             is_proper_noun: false,
+                            aliases: Vec::new(),
+                            centrality: 0.0,
+                            community: None,
                         },
                     ))
                 } else {
@@ -1343,6 +2033,9 @@ impl MultiUserMemoryManager {
                         name_embedding: None,
                         salience: 0.5,
                         is_proper_noun: true,
+                        aliases: Vec::new(),
+                        centrality: 0.0,
+                        community: None,
                     },
                 ))
             })
@@ -1371,6 +2064,9 @@ impl MultiUserMemoryManager {
                         name_embedding: None,
                         salience: 0.7,
                         is_proper_noun: true,
+                        aliases: Vec::new(),
+                        centrality: 0.0,
+                        community: None,
                     },
                 ))
             })
@@ -1418,6 +2114,9 @@ impl MultiUserMemoryManager {
                         name_embedding: None,
                         salience: 0.4,
                         is_proper_noun: false,
+                        aliases: Vec::new(),
+                        centrality: 0.0,
+                        community: None,
                     },
                 ));
             }
@@ -1497,6 +2196,10 @@ impl MultiUserMemoryManager {
         let truncated_context: String = experience.content.chars().take(150).collect();
         for i in 0..entity_uuids.len() {
             for j in (i + 1)..entity_uuids.len() {
+                // Two names resolved to the same entity (alias) — no self-loop
+                if entity_uuids[i].1 == entity_uuids[j].1 {
+                    continue;
+                }
                 let edge = RelationshipEdge {
                     uuid: uuid::Uuid::new_v4(),
                     from_entity: entity_uuids[i].1,
@@ -1,7 +1,7 @@
 //! Todo, Reminder, and Project Handlers
 //!
 //! GTD-style task management with:
-//! - Prospective memory (reminders with time/duration/context triggers)
+//! - Prospective memory (reminders with time/duration/context/recurring triggers)
 //! - Todo CRUD with semantic search
 //! - Project hierarchy with nested projects
 //! - Todo comments and activity tracking
@@ -13,17 +13,19 @@ use axum::{
 use chrono::Datelike;
 use serde::{Deserialize, Serialize};
 
+use super::lineage;
 use super::state::MultiUserMemoryManager;
 use super::types::MemoryEvent;
 use crate::errors::{AppError, ValidationErrorExt};
+use crate::memory::rrule;
 use crate::memory::sessions::SessionEvent;
 use crate::memory::todo_formatter;
-use crate::memory::{Experience, ExperienceType};
 use crate::memory::{
-    Project, ProjectId, ProjectStats, ProjectStatus, ProspectiveTask, ProspectiveTaskId,
-    ProspectiveTaskStatus, ProspectiveTrigger, Recurrence, Todo, TodoComment, TodoCommentId,
-    TodoCommentType, TodoPriority, TodoStatus, UserTodoStats,
+    DependencyEdge, Project, ProjectId, ProjectStats, ProjectStatus, ProspectiveTask,
+    ProspectiveTaskId, ProspectiveTaskStatus, ProspectiveTrigger, Recurrence, Todo, TodoComment,
+    TodoCommentId, TodoCommentType, TodoId, TodoPriority, TodoStatus, UserTodoStats,
 };
+use crate::memory::{Experience, ExperienceType};
 use crate::validation;
 
 /// Application state type alias
@@ -64,6 +66,13 @@ pub enum ReminderTriggerRequest {
         #[serde(default = "default_context_threshold")]
         threshold: f32,
     },
+    /// `rule` is daily/weekly/monthly, an RRULE or a DTSTART/RRULE/EXDATE
+    /// block; `start` anchors rules that carry no DTSTART (default: now)
+    Recurring {
+        rule: String,
+        #[serde(default)]
+        start: Option<chrono::DateTime<chrono::Utc>>,
+    },
 }
 
 fn default_context_threshold() -> f32 {
@@ -184,10 +193,18 @@ pub struct CreateTodoRequest {
     pub tags: Option<Vec<String>>,
     #[serde(default)]
     pub notes: Option<String>,
+    /// daily, weekly, monthly, an RRULE (`FREQ=MONTHLY;BYDAY=FR;BYSETPOS=-1`)
+    /// or a DTSTART/RRULE/EXDATE block
     #[serde(default)]
     pub recurrence: Option<String>,
     #[serde(default)]
     pub external_id: Option<String>,
+    /// Todos (short IDs or UUID prefixes) that must finish before this one
+    #[serde(default)]
+    pub blocked_by: Option<Vec<String>>,
+    /// Todos that can't start until this one finishes
+    #[serde(default)]
+    pub blocks: Option<Vec<String>>,
 }
 
 /// Response for todo operations
@@ -215,6 +232,8 @@ pub struct TodoCompleteResponse {
     pub success: bool,
     pub todo: Option<Todo>,
     pub next_recurrence: Option<Todo>,
+    /// Dependents left with no open blockers by this completion
+    pub unblocked: Vec<Todo>,
     pub formatted: String,
 }
 
@@ -240,6 +259,23 @@ pub struct ListTodosRequest {
     pub parent_id: Option<String>,
     #[serde(default)]
     pub query: Option<String>,
+    /// Only todos that can be started now (Todo/InProgress with no open
+    /// blockers), sorted by priority then due date; `status`, `query` and
+    /// `include_completed` are ignored
+    #[serde(default)]
+    pub actionable: Option<bool>,
+}
+
+/// Request for the next actionable todos
+#[derive(Debug, Deserialize)]
+pub struct NextTodosRequest {
+    pub user_id: String,
+    #[serde(default)]
+    pub project: Option<String>,
+    #[serde(default)]
+    pub context: Option<String>,
+    #[serde(default)]
+    pub limit: Option<usize>,
 }
 
 /// Request to update a todo
@@ -270,6 +306,15 @@ pub struct UpdateTodoRequest {
     pub parent_id: Option<String>,
     #[serde(default)]
     pub external_id: Option<String>,
+    /// Same forms as on create; "none" or "" stops the recurrence
+    #[serde(default)]
+    pub recurrence: Option<String>,
+    /// Replaces the todo's blockers (empty list clears them)
+    #[serde(default)]
+    pub blocked_by: Option<Vec<String>>,
+    /// Replaces the set of todos this one blocks
+    #[serde(default)]
+    pub blocks: Option<Vec<String>>,
 }
 
 /// Request to reorder a todo
@@ -417,20 +462,252 @@ pub struct ListProjectsRequest {
     pub user_id: String,
 }
 
+/// Response for a project's dependency graph
+#[derive(Debug, Serialize)]
+pub struct DependencyGraphResponse {
+    pub success: bool,
+    pub project: Project,
+    pub todos: Vec<Todo>,
+    pub edges: Vec<DependencyEdge>,
+    pub formatted: String,
+}
+
+/// Response for a project's critical path
+#[derive(Debug, Serialize)]
+pub struct CriticalPathResponse {
+    pub success: bool,
+    pub project: Project,
+    pub count: usize,
+    /// First todo to start comes first
+    pub todos: Vec<Todo>,
+    pub formatted: String,
+}
+
 // =============================================================================
 // HELPER FUNCTIONS
 // =============================================================================
 
-/// Parse recurrence string to Recurrence enum
-fn parse_recurrence(s: &str) -> Option<Recurrence> {
-    match s.to_lowercase().as_str() {
-        "daily" => Some(Recurrence::Daily),
-        "weekly" => Some(Recurrence::Weekly {
-            days: vec![1, 2, 3, 4, 5],
-        }),
-        "monthly" => Some(Recurrence::Monthly { day: 1 }),
-        _ => None,
+/// Parse a recurrence: daily/weekly/monthly, a bare RRULE or an iCalendar
+/// DTSTART/RRULE/EXDATE block. RRULEs without DTSTART start at `anchor`.
+pub(crate) fn parse_recurrence(
+    s: &str,
+    anchor: chrono::DateTime<chrono::Utc>,
+) -> Result<Recurrence, AppError> {
+    match s.trim().to_lowercase().as_str() {
+        "daily" => return Ok(Recurrence::Daily),
+        "weekly" => {
+            return Ok(Recurrence::Weekly {
+                days: vec![1, 2, 3, 4, 5],
+            })
+        }
+        "monthly" => return Ok(Recurrence::Monthly { day: 1 }),
+        _ => {}
+    }
+    rrule::parse_recurrence_block(s)
+        .and_then(|parsed| parsed.into_recurrence(anchor))
+        .map_err(|e| AppError::InvalidInput {
+            field: "recurrence".to_string(),
+            reason: format!("{e:#}"),
+        })
+}
+
+/// Default anchor for a new series: now, on the minute
+fn recurrence_anchor_now() -> chrono::DateTime<chrono::Utc> {
+    use chrono::DurationRound;
+
+    let now = chrono::Utc::now();
+    now.duration_trunc(chrono::Duration::minutes(1))
+        .unwrap_or(now)
+}
+
+/// First occurrence at or after `from`
+fn upcoming_occurrence(
+    recurrence: &Recurrence,
+    from: chrono::DateTime<chrono::Utc>,
+) -> Result<chrono::DateTime<chrono::Utc>, AppError> {
+    recurrence
+        .next_after(from - chrono::Duration::seconds(1))
+        .ok_or_else(|| AppError::InvalidInput {
+            field: "recurrence".to_string(),
+            reason: "Recurrence has no upcoming occurrences".to_string(),
+        })
+}
+
+/// Give an undated todo with an RRULE series the series' next instance as its due date
+fn schedule_recurring_todo(todo: &mut Todo) -> Result<(), AppError> {
+    if let (None, Some(recurrence @ Recurrence::RRule { .. })) = (todo.due_date, &todo.recurrence) {
+        todo.due_date = Some(upcoming_occurrence(recurrence, chrono::Utc::now())?);
+    }
+    Ok(())
+}
+
+/// Find a project by name or UUID
+pub(crate) fn resolve_project(
+    state: &AppState,
+    user_id: &str,
+    project_ref: &str,
+) -> Result<Project, AppError> {
+    state
+        .todo_store
+        .find_project_by_name(user_id, project_ref)
+        .map_err(AppError::Internal)?
+        .or_else(|| {
+            uuid::Uuid::parse_str(project_ref).ok().and_then(|uuid| {
+                state
+                    .todo_store
+                    .get_project(user_id, &ProjectId(uuid))
+                    .ok()
+                    .flatten()
+            })
+        })
+        .ok_or_else(|| AppError::ProjectNotFound(project_ref.to_string()))
+}
+
+/// Look up the todos named in a dependency list (short IDs or UUID prefixes)
+fn resolve_dependency_refs(
+    state: &AppState,
+    todo: &Todo,
+    refs: &[String],
+    field: &str,
+) -> Result<Vec<Todo>, AppError> {
+    let mut resolved: Vec<Todo> = Vec::new();
+    for reference in refs {
+        let other = state
+            .todo_store
+            .find_todo_by_prefix(&todo.user_id, reference)
+            .map_err(AppError::Internal)?
+            .ok_or_else(|| AppError::InvalidInput {
+                field: field.to_string(),
+                reason: format!("Todo '{}' not found", reference),
+            })?;
+        if other.id == todo.id {
+            return Err(AppError::InvalidInput {
+                field: field.to_string(),
+                reason: "A todo cannot depend on itself".to_string(),
+            });
+        }
+        if !resolved.iter().any(|t| t.id == other.id) {
+            resolved.push(other);
+        }
+    }
+    Ok(resolved)
+}
+
+/// Apply requested `blocked_by`/`blocks` links to `todo`
+///
+/// Both lists replace the current links. Links that would close a cycle are
+/// rejected. Returns the other todos whose `blocked_by` changed, paired with
+/// whether they gained the link; store them with [`store_linked_todos`] once
+/// `todo` itself is saved.
+fn apply_dependencies(
+    state: &AppState,
+    todo: &mut Todo,
+    blocked_by: Option<&[String]>,
+    blocks: Option<&[String]>,
+) -> Result<Vec<(Todo, bool)>, AppError> {
+    if let Some(refs) = blocked_by {
+        todo.blocked_by = resolve_dependency_refs(state, todo, refs, "blocked_by")?
+            .into_iter()
+            .map(|t| t.id)
+            .collect();
+    }
+
+    let mut linked = Vec::new();
+    if let Some(refs) = blocks {
+        let targets = resolve_dependency_refs(state, todo, refs, "blocks")?;
+        let current = state
+            .todo_store
+            .list_dependents(&todo.user_id, &todo.id)
+            .map_err(AppError::Internal)?;
+        for mut dependent in current {
+            if !targets.iter().any(|t| t.id == dependent.id) {
+                dependent.blocked_by.retain(|id| *id != todo.id);
+                linked.push((dependent, false));
+            }
+        }
+        for mut target in targets {
+            if !target.blocked_by.contains(&todo.id) {
+                target.blocked_by.push(todo.id.clone());
+                linked.push((target, true));
+            }
+        }
+    }
+
+    let mut pending: Vec<&Todo> = vec![&*todo];
+    pending.extend(linked.iter().map(|(t, _)| t));
+    if let Some(cycle) = state
+        .todo_store
+        .find_dependency_cycle(&todo.user_id, &pending)
+        .map_err(AppError::Internal)?
+    {
+        let names: Vec<String> = cycle
+            .iter()
+            .map(|id| match pending.iter().find(|t| t.id == *id) {
+                Some(t) => t.short_id(),
+                None => state
+                    .todo_store
+                    .get_todo(&todo.user_id, id)
+                    .ok()
+                    .flatten()
+                    .map_or_else(|| id.short(), |t| t.short_id()),
+            })
+            .collect();
+        return Err(AppError::InvalidInput {
+            field: if blocked_by.is_some() {
+                "blocked_by"
+            } else {
+                "blocks"
+            }
+            .to_string(),
+            reason: format!("Dependency cycle: {}", names.join(" → ")),
+        });
+    }
+
+    if blocked_by.is_some() {
+        let open = state
+            .todo_store
+            .open_blockers(todo)
+            .map_err(AppError::Internal)?;
+        todo.sync_blocked_status(!open.is_empty());
     }
+    for (other, _) in &mut linked {
+        let blocked_by_todo = other.blocked_by.contains(&todo.id) && !todo.status.is_finished();
+        let open = state
+            .todo_store
+            .open_blockers(other)
+            .map_err(AppError::Internal)?;
+        other.sync_blocked_status(blocked_by_todo || open.iter().any(|b| b.id != todo.id));
+    }
+
+    Ok(linked)
+}
+
+/// Store the todos returned by [`apply_dependencies`], noting the change on each
+fn store_linked_todos(
+    state: &AppState,
+    todo: &Todo,
+    linked: Vec<(Todo, bool)>,
+) -> Result<(), AppError> {
+    for (mut other, gained) in linked {
+        other.add_activity(if gained {
+            format!("Blocked by {}", todo.short_id())
+        } else {
+            format!("No longer blocked by {}", todo.short_id())
+        });
+        state
+            .todo_store
+            .update_todo(&other)
+            .map_err(AppError::Internal)?;
+    }
+    Ok(())
+}
+
+/// Short IDs of the given todos, skipping any that no longer exist
+fn todo_short_ids(state: &AppState, user_id: &str, ids: &[TodoId]) -> Vec<String> {
+    ids.iter()
+        .filter_map(|id| state.todo_store.get_todo(user_id, id).ok().flatten())
+        .map(|t| t.short_id())
+        .collect()
 }
 
 // =============================================================================
@@ -452,6 +729,22 @@ pub async fn create_reminder(
     }
 
     let trigger = match req.trigger {
+        ReminderTriggerRequest::Recurring { rule, start } => {
+            let anchor = start.unwrap_or_else(recurrence_anchor_now);
+            // Reminders fire on instances, so keywords become RRULEs anchored at `anchor`
+            let recurrence = match parse_recurrence(&rule, anchor)? {
+                r @ Recurrence::RRule { .. } => r,
+                simple => Recurrence::RRule {
+                    rule: simple.to_rrule(),
+                    dtstart: anchor,
+                    tzid: None,
+                    exdates: Vec::new(),
+                },
+            };
+            let at = upcoming_occurrence(&recurrence, anchor.max(chrono::Utc::now()))?;
+            validation::validate_reminder_timestamp(&at).map_validation_err("trigger_at")?;
+            ProspectiveTrigger::Recurring { at, recurrence }
+        }
         ReminderTriggerRequest::Time { at } => {
             validation::validate_reminder_timestamp(&at).map_validation_err("trigger_at")?;
             ProspectiveTrigger::AtTime { at }
@@ -494,6 +787,7 @@ pub async fn create_reminder(
         ProspectiveTrigger::AtTime { .. } => "time",
         ProspectiveTrigger::AfterDuration { .. } => "duration",
         ProspectiveTrigger::OnContext { .. } => "context",
+        ProspectiveTrigger::Recurring { .. } => "recurring",
     };
 
     let due_at = task.trigger.due_at();
@@ -561,6 +855,7 @@ pub async fn list_reminders(
                     ProspectiveTrigger::AtTime { .. } => "time".to_string(),
                     ProspectiveTrigger::AfterDuration { .. } => "duration".to_string(),
                     ProspectiveTrigger::OnContext { .. } => "context".to_string(),
+                    ProspectiveTrigger::Recurring { .. } => "recurring".to_string(),
                 },
                 status: format!("{:?}", t.status).to_lowercase(),
                 due_at: t.trigger.due_at(),
@@ -579,6 +874,20 @@ pub async fn list_reminders(
     Ok(Json(ListRemindersResponse { reminders, count }))
 }
 
+/// Broadcast a reminder that just moved to `triggered` (SSE + webhooks)
+fn emit_reminder_triggered(state: &AppState, user_id: &str, task: &ProspectiveTask) {
+    state.emit_event(MemoryEvent {
+        event_type: "REMINDER_TRIGGERED".to_string(),
+        timestamp: chrono::Utc::now(),
+        user_id: user_id.to_string(),
+        memory_id: Some(task.id.to_string()),
+        content_preview: Some(task.content.chars().take(100).collect()),
+        memory_type: Some("Reminder".to_string()),
+        importance: None,
+        count: None,
+    });
+}
+
 /// Get due time-based reminders
 pub async fn get_due_reminders(
     State(state): State<AppState>,
@@ -593,9 +902,12 @@ pub async fn get_due_reminders(
 
     if req.mark_triggered {
         for task in &mut due_tasks {
-            let _ = state
+            if let Ok(true) = state
                 .prospective_store
-                .mark_triggered(&req.user_id, &task.id);
+                .mark_triggered(&req.user_id, &task.id)
+            {
+                emit_reminder_triggered(&state, &req.user_id, task);
+            }
         }
     }
 
@@ -610,6 +922,7 @@ pub async fn get_due_reminders(
                     ProspectiveTrigger::AtTime { .. } => "time".to_string(),
                     ProspectiveTrigger::AfterDuration { .. } => "duration".to_string(),
                     ProspectiveTrigger::OnContext { .. } => "context".to_string(),
+                    ProspectiveTrigger::Recurring { .. } => "recurring".to_string(),
                 },
                 status: if req.mark_triggered {
                     "triggered".to_string()
@@ -668,7 +981,7 @@ pub async fn check_context_reminders(
         let memory_guard = memory_for_embedding.read();
         memory_guard
             .compute_embedding(&context_for_embed)
-            .unwrap_or_else(|_| vec![0.0; 384])
+            .unwrap_or_else(|_| vec![0.0; memory_guard.get_embedder().dimension()])
     })
     .await
     .map_err(|e| AppError::Internal(anyhow::anyhow!("Embedding task panicked: {e}")))?;
@@ -700,9 +1013,12 @@ pub async fn check_context_reminders(
 
     if mark_triggered {
         for (task, _) in &matched_tasks {
-            let _ = state
+            if let Ok(true) = state
                 .prospective_store
-                .mark_triggered(&req.user_id, &task.id);
+                .mark_triggered(&req.user_id, &task.id)
+            {
+                emit_reminder_triggered(&state, &req.user_id, task);
+            }
         }
     }
 
@@ -908,16 +1224,20 @@ pub async fn create_todo(
     todo.external_id = req.external_id;
 
     if let Some(ref recurrence_str) = req.recurrence {
-        todo.recurrence = parse_recurrence(recurrence_str);
+        let anchor = todo.due_date.unwrap_or_else(recurrence_anchor_now);
+        todo.recurrence = Some(parse_recurrence(recurrence_str, anchor)?);
+        schedule_recurring_todo(&mut todo)?;
     }
 
+    let linked = apply_dependencies(
+        &state,
+        &mut todo,
+        req.blocked_by.as_deref(),
+        req.blocks.as_deref(),
+    )?;
+
     // Compute embedding for semantic search
-    let embedding_text = format!(
-        "{} {} {}",
-        todo.content,
-        todo.notes.as_deref().unwrap_or(""),
-        todo.tags.join(" ")
-    );
+    let embedding_text = todo.embedding_text();
 
     if let Ok(memory_system) = state.get_user_memory(&req.user_id) {
         let memory_clone = memory_system.clone();
@@ -948,6 +1268,7 @@ pub async fn create_todo(
         .todo_store
         .store_todo(&todo)
         .map_err(AppError::Internal)?;
+    store_linked_todos(&state, &todo, linked)?;
 
     let activity_msg = if let Some(ref proj) = project_name {
         format!("Created in project '{}'", proj)
@@ -1079,7 +1400,13 @@ pub async fn list_todos(
             .collect()
     });
 
-    let mut todos = if let Some(ref query) = req.query {
+    let actionable = req.actionable.unwrap_or(false);
+    let mut todos = if actionable {
+        state
+            .todo_store
+            .list_actionable(&req.user_id)
+            .map_err(AppError::Internal)?
+    } else if let Some(ref query) = req.query {
         if query.trim().is_empty() {
             Vec::new()
         } else {
@@ -1135,7 +1462,7 @@ pub async fn list_todos(
     };
 
     // Apply status filter for semantic search results
-    if req.query.is_some() {
+    if req.query.is_some() && !actionable {
         if let Some(ref statuses) = status_filter {
             todos.retain(|t| statuses.contains(&t.status));
         } else if !req.include_completed.unwrap_or(false) {
@@ -1237,6 +1564,30 @@ pub async fn list_todos(
     }))
 }
 
+/// POST /api/todos/next - Next actionable todos (unblocked, by priority then due date)
+pub async fn list_next_todos(
+    State(state): State<AppState>,
+    Json(req): Json<NextTodosRequest>,
+) -> Result<Json<TodoListResponse>, AppError> {
+    list_todos(
+        State(state),
+        Json(ListTodosRequest {
+            user_id: req.user_id,
+            status: None,
+            project: req.project,
+            context: req.context,
+            include_completed: None,
+            due: None,
+            limit: req.limit,
+            offset: None,
+            parent_id: None,
+            query: None,
+            actionable: Some(true),
+        }),
+    )
+    .await
+}
+
 /// POST /api/todos/due - List due/overdue todos
 pub async fn list_due_todos(
     State(state): State<AppState>,
@@ -1312,6 +1663,8 @@ pub async fn update_todo(
         .find_todo_by_prefix(&req.user_id, &todo_id)
         .map_err(AppError::Internal)?
         .ok_or_else(|| AppError::TodoNotFound(todo_id.clone()))?;
+    let was_finished = todo.status.is_finished();
+    let was_done = todo.status == TodoStatus::Done;
 
     if let Some(ref content) = req.content {
         todo.content = content.clone();
@@ -1344,6 +1697,16 @@ pub async fn update_todo(
     if let Some(ref external_id) = req.external_id {
         todo.external_id = Some(external_id.clone());
     }
+    if let Some(ref recurrence_str) = req.recurrence {
+        todo.recurrence = match recurrence_str.trim().to_lowercase().as_str() {
+            "" | "none" => None,
+            _ => {
+                let anchor = todo.due_date.unwrap_or_else(recurrence_anchor_now);
+                Some(parse_recurrence(recurrence_str, anchor)?)
+            }
+        };
+        schedule_recurring_todo(&mut todo)?;
+    }
     if let Some(ref parent_id_str) = req.parent_id {
         if parent_id_str.is_empty() {
             todo.parent_id = None;
@@ -1365,17 +1728,19 @@ pub async fn update_todo(
         project_name = Some(project.name.clone());
     }
 
+    let linked = apply_dependencies(
+        &state,
+        &mut todo,
+        req.blocked_by.as_deref(),
+        req.blocks.as_deref(),
+    )?;
+
     todo.updated_at = chrono::Utc::now();
 
     // Re-compute embedding if needed
     let needs_reindex = req.content.is_some() || req.notes.is_some() || req.tags.is_some();
     if needs_reindex {
-        let embedding_text = format!(
-            "{} {} {}",
-            todo.content,
-            todo.notes.as_deref().unwrap_or(""),
-            todo.tags.join(" ")
-        );
+        let embedding_text = todo.embedding_text();
 
         if let Ok(memory_system) = state.get_user_memory(&req.user_id) {
             let memory_clone = memory_system.clone();
@@ -1408,6 +1773,14 @@ pub async fn update_todo(
         .todo_store
         .update_todo(&todo)
         .map_err(AppError::Internal)?;
+    store_linked_todos(&state, &todo, linked)?;
+
+    if !was_finished && todo.status.is_finished() {
+        state
+            .todo_store
+            .unblock_dependents(&req.user_id, &todo)
+            .map_err(AppError::Internal)?;
+    }
 
     let update_description = {
         let mut changes = Vec::new();
@@ -1432,6 +1805,34 @@ pub async fn update_todo(
                 todo.blocked_on.as_deref().unwrap_or("cleared")
             ));
         }
+        if req.recurrence.is_some() {
+            changes.push(match todo.recurrence {
+                Some(ref r) => format!("recurrence → {}", r.to_rrule()),
+                None => "recurrence: cleared".to_string(),
+            });
+        }
+        if req.blocked_by.is_some() {
+            let blockers = todo_short_ids(&state, &req.user_id, &todo.blocked_by);
+            changes.push(if blockers.is_empty() {
+                "blocked by: cleared".to_string()
+            } else {
+                format!("blocked by → {}", blockers.join(", "))
+            });
+        }
+        if req.blocks.is_some() {
+            let dependents: Vec<String> = state
+                .todo_store
+                .list_dependents(&req.user_id, &todo.id)
+                .map_err(AppError::Internal)?
+                .iter()
+                .map(|t| t.short_id())
+                .collect();
+            changes.push(if dependents.is_empty() {
+                "blocks: cleared".to_string()
+            } else {
+                format!("blocks → {}", dependents.join(", "))
+            });
+        }
         changes.join(", ")
     };
 
@@ -1444,6 +1845,8 @@ pub async fn update_todo(
     }
 
     if !update_description.is_empty() {
+        let post_mortem = (!was_done && todo.status == TodoStatus::Done)
+            .then(|| lineage::todo_post_mortem_subject(&todo));
         let memory_content = format!(
             "[{}] Todo updated ({}): {}",
             todo.short_id(),
@@ -1494,6 +1897,11 @@ pub async fn update_todo(
                     }
                     tracing::debug!(memory_id = %memory_id.0, "Todo update stored as memory");
                 }
+
+                // After the update memory, so the post-mortem can include it
+                if let Some(subject) = post_mortem {
+                    lineage::run_post_mortem(state_clone, user_id, subject).await;
+                }
             });
         }
     }
@@ -1559,6 +1967,14 @@ pub async fn complete_todo(
         .complete_todo(&req.user_id, &todo.id)
         .map_err(AppError::Internal)?;
 
+    let unblocked = match result {
+        Some((ref completed, _)) => state
+            .todo_store
+            .unblock_dependents(&req.user_id, completed)
+            .map_err(AppError::Internal)?,
+        None => Vec::new(),
+    };
+
     if result.is_some() {
         let days_taken = (chrono::Utc::now() - todo.created_at).num_hours() as f64 / 24.0;
         let activity_msg = format!("Marked complete after {:.1} days", days_taken);
@@ -1590,6 +2006,9 @@ pub async fn complete_todo(
             tags,
             ..Default::default()
         };
+        let post_mortem = result
+            .as_ref()
+            .map(|(completed, _)| lineage::todo_post_mortem_subject(completed));
 
         if let Ok(memory) = state.get_user_memory(&req.user_id) {
             let memory_clone = memory.clone();
@@ -1616,13 +2035,19 @@ pub async fn complete_todo(
                     }
                     tracing::debug!(memory_id = %memory_id.0, "Todo completion stored as searchable memory");
                 }
+
+                // After the completion memory, so the post-mortem can include it
+                if let Some(subject) = post_mortem {
+                    lineage::run_post_mortem(state_clone, user_id, subject).await;
+                }
             });
         }
     }
 
     match result {
         Some((completed, next)) => {
-            let formatted = todo_formatter::format_todo_completed(&completed, next.as_ref());
+            let formatted =
+                todo_formatter::format_todo_completed(&completed, next.as_ref(), &unblocked);
 
             state.emit_event(MemoryEvent {
                 event_type: "TODO_COMPLETE".to_string(),
@@ -1648,6 +2073,7 @@ pub async fn complete_todo(
                 user_id = %req.user_id,
                 todo_id = %completed.id,
                 has_next = next.is_some(),
+                unblocked = unblocked.len(),
                 "Completed todo"
             );
 
@@ -1667,6 +2093,7 @@ pub async fn complete_todo(
                 success: true,
                 todo: Some(completed),
                 next_recurrence: next,
+                unblocked,
                 formatted,
             }))
         }
@@ -2347,6 +2774,56 @@ pub async fn get_project(
     }))
 }
 
+/// GET /api/projects/{project_id}/dependencies - Dependency graph of a project's todos
+pub async fn get_project_dependencies(
+    State(state): State<AppState>,
+    Path(project_id): Path<String>,
+    Query(query): Query<TodoQuery>,
+) -> Result<Json<DependencyGraphResponse>, AppError> {
+    validation::validate_user_id(&query.user_id).map_validation_err("user_id")?;
+
+    let project = resolve_project(&state, &query.user_id, &project_id)?;
+    let graph = state
+        .todo_store
+        .dependency_graph(&query.user_id, &project.id)
+        .map_err(AppError::Internal)?;
+
+    let formatted = todo_formatter::format_dependency_graph(&project, &graph);
+
+    Ok(Json(DependencyGraphResponse {
+        success: true,
+        project,
+        todos: graph.todos,
+        edges: graph.edges,
+        formatted,
+    }))
+}
+
+/// GET /api/projects/{project_id}/critical-path - Longest chain of open dependent todos
+pub async fn get_project_critical_path(
+    State(state): State<AppState>,
+    Path(project_id): Path<String>,
+    Query(query): Query<TodoQuery>,
+) -> Result<Json<CriticalPathResponse>, AppError> {
+    validation::validate_user_id(&query.user_id).map_validation_err("user_id")?;
+
+    let project = resolve_project(&state, &query.user_id, &project_id)?;
+    let todos = state
+        .todo_store
+        .critical_path(&query.user_id, &project.id)
+        .map_err(AppError::Internal)?;
+
+    let formatted = todo_formatter::format_critical_path(&project, &todos);
+
+    Ok(Json(CriticalPathResponse {
+        success: true,
+        project,
+        count: todos.len(),
+        todos,
+        formatted,
+    }))
+}
+
 /// POST /api/projects/{project_id}/update - Update a project
 pub async fn update_project(
     State(state): State<AppState>,
@@ -2369,6 +2846,7 @@ pub async fn update_project(
             })
         })
         .ok_or_else(|| AppError::ProjectNotFound(project_id.clone()))?;
+    let was_completed = project.status == ProjectStatus::Completed;
 
     let updated = state
         .todo_store
@@ -2384,6 +2862,22 @@ pub async fn update_project(
         .map_err(AppError::Internal)?
         .ok_or_else(|| AppError::ProjectNotFound(project_id.clone()))?;
 
+    if !was_completed && updated.status == ProjectStatus::Completed {
+        match lineage::project_post_mortem_subject(&state, &req.user_id, &updated) {
+            Ok(subject) => {
+                tokio::spawn(lineage::run_post_mortem(
+                    state.clone(),
+                    req.user_id.clone(),
+                    subject,
+                ));
+            }
+            Err(e) => tracing::warn!(
+                project_id = %updated.id.0,
+                "Could not collect post-mortem memories: {e}"
+            ),
+        }
+    }
+
     let formatted = todo_formatter::format_project_updated(&updated);
 
     state.emit_event(MemoryEvent {
@@ -2454,6 +2948,16 @@ pub async fn delete_project(
         return Err(AppError::ProjectNotFound(project_id));
     }
 
+    // A watcher would keep re-indexing the deleted project's files
+    {
+        let state = state.clone();
+        let user_id = req.user_id.clone();
+        let project_id = project.id.clone();
+        tokio::task::spawn_blocking(move || state.file_watchers.stop(&user_id, &project_id))
+            .await
+            .map_err(|e| AppError::Internal(anyhow::anyhow!("Blocking task panicked: {e}")))?;
+    }
+
     let formatted = todo_formatter::format_project_deleted(&project, todos_count);
 
     state.emit_event(MemoryEvent {
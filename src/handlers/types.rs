@@ -137,6 +137,73 @@ pub struct RecallRequest {
     /// Retrieval mode: "semantic", "associative", or "hybrid" (default)
     #[serde(default = "default_recall_mode")]
     pub mode: String,
+    /// Structured metadata filter, applied inside the vector search
+    #[serde(default)]
+    pub filter: Option<RecallFilter>,
+    /// Shared spaces to search alongside the personal store (results are
+    /// merged with reciprocal rank fusion)
+    #[serde(default)]
+    pub spaces: Vec<String>,
+}
+
+/// Metadata filter for /api/recall
+///
+/// Every field is optional; the ones that are set are ANDed together.
+/// Each maps onto a storage `SearchCriteria` variant.
+#[derive(Debug, Default, Deserialize)]
+pub struct RecallFilter {
+    /// Memories with ANY of these tags
+    #[serde(default)]
+    pub tags: Option<Vec<String>>,
+    /// Experience type, e.g. "decision" or "learning"
+    #[serde(default)]
+    pub experience_type: Option<String>,
+    /// Created at or after (open-ended if omitted)
+    #[serde(default)]
+    pub start: Option<chrono::DateTime<chrono::Utc>>,
+    /// Created at or before (open-ended if omitted)
+    #[serde(default)]
+    pub end: Option<chrono::DateTime<chrono::Utc>>,
+    #[serde(default)]
+    pub min_importance: Option<f32>,
+    #[serde(default)]
+    pub max_importance: Option<f32>,
+    #[serde(default)]
+    pub entity: Option<String>,
+    #[serde(default)]
+    pub episode_id: Option<String>,
+    #[serde(default)]
+    pub robot_id: Option<String>,
+    #[serde(default)]
+    pub mission_id: Option<String>,
+    #[serde(default)]
+    pub action_type: Option<String>,
+    /// Agent that created the memory
+    #[serde(default)]
+    pub agent_id: Option<String>,
+    /// Agent run the memory was created in
+    #[serde(default)]
+    pub run_id: Option<String>,
+    /// Actor the agent acted for
+    #[serde(default)]
+    pub actor_id: Option<String>,
+    /// Spatial filter: lat, lon and radius_meters must be given together
+    #[serde(default)]
+    pub lat: Option<f64>,
+    #[serde(default)]
+    pub lon: Option<f64>,
+    #[serde(default)]
+    pub radius_meters: Option<f64>,
+    #[serde(default)]
+    pub min_reward: Option<f32>,
+    #[serde(default)]
+    pub max_reward: Option<f32>,
+    /// Only children of this memory
+    #[serde(default)]
+    pub parent_id: Option<String>,
+    /// Only memories without a parent
+    #[serde(default)]
+    pub roots_only: bool,
 }
 
 pub fn default_recall_limit() -> usize {
@@ -219,6 +286,9 @@ pub struct RecallMemory {
     pub importance: f32,
     pub created_at: String,
     pub score: f32,
+    /// Space the memory came from (absent for the personal store)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub space: Option<String>,
 }
 
 #[derive(Serialize)]
@@ -512,6 +582,20 @@ pub struct RebuildIndexResponse {
     pub is_healthy: bool,
 }
 
+#[derive(Deserialize)]
+pub struct ReembedRequest {
+    pub user_id: String,
+}
+
+#[derive(Serialize)]
+pub struct ReembedResponse {
+    pub success: bool,
+    /// Memories re-embedded and re-indexed
+    pub reembedded: usize,
+    pub model: String,
+    pub dimension: usize,
+}
+
 // =============================================================================
 // BACKUP & RESTORE
 // =============================================================================
@@ -565,6 +649,26 @@ pub struct PurgeBackupsResponse {
     pub purged_count: usize,
 }
 
+#[derive(Deserialize)]
+pub struct RestoreBackupRequest {
+    pub user_id: String,
+    /// Backup to restore (default: newest, or newest before `target_time`)
+    #[serde(default)]
+    pub backup_id: Option<u32>,
+    /// Roll forward from the backup to this point using the WAL archive
+    #[serde(default)]
+    pub target_time: Option<chrono::DateTime<chrono::Utc>>,
+    /// Also roll back shared stores (todos, reminders, files, webhooks) for all users
+    #[serde(default)]
+    pub include_shared_stores: bool,
+}
+
+#[derive(Serialize)]
+pub struct RestoreBackupResponse {
+    pub success: bool,
+    pub restore: crate::backup::RestoreReport,
+}
+
 // =============================================================================
 // CONTEXT STATUS
 // =============================================================================
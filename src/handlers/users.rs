@@ -9,6 +9,7 @@ use axum::{
 use serde::{Deserialize, Serialize};
 
 use super::state::MultiUserMemoryManager;
+use crate::auth::AuthContext;
 use crate::errors::AppError;
 use crate::memory::MemoryStats;
 use std::sync::Arc;
@@ -63,7 +64,12 @@ pub async fn delete_user(
     }))
 }
 
-/// GET /api/users - List all users
-pub async fn list_users(State(state): State<AppState>) -> Json<Vec<String>> {
-    Json(state.list_users())
+/// GET /api/users - List users visible to the calling API key
+pub async fn list_users(State(state): State<AppState>, auth: AuthContext) -> Json<Vec<String>> {
+    let users = state
+        .list_users()
+        .into_iter()
+        .filter(|user_id| auth.can_access_user(user_id))
+        .collect();
+    Json(users)
 }
@@ -15,9 +15,11 @@ use axum::{
 };
 use futures::{SinkExt, StreamExt};
 use std::convert::Infallible;
+use std::sync::Arc;
 use tokio_stream::wrappers::BroadcastStream;
 
 use super::state::MultiUserMemoryManager;
+use crate::auth::AuthContext;
 use crate::relevance;
 use crate::streaming;
 use crate::validation;
@@ -61,17 +63,24 @@ pub async fn context_status_sse(
 /// No authentication required - read-only, lightweight event stream.
 pub async fn memory_events_sse(
     State(state): State<AppState>,
+    auth: AuthContext,
 ) -> Sse<impl futures::Stream<Item = Result<Event, Infallible>>> {
     let receiver = state.subscribe_events();
     let stream = BroadcastStream::new(receiver);
-
-    let event_stream = stream.filter_map(|result| async move {
-        match result {
-            Ok(event) => {
-                let json = serde_json::to_string(&event).ok()?;
-                Some(Ok(Event::default().event(&event.event_type).data(json)))
+    let auth = Arc::new(auth);
+
+    let event_stream = stream.filter_map(move |result| {
+        let auth = Arc::clone(&auth);
+        async move {
+            match result {
+                // Tenant-scoped keys only see events for their own users
+                Ok(event) if !auth.can_access_user(&event.user_id) => None,
+                Ok(event) => {
+                    let json = serde_json::to_string(&event).ok()?;
+                    Some(Ok(Event::default().event(&event.event_type).data(json)))
+                }
+                Err(_) => None, // Lagged receiver, skip event
             }
-            Err(_) => None, // Lagged receiver, skip event
         }
     });
 
@@ -103,12 +112,13 @@ pub async fn memory_events_sse(
 pub async fn streaming_memory_ws(
     ws: WebSocketUpgrade,
     State(state): State<AppState>,
+    auth: AuthContext,
 ) -> impl IntoResponse {
-    ws.on_upgrade(|socket| handle_streaming_socket(socket, state))
+    ws.on_upgrade(|socket| handle_streaming_socket(socket, state, auth))
 }
 
 /// Handle WebSocket connection for streaming memory ingestion
-async fn handle_streaming_socket(socket: WebSocket, state: AppState) {
+async fn handle_streaming_socket(socket: WebSocket, state: AppState, auth: AuthContext) {
     let (mut sender, mut receiver) = socket.split();
     let mut session_id: Option<String> = None;
 
@@ -166,6 +176,28 @@ async fn handle_streaming_socket(socket: WebSocket, state: AppState) {
             return;
         }
 
+        // The handshake is the first place the target user is visible, so
+        // tenant scoping for this endpoint happens here, not in the middleware
+        if !auth.can_access_user(&handshake.user_id) {
+            let error = streaming::ExtractionResult::Error {
+                code: "USER_NOT_PERMITTED".to_string(),
+                message: format!(
+                    "API key is not permitted to access user '{}'",
+                    handshake.user_id
+                ),
+                fatal: true,
+                timestamp: chrono::Utc::now(),
+            };
+            let _ = sender
+                .send(Message::Text(
+                    serde_json::to_string(&error)
+                        .unwrap_or_else(|_| r#"{"error":"serialization_failed"}"#.to_string())
+                        .into(),
+                ))
+                .await;
+            return;
+        }
+
         // Validate extraction config bounds
         {
             let config = &handshake.extraction_config;
@@ -363,12 +395,13 @@ async fn handle_streaming_socket(socket: WebSocket, state: AppState) {
 pub async fn context_monitor_ws(
     ws: WebSocketUpgrade,
     State(state): State<AppState>,
+    auth: AuthContext,
 ) -> impl IntoResponse {
-    ws.on_upgrade(|socket| handle_context_monitor_socket(socket, state))
+    ws.on_upgrade(|socket| handle_context_monitor_socket(socket, state, auth))
 }
 
 /// Handle WebSocket connection for context monitoring
-async fn handle_context_monitor_socket(socket: WebSocket, state: AppState) {
+async fn handle_context_monitor_socket(socket: WebSocket, state: AppState, auth: AuthContext) {
     let (mut sender, mut receiver) = socket.split();
     let mut user_id: Option<String> = None;
     let mut config = relevance::RelevanceConfig::default();
@@ -429,6 +462,27 @@ async fn handle_context_monitor_socket(socket: WebSocket, state: AppState) {
             return;
         }
 
+        // As with the streaming socket, the handshake names the target user
+        if !auth.can_access_user(&handshake.user_id) {
+            let error = relevance::ContextMonitorResponse::Error {
+                code: "USER_NOT_PERMITTED".to_string(),
+                message: format!(
+                    "API key is not permitted to access user '{}'",
+                    handshake.user_id
+                ),
+                fatal: true,
+                timestamp: chrono::Utc::now(),
+            };
+            let _ = sender
+                .send(Message::Text(
+                    serde_json::to_string(&error)
+                        .unwrap_or_else(|_| r#"{"error":"serialization_failed"}"#.to_string())
+                        .into(),
+                ))
+                .await;
+            return;
+        }
+
         user_id = Some(handshake.user_id.clone());
         if let Some(cfg) = handshake.config {
             config = cfg;
@@ -16,6 +16,7 @@
 //! - Full offline operation
 
 pub mod ab_testing;
+pub mod api_keys;
 pub mod auth;
 pub mod backup;
 pub mod config;
@@ -23,15 +24,21 @@ pub mod constants;
 pub mod decay;
 pub mod embeddings;
 pub mod errors;
+pub mod export;
+pub mod graph_analytics;
 pub mod graph_memory;
+pub mod graph_query;
 pub mod handlers;
 pub mod integrations;
+pub mod mcp_params;
 pub mod memory;
 pub mod metrics;
 pub mod middleware;
+pub mod outbound_webhooks;
 pub mod query_parsing;
 pub mod relevance;
 pub mod similarity;
+pub mod spaces;
 pub mod streaming;
 pub mod tracing_setup;
 pub mod validation;
@@ -10,6 +10,7 @@
 //!   -H, --host <HOST>         Bind address [env: SHODH_HOST] [default: 127.0.0.1]
 //!   -p, --port <PORT>         Port number [env: SHODH_PORT] [default: 3030]
 //!   -s, --storage <PATH>      Storage directory [env: SHODH_MEMORY_PATH] [default: ./shodh_memory_data]
+//!   -c, --config <PATH>       Config file (TOML or JSON) [env: SHODH_CONFIG] [default: ./shodh_config.toml or ./shodh_config.json]
 //!   -h, --help                Print help
 //!   -V, --version             Print version
 
@@ -19,17 +20,19 @@ use std::net::SocketAddr;
 use std::path::PathBuf;
 use std::sync::Arc;
 use tokio::signal;
+use tokio::sync::watch;
 use tower::ServiceBuilder;
-use tower_governor::{governor::GovernorConfigBuilder, GovernorLayer};
 use tower_http::timeout::TimeoutLayer;
 use tracing::info;
 
 use shodh_memory::{
-    auth,
-    config::ServerConfig,
+    auth, backup,
+    config::{self, ConfigFile, ServerConfig},
     embeddings::minilm::pre_init_ort_runtime,
     handlers::{self, AppState, MultiUserMemoryManager},
+    memory::{retrieval::VectorIndexConfig, HybridSearchConfig, RerankerModel},
     metrics, middleware,
+    outbound_webhooks::WebhookDispatcher,
 };
 
 #[cfg(feature = "telemetry")]
@@ -82,6 +85,9 @@ EXAMPLES:
   # Using environment variables
   SHODH_PORT=9000 SHODH_HOST=0.0.0.0 shodh-memory-server
 
+  # Using a config file (reload rate limits/CORS/schedules with `kill -HUP`)
+  shodh-memory-server --config /etc/shodh/shodh_config.toml
+
   # Verify server is running
   curl http://localhost:3030/health
 
@@ -94,34 +100,33 @@ DOCUMENTATION:
 #[command(name = "shodh-memory-server")]
 #[command(version, about, long_about = LONG_ABOUT, after_help = AFTER_HELP)]
 struct Cli {
-    /// Bind address (use 0.0.0.0 for network access)
-    #[arg(short = 'H', long, env = "SHODH_HOST", default_value = "127.0.0.1")]
-    host: String,
-
-    /// Port number to listen on
-    #[arg(short, long, env = "SHODH_PORT", default_value_t = 3030)]
-    port: u16,
-
-    /// Storage directory for RocksDB data
-    #[arg(
-        short,
-        long = "storage",
-        env = "SHODH_MEMORY_PATH",
-        default_value = "./shodh_memory_data"
-    )]
-    storage_path: PathBuf,
+    /// Bind address (use 0.0.0.0 for network access) [default: 127.0.0.1]
+    #[arg(short = 'H', long, env = "SHODH_HOST")]
+    host: Option<String>,
+
+    /// Port number to listen on [default: 3030]
+    #[arg(short, long, env = "SHODH_PORT")]
+    port: Option<u16>,
+
+    /// Storage directory for RocksDB data [default: ./shodh_memory_data]
+    #[arg(short, long = "storage", env = "SHODH_MEMORY_PATH")]
+    storage_path: Option<PathBuf>,
+
+    /// Config file (TOML or JSON); defaults to ./shodh_config.toml or ./shodh_config.json if present
+    #[arg(short, long, env = "SHODH_CONFIG")]
+    config: Option<PathBuf>,
 
     /// Production mode: stricter CORS, automatic backups enabled
     #[arg(long, env = "SHODH_ENV")]
     production: bool,
 
-    /// Rate limit: max requests per second per client
-    #[arg(long, env = "SHODH_RATE_LIMIT", default_value_t = 4000)]
-    rate_limit: u64,
+    /// Rate limit: max requests per second per client [default: 4000]
+    #[arg(long, env = "SHODH_RATE_LIMIT")]
+    rate_limit: Option<u64>,
 
-    /// Maximum concurrent requests before load shedding
-    #[arg(long, env = "SHODH_MAX_CONCURRENT", default_value_t = 200)]
-    max_concurrent: usize,
+    /// Maximum concurrent requests before load shedding [default: 200]
+    #[arg(long, env = "SHODH_MAX_CONCURRENT")]
+    max_concurrent: Option<usize>,
 }
 
 // Timeout for draining in-flight requests (not in constants.rs — server-specific)
@@ -139,19 +144,39 @@ fn main() -> Result<()> {
     // Parse CLI arguments FIRST (enables --help without initializing storage)
     let cli = Cli::parse();
 
-    // Set environment variables from CLI args so ServerConfig::from_env() picks them up.
+    // Set environment variables from CLI args so ServerConfig::load() picks them up.
+    // Only flags that were actually given are exported, so config file values
+    // are not clobbered by CLI defaults (layering: defaults → file → env → CLI).
     // Safe here: no threads exist yet — we haven't built the tokio runtime.
-    std::env::set_var("SHODH_HOST", &cli.host);
-    std::env::set_var("SHODH_PORT", cli.port.to_string());
-    std::env::set_var(
-        "SHODH_MEMORY_PATH",
-        cli.storage_path.to_string_lossy().to_string(),
-    );
+    if let Some(host) = &cli.host {
+        std::env::set_var("SHODH_HOST", host);
+    }
+    if let Some(port) = cli.port {
+        std::env::set_var("SHODH_PORT", port.to_string());
+    }
+    if let Some(storage_path) = &cli.storage_path {
+        std::env::set_var(
+            "SHODH_MEMORY_PATH",
+            storage_path.to_string_lossy().to_string(),
+        );
+    }
     if cli.production {
         std::env::set_var("SHODH_ENV", "production");
     }
-    std::env::set_var("SHODH_RATE_LIMIT", cli.rate_limit.to_string());
-    std::env::set_var("SHODH_MAX_CONCURRENT", cli.max_concurrent.to_string());
+    if let Some(rate_limit) = cli.rate_limit {
+        std::env::set_var("SHODH_RATE_LIMIT", rate_limit.to_string());
+    }
+    if let Some(max_concurrent) = cli.max_concurrent {
+        std::env::set_var("SHODH_MAX_CONCURRENT", max_concurrent.to_string());
+    }
+
+    // Embedding, vector index and dev key settings from the config file are read
+    // through env vars, so export them now while we are still single-threaded.
+    let config_path = config::find_config_file(cli.config.as_deref());
+    if let Some(path) = &config_path {
+        let (config_file, _) = ConfigFile::load(path)?;
+        config_file.export_env();
+    }
 
     // Pre-initialize ORT_DYLIB_PATH before any threads are spawned.
     pre_init_ort_runtime(false);
@@ -169,10 +194,10 @@ fn main() -> Result<()> {
         .enable_all()
         .build()
         .expect("Failed to build tokio runtime")
-        .block_on(async_main())
+        .block_on(async_main(config_path))
 }
 
-async fn async_main() -> Result<()> {
+async fn async_main(config_path: Option<PathBuf>) -> Result<()> {
     // Initialize tracing
     #[cfg(feature = "telemetry")]
     {
@@ -192,10 +217,16 @@ async fn async_main() -> Result<()> {
     // Register Prometheus metrics
     metrics::register_metrics().expect("Failed to register metrics");
 
-    // Load configuration
-    let server_config = ServerConfig::from_env();
+    // Load configuration (defaults → config file → env/CLI)
+    if let Some(path) = &config_path {
+        info!("Loading config file {}", path.display());
+    }
+    let server_config = ServerConfig::load(config_path.as_deref())?;
     print_config(&server_config);
 
+    // Hot-reloadable settings are published to schedulers and layers via this channel
+    let (config_tx, config_rx) = watch::channel(server_config.clone());
+
     // Create memory manager
     let manager: AppState = Arc::new(MultiUserMemoryManager::new(
         server_config.storage_path.clone(),
@@ -209,40 +240,38 @@ async fn async_main() -> Result<()> {
     let manager_for_shutdown = Arc::clone(&manager);
 
     // Start background maintenance scheduler
-    start_maintenance_scheduler(
-        Arc::clone(&manager),
-        server_config.maintenance_interval_secs,
-    );
+    start_maintenance_scheduler(Arc::clone(&manager), config_rx.clone());
 
-    // Start backup scheduler if enabled
-    if server_config.backup_enabled && server_config.backup_interval_secs > 0 {
-        start_backup_scheduler(
-            Arc::clone(&manager),
-            server_config.backup_interval_secs,
-            server_config.backup_max_count,
-        );
-    }
+    // Start backup scheduler (idles while backups are disabled)
+    start_backup_scheduler(Arc::clone(&manager), config_rx.clone());
+
+    // Copy new WAL batches to the backup archive for point-in-time restores
+    start_wal_archiver(Arc::clone(&manager));
+    start_wal_clock(Arc::clone(&manager));
+
+    // Deliver queued outbound webhooks (retries with backoff)
+    start_webhook_dispatcher(Arc::clone(&manager));
 
-    // Configure rate limiting (0 = disabled, for localhost/embedded use)
-    let rate_limit_enabled = server_config.rate_limit_per_second > 0;
-    let governor_layer = if rate_limit_enabled {
-        let governor_conf = GovernorConfigBuilder::default()
-            .per_second(server_config.rate_limit_per_second)
-            .burst_size(server_config.rate_limit_burst)
-            .finish()
-            .expect("Failed to build governor rate limiter configuration");
+    // Fetch and load the reranker off the request path
+    start_cross_encoder_preload(&manager);
+
+    // Rate limiting (0 = disabled, for localhost/embedded use) and CORS,
+    // rebuilt whenever the config is reloaded
+    let reloadable_layers = Arc::new(middleware::ReloadableLayers::new(&server_config));
+    if reloadable_layers.rate_limit_enabled() {
         info!(
             "Rate limiting: {} req/sec, burst of {}",
             server_config.rate_limit_per_second, server_config.rate_limit_burst
         );
-        Some(GovernorLayer::new(governor_conf))
     } else {
         info!("Rate limiting: disabled (SHODH_RATE_LIMIT=0)");
-        None
-    };
+    }
+    start_layer_reloader(Arc::clone(&reloadable_layers), config_rx);
 
-    // Build CORS layer
-    let cors = server_config.cors.to_layer();
+    match config_path {
+        Some(path) => config::spawn_config_watcher(path, config_tx),
+        None => info!("No config file found, hot reload disabled"),
+    }
 
     // Build routes using handlers module
     let public_routes = handlers::build_public_routes(Arc::clone(&manager)).route(
@@ -265,14 +294,16 @@ async fn async_main() -> Result<()> {
         }),
     );
 
-    let protected_routes = if let Some(governor) = governor_layer {
-        handlers::build_protected_routes(Arc::clone(&manager))
-            .layer(axum::middleware::from_fn(auth::auth_middleware))
-            .layer(governor)
-    } else {
-        handlers::build_protected_routes(Arc::clone(&manager))
-            .layer(axum::middleware::from_fn(auth::auth_middleware))
-    };
+    let auth_layer = axum::middleware::from_fn_with_state(
+        Arc::clone(manager.api_key_store()),
+        auth::auth_middleware,
+    );
+    let protected_routes = handlers::build_protected_routes(Arc::clone(&manager))
+        .layer(auth_layer)
+        .layer(axum::middleware::from_fn_with_state(
+            Arc::clone(&reloadable_layers),
+            middleware::reloadable_rate_limit,
+        ));
 
     // Combine routes with global middleware
     // Note: Routes already have state from build_public_routes/build_protected_routes
@@ -290,7 +321,10 @@ async fn async_main() -> Result<()> {
                 .layer(tower::limit::ConcurrencyLimitLayer::new(
                     server_config.max_concurrent_requests,
                 ))
-                .layer(cors),
+                .layer(axum::middleware::from_fn_with_state(
+                    reloadable_layers,
+                    middleware::reloadable_cors,
+                )),
         );
 
     // Conditionally add trace propagation
@@ -358,12 +392,26 @@ async fn async_main() -> Result<()> {
 // Background Schedulers
 // =============================================================================
 
-fn start_maintenance_scheduler(manager: AppState, interval_secs: u64) {
+fn start_maintenance_scheduler(manager: AppState, mut config_rx: watch::Receiver<ServerConfig>) {
+    let initial_secs = config_rx.borrow_and_update().maintenance_interval_secs;
+
     tokio::spawn(async move {
+        let mut interval_secs = initial_secs;
         let mut interval = tokio::time::interval(std::time::Duration::from_secs(interval_secs));
 
         loop {
-            interval.tick().await;
+            tokio::select! {
+                _ = interval.tick() => {}
+                Ok(()) = config_rx.changed() => {
+                    let new_secs = config_rx.borrow_and_update().maintenance_interval_secs;
+                    if new_secs != interval_secs {
+                        interval_secs = new_secs;
+                        interval = delayed_interval(interval_secs);
+                        info!("Maintenance interval changed to {}s", interval_secs);
+                    }
+                    continue;
+                }
+            }
 
             // Cleanup stale streaming sessions
             let extractor = manager.streaming_extractor().clone();
@@ -382,6 +430,13 @@ fn start_maintenance_scheduler(manager: AppState, interval_secs: u64) {
             let manager_clone = Arc::clone(&manager);
             tokio::task::spawn_blocking(move || {
                 manager_clone.run_maintenance_all_users();
+                match manager_clone.session_store().apply_retention() {
+                    Ok(removed) if removed > 0 => {
+                        tracing::debug!("Removed {} sessions past retention", removed);
+                    }
+                    Ok(_) => {}
+                    Err(e) => tracing::warn!("Session retention failed: {}", e),
+                }
                 if let Err(e) = manager_clone.flush_all_databases() {
                     tracing::warn!("Periodic flush failed: {}", e);
                 }
@@ -391,41 +446,220 @@ fn start_maintenance_scheduler(manager: AppState, interval_secs: u64) {
 
     info!(
         "Background maintenance scheduler started (interval: {}s)",
-        interval_secs
+        initial_secs
     );
 }
 
-fn start_backup_scheduler(manager: AppState, interval_secs: u64, max_backups: usize) {
+fn start_backup_scheduler(manager: AppState, mut config_rx: watch::Receiver<ServerConfig>) {
+    let schedule = |config: &ServerConfig| {
+        (
+            config.backup_enabled && config.backup_interval_secs > 0,
+            config.backup_interval_secs,
+            config.backup_max_count,
+        )
+    };
+    let initial = schedule(&config_rx.borrow_and_update());
+
     tokio::spawn(async move {
-        let mut interval = tokio::time::interval(std::time::Duration::from_secs(interval_secs));
+        let (mut enabled, mut interval_secs, mut max_backups) = initial;
+
+        loop {
+            if !enabled {
+                // Idle until a reload turns backups on
+                if config_rx.changed().await.is_err() {
+                    return;
+                }
+                let next = schedule(&config_rx.borrow_and_update());
+                if next.0 {
+                    info!(
+                        "Automatic backups enabled (interval: {}h, keep: {} backups)",
+                        next.1 / 3600,
+                        next.2
+                    );
+                }
+                (enabled, interval_secs, max_backups) = next;
+                continue;
+            }
 
-        // Skip first immediate tick
-        interval.tick().await;
+            // First tick is one full interval away (no backup at startup)
+            let mut interval = delayed_interval(interval_secs);
+
+            loop {
+                tokio::select! {
+                    _ = interval.tick() => {
+                        info!("Starting scheduled backup run...");
+                        let manager_clone = Arc::clone(&manager);
+                        let backed_up = tokio::task::spawn_blocking(move || {
+                            manager_clone.run_backup_all_users(max_backups)
+                        })
+                        .await
+                        .unwrap_or(0);
+
+                        if backed_up > 0 {
+                            info!("Scheduled backup completed: {} users backed up", backed_up);
+                        }
+                    }
+                    Ok(()) = config_rx.changed() => {
+                        let next = schedule(&config_rx.borrow_and_update());
+                        if next == (enabled, interval_secs, max_backups) {
+                            continue;
+                        }
+                        if next.0 {
+                            info!(
+                                "Backup schedule changed (interval: {}h, keep: {} backups)",
+                                next.1 / 3600,
+                                next.2
+                            );
+                        } else {
+                            info!("Automatic backups disabled");
+                        }
+                        (enabled, interval_secs, max_backups) = next;
+                        break;
+                    }
+                }
+            }
+        }
+    });
 
+    if initial.0 {
+        info!(
+            "Automatic backup scheduler started (interval: {}h, keep: {} backups)",
+            initial.1 / 3600,
+            initial.2
+        );
+    }
+}
+
+/// How often new WAL batches are archived
+///
+/// Not the granularity of PITR: batches are dated by the sequence clock
+/// (`start_wal_clock`), not by the archiver run that copies them.
+const WAL_ARCHIVE_INTERVAL_SECS: u64 = 60;
+
+fn start_wal_archiver(manager: AppState) {
+    tokio::spawn(async move {
+        let mut interval = delayed_interval(WAL_ARCHIVE_INTERVAL_SECS);
         loop {
             interval.tick().await;
-
-            info!("Starting scheduled backup run...");
             let manager_clone = Arc::clone(&manager);
-            let backed_up = tokio::task::spawn_blocking(move || {
-                manager_clone.run_backup_all_users(max_backups)
-            })
-            .await
-            .unwrap_or(0);
-
-            if backed_up > 0 {
-                info!("Scheduled backup completed: {} users backed up", backed_up);
+            let archived =
+                tokio::task::spawn_blocking(move || manager_clone.archive_wal_all_users())
+                    .await
+                    .unwrap_or(0);
+            if archived > 0 {
+                tracing::debug!("Archived {} WAL batches", archived);
             }
         }
     });
 
     info!(
-        "Automatic backup scheduler started (interval: {}h, keep: {} backups)",
-        interval_secs / 3600,
-        max_backups
+        "WAL archiver started (interval: {}s)",
+        WAL_ARCHIVE_INTERVAL_SECS
     );
 }
 
+/// Sample the sequence numbers of open stores so archived WAL batches can be
+/// dated to within `backup::WAL_CLOCK_INTERVAL_SECS` of their commit
+fn start_wal_clock(manager: AppState) {
+    tokio::spawn(async move {
+        let mut interval = delayed_interval(backup::WAL_CLOCK_INTERVAL_SECS);
+        loop {
+            interval.tick().await;
+            let manager_clone = Arc::clone(&manager);
+            let _ = tokio::task::spawn_blocking(move || manager_clone.sample_wal_clock_all_users())
+                .await;
+        }
+    });
+}
+
+/// Poll interval for webhook retries; new deliveries wake the loop immediately
+const WEBHOOK_POLL_INTERVAL_SECS: u64 = 5;
+
+fn start_webhook_dispatcher(manager: AppState) {
+    let store = Arc::clone(manager.webhook_store());
+    let dispatcher = match WebhookDispatcher::new(Arc::clone(&store)) {
+        Ok(dispatcher) => dispatcher,
+        Err(e) => {
+            tracing::error!("Webhook dispatcher disabled: {}", e);
+            return;
+        }
+    };
+
+    tokio::spawn(async move {
+        loop {
+            match dispatcher.dispatch_due().await {
+                // A full batch may mean more are waiting
+                Ok(n) if n > 0 => continue,
+                Ok(_) => {}
+                Err(e) => tracing::warn!("Webhook dispatch failed: {}", e),
+            }
+            tokio::select! {
+                _ = store.notified() => {}
+                _ = tokio::time::sleep(std::time::Duration::from_secs(WEBHOOK_POLL_INTERVAL_SECS)) => {}
+            }
+        }
+    });
+
+    info!("Webhook dispatcher started");
+}
+
+/// Download attempts before leaving the cross-encoder to on-demand loads,
+/// which only pick up model files already on disk
+const CROSS_ENCODER_PRELOAD_ATTEMPTS: u32 = 4;
+
+/// Delay before the first retry; doubled after each failure
+const CROSS_ENCODER_PRELOAD_RETRY_SECS: u64 = 30;
+
+/// Download and load the reranker model when cross-encoder reranking is on,
+/// so the first reranked recall does not block on it
+fn start_cross_encoder_preload(manager: &AppState) {
+    let search_config = HybridSearchConfig::from_env();
+    if !search_config.use_reranking || search_config.reranker_model != RerankerModel::CrossEncoder {
+        return;
+    }
+
+    let encoder = Arc::clone(&manager.cross_encoder);
+    tokio::spawn(async move {
+        let mut delay = std::time::Duration::from_secs(CROSS_ENCODER_PRELOAD_RETRY_SECS);
+        for attempt in 1..=CROSS_ENCODER_PRELOAD_ATTEMPTS {
+            let encoder_clone = Arc::clone(&encoder);
+            match tokio::task::spawn_blocking(move || encoder_clone.preload()).await {
+                Ok(Ok(())) => return,
+                Ok(Err(e)) => tracing::warn!(
+                    "Cross-encoder preload failed (attempt {}/{}): {:#}",
+                    attempt,
+                    CROSS_ENCODER_PRELOAD_ATTEMPTS,
+                    e
+                ),
+                Err(e) => tracing::warn!("Cross-encoder preload task failed: {}", e),
+            }
+            if attempt < CROSS_ENCODER_PRELOAD_ATTEMPTS {
+                tokio::time::sleep(delay).await;
+                delay *= 2;
+            }
+        }
+    });
+}
+
+/// Interval whose first tick is one period from now (tokio's fires immediately)
+fn delayed_interval(secs: u64) -> tokio::time::Interval {
+    let period = std::time::Duration::from_secs(secs);
+    tokio::time::interval_at(tokio::time::Instant::now() + period, period)
+}
+
+/// Rebuild the rate limiter and CORS layers whenever the config is reloaded
+fn start_layer_reloader(
+    layers: Arc<middleware::ReloadableLayers>,
+    mut config_rx: watch::Receiver<ServerConfig>,
+) {
+    tokio::spawn(async move {
+        while config_rx.changed().await.is_ok() {
+            let config = config_rx.borrow_and_update().clone();
+            layers.apply(&config);
+        }
+    });
+}
+
 // =============================================================================
 // Shutdown Handling
 // =============================================================================
@@ -461,10 +695,13 @@ async fn run_shutdown_cleanup(manager: AppState) {
     info!("Proceeding with database flush...");
 
     let cleanup_future = async {
-        // Flush databases (blocking operation, must use spawn_blocking)
+        // Stop codebase watchers so nothing writes during the flush, then flush
+        // databases (blocking operations, must use spawn_blocking)
         let manager_for_flush = Arc::clone(&manager);
-        let flush_handle =
-            tokio::task::spawn_blocking(move || manager_for_flush.flush_all_databases());
+        let flush_handle = tokio::task::spawn_blocking(move || {
+            manager_for_flush.file_watchers().stop_all();
+            manager_for_flush.flush_all_databases()
+        });
 
         match tokio::time::timeout(
             std::time::Duration::from_secs(DATABASE_FLUSH_TIMEOUT_SECS),
@@ -482,24 +719,28 @@ async fn run_shutdown_cleanup(manager: AppState) {
         }
 
         // Save vector indices (blocking operation, must use spawn_blocking)
-        info!("Persisting vector indices...");
-        let manager_for_save = Arc::clone(&manager);
-        let save_handle =
-            tokio::task::spawn_blocking(move || manager_for_save.save_all_vector_indices());
-
-        match tokio::time::timeout(
-            std::time::Duration::from_secs(VECTOR_INDEX_SAVE_TIMEOUT_SECS),
-            save_handle,
-        )
-        .await
-        {
-            Ok(Ok(Ok(()))) => info!("Vector indices saved successfully"),
-            Ok(Ok(Err(e))) => tracing::error!("Failed to save vector indices: {}", e),
-            Ok(Err(e)) => tracing::error!("Save task panicked: {}", e),
-            Err(_) => tracing::error!(
-                "Vector index save timed out after {}s",
-                VECTOR_INDEX_SAVE_TIMEOUT_SECS
-            ),
+        if VectorIndexConfig::from_env().save_on_shutdown {
+            info!("Persisting vector indices...");
+            let manager_for_save = Arc::clone(&manager);
+            let save_handle =
+                tokio::task::spawn_blocking(move || manager_for_save.save_all_vector_indices());
+
+            match tokio::time::timeout(
+                std::time::Duration::from_secs(VECTOR_INDEX_SAVE_TIMEOUT_SECS),
+                save_handle,
+            )
+            .await
+            {
+                Ok(Ok(Ok(()))) => info!("Vector indices saved successfully"),
+                Ok(Ok(Err(e))) => tracing::error!("Failed to save vector indices: {}", e),
+                Ok(Err(e)) => tracing::error!("Save task panicked: {}", e),
+                Err(_) => tracing::error!(
+                    "Vector index save timed out after {}s",
+                    VECTOR_INDEX_SAVE_TIMEOUT_SECS
+                ),
+            }
+        } else {
+            info!("Skipping vector index save (save_on_shutdown disabled), indices rebuild on next start");
         }
 
         #[cfg(feature = "telemetry")]
@@ -618,13 +859,8 @@ fn count_user_directories(path: &std::path::Path) -> usize {
                     let name = e.file_name();
                     let name_str = name.to_string_lossy();
                     e.path().is_dir()
-                        && name_str != "audit_logs"
-                        && name_str != "backups"
-                        && name_str != "feedback"
-                        && name_str != "semantic_facts"
-                        && name_str != "files"
-                        && name_str != "prospective"
-                        && name_str != "todos"
+                        && !shodh_memory::validation::RESERVED_USER_IDS.contains(&name_str.as_ref())
+                        && !name_str.starts_with('.')
                 })
                 .count()
         })
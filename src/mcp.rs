@@ -12,12 +12,12 @@ use clap::{Parser, Subcommand};
 use rmcp::{
     handler::server::{router::tool::ToolRouter, wrapper::Parameters},
     model::{
-        CallToolResult, Content, ErrorCode, Implementation, ProtocolVersion, ServerCapabilities,
-        ServerInfo,
+        CallToolResult, ErrorCode, Implementation, ProtocolVersion, ServerCapabilities, ServerInfo,
     },
-    schemars, tool, tool_handler, tool_router, ErrorData as McpError, ServerHandler, ServiceExt,
+    tool, tool_handler, tool_router, ErrorData as McpError, ServerHandler, ServiceExt,
 };
 use serde::{Deserialize, Serialize};
+use shodh_memory::mcp_params::*;
 use std::{borrow::Cow, sync::Arc};
 
 // =============================================================================
@@ -166,6 +166,50 @@ impl AsyncApiClient {
 
         Ok(resp.json().await?)
     }
+
+    async fn get<R: for<'de> Deserialize<'de>>(&self, endpoint: &str) -> Result<R> {
+        let url = format!("{}{endpoint}", self.base_url);
+        let req = self.client.get(&url).query(&[("user_id", &self.user_id)]);
+        self.send(req).await
+    }
+
+    async fn delete<R: for<'de> Deserialize<'de>>(&self, endpoint: &str) -> Result<R> {
+        let url = format!("{}{endpoint}", self.base_url);
+        let req = self
+            .client
+            .delete(&url)
+            .query(&[("user_id", &self.user_id)]);
+        self.send(req).await
+    }
+
+    /// POST with `user_id` as a query parameter and no body
+    async fn post_query<R: for<'de> Deserialize<'de>>(&self, endpoint: &str) -> Result<R> {
+        let url = format!("{}{endpoint}", self.base_url);
+        let req = self.client.post(&url).query(&[("user_id", &self.user_id)]);
+        self.send(req).await
+    }
+
+    async fn send<R: for<'de> Deserialize<'de>>(&self, req: reqwest::RequestBuilder) -> Result<R> {
+        let resp = req.header("X-API-Key", &self.api_key).send().await?;
+
+        if !resp.status().is_success() {
+            let status = resp.status();
+            let text = resp.text().await.unwrap_or_default();
+            anyhow::bail!("API error {status}: {text}");
+        }
+
+        Ok(resp.json().await?)
+    }
+
+    /// Send a tool's REST call as this client's user
+    async fn call(&self, call: &RestCall) -> Result<serde_json::Value> {
+        match (call.method, call.body_for(&self.user_id)) {
+            (RestMethod::Post, Some(body)) => self.post(&call.path, &body).await,
+            (RestMethod::Post, None) => self.post_query(&call.path).await,
+            (RestMethod::Get, _) => self.get(&call.path).await,
+            (RestMethod::Delete, _) => self.delete(&call.path).await,
+        }
+    }
 }
 
 /// HTTP client for the shodh-memory API (blocking version for hooks)
@@ -238,6 +282,7 @@ struct SurfacedMemory {
 struct ListTodosRequest {
     user_id: String,
     status: Vec<String>,
+    actionable: bool,
 }
 
 #[derive(Deserialize)]
@@ -256,43 +301,6 @@ struct Todo {
     project: Option<String>,
 }
 
-#[derive(Serialize)]
-struct RememberRequest {
-    user_id: String,
-    content: String,
-    memory_type: Option<String>,
-    tags: Option<Vec<String>>,
-}
-
-#[derive(Deserialize)]
-struct RememberResponse {
-    id: String,
-    message: String,
-}
-
-#[derive(Serialize)]
-struct RecallRequest {
-    user_id: String,
-    query: String,
-    limit: Option<u32>,
-    mode: Option<String>,
-}
-
-#[derive(Deserialize)]
-struct RecallResponse {
-    memories: Vec<RecalledMemory>,
-}
-
-#[derive(Deserialize)]
-struct RecalledMemory {
-    id: String,
-    content: String,
-    memory_type: String,
-    similarity: f32,
-    #[allow(dead_code)]
-    tags: Vec<String>,
-}
-
 // =============================================================================
 // HOOK OUTPUT
 // =============================================================================
@@ -350,6 +358,7 @@ fn handle_session_start(api_url: &str, api_key: &str, user_id: &str, project_dir
         &ListTodosRequest {
             user_id: user_id.to_string(),
             status: vec!["todo".to_string(), "in_progress".to_string()],
+            actionable: true,
         },
     );
 
@@ -448,164 +457,6 @@ fn handle_prompt_submit(api_url: &str, api_key: &str, user_id: &str, message: &s
     }
 }
 
-// =============================================================================
-// MCP TOOL PARAMETER TYPES
-// =============================================================================
-
-#[derive(Debug, Deserialize, schemars::JsonSchema)]
-struct RememberParams {
-    /// The content to remember
-    content: String,
-    /// Type of memory (Observation, Decision, Learning, etc.)
-    #[serde(rename = "type")]
-    memory_type: Option<String>,
-    /// Optional tags for categorization
-    tags: Option<Vec<String>>,
-}
-
-#[derive(Debug, Deserialize, schemars::JsonSchema)]
-struct RecallParams {
-    /// Natural language search query
-    query: String,
-    /// Maximum number of results (default: 5)
-    limit: Option<u32>,
-    /// Retrieval mode: semantic, associative, or hybrid
-    mode: Option<String>,
-}
-
-#[derive(Debug, Deserialize, schemars::JsonSchema)]
-struct ProactiveContextParams {
-    /// Current conversation context
-    context: String,
-    /// Maximum memories to surface (default: 5)
-    max_results: Option<u32>,
-    /// Auto-store context for feedback (default: true)
-    auto_ingest: Option<bool>,
-}
-
-// =============================================================================
-// LINEAGE MCP TOOL PARAMETERS
-// =============================================================================
-
-#[derive(Debug, Deserialize, schemars::JsonSchema)]
-struct LineageTraceParams {
-    /// Memory ID to trace lineage from
-    memory_id: String,
-    /// Direction: "backward" (find causes), "forward" (find effects), "both"
-    direction: Option<String>,
-    /// Maximum depth to traverse (default: 10)
-    max_depth: Option<u32>,
-}
-
-#[derive(Debug, Deserialize, schemars::JsonSchema)]
-struct LineageConfirmParams {
-    /// ID of the inferred edge to confirm
-    edge_id: String,
-}
-
-#[derive(Debug, Deserialize, schemars::JsonSchema)]
-struct LineageRejectParams {
-    /// ID of the inferred edge to reject
-    edge_id: String,
-}
-
-#[derive(Debug, Deserialize, schemars::JsonSchema)]
-struct LineageLinkParams {
-    /// Source memory ID (the cause/origin)
-    from_memory_id: String,
-    /// Target memory ID (the effect/result)
-    to_memory_id: String,
-    /// Relation type: Caused, ResolvedBy, InformedBy, SupersededBy, TriggeredBy, BranchedFrom, RelatedTo
-    relation: String,
-}
-
-#[derive(Debug, Deserialize, schemars::JsonSchema)]
-struct LineageStatsParams {
-    /// Optional - leave empty to get stats for current user
-    #[serde(default)]
-    _placeholder: Option<String>,
-}
-
-// Lineage API request types (for API calls)
-#[derive(Serialize)]
-struct LineageTraceRequest {
-    user_id: String,
-    memory_id: String,
-    direction: String,
-    max_depth: u32,
-}
-
-#[derive(Serialize)]
-struct LineageEdgeRequest {
-    user_id: String,
-    edge_id: String,
-}
-
-#[derive(Serialize)]
-struct LineageAddEdgeRequest {
-    user_id: String,
-    from_memory_id: String,
-    to_memory_id: String,
-    relation: String,
-}
-
-#[derive(Serialize)]
-struct LineageStatsRequest {
-    user_id: String,
-}
-
-// Lineage API response types
-#[derive(Deserialize)]
-struct LineageTraceResponse {
-    root: String,
-    direction: String,
-    edges: Vec<LineageEdgeInfo>,
-    path: Vec<String>,
-    depth: usize,
-}
-
-#[derive(Deserialize)]
-struct LineageEdgeInfo {
-    #[allow(dead_code)]
-    id: String,
-    from: String,
-    to: String,
-    relation: String,
-    confidence: f32,
-    source: String,
-}
-
-#[derive(Deserialize)]
-struct LineageConfirmResponse {
-    message: String,
-    edge_id: String,
-}
-
-#[derive(Deserialize)]
-struct LineageRejectResponse {
-    message: String,
-    #[allow(dead_code)]
-    deleted: bool,
-}
-
-#[derive(Deserialize)]
-struct LineageAddResponse {
-    message: String,
-    edge_id: String,
-}
-
-#[derive(Deserialize)]
-struct LineageStatsResponse {
-    total_edges: usize,
-    inferred_edges: usize,
-    confirmed_edges: usize,
-    explicit_edges: usize,
-    total_branches: usize,
-    active_branches: usize,
-    edges_by_relation: std::collections::HashMap<String, usize>,
-    avg_confidence: f32,
-}
-
 // =============================================================================
 // MCP SERVER
 // =============================================================================
@@ -621,330 +472,404 @@ impl ShodhMcpServer {
     fn new(api_url: String, api_key: String, user_id: String) -> Self {
         Self {
             client: Arc::new(AsyncApiClient::new(api_url, api_key, user_id)),
-            tool_router: Self::tool_router(),
+            tool_router: describe_tools(Self::tool_router()),
         }
     }
 
-    #[tool(
-        description = "Store a memory for future recall. Use this to remember important information, decisions, user preferences, project context, or anything you want to recall later."
-    )]
+    #[tool]
     async fn remember(
         &self,
         Parameters(params): Parameters<RememberParams>,
     ) -> Result<CallToolResult, McpError> {
-        let result: Result<RememberResponse> = self
-            .client
-            .post(
-                "/api/remember",
-                &RememberRequest {
-                    user_id: self.client.user_id.clone(),
-                    content: params.content,
-                    memory_type: params.memory_type,
-                    tags: params.tags,
-                },
-            )
-            .await;
-
-        match result {
-            Ok(resp) => Ok(CallToolResult::success(vec![Content::text(format!(
-                "Stored memory: {} ({})",
-                resp.id, resp.message
-            ))])),
-            Err(e) => Err(McpError {
-                code: ErrorCode::INTERNAL_ERROR,
-                message: Cow::from(e.to_string()),
-                data: None,
-            }),
-        }
+        self.run(remember_call(&params)).await
     }
 
-    #[tool(
-        description = "Search memories using semantic, associative, or hybrid retrieval. Modes: 'semantic' (vector similarity), 'associative' (graph traversal), 'hybrid' (combines both)."
-    )]
+    #[tool]
     async fn recall(
         &self,
         Parameters(params): Parameters<RecallParams>,
     ) -> Result<CallToolResult, McpError> {
-        let result: Result<RecallResponse> = self
-            .client
-            .post(
-                "/api/recall",
-                &RecallRequest {
-                    user_id: self.client.user_id.clone(),
-                    query: params.query,
-                    limit: params.limit,
-                    mode: params.mode,
-                },
-            )
-            .await;
-
-        match result {
-            Ok(resp) => {
-                let mut output = format!("Found {} memories:\n\n", resp.memories.len());
-                for mem in resp.memories {
-                    output.push_str(&format!(
-                        "**[{}]** {} (similarity: {:.0}%)\n{}\n\n",
-                        mem.memory_type,
-                        &mem.id[..8.min(mem.id.len())],
-                        mem.similarity * 100.0,
-                        mem.content
-                    ));
-                }
-                Ok(CallToolResult::success(vec![Content::text(output)]))
-            }
-            Err(e) => Err(McpError {
-                code: ErrorCode::INTERNAL_ERROR,
-                message: Cow::from(e.to_string()),
-                data: None,
-            }),
-        }
+        self.run(recall_call(&params)).await
     }
 
-    #[tool(
-        description = "REQUIRED: Call this to surface relevant memories based on current context. Enables automatic memory surfacing and implicit feedback learning."
-    )]
+    #[tool]
     async fn proactive_context(
         &self,
         Parameters(params): Parameters<ProactiveContextParams>,
     ) -> Result<CallToolResult, McpError> {
-        let result: Result<ProactiveContextResponse> = self
-            .client
-            .post(
-                "/api/proactive_context",
-                &ProactiveContextRequest {
-                    user_id: self.client.user_id.clone(),
-                    context: params.context,
-                    max_results: params.max_results.unwrap_or(5),
-                    auto_ingest: params.auto_ingest.unwrap_or(true),
-                },
-            )
-            .await;
-
-        match result {
-            Ok(resp) => {
-                let mut output = format!("Surfaced {} relevant memories:\n\n", resp.memories.len());
-                for mem in resp.memories {
-                    output.push_str(&format!(
-                        "- [{}%] **{}**: {}\n",
-                        (mem.relevance_score * 100.0) as u32,
-                        mem.memory_type,
-                        mem.content.chars().take(200).collect::<String>()
-                    ));
-                }
-                Ok(CallToolResult::success(vec![Content::text(output)]))
-            }
-            Err(e) => Err(McpError {
-                code: ErrorCode::INTERNAL_ERROR,
-                message: Cow::from(e.to_string()),
-                data: None,
-            }),
-        }
+        self.run(proactive_context_call(&params)).await
     }
 
     // =========================================================================
     // LINEAGE TOOLS - Causal Memory Tracking
     // =========================================================================
 
-    #[tool(
-        description = "Trace the causal lineage of a memory. Find what caused it (backward), what it led to (forward), or both. Useful for understanding 'why' something happened."
-    )]
+    #[tool]
     async fn lineage_trace(
         &self,
         Parameters(params): Parameters<LineageTraceParams>,
     ) -> Result<CallToolResult, McpError> {
-        let result: Result<LineageTraceResponse> = self
-            .client
-            .post(
-                "/api/lineage/trace",
-                &LineageTraceRequest {
-                    user_id: self.client.user_id.clone(),
-                    memory_id: params.memory_id,
-                    direction: params.direction.unwrap_or_else(|| "backward".to_string()),
-                    max_depth: params.max_depth.unwrap_or(10),
-                },
-            )
-            .await;
-
-        match result {
-            Ok(resp) => {
-                let mut output = format!(
-                    "**Lineage Trace** ({})\n\nRoot: {}\nDepth: {}\n\n",
-                    resp.direction, resp.root, resp.depth
-                );
-
-                if resp.edges.is_empty() {
-                    output.push_str("No causal connections found.\n");
-                } else {
-                    output.push_str("**Causal Chain:**\n");
-                    for edge in &resp.edges {
-                        let confidence = (edge.confidence * 100.0) as u32;
-                        let source_icon = match edge.source.as_str() {
-                            "Confirmed" => "✓",
-                            "Explicit" => "⚡",
-                            _ => "?",
-                        };
-                        output.push_str(&format!(
-                            "  {} --[{} {}% {}]--> {}\n",
-                            &edge.from[..8.min(edge.from.len())],
-                            edge.relation,
-                            confidence,
-                            source_icon,
-                            &edge.to[..8.min(edge.to.len())]
-                        ));
-                    }
-
-                    output.push_str(&format!("\n**Path:** {}\n", resp.path.join(" → ")));
-                }
-
-                Ok(CallToolResult::success(vec![Content::text(output)]))
-            }
-            Err(e) => Err(McpError {
-                code: ErrorCode::INTERNAL_ERROR,
-                message: Cow::from(e.to_string()),
-                data: None,
-            }),
-        }
+        self.run(lineage_trace_call(&params)).await
     }
 
-    #[tool(
-        description = "Confirm an inferred causal relationship between memories. This improves the system's confidence and learning."
-    )]
+    #[tool]
     async fn lineage_confirm(
         &self,
         Parameters(params): Parameters<LineageConfirmParams>,
     ) -> Result<CallToolResult, McpError> {
-        let result: Result<LineageConfirmResponse> = self
-            .client
-            .post(
-                "/api/lineage/confirm",
-                &LineageEdgeRequest {
-                    user_id: self.client.user_id.clone(),
-                    edge_id: params.edge_id,
-                },
-            )
-            .await;
-
-        match result {
-            Ok(resp) => Ok(CallToolResult::success(vec![Content::text(format!(
-                "✓ Confirmed edge: {} - {}",
-                resp.edge_id, resp.message
-            ))])),
-            Err(e) => Err(McpError {
-                code: ErrorCode::INTERNAL_ERROR,
-                message: Cow::from(e.to_string()),
-                data: None,
-            }),
-        }
+        self.run(lineage_confirm_call(&params)).await
     }
 
-    #[tool(
-        description = "Reject an incorrectly inferred causal relationship. This helps the system learn better inference patterns."
-    )]
+    #[tool]
     async fn lineage_reject(
         &self,
         Parameters(params): Parameters<LineageRejectParams>,
     ) -> Result<CallToolResult, McpError> {
-        let result: Result<LineageRejectResponse> = self
-            .client
-            .post(
-                "/api/lineage/reject",
-                &LineageEdgeRequest {
-                    user_id: self.client.user_id.clone(),
-                    edge_id: params.edge_id,
-                },
-            )
-            .await;
-
-        match result {
-            Ok(resp) => Ok(CallToolResult::success(vec![Content::text(format!(
-                "✗ Rejected edge: {}",
-                resp.message
-            ))])),
-            Err(e) => Err(McpError {
-                code: ErrorCode::INTERNAL_ERROR,
-                message: Cow::from(e.to_string()),
-                data: None,
-            }),
-        }
+        self.run(lineage_reject_call(&params)).await
     }
 
-    #[tool(
-        description = "Create an explicit causal link between two memories. Relations: Caused (Error→Todo), ResolvedBy (Todo→Learning), InformedBy, SupersededBy, TriggeredBy, BranchedFrom, RelatedTo."
-    )]
+    #[tool]
     async fn lineage_link(
         &self,
         Parameters(params): Parameters<LineageLinkParams>,
     ) -> Result<CallToolResult, McpError> {
-        let result: Result<LineageAddResponse> = self
-            .client
-            .post(
-                "/api/lineage/link",
-                &LineageAddEdgeRequest {
-                    user_id: self.client.user_id.clone(),
-                    from_memory_id: params.from_memory_id,
-                    to_memory_id: params.to_memory_id,
-                    relation: params.relation,
-                },
-            )
-            .await;
-
-        match result {
-            Ok(resp) => Ok(CallToolResult::success(vec![Content::text(format!(
-                "⚡ Created link: {} - {}",
-                resp.edge_id, resp.message
-            ))])),
-            Err(e) => Err(McpError {
-                code: ErrorCode::INTERNAL_ERROR,
-                message: Cow::from(e.to_string()),
-                data: None,
-            }),
-        }
+        self.run(lineage_link_call(&params)).await
     }
 
-    #[tool(
-        description = "Get statistics about the causal lineage graph - edge counts, relation types, confidence distribution."
-    )]
+    #[tool]
     async fn lineage_stats(
         &self,
-        Parameters(_params): Parameters<LineageStatsParams>,
+        Parameters(params): Parameters<LineageStatsParams>,
     ) -> Result<CallToolResult, McpError> {
-        let result: Result<LineageStatsResponse> = self
-            .client
-            .post(
-                "/api/lineage/stats",
-                &LineageStatsRequest {
-                    user_id: self.client.user_id.clone(),
-                },
-            )
-            .await;
-
-        match result {
-            Ok(resp) => {
-                let mut output = "**Lineage Graph Statistics**\n\n".to_string();
-                output.push_str(&format!("**Edges:** {}\n", resp.total_edges));
-                output.push_str(&format!("  ✓ Confirmed: {}\n", resp.confirmed_edges));
-                output.push_str(&format!("  ? Inferred: {}\n", resp.inferred_edges));
-                output.push_str(&format!("  ⚡ Explicit: {}\n", resp.explicit_edges));
-                output.push_str(&format!(
-                    "Average Confidence: {:.1}%\n\n",
-                    resp.avg_confidence * 100.0
-                ));
-                output.push_str(&format!(
-                    "**Branches:** {} total, {} active\n\n",
-                    resp.total_branches, resp.active_branches
-                ));
+        self.run(lineage_stats_call(&params)).await
+    }
 
-                if !resp.edges_by_relation.is_empty() {
-                    output.push_str("**By Relation Type:**\n");
-                    let mut relations: Vec<_> = resp.edges_by_relation.iter().collect();
-                    relations.sort_by(|a, b| b.1.cmp(a.1));
-                    for (relation, count) in relations {
-                        output.push_str(&format!("  {}: {}\n", relation, count));
-                    }
-                }
+    #[tool]
+    async fn post_mortem(
+        &self,
+        Parameters(params): Parameters<PostMortemParams>,
+    ) -> Result<CallToolResult, McpError> {
+        self.run(post_mortem_call(&params)).await
+    }
 
-                Ok(CallToolResult::success(vec![Content::text(output)]))
-            }
+    // =========================================================================
+    // TODO / PROJECT TOOLS
+    // =========================================================================
+
+    #[tool]
+    async fn list_todos(
+        &self,
+        Parameters(params): Parameters<ListTodosParams>,
+    ) -> Result<CallToolResult, McpError> {
+        self.run(list_todos_call(&params)).await
+    }
+
+    #[tool]
+    async fn add_todo(
+        &self,
+        Parameters(params): Parameters<AddTodoParams>,
+    ) -> Result<CallToolResult, McpError> {
+        self.run(add_todo_call(&params)).await
+    }
+
+    #[tool]
+    async fn update_todo(
+        &self,
+        Parameters(params): Parameters<UpdateTodoParams>,
+    ) -> Result<CallToolResult, McpError> {
+        self.run(update_todo_call(&params)).await
+    }
+
+    #[tool]
+    async fn complete_todo(
+        &self,
+        Parameters(params): Parameters<TodoIdParams>,
+    ) -> Result<CallToolResult, McpError> {
+        self.run(complete_todo_call(&params)).await
+    }
+
+    #[tool]
+    async fn delete_todo(
+        &self,
+        Parameters(params): Parameters<TodoIdParams>,
+    ) -> Result<CallToolResult, McpError> {
+        self.run(delete_todo_call(&params)).await
+    }
+
+    #[tool]
+    async fn get_todo(
+        &self,
+        Parameters(params): Parameters<TodoIdParams>,
+    ) -> Result<CallToolResult, McpError> {
+        self.run(get_todo_call(&params)).await
+    }
+
+    #[tool]
+    async fn due_todos(
+        &self,
+        Parameters(params): Parameters<DueTodosParams>,
+    ) -> Result<CallToolResult, McpError> {
+        self.run(due_todos_call(&params)).await
+    }
+
+    #[tool]
+    async fn next_todos(
+        &self,
+        Parameters(params): Parameters<NextTodosParams>,
+    ) -> Result<CallToolResult, McpError> {
+        self.run(next_todos_call(&params)).await
+    }
+
+    #[tool]
+    async fn todo_stats(
+        &self,
+        Parameters(params): Parameters<EmptyParams>,
+    ) -> Result<CallToolResult, McpError> {
+        self.run(todo_stats_call(&params)).await
+    }
+
+    #[tool]
+    async fn list_projects(
+        &self,
+        Parameters(params): Parameters<EmptyParams>,
+    ) -> Result<CallToolResult, McpError> {
+        self.run(list_projects_call(&params)).await
+    }
+
+    #[tool]
+    async fn add_project(
+        &self,
+        Parameters(params): Parameters<AddProjectParams>,
+    ) -> Result<CallToolResult, McpError> {
+        self.run(add_project_call(&params)).await
+    }
+
+    #[tool]
+    async fn get_project(
+        &self,
+        Parameters(params): Parameters<ProjectParams>,
+    ) -> Result<CallToolResult, McpError> {
+        self.run(get_project_call(&params)).await
+    }
+
+    #[tool]
+    async fn project_dependencies(
+        &self,
+        Parameters(params): Parameters<ProjectParams>,
+    ) -> Result<CallToolResult, McpError> {
+        self.run(project_dependencies_call(&params)).await
+    }
+
+    #[tool]
+    async fn project_critical_path(
+        &self,
+        Parameters(params): Parameters<ProjectParams>,
+    ) -> Result<CallToolResult, McpError> {
+        self.run(project_critical_path_call(&params)).await
+    }
+
+    #[tool]
+    async fn update_project(
+        &self,
+        Parameters(params): Parameters<UpdateProjectParams>,
+    ) -> Result<CallToolResult, McpError> {
+        self.run(update_project_call(&params)).await
+    }
+
+    #[tool]
+    async fn delete_project(
+        &self,
+        Parameters(params): Parameters<DeleteProjectParams>,
+    ) -> Result<CallToolResult, McpError> {
+        self.run(delete_project_call(&params)).await
+    }
+
+    // =========================================================================
+    // REMINDER TOOLS
+    // =========================================================================
+
+    #[tool]
+    async fn set_reminder(
+        &self,
+        Parameters(params): Parameters<SetReminderParams>,
+    ) -> Result<CallToolResult, McpError> {
+        self.run(set_reminder_call(&params)).await
+    }
+
+    #[tool]
+    async fn list_reminders(
+        &self,
+        Parameters(params): Parameters<ListRemindersParams>,
+    ) -> Result<CallToolResult, McpError> {
+        self.run(list_reminders_call(&params)).await
+    }
+
+    #[tool]
+    async fn due_reminders(
+        &self,
+        Parameters(params): Parameters<DueRemindersParams>,
+    ) -> Result<CallToolResult, McpError> {
+        self.run(due_reminders_call(&params)).await
+    }
+
+    #[tool]
+    async fn check_reminders(
+        &self,
+        Parameters(params): Parameters<CheckRemindersParams>,
+    ) -> Result<CallToolResult, McpError> {
+        self.run(check_reminders_call(&params)).await
+    }
+
+    #[tool]
+    async fn dismiss_reminder(
+        &self,
+        Parameters(params): Parameters<ReminderIdParams>,
+    ) -> Result<CallToolResult, McpError> {
+        self.run(dismiss_reminder_call(&params)).await
+    }
+
+    #[tool]
+    async fn delete_reminder(
+        &self,
+        Parameters(params): Parameters<ReminderIdParams>,
+    ) -> Result<CallToolResult, McpError> {
+        self.run(delete_reminder_call(&params)).await
+    }
+
+    // =========================================================================
+    // FACTS / GRAPH TOOLS
+    // =========================================================================
+
+    #[tool]
+    async fn facts_list(
+        &self,
+        Parameters(params): Parameters<FactsListParams>,
+    ) -> Result<CallToolResult, McpError> {
+        self.run(facts_list_call(&params)).await
+    }
+
+    #[tool]
+    async fn facts_search(
+        &self,
+        Parameters(params): Parameters<FactsSearchParams>,
+    ) -> Result<CallToolResult, McpError> {
+        self.run(facts_search_call(&params)).await
+    }
+
+    #[tool]
+    async fn facts_by_entity(
+        &self,
+        Parameters(params): Parameters<FactsByEntityParams>,
+    ) -> Result<CallToolResult, McpError> {
+        self.run(facts_by_entity_call(&params)).await
+    }
+
+    #[tool]
+    async fn facts_stats(
+        &self,
+        Parameters(params): Parameters<EmptyParams>,
+    ) -> Result<CallToolResult, McpError> {
+        self.run(facts_stats_call(&params)).await
+    }
+
+    #[tool]
+    async fn find_entity(
+        &self,
+        Parameters(params): Parameters<FindEntityParams>,
+    ) -> Result<CallToolResult, McpError> {
+        self.run(find_entity_call(&params)).await
+    }
+
+    #[tool]
+    async fn graph_traverse(
+        &self,
+        Parameters(params): Parameters<GraphTraverseParams>,
+    ) -> Result<CallToolResult, McpError> {
+        self.run(graph_traverse_call(&params)).await
+    }
+
+    #[tool]
+    async fn merge_entities(
+        &self,
+        Parameters(params): Parameters<MergeEntitiesParams>,
+    ) -> Result<CallToolResult, McpError> {
+        self.run(merge_entities_call(&params)).await
+    }
+
+    #[tool]
+    async fn graph_query(
+        &self,
+        Parameters(params): Parameters<GraphQueryParams>,
+    ) -> Result<CallToolResult, McpError> {
+        self.run(graph_query_call(&params)).await
+    }
+
+    // =========================================================================
+    // FORGET / CONSOLIDATION TOOLS
+    // =========================================================================
+
+    #[tool]
+    async fn forget(
+        &self,
+        Parameters(params): Parameters<ForgetParams>,
+    ) -> Result<CallToolResult, McpError> {
+        self.run(forget_call(&params)).await
+    }
+
+    #[tool]
+    async fn forget_by_age(
+        &self,
+        Parameters(params): Parameters<ForgetByAgeParams>,
+    ) -> Result<CallToolResult, McpError> {
+        self.run(forget_by_age_call(&params)).await
+    }
+
+    #[tool]
+    async fn forget_by_importance(
+        &self,
+        Parameters(params): Parameters<ForgetByImportanceParams>,
+    ) -> Result<CallToolResult, McpError> {
+        self.run(forget_by_importance_call(&params)).await
+    }
+
+    #[tool]
+    async fn forget_by_pattern(
+        &self,
+        Parameters(params): Parameters<ForgetByPatternParams>,
+    ) -> Result<CallToolResult, McpError> {
+        self.run(forget_by_pattern_call(&params)).await
+    }
+
+    #[tool]
+    async fn forget_by_tags(
+        &self,
+        Parameters(params): Parameters<ForgetByTagsParams>,
+    ) -> Result<CallToolResult, McpError> {
+        self.run(forget_by_tags_call(&params)).await
+    }
+
+    #[tool]
+    async fn forget_by_date(
+        &self,
+        Parameters(params): Parameters<ForgetByDateParams>,
+    ) -> Result<CallToolResult, McpError> {
+        self.run(forget_by_date_call(&params)).await
+    }
+
+    #[tool]
+    async fn consolidation_report(
+        &self,
+        Parameters(params): Parameters<ConsolidationReportParams>,
+    ) -> Result<CallToolResult, McpError> {
+        self.run(consolidation_report_call(&params)).await
+    }
+}
+
+impl ShodhMcpServer {
+    /// Send a tool's REST call to the server and render the response
+    async fn run(&self, call: RestCall) -> Result<CallToolResult, McpError> {
+        match self.client.call(&call).await {
+            Ok(response) => Ok(call.output(&response)),
             Err(e) => Err(McpError {
                 code: ErrorCode::INTERNAL_ERROR,
                 message: Cow::from(e.to_string()),
@@ -961,16 +886,7 @@ impl ServerHandler for ShodhMcpServer {
             protocol_version: ProtocolVersion::V_2024_11_05,
             capabilities: ServerCapabilities::builder().enable_tools().build(),
             server_info: Implementation::from_build_env(),
-            instructions: Some(
-                "Shodh Memory - persistent cognitive memory with causal reasoning. \
-                 Use proactive_context at session start to surface relevant memories. \
-                 Use remember to store decisions, learnings, errors. \
-                 Use recall to search memories. \
-                 Use lineage_trace to understand 'why' - trace causal chains backward/forward. \
-                 Use lineage_link to explicitly connect cause→effect memories. \
-                 Use lineage_confirm/reject to improve inference accuracy."
-                    .to_string(),
-            ),
+            instructions: Some(SERVER_INSTRUCTIONS.to_string()),
         }
     }
 }
@@ -1120,3 +1036,576 @@ async fn handle_claude_launch(port: u16, args: Vec<String>) -> Result<()> {
         std::process::exit(status.code().unwrap_or(1));
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{
+        body::Bytes,
+        extract::State,
+        http::{Method, Uri},
+        Json, Router,
+    };
+    use serde::de::DeserializeOwned;
+    use serde_json::{json, Value};
+    use std::sync::Mutex;
+
+    /// A request as the stub REST server received it
+    #[derive(Debug)]
+    struct Recorded {
+        method: Method,
+        path: String,
+        query: Option<String>,
+        body: Value,
+    }
+
+    type Log = Arc<Mutex<Vec<Recorded>>>;
+
+    async fn record(State(log): State<Log>, method: Method, uri: Uri, body: Bytes) -> Json<Value> {
+        let body = if body.is_empty() {
+            Value::Null
+        } else {
+            serde_json::from_slice(&body).expect("tool sent a non-JSON body")
+        };
+        log.lock().unwrap().push(Recorded {
+            method,
+            path: uri.path().to_string(),
+            query: uri.query().map(str::to_string),
+            body,
+        });
+        Json(json!({ "success": true }))
+    }
+
+    /// MCP server pointed at a local stub that records every request
+    async fn stub_server() -> (ShodhMcpServer, Log) {
+        let log = Log::default();
+        let app = Router::new().fallback(record).with_state(log.clone());
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+        let server = ShodhMcpServer::new(
+            format!("http://{addr}"),
+            "test-key".to_string(),
+            "tester".to_string(),
+        );
+        (server, log)
+    }
+
+    /// Tool arguments as an MCP client would send them
+    fn args<T: DeserializeOwned>(value: Value) -> Parameters<T> {
+        Parameters(serde_json::from_value(value).expect("invalid tool arguments"))
+    }
+
+    /// The single request made since the last call
+    fn take(log: &Log) -> Recorded {
+        let mut log = log.lock().unwrap();
+        assert_eq!(log.len(), 1, "expected exactly one request, got {log:?}");
+        log.pop().unwrap()
+    }
+
+    fn assert_post(req: Recorded, path: &str, body: Value) {
+        assert_eq!(req.method, Method::POST, "{path}");
+        assert_eq!(req.path, path);
+        assert_eq!(req.query, None, "{path}");
+        assert_eq!(req.body, body, "{path}");
+    }
+
+    /// GET/DELETE and body-less POSTs carry `user_id` in the query string
+    fn assert_query(req: Recorded, method: Method, path: &str) {
+        assert_eq!(req.method, method, "{path}");
+        assert_eq!(req.path, path);
+        assert_eq!(req.query.as_deref(), Some("user_id=tester"), "{path}");
+        assert_eq!(req.body, Value::Null, "{path}");
+    }
+
+    #[tokio::test]
+    async fn todo_tools_map_to_rest_requests() {
+        let (server, log) = stub_server().await;
+
+        server
+            .list_todos(args(json!({ "status": ["todo"], "project": "Shodh" })))
+            .await
+            .unwrap();
+        assert_post(
+            take(&log),
+            "/api/todos/list",
+            json!({
+                "user_id": "tester",
+                "status": ["todo"],
+                "project": "Shodh",
+            }),
+        );
+
+        server
+            .add_todo(args(json!({
+                "content": "Ship config loader",
+                "priority": "high",
+                "due_date": "tomorrow",
+                "blocked_by": ["SHO-1"],
+            })))
+            .await
+            .unwrap();
+        let req = take(&log);
+        assert_eq!(req.path, "/api/todos/add");
+        assert_eq!(req.body["content"], "Ship config loader");
+        assert_eq!(req.body["priority"], "high");
+        assert_eq!(req.body["due_date"], "tomorrow");
+        assert_eq!(req.body["blocked_by"], json!(["SHO-1"]));
+        assert_eq!(req.body["recurrence"], Value::Null);
+        assert_eq!(req.body["user_id"], "tester");
+
+        // The todo ID goes in the path, never the body
+        server
+            .update_todo(args(json!({ "todo_id": "SHO-12", "status": "done" })))
+            .await
+            .unwrap();
+        let req = take(&log);
+        assert_eq!(req.method, Method::POST);
+        assert_eq!(req.path, "/api/todos/SHO-12/update");
+        assert_eq!(req.body["status"], "done");
+        assert!(req.body.get("todo_id").is_none());
+
+        server
+            .complete_todo(args(json!({ "todo_id": "SHO-12" })))
+            .await
+            .unwrap();
+        assert_post(
+            take(&log),
+            "/api/todos/SHO-12/complete",
+            json!({ "user_id": "tester" }),
+        );
+
+        server
+            .delete_todo(args(json!({ "todo_id": "SHO-12" })))
+            .await
+            .unwrap();
+        assert_query(take(&log), Method::DELETE, "/api/todos/SHO-12");
+
+        server
+            .get_todo(args(json!({ "todo_id": "SHO-12" })))
+            .await
+            .unwrap();
+        assert_query(take(&log), Method::GET, "/api/todos/SHO-12");
+
+        // Absent include_overdue is omitted so the REST default (true) applies
+        server.due_todos(args(json!({}))).await.unwrap();
+        assert_post(take(&log), "/api/todos/due", json!({ "user_id": "tester" }));
+        server
+            .due_todos(args(json!({ "include_overdue": false })))
+            .await
+            .unwrap();
+        assert_post(
+            take(&log),
+            "/api/todos/due",
+            json!({ "user_id": "tester", "include_overdue": false }),
+        );
+
+        server
+            .next_todos(args(json!({ "limit": 3 })))
+            .await
+            .unwrap();
+        assert_post(
+            take(&log),
+            "/api/todos/next",
+            json!({ "user_id": "tester", "limit": 3 }),
+        );
+
+        server.todo_stats(args(json!({}))).await.unwrap();
+        assert_post(
+            take(&log),
+            "/api/todos/stats",
+            json!({ "user_id": "tester" }),
+        );
+    }
+
+    #[tokio::test]
+    async fn project_tools_map_to_rest_requests() {
+        let (server, log) = stub_server().await;
+
+        server.list_projects(args(json!({}))).await.unwrap();
+        assert_post(
+            take(&log),
+            "/api/projects/list",
+            json!({ "user_id": "tester" }),
+        );
+
+        server
+            .add_project(args(json!({ "name": "Robotics", "prefix": "ROB" })))
+            .await
+            .unwrap();
+        assert_post(
+            take(&log),
+            "/api/projects/add",
+            json!({
+                "user_id": "tester",
+                "name": "Robotics",
+                "prefix": "ROB",
+            }),
+        );
+
+        server
+            .get_project(args(json!({ "project": "Robotics" })))
+            .await
+            .unwrap();
+        assert_query(take(&log), Method::GET, "/api/projects/Robotics");
+
+        server
+            .project_dependencies(args(json!({ "project": "Robotics" })))
+            .await
+            .unwrap();
+        assert_query(
+            take(&log),
+            Method::GET,
+            "/api/projects/Robotics/dependencies",
+        );
+
+        server
+            .project_critical_path(args(json!({ "project": "Robotics" })))
+            .await
+            .unwrap();
+        assert_query(
+            take(&log),
+            Method::GET,
+            "/api/projects/Robotics/critical-path",
+        );
+
+        // Only the fields being changed are sent
+        server
+            .update_project(args(json!({ "project": "Robotics", "status": "archived" })))
+            .await
+            .unwrap();
+        assert_post(
+            take(&log),
+            "/api/projects/Robotics/update",
+            json!({ "user_id": "tester", "status": "archived" }),
+        );
+
+        server
+            .delete_project(args(json!({ "project": "Robotics" })))
+            .await
+            .unwrap();
+        assert_post(
+            take(&log),
+            "/api/projects/Robotics/delete",
+            json!({ "user_id": "tester" }),
+        );
+        server
+            .delete_project(args(json!({ "project": "Robotics", "delete_todos": true })))
+            .await
+            .unwrap();
+        assert_post(
+            take(&log),
+            "/api/projects/Robotics/delete",
+            json!({ "user_id": "tester", "delete_todos": true }),
+        );
+    }
+
+    #[tokio::test]
+    async fn reminder_tools_map_to_rest_requests() {
+        let (server, log) = stub_server().await;
+
+        server
+            .set_reminder(args(json!({
+                "content": "Check the build",
+                "trigger": { "type": "duration", "after_seconds": 600 },
+            })))
+            .await
+            .unwrap();
+        assert_post(
+            take(&log),
+            "/api/reminders/set",
+            json!({
+                "user_id": "tester",
+                "content": "Check the build",
+                "trigger": { "type": "duration", "after_seconds": 600 },
+            }),
+        );
+
+        server
+            .set_reminder(args(json!({
+                "content": "Mention the migration",
+                "trigger": { "type": "context", "keywords": ["database"] },
+                "tags": ["ops"],
+                "priority": 5,
+            })))
+            .await
+            .unwrap();
+        assert_post(
+            take(&log),
+            "/api/reminders/set",
+            json!({
+                "user_id": "tester",
+                "content": "Mention the migration",
+                "trigger": { "type": "context", "keywords": ["database"] },
+                "tags": ["ops"],
+                "priority": 5,
+            }),
+        );
+
+        server
+            .list_reminders(args(json!({ "status": "pending" })))
+            .await
+            .unwrap();
+        assert_post(
+            take(&log),
+            "/api/reminders",
+            json!({ "user_id": "tester", "status": "pending" }),
+        );
+
+        server.due_reminders(args(json!({}))).await.unwrap();
+        assert_post(
+            take(&log),
+            "/api/reminders/due",
+            json!({ "user_id": "tester" }),
+        );
+
+        server
+            .check_reminders(args(
+                json!({ "context": "database work", "mark_triggered": false }),
+            ))
+            .await
+            .unwrap();
+        assert_post(
+            take(&log),
+            "/api/reminders/check",
+            json!({ "user_id": "tester", "context": "database work", "mark_triggered": false }),
+        );
+
+        server
+            .dismiss_reminder(args(json!({ "reminder_id": "r-1" })))
+            .await
+            .unwrap();
+        assert_post(
+            take(&log),
+            "/api/reminders/r-1/dismiss",
+            json!({ "user_id": "tester" }),
+        );
+
+        server
+            .delete_reminder(args(json!({ "reminder_id": "r-1" })))
+            .await
+            .unwrap();
+        assert_query(take(&log), Method::POST, "/api/reminders/r-1/delete");
+    }
+
+    #[tokio::test]
+    async fn fact_and_graph_tools_map_to_rest_requests() {
+        let (server, log) = stub_server().await;
+
+        server.facts_list(args(json!({}))).await.unwrap();
+        assert_post(
+            take(&log),
+            "/api/facts/list",
+            json!({ "user_id": "tester" }),
+        );
+
+        server
+            .facts_search(args(json!({ "query": "rust", "limit": 5 })))
+            .await
+            .unwrap();
+        assert_post(
+            take(&log),
+            "/api/facts/search",
+            json!({ "user_id": "tester", "query": "rust", "limit": 5 }),
+        );
+
+        server
+            .facts_by_entity(args(json!({
+                "entity": "PostgreSQL",
+                "as_of": "2025-01-01T00:00:00Z",
+            })))
+            .await
+            .unwrap();
+        assert_post(
+            take(&log),
+            "/api/facts/by-entity",
+            json!({
+                "user_id": "tester",
+                "entity": "PostgreSQL",
+                "as_of": "2025-01-01T00:00:00Z",
+            }),
+        );
+
+        server.facts_stats(args(json!({}))).await.unwrap();
+        assert_post(
+            take(&log),
+            "/api/facts/stats",
+            json!({ "user_id": "tester" }),
+        );
+
+        server
+            .find_entity(args(json!({ "entity_name": "Rust" })))
+            .await
+            .unwrap();
+        assert_post(
+            take(&log),
+            "/api/graph/entity/find",
+            json!({ "user_id": "tester", "entity_name": "Rust" }),
+        );
+
+        server
+            .graph_traverse(args(json!({ "entity_name": "Rust" })))
+            .await
+            .unwrap();
+        assert_post(
+            take(&log),
+            "/api/graph/traverse",
+            json!({ "user_id": "tester", "entity_name": "Rust" }),
+        );
+
+        server
+            .merge_entities(args(
+                json!({ "survivor": "PostgreSQL", "duplicate": "Postgres" }),
+            ))
+            .await
+            .unwrap();
+        assert_post(
+            take(&log),
+            "/api/graph/entity/merge",
+            json!({ "user_id": "tester", "survivor": "PostgreSQL", "duplicate": "Postgres" }),
+        );
+
+        server
+            .graph_query(args(json!({ "query": "MATCH (p:Person) RETURN p.name" })))
+            .await
+            .unwrap();
+        assert_post(
+            take(&log),
+            "/api/graph/query",
+            json!({
+                "user_id": "tester",
+                "query": "MATCH (p:Person) RETURN p.name",
+                "explain": false,
+            }),
+        );
+    }
+
+    #[tokio::test]
+    async fn forget_and_consolidation_tools_map_to_rest_requests() {
+        let (server, log) = stub_server().await;
+
+        server.forget(args(json!({ "id": "mem-1" }))).await.unwrap();
+        assert_query(take(&log), Method::DELETE, "/api/memory/mem-1");
+
+        server
+            .forget_by_age(args(json!({ "days_old": 30 })))
+            .await
+            .unwrap();
+        assert_post(
+            take(&log),
+            "/api/forget/age",
+            json!({ "user_id": "tester", "days_old": 30 }),
+        );
+
+        server
+            .forget_by_importance(args(json!({ "threshold": 0.25 })))
+            .await
+            .unwrap();
+        assert_post(
+            take(&log),
+            "/api/forget/importance",
+            json!({ "user_id": "tester", "threshold": 0.25 }),
+        );
+
+        server
+            .forget_by_pattern(args(json!({ "pattern": "^tmp" })))
+            .await
+            .unwrap();
+        assert_post(
+            take(&log),
+            "/api/forget/pattern",
+            json!({ "user_id": "tester", "pattern": "^tmp" }),
+        );
+
+        server
+            .forget_by_tags(args(json!({ "tags": ["scratch"] })))
+            .await
+            .unwrap();
+        assert_post(
+            take(&log),
+            "/api/forget/tags",
+            json!({ "user_id": "tester", "tags": ["scratch"] }),
+        );
+
+        server
+            .forget_by_date(args(json!({
+                "start": "2025-01-01T00:00:00Z",
+                "end": "2025-02-01T00:00:00Z",
+            })))
+            .await
+            .unwrap();
+        assert_post(
+            take(&log),
+            "/api/forget/date",
+            json!({
+                "user_id": "tester",
+                "start": "2025-01-01T00:00:00Z",
+                "end": "2025-02-01T00:00:00Z",
+            }),
+        );
+
+        server
+            .consolidation_report(args(json!({ "since": "2025-01-01T00:00:00Z" })))
+            .await
+            .unwrap();
+        assert_post(
+            take(&log),
+            "/api/consolidation/report",
+            json!({ "user_id": "tester", "since": "2025-01-01T00:00:00Z" }),
+        );
+    }
+
+    #[test]
+    fn every_tool_has_its_shared_description() {
+        let server = ShodhMcpServer::new(
+            "http://127.0.0.1:1".to_string(),
+            "test-key".to_string(),
+            "tester".to_string(),
+        );
+        let tools = server.tool_router.list_all();
+        assert_eq!(tools.len(), 46);
+        for tool in tools {
+            let expected = tool_description(&tool.name);
+            assert!(
+                expected.is_some(),
+                "{} has no shared description",
+                tool.name
+            );
+            assert_eq!(tool.description.as_deref(), expected, "{}", tool.name);
+        }
+    }
+
+    #[tokio::test]
+    async fn summarized_tools_render_their_response() {
+        let (server, log) = stub_server().await;
+
+        // The stub's reply is not a remember response, so it is shown as JSON
+        let result = server
+            .remember(args(json!({ "content": "Use rustls", "type": "Decision" })))
+            .await
+            .unwrap();
+        assert_post(
+            take(&log),
+            "/api/remember",
+            json!({ "user_id": "tester", "content": "Use rustls", "memory_type": "Decision" }),
+        );
+        let text = result.content[0].as_text().unwrap().text.clone();
+        assert_eq!(
+            serde_json::from_str::<Value>(&text).unwrap(),
+            json!({ "success": true })
+        );
+    }
+
+    #[tokio::test]
+    async fn unreachable_server_is_an_mcp_error() {
+        let server = ShodhMcpServer::new(
+            "http://127.0.0.1:1".to_string(),
+            "test-key".to_string(),
+            "tester".to_string(),
+        );
+
+        let err = server.todo_stats(args(json!({}))).await.unwrap_err();
+        assert_eq!(err.code, ErrorCode::INTERNAL_ERROR);
+    }
+}
@@ -0,0 +1,1150 @@
+//! MCP tool parameters, descriptions and REST mappings
+//!
+//! Shared by the stdio `shodh` binary, which forwards tool calls to the REST
+//! API over HTTP, and the `/mcp` endpoint in [`crate::handlers::mcp`], which
+//! runs them through the API router in-process. Each tool maps to one
+//! [`RestCall`] built here; the transports only differ in how they send it.
+
+use rmcp::handler::server::router::tool::ToolRouter;
+use rmcp::model::{CallToolResult, Content};
+use rmcp::schemars;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::borrow::Cow;
+use std::collections::HashMap;
+
+// =============================================================================
+// TOOL DESCRIPTIONS
+// =============================================================================
+
+pub const REMEMBER_DESCRIPTION: &str = "Store a memory for future recall. Use this to remember important information, decisions, user preferences, project context, or anything you want to recall later.";
+pub const RECALL_DESCRIPTION: &str = "Search memories using semantic, associative, or hybrid retrieval. Modes: 'semantic' (vector similarity), 'associative' (graph traversal), 'hybrid' (combines both).";
+pub const PROACTIVE_CONTEXT_DESCRIPTION: &str = "REQUIRED: Call this to surface relevant memories based on current context. Enables automatic memory surfacing and implicit feedback learning.";
+
+pub const LINEAGE_TRACE_DESCRIPTION: &str = "Trace the causal lineage of a memory. Find what caused it (backward), what it led to (forward), or both. Useful for understanding 'why' something happened.";
+pub const LINEAGE_CONFIRM_DESCRIPTION: &str = "Confirm an inferred causal relationship between memories. This improves the system's confidence and learning.";
+pub const LINEAGE_REJECT_DESCRIPTION: &str = "Reject an incorrectly inferred causal relationship. This helps the system learn better inference patterns.";
+pub const LINEAGE_LINK_DESCRIPTION: &str = "Create an explicit causal link between two memories. Relations: Caused (Error→Todo), ResolvedBy (Todo→Learning), InformedBy, SupersededBy, TriggeredBy, BranchedFrom, RelatedTo.";
+pub const LINEAGE_STATS_DESCRIPTION: &str = "Get statistics about the causal lineage graph - edge counts, relation types, confidence distribution.";
+pub const POST_MORTEM_DESCRIPTION: &str = "Get the post-mortem of a finished todo or project - root causes, decisions, learnings and dead ends traced through its lineage.";
+
+pub const LIST_TODOS_DESCRIPTION: &str =
+    "List todos with optional filters by status, project, context, due window or semantic query.";
+pub const ADD_TODO_DESCRIPTION: &str = "Create a todo. Supports priority, project, GTD contexts, natural language due dates, subtasks and recurrence.";
+pub const UPDATE_TODO_DESCRIPTION: &str =
+    "Update fields of an existing todo. Only provided fields change.";
+pub const COMPLETE_TODO_DESCRIPTION: &str =
+    "Mark a todo as done. Recurring todos spawn their next occurrence.";
+pub const DELETE_TODO_DESCRIPTION: &str = "Delete a todo.";
+pub const GET_TODO_DESCRIPTION: &str = "Get a single todo by UUID or short ID (e.g. SHO-12).";
+pub const DUE_TODOS_DESCRIPTION: &str = "List todos due today, optionally including overdue ones.";
+pub const NEXT_TODOS_DESCRIPTION: &str = "List the next actionable todos: not blocked by unfinished todos, sorted by priority then due date.";
+pub const TODO_STATS_DESCRIPTION: &str =
+    "Get todo counts by status, overdue and completion statistics.";
+pub const LIST_PROJECTS_DESCRIPTION: &str = "List projects with their todo counts.";
+pub const ADD_PROJECT_DESCRIPTION: &str =
+    "Create a project, optionally nested under a parent project.";
+pub const GET_PROJECT_DESCRIPTION: &str = "Get a project by name or ID.";
+pub const PROJECT_DEPENDENCIES_DESCRIPTION: &str =
+    "Get the blocked-by dependency graph of a project's todos.";
+pub const PROJECT_CRITICAL_PATH_DESCRIPTION: &str =
+    "Get the critical path of a project: the longest chain of unfinished dependent todos.";
+pub const UPDATE_PROJECT_DESCRIPTION: &str =
+    "Update a project's name, prefix, description, status or color.";
+pub const DELETE_PROJECT_DESCRIPTION: &str = "Delete a project, optionally deleting its todos too.";
+
+pub const SET_REMINDER_DESCRIPTION: &str = "Set a reminder triggered at a time, after a duration, on a recurring schedule (daily/weekly/monthly or an iCalendar RRULE), or when the conversation mentions given keywords.";
+pub const LIST_REMINDERS_DESCRIPTION: &str = "List reminders, optionally filtered by status.";
+pub const DUE_REMINDERS_DESCRIPTION: &str = "Get time-based reminders that are due now.";
+pub const CHECK_REMINDERS_DESCRIPTION: &str =
+    "Check context-triggered reminders against the current conversation.";
+pub const DISMISS_REMINDER_DESCRIPTION: &str = "Dismiss a triggered reminder.";
+pub const DELETE_REMINDER_DESCRIPTION: &str = "Delete a reminder.";
+
+pub const FACTS_LIST_DESCRIPTION: &str =
+    "List semantic facts distilled from memories during consolidation.";
+pub const FACTS_SEARCH_DESCRIPTION: &str = "Search semantic facts by keyword.";
+pub const FACTS_BY_ENTITY_DESCRIPTION: &str = "Get semantic facts about a specific entity.";
+pub const FACTS_STATS_DESCRIPTION: &str = "Get semantic fact counts by type and confidence.";
+pub const FIND_ENTITY_DESCRIPTION: &str = "Find an entity in the knowledge graph by name.";
+pub const GRAPH_TRAVERSE_DESCRIPTION: &str =
+    "Traverse the knowledge graph from an entity, returning connected entities and relationships.";
+pub const MERGE_ENTITIES_DESCRIPTION: &str = "Merge a duplicate knowledge-graph entity into another (e.g. 'Postgres' into 'PostgreSQL'). The duplicate's edges and episodes move to the survivor and its name becomes an alias. Returns a merge record whose id can undo the merge.";
+pub const GRAPH_QUERY_DESCRIPTION: &str = "Run a Cypher-like pattern query over the knowledge graph, e.g. MATCH (p:Person)-[:WorksAt]->(o:Organization) WHERE p.salience > 0.5 RETURN p.name, o.name LIMIT 10. Supports label alternatives, variable-length relationships (-[*1..3]->), edge tier/strength filters (r.tier = 'L3', {min_strength: 0.5}) and invalidated_at; invalidated edges are skipped unless the query mentions invalidated_at.";
+
+pub const FORGET_DESCRIPTION: &str = "Delete a single memory by ID.";
+pub const FORGET_BY_AGE_DESCRIPTION: &str =
+    "Delete all memories older than the given number of days.";
+pub const FORGET_BY_IMPORTANCE_DESCRIPTION: &str =
+    "Delete all memories with importance below a threshold.";
+pub const FORGET_BY_PATTERN_DESCRIPTION: &str =
+    "Delete all memories whose content matches a regex pattern.";
+pub const FORGET_BY_TAGS_DESCRIPTION: &str = "Delete all memories carrying any of the given tags.";
+pub const FORGET_BY_DATE_DESCRIPTION: &str = "Delete all memories created within a date range.";
+pub const CONSOLIDATION_REPORT_DESCRIPTION: &str = "Get a report of what consolidation did: strengthened, decayed and pruned memories, extracted facts and edge changes.";
+
+/// Description advertised for the tool `name`, if it is a shodh tool
+pub fn tool_description(name: &str) -> Option<&'static str> {
+    let description = match name {
+        "remember" => REMEMBER_DESCRIPTION,
+        "recall" => RECALL_DESCRIPTION,
+        "proactive_context" => PROACTIVE_CONTEXT_DESCRIPTION,
+        "lineage_trace" => LINEAGE_TRACE_DESCRIPTION,
+        "lineage_confirm" => LINEAGE_CONFIRM_DESCRIPTION,
+        "lineage_reject" => LINEAGE_REJECT_DESCRIPTION,
+        "lineage_link" => LINEAGE_LINK_DESCRIPTION,
+        "lineage_stats" => LINEAGE_STATS_DESCRIPTION,
+        "post_mortem" => POST_MORTEM_DESCRIPTION,
+        "list_todos" => LIST_TODOS_DESCRIPTION,
+        "add_todo" => ADD_TODO_DESCRIPTION,
+        "update_todo" => UPDATE_TODO_DESCRIPTION,
+        "complete_todo" => COMPLETE_TODO_DESCRIPTION,
+        "delete_todo" => DELETE_TODO_DESCRIPTION,
+        "get_todo" => GET_TODO_DESCRIPTION,
+        "due_todos" => DUE_TODOS_DESCRIPTION,
+        "next_todos" => NEXT_TODOS_DESCRIPTION,
+        "todo_stats" => TODO_STATS_DESCRIPTION,
+        "list_projects" => LIST_PROJECTS_DESCRIPTION,
+        "add_project" => ADD_PROJECT_DESCRIPTION,
+        "get_project" => GET_PROJECT_DESCRIPTION,
+        "project_dependencies" => PROJECT_DEPENDENCIES_DESCRIPTION,
+        "project_critical_path" => PROJECT_CRITICAL_PATH_DESCRIPTION,
+        "update_project" => UPDATE_PROJECT_DESCRIPTION,
+        "delete_project" => DELETE_PROJECT_DESCRIPTION,
+        "set_reminder" => SET_REMINDER_DESCRIPTION,
+        "list_reminders" => LIST_REMINDERS_DESCRIPTION,
+        "due_reminders" => DUE_REMINDERS_DESCRIPTION,
+        "check_reminders" => CHECK_REMINDERS_DESCRIPTION,
+        "dismiss_reminder" => DISMISS_REMINDER_DESCRIPTION,
+        "delete_reminder" => DELETE_REMINDER_DESCRIPTION,
+        "facts_list" => FACTS_LIST_DESCRIPTION,
+        "facts_search" => FACTS_SEARCH_DESCRIPTION,
+        "facts_by_entity" => FACTS_BY_ENTITY_DESCRIPTION,
+        "facts_stats" => FACTS_STATS_DESCRIPTION,
+        "find_entity" => FIND_ENTITY_DESCRIPTION,
+        "graph_traverse" => GRAPH_TRAVERSE_DESCRIPTION,
+        "merge_entities" => MERGE_ENTITIES_DESCRIPTION,
+        "graph_query" => GRAPH_QUERY_DESCRIPTION,
+        "forget" => FORGET_DESCRIPTION,
+        "forget_by_age" => FORGET_BY_AGE_DESCRIPTION,
+        "forget_by_importance" => FORGET_BY_IMPORTANCE_DESCRIPTION,
+        "forget_by_pattern" => FORGET_BY_PATTERN_DESCRIPTION,
+        "forget_by_tags" => FORGET_BY_TAGS_DESCRIPTION,
+        "forget_by_date" => FORGET_BY_DATE_DESCRIPTION,
+        "consolidation_report" => CONSOLIDATION_REPORT_DESCRIPTION,
+        _ => return None,
+    };
+    Some(description)
+}
+
+/// Attach the shared descriptions to a transport's tools. `#[tool]` only
+/// takes literal descriptions, so the transports leave them out and call this
+/// on their router instead.
+pub fn describe_tools<S>(mut router: ToolRouter<S>) -> ToolRouter<S> {
+    for route in router.map.values_mut() {
+        route.attr.description = tool_description(&route.attr.name).map(Cow::Borrowed);
+    }
+    router
+}
+
+/// Server instructions advertised by both transports
+pub const SERVER_INSTRUCTIONS: &str = "Shodh Memory - persistent cognitive memory with causal reasoning. \
+     Use proactive_context at session start to surface relevant memories. \
+     Use remember to store decisions, learnings, errors and recall to search them. \
+     Use lineage_* to trace and curate cause→effect links between memories, and post_mortem to review what a finished todo or project taught. \
+     Use the todo, project and reminder tools for tasks; \
+     check_reminders with the current context surfaces keyword reminders. \
+     Use facts_* and find_entity/graph_traverse to inspect distilled knowledge. \
+     Use forget and forget_by_* to delete memories, consolidation_report to audit maintenance.";
+
+// =============================================================================
+// MCP TOOL PARAMETER TYPES
+// =============================================================================
+
+#[derive(Debug, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct RememberParams {
+    /// The content to remember
+    pub content: String,
+    /// Type of memory (Observation, Decision, Learning, etc.)
+    #[serde(rename = "type")]
+    pub memory_type: Option<String>,
+    /// Optional tags for categorization
+    pub tags: Option<Vec<String>>,
+}
+
+#[derive(Debug, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct RecallParams {
+    /// Natural language search query
+    pub query: String,
+    /// Maximum number of results (default: 5)
+    pub limit: Option<u32>,
+    /// Retrieval mode: semantic, associative, or hybrid
+    pub mode: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct ProactiveContextParams {
+    /// Current conversation context
+    pub context: String,
+    /// Maximum memories to surface (default: 5)
+    pub max_results: Option<u32>,
+    /// Auto-store context for feedback (default: true)
+    pub auto_ingest: Option<bool>,
+}
+
+// =============================================================================
+// LINEAGE MCP TOOL PARAMETERS
+// =============================================================================
+
+#[derive(Debug, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct LineageTraceParams {
+    /// Memory ID to trace lineage from
+    pub memory_id: String,
+    /// Direction: "backward" (find causes), "forward" (find effects), "both"
+    pub direction: Option<String>,
+    /// Maximum depth to traverse (default: 10)
+    pub max_depth: Option<u32>,
+}
+
+#[derive(Debug, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct LineageConfirmParams {
+    /// ID of the inferred edge to confirm
+    pub edge_id: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct LineageRejectParams {
+    /// ID of the inferred edge to reject
+    pub edge_id: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct LineageLinkParams {
+    /// Source memory ID (the cause/origin)
+    pub from_memory_id: String,
+    /// Target memory ID (the effect/result)
+    pub to_memory_id: String,
+    /// Relation type: Caused, ResolvedBy, InformedBy, SupersededBy, TriggeredBy, BranchedFrom, RelatedTo
+    pub relation: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct PostMortemParams {
+    /// Todo (UUID or short ID like SHO-12) or project (name or UUID)
+    #[serde(skip_serializing)]
+    pub subject_id: String,
+    /// Output format: "json" (default) or "markdown"
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub format: Option<String>,
+    /// Rebuild from the current lineage instead of returning the stored post-mortem
+    #[serde(default)]
+    pub regenerate: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct LineageStatsParams {
+    /// Optional - leave empty to get stats for current user
+    #[serde(default)]
+    pub _placeholder: Option<String>,
+}
+
+// =============================================================================
+// TODO / PROJECT MCP TOOL PARAMETERS
+// =============================================================================
+//
+// Parameter structs mirror the REST request bodies minus `user_id`, which the
+// server injects. Path parameters (todo_id, project, reminder_id) are skipped
+// when serializing the body. Optional fields with non-Option REST defaults are
+// skipped when absent so the REST default applies.
+
+#[derive(Debug, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct ListTodosParams {
+    /// Filter by status: backlog, todo, in_progress, blocked, done, cancelled
+    pub status: Option<Vec<String>>,
+    /// Filter by project name or ID
+    pub project: Option<String>,
+    /// Filter by context (e.g. "@computer")
+    pub context: Option<String>,
+    /// Include completed todos (default: false)
+    pub include_completed: Option<bool>,
+    /// Due filter: overdue, today, this_week
+    pub due: Option<String>,
+    /// Maximum number of todos
+    pub limit: Option<usize>,
+    /// Pagination offset
+    pub offset: Option<usize>,
+    /// Only subtasks of this parent todo
+    pub parent_id: Option<String>,
+    /// Semantic search query over todo content
+    pub query: Option<String>,
+    /// Only unblocked Todo/InProgress todos, by priority then due date
+    pub actionable: Option<bool>,
+}
+
+#[derive(Debug, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct NextTodosParams {
+    /// Filter by project name or ID
+    pub project: Option<String>,
+    /// Filter by context (e.g. "@computer")
+    pub context: Option<String>,
+    /// Maximum number of todos
+    pub limit: Option<usize>,
+}
+
+#[derive(Debug, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct AddTodoParams {
+    /// What needs to be done
+    pub content: String,
+    /// Initial status (default: todo)
+    pub status: Option<String>,
+    /// Priority: urgent, high, medium, low, none
+    pub priority: Option<String>,
+    /// Project name (created if missing)
+    pub project: Option<String>,
+    /// GTD contexts (e.g. "@computer", "@phone")
+    pub contexts: Option<Vec<String>>,
+    /// Due date: ISO 8601 or natural language ("tomorrow", "next friday")
+    pub due_date: Option<String>,
+    /// What the todo is waiting on
+    pub blocked_on: Option<String>,
+    /// Parent todo ID to create a subtask
+    pub parent_id: Option<String>,
+    /// Tags for categorization
+    pub tags: Option<Vec<String>>,
+    /// Free-form notes
+    pub notes: Option<String>,
+    /// Recurrence: daily, weekly, monthly, or an iCalendar RRULE such as
+    /// "FREQ=MONTHLY;BYDAY=FR;BYSETPOS=-1" (optionally with DTSTART;TZID= and EXDATE lines)
+    pub recurrence: Option<String>,
+    /// External system ID (e.g. "todoist:123")
+    pub external_id: Option<String>,
+    /// Todo IDs that must be finished first
+    pub blocked_by: Option<Vec<String>>,
+    /// Todo IDs that wait on this one
+    pub blocks: Option<Vec<String>>,
+}
+
+#[derive(Debug, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct UpdateTodoParams {
+    /// Todo ID (UUID or short ID like "SHO-12")
+    #[serde(skip_serializing)]
+    pub todo_id: String,
+    pub content: Option<String>,
+    pub status: Option<String>,
+    pub priority: Option<String>,
+    pub project: Option<String>,
+    pub contexts: Option<Vec<String>>,
+    pub due_date: Option<String>,
+    pub blocked_on: Option<String>,
+    pub notes: Option<String>,
+    pub tags: Option<Vec<String>>,
+    /// Position within the status column
+    pub sort_order: Option<i32>,
+    /// Move under another parent todo
+    pub parent_id: Option<String>,
+    pub external_id: Option<String>,
+    /// New recurrence (same forms as on create); "none" stops it
+    pub recurrence: Option<String>,
+    /// Replace the todos this one waits on (empty list clears)
+    pub blocked_by: Option<Vec<String>>,
+    /// Replace the todos waiting on this one
+    pub blocks: Option<Vec<String>>,
+}
+
+#[derive(Debug, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct TodoIdParams {
+    /// Todo ID (UUID or short ID like "SHO-12")
+    #[serde(skip_serializing)]
+    pub todo_id: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct DueTodosParams {
+    /// Include overdue todos (default: true)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub include_overdue: Option<bool>,
+}
+
+#[derive(Debug, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct EmptyParams {}
+
+#[derive(Debug, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct AddProjectParams {
+    /// Project name
+    pub name: String,
+    /// Short ID prefix for todos (e.g. "SHO")
+    pub prefix: Option<String>,
+    pub description: Option<String>,
+    /// Display color (hex)
+    pub color: Option<String>,
+    /// Parent project name or ID
+    pub parent: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct UpdateProjectParams {
+    /// Project name or ID
+    #[serde(skip_serializing)]
+    pub project: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub prefix: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Status: active, archived, completed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub color: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct ProjectParams {
+    /// Project name or ID
+    #[serde(skip_serializing)]
+    pub project: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct DeleteProjectParams {
+    /// Project name or ID
+    #[serde(skip_serializing)]
+    pub project: String,
+    /// Also delete all todos in the project (default: false)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub delete_todos: Option<bool>,
+}
+
+// =============================================================================
+// REMINDER MCP TOOL PARAMETERS
+// =============================================================================
+
+/// Reminder trigger, same shape as the REST `trigger` object
+#[derive(Debug, Serialize, Deserialize, schemars::JsonSchema)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ReminderTriggerParams {
+    /// Fire at an absolute time
+    Time {
+        /// RFC 3339 timestamp (e.g. "2025-12-23T18:00:00Z")
+        at: String,
+    },
+    /// Fire after a delay
+    Duration {
+        /// Seconds from now
+        after_seconds: u64,
+    },
+    /// Fire when the conversation mentions any keyword
+    Context {
+        keywords: Vec<String>,
+        /// Match threshold 0.0-1.0 (default: 0.7)
+        #[serde(skip_serializing_if = "Option::is_none")]
+        threshold: Option<f32>,
+    },
+    /// Fire on every occurrence of a recurrence
+    Recurring {
+        /// daily, weekly, monthly, or an iCalendar RRULE (e.g. "FREQ=WEEKLY;BYDAY=MO;BYHOUR=9"),
+        /// optionally with DTSTART;TZID= and EXDATE lines
+        rule: String,
+        /// RFC 3339 start of the series when the rule has no DTSTART (default: now)
+        #[serde(skip_serializing_if = "Option::is_none")]
+        start: Option<String>,
+    },
+}
+
+#[derive(Debug, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct SetReminderParams {
+    /// What to be reminded about
+    pub content: String,
+    /// When the reminder fires
+    pub trigger: ReminderTriggerParams,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tags: Option<Vec<String>>,
+    /// Priority 1-5 (default: 3)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub priority: Option<u8>,
+}
+
+#[derive(Debug, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct ListRemindersParams {
+    /// Filter by status: pending, triggered, dismissed, all
+    pub status: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct DueRemindersParams {
+    /// Mark returned reminders as triggered (default: true)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mark_triggered: Option<bool>,
+}
+
+#[derive(Debug, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct CheckRemindersParams {
+    /// Current conversation context to match against context triggers
+    pub context: String,
+    /// Mark matched reminders as triggered (default: true)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mark_triggered: Option<bool>,
+}
+
+#[derive(Debug, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct ReminderIdParams {
+    /// Reminder ID
+    #[serde(skip_serializing)]
+    pub reminder_id: String,
+}
+
+// =============================================================================
+// FACTS / GRAPH MCP TOOL PARAMETERS
+// =============================================================================
+
+#[derive(Debug, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct FactsListParams {
+    /// Maximum number of facts (default: 50)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<usize>,
+    /// RFC 3339 time to list the facts that held then (default: now)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub as_of: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct FactsSearchParams {
+    /// Search query
+    pub query: String,
+    /// Maximum number of facts (default: 50)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<usize>,
+    /// RFC 3339 time to search the facts that held then (default: now)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub as_of: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct FactsByEntityParams {
+    /// Entity name
+    pub entity: String,
+    /// Maximum number of facts (default: 50)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<usize>,
+    /// RFC 3339 time to return the facts that held then (default: now)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub as_of: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct FindEntityParams {
+    /// Entity name to look up
+    pub entity_name: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct GraphTraverseParams {
+    /// Entity to start traversal from
+    pub entity_name: String,
+    /// Maximum hops (default: 2)
+    pub max_depth: Option<usize>,
+}
+
+#[derive(Debug, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct MergeEntitiesParams {
+    /// Entity to keep (name, alias or UUID)
+    pub survivor: String,
+    /// Duplicate entity folded into the survivor (name, alias or UUID)
+    pub duplicate: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct GraphQueryParams {
+    /// Query text: MATCH pattern [WHERE ...] RETURN ... [ORDER BY ...] [LIMIT n]
+    pub query: String,
+    /// Include the compiled plan in the result
+    #[serde(default)]
+    pub explain: bool,
+}
+
+// =============================================================================
+// FORGET / CONSOLIDATION MCP TOOL PARAMETERS
+// =============================================================================
+
+#[derive(Debug, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct ForgetParams {
+    /// ID of the memory to delete
+    #[serde(skip_serializing)]
+    pub id: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct ForgetByAgeParams {
+    /// Delete memories older than this many days
+    pub days_old: u32,
+}
+
+#[derive(Debug, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct ForgetByImportanceParams {
+    /// Delete memories with importance below this (0.0-1.0)
+    pub threshold: f32,
+}
+
+#[derive(Debug, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct ForgetByPatternParams {
+    /// Regex matched against memory content
+    pub pattern: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct ForgetByTagsParams {
+    /// Delete memories carrying any of these tags
+    pub tags: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct ForgetByDateParams {
+    /// Range start (RFC 3339)
+    pub start: String,
+    /// Range end (RFC 3339)
+    pub end: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct ConsolidationReportParams {
+    /// Report start (RFC 3339, default: 1 hour ago)
+    pub since: Option<String>,
+    /// Report end (RFC 3339, default: now)
+    pub until: Option<String>,
+}
+
+// =============================================================================
+// TOOL → REST CALLS
+// =============================================================================
+
+/// HTTP method of a [`RestCall`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RestMethod {
+    Get,
+    Post,
+    Delete,
+}
+
+/// The REST request a tool call maps to, and how its response is shown
+///
+/// Bodies are the tool params with null fields dropped so the REST defaults
+/// apply. Calls without a body carry `user_id` as a query parameter instead.
+#[derive(Debug, Clone)]
+pub struct RestCall {
+    pub method: RestMethod,
+    pub path: String,
+    body: Option<serde_json::Map<String, Value>>,
+    render: fn(&Value) -> Option<String>,
+}
+
+impl RestCall {
+    fn post<P: Serialize>(path: impl Into<String>, params: &P) -> Self {
+        let mut body = match serde_json::to_value(params) {
+            Ok(Value::Object(map)) => map,
+            _ => serde_json::Map::new(),
+        };
+        body.retain(|_, v| !v.is_null());
+        Self {
+            method: RestMethod::Post,
+            path: path.into(),
+            body: Some(body),
+            render: |_| None,
+        }
+    }
+
+    fn query(method: RestMethod, path: impl Into<String>) -> Self {
+        Self {
+            method,
+            path: path.into(),
+            body: None,
+            render: |_| None,
+        }
+    }
+
+    fn rendered(mut self, render: fn(&Value) -> Option<String>) -> Self {
+        self.render = render;
+        self
+    }
+
+    /// Request body acting for `user_id`, or `None` if `user_id` goes in the query
+    pub fn body_for(&self, user_id: &str) -> Option<Value> {
+        let mut body = self.body.clone()?;
+        body.insert("user_id".to_string(), Value::String(user_id.to_string()));
+        Some(Value::Object(body))
+    }
+
+    /// Render a successful REST response as tool output. Tools without a
+    /// summary, and responses the summary cannot read, are shown as JSON.
+    pub fn output(&self, response: &Value) -> CallToolResult {
+        let text = (self.render)(response).unwrap_or_else(|| {
+            serde_json::to_string_pretty(response).unwrap_or_else(|_| response.to_string())
+        });
+        CallToolResult::success(vec![Content::text(text)])
+    }
+}
+
+pub fn remember_call(params: &RememberParams) -> RestCall {
+    // The MCP `type` argument is the REST `memory_type`
+    let body = serde_json::json!({
+        "content": params.content,
+        "memory_type": params.memory_type,
+        "tags": params.tags,
+    });
+    RestCall::post("/api/remember", &body).rendered(render_remember)
+}
+
+pub fn recall_call(params: &RecallParams) -> RestCall {
+    RestCall::post("/api/recall", params).rendered(render_recall)
+}
+
+pub fn proactive_context_call(params: &ProactiveContextParams) -> RestCall {
+    RestCall::post("/api/proactive_context", params).rendered(render_proactive_context)
+}
+
+pub fn lineage_trace_call(params: &LineageTraceParams) -> RestCall {
+    RestCall::post("/api/lineage/trace", params).rendered(render_lineage_trace)
+}
+
+pub fn lineage_confirm_call(params: &LineageConfirmParams) -> RestCall {
+    RestCall::post("/api/lineage/confirm", params).rendered(render_lineage_confirm)
+}
+
+pub fn lineage_reject_call(params: &LineageRejectParams) -> RestCall {
+    RestCall::post("/api/lineage/reject", params).rendered(render_lineage_reject)
+}
+
+pub fn lineage_link_call(params: &LineageLinkParams) -> RestCall {
+    RestCall::post("/api/lineage/link", params).rendered(render_lineage_link)
+}
+
+pub fn lineage_stats_call(params: &LineageStatsParams) -> RestCall {
+    RestCall::post("/api/lineage/stats", params).rendered(render_lineage_stats)
+}
+
+pub fn post_mortem_call(params: &PostMortemParams) -> RestCall {
+    let path = format!("/api/lineage/postmortem/{}", params.subject_id);
+    RestCall::post(path, params).rendered(render_post_mortem)
+}
+
+pub fn list_todos_call(params: &ListTodosParams) -> RestCall {
+    RestCall::post("/api/todos/list", params)
+}
+
+pub fn add_todo_call(params: &AddTodoParams) -> RestCall {
+    RestCall::post("/api/todos/add", params)
+}
+
+pub fn update_todo_call(params: &UpdateTodoParams) -> RestCall {
+    RestCall::post(format!("/api/todos/{}/update", params.todo_id), params)
+}
+
+pub fn complete_todo_call(params: &TodoIdParams) -> RestCall {
+    RestCall::post(format!("/api/todos/{}/complete", params.todo_id), params)
+}
+
+pub fn delete_todo_call(params: &TodoIdParams) -> RestCall {
+    RestCall::query(RestMethod::Delete, format!("/api/todos/{}", params.todo_id))
+}
+
+pub fn get_todo_call(params: &TodoIdParams) -> RestCall {
+    RestCall::query(RestMethod::Get, format!("/api/todos/{}", params.todo_id))
+}
+
+pub fn due_todos_call(params: &DueTodosParams) -> RestCall {
+    RestCall::post("/api/todos/due", params)
+}
+
+pub fn next_todos_call(params: &NextTodosParams) -> RestCall {
+    RestCall::post("/api/todos/next", params)
+}
+
+pub fn todo_stats_call(params: &EmptyParams) -> RestCall {
+    RestCall::post("/api/todos/stats", params)
+}
+
+pub fn list_projects_call(params: &EmptyParams) -> RestCall {
+    RestCall::post("/api/projects/list", params)
+}
+
+pub fn add_project_call(params: &AddProjectParams) -> RestCall {
+    RestCall::post("/api/projects/add", params)
+}
+
+pub fn get_project_call(params: &ProjectParams) -> RestCall {
+    RestCall::query(RestMethod::Get, format!("/api/projects/{}", params.project))
+}
+
+pub fn project_dependencies_call(params: &ProjectParams) -> RestCall {
+    let path = format!("/api/projects/{}/dependencies", params.project);
+    RestCall::query(RestMethod::Get, path)
+}
+
+pub fn project_critical_path_call(params: &ProjectParams) -> RestCall {
+    let path = format!("/api/projects/{}/critical-path", params.project);
+    RestCall::query(RestMethod::Get, path)
+}
+
+pub fn update_project_call(params: &UpdateProjectParams) -> RestCall {
+    RestCall::post(format!("/api/projects/{}/update", params.project), params)
+}
+
+pub fn delete_project_call(params: &DeleteProjectParams) -> RestCall {
+    RestCall::post(format!("/api/projects/{}/delete", params.project), params)
+}
+
+pub fn set_reminder_call(params: &SetReminderParams) -> RestCall {
+    RestCall::post("/api/reminders/set", params)
+}
+
+pub fn list_reminders_call(params: &ListRemindersParams) -> RestCall {
+    RestCall::post("/api/reminders", params)
+}
+
+pub fn due_reminders_call(params: &DueRemindersParams) -> RestCall {
+    RestCall::post("/api/reminders/due", params)
+}
+
+pub fn check_reminders_call(params: &CheckRemindersParams) -> RestCall {
+    RestCall::post("/api/reminders/check", params)
+}
+
+pub fn dismiss_reminder_call(params: &ReminderIdParams) -> RestCall {
+    let path = format!("/api/reminders/{}/dismiss", params.reminder_id);
+    RestCall::post(path, params)
+}
+
+pub fn delete_reminder_call(params: &ReminderIdParams) -> RestCall {
+    let path = format!("/api/reminders/{}/delete", params.reminder_id);
+    RestCall::query(RestMethod::Post, path)
+}
+
+pub fn facts_list_call(params: &FactsListParams) -> RestCall {
+    RestCall::post("/api/facts/list", params)
+}
+
+pub fn facts_search_call(params: &FactsSearchParams) -> RestCall {
+    RestCall::post("/api/facts/search", params)
+}
+
+pub fn facts_by_entity_call(params: &FactsByEntityParams) -> RestCall {
+    RestCall::post("/api/facts/by-entity", params)
+}
+
+pub fn facts_stats_call(params: &EmptyParams) -> RestCall {
+    RestCall::post("/api/facts/stats", params)
+}
+
+pub fn find_entity_call(params: &FindEntityParams) -> RestCall {
+    RestCall::post("/api/graph/entity/find", params)
+}
+
+pub fn graph_traverse_call(params: &GraphTraverseParams) -> RestCall {
+    RestCall::post("/api/graph/traverse", params)
+}
+
+pub fn merge_entities_call(params: &MergeEntitiesParams) -> RestCall {
+    RestCall::post("/api/graph/entity/merge", params)
+}
+
+pub fn graph_query_call(params: &GraphQueryParams) -> RestCall {
+    RestCall::post("/api/graph/query", params)
+}
+
+pub fn forget_call(params: &ForgetParams) -> RestCall {
+    RestCall::query(RestMethod::Delete, format!("/api/memory/{}", params.id))
+}
+
+pub fn forget_by_age_call(params: &ForgetByAgeParams) -> RestCall {
+    RestCall::post("/api/forget/age", params)
+}
+
+pub fn forget_by_importance_call(params: &ForgetByImportanceParams) -> RestCall {
+    RestCall::post("/api/forget/importance", params)
+}
+
+pub fn forget_by_pattern_call(params: &ForgetByPatternParams) -> RestCall {
+    RestCall::post("/api/forget/pattern", params)
+}
+
+pub fn forget_by_tags_call(params: &ForgetByTagsParams) -> RestCall {
+    RestCall::post("/api/forget/tags", params)
+}
+
+pub fn forget_by_date_call(params: &ForgetByDateParams) -> RestCall {
+    RestCall::post("/api/forget/date", params)
+}
+
+pub fn consolidation_report_call(params: &ConsolidationReportParams) -> RestCall {
+    RestCall::post("/api/consolidation/report", params)
+}
+
+// =============================================================================
+// TOOL OUTPUT SUMMARIES
+// =============================================================================
+
+#[derive(Deserialize)]
+struct RememberResponse {
+    id: String,
+    message: String,
+}
+
+#[derive(Deserialize)]
+struct RecallResponse {
+    memories: Vec<RecalledMemory>,
+}
+
+#[derive(Deserialize)]
+struct RecalledMemory {
+    id: String,
+    content: String,
+    memory_type: String,
+    similarity: f32,
+}
+
+#[derive(Deserialize)]
+struct ProactiveContextResponse {
+    memories: Vec<SurfacedMemory>,
+}
+
+#[derive(Deserialize)]
+struct SurfacedMemory {
+    content: String,
+    memory_type: String,
+    relevance_score: f32,
+}
+
+#[derive(Deserialize)]
+struct LineageTraceResponse {
+    root: String,
+    direction: String,
+    edges: Vec<LineageEdgeInfo>,
+    path: Vec<String>,
+    depth: usize,
+}
+
+#[derive(Deserialize)]
+struct LineageEdgeInfo {
+    from: String,
+    to: String,
+    relation: String,
+    confidence: f32,
+    source: String,
+}
+
+#[derive(Deserialize)]
+struct LineageEdgeResponse {
+    message: String,
+    #[serde(default)]
+    edge_id: String,
+}
+
+#[derive(Deserialize)]
+struct LineageStatsResponse {
+    total_edges: usize,
+    inferred_edges: usize,
+    confirmed_edges: usize,
+    explicit_edges: usize,
+    total_branches: usize,
+    active_branches: usize,
+    edges_by_relation: HashMap<String, usize>,
+    avg_confidence: f32,
+}
+
+/// First 8 characters of an ID, for compact listings
+fn short_id(id: &str) -> &str {
+    &id[..8.min(id.len())]
+}
+
+fn render_remember(response: &Value) -> Option<String> {
+    let resp = RememberResponse::deserialize(response).ok()?;
+    Some(format!("Stored memory: {} ({})", resp.id, resp.message))
+}
+
+fn render_recall(response: &Value) -> Option<String> {
+    let resp = RecallResponse::deserialize(response).ok()?;
+    let mut output = format!("Found {} memories:\n\n", resp.memories.len());
+    for mem in resp.memories {
+        output.push_str(&format!(
+            "**[{}]** {} (similarity: {:.0}%)\n{}\n\n",
+            mem.memory_type,
+            short_id(&mem.id),
+            mem.similarity * 100.0,
+            mem.content
+        ));
+    }
+    Some(output)
+}
+
+fn render_proactive_context(response: &Value) -> Option<String> {
+    let resp = ProactiveContextResponse::deserialize(response).ok()?;
+    let mut output = format!("Surfaced {} relevant memories:\n\n", resp.memories.len());
+    for mem in resp.memories {
+        output.push_str(&format!(
+            "- [{}%] **{}**: {}\n",
+            (mem.relevance_score * 100.0) as u32,
+            mem.memory_type,
+            mem.content.chars().take(200).collect::<String>()
+        ));
+    }
+    Some(output)
+}
+
+fn render_lineage_trace(response: &Value) -> Option<String> {
+    let resp = LineageTraceResponse::deserialize(response).ok()?;
+    let mut output = format!(
+        "**Lineage Trace** ({})\n\nRoot: {}\nDepth: {}\n\n",
+        resp.direction, resp.root, resp.depth
+    );
+
+    if resp.edges.is_empty() {
+        output.push_str("No causal connections found.\n");
+    } else {
+        output.push_str("**Causal Chain:**\n");
+        for edge in &resp.edges {
+            let confidence = (edge.confidence * 100.0) as u32;
+            let source_icon = match edge.source.as_str() {
+                "Confirmed" => "✓",
+                "Explicit" => "⚡",
+                _ => "?",
+            };
+            output.push_str(&format!(
+                "  {} --[{} {}% {}]--> {}\n",
+                short_id(&edge.from),
+                edge.relation,
+                confidence,
+                source_icon,
+                short_id(&edge.to)
+            ));
+        }
+
+        output.push_str(&format!("\n**Path:** {}\n", resp.path.join(" → ")));
+    }
+    Some(output)
+}
+
+fn render_lineage_confirm(response: &Value) -> Option<String> {
+    let resp = LineageEdgeResponse::deserialize(response).ok()?;
+    Some(format!(
+        "✓ Confirmed edge: {} - {}",
+        resp.edge_id, resp.message
+    ))
+}
+
+fn render_lineage_reject(response: &Value) -> Option<String> {
+    let resp = LineageEdgeResponse::deserialize(response).ok()?;
+    Some(format!("✗ Rejected edge: {}", resp.message))
+}
+
+fn render_lineage_link(response: &Value) -> Option<String> {
+    let resp = LineageEdgeResponse::deserialize(response).ok()?;
+    Some(format!(
+        "⚡ Created link: {} - {}",
+        resp.edge_id, resp.message
+    ))
+}
+
+fn render_lineage_stats(response: &Value) -> Option<String> {
+    let resp = LineageStatsResponse::deserialize(response).ok()?;
+    let mut output = "**Lineage Graph Statistics**\n\n".to_string();
+    output.push_str(&format!("**Edges:** {}\n", resp.total_edges));
+    output.push_str(&format!("  ✓ Confirmed: {}\n", resp.confirmed_edges));
+    output.push_str(&format!("  ? Inferred: {}\n", resp.inferred_edges));
+    output.push_str(&format!("  ⚡ Explicit: {}\n", resp.explicit_edges));
+    output.push_str(&format!(
+        "Average Confidence: {:.1}%\n\n",
+        resp.avg_confidence * 100.0
+    ));
+    output.push_str(&format!(
+        "**Branches:** {} total, {} active\n\n",
+        resp.total_branches, resp.active_branches
+    ));
+
+    if !resp.edges_by_relation.is_empty() {
+        output.push_str("**By Relation Type:**\n");
+        let mut relations: Vec<_> = resp.edges_by_relation.iter().collect();
+        relations.sort_by(|a, b| b.1.cmp(a.1));
+        for (relation, count) in relations {
+            output.push_str(&format!("  {}: {}\n", relation, count));
+        }
+    }
+    Some(output)
+}
+
+/// Markdown post-mortems are shown as-is rather than as a JSON string
+fn render_post_mortem(response: &Value) -> Option<String> {
+    response.get("markdown")?.as_str().map(str::to_string)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn text(result: &CallToolResult) -> String {
+        result.content[0].as_text().unwrap().text.clone()
+    }
+
+    #[test]
+    fn path_params_stay_out_of_the_body() {
+        let call = update_todo_call(&UpdateTodoParams {
+            todo_id: "SHO-12".to_string(),
+            content: None,
+            status: Some("done".to_string()),
+            priority: None,
+            project: None,
+            contexts: None,
+            due_date: None,
+            blocked_on: None,
+            notes: None,
+            tags: None,
+            sort_order: None,
+            parent_id: None,
+            external_id: None,
+            recurrence: None,
+            blocked_by: None,
+            blocks: None,
+        });
+        assert_eq!(call.method, RestMethod::Post);
+        assert_eq!(call.path, "/api/todos/SHO-12/update");
+        assert_eq!(
+            call.body_for("alice"),
+            Some(json!({ "user_id": "alice", "status": "done" }))
+        );
+
+        let call = forget_call(&ForgetParams {
+            id: "m-1".to_string(),
+        });
+        assert_eq!(call.method, RestMethod::Delete);
+        assert_eq!(call.path, "/api/memory/m-1");
+        assert_eq!(call.body_for("alice"), None);
+    }
+
+    #[test]
+    fn summaries_fall_back_to_json() {
+        let call = remember_call(&RememberParams {
+            content: "Use rustls".to_string(),
+            memory_type: None,
+            tags: None,
+        });
+        let stored = json!({ "id": "0123456789", "message": "stored" });
+        assert_eq!(
+            text(&call.output(&stored)),
+            "Stored memory: 0123456789 (stored)"
+        );
+
+        let unexpected = json!({ "success": true });
+        assert_eq!(
+            text(&call.output(&unexpected)),
+            serde_json::to_string_pretty(&unexpected).unwrap()
+        );
+
+        let call = post_mortem_call(&PostMortemParams {
+            subject_id: "SHO-1".to_string(),
+            format: Some("markdown".to_string()),
+            regenerate: false,
+        });
+        let markdown = json!({ "markdown": "# Post-mortem" });
+        assert_eq!(text(&call.output(&markdown)), "# Post-mortem");
+    }
+}
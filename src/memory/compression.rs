@@ -3,10 +3,11 @@
 use anyhow::{anyhow, Result};
 use base64::{engine::general_purpose, Engine as _};
 use lz4;
-use rust_stemmers::{Algorithm, Stemmer};
 use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
 
+use super::language::{self, Language};
+use super::storage::decode_experience;
 use super::types::*;
 use crate::constants::{
     COMPRESSION_ACCESS_THRESHOLD, COMPRESSION_AGE_DAYS, COMPRESSION_IMPORTANCE_HIGH,
@@ -288,8 +289,7 @@ impl CompressionPipeline {
                 ));
             }
 
-            let (experience, _): (Experience, _) =
-                bincode::serde::decode_from_slice(&decompressed, bincode::config::standard())?;
+            let experience = decode_experience(&decompressed)?;
 
             // Restore the memory
             let mut restored = memory.clone();
@@ -329,15 +329,16 @@ impl KeywordExtractor {
     fn extract(&self, text: &str) -> Vec<String> {
         // Simple TF-IDF style extraction
         let mut word_freq: HashMap<String, usize> = HashMap::new();
+        let language = Language::detect(text);
 
         for word in text.split_whitespace() {
             let clean_word = word
                 .to_lowercase()
                 .chars()
-                .filter(|c| c.is_alphanumeric())
+                .filter(|c| language::is_word_char(*c))
                 .collect::<String>();
 
-            if clean_word.len() >= 2 && !self.stop_words.contains(&clean_word) {
+            if clean_word.len() >= 2 && !self.is_stop_word(&clean_word, language) {
                 *word_freq.entry(clean_word).or_insert(0) += 1;
             }
         }
@@ -353,9 +354,9 @@ impl KeywordExtractor {
             .collect()
     }
 
-    /// Check if a word is a stop word
-    fn is_stop_word(&self, word: &str) -> bool {
-        self.stop_words.contains(word)
+    /// Check if a word is a stop word (English list plus the text's own language)
+    fn is_stop_word(&self, word: &str, language: Language) -> bool {
+        self.stop_words.contains(word) || language.is_stopword(word)
     }
 
     fn load_stop_words() -> HashSet<String> {
@@ -571,6 +572,232 @@ pub struct SemanticFact {
     pub last_reinforced: chrono::DateTime<chrono::Utc>,
     /// Category of fact (preference, capability, relationship, procedure)
     pub fact_type: FactType,
+    /// When this fact started to hold (earliest supporting memory).
+    /// `None` for facts stored before validity tracking: valid from `created_at`
+    #[serde(default)]
+    pub valid_from: Option<chrono::DateTime<chrono::Utc>>,
+    /// When this fact stopped holding (`None` = still current)
+    #[serde(default)]
+    pub valid_to: Option<chrono::DateTime<chrono::Utc>>,
+    /// ID of the newer fact that contradicted and replaced this one
+    #[serde(default)]
+    pub superseded_by: Option<String>,
+}
+
+impl SemanticFact {
+    /// Start of the validity interval
+    pub fn valid_since(&self) -> chrono::DateTime<chrono::Utc> {
+        self.valid_from.unwrap_or(self.created_at)
+    }
+
+    /// Whether this fact still holds (has not been superseded)
+    pub fn is_current(&self) -> bool {
+        self.valid_to.is_none()
+    }
+
+    /// Whether this fact held at `as_of`
+    pub fn is_valid_at(&self, as_of: chrono::DateTime<chrono::Utc>) -> bool {
+        self.valid_since() <= as_of && self.valid_to.is_none_or(|end| as_of < end)
+    }
+
+    /// Close out this fact because `newer` contradicts it
+    pub fn supersede_with(&mut self, newer: &SemanticFact) {
+        self.valid_to = Some(newer.valid_since().max(self.valid_since()));
+        self.superseded_by = Some(newer.id.clone());
+    }
+
+    /// Whether the two facts make contradicting claims about the same entity
+    ///
+    /// Only claims that can't both hold count: the same subject, predicate and
+    /// object with opposite polarity ("the api uses redis" / "the api no longer
+    /// uses redis"), or a transition away from a value the other fact asserts
+    /// ("we moved from Postgres to SQLite" / "the backend uses Postgres").
+    /// Different objects for the same predicate ("uses rust" / "uses docker")
+    /// are not contradictions.
+    pub fn contradicts(&self, other: &SemanticFact) -> bool {
+        match (FactClaim::parse(&self.fact), FactClaim::parse(&other.fact)) {
+            (Some(a), Some(b)) => a.contradicts(&b) || b.contradicts(&a),
+            _ => false,
+        }
+    }
+}
+
+/// Subject-predicate-object reading of a fact statement
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct FactClaim {
+    subject: String,
+    predicate: &'static str,
+    object: String,
+    positive: bool,
+    /// For transitions ("moved from X to Y"), the value left behind
+    replaces: Option<String>,
+}
+
+impl FactClaim {
+    /// Predicates understood by the claim reader, with their canonical form
+    const PREDICATES: &'static [(&'static str, &'static str)] = &[
+        (" is using ", "uses"),
+        (" are using ", "uses"),
+        (" uses ", "uses"),
+        (" use ", "uses"),
+        (" runs on ", "runs on"),
+        (" run on ", "runs on"),
+        (" depends on ", "depends on"),
+        (" depend on ", "depends on"),
+        (" prefers ", "prefers"),
+        (" prefer ", "prefers"),
+        (" likes ", "likes"),
+        (" like ", "likes"),
+        (" lives in ", "lives in"),
+        (" live in ", "lives in"),
+        (" works at ", "works at"),
+        (" work at ", "works at"),
+        (" is ", "is"),
+        (" are ", "is"),
+    ];
+
+    /// Verb phrases that move the subject from one value to another
+    const TRANSITIONS: &'static [&'static str] = &[
+        " moved from ",
+        " migrated from ",
+        " switched from ",
+        " changed from ",
+        " transitioned from ",
+        " went from ",
+        " upgraded from ",
+    ];
+
+    /// Negations directly preceding the predicate ("does not use")
+    const NEGATIONS: &'static [&'static str] = &[
+        " does not",
+        " doesn't",
+        " do not",
+        " don't",
+        " did not",
+        " didn't",
+        " no longer",
+        " never",
+    ];
+
+    fn parse(text: &str) -> Option<Self> {
+        let lower = format!(" {}", text.trim().to_lowercase());
+        Self::parse_transition(&lower).or_else(|| Self::parse_statement(&lower))
+    }
+
+    fn parse_transition(lower: &str) -> Option<Self> {
+        let (pos, marker) = Self::TRANSITIONS
+            .iter()
+            .filter_map(|m| lower.find(m).map(|pos| (pos, *m)))
+            .min_by_key(|(pos, _)| *pos)?;
+        let rest = &lower[pos + marker.len()..];
+        let to = rest.find(" to ")?;
+        let from = Self::normalize(&rest[..to]);
+        let target = Self::normalize(&rest[to + " to ".len()..]);
+        if from.is_empty() || target.is_empty() || from == target {
+            return None;
+        }
+        Some(Self {
+            subject: Self::normalize(&lower[..pos]),
+            predicate: "uses",
+            object: target,
+            positive: true,
+            replaces: Some(from),
+        })
+    }
+
+    fn parse_statement(lower: &str) -> Option<Self> {
+        // Earliest predicate wins; on a tie the longer phrase ("is using" over "is")
+        let (pos, marker, predicate) = Self::PREDICATES
+            .iter()
+            .filter_map(|(m, p)| lower.find(m).map(|pos| (pos, *m, *p)))
+            .min_by_key(|(pos, m, _)| (*pos, usize::MAX - m.len()))?;
+
+        let mut subject = &lower[..pos];
+        let mut positive = true;
+        for negation in Self::NEGATIONS {
+            if let Some(stripped) = subject.strip_suffix(negation) {
+                subject = stripped;
+                positive = !positive;
+                break;
+            }
+        }
+        let mut object = &lower[pos + marker.len()..];
+        for negation in ["not ", "no longer "] {
+            if let Some(stripped) = object.strip_prefix(negation) {
+                object = stripped;
+                positive = !positive;
+                break;
+            }
+        }
+
+        let subject = Self::normalize(subject);
+        let object = Self::normalize(object);
+        if subject.is_empty() || object.is_empty() {
+            return None;
+        }
+        Some(Self {
+            subject,
+            predicate,
+            object,
+            positive,
+            replaces: None,
+        })
+    }
+
+    /// Trim to the first clause and drop leading articles and possessives
+    fn normalize(phrase: &str) -> String {
+        let end = phrase
+            .find(['.', ',', ';', ':', '!', '?', '(', '\n'])
+            .unwrap_or(phrase.len());
+        let clause = phrase[..end]
+            .split(" because ")
+            .next()
+            .unwrap_or("")
+            .split(" since ")
+            .next()
+            .unwrap_or("");
+        let mut words: Vec<&str> = clause.split_whitespace().collect();
+        while words.first().is_some_and(|w| {
+            matches!(
+                *w,
+                "the" | "a" | "an" | "our" | "my" | "their" | "its" | "this" | "that" | "now"
+            )
+        }) {
+            words.remove(0);
+        }
+        words.join(" ")
+    }
+
+    /// Subjects that don't name an entity ("we moved from ...")
+    fn is_pronoun(subject: &str) -> bool {
+        matches!(subject, "" | "we" | "i" | "they" | "you" | "team" | "user")
+    }
+
+    /// `phrase` begins with the whole words of `prefix`
+    fn starts_with_words(phrase: &str, prefix: &str) -> bool {
+        phrase
+            .strip_prefix(prefix)
+            .is_some_and(|rest| rest.is_empty() || rest.starts_with(' '))
+    }
+
+    /// Whether `self` rules out `other`
+    fn contradicts(&self, other: &FactClaim) -> bool {
+        if let Some(replaced) = &self.replaces {
+            // A transition away from what the other fact asserts
+            let same_subject = self.subject == other.subject
+                || Self::is_pronoun(&self.subject)
+                || Self::is_pronoun(&other.subject);
+            return other.positive
+                && same_subject
+                && Self::starts_with_words(&other.object, replaced)
+                && !Self::starts_with_words(&other.object, &self.object);
+        }
+        other.replaces.is_none()
+            && self.subject == other.subject
+            && self.predicate == other.predicate
+            && self.object == other.object
+            && self.positive != other.positive
+    }
 }
 
 /// Types of semantic facts
@@ -623,7 +850,6 @@ pub struct SemanticConsolidator {
     min_support: usize,
     /// Minimum age in days before consolidation
     min_age_days: i64,
-    stemmer: Stemmer,
 }
 
 /// A cluster of semantically similar pattern candidates
@@ -646,7 +872,6 @@ impl SemanticConsolidator {
             keyword_extractor: KeywordExtractor::new(),
             min_support: CONSOLIDATION_MIN_SUPPORT,
             min_age_days: CONSOLIDATION_MIN_AGE_DAYS,
-            stemmer: Stemmer::create(Algorithm::English),
         }
     }
 
@@ -656,7 +881,6 @@ impl SemanticConsolidator {
             keyword_extractor: KeywordExtractor::new(),
             min_support,
             min_age_days,
-            stemmer: Stemmer::create(Algorithm::English),
         }
     }
 
@@ -686,6 +910,8 @@ impl SemanticConsolidator {
         if eligible.is_empty() {
             return result;
         }
+        let observed_at: HashMap<&MemoryId, chrono::DateTime<chrono::Utc>> =
+            eligible.iter().map(|m| (&m.id, m.created_at)).collect();
 
         // Phase 1: Extract candidates using multi-extractor pipeline
         let mut all_candidates: Vec<(String, MemoryId, f32)> = Vec::new();
@@ -718,6 +944,12 @@ impl SemanticConsolidator {
                     .collect();
                 let entities = self.keyword_extractor.extract(representative);
                 let fact_type = self.classify_fact(representative);
+                // The fact holds from its earliest supporting episode
+                let valid_from = source_ids
+                    .iter()
+                    .filter_map(|id| observed_at.get(id).copied())
+                    .min()
+                    .unwrap_or(now);
 
                 let fact = SemanticFact {
                     id: uuid::Uuid::new_v4().to_string(),
@@ -729,6 +961,9 @@ impl SemanticConsolidator {
                     created_at: now,
                     last_reinforced: now,
                     fact_type,
+                    valid_from: Some(valid_from),
+                    valid_to: None,
+                    superseded_by: None,
                 };
 
                 result.new_fact_ids.push(fact.id.clone());
@@ -743,16 +978,20 @@ impl SemanticConsolidator {
     // ── Clustering ──────────────────────────────────────────────────────────
 
     /// Tokenize text into stemmed tokens, removing stop words and punctuation
+    ///
+    /// Stemmer and stop words follow the detected language of the text.
     fn stemmed_tokens(&self, text: &str) -> HashSet<String> {
+        let language = Language::detect(text);
+        let stemmer = language.stemmer();
         text.split_whitespace()
             .map(|w| {
                 w.to_lowercase()
                     .chars()
-                    .filter(|c| c.is_alphanumeric())
+                    .filter(|c| language::is_word_char(*c))
                     .collect::<String>()
             })
-            .filter(|w| w.len() >= 2 && !self.keyword_extractor.is_stop_word(w))
-            .map(|w| self.stemmer.stem(&w).to_string())
+            .filter(|w| w.len() >= 2 && !self.keyword_extractor.is_stop_word(w, language))
+            .map(|w| stemmer.stem(&w))
             .collect()
     }
 
@@ -885,12 +1124,13 @@ impl SemanticConsolidator {
         // Entity pair relationships (sorted for determinism)
         // Filter: min 3 chars, no stop words, remove substring-redundant entities
         if memory.experience.entities.len() >= 2 {
+            let language = memory.experience.detected_language();
             let mut sorted_entities: Vec<String> = memory
                 .experience
                 .entities
                 .iter()
                 .map(|e| e.to_lowercase())
-                .filter(|e| e.len() >= 3 && !self.keyword_extractor.is_stop_word(e))
+                .filter(|e| e.len() >= 3 && !self.keyword_extractor.is_stop_word(e, language))
                 .collect();
             sorted_entities.sort();
             sorted_entities.dedup();
@@ -1089,6 +1329,7 @@ impl SemanticConsolidator {
     fn extract_salient_statement(&self, content: &str, entities: &[String]) -> Option<String> {
         let sentences = Self::split_sentences(content);
         let entity_lower: Vec<String> = entities.iter().map(|e| e.to_lowercase()).collect();
+        let language = Language::detect(content);
 
         let mut best: Option<(String, f32)> = None;
 
@@ -1105,10 +1346,10 @@ impl SemanticConsolidator {
                 .split_whitespace()
                 .map(|w| {
                     w.chars()
-                        .filter(|c| c.is_alphanumeric())
+                        .filter(|c| language::is_word_char(*c))
                         .collect::<String>()
                 })
-                .filter(|w| !w.is_empty() && !self.keyword_extractor.is_stop_word(w))
+                .filter(|w| !w.is_empty() && !self.keyword_extractor.is_stop_word(w, language))
                 .count();
 
             // Require at least 3 content words for a meaningful statement
@@ -1224,10 +1465,11 @@ impl SemanticConsolidator {
         }
 
         // Add any new entities (filter noise: short tokens, stop words)
+        let language = memory.experience.detected_language();
         for entity in &memory.experience.entities {
             let lower = entity.to_lowercase();
             if lower.len() >= 3
-                && !self.keyword_extractor.is_stop_word(&lower)
+                && !self.keyword_extractor.is_stop_word(&lower, language)
                 && !fact.related_entities.contains(entity)
             {
                 fact.related_entities.push(entity.clone());
@@ -1435,6 +1677,9 @@ mod tests {
             created_at: chrono::Utc::now(),
             last_reinforced: chrono::Utc::now() - chrono::Duration::days(10),
             fact_type: FactType::Pattern,
+            valid_from: None,
+            valid_to: None,
+            superseded_by: None,
         };
         let memory = create_test_memory("reinforcing memory", 0.7);
 
@@ -1460,6 +1705,9 @@ mod tests {
             created_at: chrono::Utc::now(),
             last_reinforced: chrono::Utc::now(),
             fact_type: FactType::Pattern,
+            valid_from: None,
+            valid_to: None,
+            superseded_by: None,
         };
         assert!(!consolidator.should_decay_fact(&recent_fact));
 
@@ -1473,10 +1721,67 @@ mod tests {
             created_at: chrono::Utc::now() - chrono::Duration::days(365),
             last_reinforced: chrono::Utc::now() - chrono::Duration::days(100),
             fact_type: FactType::Pattern,
+            valid_from: None,
+            valid_to: None,
+            superseded_by: None,
         };
         assert!(consolidator.should_decay_fact(&old_fact));
     }
 
+    #[test]
+    fn test_fact_contradiction() {
+        let fact = |text: &str| SemanticFact {
+            id: text.to_string(),
+            fact: text.to_string(),
+            confidence: 0.8,
+            support_count: 2,
+            source_memories: vec![],
+            related_entities: vec![],
+            created_at: chrono::Utc::now(),
+            last_reinforced: chrono::Utc::now(),
+            fact_type: FactType::Pattern,
+            valid_from: None,
+            valid_to: None,
+            superseded_by: None,
+        };
+        let moved = fact("We moved from Postgres to SQLite.");
+
+        // Transition away from an asserted value, in either order
+        assert!(moved.contradicts(&fact("The backend uses Postgres for storage")));
+        assert!(fact("The backend uses Postgres").contradicts(&moved));
+        assert!(moved.contradicts(&fact("We migrated from MySQL to Postgres")));
+        assert!(!moved.contradicts(&fact("The backend uses SQLite")));
+        assert!(!moved.contradicts(&fact("The backend uses PostgresML")));
+
+        // Opposite polarity about the same subject, predicate and object
+        let uses_redis = fact("The api uses Redis");
+        assert!(uses_redis.contradicts(&fact("The api no longer uses Redis")));
+        assert!(uses_redis.contradicts(&fact("The api does not use Redis")));
+        assert!(fact("Auth is stateless").contradicts(&fact("Auth is not stateless")));
+
+        // Different values for the same predicate can both hold
+        assert!(!uses_redis.contradicts(&fact("The api uses Tokio")));
+        assert!(!uses_redis.contradicts(&fact("The worker does not use Redis")));
+        assert!(!uses_redis.contradicts(&fact("errors often occur after deployment")));
+    }
+
+    #[test]
+    fn test_consolidated_fact_valid_from_earliest_source() {
+        let consolidator = SemanticConsolidator::with_thresholds(2, 0);
+        let mut older = create_test_memory("The deploy pipeline uses GitHub Actions", 0.8);
+        older.created_at = chrono::Utc::now() - chrono::Duration::days(90);
+        let newer = create_test_memory("The deploy pipeline uses GitHub Actions", 0.8);
+
+        let result = consolidator.consolidate(&[older.clone(), newer]);
+        assert!(!result.new_facts.is_empty());
+        for fact in &result.new_facts {
+            assert_eq!(fact.valid_from, Some(older.created_at));
+            assert!(fact.is_current());
+            assert!(fact.is_valid_at(chrono::Utc::now()));
+            assert!(!fact.is_valid_at(older.created_at - chrono::Duration::days(1)));
+        }
+    }
+
     #[test]
     fn test_compression_stats_default() {
         let stats = CompressionStats::default();
@@ -8,13 +8,63 @@
 //! - `facts_by_entity:{user_id}:{entity}:{fact_id}` - Entity index for fast lookup
 //! - `facts_by_type:{user_id}:{type}:{fact_id}` - Type index
 //! - `facts_embedding:{user_id}:{fact_id}` - Pre-computed embedding vector (384-dim)
+//!
+//! Facts are bi-temporal: `created_at` records when a fact was learned, and
+//! `valid_from`/`valid_to` when it held. A fact contradicted by a newer one is
+//! closed out rather than deleted, so "as of" queries still see it.
 
 use anyhow::Result;
+use chrono::{DateTime, Utc};
 use rocksdb::{IteratorMode, DB};
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 
 use super::compression::{FactType, SemanticFact};
+use super::types::MemoryId;
+
+/// SemanticFact as stored before validity tracking - positional bincode match
+#[derive(Deserialize)]
+struct LegacySemanticFactV1 {
+    id: String,
+    fact: String,
+    confidence: f32,
+    support_count: usize,
+    source_memories: Vec<MemoryId>,
+    related_entities: Vec<String>,
+    created_at: DateTime<Utc>,
+    last_reinforced: DateTime<Utc>,
+    fact_type: FactType,
+}
+
+impl From<LegacySemanticFactV1> for SemanticFact {
+    fn from(legacy: LegacySemanticFactV1) -> Self {
+        Self {
+            id: legacy.id,
+            fact: legacy.fact,
+            confidence: legacy.confidence,
+            support_count: legacy.support_count,
+            source_memories: legacy.source_memories,
+            related_entities: legacy.related_entities,
+            created_at: legacy.created_at,
+            last_reinforced: legacy.last_reinforced,
+            fact_type: legacy.fact_type,
+            valid_from: None,
+            valid_to: None,
+            superseded_by: None,
+        }
+    }
+}
+
+/// Decode a stored fact, falling back to the pre-validity layout
+pub(crate) fn decode_fact(data: &[u8]) -> Result<SemanticFact> {
+    let config = bincode::config::standard();
+    match bincode::serde::decode_from_slice::<SemanticFact, _>(data, config) {
+        Ok((fact, _)) => Ok(fact),
+        Err(e) => bincode::serde::decode_from_slice::<LegacySemanticFactV1, _>(data, config)
+            .map(|(legacy, _)| legacy.into())
+            .map_err(|_| e.into()),
+    }
+}
 
 /// Response for fact queries
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -30,6 +80,9 @@ pub struct FactStats {
     pub by_type: std::collections::HashMap<String, usize>,
     pub avg_confidence: f32,
     pub avg_support: f32,
+    /// Facts closed out by a newer, contradicting fact
+    #[serde(default)]
+    pub superseded_facts: usize,
 }
 
 /// Storage for semantic facts with indexing
@@ -89,11 +142,7 @@ impl SemanticFactStore {
     pub fn get(&self, user_id: &str, fact_id: &str) -> Result<Option<SemanticFact>> {
         let key = format!("facts:{}:{}", user_id, fact_id);
         match self.db.get(key.as_bytes())? {
-            Some(data) => {
-                let (fact, _): (SemanticFact, _) =
-                    bincode::serde::decode_from_slice(&data, bincode::config::standard())?;
-                Ok(Some(fact))
-            }
+            Some(data) => Ok(Some(decode_fact(&data)?)),
             None => Ok(None),
         }
     }
@@ -140,8 +189,27 @@ impl SemanticFactStore {
         }
     }
 
-    /// List all facts for a user
+    /// List all facts for a user, including superseded ones
     pub fn list(&self, user_id: &str, limit: usize) -> Result<Vec<SemanticFact>> {
+        self.list_matching(user_id, limit, |_| true)
+    }
+
+    /// List the facts that held at `as_of`
+    pub fn list_as_of(
+        &self,
+        user_id: &str,
+        as_of: DateTime<Utc>,
+        limit: usize,
+    ) -> Result<Vec<SemanticFact>> {
+        self.list_matching(user_id, limit, |f| f.is_valid_at(as_of))
+    }
+
+    fn list_matching(
+        &self,
+        user_id: &str,
+        limit: usize,
+        keep: impl Fn(&SemanticFact) -> bool,
+    ) -> Result<Vec<SemanticFact>> {
         let prefix = format!("facts:{}:", user_id);
         let mut facts = Vec::new();
 
@@ -164,12 +232,10 @@ impl SemanticFactStore {
                 continue;
             }
 
-            if let Ok(fact) = bincode::serde::decode_from_slice::<SemanticFact, _>(
-                &value,
-                bincode::config::standard(),
-            )
-            .map(|(v, _)| v)
-            {
+            if let Ok(fact) = decode_fact(&value) {
+                if !keep(&fact) {
+                    continue;
+                }
                 facts.push(fact);
                 if facts.len() >= limit {
                     break;
@@ -193,6 +259,27 @@ impl SemanticFactStore {
         user_id: &str,
         entity: &str,
         limit: usize,
+    ) -> Result<Vec<SemanticFact>> {
+        self.find_by_entity_matching(user_id, entity, limit, |_| true)
+    }
+
+    /// Find facts by related entity that held at `as_of`
+    pub fn find_by_entity_as_of(
+        &self,
+        user_id: &str,
+        entity: &str,
+        as_of: DateTime<Utc>,
+        limit: usize,
+    ) -> Result<Vec<SemanticFact>> {
+        self.find_by_entity_matching(user_id, entity, limit, |f| f.is_valid_at(as_of))
+    }
+
+    fn find_by_entity_matching(
+        &self,
+        user_id: &str,
+        entity: &str,
+        limit: usize,
+        keep: impl Fn(&SemanticFact) -> bool,
     ) -> Result<Vec<SemanticFact>> {
         let prefix = format!("facts_by_entity:{}:{}:", user_id, entity.to_lowercase());
         let mut facts = Vec::new();
@@ -213,7 +300,7 @@ impl SemanticFactStore {
 
             let fact_id = String::from_utf8_lossy(&value);
             if seen_ids.insert(fact_id.to_string()) {
-                if let Some(fact) = self.get(user_id, &fact_id)? {
+                if let Some(fact) = self.get(user_id, &fact_id)?.filter(|f| keep(f)) {
                     facts.push(fact);
                     if facts.len() >= limit {
                         break;
@@ -263,16 +350,63 @@ impl SemanticFactStore {
 
     /// Search facts by keyword in fact content
     pub fn search(&self, user_id: &str, query: &str, limit: usize) -> Result<Vec<SemanticFact>> {
-        let query_lower = query.to_lowercase();
         let all_facts = self.list(user_id, 1000)?; // Get all facts
+        Ok(Self::filter_by_keyword(all_facts, query, limit))
+    }
+
+    /// Search the facts that held at `as_of` by keyword
+    pub fn search_as_of(
+        &self,
+        user_id: &str,
+        query: &str,
+        as_of: DateTime<Utc>,
+        limit: usize,
+    ) -> Result<Vec<SemanticFact>> {
+        let facts = self.list_as_of(user_id, as_of, 1000)?;
+        Ok(Self::filter_by_keyword(facts, query, limit))
+    }
 
+    fn filter_by_keyword(
+        all_facts: Vec<SemanticFact>,
+        query: &str,
+        limit: usize,
+    ) -> Vec<SemanticFact> {
+        let query_lower = query.to_lowercase();
         let mut matching: Vec<SemanticFact> = all_facts
             .into_iter()
             .filter(|f| f.fact.to_lowercase().contains(&query_lower))
             .collect();
 
         matching.truncate(limit);
-        Ok(matching)
+        matching
+    }
+
+    /// Close out stored facts that contradict `fact`
+    ///
+    /// Of each contradicting pair the fact that became valid first is closed
+    /// out: `valid_to` is set to when the other took over and `superseded_by`
+    /// links to it. If `fact` is the older one it is closed out itself, before
+    /// the caller stores it. Returns the stored facts that were closed out.
+    pub fn supersede_contradicted(
+        &self,
+        user_id: &str,
+        fact: &mut SemanticFact,
+    ) -> Result<Vec<SemanticFact>> {
+        let mut closed = Vec::new();
+        for mut existing in self.list_matching(user_id, usize::MAX, SemanticFact::is_current)? {
+            if existing.id == fact.id || !existing.contradicts(fact) {
+                continue;
+            }
+            if existing.valid_since() <= fact.valid_since() {
+                existing.supersede_with(fact);
+                self.update(user_id, &existing)?;
+                closed.push(existing);
+            } else if fact.valid_to.is_none_or(|end| existing.valid_since() < end) {
+                // The stored fact is newer: the earliest one to take over wins
+                fact.supersede_with(&existing);
+            }
+        }
+        Ok(closed)
     }
 
     /// Get statistics about stored facts
@@ -288,11 +422,16 @@ impl SemanticFactStore {
         let mut total_confidence: f32 = 0.0;
         let mut total_support: usize = 0;
 
+        let mut superseded = 0;
+
         for fact in &facts {
             let type_name = format!("{:?}", fact.fact_type);
             *by_type.entry(type_name).or_insert(0) += 1;
             total_confidence += fact.confidence;
             total_support += fact.support_count;
+            if !fact.is_current() {
+                superseded += 1;
+            }
         }
 
         let count = facts.len();
@@ -301,6 +440,7 @@ impl SemanticFactStore {
             by_type,
             avg_confidence: total_confidence / count as f32,
             avg_support: total_support as f32 / count as f32,
+            superseded_facts: superseded,
         })
     }
 
@@ -531,6 +671,9 @@ mod tests {
             created_at: chrono::Utc::now(),
             last_reinforced: chrono::Utc::now(),
             fact_type: FactType::Pattern,
+            valid_from: None,
+            valid_to: None,
+            superseded_by: None,
         }
     }
 
@@ -603,4 +746,83 @@ mod tests {
         assert_eq!(stats.total_facts, 2);
         assert!(stats.avg_confidence > 0.0);
     }
+
+    #[test]
+    fn test_supersede_contradicted() {
+        let (store, _dir) = create_test_store();
+        let now = chrono::Utc::now();
+
+        let mut old = create_test_fact("fact-old", "The backend uses Postgres");
+        old.valid_from = Some(now - chrono::Duration::days(30));
+        store.store("user-1", &old).unwrap();
+        let mut unrelated = create_test_fact("fact-other", "The backend uses Tokio");
+        unrelated.valid_from = old.valid_from;
+        store.store("user-1", &unrelated).unwrap();
+
+        let mut moved = create_test_fact("fact-new", "We moved from Postgres to SQLite");
+        moved.valid_from = Some(now - chrono::Duration::days(10));
+        let closed = store.supersede_contradicted("user-1", &mut moved).unwrap();
+        store.store("user-1", &moved).unwrap();
+
+        assert_eq!(closed.len(), 1);
+        assert!(moved.is_current());
+        let old = store.get("user-1", "fact-old").unwrap().unwrap();
+        assert_eq!(old.superseded_by.as_deref(), Some("fact-new"));
+        assert_eq!(old.valid_to, moved.valid_from);
+
+        let ids = |facts: Vec<SemanticFact>| {
+            let mut ids: Vec<String> = facts.into_iter().map(|f| f.id).collect();
+            ids.sort();
+            ids
+        };
+        assert_eq!(
+            ids(store.list_as_of("user-1", now, 10).unwrap()),
+            vec!["fact-new", "fact-other"]
+        );
+        assert_eq!(
+            ids(store
+                .list_as_of("user-1", now - chrono::Duration::days(20), 10)
+                .unwrap()),
+            vec!["fact-old", "fact-other"]
+        );
+        assert_eq!(store.stats("user-1").unwrap().superseded_facts, 1);
+
+        // A late-arriving fact older than the stored one it contradicts is closed out itself
+        let mut stale = create_test_fact("fact-stale", "The backend no longer uses SQLite");
+        stale.valid_from = Some(now - chrono::Duration::days(40));
+        let mut current = create_test_fact("fact-sqlite", "The backend uses SQLite");
+        current.valid_from = Some(now - chrono::Duration::days(5));
+        store.store("user-1", &current).unwrap();
+        assert!(store
+            .supersede_contradicted("user-1", &mut stale)
+            .unwrap()
+            .is_empty());
+        assert_eq!(stale.superseded_by.as_deref(), Some("fact-sqlite"));
+        assert_eq!(stale.valid_to, current.valid_from);
+    }
+
+    #[test]
+    fn test_decode_legacy_fact() {
+        let (store, _dir) = create_test_store();
+        let created = chrono::Utc::now();
+        let legacy = (
+            "fact-1".to_string(),
+            "Rust is fast".to_string(),
+            0.7f32,
+            2usize,
+            Vec::<MemoryId>::new(),
+            vec!["rust".to_string()],
+            created,
+            created,
+            FactType::Definition,
+        );
+        let value = bincode::serde::encode_to_vec(&legacy, bincode::config::standard()).unwrap();
+        store.db.put(b"facts:user-1:fact-1", &value).unwrap();
+
+        let fact = store.get("user-1", "fact-1").unwrap().unwrap();
+        assert_eq!(fact.fact, "Rust is fast");
+        assert_eq!(fact.fact_type, FactType::Definition);
+        assert!(fact.is_current());
+        assert_eq!(fact.valid_since(), created);
+    }
 }
@@ -0,0 +1,879 @@
+//! Live Codebase Watching
+//!
+//! Keeps a project's file memories in step with its working tree after the
+//! initial index. Each watched project gets a thread that listens for changes
+//! through the `notify` crate (native notifications where the platform has
+//! them; periodic rescans elsewhere or on request), debounces bursts such as a
+//! branch switch, and then re-indexes only files whose content hash changed.
+//! Deleted files are dropped, and a delete plus a create with identical
+//! content is treated as a rename, so the memory keeps its id and access
+//! history.
+
+use anyhow::Result;
+use glob::Pattern;
+use notify::event::{AccessKind, AccessMode};
+use notify::{Config, Event, EventKind, PollWatcher, RecursiveMode, Watcher};
+use parking_lot::Mutex;
+use serde::Serialize;
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::sync::Arc;
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+
+use super::files::FileMemoryStore;
+use super::gitignore::GitIgnore;
+use super::types::{CodebaseConfig, FileMemory, ProjectId};
+
+/// Quiet period after the last change before a burst is processed
+pub const DEFAULT_DEBOUNCE_MS: u64 = 500;
+/// Rescan interval for the polling backend
+pub const DEFAULT_POLL_INTERVAL_MS: u64 = 5_000;
+/// A burst that never goes quiet is still processed after this many debounce periods
+const MAX_DEBOUNCE_PERIODS: u32 = 10;
+/// How often an idle watcher thread checks for shutdown
+const STOP_CHECK_INTERVAL: Duration = Duration::from_millis(200);
+
+/// How a watcher learns about changes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WatchBackend {
+    /// Operating system change notifications (inotify, FSEvents, ReadDirectoryChangesW)
+    Native,
+    /// Periodic rescans of the watched directories
+    Polling,
+}
+
+/// Tuning for a codebase watcher
+#[derive(Debug, Clone)]
+pub struct WatchOptions {
+    pub debounce: Duration,
+    pub poll_interval: Duration,
+    /// Poll even where change notifications are available (e.g. network filesystems)
+    pub force_polling: bool,
+    /// Reconcile changes made while nothing was watching before listening
+    pub initial_sync: bool,
+}
+
+impl Default for WatchOptions {
+    fn default() -> Self {
+        Self {
+            debounce: Duration::from_millis(DEFAULT_DEBOUNCE_MS),
+            poll_interval: Duration::from_millis(DEFAULT_POLL_INTERVAL_MS),
+            force_polling: false,
+            initial_sync: true,
+        }
+    }
+}
+
+/// An index change made by a watcher
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum FileChange {
+    Added { path: String },
+    Modified { path: String },
+    Removed { path: String },
+    Renamed { from: String, to: String },
+}
+
+/// Called on the watcher thread for every index change
+pub type ChangeCallback = Arc<dyn Fn(&FileChange) + Send + Sync>;
+
+/// The project a watcher keeps indexed
+#[derive(Debug, Clone)]
+pub struct WatchTarget {
+    pub user_id: String,
+    pub project_id: ProjectId,
+    pub root: PathBuf,
+    pub config: CodebaseConfig,
+}
+
+/// Summary of a running watcher
+#[derive(Debug, Clone, Serialize)]
+pub struct WatchStatus {
+    pub project_id: String,
+    pub codebase_path: String,
+    pub backend: WatchBackend,
+    pub started_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// Handle to a running codebase watcher; dropping it stops the watcher
+pub struct CodebaseWatcher {
+    status: WatchStatus,
+    stop: Arc<AtomicBool>,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl CodebaseWatcher {
+    /// Start watching a codebase
+    ///
+    /// Registers the change listener before returning (walking the directory
+    /// tree to watch each directory); the initial sync runs on the watcher thread.
+    pub fn start(
+        store: Arc<FileMemoryStore>,
+        target: WatchTarget,
+        options: WatchOptions,
+        on_change: ChangeCallback,
+    ) -> Result<Self> {
+        anyhow::ensure!(
+            target.root.is_dir(),
+            "Not a directory: {}",
+            target.root.display()
+        );
+
+        let stop = Arc::new(AtomicBool::new(false));
+        let syncer = IndexSyncer::new(store, target, on_change, Arc::clone(&stop));
+        let source = EventSource::open(&syncer, &options)?;
+        let status = WatchStatus {
+            project_id: syncer.target.project_id.0.to_string(),
+            codebase_path: syncer.target.root.display().to_string(),
+            backend: source.backend(),
+            started_at: chrono::Utc::now(),
+        };
+
+        let handle = std::thread::Builder::new()
+            .name("codebase-watcher".to_string())
+            .spawn({
+                let stop = Arc::clone(&stop);
+                move || run_watcher(syncer, source, options, stop)
+            })?;
+
+        tracing::info!(
+            path = %status.codebase_path,
+            project_id = %status.project_id,
+            backend = ?status.backend,
+            "Started codebase watcher"
+        );
+
+        Ok(Self {
+            status,
+            stop,
+            handle: Some(handle),
+        })
+    }
+
+    pub fn status(&self) -> &WatchStatus {
+        &self.status
+    }
+
+    /// Stop the watcher and wait for its thread to finish the file in progress
+    pub fn stop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+        if let Some(handle) = self.handle.take() {
+            if handle.join().is_err() {
+                tracing::warn!(path = %self.status.codebase_path, "Codebase watcher panicked");
+            }
+            tracing::info!(path = %self.status.codebase_path, "Stopped codebase watcher");
+        }
+    }
+}
+
+impl Drop for CodebaseWatcher {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+/// Running watchers, one per (user, project)
+#[derive(Default)]
+pub struct FileWatcherRegistry {
+    watchers: Mutex<HashMap<(String, ProjectId), CodebaseWatcher>>,
+}
+
+impl FileWatcherRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Start watching a project, replacing any watcher it already has
+    pub fn start(
+        &self,
+        store: Arc<FileMemoryStore>,
+        target: WatchTarget,
+        options: WatchOptions,
+        on_change: ChangeCallback,
+    ) -> Result<WatchStatus> {
+        let key = (target.user_id.clone(), target.project_id.clone());
+        // Stop the old watcher first so the two never index concurrently
+        drop(self.watchers.lock().remove(&key));
+
+        let watcher = CodebaseWatcher::start(store, target, options, on_change)?;
+        let status = watcher.status().clone();
+        // Dropped (and joined) outside the lock if a concurrent start raced us
+        let replaced = self.watchers.lock().insert(key, watcher);
+        drop(replaced);
+        Ok(status)
+    }
+
+    /// Stop a project's watcher; returns false if it had none
+    pub fn stop(&self, user_id: &str, project_id: &ProjectId) -> bool {
+        let watcher = self
+            .watchers
+            .lock()
+            .remove(&(user_id.to_string(), project_id.clone()));
+        watcher.is_some()
+    }
+
+    pub fn list(&self, user_id: &str) -> Vec<WatchStatus> {
+        let mut statuses: Vec<WatchStatus> = self
+            .watchers
+            .lock()
+            .iter()
+            .filter(|((owner, _), _)| owner == user_id)
+            .map(|(_, watcher)| watcher.status().clone())
+            .collect();
+        statuses.sort_by(|a, b| a.codebase_path.cmp(&b.codebase_path));
+        statuses
+    }
+
+    /// Stop every watcher (shutdown)
+    pub fn stop_all(&self) {
+        let watchers: Vec<CodebaseWatcher> = self.watchers.lock().drain().map(|(_, w)| w).collect();
+        drop(watchers);
+    }
+}
+
+// =============================================================================
+// WATCH LOOP
+// =============================================================================
+
+fn run_watcher(
+    mut syncer: IndexSyncer,
+    mut source: EventSource,
+    options: WatchOptions,
+    stop: Arc<AtomicBool>,
+) {
+    if options.initial_sync {
+        syncer.sync_all();
+    }
+
+    let max_wait = options.debounce * MAX_DEBOUNCE_PERIODS;
+    let mut pending: HashSet<String> = HashSet::new();
+    let mut rescan = false;
+    let mut first_change: Option<Instant> = None;
+    let mut last_change = Instant::now();
+
+    while !stop.load(Ordering::Relaxed) {
+        let wait = if first_change.is_some() {
+            options.debounce.min(STOP_CHECK_INTERVAL)
+        } else {
+            STOP_CHECK_INTERVAL
+        };
+
+        match source.wait(&syncer, wait) {
+            Ok(changes) => {
+                if changes.rescan || !changes.paths.is_empty() {
+                    first_change.get_or_insert_with(Instant::now);
+                    last_change = Instant::now();
+                    rescan |= changes.rescan;
+                    pending.extend(changes.paths);
+                }
+            }
+            Err(e) => {
+                tracing::warn!(
+                    path = %syncer.target.root.display(),
+                    error = %e,
+                    "Change notifications failed, falling back to polling"
+                );
+                let Some(polling) = fall_back_to_polling(&syncer, &options) else {
+                    return;
+                };
+                source = polling;
+                rescan = true;
+                first_change.get_or_insert_with(Instant::now);
+            }
+        }
+
+        let Some(first) = first_change else {
+            continue;
+        };
+        if last_change.elapsed() < options.debounce && first.elapsed() < max_wait {
+            continue;
+        }
+
+        let paths = std::mem::take(&mut pending);
+        let gitignore_changed = paths
+            .iter()
+            .any(|p| p == ".gitignore" || p.ends_with("/.gitignore"));
+        if rescan || gitignore_changed {
+            syncer.sync_all();
+            if let Err(e) = source.rewatch(&syncer) {
+                tracing::warn!(error = %e, "Failed to re-watch codebase, polling instead");
+                let Some(polling) = fall_back_to_polling(&syncer, &options) else {
+                    return;
+                };
+                source = polling;
+            }
+        } else {
+            syncer.apply(paths);
+        }
+        rescan = false;
+        first_change = None;
+    }
+}
+
+/// A polling source to replace a failed one; `None` stops the watcher
+fn fall_back_to_polling(syncer: &IndexSyncer, options: &WatchOptions) -> Option<EventSource> {
+    match EventSource::polling(syncer, options.poll_interval) {
+        Ok(source) => Some(source),
+        Err(e) => {
+            tracing::error!(
+                path = %syncer.target.root.display(),
+                error = %e,
+                "Failed to poll codebase, stopping watcher"
+            );
+            None
+        }
+    }
+}
+
+/// Paths reported by an event source since the last wait
+#[derive(Default)]
+struct Changes {
+    paths: HashSet<String>,
+    /// Events were lost; only a full sync is reliable
+    rescan: bool,
+}
+
+/// A `notify` watcher over every indexable directory of a codebase
+///
+/// Directories are watched one at a time rather than recursively, so
+/// excluded trees such as `node_modules` or `target` are never watched.
+struct EventSource {
+    backend: WatchBackend,
+    watcher: Box<dyn Watcher + Send>,
+    events: Receiver<notify::Result<Event>>,
+    /// Watched directories, relative to the codebase root
+    dirs: HashSet<String>,
+}
+
+impl EventSource {
+    fn open(syncer: &IndexSyncer, options: &WatchOptions) -> notify::Result<Self> {
+        if !options.force_polling {
+            match Self::new(syncer, WatchBackend::Native, options.poll_interval) {
+                Ok(source) => return Ok(source),
+                Err(e) => tracing::warn!(
+                    path = %syncer.target.root.display(),
+                    error = %e,
+                    "Failed to watch codebase for changes, polling instead"
+                ),
+            }
+        }
+        Self::polling(syncer, options.poll_interval)
+    }
+
+    fn polling(syncer: &IndexSyncer, interval: Duration) -> notify::Result<Self> {
+        Self::new(syncer, WatchBackend::Polling, interval)
+    }
+
+    fn new(
+        syncer: &IndexSyncer,
+        backend: WatchBackend,
+        interval: Duration,
+    ) -> notify::Result<Self> {
+        let (tx, events) = mpsc::channel();
+        let watcher: Box<dyn Watcher + Send> = match backend {
+            WatchBackend::Native => Box::new(notify::recommended_watcher(tx)?),
+            // Modification times can be as coarse as a second, so compare
+            // contents to catch quick successive writes
+            WatchBackend::Polling => Box::new(PollWatcher::new(
+                tx,
+                Config::default()
+                    .with_poll_interval(interval)
+                    .with_compare_contents(true),
+            )?),
+        };
+        let mut source = Self {
+            backend,
+            watcher,
+            events,
+            dirs: HashSet::new(),
+        };
+        source.watch_tree(syncer, "")?;
+        Ok(source)
+    }
+
+    fn backend(&self) -> WatchBackend {
+        self.backend
+    }
+
+    fn wait(&mut self, syncer: &IndexSyncer, timeout: Duration) -> notify::Result<Changes> {
+        let mut changes = Changes::default();
+        let first = match self.events.recv_timeout(timeout) {
+            Ok(event) => event,
+            Err(RecvTimeoutError::Timeout) => return Ok(changes),
+            Err(RecvTimeoutError::Disconnected) => {
+                return Err(notify::Error::generic("Change notifications stopped"))
+            }
+        };
+        let events: Vec<_> = std::iter::once(first)
+            .chain(self.events.try_iter())
+            .collect();
+
+        for event in events {
+            let event = match event {
+                Ok(event) => event,
+                Err(e) => {
+                    tracing::debug!(error = %e, "Change notification error");
+                    continue;
+                }
+            };
+            if event.need_rescan() {
+                changes.rescan = true;
+                continue;
+            }
+            if let EventKind::Access(kind) = event.kind {
+                // Opening and reading files (including our own reads) is not a change
+                if kind != AccessKind::Close(AccessMode::Write) {
+                    continue;
+                }
+            }
+            for path in &event.paths {
+                let Some(relative) = relative_path(&syncer.target.root, path) else {
+                    continue;
+                };
+                if path.is_dir() {
+                    // Entries of a watched directory are reported on their own
+                    if self.dirs.contains(&relative) {
+                        continue;
+                    }
+                    let name = relative.rsplit('/').next().unwrap_or_default();
+                    if syncer.skip_dir(&relative, name) {
+                        continue;
+                    }
+                    self.watch_tree(syncer, &relative)?;
+                } else if !path.exists() {
+                    self.unwatch_tree(syncer, &relative);
+                }
+                changes.paths.insert(relative);
+            }
+        }
+        Ok(changes)
+    }
+
+    /// Watch a directory and every indexable directory below it
+    fn watch_tree(&mut self, syncer: &IndexSyncer, relative_dir: &str) -> notify::Result<()> {
+        let dir = syncer.target.root.join(relative_dir);
+        self.watcher.watch(&dir, RecursiveMode::NonRecursive)?;
+        self.dirs.insert(relative_dir.to_string());
+        let Ok(entries) = fs::read_dir(&dir) else {
+            return Ok(());
+        };
+        for entry in entries.flatten() {
+            if !entry.file_type().is_ok_and(|t| t.is_dir()) {
+                continue;
+            }
+            let file_name = entry.file_name();
+            let Some(name) = file_name.to_str() else {
+                tracing::debug!(path = ?entry.path(), "Skipping path that is not valid UTF-8");
+                continue;
+            };
+            let relative = join_relative(relative_dir, name);
+            if !syncer.skip_dir(&relative, name) && !self.dirs.contains(&relative) {
+                self.watch_tree(syncer, &relative)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Stop watching a directory and everything below it
+    fn unwatch_tree(&mut self, syncer: &IndexSyncer, relative_dir: &str) {
+        let nested = |dir: &String| {
+            relative_dir.is_empty()
+                || dir == relative_dir
+                || dir
+                    .strip_prefix(relative_dir)
+                    .is_some_and(|rest| rest.starts_with('/'))
+        };
+        let removed: Vec<String> = self.dirs.iter().filter(|d| nested(d)).cloned().collect();
+        for dir in removed {
+            // Fails harmlessly for directories that are already gone
+            let _ = self.watcher.unwatch(&syncer.target.root.join(&dir));
+            self.dirs.remove(&dir);
+        }
+    }
+
+    /// Re-register directory watches after the ignore rules changed
+    fn rewatch(&mut self, syncer: &IndexSyncer) -> notify::Result<()> {
+        self.unwatch_tree(syncer, "");
+        self.watch_tree(syncer, "")
+    }
+}
+
+/// Codebase-relative path with `/` separators
+///
+/// `None` outside the codebase and for names that are not valid UTF-8,
+/// which cannot be indexed; converting them lossily would record a path
+/// that does not exist.
+fn relative_path(root: &Path, path: &Path) -> Option<String> {
+    let relative = path.strip_prefix(root).ok()?;
+    let mut parts = Vec::new();
+    for component in relative.components() {
+        let Some(part) = component.as_os_str().to_str() else {
+            tracing::debug!(path = ?path, "Skipping path that is not valid UTF-8");
+            return None;
+        };
+        parts.push(part);
+    }
+    Some(parts.join("/"))
+}
+
+// =============================================================================
+// INDEX SYNC
+// =============================================================================
+
+/// Applies filesystem changes to a project's file memories
+struct IndexSyncer {
+    store: Arc<FileMemoryStore>,
+    target: WatchTarget,
+    exclude_patterns: Vec<Pattern>,
+    ignores: GitIgnore,
+    on_change: ChangeCallback,
+    stop: Arc<AtomicBool>,
+}
+
+impl IndexSyncer {
+    fn new(
+        store: Arc<FileMemoryStore>,
+        target: WatchTarget,
+        on_change: ChangeCallback,
+        stop: Arc<AtomicBool>,
+    ) -> Self {
+        Self {
+            exclude_patterns: FileMemoryStore::compile_exclude_patterns(&target.config),
+            ignores: FileMemoryStore::load_gitignore(&target.root),
+            store,
+            target,
+            on_change,
+            stop,
+        }
+    }
+
+    fn stopped(&self) -> bool {
+        self.stop.load(Ordering::Relaxed)
+    }
+
+    fn skip_dir(&self, relative_path: &str, name: &str) -> bool {
+        FileMemoryStore::entry_skip_reason(
+            relative_path,
+            name,
+            true,
+            &self.exclude_patterns,
+            &self.ignores,
+        )
+        .is_some()
+    }
+
+    fn skip_reason(&self, relative_path: &str) -> Option<String> {
+        FileMemoryStore::file_skip_reason(
+            &self.target.root,
+            relative_path,
+            &self.target.config,
+            &self.exclude_patterns,
+            &self.ignores,
+        )
+    }
+
+    /// Indexable files under a directory (relative to the codebase root)
+    fn walk_files(&self, relative_dir: &str) -> Vec<String> {
+        let mut files = Vec::new();
+        self.walk_dir(relative_dir, &mut files);
+        files
+    }
+
+    fn walk_dir(&self, relative_dir: &str, files: &mut Vec<String>) {
+        let Ok(entries) = fs::read_dir(self.target.root.join(relative_dir)) else {
+            return;
+        };
+        for entry in entries.flatten() {
+            let Ok(file_type) = entry.file_type() else {
+                continue;
+            };
+            let file_name = entry.file_name();
+            let Some(name) = file_name.to_str() else {
+                tracing::debug!(path = ?entry.path(), "Skipping path that is not valid UTF-8");
+                continue;
+            };
+            let relative = join_relative(relative_dir, name);
+            if file_type.is_dir() {
+                if !self.skip_dir(&relative, name) {
+                    self.walk_dir(&relative, files);
+                }
+            } else if file_type.is_file() && self.skip_reason(&relative).is_none() {
+                files.push(relative);
+            }
+        }
+    }
+
+    /// Reconcile every file on disk and every indexed file
+    fn sync_all(&mut self) {
+        self.ignores = FileMemoryStore::load_gitignore(&self.target.root);
+
+        let mut paths: HashSet<String> = self.walk_files("").into_iter().collect();
+        match self
+            .store
+            .list_by_project(&self.target.user_id, &self.target.project_id, None)
+        {
+            Ok(files) => paths.extend(files.into_iter().map(|f| f.path)),
+            Err(e) => tracing::warn!(error = %e, "Failed to list indexed files"),
+        }
+        self.apply(paths);
+    }
+
+    /// Re-check changed paths against the index
+    fn apply(&self, paths: HashSet<String>) {
+        let WatchTarget {
+            user_id,
+            project_id,
+            root,
+            ..
+        } = &self.target;
+
+        // Files that may need (re)indexing, and memories whose files are gone
+        let mut candidates: BTreeSet<String> = BTreeSet::new();
+        let mut vanished: HashMap<String, Vec<FileMemory>> = HashMap::new();
+        for path in paths {
+            match fs::metadata(root.join(&path)) {
+                Ok(metadata) if metadata.is_dir() => candidates.extend(self.walk_files(&path)),
+                Ok(_) => {
+                    candidates.insert(path);
+                }
+                Err(_) => match self.store.files_at_path(user_id, project_id, &path) {
+                    Ok(files) => {
+                        for file in files {
+                            vanished
+                                .entry(file.file_hash.clone())
+                                .or_default()
+                                .push(file);
+                        }
+                    }
+                    Err(e) => tracing::warn!(path = %path, error = %e, "Failed to look up file"),
+                },
+            }
+        }
+
+        for path in candidates {
+            if self.stopped() {
+                return;
+            }
+            if let Err(e) = self.sync_file(&path, &mut vanished) {
+                tracing::warn!(path = %path, error = %e, "Failed to reindex file");
+            }
+        }
+
+        for file in vanished.into_values().flatten() {
+            match self.store.delete(user_id, &file.id) {
+                Ok(_) => self.emit(FileChange::Removed { path: file.path }),
+                Err(e) => tracing::warn!(path = %file.path, error = %e, "Failed to drop file"),
+            }
+        }
+    }
+
+    /// Bring one existing file's memory up to date
+    ///
+    /// A new file whose content matches a vanished one is taken as a rename.
+    fn sync_file(&self, path: &str, vanished: &mut HashMap<String, Vec<FileMemory>>) -> Result<()> {
+        let WatchTarget {
+            user_id,
+            project_id,
+            root,
+            config,
+        } = &self.target;
+        let existing = self.store.get_by_path(user_id, project_id, path)?;
+
+        if let Some(reason) = self.skip_reason(path) {
+            if let Some(existing) = existing {
+                tracing::debug!(path = %path, reason = %reason, "Dropping newly excluded file");
+                self.store.delete(user_id, &existing.id)?;
+                self.emit(FileChange::Removed {
+                    path: path.to_string(),
+                });
+            }
+            return Ok(());
+        }
+
+        // A failed read means it was deleted again; its own event follows
+        let Ok(content) = fs::read(root.join(path)) else {
+            return Ok(());
+        };
+        let hash = FileMemoryStore::hash_file_content(&content);
+
+        match existing {
+            Some(existing) if !existing.has_changed(&hash) => {}
+            Some(existing) => {
+                self.store.refresh_file(
+                    root,
+                    path,
+                    project_id,
+                    user_id,
+                    &content,
+                    Some(&existing),
+                )?;
+                self.emit(FileChange::Modified {
+                    path: path.to_string(),
+                });
+            }
+            None => {
+                if let Some(moved) = vanished.get_mut(&hash).and_then(|files| files.pop()) {
+                    let from = moved.path.clone();
+                    self.store.move_file(root, moved, path)?;
+                    self.emit(FileChange::Renamed {
+                        from,
+                        to: path.to_string(),
+                    });
+                } else if self.store.count_by_project(user_id, project_id)?
+                    >= config.max_files_per_project
+                {
+                    tracing::debug!(path = %path, "File limit reached, not indexing new file");
+                } else {
+                    self.store
+                        .refresh_file(root, path, project_id, user_id, &content, None)?;
+                    self.emit(FileChange::Added {
+                        path: path.to_string(),
+                    });
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn emit(&self, change: FileChange) {
+        tracing::debug!(change = ?change, "Codebase index changed");
+        (self.on_change)(&change);
+    }
+}
+
+fn join_relative(relative_dir: &str, name: &str) -> String {
+    if relative_dir.is_empty() {
+        name.to_string()
+    } else {
+        format!("{relative_dir}/{name}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn watch_project(
+        store: &Arc<FileMemoryStore>,
+        root: &Path,
+        force_polling: bool,
+    ) -> (CodebaseWatcher, Arc<Mutex<Vec<FileChange>>>) {
+        let changes = Arc::new(Mutex::new(Vec::new()));
+        let recorded = Arc::clone(&changes);
+        let watcher = CodebaseWatcher::start(
+            Arc::clone(store),
+            WatchTarget {
+                user_id: "test-user".to_string(),
+                project_id: ProjectId::new(),
+                root: root.to_path_buf(),
+                config: CodebaseConfig::default(),
+            },
+            WatchOptions {
+                debounce: Duration::from_millis(50),
+                poll_interval: Duration::from_millis(100),
+                force_polling,
+                initial_sync: true,
+            },
+            Arc::new(move |change: &FileChange| recorded.lock().push(change.clone())),
+        )
+        .unwrap();
+        (watcher, changes)
+    }
+
+    fn wait_for(changes: &Mutex<Vec<FileChange>>, expected: &FileChange) {
+        let deadline = Instant::now() + Duration::from_secs(10);
+        while Instant::now() < deadline {
+            if changes.lock().contains(expected) {
+                return;
+            }
+            std::thread::sleep(Duration::from_millis(20));
+        }
+        panic!(
+            "timed out waiting for {expected:?}; saw {:?}",
+            changes.lock()
+        );
+    }
+
+    fn check_watcher(force_polling: bool) {
+        let data = TempDir::new().unwrap();
+        let code = TempDir::new().unwrap();
+        let store = Arc::new(FileMemoryStore::new(data.path()).unwrap());
+        let root = code.path();
+        fs::create_dir(root.join("src")).unwrap();
+        fs::write(root.join("src/lib.rs"), "pub fn one() {}\n").unwrap();
+        fs::write(root.join(".gitignore"), "*.log\n").unwrap();
+
+        let (watcher, changes) = watch_project(&store, root, force_polling);
+        let added = |path: &str| FileChange::Added {
+            path: path.to_string(),
+        };
+        wait_for(&changes, &added("src/lib.rs"));
+        wait_for(&changes, &added(".gitignore"));
+        let project_id = ProjectId(watcher.status().project_id.parse().unwrap());
+        let original = store
+            .get_by_path("test-user", &project_id, "src/lib.rs")
+            .unwrap()
+            .unwrap();
+
+        // Let the initial events settle before modifying
+        std::thread::sleep(Duration::from_millis(50));
+        fs::write(
+            root.join("src/lib.rs"),
+            "pub fn one() {}\npub fn two() {}\n",
+        )
+        .unwrap();
+        fs::write(root.join("debug.log"), "ignored").unwrap();
+        wait_for(
+            &changes,
+            &FileChange::Modified {
+                path: "src/lib.rs".to_string(),
+            },
+        );
+        let updated = store
+            .get_by_path("test-user", &project_id, "src/lib.rs")
+            .unwrap()
+            .unwrap();
+        assert_eq!(updated.id, original.id);
+        assert!(updated.key_items.iter().any(|k| k.contains("two")));
+
+        fs::rename(root.join("src/lib.rs"), root.join("src/core.rs")).unwrap();
+        wait_for(
+            &changes,
+            &FileChange::Renamed {
+                from: "src/lib.rs".to_string(),
+                to: "src/core.rs".to_string(),
+            },
+        );
+        let renamed = store
+            .get_by_path("test-user", &project_id, "src/core.rs")
+            .unwrap()
+            .unwrap();
+        assert_eq!(renamed.id, original.id);
+
+        fs::remove_file(root.join("src/core.rs")).unwrap();
+        wait_for(
+            &changes,
+            &FileChange::Removed {
+                path: "src/core.rs".to_string(),
+            },
+        );
+        assert!(!changes.lock().contains(&added("debug.log")));
+        assert_eq!(store.count_by_project("test-user", &project_id).unwrap(), 1);
+    }
+
+    #[test]
+    fn test_polling_watcher_tracks_changes() {
+        check_watcher(true);
+    }
+
+    #[test]
+    fn test_native_watcher_tracks_changes() {
+        check_watcher(false);
+    }
+}
@@ -7,6 +7,7 @@
 //! - CRUD operations for FileMemory
 //! - Indexing by project, path, and file type
 //! - Semantic search via embeddings
+//! - Per-symbol embeddings for locating functions and types
 //! - Access tracking for heat maps
 
 use anyhow::{Context, Result};
@@ -18,11 +19,61 @@ use std::fs;
 use std::path::Path;
 use std::sync::Arc;
 
+use super::gitignore::GitIgnore;
+use super::symbols::{self, CodeSymbol, SymbolMatch};
 use super::types::{
     CodebaseConfig, CodebaseScanResult, FileMemory, FileMemoryId, FileType, IndexingProgress,
     LearnedFrom, ProjectId,
 };
 
+/// Share of a symbol's search score from embedding similarity (the rest is lexical)
+const SYMBOL_SEMANTIC_WEIGHT: f32 = 0.6;
+/// Symbols without a lexical match need at least this similarity to be returned
+const MIN_SYMBOL_SIMILARITY: f32 = 0.35;
+
+/// Commonly excluded directory names (checked explicitly for performance and reliability)
+const EXCLUDED_DIR_NAMES: &[&str] = &[
+    ".git",
+    ".svn",
+    ".hg",
+    ".bzr", // VCS
+    "node_modules",
+    "__pycache__",
+    ".venv", // Dependencies
+    "venv",
+    "env",
+    ".env",
+    "virtualenv", // More Python venvs
+    "site-packages",
+    "Lib",
+    "Scripts", // Python internals
+    "target",
+    "dist",
+    "build",
+    "out",
+    "bin", // Build outputs
+    ".idea",
+    ".vscode", // IDE
+    ".cache",
+    ".tmp",
+    "tmp", // Temp
+    "data",
+    "logs",
+    "coverage", // Runtime data
+    "release-test",
+    "test-wheel", // Test artifacts
+];
+
+/// Directory name patterns to skip (suffix matching)
+const EXCLUDED_DIR_SUFFIXES: &[&str] = &[
+    "_data",    // Any *_data directories (e.g., shodh_memory_data)
+    "_cache",   // Any *_cache directories
+    "_output",  // Any *_output directories
+    "_venv",    // Any *_venv directories
+    "_env",     // Any *_env directories
+    "_install", // Any *_install directories (test installs)
+];
+
 /// Storage and query engine for file memories
 pub struct FileMemoryStore {
     /// Main file memory storage: key = {user_id}:{file_id}
@@ -31,6 +82,8 @@ pub struct FileMemoryStore {
     index_db: Arc<DB>,
     /// Default configuration
     config: CodebaseConfig,
+    /// Embedder for file summaries; indexing skips embeddings when unset
+    embedder: Option<Arc<dyn crate::embeddings::Embedder>>,
 }
 
 impl FileMemoryStore {
@@ -61,6 +114,7 @@ impl FileMemoryStore {
             file_db,
             index_db,
             config: CodebaseConfig::default(),
+            embedder: None,
         })
     }
 
@@ -70,6 +124,17 @@ impl FileMemoryStore {
         self
     }
 
+    /// Default indexing configuration
+    pub fn config(&self) -> &CodebaseConfig {
+        &self.config
+    }
+
+    /// Embed file summaries with the given embedder during `index_codebase`
+    pub fn with_embedder(mut self, embedder: Arc<dyn crate::embeddings::Embedder>) -> Self {
+        self.embedder = Some(embedder);
+        self
+    }
+
     /// Flush all RocksDB databases to disk (critical for graceful shutdown)
     pub fn flush(&self) -> Result<()> {
         self.file_db
@@ -173,6 +238,8 @@ impl FileMemoryStore {
             let key = format!("{}:{}", user_id, file_id.0);
             self.file_db.delete(key.as_bytes())?;
             self.remove_indices(&file_memory)?;
+            self.index_db
+                .delete(Self::symbols_key(user_id, file_id).as_bytes())?;
 
             tracing::debug!(
                 file_id = %file_id,
@@ -369,12 +436,15 @@ impl FileMemoryStore {
     // =========================================================================
 
     /// Scan a directory and return eligible files for indexing
+    ///
+    /// Honours the codebase's `.gitignore` files in addition to `exclude_patterns`.
     pub fn scan_codebase(
         &self,
         codebase_path: &Path,
         config: Option<&CodebaseConfig>,
     ) -> Result<CodebaseScanResult> {
         let config = config.unwrap_or(&self.config);
+        let ignores = Self::load_gitignore(codebase_path);
         let mut result = CodebaseScanResult {
             total_files: 0,
             eligible_files: 0,
@@ -384,17 +454,13 @@ impl FileMemoryStore {
             file_paths: Vec::new(),
         };
 
-        // Compile exclude patterns
-        let exclude_patterns: Vec<Pattern> = config
-            .exclude_patterns
-            .iter()
-            .filter_map(|p| Pattern::new(p).ok())
-            .collect();
+        let exclude_patterns = Self::compile_exclude_patterns(config);
 
         self.scan_directory_recursive(
             codebase_path,
             codebase_path,
             &exclude_patterns,
+            &ignores,
             config,
             &mut result,
         )?;
@@ -411,11 +477,139 @@ impl FileMemoryStore {
         Ok(result)
     }
 
+    /// Load a codebase's `.gitignore` rules, skipping always-excluded directories
+    pub fn load_gitignore(codebase_path: &Path) -> GitIgnore {
+        GitIgnore::load(codebase_path, &|name| {
+            Self::excluded_dir_reason(name).is_some()
+        })
+    }
+
+    pub(crate) fn compile_exclude_patterns(config: &CodebaseConfig) -> Vec<Pattern> {
+        config
+            .exclude_patterns
+            .iter()
+            .filter_map(|p| Pattern::new(p).ok())
+            .collect()
+    }
+
+    /// Skip reason for a commonly excluded directory name, if it is one
+    pub(crate) fn excluded_dir_reason(file_name: &str) -> Option<String> {
+        // Exact match exclusion
+        if EXCLUDED_DIR_NAMES.contains(&file_name) {
+            return Some(format!("{}/", file_name));
+        }
+        // Suffix pattern exclusion (e.g., *_data, *_cache)
+        if EXCLUDED_DIR_SUFFIXES
+            .iter()
+            .any(|&suffix| file_name.ends_with(suffix))
+        {
+            return Some(format!(
+                "*{}/",
+                file_name.rsplit_once('_').map_or(file_name, |(_, s)| s)
+            ));
+        }
+        None
+    }
+
+    /// Skip reason for one directory entry, checked by name and relative path
+    ///
+    /// Covers excluded directory names, `exclude_patterns` and `.gitignore`;
+    /// binary and size checks for files are separate.
+    pub(crate) fn entry_skip_reason(
+        relative_path: &str,
+        file_name: &str,
+        is_dir: bool,
+        exclude_patterns: &[Pattern],
+        ignores: &GitIgnore,
+    ) -> Option<String> {
+        // Quick check: skip commonly excluded directories by name
+        if is_dir {
+            if let Some(reason) = Self::excluded_dir_reason(file_name) {
+                return Some(reason);
+            }
+        }
+
+        // Check exclude patterns (for custom patterns and file patterns like *.lock)
+        for pattern in exclude_patterns {
+            // For directory patterns (ending with /), check if relative path starts with it
+            let pattern_str = pattern.as_str();
+            if pattern_str.ends_with('/') {
+                let dir_name = pattern_str.trim_end_matches('/');
+                if relative_path == dir_name || relative_path.starts_with(&format!("{}/", dir_name))
+                {
+                    return Some(pattern.to_string());
+                }
+            } else if pattern.matches(relative_path) || pattern.matches(file_name) {
+                return Some(pattern.to_string());
+            }
+        }
+
+        if ignores.is_ignored(relative_path, is_dir) {
+            return Some(".gitignore".to_string());
+        }
+
+        None
+    }
+
+    /// Skip reason for a file anywhere in the codebase, if it shouldn't be indexed
+    ///
+    /// Applies the same rules as [`scan_codebase`](Self::scan_codebase) to the
+    /// file and each of its parent directories, so single changed files can be
+    /// checked without a rescan. Returns `None` for an eligible file.
+    pub fn file_skip_reason(
+        codebase_root: &Path,
+        relative_path: &str,
+        config: &CodebaseConfig,
+        exclude_patterns: &[Pattern],
+        ignores: &GitIgnore,
+    ) -> Option<String> {
+        let mut components: Vec<&str> = relative_path.split('/').collect();
+        let file_name = components.pop().unwrap_or(relative_path);
+
+        let mut ancestor_end = 0;
+        for dir_name in components {
+            ancestor_end += dir_name.len();
+            if let Some(reason) = Self::entry_skip_reason(
+                &relative_path[..ancestor_end],
+                dir_name,
+                true,
+                exclude_patterns,
+                ignores,
+            ) {
+                return Some(reason);
+            }
+            ancestor_end += 1;
+        }
+
+        if let Some(reason) =
+            Self::entry_skip_reason(relative_path, file_name, false, exclude_patterns, ignores)
+        {
+            return Some(reason);
+        }
+
+        let path = codebase_root.join(relative_path);
+        Self::file_content_skip_reason(&path, config)
+    }
+
+    /// Binary and size checks for an eligible-by-name file
+    fn file_content_skip_reason(path: &Path, config: &CodebaseConfig) -> Option<String> {
+        if config.skip_binary && Self::is_likely_binary(path) {
+            return Some("binary".to_string());
+        }
+        if let Ok(metadata) = path.metadata() {
+            if metadata.len() > config.max_file_size_for_embedding as u64 {
+                return Some("too_large".to_string());
+            }
+        }
+        None
+    }
+
     fn scan_directory_recursive(
         &self,
         root: &Path,
         current: &Path,
         exclude_patterns: &[Pattern],
+        ignores: &GitIgnore,
         config: &CodebaseConfig,
         result: &mut CodebaseScanResult,
     ) -> Result<()> {
@@ -431,49 +625,6 @@ impl FileMemoryStore {
             }
         };
 
-        // Commonly excluded directory names (checked explicitly for performance and reliability)
-        const EXCLUDED_DIR_NAMES: &[&str] = &[
-            ".git",
-            ".svn",
-            ".hg",
-            ".bzr", // VCS
-            "node_modules",
-            "__pycache__",
-            ".venv", // Dependencies
-            "venv",
-            "env",
-            ".env",
-            "virtualenv", // More Python venvs
-            "site-packages",
-            "Lib",
-            "Scripts", // Python internals
-            "target",
-            "dist",
-            "build",
-            "out",
-            "bin", // Build outputs
-            ".idea",
-            ".vscode", // IDE
-            ".cache",
-            ".tmp",
-            "tmp", // Temp
-            "data",
-            "logs",
-            "coverage", // Runtime data
-            "release-test",
-            "test-wheel", // Test artifacts
-        ];
-
-        // Directory name patterns to skip (suffix matching)
-        const EXCLUDED_DIR_SUFFIXES: &[&str] = &[
-            "_data",    // Any *_data directories (e.g., shodh_memory_data)
-            "_cache",   // Any *_cache directories
-            "_output",  // Any *_output directories
-            "_venv",    // Any *_venv directories
-            "_env",     // Any *_env directories
-            "_install", // Any *_install directories (test installs)
-        ];
-
         for entry in entries {
             let entry = match entry {
                 Ok(e) => e,
@@ -483,36 +634,7 @@ impl FileMemoryStore {
             let path = entry.path();
             let file_name = entry.file_name();
             let file_name_str = file_name.to_string_lossy();
-
-            // Quick check: skip commonly excluded directories by name
-            if path.is_dir() {
-                // Exact match exclusion
-                if EXCLUDED_DIR_NAMES.iter().any(|&name| file_name_str == name) {
-                    *result
-                        .skip_reasons
-                        .entry(format!("{}/", file_name_str))
-                        .or_insert(0) += 1;
-                    result.skipped_files += 1;
-                    continue;
-                }
-                // Suffix pattern exclusion (e.g., *_data, *_cache)
-                if EXCLUDED_DIR_SUFFIXES
-                    .iter()
-                    .any(|&suffix| file_name_str.ends_with(suffix))
-                {
-                    *result
-                        .skip_reasons
-                        .entry(format!(
-                            "*{}/",
-                            file_name_str
-                                .rsplit_once('_')
-                                .map_or(&file_name_str[..], |(_, s)| s)
-                        ))
-                        .or_insert(0) += 1;
-                    result.skipped_files += 1;
-                    continue;
-                }
-            }
+            let is_dir = path.is_dir();
 
             let relative_path = path
                 .strip_prefix(root)
@@ -520,57 +642,38 @@ impl FileMemoryStore {
                 .to_string_lossy()
                 .replace('\\', "/");
 
-            // Check exclude patterns (for custom patterns and file patterns like *.lock)
-            let mut excluded = false;
-            for pattern in exclude_patterns {
-                // For directory patterns (ending with /), check if relative path starts with it
-                let pattern_str = pattern.as_str();
-                if pattern_str.ends_with('/') {
-                    let dir_name = pattern_str.trim_end_matches('/');
-                    if relative_path == dir_name
-                        || relative_path.starts_with(&format!("{}/", dir_name))
-                    {
-                        *result.skip_reasons.entry(pattern.to_string()).or_insert(0) += 1;
-                        excluded = true;
-                        break;
-                    }
-                } else if pattern.matches(&relative_path) || pattern.matches(&file_name_str) {
-                    *result.skip_reasons.entry(pattern.to_string()).or_insert(0) += 1;
-                    excluded = true;
-                    break;
-                }
-            }
-
-            if excluded {
+            if let Some(reason) = Self::entry_skip_reason(
+                &relative_path,
+                &file_name_str,
+                is_dir,
+                exclude_patterns,
+                ignores,
+            ) {
+                *result.skip_reasons.entry(reason).or_insert(0) += 1;
                 result.skipped_files += 1;
                 continue;
             }
 
-            if path.is_dir() {
+            if is_dir {
                 // Recurse into directory
-                self.scan_directory_recursive(root, &path, exclude_patterns, config, result)?;
+                self.scan_directory_recursive(
+                    root,
+                    &path,
+                    exclude_patterns,
+                    ignores,
+                    config,
+                    result,
+                )?;
             } else if path.is_file() {
                 result.total_files += 1;
 
-                // Check if binary
-                if config.skip_binary && Self::is_likely_binary(&path) {
-                    *result.skip_reasons.entry("binary".to_string()).or_insert(0) += 1;
+                // Check if binary or too large
+                if let Some(reason) = Self::file_content_skip_reason(&path, config) {
+                    *result.skip_reasons.entry(reason).or_insert(0) += 1;
                     result.skipped_files += 1;
                     continue;
                 }
 
-                // Check file size
-                if let Ok(metadata) = path.metadata() {
-                    if metadata.len() > config.max_file_size_for_embedding as u64 {
-                        *result
-                            .skip_reasons
-                            .entry("too_large".to_string())
-                            .or_insert(0) += 1;
-                        result.skipped_files += 1;
-                        continue;
-                    }
-                }
-
                 // File is eligible
                 result.eligible_files += 1;
                 result.file_paths.push(relative_path);
@@ -635,11 +738,30 @@ impl FileMemoryStore {
         let content = fs::read(&absolute_path)
             .with_context(|| format!("Failed to read file: {}", absolute_path.display()))?;
 
-        let file_hash = Self::hash_file_content(&content);
+        let (file_memory, symbols) =
+            Self::build_file_memory(codebase_root, relative_path, project_id, user_id, &content);
+
+        // Store it
+        self.store(&file_memory)?;
+        self.store_symbols(&file_memory, &symbols)?;
+
+        Ok(file_memory)
+    }
+
+    /// Build a FileMemory from file content: hash, count lines, detect type, extract symbols
+    fn build_file_memory(
+        codebase_root: &Path,
+        relative_path: &str,
+        project_id: &ProjectId,
+        user_id: &str,
+        content: &[u8],
+    ) -> (FileMemory, Vec<CodeSymbol>) {
+        let absolute_path = codebase_root.join(relative_path);
+        let file_hash = Self::hash_file_content(content);
         let size_bytes = content.len() as u64;
 
         // Count lines (for text files)
-        let content_str = String::from_utf8_lossy(&content);
+        let content_str = String::from_utf8_lossy(content);
         let line_count = content_str.lines().count();
 
         // Detect file type from extension
@@ -649,8 +771,8 @@ impl FileMemoryStore {
             .map(FileType::from_extension)
             .unwrap_or_default();
 
-        // Extract key items (functions, classes, etc.)
-        let key_items = Self::extract_key_items(&content_str, &file_type);
+        // Extract symbols (functions, types, methods, etc.)
+        let symbols = symbols::extract_symbols(&content_str, &file_type);
 
         // Create FileMemory
         let mut file_memory = FileMemory::new(
@@ -664,16 +786,12 @@ impl FileMemoryStore {
             size_bytes,
         );
 
-        file_memory.key_items = key_items;
-
-        // Store it
-        self.store(&file_memory)?;
-
-        Ok(file_memory)
+        file_memory.key_items = symbols::key_items(&symbols);
+        (file_memory, symbols)
     }
 
     /// Index a single file and generate embedding
-    pub fn index_file_with_embedding<E: crate::embeddings::Embedder>(
+    pub fn index_file_with_embedding<E: crate::embeddings::Embedder + ?Sized>(
         &self,
         codebase_root: &Path,
         relative_path: &str,
@@ -681,7 +799,12 @@ impl FileMemoryStore {
         user_id: &str,
         embedder: &E,
     ) -> Result<FileMemory> {
-        let mut file_memory = self.index_file(codebase_root, relative_path, project_id, user_id)?;
+        let absolute_path = codebase_root.join(relative_path);
+        let content = fs::read(&absolute_path)
+            .with_context(|| format!("Failed to read file: {}", absolute_path.display()))?;
+
+        let (mut file_memory, mut symbols) =
+            Self::build_file_memory(codebase_root, relative_path, project_id, user_id, &content);
 
         // Generate embedding from summary content
         let embed_content = Self::prepare_embed_content(&file_memory);
@@ -689,7 +812,6 @@ impl FileMemoryStore {
             match embedder.encode(&embed_content) {
                 Ok(embedding) => {
                     file_memory.embedding = Some(embedding);
-                    self.update(&file_memory)?;
                 }
                 Err(e) => {
                     tracing::warn!(
@@ -700,6 +822,10 @@ impl FileMemoryStore {
                 }
             }
         }
+        Self::embed_symbols(&file_memory.path, &mut symbols, embedder);
+
+        self.store(&file_memory)?;
+        self.store_symbols(&file_memory, &symbols)?;
 
         Ok(file_memory)
     }
@@ -729,193 +855,9 @@ impl FileMemoryStore {
         parts.join(" | ")
     }
 
-    /// Extract key items from file content based on file type
-    fn extract_key_items(content: &str, file_type: &FileType) -> Vec<String> {
-        let mut items = Vec::new();
-
-        match file_type {
-            FileType::Rust => {
-                // Extract pub fn, pub struct, pub enum, pub trait, impl
-                for line in content.lines() {
-                    let trimmed = line.trim();
-                    if trimmed.starts_with("pub fn ")
-                        || trimmed.starts_with("pub async fn ")
-                        || trimmed.starts_with("pub struct ")
-                        || trimmed.starts_with("pub enum ")
-                        || trimmed.starts_with("pub trait ")
-                        || trimmed.starts_with("impl ")
-                    {
-                        // Extract the name
-                        if let Some(name) = Self::extract_rust_name(trimmed) {
-                            if !items.contains(&name) {
-                                items.push(name);
-                            }
-                        }
-                    }
-                }
-            }
-            FileType::TypeScript | FileType::JavaScript => {
-                // Extract export function, export class, export const, export interface
-                for line in content.lines() {
-                    let trimmed = line.trim();
-                    if trimmed.starts_with("export ")
-                        || trimmed.starts_with("function ")
-                        || trimmed.starts_with("class ")
-                        || trimmed.starts_with("interface ")
-                        || trimmed.starts_with("const ")
-                    {
-                        if let Some(name) = Self::extract_js_name(trimmed) {
-                            if !items.contains(&name) {
-                                items.push(name);
-                            }
-                        }
-                    }
-                }
-            }
-            FileType::Python => {
-                // Extract def, class, async def
-                for line in content.lines() {
-                    let trimmed = line.trim();
-                    if trimmed.starts_with("def ")
-                        || trimmed.starts_with("async def ")
-                        || trimmed.starts_with("class ")
-                    {
-                        if let Some(name) = Self::extract_python_name(trimmed) {
-                            if !items.contains(&name) {
-                                items.push(name);
-                            }
-                        }
-                    }
-                }
-            }
-            FileType::Go => {
-                // Extract func, type struct, type interface
-                for line in content.lines() {
-                    let trimmed = line.trim();
-                    if trimmed.starts_with("func ") || trimmed.starts_with("type ") {
-                        if let Some(name) = Self::extract_go_name(trimmed) {
-                            if !items.contains(&name) {
-                                items.push(name);
-                            }
-                        }
-                    }
-                }
-            }
-            _ => {
-                // For other types, just count significant lines
-                // Could add more extractors later
-            }
-        }
-
-        // Limit to prevent bloat
-        items.truncate(50);
-        items
-    }
-
-    fn extract_rust_name(line: &str) -> Option<String> {
-        // "pub fn foo(" -> "foo"
-        // "pub struct Bar {" -> "Bar"
-        // "impl Foo for Bar {" -> "Foo for Bar"
-        let line = line.trim_start_matches("pub ").trim_start_matches("async ");
-
-        if line.starts_with("fn ") {
-            let rest = line.strip_prefix("fn ")?;
-            let name = rest.split(|c| c == '(' || c == '<').next()?;
-            Some(name.trim().to_string())
-        } else if line.starts_with("struct ") {
-            let rest = line.strip_prefix("struct ")?;
-            let name = rest.split(|c| c == '{' || c == '<' || c == '(').next()?;
-            Some(name.trim().to_string())
-        } else if line.starts_with("enum ") {
-            let rest = line.strip_prefix("enum ")?;
-            let name = rest.split(|c| c == '{' || c == '<').next()?;
-            Some(name.trim().to_string())
-        } else if line.starts_with("trait ") {
-            let rest = line.strip_prefix("trait ")?;
-            let name = rest.split(|c| c == '{' || c == '<' || c == ':').next()?;
-            Some(name.trim().to_string())
-        } else if line.starts_with("impl ") {
-            let rest = line.strip_prefix("impl ")?;
-            let sig = rest.split('{').next()?;
-            Some(sig.trim().to_string())
-        } else {
-            None
-        }
-    }
-
-    fn extract_js_name(line: &str) -> Option<String> {
-        // "export function foo(" -> "foo"
-        // "export class Bar {" -> "Bar"
-        let line = line
-            .trim_start_matches("export ")
-            .trim_start_matches("default ")
-            .trim_start_matches("async ");
-
-        if line.starts_with("function ") {
-            let rest = line.strip_prefix("function ")?;
-            let name = rest.split('(').next()?;
-            Some(name.trim().to_string())
-        } else if line.starts_with("class ") {
-            let rest = line.strip_prefix("class ")?;
-            let name = rest.split(|c| c == '{' || c == ' ').next()?;
-            Some(name.trim().to_string())
-        } else if line.starts_with("interface ") {
-            let rest = line.strip_prefix("interface ")?;
-            let name = rest.split(|c| c == '{' || c == ' ' || c == '<').next()?;
-            Some(name.trim().to_string())
-        } else if line.starts_with("const ") {
-            let rest = line.strip_prefix("const ")?;
-            let name = rest.split(|c| c == '=' || c == ':').next()?;
-            Some(name.trim().to_string())
-        } else {
-            None
-        }
-    }
-
-    fn extract_python_name(line: &str) -> Option<String> {
-        // "def foo(" -> "foo"
-        // "class Bar:" -> "Bar"
-        let line = line.trim_start_matches("async ");
-
-        if line.starts_with("def ") {
-            let rest = line.strip_prefix("def ")?;
-            let name = rest.split('(').next()?;
-            Some(name.trim().to_string())
-        } else if line.starts_with("class ") {
-            let rest = line.strip_prefix("class ")?;
-            let name = rest.split(|c| c == '(' || c == ':').next()?;
-            Some(name.trim().to_string())
-        } else {
-            None
-        }
-    }
-
-    fn extract_go_name(line: &str) -> Option<String> {
-        // "func Foo(" -> "Foo"
-        // "func (r *Receiver) Method(" -> "Method"
-        // "type Bar struct" -> "Bar"
-        if line.starts_with("func ") {
-            let rest = line.strip_prefix("func ")?;
-            // Handle method receivers: func (r *Type) Name(
-            if rest.starts_with('(') {
-                // Skip receiver, find method name
-                let after_receiver = rest.split(')').nth(1)?;
-                let name = after_receiver.trim().split('(').next()?;
-                Some(name.trim().to_string())
-            } else {
-                let name = rest.split('(').next()?;
-                Some(name.trim().to_string())
-            }
-        } else if line.starts_with("type ") {
-            let rest = line.strip_prefix("type ")?;
-            let name = rest.split_whitespace().next()?;
-            Some(name.trim().to_string())
-        } else {
-            None
-        }
-    }
-
     /// Index all files in a codebase (blocking version)
+    ///
+    /// Generates embeddings when the store was built `with_embedder`.
     pub fn index_codebase(
         &self,
         codebase_root: &Path,
@@ -923,6 +865,17 @@ impl FileMemoryStore {
         user_id: &str,
         config: Option<&CodebaseConfig>,
     ) -> Result<IndexingResult> {
+        if let Some(embedder) = &self.embedder {
+            return self.index_codebase_with_embeddings(
+                codebase_root,
+                project_id,
+                user_id,
+                embedder.as_ref(),
+                config,
+                None,
+            );
+        }
+
         // First scan to get eligible files
         let scan_result = self.scan_codebase(codebase_root, config)?;
 
@@ -958,7 +911,7 @@ impl FileMemoryStore {
     }
 
     /// Index codebase with embeddings (requires embedder)
-    pub fn index_codebase_with_embeddings<E: crate::embeddings::Embedder>(
+    pub fn index_codebase_with_embeddings<E: crate::embeddings::Embedder + ?Sized>(
         &self,
         codebase_root: &Path,
         project_id: &ProjectId,
@@ -1023,6 +976,227 @@ impl FileMemoryStore {
         Ok(result)
     }
 
+    // =========================================================================
+    // INCREMENTAL REINDEXING
+    // =========================================================================
+
+    /// Re-index a file from already-read content
+    ///
+    /// When `existing` is the file's current memory, its id and access history
+    /// are kept so links and heat scores survive edits. Callers compare hashes
+    /// with [`FileMemory::has_changed`] first; this always re-embeds.
+    pub fn refresh_file(
+        &self,
+        codebase_root: &Path,
+        relative_path: &str,
+        project_id: &ProjectId,
+        user_id: &str,
+        content: &[u8],
+        existing: Option<&FileMemory>,
+    ) -> Result<FileMemory> {
+        let (mut file_memory, mut symbols) =
+            Self::build_file_memory(codebase_root, relative_path, project_id, user_id, content);
+
+        if let Some(existing) = existing {
+            file_memory.id = existing.id.clone();
+            file_memory.purpose = existing.purpose.clone();
+            file_memory.access_count = existing.access_count;
+            file_memory.last_accessed = existing.last_accessed;
+            file_memory.created_at = existing.created_at;
+            file_memory.learned_from = existing.learned_from.clone();
+        }
+
+        self.embed_file(&mut file_memory);
+        if let Some(embedder) = &self.embedder {
+            Self::embed_symbols(&file_memory.path, &mut symbols, embedder.as_ref());
+        }
+        if existing.is_some() {
+            self.update(&file_memory)?;
+        } else {
+            self.store(&file_memory)?;
+        }
+        self.store_symbols(&file_memory, &symbols)?;
+
+        Ok(file_memory)
+    }
+
+    /// Move a file memory to a new path (file renamed with unchanged content)
+    pub fn move_file(
+        &self,
+        codebase_root: &Path,
+        mut file_memory: FileMemory,
+        new_relative_path: &str,
+    ) -> Result<FileMemory> {
+        let absolute_path = codebase_root.join(new_relative_path);
+        file_memory.file_type = absolute_path
+            .extension()
+            .and_then(|e| e.to_str())
+            .map(FileType::from_extension)
+            .unwrap_or_default();
+        file_memory.path = new_relative_path.to_string();
+        file_memory.absolute_path = absolute_path.to_string_lossy().to_string();
+        file_memory.updated_at = chrono::Utc::now();
+
+        // The path is part of the embedded summary
+        self.embed_file(&mut file_memory);
+        self.update(&file_memory)?;
+
+        Ok(file_memory)
+    }
+
+    /// The memory for a file, or the memories of every file under a directory
+    pub fn files_at_path(
+        &self,
+        user_id: &str,
+        project_id: &ProjectId,
+        relative_path: &str,
+    ) -> Result<Vec<FileMemory>> {
+        if let Some(file) = self.get_by_path(user_id, project_id, relative_path)? {
+            return Ok(vec![file]);
+        }
+        let dir_prefix = format!("{relative_path}/");
+        Ok(self
+            .list_by_project(user_id, project_id, None)?
+            .into_iter()
+            .filter(|f| f.path.starts_with(&dir_prefix))
+            .collect())
+    }
+
+    /// Embed a file's summary with the store's embedder, if it has one
+    fn embed_file(&self, file_memory: &mut FileMemory) {
+        let Some(embedder) = &self.embedder else {
+            return;
+        };
+        let embed_content = Self::prepare_embed_content(file_memory);
+        if embed_content.is_empty() {
+            return;
+        }
+        match embedder.encode(&embed_content) {
+            Ok(embedding) => file_memory.embedding = Some(embedding),
+            Err(e) => {
+                tracing::warn!(
+                    path = %file_memory.path,
+                    error = %e,
+                    "Failed to generate embedding for file"
+                );
+            }
+        }
+    }
+
+    // =========================================================================
+    // SYMBOLS
+    // =========================================================================
+
+    fn symbols_key(user_id: &str, file_id: &FileMemoryId) -> String {
+        format!("symbols:{}:{}", user_id, file_id.0)
+    }
+
+    /// Replace a file's symbols
+    ///
+    /// Kept apart from the file memory so listing files doesn't load every
+    /// symbol embedding.
+    fn store_symbols(&self, file: &FileMemory, symbols: &[CodeSymbol]) -> Result<()> {
+        let key = Self::symbols_key(&file.user_id, &file.id);
+        let value = bincode::serde::encode_to_vec(symbols, bincode::config::standard())
+            .context("Failed to serialize file symbols")?;
+        self.index_db
+            .put(key.as_bytes(), &value)
+            .context("Failed to store file symbols")?;
+        Ok(())
+    }
+
+    /// Symbols declared in a file (empty for files indexed before symbol extraction)
+    pub fn get_symbols(&self, user_id: &str, file_id: &FileMemoryId) -> Result<Vec<CodeSymbol>> {
+        let key = Self::symbols_key(user_id, file_id);
+        match self.index_db.get(key.as_bytes())? {
+            Some(value) => {
+                let (symbols, _): (Vec<CodeSymbol>, _) =
+                    bincode::serde::decode_from_slice(&value, bincode::config::standard())
+                        .context("Failed to deserialize file symbols")?;
+                Ok(symbols)
+            }
+            None => Ok(Vec::new()),
+        }
+    }
+
+    /// Embed each symbol's signature and doc; symbols keep no embedding on failure
+    fn embed_symbols<E: crate::embeddings::Embedder + ?Sized>(
+        path: &str,
+        symbols: &mut [CodeSymbol],
+        embedder: &E,
+    ) {
+        if symbols.is_empty() {
+            return;
+        }
+        let texts: Vec<String> = symbols.iter().map(CodeSymbol::embed_text).collect();
+        let text_refs: Vec<&str> = texts.iter().map(String::as_str).collect();
+        match embedder.encode_batch(&text_refs) {
+            Ok(embeddings) => {
+                for (symbol, embedding) in symbols.iter_mut().zip(embeddings) {
+                    symbol.embedding = Some(embedding);
+                }
+            }
+            Err(e) => {
+                tracing::warn!(
+                    path = %path,
+                    error = %e,
+                    "Failed to generate embeddings for file symbols"
+                );
+            }
+        }
+    }
+
+    /// Find symbols in a project by name, signature, doc or meaning
+    ///
+    /// Lexical matches always count; with an embedder, symbols are also
+    /// ranked by similarity to the query.
+    pub fn search_symbols(
+        &self,
+        user_id: &str,
+        project_id: &ProjectId,
+        query: &str,
+        limit: usize,
+    ) -> Result<Vec<SymbolMatch>> {
+        let query_embedding = self.embedder.as_ref().and_then(|embedder| {
+            embedder
+                .encode(query)
+                .map_err(|e| tracing::warn!(error = %e, "Failed to embed symbol query"))
+                .ok()
+        });
+        let query_lower = query.to_lowercase();
+
+        let mut matches = Vec::new();
+        for file in self.list_by_project(user_id, project_id, None)? {
+            for symbol in self.get_symbols(user_id, &file.id)? {
+                let lexical = symbol.lexical_score(&query_lower);
+                let semantic = match (&query_embedding, &symbol.embedding) {
+                    (Some(query), Some(embedding)) => {
+                        Some(crate::similarity::cosine_similarity(query, embedding).max(0.0))
+                    }
+                    _ => None,
+                };
+                let score = match semantic {
+                    Some(semantic) => {
+                        SYMBOL_SEMANTIC_WEIGHT * semantic + (1.0 - SYMBOL_SEMANTIC_WEIGHT) * lexical
+                    }
+                    None => lexical,
+                };
+                if lexical > 0.0 || semantic.is_some_and(|s| s >= MIN_SYMBOL_SIMILARITY) {
+                    matches.push(SymbolMatch::new(
+                        file.id.0.to_string(),
+                        file.path.clone(),
+                        symbol,
+                        score,
+                    ));
+                }
+            }
+        }
+
+        matches.sort_by(|a, b| b.score.total_cmp(&a.score));
+        matches.truncate(limit);
+        Ok(matches)
+    }
+
     // =========================================================================
     // INDEX MANAGEMENT
     // =========================================================================
@@ -1284,6 +1458,38 @@ mod tests {
         assert!(retrieved.is_none());
     }
 
+    #[test]
+    fn test_symbol_search() {
+        let (store, _dir) = create_test_store();
+        let codebase = TempDir::new().unwrap();
+        fs::write(
+            codebase.path().join("config.rs"),
+            "pub struct Config;\n\nimpl Config {\n    /// Read settings from disk\n    fn load(path: &str) -> Self {\n        Config\n    }\n}\n",
+        )
+        .unwrap();
+
+        let project_id = ProjectId::new();
+        let file = store
+            .index_file(codebase.path(), "config.rs", &project_id, "test-user")
+            .unwrap();
+        assert_eq!(file.key_items, vec!["Config", "load"]);
+
+        let matches = store
+            .search_symbols("test-user", &project_id, "Config::load", 10)
+            .unwrap();
+        let best = &matches[0];
+        assert_eq!(
+            (best.name.as_str(), best.path.as_str()),
+            ("load", "config.rs")
+        );
+        assert_eq!((best.start_line, best.end_line), (5, 7));
+        assert_eq!(best.doc.as_deref(), Some("Read settings from disk"));
+
+        // Symbols go with the file
+        store.delete("test-user", &file.id).unwrap();
+        assert!(store.get_symbols("test-user", &file.id).unwrap().is_empty());
+    }
+
     #[test]
     fn test_file_type_detection() {
         assert_eq!(FileType::from_extension("rs"), FileType::Rust);
@@ -0,0 +1,212 @@
+//! `.gitignore` Matching for Codebase Indexing
+//!
+//! A small matcher for the subset of gitignore syntax codebases rely on:
+//! comments, negation (`!`), directory-only rules (trailing `/`), anchored
+//! rules (containing `/`), `*`/`?`/`[..]` wildcards and `**`. Nested
+//! `.gitignore` files apply to their own subtree, and `.git/info/exclude`
+//! applies to the whole repository.
+
+use glob::{MatchOptions, Pattern};
+use std::fs;
+use std::path::Path;
+
+const MATCH_OPTIONS: MatchOptions = MatchOptions {
+    case_sensitive: true,
+    require_literal_separator: true,
+    require_literal_leading_dot: false,
+};
+
+#[derive(Debug, Clone)]
+struct IgnoreRule {
+    pattern: Pattern,
+    /// `!pattern` re-includes a previously ignored path
+    negated: bool,
+    /// `pattern/` only matches directories
+    dir_only: bool,
+    /// Patterns containing `/` match the path relative to `base`; others
+    /// match the file name at any depth
+    anchored: bool,
+    /// Directory (relative to the root) of the `.gitignore` defining this rule
+    base: String,
+}
+
+impl IgnoreRule {
+    fn parse(line: &str, base: &str) -> Option<Self> {
+        let line = line.trim_end();
+        if line.is_empty() || line.starts_with('#') {
+            return None;
+        }
+
+        let (negated, line) = match line.strip_prefix('!') {
+            Some(rest) => (true, rest),
+            None => (false, line.strip_prefix('\\').unwrap_or(line)),
+        };
+        let (dir_only, line) = match line.strip_suffix('/') {
+            Some(rest) => (true, rest),
+            None => (false, line),
+        };
+        let anchored = line.contains('/');
+        let line = line.trim_start_matches('/');
+        if line.is_empty() {
+            return None;
+        }
+
+        Some(Self {
+            pattern: Pattern::new(line).ok()?,
+            negated,
+            dir_only,
+            anchored,
+            base: base.to_string(),
+        })
+    }
+
+    fn matches(&self, relative_path: &str, is_dir: bool) -> bool {
+        if self.dir_only && !is_dir {
+            return false;
+        }
+        let local = if self.base.is_empty() {
+            relative_path
+        } else {
+            match relative_path
+                .strip_prefix(self.base.as_str())
+                .and_then(|rest| rest.strip_prefix('/'))
+            {
+                Some(rest) => rest,
+                None => return false,
+            }
+        };
+        if self.anchored {
+            self.pattern.matches_with(local, MATCH_OPTIONS)
+        } else {
+            let name = local.rsplit('/').next().unwrap_or(local);
+            self.pattern.matches_with(name, MATCH_OPTIONS)
+        }
+    }
+}
+
+/// Ignore rules collected from a codebase's `.gitignore` files
+#[derive(Debug, Clone, Default)]
+pub struct GitIgnore {
+    rules: Vec<IgnoreRule>,
+}
+
+impl GitIgnore {
+    /// Load every `.gitignore` under `root`, plus `.git/info/exclude`
+    ///
+    /// Directories rejected by `skip_dir` (given the directory name) or ignored
+    /// by rules already loaded are not descended into.
+    pub fn load(root: &Path, skip_dir: &dyn Fn(&str) -> bool) -> Self {
+        let mut ignores = Self::default();
+        ignores.add_file(&root.join(".git").join("info").join("exclude"), "");
+        ignores.load_dir(root, "", skip_dir);
+        ignores
+    }
+
+    fn load_dir(&mut self, dir: &Path, relative_dir: &str, skip_dir: &dyn Fn(&str) -> bool) {
+        self.add_file(&dir.join(".gitignore"), relative_dir);
+
+        let Ok(entries) = fs::read_dir(dir) else {
+            return;
+        };
+        for entry in entries.flatten() {
+            if !entry.file_type().is_ok_and(|t| t.is_dir()) {
+                continue;
+            }
+            let name = entry.file_name().to_string_lossy().to_string();
+            if name == ".git" || skip_dir(&name) {
+                continue;
+            }
+            let relative = if relative_dir.is_empty() {
+                name
+            } else {
+                format!("{relative_dir}/{name}")
+            };
+            if !self.is_ignored(&relative, true) {
+                self.load_dir(&entry.path(), &relative, skip_dir);
+            }
+        }
+    }
+
+    /// Add the rules of one ignore file, scoped to `relative_dir`
+    pub fn add_file(&mut self, path: &Path, relative_dir: &str) {
+        if let Ok(content) = fs::read_to_string(path) {
+            self.add_rules(&content, relative_dir);
+        }
+    }
+
+    /// Add rules in gitignore syntax, scoped to `relative_dir`
+    pub fn add_rules(&mut self, content: &str, relative_dir: &str) {
+        self.rules.extend(
+            content
+                .lines()
+                .filter_map(|line| IgnoreRule::parse(line, relative_dir)),
+        );
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// Whether a path (relative to the root, `/`-separated) is ignored
+    ///
+    /// A path inside an ignored directory is ignored too; as in git, a
+    /// negation can't re-include it.
+    pub fn is_ignored(&self, relative_path: &str, is_dir: bool) -> bool {
+        if self.rules.is_empty() {
+            return false;
+        }
+        let mut ancestor_end = 0;
+        while let Some(offset) = relative_path[ancestor_end..].find('/') {
+            ancestor_end += offset;
+            if self.matches(&relative_path[..ancestor_end], true) {
+                return true;
+            }
+            ancestor_end += 1;
+        }
+        self.matches(relative_path, is_dir)
+    }
+
+    /// Last matching rule wins
+    fn matches(&self, relative_path: &str, is_dir: bool) -> bool {
+        self.rules
+            .iter()
+            .rev()
+            .find(|rule| rule.matches(relative_path, is_dir))
+            .is_some_and(|rule| !rule.negated)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gitignore_rules() {
+        let mut ignores = GitIgnore::default();
+        ignores.add_rules(
+            "# build output\n*.log\n!keep.log\n/generated\ncache/\ndocs/**/*.pdf\n",
+            "",
+        );
+        ignores.add_rules("fixtures.json\n", "tests");
+
+        assert!(ignores.is_ignored("debug.log", false));
+        assert!(ignores.is_ignored("src/nested/trace.log", false));
+        assert!(!ignores.is_ignored("keep.log", false));
+
+        // Anchored to the root
+        assert!(ignores.is_ignored("generated", true));
+        assert!(ignores.is_ignored("generated/schema.rs", false));
+        assert!(!ignores.is_ignored("src/generated", true));
+
+        // Directory-only
+        assert!(ignores.is_ignored("src/cache/entry.rs", false));
+        assert!(!ignores.is_ignored("src/cache", false));
+
+        assert!(ignores.is_ignored("docs/api/v1/spec.pdf", false));
+        assert!(!ignores.is_ignored("docs/spec.md", false));
+
+        // Nested .gitignore only applies to its own subtree
+        assert!(ignores.is_ignored("tests/fixtures.json", false));
+        assert!(!ignores.is_ignored("fixtures.json", false));
+    }
+}
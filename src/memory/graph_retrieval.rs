@@ -450,7 +450,8 @@ pub fn spreading_activation_retrieve_with_stats(
     );
 
     // Step 2: Initialize activation map from focal entities (nouns)
-    // ACT-R inspired: weight initial activation by entity salience (attention budget)
+    // ACT-R inspired: weight initial activation by entity salience (attention budget).
+    // Structural salience folds in graph centrality, so hub entities seed more activation.
     let mut activation_map: HashMap<Uuid, f32> = HashMap::new();
 
     // First pass: collect entities with their salience values
@@ -462,7 +463,7 @@ pub fn spreading_activation_retrieve_with_stats(
                 entity_node.uuid,
                 entity.text.clone(),
                 entity.ic_weight,
-                entity_node.salience,
+                entity_node.structural_salience(),
             ));
         } else {
             tracing::debug!("  ✗ Entity '{}' not found in graph", entity.text);
@@ -23,14 +23,30 @@ use parking_lot::RwLock;
 use serde::{Deserialize, Serialize};
 use tantivy::collector::TopDocs;
 use tantivy::query::QueryParser;
-use tantivy::schema::{Field, Schema, Value, STORED, STRING, TEXT};
+use tantivy::schema::{
+    Field, IndexRecordOption, Schema, TextFieldIndexing, TextOptions, Value, STORED, STRING, TEXT,
+};
+use tantivy::tokenizer::{Token, TokenStream, Tokenizer};
 use tantivy::{Index, IndexReader, IndexWriter, TantivyDocument};
 use tracing::{debug, info};
 
+use super::language::{self, Language};
 use super::types::MemoryId;
-use crate::embeddings::minilm::MiniLMEmbedder;
+use crate::embeddings::cross_encoder::CrossEncoder;
 use crate::embeddings::Embedder;
 
+/// Model used for the reranking stage
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RerankerModel {
+    /// Cosine similarity between separate MiniLM query/document embeddings
+    BiEncoder,
+    /// Joint (query, document) scoring with an ms-marco cross-encoder.
+    /// Falls back to `BiEncoder` when the model files are unavailable.
+    #[default]
+    CrossEncoder,
+}
+
 /// Configuration for hybrid search
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct HybridSearchConfig {
@@ -63,6 +79,10 @@ pub struct HybridSearchConfig {
     #[serde(default = "default_use_reranking")]
     pub use_reranking: bool,
 
+    /// Which model scores the reranked candidates
+    #[serde(default)]
+    pub reranker_model: RerankerModel,
+
     /// Minimum BM25 score to consider (filters noise)
     #[serde(default = "default_min_bm25_score")]
     pub min_bm25_score: f32,
@@ -91,7 +111,7 @@ fn default_rerank_count() -> usize {
     20
 }
 fn default_use_reranking() -> bool {
-    false // Disabled: current implementation is bi-encoder, not true cross-encoder
+    false // Opt-in: adds a model pass over the top rerank_count candidates per query
 }
 fn default_min_bm25_score() -> f32 {
     0.01 // Lower threshold to capture more keyword matches
@@ -110,12 +130,39 @@ impl Default for HybridSearchConfig {
             candidate_count: default_candidate_count(),
             rerank_count: default_rerank_count(),
             use_reranking: default_use_reranking(),
+            reranker_model: RerankerModel::default(),
             min_bm25_score: default_min_bm25_score(),
             min_graph_score: default_min_graph_score(),
         }
     }
 }
 
+impl HybridSearchConfig {
+    /// Defaults with reranking overrides from environment variables
+    ///
+    /// - `SHODH_RERANKING=true` - Enable the reranking stage
+    /// - `SHODH_RERANKER=cross_encoder|bi_encoder` - Select the reranker model
+    pub fn from_env() -> Self {
+        let mut config = Self::default();
+
+        if let Ok(value) = std::env::var("SHODH_RERANKING") {
+            config.use_reranking = value == "true" || value == "1";
+        }
+
+        if let Ok(value) = std::env::var("SHODH_RERANKER") {
+            match value.to_lowercase().as_str() {
+                "cross_encoder" | "cross-encoder" => {
+                    config.reranker_model = RerankerModel::CrossEncoder
+                }
+                "bi_encoder" | "bi-encoder" => config.reranker_model = RerankerModel::BiEncoder,
+                other => tracing::warn!("Ignoring unknown SHODH_RERANKER value: {}", other),
+            }
+        }
+
+        config
+    }
+}
+
 /// Result from hybrid search with component scores
 #[derive(Debug, Clone)]
 pub struct HybridSearchResult {
@@ -150,6 +197,96 @@ pub struct HybridSearchResult {
     pub graph_rank: Option<usize>,
 }
 
+/// Tantivy analyzer for one language: word split, lowercase, stop words, stemming
+///
+/// Registered as `shodh_{code}` (e.g. `shodh_de`). Stop words are dropped but
+/// still advance the position so phrase queries keep their gaps.
+#[derive(Clone)]
+struct LanguageTokenizer {
+    language: Language,
+}
+
+impl LanguageTokenizer {
+    fn name(language: Language) -> String {
+        format!("shodh_{}", language.code())
+    }
+}
+
+impl Tokenizer for LanguageTokenizer {
+    type TokenStream<'a> = LanguageTokenStream;
+
+    fn token_stream<'a>(&'a mut self, text: &'a str) -> Self::TokenStream<'a> {
+        let stemmer = self.language.stemmer();
+        let mut tokens = Vec::new();
+        let mut position = 0;
+        let mut start: Option<usize> = None;
+
+        // Trailing sentinel closes the last word
+        for (offset, c) in text
+            .char_indices()
+            .chain(std::iter::once((text.len(), ' ')))
+        {
+            match (start, language::is_word_char(c)) {
+                (None, true) => start = Some(offset),
+                (Some(from), false) => {
+                    let word = text[from..offset].to_lowercase();
+                    if !self.language.is_stopword(&word) {
+                        tokens.push(Token {
+                            offset_from: from,
+                            offset_to: offset,
+                            position,
+                            text: stemmer.stem(&word),
+                            position_length: 1,
+                        });
+                    }
+                    position += 1;
+                    start = None;
+                }
+                _ => {}
+            }
+        }
+
+        tokens.reverse();
+        LanguageTokenStream {
+            tokens,
+            current: Token::default(),
+        }
+    }
+}
+
+/// Pre-computed token stream produced by [`LanguageTokenizer`]
+struct LanguageTokenStream {
+    /// Remaining tokens in reverse order
+    tokens: Vec<Token>,
+    current: Token,
+}
+
+impl TokenStream for LanguageTokenStream {
+    fn advance(&mut self) -> bool {
+        match self.tokens.pop() {
+            Some(token) => {
+                self.current = token;
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn token(&self) -> &Token {
+        &self.current
+    }
+
+    fn token_mut(&mut self) -> &mut Token {
+        &mut self.current
+    }
+}
+
+/// Languages with a dedicated analyzed content field (`content_{code}`)
+///
+/// English documents stay on the `content` field's default analyzer so
+/// existing English rankings are unchanged.
+const ANALYZED_LANGUAGES: [Language; 3] = [Language::German, Language::Spanish, Language::Hindi];
+
 /// BM25 Index using Tantivy
 pub struct BM25Index {
     index: Index,
@@ -159,10 +296,15 @@ pub struct BM25Index {
     content_field: Field,
     tags_field: Field,
     entities_field: Field,
+    /// Per-language analyzed content, indexed only for documents in that language
+    language_fields: Vec<(Language, Field)>,
 }
 
 impl BM25Index {
     /// Create or open a BM25 index at the given path
+    ///
+    /// An index created before per-language fields existed is rebuilt empty,
+    /// which triggers the startup backfill.
     pub fn new(path: &Path) -> Result<Self> {
         let mut schema_builder = Schema::builder();
 
@@ -178,19 +320,62 @@ impl BM25Index {
         // Entities (tokenized)
         let entities_field = schema_builder.add_text_field("entities", TEXT);
 
+        // Language-specific content (stemmed + stop words, not stored)
+        let language_fields: Vec<(Language, Field)> = ANALYZED_LANGUAGES
+            .iter()
+            .map(|&language| {
+                let indexing = TextFieldIndexing::default()
+                    .set_tokenizer(&LanguageTokenizer::name(language))
+                    .set_index_option(IndexRecordOption::WithFreqsAndPositions);
+                let options = TextOptions::default().set_indexing_options(indexing);
+                let name = format!("content_{}", language.code());
+                (language, schema_builder.add_text_field(&name, options))
+            })
+            .collect();
+
         let schema = schema_builder.build();
 
         // Create or open index
         std::fs::create_dir_all(path)?;
-        let dir = tantivy::directory::MmapDirectory::open(path)
+        let mut dir = tantivy::directory::MmapDirectory::open(path)
             .context("Failed to open tantivy directory")?;
 
+        if Index::exists(&dir)? {
+            let existing =
+                Index::open(dir.clone()).context("Failed to open existing BM25 index")?;
+            let existing_schema = existing.schema();
+            let missing_language_fields = ANALYZED_LANGUAGES.iter().any(|language| {
+                existing_schema
+                    .get_field(&format!("content_{}", language.code()))
+                    .is_err()
+            });
+            if missing_language_fields {
+                info!(
+                    "BM25 index lacks per-language fields, rebuilding at {:?}",
+                    path
+                );
+                drop(existing);
+                drop(dir);
+                std::fs::remove_dir_all(path)?;
+                std::fs::create_dir_all(path)?;
+                dir = tantivy::directory::MmapDirectory::open(path)
+                    .context("Failed to open tantivy directory")?;
+            }
+        }
+
         let index = if Index::exists(&dir)? {
             Index::open(dir).context("Failed to open existing BM25 index")?
         } else {
             Index::create_in_dir(path, schema.clone()).context("Failed to create BM25 index")?
         };
 
+        for language in ANALYZED_LANGUAGES {
+            index.tokenizers().register(
+                &LanguageTokenizer::name(language),
+                LanguageTokenizer { language },
+            );
+        }
+
         // 50MB writer heap
         let writer = index
             .writer(50_000_000)
@@ -212,16 +397,20 @@ impl BM25Index {
             content_field,
             tags_field,
             entities_field,
+            language_fields,
         })
     }
 
     /// Add or update a document in the index
+    ///
+    /// Non-English content is also indexed into its language's analyzed field.
     pub fn upsert(
         &self,
         memory_id: &MemoryId,
         content: &str,
         tags: &[String],
         entities: &[String],
+        language: Language,
     ) -> Result<()> {
         let writer = self.writer.write();
 
@@ -233,6 +422,9 @@ impl BM25Index {
         let mut doc = TantivyDocument::new();
         doc.add_text(self.id_field, memory_id.0.to_string());
         doc.add_text(self.content_field, content);
+        if let Some((_, field)) = self.language_fields.iter().find(|(l, _)| *l == language) {
+            doc.add_text(*field, content);
+        }
         doc.add_text(self.tags_field, tags.join(" "));
         doc.add_text(self.entities_field, entities.join(" "));
 
@@ -298,11 +490,10 @@ impl BM25Index {
 
         let searcher = self.reader.searcher();
 
-        // Parse query across content, tags, and entities fields
-        let query_parser = QueryParser::for_index(
-            &self.index,
-            vec![self.content_field, self.tags_field, self.entities_field],
-        );
+        // Parse query across content, tags, entities and per-language content fields
+        let mut fields = vec![self.content_field, self.tags_field, self.entities_field];
+        fields.extend(self.language_fields.iter().map(|(_, field)| *field));
+        let query_parser = QueryParser::for_index(&self.index, fields);
 
         // Build boosted query with term weights
         let mut query_parts: Vec<String> = Vec::new();
@@ -311,7 +502,7 @@ impl BM25Index {
         if let Some(weights) = term_weights {
             for word in query.split_whitespace() {
                 let clean_word = word
-                    .trim_matches(|c: char| !c.is_alphanumeric())
+                    .trim_matches(|c: char| !language::is_word_char(c))
                     .to_lowercase();
                 if clean_word.is_empty() {
                     continue;
@@ -327,7 +518,7 @@ impl BM25Index {
             // No term weights - add words as-is
             for word in query.split_whitespace() {
                 let clean_word = word
-                    .trim_matches(|c: char| !c.is_alphanumeric())
+                    .trim_matches(|c: char| !language::is_word_char(c))
                     .to_lowercase();
                 if !clean_word.is_empty() {
                     query_parts.push(clean_word);
@@ -475,31 +666,50 @@ impl RRFusion {
     }
 }
 
-/// Cross-encoder reranker using the same MiniLM model
-///
-/// For true cross-encoder reranking, you'd use a model trained for
-/// query-document scoring (e.g., cross-encoder/ms-marco-MiniLM-L-6-v2).
-/// This implementation uses cosine similarity as a proxy, which works
-/// but isn't as accurate as a dedicated cross-encoder.
+/// Reranker for the top fused candidates
 ///
-/// Future: Replace with actual cross-encoder model for better accuracy.
+/// Uses a dedicated cross-encoder (e.g. cross-encoder/ms-marco-MiniLM-L6-v2)
+/// when one is configured and loadable: query and document are encoded
+/// jointly, which is far more accurate than comparing separate embeddings.
+/// Without it (model files absent, offline, circuit open, inference error)
+/// it falls back to bi-encoder cosine similarity on the shared embedder.
 pub struct CrossEncoderReranker {
-    embedder: Arc<MiniLMEmbedder>,
+    embedder: Arc<dyn Embedder>,
+    cross_encoder: Option<Arc<CrossEncoder>>,
 }
 
 impl CrossEncoderReranker {
-    /// Create reranker with shared embedder
-    pub fn new(embedder: Arc<MiniLMEmbedder>) -> Self {
-        Self { embedder }
+    /// Create bi-encoder reranker with shared embedder
+    pub fn new(embedder: Arc<dyn Embedder>) -> Self {
+        Self {
+            embedder,
+            cross_encoder: None,
+        }
     }
 
-    /// Rerank candidates based on query-document similarity
-    ///
-    /// Takes (memory_id, content, current_score) and returns reranked scores.
+    /// Create reranker that prefers the cross-encoder and falls back to
+    /// bi-encoder similarity on the shared embedder
+    pub fn with_cross_encoder(
+        embedder: Arc<dyn Embedder>,
+        cross_encoder: Arc<CrossEncoder>,
+    ) -> Self {
+        Self {
+            embedder,
+            cross_encoder: Some(cross_encoder),
+        }
+    }
+
+    /// Whether reranking currently goes through the cross-encoder model
+    pub fn uses_cross_encoder(&self) -> bool {
+        self.cross_encoder
+            .as_ref()
+            .is_some_and(|encoder| encoder.is_available())
+    }
+
+    /// Rerank candidates based on query-document relevance
     ///
-    /// Note: This uses bi-encoder (separate query/doc embeddings) not true
-    /// cross-encoder (joint query+doc encoding). True cross-encoders are
-    /// more accurate but slower. This is a reasonable approximation.
+    /// Takes (memory_id, content, current_score) and returns reranked scores,
+    /// sorted descending.
     pub fn rerank(
         &self,
         query: &str,
@@ -509,6 +719,38 @@ impl CrossEncoderReranker {
             return Ok(Vec::new());
         }
 
+        if let Some(ref encoder) = self.cross_encoder {
+            if encoder.is_available() {
+                let documents: Vec<&str> = candidates.iter().map(|(_, c, _)| c.as_str()).collect();
+                match encoder.score(query, &documents) {
+                    Ok(scores) => {
+                        let mut results: Vec<(MemoryId, f32)> = candidates
+                            .into_iter()
+                            .zip(scores)
+                            .map(|((memory_id, _, _), score)| (memory_id, score))
+                            .collect();
+                        results.sort_by(|a, b| {
+                            b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal)
+                        });
+                        return Ok(results);
+                    }
+                    Err(e) => {
+                        tracing::warn!("Cross-encoder rerank failed: {}. Using bi-encoder.", e);
+                    }
+                }
+            }
+            encoder.record_fallback();
+        }
+
+        self.rerank_bi_encoder(query, candidates)
+    }
+
+    /// Bi-encoder approximation: cosine similarity of separate embeddings
+    fn rerank_bi_encoder(
+        &self,
+        query: &str,
+        candidates: Vec<(MemoryId, String, f32)>,
+    ) -> Result<Vec<(MemoryId, f32)>> {
         // Encode query
         let query_embedding = self.embedder.encode(query)?;
 
@@ -559,15 +801,30 @@ pub struct HybridSearchEngine {
 
 impl HybridSearchEngine {
     /// Create hybrid search engine
+    ///
+    /// The cross-encoder is shared like the embedder: its ONNX session is
+    /// loaded once per process, not once per engine.
     pub fn new(
         bm25_path: &Path,
-        embedder: Arc<MiniLMEmbedder>,
+        embedder: Arc<dyn Embedder>,
+        cross_encoder: Arc<CrossEncoder>,
         config: HybridSearchConfig,
     ) -> Result<Self> {
         let bm25_index = BM25Index::new(bm25_path)?;
 
         let reranker = if config.use_reranking {
-            Some(CrossEncoderReranker::new(embedder))
+            match config.reranker_model {
+                RerankerModel::CrossEncoder => {
+                    if !cross_encoder.is_available() {
+                        info!("Cross-encoder model not available, reranking with bi-encoder");
+                    }
+                    Some(CrossEncoderReranker::with_cross_encoder(
+                        embedder,
+                        cross_encoder,
+                    ))
+                }
+                RerankerModel::BiEncoder => Some(CrossEncoderReranker::new(embedder)),
+            }
         } else {
             None
         };
@@ -586,8 +843,10 @@ impl HybridSearchEngine {
         content: &str,
         tags: &[String],
         entities: &[String],
+        language: Language,
     ) -> Result<()> {
-        self.bm25_index.upsert(memory_id, content, tags, entities)
+        self.bm25_index
+            .upsert(memory_id, content, tags, entities, language)
     }
 
     /// Remove a memory from the BM25 index
@@ -903,21 +1162,21 @@ impl HybridSearchEngine {
     /// This indexes all memories into BM25 for hybrid search.
     ///
     /// # Arguments
-    /// * `memories` - Iterator of (memory_id, content, tags, entities)
+    /// * `memories` - Iterator of (memory_id, content, tags, entities, language)
     ///
     /// # Returns
     /// Number of memories indexed
     pub fn backfill<I>(&self, memories: I) -> Result<usize>
     where
-        I: Iterator<Item = (MemoryId, String, Vec<String>, Vec<String>)>,
+        I: Iterator<Item = (MemoryId, String, Vec<String>, Vec<String>, Language)>,
     {
         let mut count = 0;
         let mut batch_count = 0;
         const BATCH_SIZE: usize = 100;
 
-        for (memory_id, content, tags, entities) in memories {
+        for (memory_id, content, tags, entities, language) in memories {
             self.bm25_index
-                .upsert(&memory_id, &content, &tags, &entities)?;
+                .upsert(&memory_id, &content, &tags, &entities, language)?;
             count += 1;
             batch_count += 1;
 
@@ -945,6 +1204,7 @@ impl HybridSearchEngine {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::embeddings::cross_encoder::CrossEncoderConfig;
 
     #[test]
     fn test_rrf_fusion_basic() {
@@ -1008,6 +1268,61 @@ mod tests {
         assert!((fused[0].1 - fused[1].1).abs() < 0.001);
     }
 
+    fn simplified_embedder() -> Arc<dyn Embedder> {
+        use crate::embeddings::minilm::{EmbeddingConfig, MiniLMEmbedder};
+        let config = EmbeddingConfig::with_paths(
+            std::path::PathBuf::from("dummy.onnx"),
+            std::path::PathBuf::from("dummy.json"),
+        );
+        Arc::new(MiniLMEmbedder::new_simplified(config).unwrap())
+    }
+
+    #[test]
+    fn test_reranker_falls_back_without_cross_encoder_model() {
+        let embedder = simplified_embedder();
+        let cross_encoder = Arc::new(CrossEncoder::new(CrossEncoderConfig::with_paths(
+            std::path::PathBuf::from("/nonexistent/model.onnx"),
+            std::path::PathBuf::from("/nonexistent/tokenizer.json"),
+        )));
+        let reranker =
+            CrossEncoderReranker::with_cross_encoder(embedder.clone(), cross_encoder.clone());
+        assert!(!reranker.uses_cross_encoder());
+
+        let id1 = MemoryId(uuid::Uuid::new_v4());
+        let id2 = MemoryId(uuid::Uuid::new_v4());
+        let candidates = vec![
+            (
+                id1.clone(),
+                "rust borrow checker lifetimes".to_string(),
+                0.5,
+            ),
+            (id2.clone(), "banana bread recipe".to_string(), 0.4),
+        ];
+
+        let fallback = reranker
+            .rerank("rust borrow checker", candidates.clone())
+            .unwrap();
+        let bi_encoder = CrossEncoderReranker::new(embedder)
+            .rerank("rust borrow checker", candidates)
+            .unwrap();
+
+        // Same scores as the plain bi-encoder path
+        assert_eq!(fallback, bi_encoder);
+        assert_eq!(cross_encoder.metrics().total_fallbacks, 1);
+    }
+
+    #[test]
+    fn test_reranker_model_config_serde() {
+        let config: HybridSearchConfig =
+            serde_json::from_str(r#"{"use_reranking": true, "reranker_model": "bi_encoder"}"#)
+                .unwrap();
+        assert!(config.use_reranking);
+        assert_eq!(config.reranker_model, RerankerModel::BiEncoder);
+
+        let config: HybridSearchConfig = serde_json::from_str("{}").unwrap();
+        assert_eq!(config.reranker_model, RerankerModel::CrossEncoder);
+    }
+
     #[test]
     fn test_cosine_similarity() {
         let a = vec![1.0, 0.0, 0.0];
@@ -1030,7 +1345,8 @@ mod tests {
         assert_eq!(config.rrf_k, 45.0); // Lower k for top-rank emphasis
         assert_eq!(config.candidate_count, 100); // Increased for better recall
         assert_eq!(config.rerank_count, 20);
-        assert!(!config.use_reranking); // Disabled: bi-encoder, not cross-encoder
+        assert!(!config.use_reranking); // Opt-in: extra model pass per query
+        assert_eq!(config.reranker_model, RerankerModel::CrossEncoder);
         assert_eq!(config.min_graph_score, 0.01); // Graph score threshold (SHO-D4)
     }
 
@@ -1051,6 +1367,7 @@ mod tests {
                 "The user prefers Rust programming language for systems development",
                 &["rust".to_string(), "programming".to_string()],
                 &["Rust".to_string()],
+                Language::English,
             )
             .unwrap();
 
@@ -1060,6 +1377,7 @@ mod tests {
                 "Python is great for machine learning and data science projects",
                 &["python".to_string(), "ml".to_string()],
                 &["Python".to_string()],
+                Language::English,
             )
             .unwrap();
 
@@ -1069,6 +1387,7 @@ mod tests {
                 "The authentication system uses JWT tokens for security",
                 &["auth".to_string(), "security".to_string()],
                 &["JWT".to_string()],
+                Language::English,
             )
             .unwrap();
 
@@ -1098,6 +1417,50 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_bm25_language_analyzers() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let index = BM25Index::new(temp_dir.path()).unwrap();
+
+        let german = MemoryId(uuid::Uuid::new_v4());
+        let spanish = MemoryId(uuid::Uuid::new_v4());
+        let hindi = MemoryId(uuid::Uuid::new_v4());
+
+        index
+            .upsert(
+                &german,
+                "Die Kinder spielten gestern in den Gärten",
+                &[],
+                &[],
+                Language::German,
+            )
+            .unwrap();
+        index
+            .upsert(
+                &spanish,
+                "Los programadores escribieron las pruebas",
+                &[],
+                &[],
+                Language::Spanish,
+            )
+            .unwrap();
+        index
+            .upsert(&hindi, "लड़कों ने मैदान में खेला", &[], &[], Language::Hindi)
+            .unwrap();
+        index.commit().unwrap();
+        index.reload().unwrap();
+
+        // Inflected query forms only match through the stemmed language fields
+        let results = index.search("Garten", 10).unwrap();
+        assert_eq!(results.first().map(|r| &r.0), Some(&german));
+
+        let results = index.search("programador prueba", 10).unwrap();
+        assert_eq!(results.first().map(|r| &r.0), Some(&spanish));
+
+        let results = index.search("लड़का", 10).unwrap();
+        assert_eq!(results.first().map(|r| &r.0), Some(&hindi));
+    }
+
     #[test]
     fn test_bm25_keyword_vs_semantic_gap() {
         // This test demonstrates why BM25 is needed alongside vector search
@@ -1114,6 +1477,7 @@ mod tests {
                 "The server reloads configuration when it receives SIGHUP signal",
                 &["linux".to_string(), "signals".to_string()],
                 &[],
+                Language::English,
             )
             .unwrap();
 
@@ -1124,6 +1488,7 @@ mod tests {
                 "Configuration refresh happens automatically every hour",
                 &["config".to_string()],
                 &[],
+                Language::English,
             )
             .unwrap();
 
@@ -0,0 +1,989 @@
+//! iCalendar (RFC 5545) export and import for todos and reminders
+//!
+//! - Todos become VTODOs (DUE, STATUS, PRIORITY, CATEGORIES, RRULE/EXDATE)
+//! - Reminders become VTODOs carrying a VALARM and `X-SHODH-KIND:reminder`
+//! - Timezones referenced by TZID get a generated VTIMEZONE
+//!
+//! Exported UIDs are `{uuid}@shodh-memory`, so re-importing a file updates the
+//! original items; foreign UIDs are tracked through `external_id` (todos) or
+//! an `ics:{uid}` tag (reminders). VEVENTs import as reminders at their alarm
+//! (or start) time.
+
+use anyhow::{anyhow, bail, Context, Result};
+use chrono::{DateTime, Datelike, Duration, NaiveDateTime, Offset, TimeZone, Utc};
+use chrono_tz::{OffsetName, Tz};
+use std::collections::BTreeSet;
+use std::fmt::Write as _;
+use uuid::Uuid;
+
+use super::rrule::{parse_exdates, parse_tzid, weekday_code, IcalTime, RRule};
+use super::types::{
+    ProspectiveTask, ProspectiveTrigger, Recurrence, Todo, TodoPriority, TodoStatus,
+};
+
+/// PRODID written on export
+const PRODID: &str = "-//shodh-memory//Todos and Reminders//EN";
+
+/// UID domain for exported items
+const UID_DOMAIN: &str = "shodh-memory";
+
+/// Longest content line in octets before folding (RFC 5545 §3.1)
+const MAX_LINE_OCTETS: usize = 75;
+
+// =============================================================================
+// CONTENT LINES
+// =============================================================================
+
+/// One unfolded `NAME;PARAM=VALUE:value` line
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContentLine {
+    /// Property name, uppercased
+    pub name: String,
+    /// Parameters with uppercased names and unquoted values
+    pub params: Vec<(String, String)>,
+    pub value: String,
+}
+
+impl ContentLine {
+    /// Parse an unfolded content line; None when it has no `:` separator
+    pub fn parse(line: &str) -> Option<Self> {
+        let mut in_quotes = false;
+        let mut colon = None;
+        for (i, c) in line.char_indices() {
+            match c {
+                '"' => in_quotes = !in_quotes,
+                ':' if !in_quotes => {
+                    colon = Some(i);
+                    break;
+                }
+                _ => {}
+            }
+        }
+        let colon = colon?;
+        let (head, value) = (&line[..colon], &line[colon + 1..]);
+
+        let mut parts = split_unquoted(head, ';').into_iter();
+        let name = parts.next()?.trim().to_uppercase();
+        if name.is_empty() {
+            return None;
+        }
+        let params = parts
+            .filter_map(|p| {
+                let (k, v) = p.split_once('=')?;
+                Some((
+                    k.trim().to_uppercase(),
+                    v.trim().trim_matches('"').to_string(),
+                ))
+            })
+            .collect();
+
+        Some(Self {
+            name,
+            params,
+            value: value.to_string(),
+        })
+    }
+
+    /// Value of a parameter (case-insensitive name)
+    pub fn param(&self, name: &str) -> Option<&str> {
+        self.params
+            .iter()
+            .find(|(k, _)| k.eq_ignore_ascii_case(name))
+            .map(|(_, v)| v.as_str())
+    }
+
+    /// Timezone named by the TZID parameter, if any and known
+    fn tz(&self) -> Option<Tz> {
+        self.param("TZID").and_then(|id| parse_tzid(id).ok())
+    }
+
+    /// Read a DATE/DATE-TIME value as an instant (floating times as UTC)
+    fn instant(&self) -> Result<DateTime<Utc>> {
+        Ok(IcalTime::parse(&self.value)?.to_utc(self.tz().unwrap_or(Tz::UTC)))
+    }
+}
+
+fn split_unquoted(s: &str, sep: char) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut in_quotes = false;
+    let mut start = 0;
+    for (i, c) in s.char_indices() {
+        if c == '"' {
+            in_quotes = !in_quotes;
+        } else if c == sep && !in_quotes {
+            parts.push(&s[start..i]);
+            start = i + c.len_utf8();
+        }
+    }
+    parts.push(&s[start..]);
+    parts
+}
+
+/// Join folded lines (a line starting with space or tab continues the previous one)
+pub fn unfold(text: &str) -> Vec<String> {
+    let mut lines: Vec<String> = Vec::new();
+    for raw in text.split('\n') {
+        let raw = raw.strip_suffix('\r').unwrap_or(raw);
+        match raw.chars().next() {
+            Some(' ') | Some('\t') if !lines.is_empty() => {
+                if let Some(last) = lines.last_mut() {
+                    last.push_str(&raw[1..]);
+                }
+            }
+            _ if raw.trim().is_empty() => {}
+            _ => lines.push(raw.to_string()),
+        }
+    }
+    lines
+}
+
+/// Fold a content line at 75 octets without splitting UTF-8 sequences
+pub fn fold(line: &str) -> String {
+    let mut out = String::with_capacity(line.len() + line.len() / MAX_LINE_OCTETS * 3);
+    let mut octets = 0;
+    for c in line.chars() {
+        let width = c.len_utf8();
+        // Continuation lines spend one octet on the leading space
+        if octets + width > MAX_LINE_OCTETS {
+            out.push_str("\r\n ");
+            octets = 1;
+        }
+        out.push(c);
+        octets += width;
+    }
+    out
+}
+
+/// Escape a TEXT value
+pub fn escape_text(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '\\' => out.push_str("\\\\"),
+            ';' => out.push_str("\\;"),
+            ',' => out.push_str("\\,"),
+            '\n' => out.push_str("\\n"),
+            '\r' => {}
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+/// Unescape a TEXT value
+pub fn unescape_text(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    let mut chars = s.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') | Some('N') => out.push('\n'),
+            Some(other) => out.push(other),
+            None => out.push('\\'),
+        }
+    }
+    out
+}
+
+/// Split a multi-valued TEXT property on unescaped commas
+fn split_text_list(s: &str) -> Vec<String> {
+    let mut items = Vec::new();
+    let mut current = String::new();
+    let mut escaped = false;
+    for c in s.chars() {
+        if escaped {
+            current.push('\\');
+            current.push(c);
+            escaped = false;
+        } else if c == '\\' {
+            escaped = true;
+        } else if c == ',' {
+            items.push(unescape_text(&current));
+            current.clear();
+        } else {
+            current.push(c);
+        }
+    }
+    items.push(unescape_text(&current));
+    items
+        .into_iter()
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+        .collect()
+}
+
+/// Parse a DURATION value such as `-PT15M` or `P1DT2H`
+pub fn parse_duration(value: &str) -> Result<Duration> {
+    let value = value.trim();
+    let (negative, rest) = match value.as_bytes().first() {
+        Some(b'-') => (true, &value[1..]),
+        Some(b'+') => (false, &value[1..]),
+        _ => (false, value),
+    };
+    let rest = rest
+        .strip_prefix('P')
+        .or_else(|| rest.strip_prefix('p'))
+        .ok_or_else(|| anyhow!("invalid duration '{value}'"))?;
+
+    let mut seconds: i64 = 0;
+    let mut number = String::new();
+    let mut in_time = false;
+    for c in rest.chars() {
+        match c.to_ascii_uppercase() {
+            'T' => in_time = true,
+            d if d.is_ascii_digit() => number.push(d),
+            unit => {
+                let n: i64 = number
+                    .parse()
+                    .map_err(|_| anyhow!("invalid duration '{value}'"))?;
+                number.clear();
+                seconds += n * match (unit, in_time) {
+                    ('W', false) => 7 * 86_400,
+                    ('D', false) => 86_400,
+                    ('H', true) => 3600,
+                    ('M', true) => 60,
+                    ('S', true) => 1,
+                    _ => bail!("invalid duration '{value}'"),
+                };
+            }
+        }
+    }
+    if !number.is_empty() {
+        bail!("invalid duration '{value}'");
+    }
+    Ok(Duration::seconds(if negative { -seconds } else { seconds }))
+}
+
+// =============================================================================
+// EXPORT
+// =============================================================================
+
+/// UID for an exported item
+pub fn export_uid(id: &Uuid) -> String {
+    format!("{id}@{UID_DOMAIN}")
+}
+
+/// Item ID encoded in a UID written by [`export_uid`]
+pub fn parse_export_uid(uid: &str) -> Option<Uuid> {
+    uid.strip_suffix(UID_DOMAIN)
+        .and_then(|s| s.strip_suffix('@'))
+        .and_then(|s| Uuid::parse_str(s).ok())
+}
+
+/// iCalendar PRIORITY (1 highest … 9 lowest, 0 undefined) for a todo priority
+pub fn todo_priority_to_ical(priority: &TodoPriority) -> u8 {
+    match priority {
+        TodoPriority::Urgent => 1,
+        TodoPriority::High => 3,
+        TodoPriority::Medium => 5,
+        TodoPriority::Low => 7,
+        TodoPriority::None => 0,
+    }
+}
+
+pub fn todo_priority_from_ical(priority: u8) -> TodoPriority {
+    match priority {
+        1 | 2 => TodoPriority::Urgent,
+        3 | 4 => TodoPriority::High,
+        5 => TodoPriority::Medium,
+        6..=9 => TodoPriority::Low,
+        _ => TodoPriority::None,
+    }
+}
+
+/// iCalendar PRIORITY for a reminder priority (1-5, 5 most important)
+pub fn reminder_priority_to_ical(priority: u8) -> u8 {
+    match priority.clamp(1, 5) {
+        5 => 1,
+        4 => 3,
+        3 => 5,
+        2 => 7,
+        _ => 9,
+    }
+}
+
+pub fn reminder_priority_from_ical(priority: u8) -> u8 {
+    match priority {
+        1 | 2 => 5,
+        3 | 4 => 4,
+        6 | 7 => 2,
+        8 | 9 => 1,
+        _ => 3,
+    }
+}
+
+fn status_to_ical(status: &TodoStatus) -> &'static str {
+    match status {
+        TodoStatus::InProgress => "IN-PROCESS",
+        TodoStatus::Done => "COMPLETED",
+        TodoStatus::Cancelled => "CANCELLED",
+        TodoStatus::Backlog | TodoStatus::Todo | TodoStatus::Blocked => "NEEDS-ACTION",
+    }
+}
+
+fn status_from_ical(status: &str) -> Option<TodoStatus> {
+    match status.trim().to_uppercase().as_str() {
+        "NEEDS-ACTION" => Some(TodoStatus::Todo),
+        "IN-PROCESS" => Some(TodoStatus::InProgress),
+        "COMPLETED" => Some(TodoStatus::Done),
+        "CANCELLED" => Some(TodoStatus::Cancelled),
+        _ => None,
+    }
+}
+
+/// Accumulates a VCALENDAR, remembering which VTIMEZONEs it needs
+struct CalendarWriter {
+    body: Vec<String>,
+    timezones: BTreeSet<&'static str>,
+    stamp: String,
+}
+
+impl CalendarWriter {
+    fn new(now: DateTime<Utc>) -> Self {
+        Self {
+            body: Vec::new(),
+            timezones: BTreeSet::new(),
+            stamp: utc_value(now),
+        }
+    }
+
+    fn line(&mut self, line: impl Into<String>) {
+        self.body.push(line.into());
+    }
+
+    fn text(&mut self, name: &str, value: &str) {
+        self.body.push(format!("{name}:{}", escape_text(value)));
+    }
+
+    /// DATE-TIME property, local with TZID when the series has a timezone
+    fn time(&mut self, name: &str, at: DateTime<Utc>, tz: Option<Tz>) {
+        match tz.filter(|tz| *tz != Tz::UTC) {
+            Some(tz) => {
+                self.timezones.insert(tz.name());
+                self.body.push(format!(
+                    "{name};TZID={}:{}",
+                    tz.name(),
+                    at.with_timezone(&tz).format("%Y%m%dT%H%M%S")
+                ));
+            }
+            None => self.body.push(format!("{name}:{}", utc_value(at))),
+        }
+    }
+
+    /// RRULE + EXDATE for a series continuing from `first`
+    fn recurrence(&mut self, recurrence: &Recurrence, first: DateTime<Utc>) {
+        let (rule, exdates, tz) = series_from(recurrence, first);
+        self.body.push(format!("RRULE:{rule}"));
+        for exdate in exdates {
+            self.time("EXDATE", exdate, tz);
+        }
+    }
+
+    fn finish(self) -> String {
+        let year = Utc::now().year();
+        let mut lines = vec![
+            "BEGIN:VCALENDAR".to_string(),
+            "VERSION:2.0".to_string(),
+            format!("PRODID:{PRODID}"),
+            "CALSCALE:GREGORIAN".to_string(),
+        ];
+        for name in &self.timezones {
+            if let Ok(tz) = name.parse::<Tz>() {
+                lines.extend(vtimezone(tz, year));
+            }
+        }
+        lines.extend(self.body);
+        lines.push("END:VCALENDAR".to_string());
+
+        let mut out = String::new();
+        for line in lines {
+            out.push_str(&fold(&line));
+            out.push_str("\r\n");
+        }
+        out
+    }
+}
+
+fn utc_value(at: DateTime<Utc>) -> String {
+    at.format("%Y%m%dT%H%M%SZ").to_string()
+}
+
+/// Timezone of a recurrence (None for UTC and for the legacy patterns)
+fn recurrence_tz(recurrence: Option<&Recurrence>) -> Option<Tz> {
+    match recurrence {
+        Some(Recurrence::RRule {
+            tzid: Some(tzid), ..
+        }) => parse_tzid(tzid).ok(),
+        _ => None,
+    }
+}
+
+/// Rule, exclusions and timezone for a series re-anchored at `first`,
+/// with COUNT reduced to the instances that remain
+fn series_from(
+    recurrence: &Recurrence,
+    first: DateTime<Utc>,
+) -> (String, Vec<DateTime<Utc>>, Option<Tz>) {
+    let tz = recurrence_tz(Some(recurrence));
+    let Some(mut set) = recurrence.recurrence_set() else {
+        return (recurrence.to_rrule(), Vec::new(), tz);
+    };
+
+    let exdates: Vec<DateTime<Utc>> = set
+        .exdates
+        .iter()
+        .copied()
+        .filter(|t| *t >= first)
+        .collect();
+    let mut rule: RRule = set.rule.clone();
+    if rule.count.is_some() {
+        // COUNT covers excluded instances too, so count without EXDATEs
+        set.exdates.clear();
+        let before = set.iter().take_while(|t| *t < first).count();
+        let remaining = rule.count.unwrap_or(0).saturating_sub(before as u32);
+        rule.count = Some(remaining.max(1));
+    }
+    (rule.to_string(), exdates, tz)
+}
+
+/// Serialize todos and reminders as one VCALENDAR
+///
+/// Todos are written as VTODOs; reminders with a time (one-shot or
+/// recurring) as VTODOs with a VALARM. Context reminders have no time and are
+/// skipped.
+pub fn export_calendar(todos: &[Todo], reminders: &[ProspectiveTask]) -> String {
+    let now = Utc::now();
+    let mut cal = CalendarWriter::new(now);
+
+    for todo in todos {
+        let tz = recurrence_tz(todo.recurrence.as_ref());
+        cal.line("BEGIN:VTODO");
+        cal.line(format!("UID:{}", export_uid(&todo.id.0)));
+        cal.line(format!("DTSTAMP:{}", cal.stamp));
+        cal.line(format!("CREATED:{}", utc_value(todo.created_at)));
+        cal.line(format!("LAST-MODIFIED:{}", utc_value(todo.updated_at)));
+        cal.text("SUMMARY", &todo.content);
+        if let Some(ref notes) = todo.notes {
+            cal.text("DESCRIPTION", notes);
+        }
+        if let Some(due) = todo.due_date {
+            // A recurring VTODO needs DTSTART to anchor its RRULE
+            if todo.recurrence.is_some() {
+                cal.time("DTSTART", due, tz);
+            }
+            cal.time("DUE", due, tz);
+        }
+        cal.line(format!("STATUS:{}", status_to_ical(&todo.status)));
+        let priority = todo_priority_to_ical(&todo.priority);
+        if priority > 0 {
+            cal.line(format!("PRIORITY:{priority}"));
+        }
+        if !todo.tags.is_empty() {
+            let tags: Vec<String> = todo.tags.iter().map(|t| escape_text(t)).collect();
+            cal.line(format!("CATEGORIES:{}", tags.join(",")));
+        }
+        if let (Some(recurrence), Some(due)) = (&todo.recurrence, todo.due_date) {
+            cal.recurrence(recurrence, due);
+        }
+        if let Some(completed) = todo.completed_at {
+            cal.line(format!("COMPLETED:{}", utc_value(completed)));
+        }
+        cal.text("X-SHODH-ID", &todo.short_id());
+        cal.line("END:VTODO");
+    }
+
+    for task in reminders {
+        let Some(at) = task.trigger.due_at() else {
+            continue;
+        };
+        let recurrence = match &task.trigger {
+            ProspectiveTrigger::Recurring { recurrence, .. } => Some(recurrence),
+            _ => None,
+        };
+        let tz = recurrence_tz(recurrence);
+
+        cal.line("BEGIN:VTODO");
+        cal.line(format!("UID:{}", export_uid(&task.id.0)));
+        cal.line(format!("DTSTAMP:{}", cal.stamp));
+        cal.line(format!("CREATED:{}", utc_value(task.created_at)));
+        cal.text("SUMMARY", &task.content);
+        cal.time("DTSTART", at, tz);
+        cal.line("STATUS:NEEDS-ACTION");
+        cal.line(format!(
+            "PRIORITY:{}",
+            reminder_priority_to_ical(task.priority)
+        ));
+        if !task.tags.is_empty() {
+            let tags: Vec<String> = task.tags.iter().map(|t| escape_text(t)).collect();
+            cal.line(format!("CATEGORIES:{}", tags.join(",")));
+        }
+        if let Some(recurrence) = recurrence {
+            cal.recurrence(recurrence, at);
+        }
+        cal.line("X-SHODH-KIND:reminder");
+        cal.line("BEGIN:VALARM");
+        cal.line("ACTION:DISPLAY");
+        cal.text("DESCRIPTION", &task.content);
+        cal.line("TRIGGER;RELATED=START:PT0S");
+        cal.line("END:VALARM");
+        cal.line("END:VTODO");
+    }
+
+    cal.finish()
+}
+
+/// VTIMEZONE for `tz` with yearly rules derived from its transitions in `year`
+fn vtimezone(tz: Tz, year: i32) -> Vec<String> {
+    let offset_at = |t: DateTime<Utc>| tz.offset_from_utc_datetime(&t.naive_utc());
+    let seconds = |t: DateTime<Utc>| offset_at(t).fix().local_minus_utc();
+
+    let mut lines = vec!["BEGIN:VTIMEZONE".to_string(), format!("TZID:{}", tz.name())];
+    let start = Utc
+        .with_ymd_and_hms(year, 1, 1, 0, 0, 0)
+        .single()
+        .unwrap_or_else(Utc::now);
+
+    let mut transitions = Vec::new();
+    let mut day = start;
+    while day.year() == year {
+        let next = day + Duration::days(1);
+        if seconds(day) != seconds(next) {
+            // Binary search for the first second with the new offset
+            let (mut lo, mut hi) = (day, next);
+            while hi - lo > Duration::seconds(1) {
+                let mid = lo + (hi - lo) / 2;
+                if seconds(mid) == seconds(lo) {
+                    lo = mid;
+                } else {
+                    hi = mid;
+                }
+            }
+            transitions.push((hi, seconds(lo), seconds(hi)));
+        }
+        day = next;
+    }
+
+    let abbreviation = |t: DateTime<Utc>| offset_at(t).abbreviation().map(str::to_string);
+
+    if transitions.is_empty() {
+        let offset = format_offset(seconds(start));
+        lines.push("BEGIN:STANDARD".to_string());
+        lines.push("DTSTART:19700101T000000".to_string());
+        lines.push(format!("TZOFFSETFROM:{offset}"));
+        lines.push(format!("TZOFFSETTO:{offset}"));
+        if let Some(name) = abbreviation(start) {
+            lines.push(format!("TZNAME:{name}"));
+        }
+        lines.push("END:STANDARD".to_string());
+    }
+
+    for (at, from, to) in transitions {
+        let kind = if to > from { "DAYLIGHT" } else { "STANDARD" };
+        let local: NaiveDateTime = at.naive_utc() + Duration::seconds(from as i64);
+        let date = local.date();
+        let last_week = date.day() + 7 > days_in_month(date.year(), date.month());
+        let ordinal = if last_week {
+            -1
+        } else {
+            (date.day() as i32 - 1) / 7 + 1
+        };
+        lines.push(format!("BEGIN:{kind}"));
+        lines.push(format!("DTSTART:{}", local.format("%Y%m%dT%H%M%S")));
+        lines.push(format!(
+            "RRULE:FREQ=YEARLY;BYMONTH={};BYDAY={}{}",
+            date.month(),
+            ordinal,
+            weekday_code(date.weekday())
+        ));
+        lines.push(format!("TZOFFSETFROM:{}", format_offset(from)));
+        lines.push(format!("TZOFFSETTO:{}", format_offset(to)));
+        if let Some(name) = abbreviation(at) {
+            lines.push(format!("TZNAME:{name}"));
+        }
+        lines.push(format!("END:{kind}"));
+    }
+
+    lines.push("END:VTIMEZONE".to_string());
+    lines
+}
+
+fn days_in_month(year: i32, month: u32) -> u32 {
+    let (y, m) = if month == 12 {
+        (year + 1, 1)
+    } else {
+        (year, month + 1)
+    };
+    chrono::NaiveDate::from_ymd_opt(y, m, 1)
+        .and_then(|d| d.pred_opt())
+        .map_or(31, |d| d.day())
+}
+
+/// UTC offset as `+HHMM` (with seconds when not whole minutes)
+fn format_offset(seconds: i32) -> String {
+    let sign = if seconds < 0 { '-' } else { '+' };
+    let abs = seconds.abs();
+    let mut out = format!("{sign}{:02}{:02}", abs / 3600, abs % 3600 / 60);
+    if abs % 60 != 0 {
+        let _ = write!(out, "{:02}", abs % 60);
+    }
+    out
+}
+
+// =============================================================================
+// IMPORT
+// =============================================================================
+
+/// What an imported component becomes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IcsEntryKind {
+    Todo,
+    Reminder,
+}
+
+/// A VTODO or VEVENT read from an iCalendar file
+#[derive(Debug, Clone)]
+pub struct IcsEntry {
+    pub uid: String,
+    pub kind: IcsEntryKind,
+    pub summary: String,
+    pub description: Option<String>,
+    /// Todo due date, or the time a reminder fires
+    pub due: Option<DateTime<Utc>>,
+    pub status: Option<TodoStatus>,
+    /// iCalendar PRIORITY (1 highest … 9 lowest)
+    pub priority: Option<u8>,
+    pub categories: Vec<String>,
+    /// For reminders, already shifted by a relative VALARM offset
+    pub recurrence: Option<Recurrence>,
+}
+
+/// Components read from a calendar, plus the ones that couldn't be used
+#[derive(Debug, Default)]
+pub struct ParsedCalendar {
+    pub entries: Vec<IcsEntry>,
+    /// One message per skipped component
+    pub skipped: Vec<String>,
+}
+
+/// Parse a VCALENDAR into todo and reminder entries
+pub fn parse_calendar(text: &str) -> Result<ParsedCalendar> {
+    let lines = unfold(text);
+    if !lines
+        .iter()
+        .any(|l| l.trim().eq_ignore_ascii_case("BEGIN:VCALENDAR"))
+    {
+        bail!("not an iCalendar file (no BEGIN:VCALENDAR)");
+    }
+
+    let mut parsed = ParsedCalendar::default();
+    let mut stack: Vec<String> = Vec::new();
+    let mut props: Vec<ContentLine> = Vec::new();
+    let mut alarm: Vec<ContentLine> = Vec::new();
+    let mut alarms_seen = 0;
+
+    for line in &lines {
+        let Some(prop) = ContentLine::parse(line) else {
+            continue;
+        };
+        match prop.name.as_str() {
+            "BEGIN" => {
+                let component = prop.value.trim().to_uppercase();
+                if component == "VTODO" || component == "VEVENT" {
+                    props.clear();
+                    alarm.clear();
+                    alarms_seen = 0;
+                } else if component == "VALARM" {
+                    alarms_seen += 1;
+                }
+                stack.push(component);
+            }
+            "END" => {
+                let component = prop.value.trim().to_uppercase();
+                if stack.last() == Some(&component) {
+                    stack.pop();
+                }
+                let kind = match component.as_str() {
+                    "VTODO" => IcsEntryKind::Todo,
+                    "VEVENT" => IcsEntryKind::Reminder,
+                    _ => continue,
+                };
+                match build_entry(kind, &props, &alarm) {
+                    Ok(entry) => parsed.entries.push(entry),
+                    Err(e) => {
+                        let uid = props
+                            .iter()
+                            .find(|p| p.name == "UID")
+                            .map_or("(no UID)", |p| p.value.as_str());
+                        parsed.skipped.push(format!("{component} {uid}: {e}"));
+                    }
+                }
+            }
+            _ => match stack.last().map(String::as_str) {
+                Some("VTODO") | Some("VEVENT") => props.push(prop),
+                // Only the first alarm of a component is used
+                Some("VALARM")
+                    if alarms_seen == 1
+                        && stack.len() >= 2
+                        && matches!(stack[stack.len() - 2].as_str(), "VTODO" | "VEVENT") =>
+                {
+                    alarm.push(prop)
+                }
+                _ => {}
+            },
+        }
+    }
+
+    Ok(parsed)
+}
+
+fn build_entry(
+    mut kind: IcsEntryKind,
+    props: &[ContentLine],
+    alarm: &[ContentLine],
+) -> Result<IcsEntry> {
+    let get = |name: &str| props.iter().find(|p| p.name == name);
+
+    let uid = get("UID")
+        .map(|p| p.value.trim().to_string())
+        .filter(|u| !u.is_empty())
+        .ok_or_else(|| anyhow!("missing UID"))?;
+    let summary = get("SUMMARY")
+        .map(|p| unescape_text(&p.value).trim().to_string())
+        .filter(|s| !s.is_empty())
+        .ok_or_else(|| anyhow!("missing SUMMARY"))?;
+    if get("X-SHODH-KIND").is_some_and(|p| p.value.trim().eq_ignore_ascii_case("reminder")) {
+        kind = IcsEntryKind::Reminder;
+    }
+
+    let dtstart = get("DTSTART")
+        .map(|p| p.instant().with_context(|| "invalid DTSTART"))
+        .transpose()?;
+    let due = get("DUE")
+        .map(|p| p.instant().with_context(|| "invalid DUE"))
+        .transpose()?;
+
+    // Relative alarm offset (reminders fire at start/due plus this)
+    let mut alarm_offset = Duration::zero();
+    let mut remind_at = None;
+    if let Some(trigger) = alarm.iter().find(|p| p.name == "TRIGGER") {
+        if trigger
+            .param("VALUE")
+            .is_some_and(|v| v.eq_ignore_ascii_case("DATE-TIME"))
+        {
+            remind_at = Some(trigger.instant()?);
+        } else {
+            alarm_offset = parse_duration(&trigger.value)?;
+            let related_end = trigger
+                .param("RELATED")
+                .is_some_and(|r| r.eq_ignore_ascii_case("END"));
+            let base = if related_end {
+                due.or(dtstart)
+            } else {
+                dtstart.or(due)
+            };
+            remind_at = base.map(|b| b + alarm_offset);
+        }
+    }
+
+    let anchor_prop = get("DTSTART").or_else(|| get("DUE"));
+    let recurrence = match (get("RRULE"), anchor_prop) {
+        (Some(rrule), Some(anchor)) => {
+            let rule: RRule = rrule.value.parse()?;
+            let anchor_time = IcalTime::parse(&anchor.value)?;
+            let tz = anchor.tz();
+            let mut exdates = Vec::new();
+            for exdate in props.iter().filter(|p| p.name == "EXDATE") {
+                let ex_tz = exdate.tz().or(tz).unwrap_or(Tz::UTC);
+                exdates.extend(parse_exdates(&exdate.value, ex_tz, Some(anchor_time))?);
+            }
+            let mut dtstart = anchor_time.to_utc(tz.unwrap_or(Tz::UTC));
+            if kind == IcsEntryKind::Reminder {
+                dtstart += alarm_offset;
+                exdates.iter_mut().for_each(|t| *t += alarm_offset);
+            }
+            Some(Recurrence::RRule {
+                rule: rule.to_string(),
+                dtstart,
+                tzid: tz.map(|tz| tz.name().to_string()),
+                exdates,
+            })
+        }
+        (Some(_), None) => bail!("RRULE without DTSTART or DUE"),
+        _ => None,
+    };
+
+    let due = match kind {
+        IcsEntryKind::Todo => due.or(dtstart),
+        IcsEntryKind::Reminder => remind_at.or(dtstart).or(due),
+    };
+
+    Ok(IcsEntry {
+        uid,
+        kind,
+        summary,
+        description: get("DESCRIPTION").map(|p| unescape_text(&p.value)),
+        due,
+        status: get("STATUS").and_then(|p| status_from_ical(&p.value)),
+        priority: get("PRIORITY")
+            .and_then(|p| p.value.trim().parse().ok())
+            .filter(|p| (1..=9).contains(p)),
+        categories: props
+            .iter()
+            .filter(|p| p.name == "CATEGORIES")
+            .flat_map(|p| split_text_list(&p.value))
+            .collect(),
+        recurrence,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fold_unfold_and_escape() {
+        let summary = "Ship the ünïcödé release; then, celebrate\nwith the team ".repeat(3);
+        let line = format!("SUMMARY:{}", escape_text(&summary));
+        let folded = fold(&line);
+        assert!(folded.split("\r\n").all(|l| l.len() <= MAX_LINE_OCTETS));
+
+        let lines = unfold(&folded);
+        assert_eq!(lines.len(), 1);
+        let prop = ContentLine::parse(&lines[0]).unwrap();
+        assert_eq!(unescape_text(&prop.value), summary);
+    }
+
+    #[test]
+    fn test_content_line_quoted_params() {
+        let prop =
+            ContentLine::parse("dtstart;TZID=\"America/New_York\";X-A=\"a:b\":20250101T090000")
+                .unwrap();
+        assert_eq!(prop.name, "DTSTART");
+        assert_eq!(prop.param("tzid"), Some("America/New_York"));
+        assert_eq!(prop.param("X-A"), Some("a:b"));
+        assert_eq!(prop.value, "20250101T090000");
+    }
+
+    #[test]
+    fn test_parse_duration() {
+        assert_eq!(parse_duration("-PT15M").unwrap(), Duration::minutes(-15));
+        assert_eq!(
+            parse_duration("P1DT2H3S").unwrap(),
+            Duration::seconds(86_400 + 7200 + 3)
+        );
+        assert_eq!(parse_duration("P2W").unwrap(), Duration::days(14));
+        assert!(parse_duration("PT5").is_err());
+        assert!(parse_duration("P1H").is_err());
+    }
+
+    #[test]
+    fn test_export_then_import_round_trip() {
+        let mut todo = Todo::new("u".to_string(), "Pay rent, water; gas".to_string());
+        todo.due_date = Some(Utc.with_ymd_and_hms(2025, 2, 28, 9, 0, 0).unwrap());
+        todo.priority = TodoPriority::High;
+        todo.tags = vec!["home".to_string(), "money".to_string()];
+        todo.recurrence = Some(Recurrence::RRule {
+            rule: "FREQ=MONTHLY;BYDAY=FR;BYSETPOS=-1;COUNT=6".to_string(),
+            dtstart: Utc.with_ymd_and_hms(2025, 1, 31, 9, 0, 0).unwrap(),
+            tzid: Some("Europe/London".to_string()),
+            exdates: vec![
+                Utc.with_ymd_and_hms(2025, 1, 31, 9, 0, 0).unwrap(),
+                Utc.with_ymd_and_hms(2025, 3, 28, 9, 0, 0).unwrap(),
+            ],
+        });
+
+        let reminder = ProspectiveTask::new(
+            "u".to_string(),
+            "Call the dentist".to_string(),
+            ProspectiveTrigger::AtTime {
+                at: Utc.with_ymd_and_hms(2025, 3, 3, 15, 30, 0).unwrap(),
+            },
+        );
+
+        let ics = export_calendar(&[todo.clone()], std::slice::from_ref(&reminder));
+        assert!(ics.contains("BEGIN:VTIMEZONE\r\nTZID:Europe/London"));
+        assert!(ics.contains("DUE;TZID=Europe/London:20250228T090000"));
+        // January was already used up: five instances remain from February
+        assert!(ics.contains("RRULE:FREQ=MONTHLY;COUNT=5;BYDAY=FR;BYSETPOS=-1"));
+        assert!(ics.contains("EXDATE;TZID=Europe/London:20250328T090000"));
+        assert!(!ics.contains("20250131T090000"));
+        assert!(ics.contains("SUMMARY:Pay rent\\, water\\; gas"));
+        assert!(ics.contains("X-SHODH-KIND:reminder"));
+
+        let parsed = parse_calendar(&ics).unwrap();
+        assert!(parsed.skipped.is_empty(), "{:?}", parsed.skipped);
+        assert_eq!(parsed.entries.len(), 2);
+
+        let t = &parsed.entries[0];
+        assert_eq!(t.kind, IcsEntryKind::Todo);
+        assert_eq!(parse_export_uid(&t.uid), Some(todo.id.0));
+        assert_eq!(t.summary, todo.content);
+        assert_eq!(t.due, todo.due_date);
+        assert_eq!(
+            t.priority.map(todo_priority_from_ical),
+            Some(TodoPriority::High)
+        );
+        assert_eq!(t.categories, todo.tags);
+        let occurrences: Vec<_> = t
+            .recurrence
+            .as_ref()
+            .and_then(Recurrence::recurrence_set)
+            .unwrap()
+            .iter()
+            .collect();
+        // Feb, (Mar excluded), Apr, May, Jun — 09:00 London across the DST change
+        assert_eq!(occurrences.len(), 4);
+        assert_eq!(
+            occurrences[1],
+            Utc.with_ymd_and_hms(2025, 4, 25, 8, 0, 0).unwrap()
+        );
+
+        let r = &parsed.entries[1];
+        assert_eq!(r.kind, IcsEntryKind::Reminder);
+        assert_eq!(parse_export_uid(&r.uid), Some(reminder.id.0));
+        assert_eq!(r.due, reminder.trigger.due_at());
+    }
+
+    #[test]
+    fn test_import_foreign_event_with_relative_alarm() {
+        let ics = "BEGIN:VCALENDAR\r\n\
+                   VERSION:2.0\r\n\
+                   BEGIN:VEVENT\r\n\
+                   UID:weekly-sync@example.com\r\n\
+                   DTSTART;TZID=/mozilla.org/20050126_1/America/New_York:20250106T100000\r\n\
+                   RRULE:FREQ=WEEKLY;BYDAY=MO\r\n\
+                   SUMMARY:Weekly sync\r\n\
+                   BEGIN:VALARM\r\n\
+                   ACTION:DISPLAY\r\n\
+                   TRIGGER:-PT10M\r\n\
+                   END:VALARM\r\n\
+                   END:VEVENT\r\n\
+                   BEGIN:VTODO\r\n\
+                   SUMMARY:No UID here\r\n\
+                   END:VTODO\r\n\
+                   END:VCALENDAR\r\n";
+        let parsed = parse_calendar(ics).unwrap();
+        assert_eq!(parsed.entries.len(), 1);
+        assert_eq!(parsed.skipped.len(), 1);
+
+        let e = &parsed.entries[0];
+        assert_eq!(e.kind, IcsEntryKind::Reminder);
+        // 09:50 EST
+        assert_eq!(
+            e.due,
+            Some(Utc.with_ymd_and_hms(2025, 1, 6, 14, 50, 0).unwrap())
+        );
+        let recurrence = e.recurrence.as_ref().unwrap();
+        let after_dst = Utc.with_ymd_and_hms(2025, 3, 20, 0, 0, 0).unwrap();
+        // 09:50 EDT
+        assert_eq!(
+            recurrence.next_after(after_dst),
+            Some(Utc.with_ymd_and_hms(2025, 3, 24, 13, 50, 0).unwrap())
+        );
+
+        assert!(parse_calendar("BEGIN:VTODO\r\nEND:VTODO").is_err());
+    }
+}
@@ -115,6 +115,18 @@ pub enum ConsolidationEvent {
         timestamp: DateTime<Utc>,
     },
 
+    /// Fact was closed out by a newer fact contradicting it
+    FactSuperseded {
+        fact_id: String,
+        fact_content: String,
+        superseded_by: String,
+        confidence: f32,
+        support_count: usize,
+        /// When the fact stopped holding
+        valid_to: DateTime<Utc>,
+        timestamp: DateTime<Utc>,
+    },
+
     /// Memory was promoted to a higher tier
     MemoryPromoted {
         memory_id: String,
@@ -340,6 +352,10 @@ pub struct ConsolidationReport {
     /// Facts that were deleted (confidence too low)
     pub deleted_facts: Vec<FactChange>,
 
+    /// Facts closed out by a newer, contradicting fact
+    #[serde(default)]
+    pub superseded_facts: Vec<FactChange>,
+
     // SHO-105: Replay events
     /// Memories that were replayed for consolidation
     pub replayed_memories: Vec<ReplayEvent>,
@@ -434,6 +450,8 @@ pub struct ConsolidationStats {
     pub facts_reinforced: usize,
     pub facts_decayed: usize,
     pub facts_deleted: usize,
+    #[serde(default)]
+    pub facts_superseded: usize,
     pub maintenance_cycles: usize,
     pub total_maintenance_duration_ms: u64,
     // SHO-105: Replay statistics
@@ -535,6 +553,7 @@ impl ConsolidationEventBuffer {
             reinforced_facts: Vec::new(),
             decayed_facts: Vec::new(),
             deleted_facts: Vec::new(),
+            superseded_facts: Vec::new(),
             // SHO-105: Replay events
             replayed_memories: Vec::new(),
             // SHO-106: Interference events
@@ -741,6 +760,26 @@ impl ConsolidationEventBuffer {
                     report.statistics.facts_deleted += 1;
                 }
 
+                ConsolidationEvent::FactSuperseded {
+                    fact_id,
+                    fact_content,
+                    superseded_by,
+                    confidence,
+                    support_count,
+                    timestamp,
+                    ..
+                } => {
+                    report.superseded_facts.push(FactChange {
+                        fact_id: fact_id.clone(),
+                        fact_content: fact_content.clone(),
+                        confidence: *confidence,
+                        support_count: *support_count,
+                        fact_type: format!("superseded by {superseded_by}"),
+                        timestamp: *timestamp,
+                    });
+                    report.statistics.facts_superseded += 1;
+                }
+
                 ConsolidationEvent::MemoryPromoted { .. } => {
                     // Track promotions if needed
                 }
@@ -882,6 +921,7 @@ impl ConsolidationEventBuffer {
             reinforced_facts: Vec::new(),
             decayed_facts: Vec::new(),
             deleted_facts: Vec::new(),
+            superseded_facts: Vec::new(),
             replayed_memories: Vec::new(),
             interference_events: Vec::new(),
             weakened_memories: Vec::new(),
@@ -1086,6 +1126,26 @@ impl ConsolidationEventBuffer {
                     report.statistics.facts_deleted += 1;
                 }
 
+                ConsolidationEvent::FactSuperseded {
+                    fact_id,
+                    fact_content,
+                    superseded_by,
+                    confidence,
+                    support_count,
+                    timestamp,
+                    ..
+                } => {
+                    report.superseded_facts.push(FactChange {
+                        fact_id: fact_id.clone(),
+                        fact_content: fact_content.clone(),
+                        confidence: *confidence,
+                        support_count: *support_count,
+                        fact_type: format!("superseded by {superseded_by}"),
+                        timestamp: *timestamp,
+                    });
+                    report.statistics.facts_superseded += 1;
+                }
+
                 ConsolidationEvent::MemoryPromoted { .. } => {
                     // Track promotions if needed
                 }
@@ -1201,6 +1261,7 @@ impl ConsolidationEvent {
             ConsolidationEvent::FactReinforced { timestamp, .. } => *timestamp,
             ConsolidationEvent::FactDecayed { timestamp, .. } => *timestamp,
             ConsolidationEvent::FactDeleted { timestamp, .. } => *timestamp,
+            ConsolidationEvent::FactSuperseded { timestamp, .. } => *timestamp,
             ConsolidationEvent::MemoryPromoted { timestamp, .. } => *timestamp,
             ConsolidationEvent::MaintenanceCycleCompleted { timestamp, .. } => *timestamp,
             // SHO-105: Replay events
@@ -1227,6 +1288,7 @@ impl ConsolidationEvent {
     /// - EdgePotentiated: Permanent association formed (LTP)
     /// - FactExtracted: New semantic knowledge created
     /// - FactDeleted: Knowledge was lost
+    /// - FactSuperseded: Knowledge was revised by a contradicting fact
     /// - FactReinforced: Fact got stronger evidence
     /// - InterferenceDetected: Memory conflict occurred
     /// - MemoryReplayed: Consolidation strengthened this memory
@@ -1249,6 +1311,7 @@ impl ConsolidationEvent {
             ConsolidationEvent::EdgePotentiated { .. }
                 | ConsolidationEvent::FactExtracted { .. }
                 | ConsolidationEvent::FactDeleted { .. }
+                | ConsolidationEvent::FactSuperseded { .. }
                 | ConsolidationEvent::FactReinforced { .. }
                 | ConsolidationEvent::InterferenceDetected { .. }
                 | ConsolidationEvent::MemoryReplayed { .. }
@@ -1282,6 +1345,7 @@ impl Default for ConsolidationReport {
             reinforced_facts: Vec::new(),
             decayed_facts: Vec::new(),
             deleted_facts: Vec::new(),
+            superseded_facts: Vec::new(),
             // SHO-105: Replay events
             replayed_memories: Vec::new(),
             // SHO-106: Interference events
@@ -0,0 +1,601 @@
+//! Language detection and language-aware text normalization
+//!
+//! Memories arrive in English, German, Spanish and Hindi. Stemming, stop-word
+//! filtering and BM25 analysis all need to know which language a piece of text
+//! is in, so this module provides:
+//!
+//! - [`Language::detect`]: lightweight detection (script + stop-word evidence)
+//! - [`Language::is_stopword`]: per-language stop-word lists
+//! - [`TextStemmer`]: Snowball stemming for Latin-script languages and a light
+//!   suffix stripper for Hindi (Ramanathan & Rao, 2003)
+//!
+//! English is the fallback whenever the evidence is inconclusive.
+
+use rust_stemmers::{Algorithm, Stemmer};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::sync::OnceLock;
+
+/// Languages with dedicated text processing
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+pub enum Language {
+    #[default]
+    #[serde(rename = "en")]
+    English,
+    #[serde(rename = "de")]
+    German,
+    #[serde(rename = "es")]
+    Spanish,
+    #[serde(rename = "hi")]
+    Hindi,
+}
+
+impl Language {
+    /// All supported languages
+    pub const ALL: [Language; 4] = [
+        Language::English,
+        Language::German,
+        Language::Spanish,
+        Language::Hindi,
+    ];
+
+    /// ISO 639-1 code
+    pub fn code(self) -> &'static str {
+        match self {
+            Self::English => "en",
+            Self::German => "de",
+            Self::Spanish => "es",
+            Self::Hindi => "hi",
+        }
+    }
+
+    /// Parse an ISO 639-1 code or English language name (case-insensitive)
+    pub fn from_code(code: &str) -> Option<Self> {
+        match code.trim().to_lowercase().as_str() {
+            "en" | "eng" | "english" => Some(Self::English),
+            "de" | "deu" | "ger" | "german" | "deutsch" => Some(Self::German),
+            "es" | "spa" | "spanish" | "español" | "espanol" => Some(Self::Spanish),
+            "hi" | "hin" | "hindi" => Some(Self::Hindi),
+            _ => None,
+        }
+    }
+
+    /// Detect the dominant language of a text
+    ///
+    /// Devanagari script means Hindi. Latin-script text is scored by stop-word
+    /// hits plus language-specific characters (umlauts, ñ, accents, ¿¡), so
+    /// even a single word like "Häuser" is classified. Ties go to English.
+    pub fn detect(text: &str) -> Self {
+        let mut letters = 0usize;
+        let mut devanagari = 0usize;
+        let mut german_chars = 0usize;
+        let mut spanish_chars = 0usize;
+
+        for c in text.chars() {
+            if is_devanagari(c) {
+                devanagari += 1;
+                letters += 1;
+                continue;
+            }
+            if c.is_alphabetic() {
+                letters += 1;
+            }
+            match c {
+                'ä' | 'ö' | 'ü' | 'ß' | 'Ä' | 'Ö' | 'Ü' => german_chars += 1,
+                'ñ' | 'Ñ' | '¿' | '¡' | 'á' | 'é' | 'í' | 'ó' | 'ú' | 'Á' | 'É' | 'Í' | 'Ó'
+                | 'Ú' => spanish_chars += 1,
+                _ => {}
+            }
+        }
+
+        if letters == 0 {
+            return Self::English;
+        }
+        if devanagari * 3 >= letters {
+            return Self::Hindi;
+        }
+
+        let mut scores = [0usize; 3];
+        for word in words(text) {
+            let lower = word.to_lowercase();
+            for (score, lang) in scores
+                .iter_mut()
+                .zip([Self::English, Self::German, Self::Spanish])
+            {
+                if lang.is_stopword(&lower) {
+                    *score += 1;
+                }
+            }
+        }
+        scores[1] += german_chars;
+        scores[2] += spanish_chars;
+
+        let [english, german, spanish] = scores;
+        if german > english && german >= spanish {
+            Self::German
+        } else if spanish > english && spanish > german {
+            Self::Spanish
+        } else {
+            Self::English
+        }
+    }
+
+    /// Check whether a lowercase word is a stop word in this language
+    pub fn is_stopword(self, word: &str) -> bool {
+        self.stopwords().contains(word)
+    }
+
+    /// Stop words for this language (lowercase)
+    pub fn stopwords(self) -> &'static HashSet<&'static str> {
+        static ENGLISH: OnceLock<HashSet<&'static str>> = OnceLock::new();
+        static GERMAN: OnceLock<HashSet<&'static str>> = OnceLock::new();
+        static SPANISH: OnceLock<HashSet<&'static str>> = OnceLock::new();
+        static HINDI: OnceLock<HashSet<&'static str>> = OnceLock::new();
+
+        match self {
+            Self::English => ENGLISH.get_or_init(|| ENGLISH_STOPWORDS.iter().copied().collect()),
+            Self::German => GERMAN.get_or_init(|| GERMAN_STOPWORDS.iter().copied().collect()),
+            Self::Spanish => SPANISH.get_or_init(|| SPANISH_STOPWORDS.iter().copied().collect()),
+            Self::Hindi => HINDI.get_or_init(|| HINDI_STOPWORDS.iter().copied().collect()),
+        }
+    }
+
+    /// Stemmer for this language
+    pub fn stemmer(self) -> TextStemmer {
+        TextStemmer::new(self)
+    }
+}
+
+impl std::fmt::Display for Language {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.code())
+    }
+}
+
+/// Language-aware stemmer
+///
+/// Expects lowercase input, like `rust_stemmers::Stemmer`.
+pub struct TextStemmer {
+    language: Language,
+    snowball: Option<Stemmer>,
+}
+
+impl TextStemmer {
+    pub fn new(language: Language) -> Self {
+        let algorithm = match language {
+            Language::English => Some(Algorithm::English),
+            Language::German => Some(Algorithm::German),
+            Language::Spanish => Some(Algorithm::Spanish),
+            Language::Hindi => None,
+        };
+        Self {
+            language,
+            snowball: algorithm.map(Stemmer::create),
+        }
+    }
+
+    pub fn language(&self) -> Language {
+        self.language
+    }
+
+    /// Stem a single lowercase word
+    pub fn stem(&self, word: &str) -> String {
+        match self.snowball {
+            Some(ref stemmer) => stemmer.stem(word).into_owned(),
+            None => stem_hindi(word).to_string(),
+        }
+    }
+}
+
+/// Stems to try when looking up a word whose language is uncertain
+///
+/// Returns the stem in `language` followed by the English stem (if different),
+/// so lookups fall back to English the same way detection does.
+pub fn stem_candidates(word: &str, language: Language) -> Vec<String> {
+    let lower = word.to_lowercase();
+    let mut stems = vec![language.stemmer().stem(&lower)];
+    if language != Language::English {
+        let english = Language::English.stemmer().stem(&lower);
+        if !stems.contains(&english) {
+            stems.push(english);
+        }
+    }
+    stems
+}
+
+/// Whether a character belongs inside a word
+///
+/// `char::is_alphanumeric` rejects Devanagari vowel signs and viramas, which
+/// would split Hindi words apart. The danda (।, ॥) is punctuation.
+pub fn is_word_char(c: char) -> bool {
+    c.is_alphanumeric() || (is_devanagari(c) && !matches!(c, '\u{0964}' | '\u{0965}'))
+}
+
+/// Split text into words using [`is_word_char`]
+pub fn words(text: &str) -> impl Iterator<Item = &str> {
+    text.split(|c: char| !is_word_char(c))
+        .filter(|w| !w.is_empty())
+}
+
+fn is_devanagari(c: char) -> bool {
+    ('\u{0900}'..='\u{097F}').contains(&c)
+}
+
+/// Hindi suffixes grouped by length (in chars), longest first
+const HINDI_SUFFIXES: [&[&str]; 5] = [
+    &["ाएंगी", "ाएंगे", "ाऊंगी", "ाऊंगा", "ाइयाँ", "ाइयों", "ाइयां"],
+    &[
+        "ाएगी",
+        "ाएगा",
+        "ाओगी",
+        "ाओगे",
+        "एंगी",
+        "ेंगी",
+        "एंगे",
+        "ेंगे",
+        "ूंगी",
+        "ूंगा",
+        "ातीं",
+        "नाओं",
+        "नाएं",
+        "ताओं",
+        "ताएं",
+        "ियाँ",
+        "ियों",
+        "ियां",
+    ],
+    &[
+        "ाकर",
+        "ाइए",
+        "ाईं",
+        "ाया",
+        "ेगी",
+        "ेगा",
+        "ोगी",
+        "ोगे",
+        "ाने",
+        "ाना",
+        "ाते",
+        "ाती",
+        "ाता",
+        "तीं",
+        "ाओं",
+        "ाएं",
+        "ुओं",
+        "ुएं",
+        "ुआं",
+    ],
+    &[
+        "कर", "ाओ", "िए", "ाई", "ाए", "ने", "नी", "ना", "ते", "ीं", "ती", "ता", "ां", "ों", "ें",
+    ],
+    &["ो", "े", "ू", "ु", "ी", "ि", "ा"],
+];
+
+/// Light Hindi stemmer: strip the longest matching inflectional suffix
+fn stem_hindi(word: &str) -> &str {
+    let len = word.chars().count();
+    for group in HINDI_SUFFIXES {
+        for suffix in group {
+            // Keep at least two characters of stem
+            if len >= suffix.chars().count() + 2 {
+                if let Some(stem) = word.strip_suffix(suffix) {
+                    return stem;
+                }
+            }
+        }
+    }
+    word
+}
+
+const ENGLISH_STOPWORDS: &[&str] = &[
+    "a",
+    "about",
+    "above",
+    "after",
+    "again",
+    "against",
+    "all",
+    "am",
+    "an",
+    "and",
+    "any",
+    "are",
+    "as",
+    "at",
+    "be",
+    "because",
+    "been",
+    "before",
+    "being",
+    "below",
+    "between",
+    "both",
+    "but",
+    "by",
+    "can",
+    "could",
+    "did",
+    "do",
+    "does",
+    "doing",
+    "down",
+    "during",
+    "each",
+    "few",
+    "for",
+    "from",
+    "further",
+    "had",
+    "has",
+    "have",
+    "having",
+    "he",
+    "her",
+    "here",
+    "hers",
+    "herself",
+    "him",
+    "himself",
+    "his",
+    "how",
+    "i",
+    "if",
+    "in",
+    "into",
+    "is",
+    "it",
+    "its",
+    "itself",
+    "just",
+    "me",
+    "might",
+    "more",
+    "most",
+    "must",
+    "my",
+    "myself",
+    "no",
+    "nor",
+    "not",
+    "now",
+    "of",
+    "off",
+    "on",
+    "once",
+    "only",
+    "or",
+    "other",
+    "our",
+    "ours",
+    "ourselves",
+    "out",
+    "over",
+    "own",
+    "same",
+    "shall",
+    "she",
+    "should",
+    "so",
+    "some",
+    "such",
+    "than",
+    "that",
+    "the",
+    "their",
+    "theirs",
+    "them",
+    "themselves",
+    "then",
+    "there",
+    "these",
+    "they",
+    "this",
+    "those",
+    "through",
+    "to",
+    "too",
+    "under",
+    "until",
+    "up",
+    "very",
+    "was",
+    "we",
+    "were",
+    "what",
+    "when",
+    "where",
+    "which",
+    "while",
+    "who",
+    "whom",
+    "why",
+    "will",
+    "with",
+    "would",
+    "you",
+    "your",
+    "yours",
+    "yourself",
+    "yourselves",
+];
+
+const GERMAN_STOPWORDS: &[&str] = &[
+    "aber", "alle", "allem", "allen", "aller", "alles", "als", "also", "am", "an", "ander",
+    "andere", "anderem", "anderen", "anderer", "anderes", "auch", "auf", "aus", "bei", "bin",
+    "bis", "bist", "da", "damit", "dann", "das", "dass", "daß", "dein", "deine", "dem", "den",
+    "der", "des", "dich", "die", "dir", "doch", "dort", "du", "durch", "ein", "eine", "einem",
+    "einen", "einer", "eines", "er", "es", "etwas", "euch", "euer", "für", "gegen", "gewesen",
+    "hab", "habe", "haben", "hat", "hatte", "hatten", "hier", "hin", "hinter", "ich", "ihm", "ihn",
+    "ihr", "ihre", "ihrem", "ihren", "im", "in", "indem", "ins", "ist", "jede", "jedem", "jeden",
+    "jeder", "jedes", "jetzt", "kann", "kein", "keine", "können", "könnte", "man", "manche",
+    "mein", "meine", "meinem", "meinen", "mich", "mir", "mit", "muss", "musste", "nach", "nicht",
+    "nichts", "noch", "nun", "nur", "ob", "oder", "ohne", "sehr", "sein", "seine", "seinem",
+    "seinen", "sich", "sie", "sind", "so", "solche", "soll", "sollte", "sondern", "sonst", "über",
+    "um", "und", "uns", "unser", "unsere", "unter", "viel", "vom", "von", "vor", "während", "war",
+    "waren", "warst", "was", "weil", "weiter", "welche", "wenn", "werde", "werden", "wie",
+    "wieder", "will", "wir", "wird", "wirst", "wo", "wollen", "wollte", "würde", "würden", "zu",
+    "zum", "zur", "zwar", "zwischen",
+];
+
+const SPANISH_STOPWORDS: &[&str] = &[
+    "a", "al", "algo", "algunas", "algunos", "ante", "antes", "como", "con", "contra", "cual",
+    "cuando", "de", "del", "desde", "donde", "durante", "e", "el", "él", "ella", "ellas", "ellos",
+    "en", "entre", "era", "eran", "es", "esa", "esas", "ese", "eso", "esos", "esta", "está",
+    "están", "estas", "este", "esto", "estos", "estoy", "fue", "fueron", "ha", "había", "han",
+    "hasta", "hay", "la", "las", "le", "les", "lo", "los", "más", "me", "mi", "mis", "mucho",
+    "muy", "nada", "ni", "no", "nos", "nosotros", "o", "os", "otra", "otro", "para", "pero",
+    "poco", "por", "porque", "que", "qué", "quien", "se", "sea", "según", "ser", "si", "sí", "sin",
+    "sobre", "son", "su", "sus", "también", "tanto", "te", "tengo", "ti", "tiene", "tienen",
+    "todo", "todos", "tu", "tus", "un", "una", "uno", "unos", "y", "ya", "yo",
+];
+
+const HINDI_STOPWORDS: &[&str] = &[
+    "अपना",
+    "अपनी",
+    "अपने",
+    "अब",
+    "आप",
+    "इन",
+    "इस",
+    "इसका",
+    "इसकी",
+    "इसके",
+    "उन",
+    "उस",
+    "उसका",
+    "उसकी",
+    "उसके",
+    "एक",
+    "और",
+    "कर",
+    "करना",
+    "करने",
+    "का",
+    "कि",
+    "किया",
+    "किए",
+    "की",
+    "कुछ",
+    "के",
+    "को",
+    "कोई",
+    "क्या",
+    "गई",
+    "गए",
+    "गया",
+    "जब",
+    "जो",
+    "तक",
+    "तब",
+    "तो",
+    "था",
+    "थी",
+    "थे",
+    "दिया",
+    "द्वारा",
+    "न",
+    "नहीं",
+    "ने",
+    "पर",
+    "फिर",
+    "बहुत",
+    "भी",
+    "मेरा",
+    "मेरी",
+    "मेरे",
+    "मैं",
+    "में",
+    "यह",
+    "या",
+    "ये",
+    "रहा",
+    "रही",
+    "रहे",
+    "लिए",
+    "लेकिन",
+    "वह",
+    "वे",
+    "सकता",
+    "साथ",
+    "से",
+    "हम",
+    "हि",
+    "ही",
+    "हुआ",
+    "हुई",
+    "हुए",
+    "है",
+    "हैं",
+    "हो",
+    "होता",
+    "होती",
+    "होने",
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_language() {
+        assert_eq!(
+            Language::detect("I met Anna at the office and we talked about the project"),
+            Language::English
+        );
+        assert_eq!(
+            Language::detect("Ich habe gestern mit Anna über das Projekt gesprochen"),
+            Language::German
+        );
+        assert_eq!(
+            Language::detect("Ayer hablé con Ana sobre el proyecto en la oficina"),
+            Language::Spanish
+        );
+        assert_eq!(
+            Language::detect("मैंने कल अनु से परियोजना के बारे में बात की"),
+            Language::Hindi
+        );
+        assert_eq!(Language::detect("Häuser"), Language::German);
+        assert_eq!(Language::detect("Rust"), Language::English);
+        assert_eq!(Language::detect(""), Language::English);
+    }
+
+    #[test]
+    fn test_stemming_per_language() {
+        assert_eq!(Language::English.stemmer().stem("running"), "run");
+        assert_eq!(
+            Language::German.stemmer().stem("häuser"),
+            Language::German.stemmer().stem("haus")
+        );
+        assert_eq!(
+            Language::Spanish.stemmer().stem("proyectos"),
+            Language::Spanish.stemmer().stem("proyecto")
+        );
+        // लड़कों (boys) and लड़का (boy) share the stem लड़क
+        assert_eq!(Language::Hindi.stemmer().stem("लड़कों"), "लड़क");
+        assert_eq!(Language::Hindi.stemmer().stem("लड़का"), "लड़क");
+        // Short words are left alone
+        assert_eq!(Language::Hindi.stemmer().stem("का"), "का");
+    }
+
+    #[test]
+    fn test_stopwords_and_words() {
+        assert!(Language::German.is_stopword("und"));
+        assert!(Language::Spanish.is_stopword("para"));
+        assert!(Language::Hindi.is_stopword("में"));
+        assert!(!Language::English.is_stopword("und"));
+
+        let hindi: Vec<&str> = words("लड़कों ने, किताबें पढ़ीं।").collect();
+        assert_eq!(hindi, vec!["लड़कों", "ने", "किताबें", "पढ़ीं"]);
+    }
+
+    #[test]
+    fn test_codes_round_trip() {
+        for lang in Language::ALL {
+            assert_eq!(Language::from_code(lang.code()), Some(lang));
+            let json = serde_json::to_string(&lang).unwrap();
+            assert_eq!(json, format!("\"{}\"", lang.code()));
+        }
+        assert_eq!(Language::from_code("Deutsch"), Some(Language::German));
+        assert_eq!(Language::from_code("fr"), None);
+    }
+}
@@ -76,6 +76,8 @@ pub enum LearningEventType {
     ReplayCycleCompleted,
     /// Maintenance cycle completed (summary)
     MaintenanceCycleCompleted,
+    /// Fact was closed out by a newer, contradicting fact
+    FactSuperseded,
 }
 
 impl LearningEventType {
@@ -90,6 +92,7 @@ impl LearningEventType {
             Self::MemoryPromoted => "memory_promoted",
             Self::ReplayCycleCompleted => "replay_cycle_completed",
             Self::MaintenanceCycleCompleted => "maintenance_cycle_completed",
+            Self::FactSuperseded => "fact_superseded",
         }
     }
 }
@@ -236,6 +239,12 @@ impl LearningHistoryStore {
                 None,
                 Some(fact_id.clone()),
             ),
+            ConsolidationEvent::FactSuperseded { fact_id, .. } => (
+                LearningEventType::FactSuperseded,
+                None,
+                None,
+                Some(fact_id.clone()),
+            ),
             ConsolidationEvent::InterferenceDetected {
                 new_memory_id,
                 old_memory_id,
@@ -13,6 +13,7 @@
 //! - `lineage:by_to:{user_id}:{to_id}:{edge_id}` - Index by target memory
 //! - `lineage:branches:{user_id}:{branch_id}` - Branch metadata
 //! - `lineage:branch_members:{user_id}:{branch_id}:{memory_id}` - Memories in branch
+//! - `lineage:postmortems:{user_id}:{subject_id}` - Latest post-mortem per todo/project
 
 use anyhow::Result;
 use chrono::{DateTime, Utc};
@@ -749,13 +750,41 @@ impl LineageGraph {
         direction: TraceDirection,
         max_depth: usize,
     ) -> Result<LineageTrace> {
+        self.trace_from(
+            user_id,
+            std::slice::from_ref(memory_id),
+            direction,
+            max_depth,
+        )
+    }
+
+    /// Trace lineage from several memories at once
+    ///
+    /// The roots share one traversal, so a memory reachable from more than
+    /// one root appears once. The first root is reported as the trace root.
+    pub fn trace_from(
+        &self,
+        user_id: &str,
+        roots: &[MemoryId],
+        direction: TraceDirection,
+        max_depth: usize,
+    ) -> Result<LineageTrace> {
+        let Some(first) = roots.first() else {
+            anyhow::bail!("trace needs at least one root memory");
+        };
+
         let mut visited = HashSet::new();
         let mut edges = Vec::new();
-        let mut path = vec![memory_id.clone()];
+        let mut path = Vec::new();
         let mut queue: VecDeque<(MemoryId, usize)> = VecDeque::new();
 
-        queue.push_back((memory_id.clone(), 0));
-        visited.insert(memory_id.clone());
+        for root in roots {
+            if visited.insert(root.clone()) {
+                path.push(root.clone());
+                queue.push_back((root.clone(), 0));
+            }
+        }
+        let root_count = path.len();
 
         while let Some((current_id, depth)) = queue.pop_front() {
             if depth >= max_depth {
@@ -794,9 +823,9 @@ impl LineageGraph {
             }
         }
 
-        let depth = path.len().saturating_sub(1);
+        let depth = path.len().saturating_sub(root_count);
         Ok(LineageTrace {
-            root: memory_id.clone(),
+            root: first.clone(),
             direction,
             edges,
             path,
@@ -860,6 +889,44 @@ impl LineageGraph {
         Ok(edges.iter().any(|e| &e.to == to))
     }
 
+    // =========================================================================
+    // POST-MORTEM STORAGE
+    // =========================================================================
+
+    /// Store the post-mortem for a todo or project, replacing any earlier one
+    pub fn store_post_mortem(&self, user_id: &str, post_mortem: &PostMortem) -> Result<()> {
+        let key = format!("lineage:postmortems:{}:{}", user_id, post_mortem.task_id.0);
+        let value = bincode::serde::encode_to_vec(post_mortem, bincode::config::standard())?;
+        self.db.put(key.as_bytes(), &value)?;
+        Ok(())
+    }
+
+    /// Get the stored post-mortem for a todo or project
+    pub fn get_post_mortem(&self, user_id: &str, subject_id: &Uuid) -> Result<Option<PostMortem>> {
+        let key = format!("lineage:postmortems:{}:{}", user_id, subject_id);
+        match self.db.get(key.as_bytes())? {
+            Some(data) => {
+                let (post_mortem, _): (PostMortem, _) =
+                    bincode::serde::decode_from_slice(&data, bincode::config::standard())?;
+                Ok(Some(post_mortem))
+            }
+            None => Ok(None),
+        }
+    }
+
+    /// Delete every edge touching a memory, returning how many were removed
+    pub fn delete_edges_for(&self, user_id: &str, memory_id: &MemoryId) -> Result<usize> {
+        let mut edges = self.get_edges_from(user_id, memory_id)?;
+        edges.extend(self.get_edges_to(user_id, memory_id)?);
+        let mut deleted = 0;
+        for edge in edges {
+            if self.delete_edge(user_id, &edge.id)? {
+                deleted += 1;
+            }
+        }
+        Ok(deleted)
+    }
+
     // =========================================================================
     // STATISTICS
     // =========================================================================
@@ -903,6 +970,22 @@ impl LineageGraph {
 // POST-MORTEM GENERATION
 // =========================================================================
 
+/// Tag carried by the `Learning` memory a post-mortem is stored as
+pub const POST_MORTEM_TAG: &str = "post-mortem";
+
+/// What a post-mortem is written about: a finished todo or project
+#[derive(Debug, Clone)]
+pub struct PostMortemSubject {
+    /// ID of the todo or project (stored as the post-mortem's `task_id`)
+    pub id: MemoryId,
+    /// Todo content or project name
+    pub content: String,
+    /// Memories explicitly linked to the subject
+    pub seeds: Vec<MemoryId>,
+    /// Tags whose memories also seed the trace (`todo:BOLT-1`, `project:bolt`)
+    pub seed_tags: Vec<String>,
+}
+
 /// Summary of learnings from a completed task
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PostMortem {
@@ -918,8 +1001,17 @@ pub struct PostMortem {
     pub errors_resolved: Vec<String>,
     /// Patterns discovered
     pub patterns: Vec<String>,
+    /// Memories where the traced chain starts (causes with no cause of their own)
+    #[serde(default)]
+    pub root_causes: Vec<String>,
+    /// Approaches that were later superseded
+    #[serde(default)]
+    pub dead_ends: Vec<String>,
     /// Related memory IDs in the lineage
     pub related_memories: Vec<MemoryId>,
+    /// Learning memory this post-mortem was stored as, if any
+    #[serde(default)]
+    pub memory_id: Option<MemoryId>,
     /// When the post-mortem was generated
     pub generated_at: DateTime<Utc>,
 }
@@ -936,6 +1028,10 @@ impl PostMortem {
         let mut decisions = Vec::new();
         let mut errors_resolved = Vec::new();
         let mut patterns = Vec::new();
+        let mut root_causes = Vec::new();
+        let mut dead_ends = Vec::new();
+
+        let content_of = |id: &MemoryId| memories.get(id).map(|m| m.experience.content.clone());
 
         for mem_id in &trace.path {
             if let Some(memory) = memories.get(mem_id) {
@@ -955,8 +1051,22 @@ impl PostMortem {
                     _ => {}
                 }
             }
+
+            // A root cause leads somewhere but nothing in the trace led to it
+            let is_cause = trace.edges.iter().any(|e| &e.from == mem_id);
+            let is_effect = trace.edges.iter().any(|e| &e.to == mem_id);
+            if is_cause && !is_effect {
+                root_causes.extend(content_of(mem_id));
+            }
         }
 
+        for edge in &trace.edges {
+            if edge.relation == CausalRelation::SupersededBy {
+                dead_ends.extend(content_of(&edge.from));
+            }
+        }
+        dead_ends.dedup();
+
         PostMortem {
             task_id,
             summary: format!("Completed: {}", task_content),
@@ -964,7 +1074,10 @@ impl PostMortem {
             decisions,
             errors_resolved,
             patterns,
+            root_causes,
+            dead_ends,
             related_memories: trace.path.clone(),
+            memory_id: None,
             generated_at: Utc::now(),
         }
     }
@@ -980,6 +1093,14 @@ impl PostMortem {
             self.generated_at.format("%Y-%m-%d %H:%M UTC")
         ));
 
+        if !self.root_causes.is_empty() {
+            md.push_str("## Root Causes\n");
+            for cause in &self.root_causes {
+                md.push_str(&format!("- {}\n", cause));
+            }
+            md.push('\n');
+        }
+
         if !self.learnings.is_empty() {
             md.push_str("## Learnings\n");
             for learning in &self.learnings {
@@ -1012,6 +1133,14 @@ impl PostMortem {
             md.push('\n');
         }
 
+        if !self.dead_ends.is_empty() {
+            md.push_str("## Dead Ends\n");
+            for dead_end in &self.dead_ends {
+                md.push_str(&format!("- {}\n", dead_end));
+            }
+            md.push('\n');
+        }
+
         md.push_str(&format!(
             "---\n*Related memories: {}*\n",
             self.related_memories.len()
@@ -1189,6 +1318,97 @@ mod tests {
         assert!(graph.get_edge("user-1", &edge2.id).unwrap().is_none());
     }
 
+    #[test]
+    fn test_trace_from_multiple_roots() {
+        let (graph, _dir) = create_test_graph();
+        let a = MemoryId(Uuid::new_v4());
+        let b = MemoryId(Uuid::new_v4());
+        let shared = MemoryId(Uuid::new_v4());
+
+        graph
+            .add_explicit_edge("user-1", a.clone(), shared.clone(), CausalRelation::Caused)
+            .unwrap();
+        graph
+            .add_explicit_edge("user-1", b.clone(), shared.clone(), CausalRelation::Caused)
+            .unwrap();
+
+        let trace = graph
+            .trace_from(
+                "user-1",
+                &[a.clone(), b.clone()],
+                TraceDirection::Forward,
+                5,
+            )
+            .unwrap();
+        assert_eq!(trace.root, a);
+        assert_eq!(trace.path, vec![a, b, shared]);
+        assert_eq!(trace.edges.len(), 1);
+        assert_eq!(trace.depth, 1);
+    }
+
+    #[test]
+    fn test_post_mortem_root_causes_and_dead_ends() {
+        let (graph, _dir) = create_test_graph();
+        let mut error = create_test_memory(ExperienceType::Error, vec!["auth"]);
+        error.experience.content = "Login times out".to_string();
+        let mut first_try = create_test_memory(ExperienceType::Decision, vec!["auth"]);
+        first_try.experience.content = "Raise the proxy timeout".to_string();
+        let mut fix = create_test_memory(ExperienceType::Learning, vec!["auth"]);
+        fix.experience.content = "Sessions expired early".to_string();
+
+        graph
+            .add_explicit_edge(
+                "user-1",
+                error.id.clone(),
+                first_try.id.clone(),
+                CausalRelation::Caused,
+            )
+            .unwrap();
+        graph
+            .add_explicit_edge(
+                "user-1",
+                first_try.id.clone(),
+                fix.id.clone(),
+                CausalRelation::SupersededBy,
+            )
+            .unwrap();
+
+        let trace = graph
+            .trace_from(
+                "user-1",
+                std::slice::from_ref(&fix.id),
+                TraceDirection::Both,
+                5,
+            )
+            .unwrap();
+        let memories: HashMap<MemoryId, Memory> = [error, first_try, fix]
+            .into_iter()
+            .map(|m| (m.id.clone(), m))
+            .collect();
+        let task_id = MemoryId(Uuid::new_v4());
+        let post_mortem = PostMortem::from_trace(task_id.clone(), "Fix login", &trace, &memories);
+
+        assert_eq!(post_mortem.root_causes, vec!["Login times out"]);
+        assert_eq!(post_mortem.dead_ends, vec!["Raise the proxy timeout"]);
+        assert_eq!(post_mortem.learnings, vec!["Sessions expired early"]);
+        assert_eq!(post_mortem.related_memories.len(), 3);
+
+        let markdown = post_mortem.to_markdown();
+        assert!(markdown.contains("## Root Causes\n- Login times out"));
+        assert!(markdown.contains("## Dead Ends\n- Raise the proxy timeout"));
+
+        graph.store_post_mortem("user-1", &post_mortem).unwrap();
+        let stored = graph
+            .get_post_mortem("user-1", &task_id.0)
+            .unwrap()
+            .unwrap();
+        assert_eq!(stored.root_causes, post_mortem.root_causes);
+        assert!(graph
+            .get_post_mortem("user-2", &task_id.0)
+            .unwrap()
+            .is_none());
+    }
+
     #[test]
     fn test_lineage_stats() {
         let (graph, _dir) = create_test_graph();
@@ -10,11 +10,15 @@ pub mod compression;
 pub mod context;
 pub mod facts;
 pub mod feedback;
+pub mod file_watcher;
 pub mod files;
+pub mod gitignore;
 pub mod graph_retrieval;
 pub mod hybrid_search;
+pub mod ics;
 pub mod injection;
 pub mod introspection;
+pub mod language;
 pub mod learning_history;
 pub mod lineage;
 pub mod pattern_detection;
@@ -22,9 +26,11 @@ pub mod prospective;
 pub mod query_parser;
 pub mod replay;
 pub mod retrieval;
+pub mod rrule;
 pub mod segmentation;
 pub mod sessions;
 pub mod storage;
+pub mod symbols;
 pub mod temporal_facts;
 pub mod todo_formatter;
 pub mod todos;
@@ -50,6 +56,7 @@ use crate::constants::{
     DEFAULT_COMPRESSION_AGE_DAYS, DEFAULT_IMPORTANCE_THRESHOLD, DEFAULT_MAX_HEAP_PER_USER_MB,
     DEFAULT_SESSION_MEMORY_SIZE_MB, DEFAULT_WORKING_MEMORY_SIZE, EDGE_SEMANTIC_WEIGHT_FLOOR,
     ESTIMATED_BYTES_PER_MEMORY, HEBBIAN_BOOST_HELPFUL, HEBBIAN_DECAY_MISLEADING,
+    POSTMORTEM_MAX_MEMORIES, POSTMORTEM_MAX_TAGGED_SEEDS, POSTMORTEM_TRACE_DEPTH,
     POTENTIATION_ACCESS_THRESHOLD, POTENTIATION_MAINTENANCE_BOOST, TIER_PROMOTION_SESSION_AGE_SECS,
     TIER_PROMOTION_SESSION_IMPORTANCE, TIER_PROMOTION_WORKING_AGE_SECS,
     TIER_PROMOTION_WORKING_IMPORTANCE,
@@ -58,6 +65,7 @@ use crate::constants::{
 use crate::memory::storage::{MemoryStorage, SearchCriteria};
 pub use crate::memory::types::*;
 // pub use crate::memory::vector_storage::{VectorIndexedMemoryStorage, StorageStats};  // Disabled
+use crate::embeddings::cross_encoder::{CrossEncoder, CrossEncoderConfig};
 use crate::embeddings::Embedder;
 use crate::memory::compression::CompressionPipeline;
 pub use crate::memory::compression::{
@@ -77,19 +85,20 @@ pub use crate::memory::graph_retrieval::{
 };
 pub use crate::memory::hybrid_search::{
     BM25Index, CrossEncoderReranker, HybridSearchConfig, HybridSearchEngine, HybridSearchResult,
-    RRFusion,
+    RRFusion, RerankerModel,
 };
 pub use crate::memory::introspection::{
     AssociationChange, ConsolidationEvent, ConsolidationEventBuffer, ConsolidationReport,
     ConsolidationStats, EdgeFormationReason, FactChange, InterferenceEvent, InterferenceType,
     MemoryChange, PruningReason, ReplayEvent, ReportPeriod, StrengtheningReason,
 };
+pub use crate::memory::language::Language;
 pub use crate::memory::learning_history::{
     LearningEventType, LearningHistoryStore, LearningStats, LearningVelocity, StoredLearningEvent,
 };
 pub use crate::memory::lineage::{
     CausalRelation, InferenceConfig, LineageBranch, LineageEdge, LineageGraph, LineageSource,
-    LineageStats, LineageTrace, PostMortem, TraceDirection,
+    LineageStats, LineageTrace, PostMortem, PostMortemSubject, TraceDirection, POST_MORTEM_TAG,
 };
 pub use crate::memory::prospective::ProspectiveStore;
 pub use crate::memory::replay::{
@@ -105,11 +114,13 @@ pub use crate::memory::segmentation::{
     AtomicMemory, DeduplicationEngine, DeduplicationResult, InputSource, SegmentationEngine,
 };
 pub use crate::memory::sessions::{
-    Session, SessionEvent, SessionId, SessionStats, SessionStatus, SessionStore, SessionStoreStats,
-    SessionSummary, TemporalContext, TimeOfDay,
+    Session, SessionEvent, SessionId, SessionPage, SessionQuery, SessionRetention, SessionStats,
+    SessionStatus, SessionStore, SessionStoreStats, SessionSummary, TemporalContext, TimeOfDay,
 };
 pub use crate::memory::temporal_facts::{EventType, ResolvedTime, TemporalFact, TemporalFactStore};
-pub use crate::memory::todos::{ProjectStats, TodoStore, UserTodoStats};
+pub use crate::memory::todos::{
+    DependencyEdge, ProjectStats, TodoDependencyGraph, TodoStore, UserTodoStats,
+};
 pub use crate::memory::visualization::{GraphStats, MemoryLogger};
 
 /// Configuration for the memory system
@@ -167,7 +178,7 @@ pub struct MemorySystem {
     retriever: RetrievalEngine,
 
     /// Embedder for semantic search
-    embedder: Arc<crate::embeddings::minilm::MiniLMEmbedder>,
+    embedder: Arc<dyn Embedder>,
 
     /// Query embedding cache - SHA256(query_text) → embedding
     /// Uses SHA256 for stable hashing across restarts (unlike DefaultHasher)
@@ -269,21 +280,39 @@ fn build_ner_lookup(
 
 impl MemorySystem {
     /// Create a new memory system
+    ///
+    /// The embedding backend is selected from the environment
+    /// (see [`crate::embeddings::EmbeddingBackend::from_env`]).
     pub fn new(config: MemoryConfig) -> Result<Self> {
+        let embedder = crate::embeddings::EmbeddingBackend::from_env()?.build()?;
+        Self::with_embedder(config, embedder)
+    }
+
+    /// Create a memory system using an existing embedder
+    ///
+    /// CRITICAL: The embedder is shared between MemorySystem, RetrievalEngine and
+    /// hybrid search so the model is loaded once (50-200ms overhead per load).
+    /// If the stored vectors were built by a different model they are re-embedded.
+    pub fn with_embedder(config: MemoryConfig, embedder: Arc<dyn Embedder>) -> Result<Self> {
+        let cross_encoder = Arc::new(CrossEncoder::new(CrossEncoderConfig::default()));
+        Self::with_models(config, embedder, cross_encoder)
+    }
+
+    /// Create a memory system using an existing embedder and reranking model
+    ///
+    /// Servers hosting many users pass the same cross-encoder to every memory
+    /// system so its ONNX session is loaded once, like the embedder.
+    pub fn with_models(
+        config: MemoryConfig,
+        embedder: Arc<dyn Embedder>,
+        cross_encoder: Arc<CrossEncoder>,
+    ) -> Result<Self> {
         let storage_path = config.storage_path.clone();
         let storage = Arc::new(
             MemoryStorage::new(&storage_path)
                 .with_context(|| format!("Failed to open storage at {:?}", storage_path))?,
         );
 
-        // CRITICAL: Initialize embedder ONCE and share between MemorySystem and RetrievalEngine
-        // This prevents loading the ONNX model multiple times (50-200ms overhead per load)
-        let embedding_config = crate::embeddings::minilm::EmbeddingConfig::default();
-        let embedder = Arc::new(
-            crate::embeddings::minilm::MiniLMEmbedder::new(embedding_config)
-                .context("Failed to initialize MiniLM embedder (ONNX model)")?,
-        );
-
         // Create consolidation event buffer first so we can share it with retriever
         let consolidation_events = Arc::new(RwLock::new(ConsolidationEventBuffer::new()));
 
@@ -400,6 +429,7 @@ impl MemorySystem {
                 average_importance: storage_stats.average_importance,
                 graph_nodes: 0, // Loaded separately from GraphMemory
                 graph_edges: 0, // Loaded separately from GraphMemory
+                agents: Default::default(),
             }
         };
 
@@ -413,10 +443,11 @@ impl MemorySystem {
 
         // Initialize hybrid search engine (BM25 + Vector + RRF + Reranking)
         let bm25_path = storage_path.join("bm25_index");
-        let hybrid_search_config = hybrid_search::HybridSearchConfig::default();
+        let hybrid_search_config = hybrid_search::HybridSearchConfig::from_env();
         let hybrid_search_engine = hybrid_search::HybridSearchEngine::new(
             &bm25_path,
             embedder.clone(),
+            cross_encoder,
             hybrid_search_config,
         )
         .context("Failed to initialize hybrid search engine")?;
@@ -433,11 +464,13 @@ impl MemorySystem {
                 );
 
                 let memories_iter = existing_memories.into_iter().map(|mem| {
+                    let language = mem.experience.detected_language();
                     (
                         mem.id,
                         mem.experience.content,
                         mem.experience.tags,
                         mem.experience.entities,
+                        language,
                     )
                 });
 
@@ -479,7 +512,7 @@ impl MemorySystem {
             Arc::new(RwLock::new(detector))
         };
 
-        Ok(Self {
+        let system = Self {
             config: config.clone(),
             working_memory: Arc::new(RwLock::new(WorkingMemory::new(config.working_memory_size))),
             session_memory: Arc::new(RwLock::new(SessionMemory::new(
@@ -516,7 +549,66 @@ impl MemorySystem {
             learning_history,
             // Temporal fact store for multi-hop temporal reasoning
             temporal_fact_store,
-        })
+        };
+
+        // Re-embedding a whole corpus is too slow to do while opening a store
+        // (this runs on the first request for a user), so only report it and
+        // leave the migration to POST /api/index/reembed.
+        if system.requires_reembed()? {
+            tracing::warn!(
+                path = %storage_path.display(),
+                model = %system.embedder.model_id(),
+                dimension = system.embedder.dimension(),
+                "Stored vectors were built by a different embedding model; semantic recall is \
+                 degraded until the store is re-embedded"
+            );
+        } else {
+            crate::embeddings::EmbeddingFingerprint::of(system.embedder.as_ref())
+                .save(&storage_path)?;
+        }
+
+        Ok(system)
+    }
+
+    /// Whether stored vectors were built by a different embedding model
+    pub fn requires_reembed(&self) -> Result<bool> {
+        let fingerprint = crate::embeddings::EmbeddingFingerprint::of(self.embedder.as_ref());
+        let has_data = || self.long_term_memory.count_vector_mappings() > 0;
+        fingerprint.requires_reembed(&self.config.storage_path, has_data)
+    }
+
+    /// Re-embed all memories and facts with the current embedder
+    ///
+    /// Rebuilds the vector index from scratch. Needed after the embedding
+    /// model or dimension changed (see `requires_reembed`); returns the
+    /// number of memories re-embedded.
+    pub fn reembed(&self) -> Result<usize> {
+        let reembedded = self.retriever.reembed_all()?;
+
+        let mut facts = 0;
+        for user_id in self.fact_store.list_users(usize::MAX)? {
+            let user_facts = self.fact_store.list(&user_id, usize::MAX)?;
+            let texts: Vec<&str> = user_facts.iter().map(|f| f.fact.as_str()).collect();
+            let embeddings = self.embedder.encode_batch(&texts)?;
+            for (fact, embedding) in user_facts.iter().zip(embeddings) {
+                self.fact_store
+                    .store_embedding(&user_id, &fact.id, &embedding)?;
+                facts += 1;
+            }
+        }
+
+        self.query_cache.invalidate_all();
+        self.content_cache.invalidate_all();
+        crate::embeddings::EmbeddingFingerprint::of(self.embedder.as_ref())
+            .save(&self.config.storage_path)?;
+
+        tracing::info!(
+            memories = reembedded,
+            facts,
+            model = %self.embedder.model_id(),
+            "Re-embedded memory store for new embedding model"
+        );
+        Ok(reembedded)
     }
 
     /// Wire up GraphMemory for entity relationships and spreading activation
@@ -603,10 +695,18 @@ impl MemorySystem {
             }
         }
 
+        // LANGUAGE DETECTION: drives stemming, stop words and the BM25 analyzer
+        let language = *experience
+            .language
+            .get_or_insert_with(|| Language::detect(&experience.content));
+
         // TEMPORAL EXTRACTION: Extract dates from content for temporal filtering
         // Based on TEMPR approach (Hindsight paper achieving 89.6% on LoCoMo)
         if experience.temporal_refs.is_empty() {
-            let temporal = crate::memory::query_parser::extract_temporal_refs(&experience.content);
+            let temporal = crate::memory::query_parser::extract_temporal_refs_in(
+                &experience.content,
+                language,
+            );
             for temp_ref in temporal.refs {
                 experience.temporal_refs.push(temp_ref.date.to_string());
             }
@@ -704,6 +804,9 @@ impl MemorySystem {
                             .next()
                             .map(|c| c.is_uppercase())
                             .unwrap_or(false),
+                        aliases: Vec::new(),
+                        centrality: 0.0,
+                        community: None,
                     }
                 })
                 .collect();
@@ -791,6 +894,7 @@ impl MemorySystem {
             &memory.experience.content,
             &memory.experience.tags,
             &memory.experience.entities,
+            memory.experience.detected_language(),
         ) {
             tracing::warn!("Failed to index memory {} in BM25: {}", memory.id.0, e);
         }
@@ -1007,14 +1111,16 @@ impl MemorySystem {
 
     /// Remember with agent context for multi-agent systems
     ///
-    /// Same as `remember` but tracks which agent created the memory,
-    /// enabling agent-specific retrieval and hierarchical memory tracking.
+    /// Same as `remember` but tracks which agent created the memory, in which
+    /// run and on behalf of which actor. All three are indexed for scoped
+    /// recall and forget.
     pub fn remember_with_agent(
         &self,
         mut experience: Experience,
         created_at: Option<chrono::DateTime<chrono::Utc>>,
         agent_id: Option<String>,
         run_id: Option<String>,
+        actor_id: Option<String>,
     ) -> Result<MemoryId> {
         // CRITICAL: Check resource limits before recording to prevent OOM
         self.check_resource_limits()?;
@@ -1040,9 +1146,17 @@ impl MemorySystem {
             }
         }
 
+        // LANGUAGE DETECTION: drives stemming, stop words and the BM25 analyzer
+        let language = *experience
+            .language
+            .get_or_insert_with(|| Language::detect(&experience.content));
+
         // TEMPORAL EXTRACTION: Extract dates from content for temporal filtering
         if experience.temporal_refs.is_empty() {
-            let temporal = crate::memory::query_parser::extract_temporal_refs(&experience.content);
+            let temporal = crate::memory::query_parser::extract_temporal_refs_in(
+                &experience.content,
+                language,
+            );
             for temp_ref in temporal.refs {
                 experience.temporal_refs.push(temp_ref.date.to_string());
             }
@@ -1055,7 +1169,7 @@ impl MemorySystem {
             importance,
             agent_id,
             run_id,
-            None, // actor_id
+            actor_id,
             created_at,
         ));
 
@@ -1127,6 +1241,9 @@ impl MemorySystem {
                             .next()
                             .map(|c| c.is_uppercase())
                             .unwrap_or(false),
+                        aliases: Vec::new(),
+                        centrality: 0.0,
+                        community: None,
                     }
                 })
                 .collect();
@@ -1204,6 +1321,7 @@ impl MemorySystem {
             &memory.experience.content,
             &memory.experience.tags,
             &memory.experience.entities,
+            memory.experience.detected_language(),
         ) {
             tracing::warn!("Failed to index memory {} in BM25: {}", memory.id.0, e);
         }
@@ -1670,6 +1788,25 @@ impl MemorySystem {
             None
         };
 
+        // ===========================================================================
+        // LAYER 1.5: METADATA FILTER (Structured Pre-filter)
+        // ===========================================================================
+        // Resolve the structured filter to candidate IDs once. The vector search
+        // applies it during index traversal, and graph/BM25 hits outside it are
+        // dropped before truncation so they can't take the result slots.
+        let filter_candidates: Option<HashSet<MemoryId>> = match query.index_filter() {
+            Some(filter) => {
+                let ids: HashSet<MemoryId> = self
+                    .long_term_memory
+                    .search_ids(filter)?
+                    .into_iter()
+                    .collect();
+                tracing::debug!("Layer 1.5: {} candidates match filter", ids.len());
+                Some(ids)
+            }
+            None => None,
+        };
+
         // ===========================================================================
         // LAYER 2: GRAPH EXPANSION (Knowledge Graph Traversal)
         // ===========================================================================
@@ -1873,8 +2010,13 @@ impl MemorySystem {
             robot_id: query.robot_id.clone(),
             mission_id: query.mission_id.clone(),
             geo_filter: query.geo_filter.clone(),
+            local_filter: query.local_filter.clone(),
             action_type: query.action_type.clone(),
             reward_range: query.reward_range,
+            // Multi-agent filters (carry over from original query)
+            agent_id: query.agent_id.clone(),
+            run_id: query.run_id.clone(),
+            actor_id: query.actor_id.clone(),
             // Decision & Learning filters (carry over from original query)
             outcome_type: query.outcome_type.clone(),
             failures_only: query.failures_only,
@@ -1886,15 +2028,18 @@ impl MemorySystem {
             confidence_range: query.confidence_range,
             offset: query.offset,
             episode_id: query.episode_id.clone(),
+            filter: query.filter.clone(),
             prospective_signals: query.prospective_signals.clone(),
         };
 
         // ===========================================================================
         // LAYER 3: VECTOR SEARCH (Vamana Index)
         // ===========================================================================
-        let vr = self
-            .retriever
-            .search_ids(&vector_query, query.max_results * 3)?;
+        let vr = self.retriever.search_ids_within(
+            &vector_query,
+            query.max_results * 3,
+            filter_candidates.as_ref(),
+        )?;
         let vector_results: Vec<(MemoryId, f32)> = if let Some(ref c) = episode_candidates {
             vr.into_iter().filter(|(id, _)| c.contains(id)).collect()
         } else {
@@ -2093,9 +2238,18 @@ impl MemorySystem {
                 }
             }
 
+            // Index buckets are coarse (day/tenth), so keep spare filtered
+            // candidates for the exact check in the fetch loop below
+            let keep = if let Some(ref candidates) = filter_candidates {
+                fused.retain(|id, _| candidates.contains(id));
+                query.max_results * 3
+            } else {
+                query.max_results
+            };
+
             let mut res: Vec<_> = fused.into_iter().collect();
             res.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
-            res.truncate(query.max_results);
+            res.truncate(keep);
             tracing::debug!("Layer 4: {} fused results", res.len());
             (res, heb)
         };
@@ -2821,6 +2975,12 @@ impl MemorySystem {
                 // Remove memories of a specific type
                 self.forget_by_type(exp_type)?
             }
+            ForgetCriteria::ByAgent(agent_id) => {
+                self.forget_matching(storage::SearchCriteria::ByAgent(agent_id))?
+            }
+            ForgetCriteria::ByRun(run_id) => {
+                self.forget_matching(storage::SearchCriteria::ByRun(run_id))?
+            }
             ForgetCriteria::All => {
                 // GDPR: Clear ALL memories for the user
                 self.forget_all()?
@@ -2854,6 +3014,13 @@ impl MemorySystem {
         stats
     }
 
+    /// Memory and run counts per agent_id, read from the agent index
+    ///
+    /// Kept out of [`stats`](Self::stats) since that runs on hot paths.
+    pub fn agent_stats(&self) -> Result<std::collections::BTreeMap<String, AgentStats>> {
+        self.long_term_memory.agent_stats()
+    }
+
     /// Export visualization graph as DOT format for Graphviz
     pub fn export_visualization_dot(&self) -> String {
         self.logger.read().graph.to_dot()
@@ -3885,6 +4052,91 @@ impl MemorySystem {
         Ok(count)
     }
 
+    /// Forget memories matching indexed search criteria (agent or run scope)
+    ///
+    /// A memory held in several tiers is counted once.
+    fn forget_matching(&self, criteria: storage::SearchCriteria) -> Result<usize> {
+        let mut working_removed = 0;
+        let mut session_removed = 0;
+        let mut long_term_removed = 0;
+
+        // Collect IDs from working memory that match
+        let working_ids: Vec<MemoryId> = {
+            let working = self.working_memory.read();
+            working
+                .all_memories()
+                .iter()
+                .filter(|m| criteria.matches(m))
+                .map(|m| m.id.clone())
+                .collect()
+        };
+        {
+            let mut working = self.working_memory.write();
+            for id in &working_ids {
+                if working.remove(id).is_ok() {
+                    self.retriever.remove_memory(id);
+                    let _ = self.hybrid_search.remove_memory(id);
+                    working_removed += 1;
+                }
+            }
+        }
+
+        // Collect IDs from session memory that match
+        let session_ids: Vec<MemoryId> = {
+            let session = self.session_memory.read();
+            session
+                .all_memories()
+                .iter()
+                .filter(|m| criteria.matches(m))
+                .map(|m| m.id.clone())
+                .collect()
+        };
+        {
+            let mut session = self.session_memory.write();
+            for id in &session_ids {
+                if session.remove(id).is_ok() {
+                    self.retriever.remove_memory(id);
+                    let _ = self.hybrid_search.remove_memory(id);
+                    session_removed += 1;
+                }
+            }
+        }
+
+        // Remove from long-term memory using the index
+        let lt_ids = self.long_term_memory.search_ids(criteria)?;
+        for id in &lt_ids {
+            self.retriever.remove_memory(id);
+            let _ = self.hybrid_search.remove_memory(id);
+            self.long_term_memory.delete(id)?;
+            long_term_removed += 1;
+        }
+
+        let all_ids: Vec<MemoryId> = working_ids
+            .into_iter()
+            .chain(session_ids)
+            .chain(lt_ids)
+            .collect::<HashSet<_>>()
+            .into_iter()
+            .collect();
+        let count = all_ids.len();
+        self.cleanup_graph_for_ids(&all_ids);
+        self.cleanup_interference_for_ids(&all_ids);
+
+        // Update stats
+        {
+            let mut stats = self.stats.write();
+            stats.total_memories = stats.total_memories.saturating_sub(count);
+            stats.working_memory_count = stats.working_memory_count.saturating_sub(working_removed);
+            stats.session_memory_count = stats.session_memory_count.saturating_sub(session_removed);
+            stats.long_term_memory_count = stats
+                .long_term_memory_count
+                .saturating_sub(long_term_removed);
+            stats.vector_index_count = stats.vector_index_count.saturating_sub(count);
+        }
+
+        Ok(count)
+    }
+
     /// Forget ALL memories for a user (GDPR compliance - right to erasure)
     ///
     /// WARNING: This is a destructive operation. All memories across all tiers
@@ -4056,8 +4308,11 @@ impl MemorySystem {
         // Flush RocksDB storage
         self.long_term_memory.flush()?;
 
-        // Persist vector index and ID mapping for restart recovery
-        self.retriever.save()?;
+        // Persist vector index and ID mapping for restart recovery, unless the
+        // index is configured to rebuild from RocksDB instead
+        if self.retriever.save_on_shutdown() {
+            self.retriever.save()?;
+        }
 
         Ok(())
     }
@@ -4071,6 +4326,11 @@ impl MemorySystem {
         self.long_term_memory.db()
     }
 
+    /// Get the secondary index database handle for backup operations
+    pub fn get_index_db(&self) -> std::sync::Arc<rocksdb::DB> {
+        self.long_term_memory.index_db()
+    }
+
     /// Advanced search using storage criteria
     pub fn advanced_search(&self, criteria: storage::SearchCriteria) -> Result<Vec<Memory>> {
         self.long_term_memory.search(criteria)
@@ -4109,6 +4369,7 @@ impl MemorySystem {
             &memory.experience.content,
             &memory.experience.tags,
             &memory.experience.entities,
+            memory.experience.detected_language(),
         ) {
             tracing::warn!("Failed to reindex memory {} in BM25: {}", memory_id.0, e);
         }
@@ -4135,6 +4396,59 @@ impl MemorySystem {
         Ok(())
     }
 
+    /// Import memories from an export, preserving their IDs
+    ///
+    /// Memories that already exist are updated in place; new ones are stored
+    /// and indexed. Embeddings from a model of a different dimension are
+    /// dropped and recomputed. BM25 is committed once for the whole batch.
+    /// Returns the number of memories written.
+    pub fn import_memories(&self, memories: &[Memory]) -> Result<usize> {
+        let dimension = self.embedder.dimension();
+        let mut imported = 0;
+        for memory in memories {
+            let mut memory = memory.clone();
+            if memory
+                .experience
+                .embeddings
+                .as_ref()
+                .is_some_and(|e| e.len() != dimension)
+            {
+                memory.experience.embeddings = None;
+            }
+
+            let indexed = if self.long_term_memory.get(&memory.id).is_ok() {
+                self.long_term_memory.update(&memory)?;
+                self.retriever.reindex_memory(&memory)
+            } else {
+                self.long_term_memory.store(&memory)?;
+                self.retriever.index_memory(&memory)
+            };
+            if let Err(e) = indexed {
+                tracing::warn!("Failed to index imported memory {}: {}", memory.id.0, e);
+            }
+            if let Err(e) = self.hybrid_search.index_memory(
+                &memory.id,
+                &memory.experience.content,
+                &memory.experience.tags,
+                &memory.experience.entities,
+                memory.experience.detected_language(),
+            ) {
+                tracing::warn!(
+                    "Failed to index imported memory {} in BM25: {}",
+                    memory.id.0,
+                    e
+                );
+            }
+            imported += 1;
+        }
+        if imported > 0 {
+            if let Err(e) = self.hybrid_search.commit_and_reload() {
+                tracing::warn!("Failed to commit/reload BM25 index: {}", e);
+            }
+        }
+        Ok(imported)
+    }
+
     /// Set or update the parent of a memory for hierarchical organization
     ///
     /// This enables memory trees where memories can have parent-child relationships.
@@ -4677,6 +4991,9 @@ impl MemorySystem {
                         .next()
                         .map(|c| c.is_uppercase())
                         .unwrap_or(false),
+                    aliases: Vec::new(),
+                    centrality: 0.0,
+                    community: None,
                 };
                 if graph_guard.add_entity(entity).is_ok() {
                     entities_added += 1;
@@ -4847,9 +5164,15 @@ impl MemorySystem {
                 }
             }
 
+            // LANGUAGE DETECTION: Re-detect when content changes
+            let language = Language::detect(&existing.experience.content);
+            existing.experience.language = Some(language);
+
             // TEMPORAL EXTRACTION: Re-extract dates when content changes
-            let temporal =
-                crate::memory::query_parser::extract_temporal_refs(&existing.experience.content);
+            let temporal = crate::memory::query_parser::extract_temporal_refs_in(
+                &existing.experience.content,
+                language,
+            );
             existing.experience.temporal_refs.clear();
             for temp_ref in temporal.refs {
                 existing
@@ -4876,6 +5199,7 @@ impl MemorySystem {
                 &existing.experience.content,
                 &existing.experience.tags,
                 &existing.experience.entities,
+                existing.experience.detected_language(),
             ) {
                 tracing::warn!("Failed to reindex memory {} in BM25: {}", memory_id.0, e);
             }
@@ -4930,10 +5254,17 @@ impl MemorySystem {
                 }
             }
 
+            // LANGUAGE DETECTION: drives stemming, stop words and the BM25 analyzer
+            let language = *experience
+                .language
+                .get_or_insert_with(|| Language::detect(&experience.content));
+
             // TEMPORAL EXTRACTION: Extract dates from content for temporal filtering
             if experience.temporal_refs.is_empty() {
-                let temporal =
-                    crate::memory::query_parser::extract_temporal_refs(&experience.content);
+                let temporal = crate::memory::query_parser::extract_temporal_refs_in(
+                    &experience.content,
+                    language,
+                );
                 for temp_ref in temporal.refs {
                     experience.temporal_refs.push(temp_ref.date.to_string());
                 }
@@ -5020,6 +5351,9 @@ impl MemorySystem {
                                 .next()
                                 .map(|c| c.is_uppercase())
                                 .unwrap_or(false),
+                            aliases: Vec::new(),
+                            centrality: 0.0,
+                            community: None,
                         }
                     })
                     .collect();
@@ -5098,6 +5432,7 @@ impl MemorySystem {
                 &memory.experience.content,
                 &memory.experience.tags,
                 &memory.experience.entities,
+                memory.experience.detected_language(),
             ) {
                 tracing::warn!("Failed to index memory {} in BM25: {}", memory.id.0, e);
             }
@@ -5392,11 +5727,11 @@ impl MemorySystem {
                         }
                     }
 
-                    // Store new facts
+                    // Store new facts, closing out the stored facts they contradict
                     if !truly_new.is_empty() {
-                        let facts_only: Vec<SemanticFact> =
+                        let mut facts_only: Vec<SemanticFact> =
                             truly_new.iter().map(|(f, _)| f.clone()).collect();
-                        match self.fact_store.store_batch(user_id, &facts_only) {
+                        match self.store_new_facts(user_id, &mut facts_only) {
                             Ok(stored) => {
                                 facts_extracted_count = stored;
                                 // Store embeddings for newly persisted facts
@@ -5826,11 +6161,11 @@ impl MemorySystem {
             compression::SemanticConsolidator::with_thresholds(min_support, min_age_days);
 
         // Run consolidation
-        let result = consolidator.consolidate(&memories);
+        let mut result = consolidator.consolidate(&memories);
 
-        // Store extracted facts
+        // Store extracted facts, closing out the stored facts they contradict
         if !result.new_facts.is_empty() {
-            let stored = self.fact_store.store_batch(user_id, &result.new_facts)?;
+            let stored = self.store_new_facts(user_id, &mut result.new_facts)?;
             tracing::info!(
                 user_id = %user_id,
                 facts_extracted = result.facts_extracted,
@@ -5865,13 +6200,61 @@ impl MemorySystem {
         Ok(result)
     }
 
+    /// Store newly extracted facts, resolving contradictions with stored ones
+    ///
+    /// Facts are stored oldest-first, so contradictions within the batch are
+    /// resolved the same way as against already stored facts: the fact that
+    /// became valid first is closed out and linked to the one replacing it.
+    fn store_new_facts(&self, user_id: &str, facts: &mut [SemanticFact]) -> Result<usize> {
+        facts.sort_by_key(|f| f.valid_since());
+
+        let mut stored = 0;
+        for fact in facts.iter_mut() {
+            let closed = self.fact_store.supersede_contradicted(user_id, fact)?;
+            if let Err(e) = self.fact_store.store(user_id, fact) {
+                tracing::debug!("Failed to store fact: {e}");
+                continue;
+            }
+            stored += 1;
+
+            let superseded = closed
+                .iter()
+                .chain(fact.superseded_by.is_some().then_some(&*fact));
+            for old in superseded {
+                let (Some(superseded_by), Some(valid_to)) = (&old.superseded_by, old.valid_to)
+                else {
+                    continue;
+                };
+                self.record_consolidation_event_for_user(
+                    user_id,
+                    ConsolidationEvent::FactSuperseded {
+                        fact_id: old.id.clone(),
+                        fact_content: old.fact.clone(),
+                        superseded_by: superseded_by.clone(),
+                        confidence: old.confidence,
+                        support_count: old.support_count,
+                        valid_to,
+                        timestamp: chrono::Utc::now(),
+                    },
+                );
+            }
+        }
+        Ok(stored)
+    }
+
     /// Get semantic facts for a user
     ///
     /// # Arguments
     /// * `user_id` - User whose facts to retrieve
+    /// * `as_of` - Only facts that held at this time (pass now for current facts)
     /// * `limit` - Maximum number of facts to return
-    pub fn get_facts(&self, user_id: &str, limit: usize) -> Result<Vec<SemanticFact>> {
-        self.fact_store.list(user_id, limit)
+    pub fn get_facts(
+        &self,
+        user_id: &str,
+        as_of: chrono::DateTime<chrono::Utc>,
+        limit: usize,
+    ) -> Result<Vec<SemanticFact>> {
+        self.fact_store.list_as_of(user_id, as_of, limit)
     }
 
     /// Get facts related to a specific entity
@@ -5879,14 +6262,17 @@ impl MemorySystem {
     /// # Arguments
     /// * `user_id` - User whose facts to search
     /// * `entity` - Entity to search for (e.g., "authentication", "JWT")
+    /// * `as_of` - Only facts that held at this time (pass now for current facts)
     /// * `limit` - Maximum number of facts to return
     pub fn get_facts_by_entity(
         &self,
         user_id: &str,
         entity: &str,
+        as_of: chrono::DateTime<chrono::Utc>,
         limit: usize,
     ) -> Result<Vec<SemanticFact>> {
-        self.fact_store.find_by_entity(user_id, entity, limit)
+        self.fact_store
+            .find_by_entity_as_of(user_id, entity, as_of, limit)
     }
 
     /// Get facts of a specific type
@@ -5909,14 +6295,16 @@ impl MemorySystem {
     /// # Arguments
     /// * `user_id` - User whose facts to search
     /// * `query` - Search query
+    /// * `as_of` - Only facts that held at this time (pass now for current facts)
     /// * `limit` - Maximum number of facts to return
     pub fn search_facts(
         &self,
         user_id: &str,
         query: &str,
+        as_of: chrono::DateTime<chrono::Utc>,
         limit: usize,
     ) -> Result<Vec<SemanticFact>> {
-        self.fact_store.search(user_id, query, limit)
+        self.fact_store.search_as_of(user_id, query, as_of, limit)
     }
 
     /// Get statistics about stored facts
@@ -5928,7 +6316,8 @@ impl MemorySystem {
     ///
     /// Bridges graph traversal → fact retrieval: when spreading activation discovers
     /// entity nodes, this method returns the semantic facts linked to those entities.
-    /// Results are deduplicated and sorted by confidence (highest first).
+    /// Only facts that currently hold are returned, deduplicated and sorted by
+    /// confidence (highest first).
     pub fn get_facts_for_graph_entities(
         &self,
         user_id: &str,
@@ -5937,11 +6326,12 @@ impl MemorySystem {
     ) -> Result<Vec<SemanticFact>> {
         let mut seen_ids = std::collections::HashSet::new();
         let mut results = Vec::new();
+        let now = chrono::Utc::now();
 
         for name in entity_names {
-            let facts = self
-                .fact_store
-                .find_by_entity(user_id, name, limit_per_entity)?;
+            let facts =
+                self.fact_store
+                    .find_by_entity_as_of(user_id, name, now, limit_per_entity)?;
             for fact in facts {
                 if seen_ids.insert(fact.id.clone()) {
                     results.push(fact);
@@ -6016,6 +6406,11 @@ impl MemorySystem {
         &self.fact_store
     }
 
+    /// Get the temporal fact store for direct access
+    pub fn temporal_fact_store(&self) -> &Arc<temporal_facts::TemporalFactStore> {
+        &self.temporal_fact_store
+    }
+
     // =========================================================================
     // SHO-118: DECISION LINEAGE GRAPH METHODS
     // =========================================================================
@@ -6090,6 +6485,111 @@ impl MemorySystem {
         self.lineage_graph.stats(user_id)
     }
 
+    /// Generate and store the post-mortem for a finished todo or project
+    ///
+    /// Traces lineage in both directions from the subject's linked and tagged
+    /// memories, then stores the summary as a `Learning` memory that each
+    /// traced memory points to with a `ResolvedBy` edge. An earlier
+    /// post-mortem for the same subject (and its memory) is replaced.
+    /// Returns `None` when no memories are linked to the subject.
+    pub fn generate_post_mortem(
+        &self,
+        user_id: &str,
+        subject: &PostMortemSubject,
+    ) -> Result<Option<PostMortem>> {
+        let previous_memory = self
+            .lineage_graph
+            .get_post_mortem(user_id, &subject.id.0)?
+            .and_then(|p| p.memory_id);
+        let is_post_mortem = |m: &Memory| m.experience.tags.iter().any(|t| t == POST_MORTEM_TAG);
+
+        let mut seeds = subject.seeds.clone();
+        if !subject.seed_tags.is_empty() {
+            let tagged = self.advanced_search(SearchCriteria::ByTags(subject.seed_tags.clone()))?;
+            seeds.extend(
+                tagged
+                    .into_iter()
+                    .filter(|m| !is_post_mortem(m))
+                    .take(POSTMORTEM_MAX_TAGGED_SEEDS)
+                    .map(|m| m.id),
+            );
+        }
+        let mut seen = HashSet::new();
+        seeds.retain(|id| Some(id) != previous_memory.as_ref() && seen.insert(id.clone()));
+        if seeds.is_empty() {
+            return Ok(None);
+        }
+
+        let mut trace = self.lineage_graph.trace_from(
+            user_id,
+            &seeds,
+            TraceDirection::Both,
+            POSTMORTEM_TRACE_DEPTH,
+        )?;
+
+        // Only summarize memories that still exist, and never an older post-mortem
+        let mut memories = std::collections::HashMap::new();
+        for id in &trace.path {
+            if memories.len() >= POSTMORTEM_MAX_MEMORIES {
+                break;
+            }
+            if Some(id) == previous_memory.as_ref() {
+                continue;
+            }
+            if let Ok(memory) = self.get_memory(id) {
+                if !is_post_mortem(&memory) {
+                    memories.insert(id.clone(), memory);
+                }
+            }
+        }
+        if memories.is_empty() {
+            return Ok(None);
+        }
+        trace.path.retain(|id| memories.contains_key(id));
+        trace
+            .edges
+            .retain(|e| memories.contains_key(&e.from) && memories.contains_key(&e.to));
+
+        let mut post_mortem =
+            PostMortem::from_trace(subject.id.clone(), &subject.content, &trace, &memories);
+
+        if let Some(old) = previous_memory {
+            self.lineage_graph.delete_edges_for(user_id, &old)?;
+            if let Err(e) = self.forget(ForgetCriteria::ById(old.clone())) {
+                debug!("Failed to remove previous post-mortem {}: {}", old.0, e);
+            }
+        }
+
+        let mut tags = subject.seed_tags.clone();
+        tags.push(POST_MORTEM_TAG.to_string());
+        let experience = Experience {
+            content: post_mortem.to_markdown(),
+            experience_type: ExperienceType::Learning,
+            tags,
+            ..Default::default()
+        };
+        let memory_id = self.remember(experience, None)?;
+        for related in &post_mortem.related_memories {
+            self.lineage_graph.add_explicit_edge(
+                user_id,
+                related.clone(),
+                memory_id.clone(),
+                CausalRelation::ResolvedBy,
+            )?;
+        }
+
+        debug!(
+            "Post-mortem for {} stored as {} ({} related memories)",
+            subject.id.0,
+            memory_id.0,
+            post_mortem.related_memories.len()
+        );
+        post_mortem.memory_id = Some(memory_id);
+        self.lineage_graph
+            .store_post_mortem(user_id, &post_mortem)?;
+        Ok(Some(post_mortem))
+    }
+
     /// Decay facts for all users during maintenance
     ///
     /// Facts decay based on lack of reinforcement. The decay rate is modulated by support_count:
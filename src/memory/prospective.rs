@@ -2,6 +2,7 @@
 //!
 //! Implements the "remembering to remember" capability:
 //! - Time-based triggers (at specific time, after duration)
+//! - Recurring triggers (RFC 5545 RRULE, re-armed after each firing)
 //! - Context-based triggers (keyword match, semantic similarity)
 //!
 //! Architecture:
@@ -498,10 +499,14 @@ impl ProspectiveStore {
     }
 
     /// Mark a task as triggered
+    ///
+    /// Recurring tasks record the trigger time and go straight back to
+    /// pending for their next occurrence; only an ended series stays triggered.
     pub fn mark_triggered(&self, user_id: &str, task_id: &ProspectiveTaskId) -> Result<bool> {
         if let Some(mut task) = self.get(user_id, task_id)? {
             if task.status == ProspectiveTaskStatus::Pending {
                 task.mark_triggered();
+                task.rearm();
                 self.update(&task)?;
                 return Ok(true);
             }
@@ -829,4 +834,51 @@ mod tests {
         assert!(deleted);
         assert!(store.get("test-user", &task.id).unwrap().is_none());
     }
+
+    #[test]
+    fn test_recurring_trigger_rearms() {
+        use crate::memory::types::Recurrence;
+
+        let (_temp, store) = setup_store();
+
+        // Daily series that started three days ago and has two instances left
+        let start =
+            chrono::DateTime::from_timestamp(Utc::now().timestamp() - 3 * 86_400 - 60, 0).unwrap();
+        let recurrence = Recurrence::RRule {
+            rule: "FREQ=DAILY;COUNT=5".to_string(),
+            dtstart: start,
+            tzid: None,
+            exdates: Vec::new(),
+        };
+        let task = ProspectiveTask::new(
+            "test-user".to_string(),
+            "Stand-up notes".to_string(),
+            ProspectiveTrigger::Recurring {
+                at: start,
+                recurrence,
+            },
+        );
+        store.store(&task).unwrap();
+        assert_eq!(store.get_due_tasks("test-user").unwrap().len(), 1);
+
+        // Firing skips the missed instances and re-arms for tomorrow
+        assert!(store.mark_triggered("test-user", &task.id).unwrap());
+        let rearmed = store.get("test-user", &task.id).unwrap().unwrap();
+        assert_eq!(rearmed.status, ProspectiveTaskStatus::Pending);
+        assert!(rearmed.triggered_at.is_some());
+        let next = rearmed.trigger.due_at().unwrap();
+        assert_eq!(next, start + chrono::Duration::days(4));
+        assert!(store.get_due_tasks("test-user").unwrap().is_empty());
+
+        // The last instance fires once and then the series ends
+        let mut last = rearmed.clone();
+        last.trigger = match last.trigger {
+            ProspectiveTrigger::Recurring { recurrence, .. } => ProspectiveTrigger::Recurring {
+                at: start + chrono::Duration::days(4),
+                recurrence,
+            },
+            other => other,
+        };
+        assert!(!last.rearm());
+    }
 }
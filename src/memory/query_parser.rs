@@ -25,9 +25,9 @@
 //! - Detect temporal queries ("when did", "what date", "how long ago")
 //! - Based on TEMPR approach (Hindsight paper achieving 89.6% on LoCoMo)
 
+use super::language::{self, Language, TextStemmer};
 use crate::constants::{IC_ADJECTIVE, IC_NOUN, IC_VERB};
 use chrono::{DateTime, Datelike, NaiveDate, Utc};
-use rust_stemmers::{Algorithm, Stemmer};
 use serde::{Deserialize, Serialize};
 use std::collections::HashSet;
 
@@ -234,7 +234,20 @@ impl TemporalExtraction {
 /// - Relative dates: "yesterday", "last week", "3 days ago"
 /// - Day of week: "on Monday", "last Tuesday"
 /// - Month/year: "in May", "last year", "2023"
+///
+/// The language is detected from the text; see [`extract_temporal_refs_in`].
 pub fn extract_temporal_refs(text: &str) -> TemporalExtraction {
+    extract_temporal_refs_in(text, Language::detect(text))
+}
+
+/// Extract temporal references from text written in `language`
+///
+/// English patterns always run (English is the fallback). German, Spanish and
+/// Hindi add localized month names ("7. Mai 2023", "7 de mayo de 2023",
+/// "7 मई 2023"), relative days ("gestern", "ayer", "कल") and "N days ago"
+/// phrases ("vor 3 Tagen", "hace 3 días", "3 दिन पहले"). Slash dates are read
+/// as DD/MM/YYYY outside English.
+pub fn extract_temporal_refs_in(text: &str, language: Language) -> TemporalExtraction {
     let now = Utc::now();
     let mut refs = Vec::new();
     let mut earliest: Option<NaiveDate> = None;
@@ -283,7 +296,7 @@ pub fn extract_temporal_refs(text: &str) -> TemporalExtraction {
     }
 
     // Also use regex-based extraction for explicit date patterns
-    let explicit_dates = extract_explicit_dates(text);
+    let explicit_dates = extract_explicit_dates(text, language);
     for (date, original, pos) in explicit_dates {
         if !is_valid_date(&date) {
             continue;
@@ -301,6 +314,22 @@ pub fn extract_temporal_refs(text: &str) -> TemporalExtraction {
         update_bounds(&mut earliest, &mut latest, date);
     }
 
+    // Localized relative expressions ("gestern", "hace 3 días", "2 दिन पहले")
+    for (date, original, pos) in extract_localized_relative_dates(text, language, now.date_naive())
+    {
+        if !is_valid_date(&date) || refs.iter().any(|r| r.date == date) {
+            continue;
+        }
+        refs.push(TemporalRef {
+            date,
+            original_text: original,
+            confidence: 0.7,
+            position: pos,
+            ref_type: TemporalRefType::Relative,
+        });
+        update_bounds(&mut earliest, &mut latest, date);
+    }
+
     // Sort by position in text
     refs.sort_by_key(|r| r.position);
 
@@ -415,7 +444,7 @@ fn split_temporal_phrases(text: &str) -> Vec<String> {
 }
 
 /// Extract explicit date patterns that date_time_parser might miss
-fn extract_explicit_dates(text: &str) -> Vec<(NaiveDate, String, usize)> {
+fn extract_explicit_dates(text: &str, language: Language) -> Vec<(NaiveDate, String, usize)> {
     use regex::Regex;
 
     let mut results = Vec::new();
@@ -450,12 +479,53 @@ fn extract_explicit_dates(text: &str) -> Vec<(NaiveDate, String, usize)> {
         }
     }
 
-    // Pattern: "MM/DD/YYYY" or "DD/MM/YYYY" (assume US format MM/DD)
+    // Pattern: "MM/DD/YYYY" or "DD/MM/YYYY" (US format MM/DD for English only)
     let slash_date = Regex::new(r"(\d{1,2})/(\d{1,2})/(\d{4})").unwrap();
     for cap in slash_date.captures_iter(text) {
-        let month: u32 = cap[1].parse().unwrap_or(1);
-        let day: u32 = cap[2].parse().unwrap_or(1);
-        let year: i32 = cap[3].parse().unwrap_or(2000);
+        let (first, second) = (parse_number(&cap[1]), parse_number(&cap[2]));
+        let (month, day) = if language == Language::English {
+            (first, second)
+        } else {
+            (second, first)
+        };
+        let year = parse_number(&cap[3]) as i32;
+
+        if let Some(date) = NaiveDate::from_ymd_opt(year, month, day) {
+            let pos = cap.get(0).map(|m| m.start()).unwrap_or(0);
+            results.push((date, cap[0].to_string(), pos));
+        }
+    }
+
+    if language != Language::English {
+        results.extend(extract_localized_dates(text, language));
+    }
+
+    results
+}
+
+/// Localized "day month year" dates and German "DD.MM.YYYY"
+fn extract_localized_dates(text: &str, language: Language) -> Vec<(NaiveDate, String, usize)> {
+    use regex::Regex;
+
+    let pattern = match language {
+        Language::German => {
+            r"(?i)(\d{1,2})\.\s*(Januar|Jänner|Februar|März|Maerz|April|Mai|Juni|Juli|August|September|Oktober|November|Dezember)\s+(\d{4})"
+        }
+        Language::Spanish => {
+            r"(?i)(\d{1,2})\s+de\s+(enero|febrero|marzo|abril|mayo|junio|julio|agosto|septiembre|setiembre|octubre|noviembre|diciembre)(?:\s+de(?:l)?)?\s+(\d{4})"
+        }
+        Language::Hindi => {
+            r"(\d{1,2})\s+(जनवरी|फ़रवरी|फरवरी|मार्च|अप्रैल|मई|जून|जुलाई|अगस्त|सितंबर|सितम्बर|अक्टूबर|अक्तूबर|नवंबर|नवम्बर|दिसंबर|दिसम्बर)\s+(\d{4})"
+        }
+        Language::English => return Vec::new(),
+    };
+
+    let mut results = Vec::new();
+    let day_month_year = Regex::new(pattern).unwrap();
+    for cap in day_month_year.captures_iter(text) {
+        let day = parse_number(&cap[1]);
+        let year = parse_number(&cap[3]) as i32;
+        let month = localized_month_to_num(&cap[2]);
 
         if let Some(date) = NaiveDate::from_ymd_opt(year, month, day) {
             let pos = cap.get(0).map(|m| m.start()).unwrap_or(0);
@@ -463,9 +533,160 @@ fn extract_explicit_dates(text: &str) -> Vec<(NaiveDate, String, usize)> {
         }
     }
 
+    if language == Language::German {
+        // Pattern: "DD.MM.YYYY"
+        let dotted_date = Regex::new(r"\b(\d{1,2})\.(\d{1,2})\.(\d{4})\b").unwrap();
+        for cap in dotted_date.captures_iter(text) {
+            let day = parse_number(&cap[1]);
+            let month = parse_number(&cap[2]);
+            let year = parse_number(&cap[3]) as i32;
+
+            if let Some(date) = NaiveDate::from_ymd_opt(year, month, day) {
+                let pos = cap.get(0).map(|m| m.start()).unwrap_or(0);
+                results.push((date, cap[0].to_string(), pos));
+            }
+        }
+    }
+
     results
 }
 
+/// Localized relative days ("gestern", "ayer", "कल") and "N units ago" phrases
+fn extract_localized_relative_dates(
+    text: &str,
+    language: Language,
+    today: NaiveDate,
+) -> Vec<(NaiveDate, String, usize)> {
+    use regex::Regex;
+
+    let (words_pattern, ago_pattern) = match language {
+        Language::German => (
+            r"(?i)\b(vorgestern|gestern|heute|übermorgen|morgen)\b",
+            r"(?i)\bvor\s+(\d+)\s+(tag|tagen|woche|wochen|monat|monaten|jahr|jahren)\b",
+        ),
+        Language::Spanish => (
+            r"(?i)\b(anteayer|antier|ayer|hoy|pasado\s+mañana|mañana)\b",
+            r"(?i)\bhace\s+(\d+)\s+(día|días|dia|dias|semana|semanas|mes|meses|año|años)\b",
+        ),
+        Language::Hindi => (
+            r"\b(परसों|कल|आज)\b",
+            r"(\d+)\s+(दिन|हफ्ते|हफ़्ते|हफ्ता|सप्ताह|महीने|महीना|साल|वर्ष)\s+पहले",
+        ),
+        Language::English => return Vec::new(),
+    };
+
+    // Hindi कल/परसों mean both "yesterday" and "tomorrow"; past-tense markers decide
+    let hindi_past = ["था", "थी", "थे", "गया", "गई", "गए", "किया", "चुका"]
+        .iter()
+        .any(|m| text.contains(m));
+
+    let mut results = Vec::new();
+    let relative_words = Regex::new(words_pattern).unwrap();
+    for m in relative_words.find_iter(text) {
+        let word = m.as_str().to_lowercase();
+        let previous_word = text[..m.start()]
+            .split_whitespace()
+            .last()
+            .map(str::to_lowercase)
+            .unwrap_or_default();
+        // "heute Morgen", "guten Morgen" and "por la mañana" mean "morning"
+        let means_morning = matches!(
+            previous_word.as_str(),
+            "heute" | "guten" | "la" | "esta" | "cada"
+        );
+        let offset = match word
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ")
+            .as_str()
+        {
+            "vorgestern" | "anteayer" | "antier" => -2,
+            "gestern" | "ayer" => -1,
+            "heute" | "hoy" | "आज" => 0,
+            "morgen" | "mañana" if means_morning => continue,
+            "morgen" | "mañana" => 1,
+            "übermorgen" | "pasado mañana" => 2,
+            "कल" => {
+                if hindi_past {
+                    -1
+                } else {
+                    1
+                }
+            }
+            "परसों" => {
+                if hindi_past {
+                    -2
+                } else {
+                    2
+                }
+            }
+            _ => continue,
+        };
+        results.push((
+            today + chrono::Duration::days(offset),
+            m.as_str().to_string(),
+            m.start(),
+        ));
+    }
+
+    let ago = Regex::new(ago_pattern).unwrap();
+    for cap in ago.captures_iter(text) {
+        let amount = parse_number(&cap[1]);
+        let unit = cap[2].to_lowercase();
+        let date = match unit.as_str() {
+            "tag" | "tagen" | "día" | "días" | "dia" | "dias" | "दिन" => {
+                today.checked_sub_days(chrono::Days::new(amount.into()))
+            }
+            "woche" | "wochen" | "semana" | "semanas" | "हफ्ते" | "हफ़्ते" | "हफ्ता" | "सप्ताह" => {
+                today.checked_sub_days(chrono::Days::new(u64::from(amount) * 7))
+            }
+            "monat" | "monaten" | "mes" | "meses" | "महीने" | "महीना" => {
+                today.checked_sub_months(chrono::Months::new(amount))
+            }
+            _ => today.checked_sub_months(chrono::Months::new(amount.saturating_mul(12))),
+        };
+        if let Some(date) = date {
+            let pos = cap.get(0).map(|m| m.start()).unwrap_or(0);
+            results.push((date, cap[0].to_string(), pos));
+        }
+    }
+
+    results
+}
+
+/// Parse ASCII or Devanagari digits ("२०२३")
+fn parse_number(digits: &str) -> u32 {
+    digits.chars().fold(0u32, |acc, c| {
+        let digit = c.to_digit(10).or_else(|| {
+            ('\u{0966}'..='\u{096F}')
+                .contains(&c)
+                .then(|| c as u32 - 0x0966)
+        });
+        acc.saturating_mul(10).saturating_add(digit.unwrap_or(0))
+    })
+}
+
+/// Convert a German, Spanish or Hindi month name to number
+fn localized_month_to_num(month: &str) -> u32 {
+    match month.to_lowercase().as_str() {
+        "januar" | "jänner" | "enero" | "जनवरी" => 1,
+        "februar" | "febrero" | "फ़रवरी" | "फरवरी" => 2,
+        "märz" | "maerz" | "marzo" | "मार्च" => 3,
+        "april" | "abril" | "अप्रैल" => 4,
+        "mai" | "mayo" | "मई" => 5,
+        "juni" | "junio" | "जून" => 6,
+        "juli" | "julio" | "जुलाई" => 7,
+        "august" | "agosto" | "अगस्त" => 8,
+        "september" | "septiembre" | "setiembre" | "सितंबर" | "सितम्बर" => {
+            9
+        }
+        "oktober" | "octubre" | "अक्टूबर" | "अक्तूबर" => 10,
+        "november" | "noviembre" | "नवंबर" | "नवम्बर" => 11,
+        "dezember" | "diciembre" | "दिसंबर" | "दिसम्बर" => 12,
+        _ => 1,
+    }
+}
+
 /// Convert month name to number
 fn month_to_num(month: &str) -> u32 {
     match month.to_lowercase().as_str() {
@@ -965,7 +1186,8 @@ fn capitalize_first(s: &str) -> String {
 ///
 /// Unlike YAKE, this doesn't rank by frequency - ALL content words are extracted.
 pub fn extract_chunks(text: &str) -> ChunkExtraction {
-    let stemmer = Stemmer::create(Algorithm::English);
+    let language = Language::detect(text);
+    let stemmer = language.stemmer();
     let sentences = split_sentences(text);
 
     let mut chunks = Vec::with_capacity(sentences.len());
@@ -986,8 +1208,12 @@ pub fn extract_chunks(text: &str) -> ChunkExtraction {
                 continue;
             }
 
-            let stem = stemmer.stem(&word_lower).to_string();
-            let pos = classify_pos_for_chunking(&word_lower, *is_capitalized, position, &words);
+            let stem = stemmer.stem(&word_lower);
+            let pos = if is_foreign_stop_word(&word_lower, language) {
+                PosTag::StopWord
+            } else {
+                classify_pos_for_chunking(&word_lower, *is_capitalized, position, &words)
+            };
 
             match pos {
                 PosTag::Noun => {
@@ -1044,7 +1270,7 @@ fn split_sentences(text: &str) -> Vec<String> {
     for ch in text.chars() {
         current.push(ch);
 
-        if ch == '.' || ch == '!' || ch == '?' || ch == '\n' {
+        if ch == '.' || ch == '!' || ch == '?' || ch == '\n' || ch == '।' {
             let trimmed = current.trim();
             if !trimmed.is_empty() && trimmed.len() > 3 {
                 // Avoid splitting on abbreviations like "Dr." or "Mr."
@@ -1097,7 +1323,7 @@ fn tokenize_with_case(text: &str) -> Vec<(String, bool)> {
     text.split_whitespace()
         .map(|w| {
             let clean: String = w
-                .trim_matches(|c: char| !c.is_alphanumeric() && c != '\'')
+                .trim_matches(|c: char| !language::is_word_char(c) && c != '\'')
                 .to_string();
             let is_capitalized = clean
                 .chars()
@@ -1110,6 +1336,11 @@ fn tokenize_with_case(text: &str) -> Vec<(String, bool)> {
         .collect()
 }
 
+/// Stop word of a non-English language (English ones are handled by `is_stop_word`)
+fn is_foreign_stop_word(word: &str, language: Language) -> bool {
+    language != Language::English && language.is_stopword(word)
+}
+
 /// Classify POS for chunking purposes
 fn classify_pos_for_chunking(
     word: &str,
@@ -2152,9 +2383,9 @@ enum PartOfSpeech {
     Unknown,
 }
 
-/// Parse query using linguistic analysis with Porter2 stemming
+/// Parse query using linguistic analysis with stemming in the query's language
 pub fn analyze_query(query_text: &str) -> QueryAnalysis {
-    let stemmer = Stemmer::create(Algorithm::English);
+    let stemmer = Language::detect(query_text).stemmer();
     let words = tokenize(query_text);
 
     if words.is_empty() {
@@ -2231,7 +2462,7 @@ pub fn analyze_query(query_text: &str) -> QueryAnalysis {
 
     // Add compound nouns as high-weight entities
     for compound in &compound_nouns {
-        let stem = stemmer.stem(compound).to_string();
+        let stem = stemmer.stem(compound);
         focal_entities.push(FocalEntity {
             text: compound.clone(),
             stem,
@@ -2364,7 +2595,7 @@ fn detect_query_intent(
 fn tokenize(text: &str) -> Vec<String> {
     text.split_whitespace()
         .map(|w| {
-            w.trim_matches(|c: char| !c.is_alphanumeric())
+            w.trim_matches(|c: char| !language::is_word_char(c))
                 .to_lowercase()
         })
         .filter(|w| !w.is_empty())
@@ -2372,14 +2603,18 @@ fn tokenize(text: &str) -> Vec<String> {
 }
 
 /// Annotate tokens with POS tags and negation scope
-fn annotate_tokens(words: &[String], stemmer: &Stemmer) -> Vec<AnnotatedToken> {
+fn annotate_tokens(words: &[String], stemmer: &TextStemmer) -> Vec<AnnotatedToken> {
     let mut annotated = Vec::with_capacity(words.len());
     let mut in_negation_scope = false;
     let mut negation_distance = 0;
 
     for (i, word) in words.iter().enumerate() {
-        let stem = stemmer.stem(word).to_string();
-        let pos = classify_pos(word, i, words);
+        let stem = stemmer.stem(word);
+        let pos = if is_foreign_stop_word(word, stemmer.language()) {
+            PartOfSpeech::StopWord
+        } else {
+            classify_pos(word, i, words)
+        };
 
         // Track negation scope (extends 2-3 words after negation)
         if pos == PartOfSpeech::Negation {
@@ -3656,4 +3891,79 @@ mod tests {
             phrases
         );
     }
+
+    #[test]
+    fn test_localized_temporal_refs() {
+        let has_date = |text: &str, language: Language, date: NaiveDate| {
+            extract_temporal_refs_in(text, language)
+                .refs
+                .iter()
+                .any(|r| r.date == date)
+        };
+        let may_7 = NaiveDate::from_ymd_opt(2023, 5, 7).unwrap();
+
+        assert!(has_date(
+            "Wir haben uns am 7. Mai 2023 getroffen",
+            Language::German,
+            may_7
+        ));
+        assert!(has_date(
+            "Termin war am 07.05.2023",
+            Language::German,
+            may_7
+        ));
+        assert!(has_date(
+            "Nos vimos el 7 de mayo de 2023",
+            Language::Spanish,
+            may_7
+        ));
+        assert!(has_date("हम 7 मई 2023 को मिले थे", Language::Hindi, may_7));
+        assert!(has_date("हम ७ मई २०२३ को मिले थे", Language::Hindi, may_7));
+        // DD/MM outside English
+        assert!(has_date(
+            "La reunión fue el 7/5/2023",
+            Language::Spanish,
+            may_7
+        ));
+
+        let today = Utc::now().date_naive();
+        let yesterday = today - chrono::Duration::days(1);
+        assert!(has_date(
+            "Gestern war ich im Büro",
+            Language::German,
+            yesterday
+        ));
+        assert!(has_date(
+            "Ayer fui a la oficina",
+            Language::Spanish,
+            yesterday
+        ));
+        assert!(has_date("मैं कल दफ्तर गया था", Language::Hindi, yesterday));
+        assert!(has_date(
+            "Ich habe vor 3 Tagen angerufen",
+            Language::German,
+            today - chrono::Duration::days(3)
+        ));
+        assert!(has_date(
+            "Llamé hace 2 semanas",
+            Language::Spanish,
+            today - chrono::Duration::days(14)
+        ));
+
+        // "heute Morgen" is this morning, not tomorrow
+        let tomorrow = today + chrono::Duration::days(1);
+        assert!(!has_date(
+            "Ich war heute Morgen joggen",
+            Language::German,
+            tomorrow
+        ));
+
+        // Language detection picks the localized patterns automatically
+        assert!(
+            extract_temporal_refs("Wir haben uns am 7. Mai 2023 getroffen")
+                .refs
+                .iter()
+                .any(|r| r.date == may_7)
+        );
+    }
 }
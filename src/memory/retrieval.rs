@@ -23,12 +23,79 @@ use crate::constants::{
     PREFETCH_RECENCY_PARTIAL_HOURS, PREFETCH_TEMPORAL_WINDOW_HOURS,
     VECTOR_SEARCH_CANDIDATE_MULTIPLIER,
 };
-use crate::embeddings::{minilm::MiniLMEmbedder, Embedder};
+use crate::embeddings::Embedder;
 use crate::vector_db::vamana::{VamanaConfig, VamanaIndex};
 
 /// Filename for persisted Vamana index (instant startup)
 const VAMANA_INDEX_FILE: &str = "vamana.idx";
 
+/// Tuning for the per-user Vamana index
+///
+/// Read from `SHODH_VECTOR_*` environment variables, which the server also
+/// exports from the `[vector_index]` config file section. Changes apply to
+/// indices built after a restart.
+#[derive(Debug, Clone, PartialEq)]
+pub struct VectorIndexConfig {
+    /// Maximum graph degree (R)
+    pub max_degree: usize,
+    /// Search list size during construction (L)
+    pub search_list_size: usize,
+    /// RNG pruning factor (α)
+    pub alpha: f32,
+    /// Keep vectors in a memory-mapped file under `vector_index/` instead of RAM
+    pub use_mmap: bool,
+    /// Ignore the persisted `.vamana` file and rebuild from RocksDB on startup
+    pub rebuild_on_startup: bool,
+    /// Persist the index on graceful shutdown for instant startup
+    pub save_on_shutdown: bool,
+}
+
+impl Default for VectorIndexConfig {
+    fn default() -> Self {
+        Self {
+            max_degree: 32,        // Increased for better recall at scale
+            search_list_size: 100, // 2x for better accuracy with 10M vectors
+            alpha: 1.2,
+            use_mmap: false, // Keep in memory for low-latency robotics
+            rebuild_on_startup: false,
+            save_on_shutdown: true,
+        }
+    }
+}
+
+impl VectorIndexConfig {
+    /// Defaults overridden by any `SHODH_VECTOR_*` environment variables that are set
+    ///
+    /// - `SHODH_VECTOR_MAX_DEGREE`, `SHODH_VECTOR_SEARCH_LIST_SIZE`, `SHODH_VECTOR_ALPHA`
+    /// - `SHODH_VECTOR_USE_MMAP`, `SHODH_VECTOR_REBUILD_ON_STARTUP`,
+    ///   `SHODH_VECTOR_SAVE_ON_SHUTDOWN`: `true`/`1` or `false`/`0`
+    pub fn from_env() -> Self {
+        let mut config = Self::default();
+        let parse = |name: &str| std::env::var(name).ok().map(|v| v.trim().to_lowercase());
+        let flag = |name: &str| parse(name).map(|v| v == "true" || v == "1");
+
+        if let Some(n) = parse("SHODH_VECTOR_MAX_DEGREE").and_then(|v| v.parse().ok()) {
+            config.max_degree = n;
+        }
+        if let Some(n) = parse("SHODH_VECTOR_SEARCH_LIST_SIZE").and_then(|v| v.parse().ok()) {
+            config.search_list_size = n;
+        }
+        if let Some(n) = parse("SHODH_VECTOR_ALPHA").and_then(|v| v.parse().ok()) {
+            config.alpha = n;
+        }
+        if let Some(b) = flag("SHODH_VECTOR_USE_MMAP") {
+            config.use_mmap = b;
+        }
+        if let Some(b) = flag("SHODH_VECTOR_REBUILD_ON_STARTUP") {
+            config.rebuild_on_startup = b;
+        }
+        if let Some(b) = flag("SHODH_VECTOR_SAVE_ON_SHUTDOWN") {
+            config.save_on_shutdown = b;
+        }
+        config
+    }
+}
+
 /// Multi-modal retrieval engine with production vector search
 ///
 /// # Lock Ordering (SHO-72)
@@ -48,13 +115,15 @@ const VAMANA_INDEX_FILE: &str = "vamana.idx";
 /// which is managed at the API layer (MultiUserMemoryManager.graph_memories)
 pub struct RetrievalEngine {
     storage: Arc<MemoryStorage>,
-    embedder: Arc<MiniLMEmbedder>,
+    embedder: Arc<dyn Embedder>,
     /// Lock order: 1 - Acquire first
     vector_index: Arc<RwLock<VamanaIndex>>,
     /// Lock order: 2
     id_mapping: Arc<RwLock<IdMapping>>,
     /// Storage path for persisting vector index and ID mapping
     storage_path: PathBuf,
+    /// Vamana tuning and persistence policy, read once at construction
+    index_config: VectorIndexConfig,
     /// Lock order: 3 - Acquire last (was 4 when graph was here)
     /// Shared consolidation event buffer for introspection
     /// Records edge formation, strengthening, and pruning events
@@ -136,7 +205,7 @@ impl RetrievalEngine {
     /// - Vector mappings are stored atomically with memories in RocksDB
     /// - Vamana index is rebuilt from RocksDB on startup (pure in-memory cache)
     /// - No more file-based IdMapping = no more orphaned memories
-    pub fn new(storage: Arc<MemoryStorage>, embedder: Arc<MiniLMEmbedder>) -> Result<Self> {
+    pub fn new(storage: Arc<MemoryStorage>, embedder: Arc<dyn Embedder>) -> Result<Self> {
         Self::with_event_buffer(storage, embedder, None)
     }
 
@@ -151,23 +220,15 @@ impl RetrievalEngine {
     /// ATOMIC STARTUP: Rebuilds Vamana from RocksDB mappings for crash safety.
     pub fn with_event_buffer(
         storage: Arc<MemoryStorage>,
-        embedder: Arc<MiniLMEmbedder>,
+        embedder: Arc<dyn Embedder>,
         consolidation_events: Option<Arc<RwLock<ConsolidationEventBuffer>>>,
     ) -> Result<Self> {
         let storage_path = storage.path().to_path_buf();
-
-        // Initialize Vamana index optimized for 10M+ memories per user
-        let vamana_config = VamanaConfig {
-            dimension: 384,        // MiniLM dimension
-            max_degree: 32,        // Increased for better recall at scale
-            search_list_size: 100, // 2x for better accuracy with 10M vectors
-            alpha: 1.2,
-            use_mmap: false, // Keep in memory for low-latency robotics
-            ..Default::default()
-        };
+        let index_config = VectorIndexConfig::from_env();
 
         let vector_index =
-            VamanaIndex::new(vamana_config).context("Failed to initialize Vamana vector index")?;
+            Self::new_vector_index(&index_config, &storage_path, embedder.dimension())
+                .context("Failed to initialize Vamana vector index")?;
         let id_mapping = IdMapping::new();
 
         // NOTE: Memory graph (Hebbian associations) has been consolidated into GraphMemory
@@ -180,6 +241,7 @@ impl RetrievalEngine {
             vector_index: Arc::new(RwLock::new(vector_index)),
             id_mapping: Arc::new(RwLock::new(id_mapping)),
             storage_path,
+            index_config,
             consolidation_events,
         };
 
@@ -189,6 +251,33 @@ impl RetrievalEngine {
         Ok(engine)
     }
 
+    /// Empty Vamana index using the configured tuning
+    ///
+    /// Defaults are optimized for 10M+ memories per user.
+    fn new_vector_index(
+        index_config: &VectorIndexConfig,
+        storage_path: &Path,
+        dimension: usize,
+    ) -> Result<VamanaIndex> {
+        let config = VamanaConfig {
+            dimension,
+            max_degree: index_config.max_degree,
+            search_list_size: index_config.search_list_size,
+            alpha: index_config.alpha,
+            use_mmap: index_config.use_mmap,
+            ..Default::default()
+        };
+        let mmap_path = index_config
+            .use_mmap
+            .then(|| storage_path.join("vector_index"));
+        VamanaIndex::with_storage_path(config, mmap_path)
+    }
+
+    /// Whether the index should be persisted on graceful shutdown
+    pub fn save_on_shutdown(&self) -> bool {
+        self.index_config.save_on_shutdown
+    }
+
     /// Initialize Vamana index from persisted file or rebuild from RocksDB
     ///
     /// INSTANT STARTUP ARCHITECTURE:
@@ -205,7 +294,9 @@ impl RetrievalEngine {
             .storage_path
             .join("vector_index")
             .join(VAMANA_INDEX_FILE);
-        if vamana_path.exists() {
+        if self.index_config.rebuild_on_startup {
+            info!("Vector index rebuild on startup requested, ignoring persisted .vamana file");
+        } else if vamana_path.exists() {
             if let Ok(loaded) = self.try_load_persisted_vamana(&vamana_path) {
                 if loaded {
                     info!(
@@ -382,6 +473,16 @@ impl RetrievalEngine {
             }
         };
 
+        // An index built by a different embedding model cannot answer queries
+        if loaded_index.config.dimension != self.embedder.dimension() {
+            warn!(
+                "Vamana file has dimension {} but embedder produces {}, will rebuild",
+                loaded_index.config.dimension,
+                self.embedder.dimension()
+            );
+            return Ok(false);
+        }
+
         let loaded_count = loaded_index.len();
 
         // Get mappings from RocksDB to rebuild IdMapping
@@ -727,8 +828,34 @@ impl RetrievalEngine {
     /// With chunked embeddings, multiple vectors can map to the same memory.
     /// This function deduplicates by MemoryId, keeping the highest-scoring chunk.
     ///
+    /// If the query has a structured filter ([`Query::index_filter`]), it is
+    /// resolved against the storage indices and pushed into the vector search
+    /// (see [`search_ids_within`](Self::search_ids_within)).
+    ///
     /// Returns (MemoryId, similarity_score) pairs
     pub fn search_ids(&self, query: &Query, limit: usize) -> Result<Vec<(MemoryId, f32)>> {
+        let candidates: Option<HashSet<MemoryId>> = match query.index_filter() {
+            Some(filter) => Some(self.storage.search_ids(filter)?.into_iter().collect()),
+            None => None,
+        };
+        self.search_ids_within(query, limit, candidates.as_ref())
+    }
+
+    /// Search for memory IDs, restricted to a candidate set
+    ///
+    /// The candidates are mapped to their vector IDs and applied during index
+    /// traversal (filtered greedy search), so up to `limit` results come back
+    /// even when the candidate set is a small fraction of the index.
+    pub fn search_ids_within(
+        &self,
+        query: &Query,
+        limit: usize,
+        candidates: Option<&HashSet<MemoryId>>,
+    ) -> Result<Vec<(MemoryId, f32)>> {
+        if candidates.is_some_and(|c| c.is_empty()) {
+            return Ok(Vec::new());
+        }
+
         // BUG-006 FIX: Log warning for empty queries
         let query_embedding = if let Some(embedding) = &query.query_embedding {
             embedding.clone()
@@ -768,13 +895,23 @@ impl RetrievalEngine {
             };
 
         // Search vector index - fetch more candidates for chunk deduplication
+        // Lock order: vector_index (1) before id_mapping (2)
         let index = self.vector_index.read();
-        let results = index
-            .search(
-                &query_embedding,
-                limit * VECTOR_SEARCH_CANDIDATE_MULTIPLIER * 2,
-            )
-            .context("Vector search failed")?;
+        let id_mapping = self.id_mapping.read();
+        let search_k = limit * VECTOR_SEARCH_CANDIDATE_MULTIPLIER * 2;
+        let results = match candidates {
+            Some(candidates) => {
+                let allowed: HashSet<u32> = candidates
+                    .iter()
+                    .filter_map(|id| id_mapping.memory_to_vectors.get(id))
+                    .flatten()
+                    .copied()
+                    .collect();
+                index.search_filtered(&query_embedding, search_k, &allowed)
+            }
+            None => index.search(&query_embedding, search_k),
+        }
+        .context("Vector search failed")?;
 
         // Map vector IDs to memory IDs, deduplicating by MemoryId (keep highest similarity)
         //
@@ -783,7 +920,6 @@ impl RetrievalEngine {
         // - Similar vectors have dot ≈ 1.0, so distance ≈ -1.0
         // - Orthogonal vectors have dot ≈ 0.0, so distance ≈ 0.0
         // Convert: similarity = -distance (so similarity = dot product = cosine similarity)
-        let id_mapping = self.id_mapping.read();
         let mut best_scores: std::collections::HashMap<MemoryId, f32> =
             std::collections::HashMap::new();
 
@@ -1120,11 +1256,16 @@ impl RetrievalEngine {
 
     /// Spatial search: Find memories within geographic radius
     /// Uses haversine distance for accurate earth-surface calculations
+    /// A local_filter takes precedence and searches the robot's map frame instead
     fn spatial_search(&self, query: &Query, limit: usize) -> Result<Vec<SharedMemory>> {
+        if let Some(local_filter) = &query.local_filter {
+            return self.local_spatial_search(query, local_filter, limit);
+        }
+
         let geo_filter = query
             .geo_filter
             .as_ref()
-            .ok_or_else(|| anyhow::anyhow!("Spatial search requires geo_filter"))?;
+            .ok_or_else(|| anyhow::anyhow!("Spatial search requires geo_filter or local_filter"))?;
 
         let criteria = SearchCriteria::ByLocation {
             lat: geo_filter.lat,
@@ -1161,6 +1302,34 @@ impl RetrievalEngine {
         Ok(memories)
     }
 
+    /// Local-frame spatial search: radius, box or k-nearest in a robot's map frame
+    /// Results come from the voxel index already ordered by distance
+    fn local_spatial_search(
+        &self,
+        query: &Query,
+        local_filter: &LocalSpatialFilter,
+        limit: usize,
+    ) -> Result<Vec<SharedMemory>> {
+        let robot_id = query
+            .robot_id
+            .as_ref()
+            .ok_or_else(|| anyhow::anyhow!("Local spatial search requires robot_id"))?;
+
+        let mut memories = Vec::new();
+        for (id, _distance) in self.storage.search_local(robot_id, local_filter)? {
+            if memories.len() >= limit {
+                break;
+            }
+            if let Ok(memory) = self.storage.get(&id) {
+                // Apply additional filters
+                if self.matches_filters(&memory, query) {
+                    memories.push(Arc::new(memory));
+                }
+            }
+        }
+        Ok(memories)
+    }
+
     /// Mission search: Retrieve all memories from a specific mission
     /// Useful for mission replay, analysis, and learning
     fn mission_search(&self, query: &Query, limit: usize) -> Result<Vec<SharedMemory>> {
@@ -1303,6 +1472,66 @@ impl RetrievalEngine {
         Ok(())
     }
 
+    /// Re-embed every stored memory with the current embedder and rebuild the index
+    ///
+    /// Required after switching embedding model or dimension: vectors from the
+    /// old model live in a different space, so the index is replaced rather
+    /// than updated. Storage is read one batch at a time. Returns the number
+    /// of memories re-embedded.
+    pub fn reembed_all(&self) -> Result<usize> {
+        const BATCH_SIZE: usize = 64;
+
+        info!(
+            "Re-embedding memories with {} ({} dims)",
+            self.embedder.model_id(),
+            self.embedder.dimension()
+        );
+
+        {
+            let mut index = self.vector_index.write();
+            *index = Self::new_vector_index(
+                &self.index_config,
+                &self.storage_path,
+                self.embedder.dimension(),
+            )
+            .context("Failed to initialize Vamana vector index")?;
+            self.id_mapping.write().clear();
+        }
+
+        let start_time = std::time::Instant::now();
+        let mut reembedded = 0;
+        let mut after: Option<MemoryId> = None;
+        loop {
+            let batch = self.storage.get_page(after.as_ref(), BATCH_SIZE)?;
+            let Some(last) = batch.last() else { break };
+            after = Some(last.id.clone());
+
+            let texts: Vec<&str> = batch
+                .iter()
+                .map(|m| m.experience.content.as_str())
+                .collect();
+            let embeddings = self
+                .embedder
+                .encode_batch(&texts)
+                .context("Failed to re-embed memory batch")?;
+
+            for (mut memory, embedding) in batch.into_iter().zip(embeddings) {
+                memory.experience.embeddings = Some(embedding);
+                self.storage.update(&memory)?;
+                self.index_memory(&memory)?;
+                reembedded += 1;
+            }
+        }
+
+        self.save()?;
+        info!(
+            "Re-embedded {} memories in {:.2}s",
+            reembedded,
+            start_time.elapsed().as_secs_f64()
+        );
+        Ok(reembedded)
+    }
+
     // NOTE: Memory graph functionality has been consolidated into GraphMemory
     // which is managed at the API layer (MultiUserMemoryManager.graph_memories)
     // The following methods are preserved for API compatibility but are no-ops:
@@ -0,0 +1,1346 @@
+//! RFC 5545 Recurrence Rules (RRULE / EXDATE)
+//!
+//! Expands iCalendar recurrence rules for todos and recurring reminders:
+//! - FREQ from SECONDLY to YEARLY with INTERVAL, COUNT and UNTIL
+//! - BYSECOND, BYMINUTE, BYHOUR, BYDAY (with ordinals), BYMONTHDAY,
+//!   BYYEARDAY, BYWEEKNO, BYMONTH and WKST
+//! - BYSETPOS, e.g. "last Friday": `FREQ=MONTHLY;BYDAY=FR;BYSETPOS=-1`
+//! - EXDATE exclusions and DTSTART with TZID
+//!
+//! Occurrences are generated as local wall-clock times in the series'
+//! timezone and only then converted to UTC, so "09:00 Europe/Berlin" stays
+//! at 09:00 across DST changes. Local times inside a DST gap use the offset
+//! in effect before the gap and ambiguous times resolve to the first
+//! instance, as RFC 5545 §3.3.5 specifies.
+//!
+//! Lookups such as [`RecurrenceSet::next_after`] skip straight to the period
+//! containing the requested instant, and every iterator stops after a fixed
+//! expansion budget, so an imported `FREQ=SECONDLY` rule anchored decades ago
+//! cannot stall a request.
+
+use anyhow::{anyhow, bail, Context, Result};
+use chrono::{
+    DateTime, Datelike, Duration, LocalResult, NaiveDate, NaiveDateTime, NaiveTime, Offset,
+    TimeZone, Timelike, Utc, Weekday,
+};
+use chrono_tz::Tz;
+use std::collections::VecDeque;
+use std::fmt;
+use std::str::FromStr;
+
+use super::ics::ContentLine;
+use super::types::Recurrence;
+
+/// Periods scanned without a match before giving up, so rules that can
+/// never match (e.g. `FREQ=YEARLY;BYMONTH=2;BYMONTHDAY=30`) terminate
+const MAX_EMPTY_PERIODS: u32 = 10_000;
+
+/// Latest year expanded; keeps period arithmetic far from chrono's limits
+const MAX_YEAR: i32 = 9999;
+
+/// Periods plus candidate instances one iterator may expand before giving
+/// up, so no single lookup can spin through millions of SECONDLY periods or
+/// allocate a YEARLY period with every second of the year
+const MAX_EXPANDED_PER_ITER: usize = 500_000;
+
+/// How far before the requested instant [`RecurrenceSet::iter_from`] starts.
+/// Periods are laid out in local time, so this covers the largest jump in
+/// UTC offset (Samoa's 2011 dateline switch, 24h) plus DST gap handling.
+const JUMP_SLACK_HOURS: i64 = 26;
+
+/// RRULE frequency (`FREQ=`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Frequency {
+    Secondly,
+    Minutely,
+    Hourly,
+    Daily,
+    Weekly,
+    Monthly,
+    Yearly,
+}
+
+impl Frequency {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Frequency::Secondly => "SECONDLY",
+            Frequency::Minutely => "MINUTELY",
+            Frequency::Hourly => "HOURLY",
+            Frequency::Daily => "DAILY",
+            Frequency::Weekly => "WEEKLY",
+            Frequency::Monthly => "MONTHLY",
+            Frequency::Yearly => "YEARLY",
+        }
+    }
+
+    /// Length of one period for the sub-daily frequencies
+    fn step_seconds(&self) -> Option<i64> {
+        match self {
+            Frequency::Secondly => Some(1),
+            Frequency::Minutely => Some(60),
+            Frequency::Hourly => Some(3600),
+            _ => None,
+        }
+    }
+}
+
+impl FromStr for Frequency {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_uppercase().as_str() {
+            "SECONDLY" => Ok(Frequency::Secondly),
+            "MINUTELY" => Ok(Frequency::Minutely),
+            "HOURLY" => Ok(Frequency::Hourly),
+            "DAILY" => Ok(Frequency::Daily),
+            "WEEKLY" => Ok(Frequency::Weekly),
+            "MONTHLY" => Ok(Frequency::Monthly),
+            "YEARLY" => Ok(Frequency::Yearly),
+            other => bail!("unknown FREQ '{other}'"),
+        }
+    }
+}
+
+/// BYDAY entry: a weekday with an optional ordinal (`-1FR` = last Friday)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WeekdayNum {
+    pub ordinal: Option<i32>,
+    pub weekday: Weekday,
+}
+
+impl FromStr for WeekdayNum {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let s = s.trim();
+        if s.len() < 2 || !s.is_char_boundary(s.len() - 2) {
+            bail!("invalid BYDAY value '{s}'");
+        }
+        let (ordinal, day) = s.split_at(s.len() - 2);
+        let weekday = parse_weekday(day)?;
+        let ordinal = if ordinal.is_empty() {
+            None
+        } else {
+            let n: i32 = ordinal
+                .parse()
+                .map_err(|_| anyhow!("invalid BYDAY ordinal '{ordinal}'"))?;
+            if n == 0 || n.abs() > 53 {
+                bail!("BYDAY ordinal {n} out of range");
+            }
+            Some(n)
+        };
+        Ok(WeekdayNum { ordinal, weekday })
+    }
+}
+
+impl fmt::Display for WeekdayNum {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(n) = self.ordinal {
+            write!(f, "{n}")?;
+        }
+        f.write_str(weekday_code(self.weekday))
+    }
+}
+
+/// Two-letter iCalendar weekday code
+pub fn weekday_code(day: Weekday) -> &'static str {
+    match day {
+        Weekday::Mon => "MO",
+        Weekday::Tue => "TU",
+        Weekday::Wed => "WE",
+        Weekday::Thu => "TH",
+        Weekday::Fri => "FR",
+        Weekday::Sat => "SA",
+        Weekday::Sun => "SU",
+    }
+}
+
+fn parse_weekday(s: &str) -> Result<Weekday> {
+    match s.to_uppercase().as_str() {
+        "MO" => Ok(Weekday::Mon),
+        "TU" => Ok(Weekday::Tue),
+        "WE" => Ok(Weekday::Wed),
+        "TH" => Ok(Weekday::Thu),
+        "FR" => Ok(Weekday::Fri),
+        "SA" => Ok(Weekday::Sat),
+        "SU" => Ok(Weekday::Sun),
+        other => bail!("unknown weekday '{other}'"),
+    }
+}
+
+/// An iCalendar DATE or DATE-TIME value, in the form it was written
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IcalTime {
+    /// `20250131`
+    Date(NaiveDate),
+    /// `20250131T090000` (local to the property's TZID, or floating)
+    Local(NaiveDateTime),
+    /// `20250131T090000Z`
+    Utc(DateTime<Utc>),
+}
+
+impl IcalTime {
+    /// Parse a DATE or DATE-TIME value
+    pub fn parse(value: &str) -> Result<Self> {
+        let value = value.trim();
+        if let Some(stripped) = value.strip_suffix('Z').or_else(|| value.strip_suffix('z')) {
+            let naive = NaiveDateTime::parse_from_str(stripped, "%Y%m%dT%H%M%S")
+                .with_context(|| format!("invalid UTC date-time '{value}'"))?;
+            return Ok(IcalTime::Utc(Utc.from_utc_datetime(&naive)));
+        }
+        if value.contains('T') || value.contains('t') {
+            let naive = NaiveDateTime::parse_from_str(&value.to_uppercase(), "%Y%m%dT%H%M%S")
+                .with_context(|| format!("invalid date-time '{value}'"))?;
+            return Ok(IcalTime::Local(naive));
+        }
+        let date = NaiveDate::parse_from_str(value, "%Y%m%d")
+            .with_context(|| format!("invalid date '{value}'"))?;
+        Ok(IcalTime::Date(date))
+    }
+
+    /// Resolve to an instant, reading local values in `tz`
+    /// (DATE values resolve to local midnight)
+    pub fn to_utc(&self, tz: Tz) -> DateTime<Utc> {
+        match self {
+            IcalTime::Date(d) => resolve_local(tz, d.and_time(NaiveTime::MIN)),
+            IcalTime::Local(n) => resolve_local(tz, *n),
+            IcalTime::Utc(u) => *u,
+        }
+    }
+
+    pub fn is_date(&self) -> bool {
+        matches!(self, IcalTime::Date(_))
+    }
+}
+
+impl fmt::Display for IcalTime {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            IcalTime::Date(d) => write!(f, "{}", d.format("%Y%m%d")),
+            IcalTime::Local(n) => write!(f, "{}", n.format("%Y%m%dT%H%M%S")),
+            IcalTime::Utc(u) => write!(f, "{}", u.format("%Y%m%dT%H%M%SZ")),
+        }
+    }
+}
+
+/// Convert a local wall-clock time in `tz` to UTC
+///
+/// Times skipped by a DST gap are read with the offset in effect before the
+/// gap (02:30 on a spring-forward night becomes 03:30); times repeated by a
+/// fall-back transition resolve to their first instance.
+pub fn resolve_local(tz: Tz, local: NaiveDateTime) -> DateTime<Utc> {
+    match tz.from_local_datetime(&local) {
+        LocalResult::Single(dt) => dt.with_timezone(&Utc),
+        LocalResult::Ambiguous(first, _) => first.with_timezone(&Utc),
+        LocalResult::None => {
+            let before = tz
+                .offset_from_local_datetime(&(local - Duration::hours(3)))
+                .earliest()
+                .map(|o| o.fix().local_minus_utc())
+                .unwrap_or(0);
+            Utc.from_utc_datetime(&(local - Duration::seconds(before as i64)))
+        }
+    }
+}
+
+/// Parse an IANA timezone name (accepting Mozilla-style `/vendor/.../Area/City` prefixes)
+pub fn parse_tzid(tzid: &str) -> Result<Tz> {
+    let tzid = tzid.trim().trim_matches('"');
+    if let Ok(tz) = tzid.parse::<Tz>() {
+        return Ok(tz);
+    }
+    // Some producers prefix the Olson name with a vendor path
+    let parts: Vec<&str> = tzid.split('/').filter(|p| !p.is_empty()).collect();
+    for start in 0..parts.len() {
+        if let Ok(tz) = parts[start..].join("/").parse::<Tz>() {
+            return Ok(tz);
+        }
+    }
+    bail!("unknown timezone '{tzid}'")
+}
+
+/// A parsed RRULE value
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RRule {
+    pub freq: Frequency,
+    pub interval: u32,
+    pub count: Option<u32>,
+    pub until: Option<IcalTime>,
+    pub by_second: Vec<u32>,
+    pub by_minute: Vec<u32>,
+    pub by_hour: Vec<u32>,
+    pub by_day: Vec<WeekdayNum>,
+    pub by_month_day: Vec<i32>,
+    pub by_year_day: Vec<i32>,
+    pub by_week_no: Vec<i32>,
+    pub by_month: Vec<u32>,
+    pub by_set_pos: Vec<i32>,
+    pub wkst: Weekday,
+}
+
+impl RRule {
+    /// A rule with only FREQ set
+    pub fn new(freq: Frequency) -> Self {
+        Self {
+            freq,
+            interval: 1,
+            count: None,
+            until: None,
+            by_second: Vec::new(),
+            by_minute: Vec::new(),
+            by_hour: Vec::new(),
+            by_day: Vec::new(),
+            by_month_day: Vec::new(),
+            by_year_day: Vec::new(),
+            by_week_no: Vec::new(),
+            by_month: Vec::new(),
+            by_set_pos: Vec::new(),
+            wkst: Weekday::Mon,
+        }
+    }
+}
+
+fn parse_list<T: FromStr>(name: &str, value: &str, valid: impl Fn(&T) -> bool) -> Result<Vec<T>> {
+    value
+        .split(',')
+        .map(|v| {
+            v.trim()
+                .parse::<T>()
+                .ok()
+                .filter(&valid)
+                .ok_or_else(|| anyhow!("invalid {name} value '{v}'"))
+        })
+        .collect()
+}
+
+fn signed_in_range(max: i32) -> impl Fn(&i32) -> bool {
+    move |n| *n != 0 && n.abs() <= max
+}
+
+impl FromStr for RRule {
+    type Err = anyhow::Error;
+
+    /// Parse `FREQ=...;...`, with or without a leading `RRULE:`
+    fn from_str(s: &str) -> Result<Self> {
+        let s = s.trim();
+        let s = if s.len() >= 6 && s[..6].eq_ignore_ascii_case("RRULE:") {
+            &s[6..]
+        } else {
+            s
+        };
+
+        let mut freq = None;
+        let mut rule = RRule::new(Frequency::Daily);
+
+        for part in s.split(';').map(str::trim).filter(|p| !p.is_empty()) {
+            let (name, value) = part
+                .split_once('=')
+                .ok_or_else(|| anyhow!("malformed RRULE part '{part}'"))?;
+            let name = name.trim().to_uppercase();
+            let value = value.trim();
+            match name.as_str() {
+                "FREQ" => freq = Some(value.parse::<Frequency>()?),
+                "INTERVAL" => {
+                    rule.interval = value
+                        .parse()
+                        .ok()
+                        .filter(|n| *n >= 1)
+                        .ok_or_else(|| anyhow!("invalid INTERVAL '{value}'"))?
+                }
+                "COUNT" => {
+                    rule.count = Some(
+                        value
+                            .parse()
+                            .ok()
+                            .filter(|n| *n >= 1)
+                            .ok_or_else(|| anyhow!("invalid COUNT '{value}'"))?,
+                    )
+                }
+                "UNTIL" => rule.until = Some(IcalTime::parse(value)?),
+                "BYSECOND" => rule.by_second = parse_list(&name, value, |n: &u32| *n <= 59)?,
+                "BYMINUTE" => rule.by_minute = parse_list(&name, value, |n: &u32| *n <= 59)?,
+                "BYHOUR" => rule.by_hour = parse_list(&name, value, |n: &u32| *n <= 23)?,
+                "BYDAY" => rule.by_day = parse_list(&name, value, |_: &WeekdayNum| true)?,
+                "BYMONTHDAY" => rule.by_month_day = parse_list(&name, value, signed_in_range(31))?,
+                "BYYEARDAY" => rule.by_year_day = parse_list(&name, value, signed_in_range(366))?,
+                "BYWEEKNO" => rule.by_week_no = parse_list(&name, value, signed_in_range(53))?,
+                "BYMONTH" => {
+                    rule.by_month = parse_list(&name, value, |n: &u32| (1..=12).contains(n))?
+                }
+                "BYSETPOS" => rule.by_set_pos = parse_list(&name, value, signed_in_range(366))?,
+                "WKST" => rule.wkst = parse_weekday(value)?,
+                // Experimental parts are allowed by RFC 5545 and ignored here
+                x if x.starts_with("X-") => {}
+                other => bail!("unsupported RRULE part '{other}'"),
+            }
+        }
+
+        rule.freq = freq.ok_or_else(|| anyhow!("RRULE is missing FREQ"))?;
+        if rule.count.is_some() && rule.until.is_some() {
+            bail!("RRULE cannot have both COUNT and UNTIL");
+        }
+        if rule.freq != Frequency::Yearly && !rule.by_week_no.is_empty() {
+            bail!("BYWEEKNO is only valid with FREQ=YEARLY");
+        }
+        if matches!(
+            rule.freq,
+            Frequency::Daily | Frequency::Weekly | Frequency::Monthly
+        ) && !rule.by_year_day.is_empty()
+        {
+            bail!("BYYEARDAY is not valid with FREQ={}", rule.freq.as_str());
+        }
+        if rule.freq == Frequency::Weekly && !rule.by_month_day.is_empty() {
+            bail!("BYMONTHDAY is not valid with FREQ=WEEKLY");
+        }
+        Ok(rule)
+    }
+}
+
+impl fmt::Display for RRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fn join<T: ToString>(items: &[T]) -> String {
+            items
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+                .join(",")
+        }
+
+        write!(f, "FREQ={}", self.freq.as_str())?;
+        if self.interval > 1 {
+            write!(f, ";INTERVAL={}", self.interval)?;
+        }
+        if let Some(count) = self.count {
+            write!(f, ";COUNT={count}")?;
+        }
+        if let Some(until) = self.until {
+            write!(f, ";UNTIL={until}")?;
+        }
+        let lists = [
+            ("BYMONTH", join(&self.by_month)),
+            ("BYWEEKNO", join(&self.by_week_no)),
+            ("BYYEARDAY", join(&self.by_year_day)),
+            ("BYMONTHDAY", join(&self.by_month_day)),
+            ("BYDAY", join(&self.by_day)),
+            ("BYHOUR", join(&self.by_hour)),
+            ("BYMINUTE", join(&self.by_minute)),
+            ("BYSECOND", join(&self.by_second)),
+            ("BYSETPOS", join(&self.by_set_pos)),
+        ];
+        for (name, value) in lists {
+            if !value.is_empty() {
+                write!(f, ";{name}={value}")?;
+            }
+        }
+        if self.wkst != Weekday::Mon {
+            write!(f, ";WKST={}", weekday_code(self.wkst))?;
+        }
+        Ok(())
+    }
+}
+
+// =============================================================================
+// RECURRENCE SETS
+// =============================================================================
+
+/// A rule anchored at DTSTART in a timezone, minus EXDATE exclusions
+#[derive(Debug, Clone)]
+pub struct RecurrenceSet {
+    pub rule: RRule,
+    /// First instance of the series, as local wall-clock time in `tz`
+    pub dtstart: NaiveDateTime,
+    pub tz: Tz,
+    pub exdates: Vec<DateTime<Utc>>,
+}
+
+impl RecurrenceSet {
+    /// Anchor `rule` at `dtstart` (whole seconds; iCalendar has no finer unit)
+    pub fn new(rule: RRule, dtstart: DateTime<Utc>, tz: Tz) -> Self {
+        let local = dtstart.with_timezone(&tz).naive_local();
+        Self {
+            rule,
+            dtstart: local.with_nanosecond(0).unwrap_or(local),
+            tz,
+            exdates: Vec::new(),
+        }
+    }
+
+    /// All occurrences in order (bounded by COUNT/UNTIL, otherwise open-ended)
+    ///
+    /// Stops early once [`MAX_EXPANDED_PER_ITER`] periods and candidates have
+    /// been expanded; use [`Self::iter_from`] to start near a given instant.
+    pub fn iter(&self) -> Occurrences<'_> {
+        Occurrences {
+            set: self,
+            rule: with_dtstart_defaults(&self.rule, self.dtstart),
+            period: 0,
+            buffer: VecDeque::new(),
+            generated: 0,
+            empty_periods: 0,
+            budget: MAX_EXPANDED_PER_ITER,
+            done: false,
+        }
+    }
+
+    /// Occurrences from shortly before `from` onwards (earlier ones may be
+    /// included, later ones are never missed)
+    ///
+    /// Earlier periods are skipped arithmetically rather than expanded, so a
+    /// SECONDLY rule anchored in 1970 costs the same as one anchored today.
+    /// With COUNT, skipping needs to know how many instances were passed, so
+    /// it only happens for rules with exactly one instance per period (no
+    /// BYxxx parts and a fixed-length period); other COUNT rules start at
+    /// DTSTART and rely on the expansion cap.
+    pub fn iter_from(&self, from: DateTime<Utc>) -> Occurrences<'_> {
+        let mut iter = self.iter();
+        let period = from
+            .checked_sub_signed(Duration::hours(JUMP_SLACK_HOURS))
+            .and_then(|t| self.period_index(t.with_timezone(&self.tz).naive_local()));
+        let Some(period) = period.filter(|p| *p > 0) else {
+            return iter;
+        };
+        if self.rule.count.is_some() {
+            if !self.one_instance_per_period() {
+                return iter;
+            }
+            iter.generated = u32::try_from(period).unwrap_or(u32::MAX);
+        }
+        iter.period = period;
+        iter
+    }
+
+    /// First occurrence strictly after `after`
+    pub fn next_after(&self, after: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        self.iter_from(after).find(|t| *t > after)
+    }
+
+    /// Occurrences in `[start, end)`, at most `limit`
+    pub fn between(
+        &self,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+        limit: usize,
+    ) -> Vec<DateTime<Utc>> {
+        self.iter_from(start)
+            .skip_while(|t| *t < start)
+            .take_while(|t| *t < end)
+            .take(limit)
+            .collect()
+    }
+
+    /// Index (in units of INTERVAL) of the last period starting at or
+    /// before `local`, matching the periods [`Occurrences`] expands
+    fn period_index(&self, local: NaiveDateTime) -> Option<i64> {
+        let start = self.dtstart;
+        let units = match self.rule.freq {
+            Frequency::Yearly => i64::from(local.year()) - i64::from(start.year()),
+            Frequency::Monthly => {
+                (i64::from(local.year()) * 12 + i64::from(local.month0()))
+                    - (i64::from(start.year()) * 12 + i64::from(start.month0()))
+            }
+            Frequency::Weekly => {
+                let date = start.date();
+                let week_start = date - Duration::days(days_since_wkst(date, self.rule.wkst));
+                (local.date() - week_start).num_days().div_euclid(7)
+            }
+            Frequency::Daily => (local.date() - start.date()).num_days(),
+            freq => {
+                let unit = freq.step_seconds().unwrap_or(1);
+                (local - period_anchor(freq, start)?)
+                    .num_seconds()
+                    .div_euclid(unit)
+            }
+        };
+        Some(units.div_euclid(i64::from(self.rule.interval.max(1))))
+    }
+
+    /// Whether every period holds exactly one instance, so the number of
+    /// instances before a period is its index
+    fn one_instance_per_period(&self) -> bool {
+        let rule = &self.rule;
+        rule.freq <= Frequency::Weekly
+            && rule.by_second.is_empty()
+            && rule.by_minute.is_empty()
+            && rule.by_hour.is_empty()
+            && rule.by_day.is_empty()
+            && rule.by_month_day.is_empty()
+            && rule.by_year_day.is_empty()
+            && rule.by_week_no.is_empty()
+            && rule.by_month.is_empty()
+            && rule.by_set_pos.is_empty()
+    }
+}
+
+/// Start of the first sub-daily period: DTSTART truncated to the frequency
+fn period_anchor(freq: Frequency, dtstart: NaiveDateTime) -> Option<NaiveDateTime> {
+    match freq {
+        Frequency::Hourly => dtstart.with_minute(0)?.with_second(0),
+        Frequency::Minutely => dtstart.with_second(0),
+        _ => Some(dtstart),
+    }
+}
+
+/// Fill in the BYxxx parts RFC 5545 takes from DTSTART when a rule omits them
+fn with_dtstart_defaults(rule: &RRule, dtstart: NaiveDateTime) -> RRule {
+    let mut rule = rule.clone();
+    let no_day_parts = rule.by_week_no.is_empty()
+        && rule.by_year_day.is_empty()
+        && rule.by_month_day.is_empty()
+        && rule.by_day.is_empty();
+    match rule.freq {
+        Frequency::Yearly if no_day_parts => {
+            if rule.by_month.is_empty() {
+                rule.by_month = vec![dtstart.month()];
+            }
+            rule.by_month_day = vec![dtstart.day() as i32];
+        }
+        Frequency::Monthly if no_day_parts => {
+            rule.by_month_day = vec![dtstart.day() as i32];
+        }
+        Frequency::Weekly if no_day_parts => {
+            rule.by_day = vec![WeekdayNum {
+                ordinal: None,
+                weekday: dtstart.weekday(),
+            }];
+        }
+        _ => {}
+    }
+    rule
+}
+
+/// Iterator over a [`RecurrenceSet`]
+pub struct Occurrences<'a> {
+    set: &'a RecurrenceSet,
+    /// Rule with DTSTART defaults applied
+    rule: RRule,
+    /// Index of the next period to expand (in units of INTERVAL)
+    period: i64,
+    buffer: VecDeque<NaiveDateTime>,
+    /// Instances produced by the rule so far, before EXDATE (for COUNT)
+    generated: u32,
+    empty_periods: u32,
+    /// Periods and candidate instances left to expand (see [`MAX_EXPANDED_PER_ITER`])
+    budget: usize,
+    done: bool,
+}
+
+impl Iterator for Occurrences<'_> {
+    type Item = DateTime<Utc>;
+
+    fn next(&mut self) -> Option<DateTime<Utc>> {
+        loop {
+            if self.done {
+                return None;
+            }
+            if let Some(local) = self.buffer.pop_front() {
+                if self.rule.count.is_some_and(|c| self.generated >= c) {
+                    self.done = true;
+                    return None;
+                }
+                let at = resolve_local(self.set.tz, local);
+                let past_until = match self.rule.until {
+                    Some(IcalTime::Date(d)) => local.date() > d,
+                    Some(IcalTime::Local(n)) => local > n,
+                    Some(IcalTime::Utc(u)) => at > u,
+                    None => false,
+                };
+                if past_until {
+                    self.done = true;
+                    return None;
+                }
+                self.generated += 1;
+                if self.set.exdates.contains(&at) {
+                    continue;
+                }
+                return Some(at);
+            }
+
+            if self.empty_periods >= MAX_EMPTY_PERIODS {
+                self.done = true;
+                return None;
+            }
+            match self.expand_period() {
+                Some(instances) if instances.is_empty() => self.empty_periods += 1,
+                Some(instances) => {
+                    self.empty_periods = 0;
+                    self.buffer.extend(instances);
+                }
+                None => self.done = true,
+            }
+        }
+    }
+}
+
+impl Occurrences<'_> {
+    /// Expand the current period into its sorted instances and advance.
+    /// Returns None once the calendar range is exhausted.
+    fn expand_period(&mut self) -> Option<Vec<NaiveDateTime>> {
+        self.budget = self.budget.checked_sub(1)?;
+        let step = self.period.checked_mul(self.rule.interval as i64)?;
+        self.period += 1;
+        let rule = &self.rule;
+        let dtstart = self.set.dtstart;
+
+        let (dates, period_time) = match rule.freq {
+            Frequency::Yearly => {
+                let year = dtstart.year().checked_add(i32::try_from(step).ok()?)?;
+                if year > MAX_YEAR {
+                    return None;
+                }
+                let first = NaiveDate::from_ymd_opt(year, 1, 1)?;
+                let last = NaiveDate::from_ymd_opt(year, 12, 31)?;
+                (days_between(first, last), None)
+            }
+            Frequency::Monthly => {
+                let index =
+                    (dtstart.year() as i64 * 12 + dtstart.month0() as i64).checked_add(step)?;
+                let year = i32::try_from(index.div_euclid(12)).ok()?;
+                if year > MAX_YEAR {
+                    return None;
+                }
+                let month = index.rem_euclid(12) as u32 + 1;
+                let first = NaiveDate::from_ymd_opt(year, month, 1)?;
+                let last = first + Duration::days(days_in_month(year, month) as i64 - 1);
+                (days_between(first, last), None)
+            }
+            Frequency::Weekly => {
+                let date = dtstart.date();
+                let week_start = date - Duration::days(days_since_wkst(date, rule.wkst));
+                let first =
+                    week_start.checked_add_signed(Duration::try_days(step.checked_mul(7)?)?)?;
+                if first.year() > MAX_YEAR {
+                    return None;
+                }
+                (days_between(first, first + Duration::days(6)), None)
+            }
+            Frequency::Daily => {
+                let day = dtstart
+                    .date()
+                    .checked_add_signed(Duration::try_days(step)?)?;
+                if day.year() > MAX_YEAR {
+                    return None;
+                }
+                (vec![day], None)
+            }
+            freq => {
+                let unit = freq.step_seconds().unwrap_or(1);
+                let at = period_anchor(freq, dtstart)?
+                    .checked_add_signed(Duration::try_seconds(step.checked_mul(unit)?)?)?;
+                if at.year() > MAX_YEAR {
+                    return None;
+                }
+                if !self.day_matches(at.date()) {
+                    // Jump straight to the first period on the next day
+                    let next_midnight = at.date().succ_opt()?.and_time(NaiveTime::MIN);
+                    let stride = unit * rule.interval as i64;
+                    let skip = ((next_midnight - at).num_seconds() + stride - 1) / stride;
+                    self.period += skip.max(1) - 1;
+                    return Some(Vec::new());
+                }
+                (vec![at.date()], Some(at.time()))
+            }
+        };
+
+        let times = self.times(period_time);
+        let days: Vec<NaiveDate> = dates.into_iter().filter(|d| self.day_matches(*d)).collect();
+        self.budget = self
+            .budget
+            .checked_sub(days.len().saturating_mul(times.len()))?;
+        let mut instances: Vec<NaiveDateTime> = days
+            .into_iter()
+            .flat_map(|d| times.iter().map(move |t| d.and_time(*t)))
+            .collect();
+
+        if !self.rule.by_set_pos.is_empty() {
+            let len = instances.len() as i64;
+            let mut picked: Vec<NaiveDateTime> = self
+                .rule
+                .by_set_pos
+                .iter()
+                .filter_map(|&pos| {
+                    let idx = if pos > 0 {
+                        pos as i64 - 1
+                    } else {
+                        len + pos as i64
+                    };
+                    (0..len).contains(&idx).then(|| instances[idx as usize])
+                })
+                .collect();
+            picked.sort();
+            picked.dedup();
+            instances = picked;
+        }
+
+        instances.retain(|t| *t >= dtstart);
+        Some(instances)
+    }
+
+    /// Times of day within a period, sorted. Sub-daily frequencies pass the
+    /// period's own time, which BYHOUR/BYMINUTE/BYSECOND limit rather than expand.
+    fn times(&self, period_time: Option<NaiveTime>) -> Vec<NaiveTime> {
+        let rule = &self.rule;
+        let start = self.set.dtstart.time();
+        // Components at or above the frequency come from the period and are
+        // limited by BYxxx; finer ones default to DTSTART and are expanded
+        let pick = |by: &[u32], unit: Frequency, own: fn(&NaiveTime) -> u32| -> Vec<u32> {
+            match period_time {
+                Some(t) if rule.freq <= unit => {
+                    if by.is_empty() || by.contains(&own(&t)) {
+                        vec![own(&t)]
+                    } else {
+                        Vec::new()
+                    }
+                }
+                _ if by.is_empty() => vec![own(&start)],
+                _ => {
+                    let mut v = by.to_vec();
+                    v.sort_unstable();
+                    v.dedup();
+                    v
+                }
+            }
+        };
+
+        let hours = pick(&rule.by_hour, Frequency::Hourly, NaiveTime::hour);
+        let minutes = pick(&rule.by_minute, Frequency::Minutely, NaiveTime::minute);
+        let seconds = pick(&rule.by_second, Frequency::Secondly, NaiveTime::second);
+
+        let mut times = Vec::new();
+        for h in &hours {
+            for m in &minutes {
+                for s in &seconds {
+                    if let Some(t) = NaiveTime::from_hms_opt(*h, *m, *s) {
+                        times.push(t);
+                    }
+                }
+            }
+        }
+        times
+    }
+
+    /// Whether a date passes every day-level BYxxx part
+    fn day_matches(&self, date: NaiveDate) -> bool {
+        let rule = &self.rule;
+        let year = date.year();
+        let month = date.month();
+
+        if !rule.by_month.is_empty() && !rule.by_month.contains(&month) {
+            return false;
+        }
+
+        if !rule.by_week_no.is_empty() {
+            let (week, weeks) = week_number(date, rule.wkst);
+            if !rule.by_week_no.iter().any(|&n| {
+                if n > 0 {
+                    n == week
+                } else {
+                    weeks + n + 1 == week
+                }
+            }) {
+                return false;
+            }
+        }
+
+        if !rule.by_year_day.is_empty() {
+            let day = date.ordinal() as i32;
+            let total = days_in_year(year) as i32;
+            if !rule.by_year_day.iter().any(|&n| {
+                if n > 0 {
+                    n == day
+                } else {
+                    total + n + 1 == day
+                }
+            }) {
+                return false;
+            }
+        }
+
+        if !rule.by_month_day.is_empty() {
+            let day = date.day() as i32;
+            let total = days_in_month(year, month) as i32;
+            if !rule.by_month_day.iter().any(|&n| {
+                if n > 0 {
+                    n == day
+                } else {
+                    total + n + 1 == day
+                }
+            }) {
+                return false;
+            }
+        }
+
+        if !rule.by_day.is_empty() {
+            // Ordinals count within the month for MONTHLY (and YEARLY+BYMONTH),
+            // within the year for plain YEARLY, and are ignored otherwise
+            let (index, total) = match rule.freq {
+                Frequency::Monthly => (date.day(), days_in_month(year, month)),
+                Frequency::Yearly if !rule.by_month.is_empty() => {
+                    (date.day(), days_in_month(year, month))
+                }
+                Frequency::Yearly if rule.by_week_no.is_empty() => {
+                    (date.ordinal(), days_in_year(year))
+                }
+                _ => (0, 0),
+            };
+            let from_start = ((index as i32) - 1) / 7 + 1;
+            let from_end = -(((total as i32) - (index as i32)) / 7 + 1);
+            if !rule.by_day.iter().any(|wd| {
+                wd.weekday == date.weekday()
+                    && match wd.ordinal {
+                        Some(n) if total > 0 => n == from_start || n == from_end,
+                        _ => true,
+                    }
+            }) {
+                return false;
+            }
+        }
+
+        true
+    }
+}
+
+fn days_between(first: NaiveDate, last: NaiveDate) -> Vec<NaiveDate> {
+    first.iter_days().take_while(|d| *d <= last).collect()
+}
+
+fn days_in_month(year: i32, month: u32) -> u32 {
+    let next = if month == 12 {
+        NaiveDate::from_ymd_opt(year + 1, 1, 1)
+    } else {
+        NaiveDate::from_ymd_opt(year, month + 1, 1)
+    };
+    next.and_then(|n| n.pred_opt()).map_or(31, |d| d.day())
+}
+
+fn days_in_year(year: i32) -> u32 {
+    if NaiveDate::from_ymd_opt(year, 2, 29).is_some() {
+        366
+    } else {
+        365
+    }
+}
+
+fn days_since_wkst(date: NaiveDate, wkst: Weekday) -> i64 {
+    ((date.weekday().num_days_from_monday() + 7 - wkst.num_days_from_monday()) % 7) as i64
+}
+
+/// Start of week 1: the week (starting on WKST) holding at least four days of the year
+fn week_one_start(year: i32, wkst: Weekday) -> NaiveDate {
+    let jan4 = NaiveDate::from_ymd_opt(year, 1, 4).unwrap_or(NaiveDate::MIN);
+    jan4 - Duration::days(days_since_wkst(jan4, wkst))
+}
+
+/// Week number of `date` and the number of weeks in its week-numbering year
+fn week_number(date: NaiveDate, wkst: Weekday) -> (i32, i32) {
+    let mut year = date.year();
+    if date >= week_one_start(year + 1, wkst) {
+        year += 1;
+    } else if date < week_one_start(year, wkst) {
+        year -= 1;
+    }
+    let start = week_one_start(year, wkst);
+    let week = (date - start).num_days() / 7 + 1;
+    let weeks = (week_one_start(year + 1, wkst) - start).num_days() / 7;
+    (week as i32, weeks as i32)
+}
+
+// =============================================================================
+// PROPERTY BLOCKS
+// =============================================================================
+
+/// A recurrence given as iCalendar properties
+#[derive(Debug, Clone)]
+pub struct ParsedRecurrence {
+    pub rule: RRule,
+    pub dtstart: Option<IcalTime>,
+    pub tzid: Option<String>,
+    pub exdates: Vec<DateTime<Utc>>,
+}
+
+impl ParsedRecurrence {
+    /// Build a stored [`Recurrence`], anchoring at `default_start` when no
+    /// DTSTART was given
+    pub fn into_recurrence(self, default_start: DateTime<Utc>) -> Result<Recurrence> {
+        let tz = self.tzid.as_deref().map(parse_tzid).transpose()?;
+        let dtstart = self
+            .dtstart
+            .map(|t| t.to_utc(tz.unwrap_or(Tz::UTC)))
+            .unwrap_or(default_start);
+        Ok(Recurrence::RRule {
+            rule: self.rule.to_string(),
+            dtstart,
+            tzid: tz.map(|tz| tz.name().to_string()),
+            exdates: self.exdates,
+        })
+    }
+}
+
+/// Parse a bare RRULE (`FREQ=WEEKLY;BYDAY=MO`) or a block of DTSTART, RRULE
+/// and EXDATE properties, one per line:
+///
+/// ```text
+/// DTSTART;TZID=Europe/Berlin:20250106T090000
+/// RRULE:FREQ=MONTHLY;BYDAY=FR;BYSETPOS=-1
+/// EXDATE;TZID=Europe/Berlin:20250131T090000
+/// ```
+pub fn parse_recurrence_block(text: &str) -> Result<ParsedRecurrence> {
+    let text = text.trim();
+    if !text.contains(':') {
+        return Ok(ParsedRecurrence {
+            rule: text.parse()?,
+            dtstart: None,
+            tzid: None,
+            exdates: Vec::new(),
+        });
+    }
+
+    let mut rule = None;
+    let mut dtstart = None;
+    let mut tzid = None;
+    let mut exdate_lines = Vec::new();
+
+    for line in text.split_whitespace() {
+        let prop = ContentLine::parse(line)
+            .ok_or_else(|| anyhow!("malformed recurrence line '{line}'"))?;
+        match prop.name.as_str() {
+            "RRULE" => rule = Some(prop.value.parse::<RRule>()?),
+            "DTSTART" => {
+                tzid = prop.param("TZID").map(str::to_string);
+                dtstart = Some(IcalTime::parse(&prop.value)?);
+            }
+            "EXDATE" => exdate_lines.push(prop),
+            other => bail!("unsupported recurrence property '{other}'"),
+        }
+    }
+
+    let rule = rule.ok_or_else(|| anyhow!("recurrence is missing an RRULE"))?;
+    let default_tz = tzid
+        .as_deref()
+        .map(parse_tzid)
+        .transpose()?
+        .unwrap_or(Tz::UTC);
+    let mut exdates = Vec::new();
+    for prop in exdate_lines {
+        let tz = prop
+            .param("TZID")
+            .map(parse_tzid)
+            .transpose()?
+            .unwrap_or(default_tz);
+        exdates.extend(parse_exdates(&prop.value, tz, dtstart)?);
+    }
+
+    Ok(ParsedRecurrence {
+        rule,
+        dtstart,
+        tzid,
+        exdates,
+    })
+}
+
+/// Parse a comma-separated EXDATE value. DATE-only exclusions take the
+/// series' time of day so they match generated instances.
+pub fn parse_exdates(value: &str, tz: Tz, dtstart: Option<IcalTime>) -> Result<Vec<DateTime<Utc>>> {
+    let time_of_day = match dtstart {
+        Some(IcalTime::Local(n)) => n.time(),
+        Some(IcalTime::Utc(u)) => u.with_timezone(&tz).time(),
+        _ => NaiveTime::MIN,
+    };
+    value
+        .split(',')
+        .map(|v| {
+            Ok(match IcalTime::parse(v)? {
+                IcalTime::Date(d) => resolve_local(tz, d.and_time(time_of_day)),
+                other => other.to_utc(tz),
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn local(s: &str) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M").unwrap()
+    }
+
+    fn set(rule: &str, start: &str, tz: Tz) -> RecurrenceSet {
+        RecurrenceSet {
+            rule: rule.parse().unwrap(),
+            dtstart: local(start),
+            tz,
+            exdates: Vec::new(),
+        }
+    }
+
+    fn locals(set: &RecurrenceSet, n: usize) -> Vec<String> {
+        set.iter()
+            .take(n)
+            .map(|t| {
+                t.with_timezone(&set.tz)
+                    .format("%Y-%m-%d %H:%M")
+                    .to_string()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_parse_and_format_round_trip() {
+        let rule: RRule = "RRULE:FREQ=monthly;INTERVAL=2;BYDAY=-1FR,2MO;BYSETPOS=-1;WKST=SU"
+            .parse()
+            .unwrap();
+        assert_eq!(rule.freq, Frequency::Monthly);
+        assert_eq!(rule.interval, 2);
+        assert_eq!(
+            rule.by_day[0],
+            WeekdayNum {
+                ordinal: Some(-1),
+                weekday: Weekday::Fri
+            }
+        );
+        assert_eq!(
+            rule.to_string(),
+            "FREQ=MONTHLY;INTERVAL=2;BYDAY=-1FR,2MO;BYSETPOS=-1;WKST=SU"
+        );
+
+        assert!("BYDAY=MO".parse::<RRule>().is_err());
+        assert!("FREQ=DAILY;COUNT=3;UNTIL=20250101"
+            .parse::<RRule>()
+            .is_err());
+        assert!("FREQ=DAILY;BYMONTHDAY=32".parse::<RRule>().is_err());
+    }
+
+    #[test]
+    fn test_last_friday_with_bysetpos() {
+        let s = set(
+            "FREQ=MONTHLY;BYDAY=FR;BYSETPOS=-1;COUNT=3",
+            "2025-01-01 17:00",
+            Tz::UTC,
+        );
+        assert_eq!(
+            locals(&s, 10),
+            vec!["2025-01-31 17:00", "2025-02-28 17:00", "2025-03-28 17:00"]
+        );
+    }
+
+    #[test]
+    fn test_last_weekday_of_month() {
+        let s = set(
+            "FREQ=MONTHLY;BYDAY=MO,TU,WE,TH,FR;BYSETPOS=-1",
+            "2025-05-01 09:00",
+            Tz::UTC,
+        );
+        assert_eq!(
+            locals(&s, 3),
+            vec!["2025-05-30 09:00", "2025-06-30 09:00", "2025-07-31 09:00"]
+        );
+    }
+
+    #[test]
+    fn test_interval_until_and_exdate() {
+        let mut s = set(
+            "FREQ=WEEKLY;INTERVAL=2;BYDAY=MO,TH;UNTIL=20250131T235959Z",
+            "2025-01-06 08:30",
+            Tz::UTC,
+        );
+        s.exdates
+            .push(Utc.with_ymd_and_hms(2025, 1, 20, 8, 30, 0).unwrap());
+        assert_eq!(
+            locals(&s, 10),
+            vec!["2025-01-06 08:30", "2025-01-09 08:30", "2025-01-23 08:30"]
+        );
+    }
+
+    #[test]
+    fn test_count_includes_excluded_instances() {
+        let mut s = set("FREQ=DAILY;COUNT=3", "2025-03-01 10:00", Tz::UTC);
+        s.exdates
+            .push(Utc.with_ymd_and_hms(2025, 3, 2, 10, 0, 0).unwrap());
+        assert_eq!(locals(&s, 10), vec!["2025-03-01 10:00", "2025-03-03 10:00"]);
+    }
+
+    #[test]
+    fn test_monthly_skips_short_months_and_negative_monthday() {
+        let s = set("FREQ=MONTHLY;BYMONTHDAY=31", "2025-01-31 12:00", Tz::UTC);
+        assert_eq!(
+            locals(&s, 3),
+            vec!["2025-01-31 12:00", "2025-03-31 12:00", "2025-05-31 12:00"]
+        );
+        let s = set("FREQ=MONTHLY;BYMONTHDAY=-1", "2024-01-15 12:00", Tz::UTC);
+        assert_eq!(
+            locals(&s, 3),
+            vec!["2024-01-31 12:00", "2024-02-29 12:00", "2024-03-31 12:00"]
+        );
+    }
+
+    #[test]
+    fn test_yearly_thanksgiving_and_leap_day() {
+        let s = set(
+            "FREQ=YEARLY;BYMONTH=11;BYDAY=4TH",
+            "2024-01-01 00:00",
+            Tz::UTC,
+        );
+        assert_eq!(locals(&s, 2), vec!["2024-11-28 00:00", "2025-11-27 00:00"]);
+
+        let s = set("FREQ=YEARLY", "2024-02-29 07:00", Tz::UTC);
+        assert_eq!(locals(&s, 2), vec!["2024-02-29 07:00", "2028-02-29 07:00"]);
+    }
+
+    #[test]
+    fn test_local_time_survives_dst() {
+        let tz: Tz = "America/New_York".parse().unwrap();
+        let s = set("FREQ=DAILY;COUNT=3", "2025-03-08 09:00", tz);
+        let utc: Vec<String> = s.iter().map(|t| t.format("%d %H:%M").to_string()).collect();
+        // 09:00 EST is 14:00Z, 09:00 EDT is 13:00Z
+        assert_eq!(utc, vec!["08 14:00", "09 13:00", "10 13:00"]);
+
+        // 02:30 doesn't exist on spring-forward night; it shifts to 03:30 EDT
+        let s = set("FREQ=DAILY;COUNT=2", "2025-03-08 02:30", tz);
+        assert_eq!(locals(&s, 2), vec!["2025-03-08 02:30", "2025-03-09 03:30"]);
+    }
+
+    #[test]
+    fn test_hourly_with_byday_skips_days() {
+        let s = set(
+            "FREQ=HOURLY;INTERVAL=6;BYDAY=MO",
+            "2025-01-04 00:00",
+            Tz::UTC,
+        );
+        assert_eq!(
+            locals(&s, 5),
+            vec![
+                "2025-01-06 00:00",
+                "2025-01-06 06:00",
+                "2025-01-06 12:00",
+                "2025-01-06 18:00",
+                "2025-01-13 00:00"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_impossible_rule_terminates() {
+        let s = set(
+            "FREQ=YEARLY;BYMONTH=2;BYMONTHDAY=30",
+            "2025-01-01 00:00",
+            Tz::UTC,
+        );
+        assert_eq!(s.iter().next(), None);
+    }
+
+    #[test]
+    fn test_old_dtstart_jumps_ahead() {
+        let epoch = local("1970-01-01 00:00");
+        let after = Utc.with_ymd_and_hms(2026, 10, 17, 12, 0, 0).unwrap();
+
+        // ~1.8e9 periods since DTSTART; without the jump this never returns
+        let secondly = set("FREQ=SECONDLY", "1970-01-01 00:00", Tz::UTC);
+        assert_eq!(
+            secondly.next_after(after),
+            Some(after + Duration::seconds(1))
+        );
+
+        // The INTERVAL grid stays anchored at DTSTART
+        let minutely = set("FREQ=MINUTELY;INTERVAL=7", "1970-01-01 00:00", Tz::UTC);
+        let next = minutely.next_after(after).unwrap();
+        let offset = (next.naive_utc() - epoch).num_seconds();
+        assert_eq!(offset % 420, 0);
+        assert!(next > after && next <= after + Duration::minutes(7));
+
+        let berlin = set(
+            "FREQ=MINUTELY;BYHOUR=9;BYMINUTE=30",
+            "1970-01-01 00:00",
+            chrono_tz::Europe::Berlin,
+        );
+        assert_eq!(
+            berlin.between(after, after + Duration::days(2), 10),
+            vec![
+                Utc.with_ymd_and_hms(2026, 10, 18, 7, 30, 0).unwrap(),
+                Utc.with_ymd_and_hms(2026, 10, 19, 7, 30, 0).unwrap(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_count_survives_jump() {
+        // One instance per period, so COUNT is tracked across the skip
+        let hourly = set("FREQ=HOURLY;COUNT=100000", "2020-01-01 00:00", Tz::UTC);
+        let after = Utc.with_ymd_and_hms(2025, 6, 1, 10, 0, 0).unwrap();
+        assert_eq!(hourly.next_after(after), Some(after + Duration::hours(1)));
+        // 100000 hours after 2020-01-01 is 2031-05-29 16:00
+        let last = Utc.with_ymd_and_hms(2031, 5, 29, 15, 0, 0).unwrap();
+        assert_eq!(hourly.next_after(last - Duration::hours(1)), Some(last));
+        assert_eq!(hourly.next_after(last), None);
+
+        let minutely = set("FREQ=MINUTELY;COUNT=5", "1970-01-01 00:00", Tz::UTC);
+        assert_eq!(minutely.next_after(after), None);
+    }
+
+    #[test]
+    fn test_expansion_is_capped() {
+        // COUNT with BYxxx parts cannot skip ahead, so it hits the cap
+        let filtered = set(
+            "FREQ=SECONDLY;COUNT=4000000000;BYMINUTE=0,30",
+            "1970-01-01 00:00",
+            Tz::UTC,
+        );
+        let after = Utc.with_ymd_and_hms(2026, 10, 17, 12, 0, 0).unwrap();
+        assert_eq!(filtered.next_after(after), None);
+        assert!(filtered.iter().count() <= MAX_EXPANDED_PER_ITER);
+
+        // A single period larger than the cap is never allocated
+        let every_second = set(
+            &format!(
+                "FREQ=YEARLY;BYMONTH=1;BYMONTHDAY=1;BYHOUR={};BYMINUTE={};BYSECOND={}",
+                (0..24).map(|n| n.to_string()).collect::<Vec<_>>().join(","),
+                (0..60).map(|n| n.to_string()).collect::<Vec<_>>().join(","),
+                (0..60).map(|n| n.to_string()).collect::<Vec<_>>().join(","),
+            ),
+            "2025-01-01 00:00",
+            Tz::UTC,
+        );
+        assert_eq!(every_second.iter().take(3).count(), 3);
+        // DTSTART defaults keep the month day, so this is 12 days of every second
+        let yearly_all = set(
+            &format!(
+                "FREQ=YEARLY;BYMONTH=1,2,3,4,5,6,7,8,9,10,11,12;BYHOUR={};BYMINUTE={};BYSECOND={}",
+                (0..24).map(|n| n.to_string()).collect::<Vec<_>>().join(","),
+                (0..60).map(|n| n.to_string()).collect::<Vec<_>>().join(","),
+                (0..60).map(|n| n.to_string()).collect::<Vec<_>>().join(","),
+            ),
+            "2025-01-01 00:00",
+            Tz::UTC,
+        );
+        assert_eq!(yearly_all.iter().next(), None);
+    }
+
+    #[test]
+    fn test_parse_block_with_tzid_and_date_exdate() {
+        let parsed = parse_recurrence_block(
+            "DTSTART;TZID=Europe/Berlin:20250106T090000\n\
+             RRULE:FREQ=WEEKLY;COUNT=3\n\
+             EXDATE;VALUE=DATE:20250113",
+        )
+        .unwrap();
+        assert_eq!(parsed.tzid.as_deref(), Some("Europe/Berlin"));
+        // Date-only exclusion takes the series' 09:00 Berlin time of day
+        assert_eq!(
+            parsed.exdates,
+            vec![Utc.with_ymd_and_hms(2025, 1, 13, 8, 0, 0).unwrap()]
+        );
+
+        let recurrence = parsed.into_recurrence(Utc::now()).unwrap();
+        let occurrences: Vec<_> = recurrence.recurrence_set().unwrap().iter().collect();
+        assert_eq!(
+            occurrences,
+            vec![
+                Utc.with_ymd_and_hms(2025, 1, 6, 8, 0, 0).unwrap(),
+                Utc.with_ymd_and_hms(2025, 1, 20, 8, 0, 0).unwrap(),
+            ]
+        );
+        assert_eq!(
+            recurrence.next_after(Utc.with_ymd_and_hms(2025, 1, 20, 8, 0, 0).unwrap()),
+            None
+        );
+    }
+}
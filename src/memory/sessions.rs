@@ -2,13 +2,29 @@
 //!
 //! Tracks user sessions with timeline, metrics, and analytics.
 //! Each session represents a conversation/work period with the AI.
+//!
+//! When opened with [`SessionStore::with_persistence`], every session is
+//! written to RocksDB under a time-ordered key so history survives restarts
+//! and can be paged through with [`SessionStore::query_sessions`]:
+//!
+//! - `session:{user_id}:{started_at_millis}:{session_id}` → session JSON
+//! - `sid:{session_id}` → session key (lookup by ID)
+//! - `active:{session_id}` → session key (resumed on startup)
 
+use anyhow::Context;
 use chrono::{DateTime, Datelike, Duration, Local, Timelike, Utc};
 use parking_lot::RwLock;
+use rocksdb::{Direction, IteratorMode, Options, WriteBatch, DB};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+use std::sync::Arc;
 use uuid::Uuid;
 
+const SESSION_PREFIX: &str = "session:";
+const SESSION_INDEX_PREFIX: &str = "sid:";
+const ACTIVE_PREFIX: &str = "active:";
+
 /// Time of day classification
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
@@ -487,6 +503,46 @@ impl Session {
         end - self.started_at
     }
 
+    /// Whether this session worked on the given project (case-insensitive)
+    ///
+    /// Matches todos created in the project and a `project` metadata entry.
+    pub fn involves_project(&self, project: &str) -> bool {
+        let is_match = |p: &str| p.eq_ignore_ascii_case(project);
+        if self
+            .metadata
+            .get("project")
+            .and_then(|v| v.as_str())
+            .is_some_and(is_match)
+        {
+            return true;
+        }
+        self.timeline.iter().any(|event| {
+            matches!(event, SessionEvent::TodoCreated { project: Some(p), .. } if is_match(p))
+        })
+    }
+
+    /// Whether any event in the timeline created, surfaced or used the memory
+    pub fn references_memory(&self, memory_id: &str) -> bool {
+        self.timeline.iter().any(|event| match event {
+            SessionEvent::MemoryCreated { memory_id: id, .. }
+            | SessionEvent::MemoryUsed { memory_id: id, .. } => id == memory_id,
+            SessionEvent::MemoriesSurfaced { memory_ids, .. } => {
+                memory_ids.iter().any(|id| id == memory_id)
+            }
+            _ => false,
+        })
+    }
+
+    /// Storage key: sorts by user, then start time, then ID
+    fn storage_key(&self) -> String {
+        format!(
+            "{SESSION_PREFIX}{}:{:020}:{}",
+            self.user_id,
+            self.started_at.timestamp_millis().max(0),
+            self.id.0
+        )
+    }
+
     /// Get summary for display
     pub fn summary(&self) -> SessionSummary {
         SessionSummary {
@@ -527,6 +583,89 @@ impl SessionSummary {
     }
 }
 
+/// Retention policy for persisted session history
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SessionRetention {
+    /// Delete finished sessions that started more than this many days ago (0 = keep forever)
+    pub max_age_days: u32,
+    /// Keep at most this many sessions per user, oldest dropped first (0 = unlimited)
+    pub max_sessions_per_user: usize,
+}
+
+impl Default for SessionRetention {
+    fn default() -> Self {
+        Self {
+            max_age_days: 365,
+            max_sessions_per_user: 1000,
+        }
+    }
+}
+
+/// Filters for [`SessionStore::query_sessions`]
+#[derive(Debug, Clone, Default)]
+pub struct SessionQuery {
+    pub user_id: String,
+    /// Only sessions started at or after this time
+    pub start: Option<DateTime<Utc>>,
+    /// Only sessions started before this time
+    pub end: Option<DateTime<Utc>>,
+    /// Only sessions that worked on this project (see [`Session::involves_project`])
+    pub project: Option<String>,
+    /// Only sessions whose timeline references this memory ID
+    pub memory_id: Option<String>,
+    /// Maximum sessions per page
+    pub limit: usize,
+    /// `next_cursor` from the previous page
+    pub cursor: Option<String>,
+}
+
+impl SessionQuery {
+    /// Check the non-time filters against a session
+    pub fn matches(&self, session: &Session) -> bool {
+        self.project
+            .as_deref()
+            .is_none_or(|p| session.involves_project(p))
+            && self
+                .memory_id
+                .as_deref()
+                .is_none_or(|id| session.references_memory(id))
+    }
+}
+
+/// One page of session query results, newest first
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionPage {
+    pub sessions: Vec<SessionSummary>,
+    /// Pass back as `cursor` to fetch the next page; `None` on the last page
+    pub next_cursor: Option<String>,
+}
+
+/// Parsed `session:{user_id}:{started_at_millis}:{session_id}` key
+struct SessionKey {
+    key: Box<[u8]>,
+    user_id: String,
+    started_millis: i64,
+    session_id: Uuid,
+}
+
+impl SessionKey {
+    fn parse(key: Box<[u8]>) -> Option<Self> {
+        let rest = std::str::from_utf8(&key)
+            .ok()?
+            .strip_prefix(SESSION_PREFIX)?;
+        let mut parts = rest.rsplitn(3, ':');
+        let session_id = Uuid::parse_str(parts.next()?).ok()?;
+        let started_millis = parts.next()?.parse().ok()?;
+        let user_id = parts.next()?.to_string();
+        Some(Self {
+            key,
+            user_id,
+            started_millis,
+            session_id,
+        })
+    }
+}
+
 /// Session store - manages all sessions for users
 pub struct SessionStore {
     /// Active sessions by session ID
@@ -537,6 +676,10 @@ pub struct SessionStore {
     max_completed_per_user: usize,
     /// Session timeout in seconds
     timeout_secs: i64,
+    /// Durable session history (None = in-memory only)
+    db: Option<Arc<DB>>,
+    /// Retention applied to the durable history
+    retention: SessionRetention,
 }
 
 impl SessionStore {
@@ -546,6 +689,8 @@ impl SessionStore {
             completed: RwLock::new(HashMap::new()),
             max_completed_per_user: 50,
             timeout_secs: 3600, // 1 hour
+            db: None,
+            retention: SessionRetention::default(),
         }
     }
 
@@ -555,13 +700,142 @@ impl SessionStore {
             completed: RwLock::new(HashMap::new()),
             max_completed_per_user,
             timeout_secs,
+            db: None,
+            retention: SessionRetention::default(),
+        }
+    }
+
+    /// Create a store backed by RocksDB at `{storage_path}/sessions`
+    ///
+    /// Sessions that were still active at shutdown are resumed; stale ones
+    /// are ended by the next [`Self::cleanup_stale_sessions`] pass.
+    pub fn with_persistence(
+        storage_path: &Path,
+        retention: SessionRetention,
+    ) -> anyhow::Result<Self> {
+        let path = storage_path.join("sessions");
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        opts.set_compression_type(rocksdb::DBCompressionType::Lz4);
+        let db = Arc::new(DB::open(&opts, &path).context("Failed to open session store")?);
+
+        let mut active = HashMap::new();
+        for item in db.prefix_iterator(ACTIVE_PREFIX.as_bytes()) {
+            let (key, session_key) = item.context("Failed to iterate session store")?;
+            if !key.starts_with(ACTIVE_PREFIX.as_bytes()) {
+                break;
+            }
+            let Some(value) = db.get(&session_key)? else {
+                continue;
+            };
+            match serde_json::from_slice::<Session>(&value) {
+                Ok(session) => {
+                    active.insert(session.id.clone(), session);
+                }
+                Err(e) => tracing::warn!("Skipping corrupt session record: {}", e),
+            }
+        }
+
+        if !active.is_empty() {
+            tracing::info!("Resumed {} active sessions", active.len());
         }
+
+        Ok(Self {
+            active: RwLock::new(active),
+            db: Some(db),
+            retention,
+            ..Self::new()
+        })
+    }
+
+    /// Underlying database, if persistence is enabled
+    pub fn database(&self) -> Option<&Arc<DB>> {
+        self.db.as_ref()
+    }
+
+    /// Flush persisted sessions to disk
+    pub fn flush(&self) -> anyhow::Result<()> {
+        if let Some(ref db) = self.db {
+            db.flush()
+                .map_err(|e| anyhow::anyhow!("Failed to flush sessions db: {e}"))?;
+        }
+        Ok(())
+    }
+
+    /// Write a session and its index entries to disk
+    fn persist(&self, session: &Session) {
+        let Some(ref db) = self.db else {
+            return;
+        };
+        let value = match serde_json::to_vec(session) {
+            Ok(value) => value,
+            Err(e) => {
+                tracing::warn!("Failed to serialize session {}: {}", session.id, e);
+                return;
+            }
+        };
+
+        let key = session.storage_key();
+        let active_key = format!("{ACTIVE_PREFIX}{}", session.id.0);
+        let mut batch = WriteBatch::default();
+        batch.put(key.as_bytes(), value);
+        batch.put(
+            format!("{SESSION_INDEX_PREFIX}{}", session.id.0),
+            key.as_bytes(),
+        );
+        if session.is_active() {
+            batch.put(active_key, key.as_bytes());
+        } else {
+            batch.delete(active_key);
+        }
+        if let Err(e) = db.write(batch) {
+            tracing::warn!("Failed to persist session {}: {}", session.id, e);
+        }
+    }
+
+    /// Remove a persisted session and its index entries
+    fn remove_persisted(&self, session_id: &SessionId) {
+        let Some(ref db) = self.db else {
+            return;
+        };
+        let index_key = format!("{SESSION_INDEX_PREFIX}{}", session_id.0);
+        if let Ok(Some(key)) = db.get(&index_key) {
+            let mut batch = WriteBatch::default();
+            batch.delete(key);
+            batch.delete(index_key);
+            batch.delete(format!("{ACTIVE_PREFIX}{}", session_id.0));
+            if let Err(e) = db.write(batch) {
+                tracing::warn!("Failed to remove session {}: {}", session_id, e);
+            }
+        }
+    }
+
+    /// Load a session from disk by ID
+    fn load_persisted(&self, session_id: &SessionId) -> Option<Session> {
+        let db = self.db.as_ref()?;
+        let key = db
+            .get(format!("{SESSION_INDEX_PREFIX}{}", session_id.0))
+            .ok()??;
+        let value = db.get(key).ok()??;
+        serde_json::from_slice(&value).ok()
+    }
+
+    /// All persisted session keys, in key order (by user, then start time)
+    fn persisted_keys(&self) -> Vec<SessionKey> {
+        let Some(ref db) = self.db else {
+            return Vec::new();
+        };
+        db.prefix_iterator(SESSION_PREFIX.as_bytes())
+            .map_while(Result::ok)
+            .map_while(|(key, _)| SessionKey::parse(key))
+            .collect()
     }
 
     /// Start a new session for a user
     pub fn start_session(&self, user_id: &str) -> SessionId {
         let session = Session::new(user_id.to_string());
         let id = session.id.clone();
+        self.persist(&session);
         self.active.write().insert(id.clone(), session);
         id
     }
@@ -569,6 +843,10 @@ impl SessionStore {
     /// Start a session with a specific ID (for resumption)
     pub fn start_session_with_id(&self, user_id: &str, session_id: SessionId) -> SessionId {
         let session = Session::with_id(user_id.to_string(), session_id.clone());
+        // A resumed ID starts a new timeline; drop the old record so it
+        // doesn't linger under its previous start time
+        self.remove_persisted(&session_id);
+        self.persist(&session);
         self.active.write().insert(session_id.clone(), session);
         session_id
     }
@@ -593,6 +871,7 @@ impl SessionStore {
         let mut active = self.active.write();
         if let Some(session) = active.get_mut(session_id) {
             session.add_event(event);
+            self.persist(session);
             true
         } else {
             false
@@ -605,6 +884,7 @@ impl SessionStore {
         for (id, session) in active.iter_mut() {
             if session.user_id == user_id && session.is_active() {
                 session.add_event(event);
+                self.persist(session);
                 return Some(id.clone());
             }
         }
@@ -616,6 +896,7 @@ impl SessionStore {
         let mut active = self.active.write();
         if let Some(mut session) = active.remove(session_id) {
             session.end(reason);
+            self.persist(&session);
 
             // Move to completed
             let mut completed = self.completed.write();
@@ -649,11 +930,21 @@ impl SessionStore {
                 return Some(session.clone());
             }
         }
-        None
+        self.load_persisted(session_id)
     }
 
     /// Get all sessions for a user
     pub fn get_user_sessions(&self, user_id: &str, limit: usize) -> Vec<SessionSummary> {
+        if self.db.is_some() {
+            return self
+                .query_sessions(&SessionQuery {
+                    user_id: user_id.to_string(),
+                    limit,
+                    ..Default::default()
+                })
+                .sessions;
+        }
+
         let mut result = Vec::new();
 
         // Add active sessions
@@ -686,6 +977,153 @@ impl SessionStore {
         result
     }
 
+    /// Query a user's session history by date range, project or memory, newest first
+    ///
+    /// Reads from disk when persistence is enabled, otherwise from the
+    /// in-memory sessions. Pages are keyed by an opaque cursor, so sessions
+    /// started while paging don't shift later pages.
+    pub fn query_sessions(&self, query: &SessionQuery) -> SessionPage {
+        let prefix = format!("{SESSION_PREFIX}{}:", query.user_id);
+        let mut upper = match query.end {
+            Some(end) => format!("{prefix}{:020}", end.timestamp_millis().max(0)),
+            None => format!("{prefix}~"),
+        };
+        if let Some(ref cursor) = query.cursor {
+            upper = upper.min(cursor.clone());
+        }
+        let lower = match query.start {
+            Some(start) => format!("{prefix}{:020}", start.timestamp_millis().max(0)),
+            None => prefix.clone(),
+        };
+
+        match self.db {
+            Some(ref db) => {
+                let entries = db
+                    .iterator(IteratorMode::From(upper.as_bytes(), Direction::Reverse))
+                    .map_while(Result::ok)
+                    .map_while(|(key, value)| {
+                        let key = String::from_utf8(key.into_vec()).ok()?;
+                        key.starts_with(&prefix).then_some((key, value))
+                    })
+                    .filter_map(
+                        |(key, value)| match serde_json::from_slice::<Session>(&value) {
+                            Ok(session) => Some((key, session)),
+                            Err(e) => {
+                                tracing::warn!("Skipping corrupt session record {}: {}", key, e);
+                                None
+                            }
+                        },
+                    );
+                Self::collect_page(entries, query, &upper, &lower)
+            }
+            None => {
+                let mut entries: Vec<(String, Session)> = {
+                    let active = self.active.read();
+                    let completed = self.completed.read();
+                    active
+                        .values()
+                        .filter(|s| s.user_id == query.user_id)
+                        .chain(completed.get(&query.user_id).into_iter().flatten())
+                        .map(|s| (s.storage_key(), s.clone()))
+                        .collect()
+                };
+                entries.sort_by(|a, b| a.0.cmp(&b.0));
+                Self::collect_page(entries.into_iter().rev(), query, &upper, &lower)
+            }
+        }
+    }
+
+    /// Fill one page from `(key, session)` entries sorted newest first
+    fn collect_page(
+        entries: impl Iterator<Item = (String, Session)>,
+        query: &SessionQuery,
+        upper: &str,
+        lower: &str,
+    ) -> SessionPage {
+        let mut page = SessionPage {
+            sessions: Vec::new(),
+            next_cursor: None,
+        };
+        let mut last_key = None;
+
+        for (key, session) in entries {
+            if key.as_str() >= upper {
+                continue;
+            }
+            if key.as_str() < lower {
+                break;
+            }
+            if !query.matches(&session) {
+                continue;
+            }
+            if page.sessions.len() == query.limit {
+                page.next_cursor = last_key;
+                break;
+            }
+            page.sessions.push(session.summary());
+            last_key = Some(key);
+        }
+        page
+    }
+
+    /// Delete persisted sessions outside the retention policy
+    ///
+    /// Active sessions are never deleted. Returns the number removed.
+    pub fn apply_retention(&self) -> anyhow::Result<usize> {
+        let Some(ref db) = self.db else {
+            return Ok(0);
+        };
+        let SessionRetention {
+            max_age_days,
+            max_sessions_per_user,
+        } = self.retention;
+        if max_age_days == 0 && max_sessions_per_user == 0 {
+            return Ok(0);
+        }
+
+        let cutoff_millis = (max_age_days > 0)
+            .then(|| (Utc::now() - Duration::days(i64::from(max_age_days))).timestamp_millis());
+        let active: HashSet<Uuid> = self.active.read().keys().map(|id| id.0).collect();
+        let keys = self.persisted_keys();
+
+        let mut batch = WriteBatch::default();
+        let mut removed = HashSet::new();
+        // Keys are ordered by user, then start time, so each chunk is one
+        // user's sessions from oldest to newest
+        for user_keys in keys.chunk_by(|a, b| a.user_id == b.user_id) {
+            let excess = if max_sessions_per_user > 0 {
+                user_keys.len().saturating_sub(max_sessions_per_user)
+            } else {
+                0
+            };
+            for (i, entry) in user_keys.iter().enumerate() {
+                if active.contains(&entry.session_id) {
+                    continue;
+                }
+                let expired = cutoff_millis.is_some_and(|cutoff| entry.started_millis < cutoff);
+                if i < excess || expired {
+                    batch.delete(&entry.key);
+                    batch.delete(format!("{SESSION_INDEX_PREFIX}{}", entry.session_id));
+                    removed.insert(entry.session_id);
+                }
+            }
+        }
+
+        if removed.is_empty() {
+            return Ok(0);
+        }
+        db.write(batch)
+            .context("Failed to apply session retention")?;
+
+        let mut completed = self.completed.write();
+        for sessions in completed.values_mut() {
+            sessions.retain(|s| !removed.contains(&s.id.0));
+        }
+        completed.retain(|_, sessions| !sessions.is_empty());
+
+        Ok(removed.len())
+    }
+
     /// Get active session for user
     pub fn get_active_session(&self, user_id: &str) -> Option<Session> {
         let active = self.active.read();
@@ -720,6 +1158,17 @@ impl SessionStore {
 
     /// Get store statistics
     pub fn stats(&self) -> SessionStoreStats {
+        if self.db.is_some() {
+            let keys = self.persisted_keys();
+            let active_sessions = self.active.read().len();
+            let users: HashSet<&str> = keys.iter().map(|k| k.user_id.as_str()).collect();
+            return SessionStoreStats {
+                active_sessions,
+                completed_sessions: keys.len().saturating_sub(active_sessions),
+                users_with_sessions: users.len(),
+            };
+        }
+
         let active = self.active.read();
         let completed = self.completed.read();
 
@@ -894,4 +1343,166 @@ mod tests {
         assert!(!summary.temporal.label.is_empty());
         assert_eq!(summary.display_title(), session.temporal_label());
     }
+
+    /// Persist a finished session that started `days_ago` days in the past
+    fn persist_finished(store: &SessionStore, user_id: &str, days_ago: i64) -> Session {
+        let mut session = Session::new(user_id.to_string());
+        session.started_at = Utc::now() - Duration::days(days_ago);
+        session.end("user_ended");
+        store.persist(&session);
+        session
+    }
+
+    #[test]
+    fn test_persistence_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+
+        let (active_id, ended_id) = {
+            let store =
+                SessionStore::with_persistence(dir.path(), SessionRetention::default()).unwrap();
+            let ended_id = store.start_session("user-1");
+            store.add_event(
+                &ended_id,
+                SessionEvent::MemoryCreated {
+                    timestamp: Utc::now(),
+                    memory_id: "m1".to_string(),
+                    memory_type: "Observation".to_string(),
+                    content_preview: "test".to_string(),
+                    entities: vec![],
+                },
+            );
+            store.end_session(&ended_id, "user_ended");
+            let active_id = store.start_session("user-1");
+            (active_id, ended_id)
+        };
+
+        let store =
+            SessionStore::with_persistence(dir.path(), SessionRetention::default()).unwrap();
+
+        // Active session resumes, finished one is read back from disk
+        assert_eq!(store.get_active_session("user-1").unwrap().id, active_id);
+        let ended = store.get_session(&ended_id).unwrap();
+        assert_eq!(ended.status, SessionStatus::Completed);
+        assert_eq!(ended.stats.memories_created, 1);
+        assert!(ended.references_memory("m1"));
+
+        assert_eq!(store.get_user_sessions("user-1", 10).len(), 2);
+        let stats = store.stats();
+        assert_eq!(stats.active_sessions, 1);
+        assert_eq!(stats.completed_sessions, 1);
+        assert_eq!(stats.users_with_sessions, 1);
+    }
+
+    #[test]
+    fn test_query_sessions() {
+        let dir = tempfile::tempdir().unwrap();
+        let store =
+            SessionStore::with_persistence(dir.path(), SessionRetention::default()).unwrap();
+
+        for days_ago in [1, 2, 3, 10, 20] {
+            persist_finished(&store, "user-1", days_ago);
+        }
+        persist_finished(&store, "user-2", 1);
+
+        let mut project_session = Session::new("user-1".to_string());
+        project_session.started_at = Utc::now() - Duration::days(5);
+        project_session.add_event(SessionEvent::TodoCreated {
+            timestamp: Utc::now(),
+            todo_id: "t1".to_string(),
+            content: "ship it".to_string(),
+            project: Some("Backend".to_string()),
+        });
+        project_session.add_event(SessionEvent::MemoriesSurfaced {
+            timestamp: Utc::now(),
+            query_preview: "q".to_string(),
+            memory_count: 2,
+            memory_ids: vec!["m1".to_string(), "m2".to_string()],
+            avg_score: 0.5,
+        });
+        store.persist(&project_session);
+
+        // Date range: sessions started 1..=7 days ago
+        let page = store.query_sessions(&SessionQuery {
+            user_id: "user-1".to_string(),
+            start: Some(Utc::now() - Duration::days(7)),
+            end: Some(Utc::now()),
+            limit: 10,
+            ..Default::default()
+        });
+        assert_eq!(page.sessions.len(), 4);
+        assert!(page.next_cursor.is_none());
+        assert!(page
+            .sessions
+            .windows(2)
+            .all(|w| w[0].started_at >= w[1].started_at));
+
+        // Project and memory filters
+        let by_project = store.query_sessions(&SessionQuery {
+            user_id: "user-1".to_string(),
+            project: Some("backend".to_string()),
+            limit: 10,
+            ..Default::default()
+        });
+        assert_eq!(by_project.sessions.len(), 1);
+        assert_eq!(by_project.sessions[0].id, project_session.id);
+
+        let by_memory = store.query_sessions(&SessionQuery {
+            user_id: "user-1".to_string(),
+            memory_id: Some("m2".to_string()),
+            limit: 10,
+            ..Default::default()
+        });
+        assert_eq!(by_memory.sessions.len(), 1);
+
+        // Pagination walks all of user-1's sessions exactly once
+        let mut seen = HashSet::new();
+        let mut cursor = None;
+        loop {
+            let page = store.query_sessions(&SessionQuery {
+                user_id: "user-1".to_string(),
+                limit: 2,
+                cursor,
+                ..Default::default()
+            });
+            assert!(page.sessions.len() <= 2);
+            for s in &page.sessions {
+                assert!(seen.insert(s.id.clone()));
+            }
+            cursor = page.next_cursor;
+            if cursor.is_none() {
+                break;
+            }
+        }
+        assert_eq!(seen.len(), 6);
+    }
+
+    #[test]
+    fn test_retention() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = SessionStore::with_persistence(
+            dir.path(),
+            SessionRetention {
+                max_age_days: 30,
+                max_sessions_per_user: 2,
+            },
+        )
+        .unwrap();
+
+        let expired = persist_finished(&store, "user-1", 100);
+        for days_ago in [10, 5] {
+            persist_finished(&store, "user-1", days_ago);
+        }
+        let kept = persist_finished(&store, "user-1", 1);
+        let active_id = store.start_session("user-1");
+
+        assert_eq!(store.apply_retention().unwrap(), 3);
+        assert!(store.get_session(&expired.id).is_none());
+
+        let remaining: Vec<SessionId> = store
+            .get_user_sessions("user-1", 10)
+            .into_iter()
+            .map(|s| s.id)
+            .collect();
+        assert_eq!(remaining, vec![active_id, kept.id]);
+    }
 }
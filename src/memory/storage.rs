@@ -5,10 +5,12 @@ use bincode;
 use chrono::{DateTime, Utc};
 use rocksdb::{IteratorMode, Options, WriteBatch, WriteOptions, DB};
 use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
 use super::types::*;
+use crate::constants::{LOCAL_SPATIAL_CELL_SIZE_METERS, LOCAL_SPATIAL_MAX_CELL_PROBES};
 
 /// Helper trait to safely iterate over RocksDB results with error logging.
 /// Unlike `.flatten()` which silently ignores errors, this logs them.
@@ -62,7 +64,7 @@ impl Default for WriteMode {
 
 const STORAGE_MAGIC: &[u8; 3] = b"SHO";
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap, HashSet};
 
 /// Default experience type for legacy deserialization
 fn default_legacy_experience_type() -> ExperienceType {
@@ -481,6 +483,149 @@ impl LegacyExperienceV1 {
             temporal_refs: Vec::new(),
             ner_entities: Vec::new(),
             cooccurrence_pairs: Vec::new(),
+            language: None,
+        }
+    }
+}
+
+/// Legacy Experience type from before language detection - positional bincode 2.x match
+/// Identical to the current Experience minus the trailing `language` field.
+#[derive(Deserialize)]
+pub(crate) struct LegacyExperienceV2 {
+    #[serde(default = "default_legacy_experience_type")]
+    experience_type: ExperienceType,
+    content: String,
+    #[serde(default)]
+    context: Option<RichContext>,
+    #[serde(default)]
+    entities: Vec<String>,
+    #[serde(default)]
+    metadata: HashMap<String, String>,
+    #[serde(default)]
+    embeddings: Option<Vec<f32>>,
+    #[serde(default)]
+    image_embeddings: Option<Vec<f32>>,
+    #[serde(default)]
+    audio_embeddings: Option<Vec<f32>>,
+    #[serde(default)]
+    video_embeddings: Option<Vec<f32>>,
+    #[serde(default)]
+    media_refs: Vec<MediaRef>,
+    #[serde(default)]
+    related_memories: Vec<MemoryId>,
+    #[serde(default)]
+    causal_chain: Vec<MemoryId>,
+    #[serde(default)]
+    outcomes: Vec<String>,
+    #[serde(default)]
+    robot_id: Option<String>,
+    #[serde(default)]
+    mission_id: Option<String>,
+    #[serde(default)]
+    geo_location: Option<[f64; 3]>,
+    #[serde(default)]
+    local_position: Option<[f32; 3]>,
+    #[serde(default)]
+    heading: Option<f32>,
+    #[serde(default)]
+    action_type: Option<String>,
+    #[serde(default)]
+    reward: Option<f32>,
+    #[serde(default)]
+    sensor_data: HashMap<String, f64>,
+    #[serde(default)]
+    decision_context: Option<HashMap<String, String>>,
+    #[serde(default)]
+    action_params: Option<HashMap<String, String>>,
+    #[serde(default)]
+    outcome_type: Option<String>,
+    #[serde(default)]
+    outcome_details: Option<String>,
+    #[serde(default)]
+    confidence: Option<f32>,
+    #[serde(default)]
+    alternatives_considered: Vec<String>,
+    #[serde(default)]
+    weather: Option<HashMap<String, String>>,
+    #[serde(default)]
+    terrain_type: Option<String>,
+    #[serde(default)]
+    lighting: Option<String>,
+    #[serde(default)]
+    nearby_agents: Vec<HashMap<String, String>>,
+    #[serde(default)]
+    is_failure: bool,
+    #[serde(default)]
+    is_anomaly: bool,
+    #[serde(default)]
+    severity: Option<String>,
+    #[serde(default)]
+    recovery_action: Option<String>,
+    #[serde(default)]
+    root_cause: Option<String>,
+    #[serde(default)]
+    pattern_id: Option<String>,
+    #[serde(default)]
+    predicted_outcome: Option<String>,
+    #[serde(default)]
+    prediction_accurate: Option<bool>,
+    #[serde(default)]
+    tags: Vec<String>,
+    #[serde(default)]
+    temporal_refs: Vec<String>,
+    #[serde(default)]
+    ner_entities: Vec<NerEntityRecord>,
+    #[serde(default)]
+    cooccurrence_pairs: Vec<(String, String)>,
+}
+
+impl LegacyExperienceV2 {
+    pub(crate) fn into_experience(self) -> Experience {
+        Experience {
+            experience_type: self.experience_type,
+            content: self.content,
+            context: self.context,
+            entities: self.entities,
+            metadata: self.metadata,
+            embeddings: self.embeddings,
+            image_embeddings: self.image_embeddings,
+            audio_embeddings: self.audio_embeddings,
+            video_embeddings: self.video_embeddings,
+            media_refs: self.media_refs,
+            related_memories: self.related_memories,
+            causal_chain: self.causal_chain,
+            outcomes: self.outcomes,
+            robot_id: self.robot_id,
+            mission_id: self.mission_id,
+            geo_location: self.geo_location,
+            local_position: self.local_position,
+            heading: self.heading,
+            action_type: self.action_type,
+            reward: self.reward,
+            sensor_data: self.sensor_data,
+            decision_context: self.decision_context,
+            action_params: self.action_params,
+            outcome_type: self.outcome_type,
+            outcome_details: self.outcome_details,
+            confidence: self.confidence,
+            alternatives_considered: self.alternatives_considered,
+            weather: self.weather,
+            terrain_type: self.terrain_type,
+            lighting: self.lighting,
+            nearby_agents: self.nearby_agents,
+            is_failure: self.is_failure,
+            is_anomaly: self.is_anomaly,
+            severity: self.severity,
+            recovery_action: self.recovery_action,
+            root_cause: self.root_cause,
+            pattern_id: self.pattern_id,
+            predicted_outcome: self.predicted_outcome,
+            prediction_accurate: self.prediction_accurate,
+            tags: self.tags,
+            temporal_refs: self.temporal_refs,
+            ner_entities: self.ner_entities,
+            cooccurrence_pairs: self.cooccurrence_pairs,
+            language: None,
         }
     }
 }
@@ -636,7 +781,7 @@ impl LegacyMemoryV2 {
 ///
 /// Returns (Memory, needs_migration) where needs_migration=true means the data
 /// was in a legacy format and should be re-written for future performance.
-fn deserialize_memory(data: &[u8]) -> Result<(Memory, bool)> {
+pub(crate) fn deserialize_memory(data: &[u8]) -> Result<(Memory, bool)> {
     // Check for versioned format: SHO + version byte + payload + 4-byte CRC32
     if data.len() >= 8 && &data[0..3] == STORAGE_MAGIC {
         let version = data[3];
@@ -718,6 +863,91 @@ impl LegacyMemoryFlatV2 {
     }
 }
 
+/// Legacy MemoryFlat for bincode 2.x data written BEFORE Experience gained `language`
+#[derive(Deserialize)]
+struct LegacyMemoryFlatV3 {
+    id: MemoryId,
+    experience: LegacyExperienceV2,
+    importance: f32,
+    access_count: u32,
+    created_at: DateTime<Utc>,
+    last_accessed: DateTime<Utc>,
+    compressed: bool,
+    tier: MemoryTier,
+    entity_refs: Vec<EntityRef>,
+    activation: f32,
+    last_retrieval_id: Option<uuid::Uuid>,
+    agent_id: Option<String>,
+    run_id: Option<String>,
+    actor_id: Option<String>,
+    temporal_relevance: f32,
+    score: Option<f32>,
+    external_id: Option<String>,
+    version: u32,
+    history: Vec<MemoryRevision>,
+    related_todo_ids: Vec<TodoId>,
+    parent_id: Option<MemoryId>,
+}
+
+impl LegacyMemoryFlatV3 {
+    fn into_memory(self) -> Memory {
+        let parent_id = self.parent_id;
+        let mut memory = Memory::from_legacy(
+            self.id,
+            self.experience.into_experience(),
+            self.importance,
+            self.access_count,
+            self.created_at,
+            self.last_accessed,
+            self.compressed,
+            self.tier,
+            self.entity_refs,
+            self.activation,
+            self.last_retrieval_id,
+            self.agent_id,
+            self.run_id,
+            self.actor_id,
+            self.temporal_relevance,
+            self.score,
+            self.external_id,
+            self.version,
+            self.history,
+            self.related_todo_ids,
+        );
+        memory.set_parent(parent_id);
+        memory
+    }
+}
+
+/// Decode a current-format memory, rejecting decodes that leave trailing bytes.
+///
+/// bincode is positional, so data written before a trailing Experience field
+/// existed can occasionally decode as the current layout with bytes left over.
+fn decode_current(data: &[u8]) -> Result<Option<Memory>> {
+    let (memory, consumed) =
+        bincode::serde::decode_from_slice::<Memory, _>(data, bincode::config::standard())?;
+    Ok((consumed == data.len()).then_some(memory))
+}
+
+/// Decode a bincode 2.x Experience blob, accepting data written before `language`
+pub(crate) fn decode_experience(data: &[u8]) -> Result<Experience> {
+    match bincode::serde::decode_from_slice::<Experience, _>(data, bincode::config::standard()) {
+        Ok((experience, consumed)) if consumed == data.len() => Ok(experience),
+        current => {
+            match bincode::serde::decode_from_slice::<LegacyExperienceV2, _>(
+                data,
+                bincode::config::standard(),
+            ) {
+                Ok((legacy, _)) => Ok(legacy.into_experience()),
+                Err(e) => match current {
+                    Ok((experience, _)) => Ok(experience),
+                    Err(_) => Err(e.into()),
+                },
+            }
+        }
+    }
+}
+
 /// Try deserializing with multiple format fallbacks
 /// Supports bincode 2.x (current), MessagePack, and bincode 1.x (legacy) wire formats
 ///
@@ -733,9 +963,35 @@ fn deserialize_with_fallback(data: &[u8]) -> Result<(Memory, bool)> {
     let mut errors: Vec<(&str, String)> = Vec::new();
 
     // Try current format first (bincode 2.x with current Memory/Experience)
-    match bincode::serde::decode_from_slice::<Memory, _>(data, bincode::config::standard()) {
-        Ok((memory, _)) => return Ok((memory, false)), // Current format, no migration needed
-        Err(e) => errors.push(("bincode2 Memory", e.to_string())),
+    let partial = match decode_current(data) {
+        Ok(Some(memory)) => return Ok((memory, false)), // Current format, no migration needed
+        Ok(None) => {
+            bincode::serde::decode_from_slice::<Memory, _>(data, bincode::config::standard())
+                .ok()
+                .map(|(memory, _)| memory)
+        }
+        Err(e) => {
+            errors.push(("bincode2 Memory", e.to_string()));
+            None
+        }
+    };
+
+    // Try bincode 2.x with Experience from before language detection
+    match bincode::serde::decode_from_slice::<LegacyMemoryFlatV3, _>(
+        data,
+        bincode::config::standard(),
+    ) {
+        Ok((legacy, consumed)) if consumed == data.len() => {
+            tracing::debug!("Migrated memory from bincode 2.x pre-language format");
+            return Ok((legacy.into_memory(), true));
+        }
+        Ok(_) => errors.push(("bincode2 LegacyMemoryFlatV3", "trailing bytes".to_string())),
+        Err(e) => errors.push(("bincode2 LegacyMemoryFlatV3", e.to_string())),
+    }
+
+    // Fall back to the current layout even with trailing bytes (previous behaviour)
+    if let Some(memory) = partial {
+        return Ok((memory, false));
     }
 
     // Try bincode 2.x MINIMAL format (just UUID + content string)
@@ -946,6 +1202,99 @@ fn crc32_simple(data: &[u8]) -> u32 {
     !crc
 }
 
+/// Index DB key marking that agent/run/actor indices cover all stored memories
+const SCOPE_INDEX_MARKER: &[u8] = b"meta:scope_index_v1";
+
+/// Index DB key marking that the local-frame spatial index covers all stored memories
+///
+/// v2 escapes robot_id and frame in keys; v1 entries are dropped and rebuilt.
+const LOCAL_INDEX_MARKER: &[u8] = b"meta:local_index_v2";
+
+/// Key prefix of every local-frame index entry
+const LOCAL_INDEX_PREFIX: &str = "local:";
+
+/// Voxel cell of the local-frame index containing a position
+fn local_cell(position: [f32; 3]) -> [i64; 3] {
+    position.map(|c| (c / LOCAL_SPATIAL_CELL_SIZE_METERS).floor() as i64)
+}
+
+/// Percent-encode ':' and '%' so a key component never contains the separator
+///
+/// Without this, robot "a" in frame "b:c" and robot "a:b" in frame "c" would
+/// share the prefix `local:a:b:c:` and see each other's entries.
+fn escape_key_component(component: &str) -> Cow<'_, str> {
+    if !component.contains([':', '%']) {
+        return Cow::Borrowed(component);
+    }
+    Cow::Owned(component.replace('%', "%25").replace(':', "%3A"))
+}
+
+/// Key prefix shared by all local-frame index entries of one robot and frame
+fn local_frame_prefix(robot_id: &str, frame: &str) -> String {
+    format!(
+        "{LOCAL_INDEX_PREFIX}{}:{}:",
+        escape_key_component(robot_id),
+        escape_key_component(frame)
+    )
+}
+
+/// Key prefix of the local-frame index entries inside one voxel cell
+fn local_cell_prefix(robot_id: &str, frame: &str, cell: [i64; 3]) -> String {
+    format!(
+        "{}{}:{}:{}:",
+        local_frame_prefix(robot_id, frame),
+        cell[0],
+        cell[1],
+        cell[2]
+    )
+}
+
+/// Local-frame index entry for a memory with both a robot_id and a local_position
+///
+/// Key format: local:{robot_id}:{frame}:{cx}:{cy}:{cz}:{uuid} -> x, y, z and
+/// heading as little-endian f32s (heading NaN if absent), so queries check
+/// exact positions and heading cones without loading memories. robot_id and
+/// frame are escaped with `escape_key_component`.
+///
+/// The index is a uniform voxel grid rather than an octree or R-tree. Robot
+/// positions are points inside bounded map frames, so fixed cells never need
+/// splitting or rebalancing, and RocksDB's sorted keys already give one seek
+/// per probed cell. Each entry is a single key written in the same batch as
+/// its memory, so there is no in-memory tree to rebuild at startup or keep
+/// consistent with the database after a crash.
+fn local_index_entry(memory: &Memory) -> Option<(String, Vec<u8>)> {
+    let robot_id = memory.experience.robot_id.as_ref()?;
+    let position = memory.experience.local_position?;
+    let frame = memory.experience.local_frame();
+    let key = format!(
+        "{}{}",
+        local_cell_prefix(robot_id, frame, local_cell(position)),
+        memory.id.0
+    );
+    let heading = memory.experience.heading.unwrap_or(f32::NAN);
+    let value = position
+        .iter()
+        .chain(std::iter::once(&heading))
+        .flat_map(|v| v.to_le_bytes())
+        .collect();
+    Some((key, value))
+}
+
+/// Decode a local-frame index entry into (memory id, position, heading)
+fn decode_local_entry(key: &[u8], value: &[u8]) -> Option<(MemoryId, [f32; 3], Option<f32>)> {
+    let (_, id_str) = std::str::from_utf8(key).ok()?.rsplit_once(':')?;
+    let uuid = uuid::Uuid::parse_str(id_str).ok()?;
+    if value.len() != 16 {
+        return None;
+    }
+    let v: Vec<f32> = value
+        .chunks_exact(4)
+        .map(|b| f32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+        .collect();
+    let heading = Some(v[3]).filter(|h| !h.is_nan());
+    Some((MemoryId(uuid), [v[0], v[1], v[2]], heading))
+}
+
 /// Storage engine for long-term memory persistence
 pub struct MemoryStorage {
     db: Arc<DB>,
@@ -979,6 +1328,8 @@ impl MemoryStorage {
 
         // WAL stays in default location (same as data dir) - avoids corruption issues
         opts.set_manual_wal_flush(false); // Auto-flush WAL entries
+                                          // Keep obsolete WAL files until the backup archiver has copied them (PITR)
+        opts.set_wal_ttl_seconds(crate::backup::WAL_RETENTION_SECS);
 
         // Write performance optimizations for 10M+ memories per user
         opts.set_max_write_buffer_number(4);
@@ -1017,12 +1368,100 @@ impl MemoryStorage {
             }
         );
 
-        Ok(Self {
+        let storage = Self {
             db,
             index_db,
             storage_path: path.to_path_buf(),
             write_mode,
-        })
+        };
+        if let Err(e) = storage.backfill_scope_indices() {
+            tracing::warn!("Failed to backfill agent/run/actor indices: {}", e);
+        }
+        if let Err(e) = storage.backfill_local_indices() {
+            tracing::warn!("Failed to backfill local-frame spatial index: {}", e);
+        }
+        Ok(storage)
+    }
+
+    /// Index agent/run/actor ids of memories stored before those indices existed
+    ///
+    /// Runs once per database; a marker key in the index DB records completion.
+    fn backfill_scope_indices(&self) -> Result<()> {
+        if self.index_db.get(SCOPE_INDEX_MARKER)?.is_some() {
+            return Ok(());
+        }
+
+        let mut batch = WriteBatch::default();
+        let mut indexed = 0usize;
+        for (key, value) in self.db.iterator(IteratorMode::Start).log_errors() {
+            if key.len() != 16 {
+                continue;
+            }
+            if let Ok((memory, _)) = deserialize_memory(&value) {
+                if memory.agent_id.is_some() || memory.run_id.is_some() || memory.actor_id.is_some()
+                {
+                    Self::put_scope_indices(&mut batch, &memory);
+                    indexed += 1;
+                }
+            }
+        }
+        batch.put(SCOPE_INDEX_MARKER, b"1");
+        self.index_db.write(batch)?;
+
+        if indexed > 0 {
+            tracing::info!(
+                "Backfilled agent/run/actor indices for {} memories",
+                indexed
+            );
+        }
+        Ok(())
+    }
+
+    /// Index local positions of memories stored before the local-frame index existed
+    ///
+    /// Runs once per database (and again when the key format version in
+    /// `LOCAL_INDEX_MARKER` changes); a marker key records completion.
+    fn backfill_local_indices(&self) -> Result<()> {
+        if self.index_db.get(LOCAL_INDEX_MARKER)?.is_some() {
+            return Ok(());
+        }
+
+        let mut batch = WriteBatch::default();
+
+        // Drop entries written under an older key format before re-indexing
+        let iter = self.index_db.iterator(IteratorMode::From(
+            LOCAL_INDEX_PREFIX.as_bytes(),
+            rocksdb::Direction::Forward,
+        ));
+        for (key, _) in iter.log_errors() {
+            if !key.starts_with(LOCAL_INDEX_PREFIX.as_bytes()) {
+                break;
+            }
+            batch.delete(&key);
+        }
+
+        let mut indexed = 0usize;
+        for (key, value) in self.db.iterator(IteratorMode::Start).log_errors() {
+            if key.len() != 16 {
+                continue;
+            }
+            if let Ok((memory, _)) = deserialize_memory(&value) {
+                if let Some((local_key, local_value)) = local_index_entry(&memory) {
+                    batch.put(local_key.as_bytes(), &local_value);
+                    indexed += 1;
+                }
+            }
+        }
+        batch.put(LOCAL_INDEX_MARKER, b"1");
+        self.index_db.write(batch)?;
+
+        if indexed > 0 {
+            tracing::info!(
+                "Backfilled local-frame spatial index for {} memories",
+                indexed
+            );
+        }
+        Ok(())
     }
 
     /// Open a RocksDB database, automatically repairing if corruption is detected.
@@ -1200,6 +1639,13 @@ impl MemoryStorage {
             batch.put(geo_key.as_bytes(), b"1");
         }
 
+        // Index by local_position in the robot's map frame (indoor spatial queries)
+        // Key format: local:{robot_id}:{frame}:{cx}:{cy}:{cz}:memory_id (1m voxel cells,
+        // robot_id and frame escaped)
+        if let Some((local_key, local_value)) = local_index_entry(memory) {
+            batch.put(local_key.as_bytes(), &local_value);
+        }
+
         // Index by action_type (for action-based retrieval)
         if let Some(ref action_type) = memory.experience.action_type {
             let action_key = format!("action:{}:{}", action_type, memory.id.0);
@@ -1215,6 +1661,9 @@ impl MemoryStorage {
             batch.put(reward_key.as_bytes(), b"1");
         }
 
+        // === Multi-Agent Indices ===
+        Self::put_scope_indices(&mut batch, memory);
+
         // === External Linking Index ===
         // Index by external_id for upsert operations (Linear, GitHub, etc.)
         // Key format: external:{source}:{id}:{memory_id} -> memory_id
@@ -1241,6 +1690,27 @@ impl MemoryStorage {
         Ok(())
     }
 
+    /// Add the agent/run/actor index entries for a memory to a batch
+    ///
+    /// Key format: agent:{agent_id}:{uuid} -> run_id (empty if none), so
+    /// per-agent run counts can be read from the index alone;
+    /// run:{run_id}:{uuid} and actor:{actor_id}:{uuid} -> 1
+    fn put_scope_indices(batch: &mut WriteBatch, memory: &Memory) {
+        if let Some(ref agent_id) = memory.agent_id {
+            let agent_key = format!("agent:{}:{}", agent_id, memory.id.0);
+            let run_id = memory.run_id.as_deref().unwrap_or_default();
+            batch.put(agent_key.as_bytes(), run_id.as_bytes());
+        }
+        if let Some(ref run_id) = memory.run_id {
+            let run_key = format!("run:{}:{}", run_id, memory.id.0);
+            batch.put(run_key.as_bytes(), b"1");
+        }
+        if let Some(ref actor_id) = memory.actor_id {
+            let actor_key = format!("actor:{}:{}", actor_id, memory.id.0);
+            batch.put(actor_key.as_bytes(), b"1");
+        }
+    }
+
     /// Retrieve a memory by ID
     ///
     /// Performs lazy migration: if memory is in legacy format, re-writes it
@@ -1422,6 +1892,11 @@ impl MemoryStorage {
             batch.delete(geo_key.as_bytes());
         }
 
+        // Local-frame index
+        if let Some((local_key, _)) = local_index_entry(&memory) {
+            batch.delete(local_key.as_bytes());
+        }
+
         // Action index
         if let Some(ref action_type) = memory.experience.action_type {
             let action_key = format!("action:{}:{}", action_type, id.0);
@@ -1436,6 +1911,20 @@ impl MemoryStorage {
             batch.delete(reward_key.as_bytes());
         }
 
+        // Multi-agent indices
+        if let Some(ref agent_id) = memory.agent_id {
+            let agent_key = format!("agent:{}:{}", agent_id, id.0);
+            batch.delete(agent_key.as_bytes());
+        }
+        if let Some(ref run_id) = memory.run_id {
+            let run_key = format!("run:{}:{}", run_id, id.0);
+            batch.delete(run_key.as_bytes());
+        }
+        if let Some(ref actor_id) = memory.actor_id {
+            let actor_key = format!("actor:{}:{}", actor_id, id.0);
+            batch.delete(actor_key.as_bytes());
+        }
+
         // External linking index
         if let Some(ref external_id) = memory.external_id {
             let external_key = format!("external:{}:{}", external_id, id.0);
@@ -1457,6 +1946,28 @@ impl MemoryStorage {
 
     /// Search memories by various criteria
     pub fn search(&self, criteria: SearchCriteria) -> Result<Vec<Memory>> {
+        let memory_ids = self.search_ids(criteria)?;
+
+        // Fetch full memories, filtering out forgotten ones
+        let mut memories = Vec::new();
+        for id in memory_ids {
+            if let Ok(memory) = self.get(&id) {
+                if !memory.is_forgotten() {
+                    memories.push(memory);
+                }
+            }
+        }
+
+        Ok(memories)
+    }
+
+    /// Resolve search criteria to candidate memory IDs using the secondary indices
+    ///
+    /// No memories are deserialized (except for `RootsOnly`, which has no index), so
+    /// this is cheap enough to run before a vector search to build its allow-list.
+    /// Index buckets are coarse (dates by day, importance and reward by tenth), so
+    /// callers needing exact bounds should confirm with [`SearchCriteria::matches`].
+    pub fn search_ids(&self, criteria: SearchCriteria) -> Result<Vec<MemoryId>> {
         let mut memory_ids = Vec::new();
 
         match criteria {
@@ -1511,6 +2022,17 @@ impl MemoryStorage {
                 memory_ids = self.search_by_reward(min, max)?;
             }
 
+            // === Multi-Agent Criteria ===
+            SearchCriteria::ByAgent(agent_id) => {
+                memory_ids = self.search_by_scope("agent", &agent_id)?;
+            }
+            SearchCriteria::ByRun(run_id) => {
+                memory_ids = self.search_by_scope("run", &run_id)?;
+            }
+            SearchCriteria::ByActor(actor_id) => {
+                memory_ids = self.search_by_scope("actor", &actor_id)?;
+            }
+
             // === Compound Criteria ===
             SearchCriteria::Combined(criterias) => {
                 // Intersection of all criteria results
@@ -1518,12 +2040,7 @@ impl MemoryStorage {
                 use std::collections::HashSet;
                 let mut result_sets: Vec<HashSet<MemoryId>> = Vec::new();
                 for c in criterias {
-                    result_sets.push(
-                        self.search(c)?
-                            .into_iter()
-                            .map(|m| m.id)
-                            .collect::<HashSet<_>>(),
-                    );
+                    result_sets.push(self.search_ids(c)?.into_iter().collect::<HashSet<_>>());
                 }
 
                 if !result_sets.is_empty() {
@@ -1544,17 +2061,7 @@ impl MemoryStorage {
             }
         }
 
-        // Fetch full memories, filtering out forgotten ones
-        let mut memories = Vec::new();
-        for id in memory_ids {
-            if let Ok(memory) = self.get(&id) {
-                if !memory.is_forgotten() {
-                    memories.push(memory);
-                }
-            }
-        }
-
-        Ok(memories)
+        Ok(memory_ids)
     }
 
     fn search_by_date_range(
@@ -1882,6 +2389,188 @@ impl MemoryStorage {
         Ok(ids)
     }
 
+    /// Search a robot's local-frame index for positions inside a filter
+    ///
+    /// Returns memory IDs with their distance from the filter center, closest
+    /// first; `Nearest` regions return at most `k` entries. Only voxel cells
+    /// overlapping the region are probed, up to `LOCAL_SPATIAL_MAX_CELL_PROBES`
+    /// before falling back to one scan over the whole frame.
+    pub fn search_local(
+        &self,
+        robot_id: &str,
+        filter: &LocalSpatialFilter,
+    ) -> Result<Vec<(MemoryId, f32)>> {
+        let mut hits = match filter.region {
+            LocalRegion::Radius {
+                center,
+                radius_meters,
+            } => self.scan_local_box(
+                robot_id,
+                filter,
+                center.map(|c| c - radius_meters),
+                center.map(|c| c + radius_meters),
+            ),
+            LocalRegion::BoundingBox { min, max } => {
+                self.scan_local_box(robot_id, filter, min, max)
+            }
+            LocalRegion::Nearest { center, k } => {
+                self.search_local_nearest(robot_id, filter, center, k)
+            }
+        };
+
+        hits.sort_by(|a, b| a.1.total_cmp(&b.1));
+        if let LocalRegion::Nearest { k, .. } = filter.region {
+            hits.truncate(k);
+        }
+        Ok(hits)
+    }
+
+    /// Collect entries passing `filter` from the cells overlapping `min`..=`max`
+    fn scan_local_box(
+        &self,
+        robot_id: &str,
+        filter: &LocalSpatialFilter,
+        min: [f32; 3],
+        max: [f32; 3],
+    ) -> Vec<(MemoryId, f32)> {
+        let mut hits = Vec::new();
+        let (lo, hi) = (local_cell(min), local_cell(max));
+        let probes = (0..3).try_fold(1usize, |acc, i| {
+            let span = hi[i].saturating_sub(lo[i]).saturating_add(1).max(0);
+            acc.checked_mul(usize::try_from(span).ok()?)
+        });
+
+        match probes {
+            Some(n) if n <= LOCAL_SPATIAL_MAX_CELL_PROBES => {
+                for x in lo[0]..=hi[0] {
+                    for y in lo[1]..=hi[1] {
+                        for z in lo[2]..=hi[2] {
+                            let prefix = local_cell_prefix(robot_id, &filter.frame, [x, y, z]);
+                            self.scan_local_prefix(&prefix, filter, &mut hits);
+                        }
+                    }
+                }
+            }
+            _ => {
+                let prefix = local_frame_prefix(robot_id, &filter.frame);
+                self.scan_local_prefix(&prefix, filter, &mut hits);
+            }
+        }
+        hits
+    }
+
+    /// k-nearest search over growing shells of cells around the center cell
+    ///
+    /// Once every cell within `ring` cells of the center has been scanned, any
+    /// unscanned entry is at least `ring` cell widths away, so the search stops
+    /// as soon as the k-th closest hit is nearer than that. Sparse frames fall
+    /// back to one scan over the whole frame when the shell outgrows the probe
+    /// budget; that scan keeps only the closest `k` hits as it goes.
+    fn search_local_nearest(
+        &self,
+        robot_id: &str,
+        filter: &LocalSpatialFilter,
+        center: [f32; 3],
+        k: usize,
+    ) -> Vec<(MemoryId, f32)> {
+        let mut hits = Vec::new();
+        if k == 0 {
+            return hits;
+        }
+
+        let origin = local_cell(center);
+        let mut ring: i64 = 0;
+        loop {
+            let side = (2 * ring + 1) as usize;
+            if side.pow(3) > LOCAL_SPATIAL_MAX_CELL_PROBES {
+                let prefix = local_frame_prefix(robot_id, &filter.frame);
+                return self.scan_local_prefix_nearest(&prefix, filter, k);
+            }
+
+            for dx in -ring..=ring {
+                for dy in -ring..=ring {
+                    for dz in -ring..=ring {
+                        // Only the outer shell; inner cells were scanned by earlier rings
+                        if dx.abs().max(dy.abs()).max(dz.abs()) != ring {
+                            continue;
+                        }
+                        let cell = [
+                            origin[0].saturating_add(dx),
+                            origin[1].saturating_add(dy),
+                            origin[2].saturating_add(dz),
+                        ];
+                        let prefix = local_cell_prefix(robot_id, &filter.frame, cell);
+                        self.scan_local_prefix(&prefix, filter, &mut hits);
+                    }
+                }
+            }
+
+            if hits.len() >= k {
+                hits.sort_by(|a, b| a.1.total_cmp(&b.1));
+                if hits[k - 1].1 <= ring as f32 * LOCAL_SPATIAL_CELL_SIZE_METERS {
+                    return hits;
+                }
+            }
+            ring += 1;
+        }
+    }
+
+    /// Append local-frame entries under a key prefix that pass `filter`
+    fn scan_local_prefix(
+        &self,
+        prefix: &str,
+        filter: &LocalSpatialFilter,
+        hits: &mut Vec<(MemoryId, f32)>,
+    ) {
+        self.for_each_local_hit(prefix, filter, |id, distance| hits.push((id, distance)));
+    }
+
+    /// The `k` entries under a key prefix closest to the filter center, unsorted
+    ///
+    /// Holds fewer than `2 * k` hits at any time, so scanning a whole frame
+    /// stays bounded in memory however many entries it has.
+    fn scan_local_prefix_nearest(
+        &self,
+        prefix: &str,
+        filter: &LocalSpatialFilter,
+        k: usize,
+    ) -> Vec<(MemoryId, f32)> {
+        let mut hits = Vec::with_capacity(2 * k);
+        self.for_each_local_hit(prefix, filter, |id, distance| {
+            hits.push((id, distance));
+            if hits.len() == 2 * k {
+                hits.select_nth_unstable_by(k - 1, |a, b| a.1.total_cmp(&b.1));
+                hits.truncate(k);
+            }
+        });
+        hits
+    }
+
+    /// Call `on_hit` with the id and center distance of each entry under a key
+    /// prefix that passes `filter`
+    fn for_each_local_hit(
+        &self,
+        prefix: &str,
+        filter: &LocalSpatialFilter,
+        mut on_hit: impl FnMut(MemoryId, f32),
+    ) {
+        let center = filter.center();
+        let iter = self.index_db.iterator(IteratorMode::From(
+            prefix.as_bytes(),
+            rocksdb::Direction::Forward,
+        ));
+        for (key, value) in iter.log_errors() {
+            if !key.starts_with(prefix.as_bytes()) {
+                break;
+            }
+            if let Some((id, position, heading)) = decode_local_entry(&key, &value) {
+                if filter.contains(position, heading) {
+                    on_hit(id, local_distance(center, position));
+                }
+            }
+        }
+    }
+
     /// Search memories by action type
     fn search_by_action_type(&self, action_type: &str) -> Result<Vec<MemoryId>> {
         let mut ids = Vec::new();
@@ -1940,6 +2629,76 @@ impl MemoryStorage {
         Ok(ids)
     }
 
+    // =========================================================================
+    // MULTI-AGENT SEARCH METHODS
+    // =========================================================================
+
+    /// Search memories by agent, run or actor identifier
+    ///
+    /// `kind` is the index prefix: `agent`, `run` or `actor`.
+    fn search_by_scope(&self, kind: &str, scope_id: &str) -> Result<Vec<MemoryId>> {
+        let mut ids = Vec::new();
+        let prefix = format!("{kind}:{scope_id}:");
+
+        let iter = self.index_db.iterator(IteratorMode::From(
+            prefix.as_bytes(),
+            rocksdb::Direction::Forward,
+        ));
+        for (key, _) in iter.log_errors() {
+            let key_str = String::from_utf8_lossy(&key);
+            if !key_str.starts_with(&prefix) {
+                break;
+            }
+            if let Some(id_str) = key_str.strip_prefix(&prefix) {
+                if let Ok(uuid) = uuid::Uuid::parse_str(id_str) {
+                    ids.push(MemoryId(uuid));
+                }
+            }
+        }
+
+        Ok(ids)
+    }
+
+    /// Per-agent memory and run counts, read from the agent index
+    ///
+    /// The agent index stores each memory's run_id as its value, so this never
+    /// deserializes a memory. Memories without an agent_id are not counted.
+    pub fn agent_stats(&self) -> Result<BTreeMap<String, AgentStats>> {
+        let mut runs: BTreeMap<String, (usize, HashSet<Vec<u8>>)> = BTreeMap::new();
+
+        let iter = self
+            .index_db
+            .iterator(IteratorMode::From(b"agent:", rocksdb::Direction::Forward));
+        for (key, value) in iter.log_errors() {
+            let Some(rest) = key.strip_prefix(b"agent:") else {
+                break;
+            };
+            // Key format: agent:{agent_id}:{uuid} (agent ids may contain ':')
+            let rest = String::from_utf8_lossy(rest);
+            let Some((agent_id, _)) = rest.rsplit_once(':') else {
+                continue;
+            };
+            let entry = runs.entry(agent_id.to_string()).or_default();
+            entry.0 += 1;
+            if !value.is_empty() {
+                entry.1.insert(value.to_vec());
+            }
+        }
+
+        Ok(runs
+            .into_iter()
+            .map(|(agent_id, (memory_count, run_ids))| {
+                (
+                    agent_id,
+                    AgentStats {
+                        memory_count,
+                        run_count: run_ids.len(),
+                    },
+                )
+            })
+            .collect())
+    }
+
     // =========================================================================
     // HIERARCHY SEARCH METHODS
     // =========================================================================
@@ -2085,6 +2844,35 @@ impl MemoryStorage {
         Ok(memories)
     }
 
+    /// Get up to `limit` memories in key order, starting after `after`
+    ///
+    /// Walks the same entries as `get_all` one page at a time; pass the last
+    /// returned ID as `after` to continue. An empty page means the end.
+    pub fn get_page(&self, after: Option<&MemoryId>, limit: usize) -> Result<Vec<Memory>> {
+        let mode = match after {
+            Some(id) => IteratorMode::From(id.0.as_bytes(), rocksdb::Direction::Forward),
+            None => IteratorMode::Start,
+        };
+
+        let mut memories = Vec::new();
+        for item in self.db.iterator(mode) {
+            let Ok((key, value)) = item else { continue };
+            if key.len() != 16 || after.is_some_and(|id| *key == *id.0.as_bytes()) {
+                continue;
+            }
+            if let Ok((memory, _)) = deserialize_memory(&value) {
+                if !memory.is_forgotten() {
+                    memories.push(memory);
+                    if memories.len() >= limit {
+                        break;
+                    }
+                }
+            }
+        }
+
+        Ok(memories)
+    }
+
     pub fn get_uncompressed_older_than(&self, cutoff: DateTime<Utc>) -> Result<Vec<Memory>> {
         let mut memories = Vec::new();
 
@@ -2432,11 +3220,7 @@ impl MemoryStorage {
                 }
 
                 // Try current format first (quick check)
-                let is_current = bincode::serde::decode_from_slice::<Memory, _>(
-                    &value,
-                    bincode::config::standard(),
-                )
-                .is_ok();
+                let is_current = matches!(decode_current(&value), Ok(Some(_)));
 
                 if is_current {
                     already_current += 1;
@@ -2525,6 +3309,11 @@ impl MemoryStorage {
     pub fn db(&self) -> Arc<DB> {
         self.db.clone()
     }
+
+    /// Get a reference to the secondary index database (for backup/restore)
+    pub fn index_db(&self) -> Arc<DB> {
+        self.index_db.clone()
+    }
 }
 
 /// Search criteria for memory retrieval
@@ -2575,6 +3364,14 @@ pub enum SearchCriteria {
         max: f32,
     },
 
+    // === Multi-Agent Criteria ===
+    /// Filter by the agent that created the memory
+    ByAgent(String),
+    /// Filter by agent run (one execution of an agent)
+    ByRun(String),
+    /// Filter by the actor (end user or caller) the agent acted for
+    ByActor(String),
+
     // === Compound Criteria ===
     Combined(Vec<SearchCriteria>),
 
@@ -2585,6 +3382,90 @@ pub enum SearchCriteria {
     RootsOnly,
 }
 
+impl SearchCriteria {
+    /// Check whether a single memory satisfies these criteria
+    ///
+    /// Mirrors [`MemoryStorage::search_ids`] but with exact bounds, so it can
+    /// confirm candidates from the bucketed indices and filter in-memory tiers.
+    pub fn matches(&self, memory: &Memory) -> bool {
+        let episode = memory.experience.context.as_ref().map(|ctx| &ctx.episode);
+
+        match self {
+            SearchCriteria::ByDate { start, end } => {
+                memory.created_at >= *start && memory.created_at <= *end
+            }
+            SearchCriteria::ByType(exp_type) => {
+                std::mem::discriminant(&memory.experience.experience_type)
+                    == std::mem::discriminant(exp_type)
+            }
+            SearchCriteria::ByImportance { min, max } => {
+                let importance = memory.importance();
+                importance >= *min && importance <= *max
+            }
+            SearchCriteria::ByEntity(entity) => {
+                // Indices are lowercased, so compare the same way
+                let entity = entity.to_lowercase();
+                memory
+                    .experience
+                    .entities
+                    .iter()
+                    .any(|e| e.to_lowercase() == entity)
+            }
+            SearchCriteria::ByTags(tags) => {
+                let tags: Vec<String> = tags.iter().map(|t| t.to_lowercase()).collect();
+                memory
+                    .experience
+                    .tags
+                    .iter()
+                    .any(|t| tags.contains(&t.to_lowercase()))
+            }
+            SearchCriteria::ByEpisode(episode_id) => {
+                episode.and_then(|e| e.episode_id.as_ref()) == Some(episode_id)
+            }
+            SearchCriteria::ByEpisodeSequence {
+                episode_id,
+                min_sequence,
+                max_sequence,
+            } => match episode {
+                Some(e) if e.episode_id.as_ref() == Some(episode_id) => match e.sequence_number {
+                    Some(seq) => {
+                        min_sequence.is_none_or(|min| seq >= min)
+                            && max_sequence.is_none_or(|max| seq <= max)
+                    }
+                    None => min_sequence.is_none() && max_sequence.is_none(),
+                },
+                _ => false,
+            },
+            SearchCriteria::ByRobot(robot_id) => {
+                memory.experience.robot_id.as_ref() == Some(robot_id)
+            }
+            SearchCriteria::ByMission(mission_id) => {
+                memory.experience.mission_id.as_ref() == Some(mission_id)
+            }
+            SearchCriteria::ByLocation {
+                lat,
+                lon,
+                radius_meters,
+            } => memory.experience.geo_location.is_some_and(|geo| {
+                GeoFilter::new(*lat, *lon, *radius_meters).contains(geo[0], geo[1])
+            }),
+            SearchCriteria::ByActionType(action_type) => {
+                memory.experience.action_type.as_ref() == Some(action_type)
+            }
+            SearchCriteria::ByReward { min, max } => memory
+                .experience
+                .reward
+                .is_some_and(|reward| reward >= *min && reward <= *max),
+            SearchCriteria::ByAgent(agent_id) => memory.agent_id.as_ref() == Some(agent_id),
+            SearchCriteria::ByRun(run_id) => memory.run_id.as_ref() == Some(run_id),
+            SearchCriteria::ByActor(actor_id) => memory.actor_id.as_ref() == Some(actor_id),
+            SearchCriteria::Combined(criterias) => criterias.iter().all(|c| c.matches(memory)),
+            SearchCriteria::ByParent(parent_id) => memory.parent_id.as_ref() == Some(parent_id),
+            SearchCriteria::RootsOnly => memory.parent_id.is_none(),
+        }
+    }
+}
+
 /// Storage statistics
 #[derive(Debug, Default, Serialize, Deserialize)]
 pub struct StorageStats {
@@ -3359,4 +4240,249 @@ mod tests {
         assert_eq!(mv.dimension, 384);
         assert!(mv.chunk_ranges.is_none());
     }
+
+    #[test]
+    fn test_agent_run_actor_indices() {
+        let dir = tempfile::tempdir().unwrap();
+        let storage = MemoryStorage::new(dir.path()).unwrap();
+
+        let scoped = |agent: &str, run: &str, actor: Option<&str>| {
+            Memory::new(
+                MemoryId(uuid::Uuid::new_v4()),
+                Experience {
+                    content: format!("{agent} in {run}"),
+                    ..Default::default()
+                },
+                0.5,
+                Some(agent.to_string()),
+                Some(run.to_string()),
+                actor.map(str::to_string),
+                None,
+            )
+        };
+        let planner_a = scoped("planner", "run-a", Some("alice"));
+        let planner_b = scoped("planner", "run-b", None);
+        let coder_a = scoped("coder", "run-a", Some("alice"));
+        for memory in [&planner_a, &planner_b, &coder_a] {
+            storage.store(memory).unwrap();
+        }
+
+        let planner = storage
+            .search_ids(SearchCriteria::ByAgent("planner".to_string()))
+            .unwrap();
+        assert_eq!(planner.len(), 2);
+        let planner_in_a = storage
+            .search_ids(SearchCriteria::Combined(vec![
+                SearchCriteria::ByAgent("planner".to_string()),
+                SearchCriteria::ByRun("run-a".to_string()),
+            ]))
+            .unwrap();
+        assert_eq!(planner_in_a, vec![planner_a.id.clone()]);
+        let alice = storage
+            .search_ids(SearchCriteria::ByActor("alice".to_string()))
+            .unwrap();
+        assert_eq!(alice.len(), 2);
+        assert!(SearchCriteria::ByRun("run-b".to_string()).matches(&planner_b));
+        assert!(!SearchCriteria::ByRun("run-b".to_string()).matches(&coder_a));
+
+        let stats = storage.agent_stats().unwrap();
+        assert_eq!(
+            stats["planner"],
+            AgentStats {
+                memory_count: 2,
+                run_count: 2
+            }
+        );
+        assert_eq!(stats["coder"].memory_count, 1);
+
+        storage.delete(&planner_b.id).unwrap();
+        assert!(storage
+            .search_ids(SearchCriteria::ByRun("run-b".to_string()))
+            .unwrap()
+            .is_empty());
+        assert_eq!(storage.agent_stats().unwrap()["planner"].run_count, 1);
+    }
+
+    #[test]
+    fn test_local_spatial_index() {
+        let dir = tempfile::tempdir().unwrap();
+        let storage = MemoryStorage::new(dir.path()).unwrap();
+
+        let at = |robot: &str, frame: &str, position: [f32; 3], heading: Option<f32>| {
+            let mut experience = Experience {
+                content: format!("{robot} at {position:?}"),
+                robot_id: Some(robot.to_string()),
+                local_position: Some(position),
+                heading,
+                ..Default::default()
+            };
+            experience
+                .metadata
+                .insert(LOCAL_FRAME_METADATA_KEY.to_string(), frame.to_string());
+            let memory = Memory::new(
+                MemoryId(uuid::Uuid::new_v4()),
+                experience,
+                0.5,
+                None,
+                None,
+                None,
+                None,
+            );
+            storage.store(&memory).unwrap();
+            memory.id
+        };
+        let dock = at("amr", "floor1", [0.5, 0.5, 0.0], Some(0.0));
+        let aisle = at("amr", "floor1", [3.0, 4.0, 0.0], Some(180.0));
+        let negative = at("amr", "floor1", [-2.5, -0.5, 0.0], None);
+        let far = at("amr", "floor1", [40.0, 0.0, 0.0], Some(90.0));
+        at("amr", "floor2", [0.5, 0.5, 0.0], None);
+        at("drone", "floor1", [0.5, 0.5, 0.0], None);
+
+        let ids =
+            |hits: Vec<(MemoryId, f32)>| hits.into_iter().map(|(id, _)| id).collect::<Vec<_>>();
+        let search = |region: LocalRegion| {
+            ids(storage
+                .search_local("amr", &LocalSpatialFilter::new("floor1", region))
+                .unwrap())
+        };
+
+        let radius = search(LocalRegion::Radius {
+            center: [0.0, 0.0, 0.0],
+            radius_meters: 5.0,
+        });
+        assert_eq!(radius, vec![dock.clone(), negative.clone(), aisle.clone()]);
+
+        let bbox = search(LocalRegion::BoundingBox {
+            min: [-3.0, -1.0, -1.0],
+            max: [1.0, 1.0, 1.0],
+        });
+        assert_eq!(bbox.len(), 2);
+        assert!(bbox.contains(&dock) && bbox.contains(&negative));
+
+        // aisle is 35m away, past the probe budget: found by the frame scan
+        let nearest = search(LocalRegion::Nearest {
+            center: [38.0, 0.0, 0.0],
+            k: 2,
+        });
+        assert_eq!(nearest, vec![far.clone(), aisle.clone()]);
+        let nearest = search(LocalRegion::Nearest {
+            center: [0.0, 0.0, 0.0],
+            k: 2,
+        });
+        assert_eq!(nearest, vec![dock.clone(), negative.clone()]);
+        // Nothing within the probe budget; the frame scan keeps the closest
+        let nearest = search(LocalRegion::Nearest {
+            center: [-60.0, 0.0, 0.0],
+            k: 1,
+        });
+        assert_eq!(nearest, vec![negative.clone()]);
+
+        // Heading cone drops memories facing away or without a heading
+        let facing_back = ids(storage
+            .search_local(
+                "amr",
+                &LocalSpatialFilter::new(
+                    "floor1",
+                    LocalRegion::Radius {
+                        center: [0.0, 0.0, 0.0],
+                        radius_meters: 5.0,
+                    },
+                )
+                .with_heading_cone(HeadingCone::new(170.0, 20.0)),
+            )
+            .unwrap());
+        assert_eq!(facing_back, vec![aisle.clone()]);
+
+        storage.delete(&aisle).unwrap();
+        let nearest = search(LocalRegion::Nearest {
+            center: [0.0, 0.0, 0.0],
+            k: 10,
+        });
+        assert_eq!(nearest, vec![dock, negative, far]);
+    }
+
+    #[test]
+    fn test_local_index_keys_escape_separators() {
+        let dir = tempfile::tempdir().unwrap();
+        let storage = MemoryStorage::new(dir.path()).unwrap();
+
+        let mut experience = Experience {
+            content: "robot a:b in frame c".to_string(),
+            robot_id: Some("a:b".to_string()),
+            local_position: Some([0.5, 0.5, 0.0]),
+            ..Default::default()
+        };
+        experience
+            .metadata
+            .insert(LOCAL_FRAME_METADATA_KEY.to_string(), "c".to_string());
+        let memory = Memory::new(
+            MemoryId(uuid::Uuid::new_v4()),
+            experience,
+            0.5,
+            None,
+            None,
+            None,
+            None,
+        );
+        storage.store(&memory).unwrap();
+
+        let radius = LocalRegion::Radius {
+            center: [0.0, 0.0, 0.0],
+            radius_meters: 2.0,
+        };
+        let hits = storage
+            .search_local("a", &LocalSpatialFilter::new("b:c", radius.clone()))
+            .unwrap();
+        assert!(hits.is_empty());
+        let hits = storage
+            .search_local("a:b", &LocalSpatialFilter::new("c", radius))
+            .unwrap();
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].0, memory.id);
+
+        assert_eq!(escape_key_component("50%:x"), "50%25%3Ax");
+        assert_eq!(escape_key_component("map"), "map");
+    }
+
+    #[test]
+    fn test_get_page_walks_all_memories() {
+        let dir = tempfile::tempdir().unwrap();
+        let storage = MemoryStorage::new(dir.path()).unwrap();
+        for i in 0..5 {
+            let memory = Memory::new(
+                MemoryId(uuid::Uuid::new_v4()),
+                Experience {
+                    content: format!("memory {i}"),
+                    ..Default::default()
+                },
+                0.5,
+                None,
+                None,
+                None,
+                None,
+            );
+            storage.store(&memory).unwrap();
+        }
+
+        let mut seen = Vec::new();
+        let mut after: Option<MemoryId> = None;
+        loop {
+            let page = storage.get_page(after.as_ref(), 2).unwrap();
+            let Some(last) = page.last() else { break };
+            assert!(page.len() <= 2);
+            after = Some(last.id.clone());
+            seen.extend(page.into_iter().map(|m| m.id));
+        }
+
+        let mut all: Vec<MemoryId> = storage
+            .get_all()
+            .unwrap()
+            .into_iter()
+            .map(|m| m.id)
+            .collect();
+        all.sort();
+        seen.sort();
+        assert_eq!(seen, all);
+        assert_eq!(seen.len(), 5);
+    }
 }
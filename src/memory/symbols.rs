@@ -0,0 +1,1270 @@
+//! Structural Symbol Extraction for Indexed Source Files
+//!
+//! Finds the items declared in a source file - functions, methods, types,
+//! impls, constants - with their line span, enclosing scope, doc comment and
+//! signature, so each can be embedded and searched on its own.
+//!
+//! There is no parser dependency: a small lexer blanks out strings and
+//! comments (keeping comment text for doc lookup), declarations are matched
+//! line by line, and spans come from brace matching (indentation for Python).
+//! That handles conventionally formatted Rust, Python, TypeScript/JavaScript
+//! and Go; other file types yield no symbols.
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::sync::LazyLock;
+
+use super::types::FileType;
+
+/// Symbols kept per file (declaration order)
+pub const MAX_SYMBOLS_PER_FILE: usize = 500;
+/// Declarations considered per file, including TS/JS member candidates that are discarded
+const MAX_CANDIDATES_PER_FILE: usize = MAX_SYMBOLS_PER_FILE * 4;
+const MAX_SIGNATURE_CHARS: usize = 240;
+const MAX_DOC_CHARS: usize = 500;
+/// Key items kept on the file memory
+const MAX_KEY_ITEMS: usize = 50;
+
+/// What kind of item a symbol is
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SymbolKind {
+    Function,
+    /// Function declared in an impl, trait, class or interface, or with a Go receiver
+    Method,
+    Struct,
+    Enum,
+    Trait,
+    Impl,
+    Interface,
+    Class,
+    TypeAlias,
+    Const,
+    Variable,
+    Module,
+    Macro,
+}
+
+impl SymbolKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SymbolKind::Function => "function",
+            SymbolKind::Method => "method",
+            SymbolKind::Struct => "struct",
+            SymbolKind::Enum => "enum",
+            SymbolKind::Trait => "trait",
+            SymbolKind::Impl => "impl",
+            SymbolKind::Interface => "interface",
+            SymbolKind::Class => "class",
+            SymbolKind::TypeAlias => "type",
+            SymbolKind::Const => "const",
+            SymbolKind::Variable => "variable",
+            SymbolKind::Module => "module",
+            SymbolKind::Macro => "macro",
+        }
+    }
+
+    fn is_callable(self) -> bool {
+        matches!(self, SymbolKind::Function | SymbolKind::Method)
+    }
+
+    /// Functions declared directly inside these are methods
+    fn has_methods(self) -> bool {
+        matches!(
+            self,
+            SymbolKind::Impl | SymbolKind::Trait | SymbolKind::Class | SymbolKind::Interface
+        )
+    }
+}
+
+/// An item declared in a source file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CodeSymbol {
+    pub kind: SymbolKind,
+    pub name: String,
+    /// First line of the declaration (1-based, including the signature)
+    pub start_line: usize,
+    /// Last line of the body (1-based, inclusive)
+    pub end_line: usize,
+    /// Enclosing type, impl target, class, module or function
+    pub parent: Option<String>,
+    /// Doc comment or docstring, joined into one line
+    pub doc: Option<String>,
+    /// Declaration up to its body, whitespace collapsed
+    pub signature: String,
+    /// Embedding of [`CodeSymbol::embed_text`]
+    pub embedding: Option<Vec<f32>>,
+}
+
+impl CodeSymbol {
+    /// `Parent::name`, or just the name for top-level symbols
+    pub fn qualified_name(&self) -> String {
+        match &self.parent {
+            Some(parent) => format!("{parent}::{}", self.name),
+            None => self.name.clone(),
+        }
+    }
+
+    /// Text embedded for semantic symbol search
+    pub fn embed_text(&self) -> String {
+        let mut text = format!(
+            "{} {} | {}",
+            self.kind.as_str(),
+            self.qualified_name(),
+            self.signature
+        );
+        if let Some(doc) = &self.doc {
+            text.push_str(" | ");
+            text.push_str(doc);
+        }
+        text
+    }
+
+    /// How well the symbol's name, signature or doc matches a query (0.0-1.0)
+    ///
+    /// `query` must already be lowercased; `.` and `::` qualify names alike.
+    pub fn lexical_score(&self, query: &str) -> f32 {
+        let query = query.trim().replace('.', "::");
+        if query.is_empty() {
+            return 0.0;
+        }
+        let name = self.name.to_lowercase();
+        if name == query || self.qualified_name().to_lowercase() == query {
+            1.0
+        } else if name.contains(&query) || (name.len() >= 3 && query.contains(&name)) {
+            0.7
+        } else if self.signature.to_lowercase().contains(&query) {
+            0.5
+        } else if self
+            .doc
+            .as_ref()
+            .is_some_and(|doc| doc.to_lowercase().contains(&query))
+        {
+            0.4
+        } else {
+            0.0
+        }
+    }
+}
+
+/// A symbol returned by file search, with its location
+#[derive(Debug, Clone, Serialize)]
+pub struct SymbolMatch {
+    pub file_id: String,
+    pub path: String,
+    pub kind: SymbolKind,
+    pub name: String,
+    pub parent: Option<String>,
+    pub start_line: usize,
+    pub end_line: usize,
+    pub signature: String,
+    pub doc: Option<String>,
+    pub score: f32,
+}
+
+impl SymbolMatch {
+    pub fn new(file_id: String, path: String, symbol: CodeSymbol, score: f32) -> Self {
+        Self {
+            file_id,
+            path,
+            kind: symbol.kind,
+            name: symbol.name,
+            parent: symbol.parent,
+            start_line: symbol.start_line,
+            end_line: symbol.end_line,
+            signature: symbol.signature,
+            doc: symbol.doc,
+            score,
+        }
+    }
+}
+
+/// Extract the symbols declared in a file, in declaration order
+pub fn extract_symbols(content: &str, file_type: &FileType) -> Vec<CodeSymbol> {
+    let mut symbols = match file_type {
+        FileType::Rust => extract_braced(content, Syntax::Rust),
+        FileType::TypeScript | FileType::JavaScript => extract_braced(content, Syntax::Script),
+        FileType::Go => extract_braced(content, Syntax::Go),
+        FileType::Python => extract_python(content),
+        _ => Vec::new(),
+    };
+    symbols.truncate(MAX_SYMBOLS_PER_FILE);
+    symbols
+}
+
+/// Distinct symbol names, for [`FileMemory::key_items`](super::types::FileMemory)
+pub fn key_items(symbols: &[CodeSymbol]) -> Vec<String> {
+    let mut items: Vec<String> = Vec::new();
+    for symbol in symbols {
+        if items.len() == MAX_KEY_ITEMS {
+            break;
+        }
+        if !items.contains(&symbol.name) {
+            items.push(symbol.name.clone());
+        }
+    }
+    items
+}
+
+// =============================================================================
+// SCOPE RESOLUTION
+// =============================================================================
+
+/// A declaration before its enclosing scope is known (0-based lines)
+struct RawSymbol {
+    kind: SymbolKind,
+    name: String,
+    start_line: usize,
+    end_line: usize,
+    signature: String,
+    doc: Option<String>,
+    /// Parent named by the declaration itself (Go method receivers)
+    explicit_parent: Option<String>,
+    /// Name members of this symbol are qualified with (an impl's self type)
+    scope_name: String,
+    /// Only a symbol when declared directly in a class or interface body
+    member_only: bool,
+    /// Brace depth at the declaration (brace languages only)
+    depth: Option<usize>,
+}
+
+/// Nest symbols by span, name their parents, and drop declarations that
+/// turned out not to be symbols (locals, calls that looked like methods)
+fn resolve_scopes(raw: Vec<RawSymbol>) -> Vec<CodeSymbol> {
+    let mut direct_parent: Vec<Option<usize>> = Vec::with_capacity(raw.len());
+    let mut stack: Vec<usize> = Vec::new();
+    for (i, symbol) in raw.iter().enumerate() {
+        while let Some(&top) = stack.last() {
+            if raw[top].start_line < symbol.start_line && symbol.end_line <= raw[top].end_line {
+                break;
+            }
+            stack.pop();
+        }
+        direct_parent.push(stack.last().copied());
+        stack.push(i);
+    }
+
+    let mut kept = vec![false; raw.len()];
+    let mut symbols = Vec::new();
+    for (i, symbol) in raw.iter().enumerate() {
+        // Discarded candidates don't count as scopes
+        let mut scope = direct_parent[i];
+        while let Some(p) = scope {
+            if kept[p] {
+                break;
+            }
+            scope = direct_parent[p];
+        }
+        let scope = scope.map(|p| &raw[p]);
+
+        // Declarations in anonymous blocks (closures, callbacks, control flow) are locals
+        if let Some(depth) = symbol.depth {
+            let expected = scope.and_then(|s| s.depth).map_or(0, |d| d + 1);
+            if depth != expected {
+                continue;
+            }
+        }
+        let keep = match scope {
+            _ if symbol.member_only => {
+                scope.is_some_and(|s| matches!(s.kind, SymbolKind::Class | SymbolKind::Interface))
+            }
+            Some(s) if matches!(symbol.kind, SymbolKind::Const | SymbolKind::Variable) => {
+                !s.kind.is_callable()
+            }
+            _ => true,
+        };
+        if !keep {
+            continue;
+        }
+        kept[i] = true;
+
+        let kind = match scope {
+            Some(s) if symbol.kind == SymbolKind::Function && s.kind.has_methods() => {
+                SymbolKind::Method
+            }
+            _ => symbol.kind,
+        };
+        symbols.push(CodeSymbol {
+            kind,
+            name: symbol.name.clone(),
+            start_line: symbol.start_line + 1,
+            end_line: symbol.end_line + 1,
+            parent: symbol
+                .explicit_parent
+                .clone()
+                .or_else(|| scope.map(|s| s.scope_name.clone())),
+            doc: symbol.doc.clone(),
+            signature: symbol.signature.clone(),
+            embedding: None,
+        });
+    }
+    symbols
+}
+
+// =============================================================================
+// BRACE LANGUAGES (RUST, TYPESCRIPT/JAVASCRIPT, GO)
+// =============================================================================
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Syntax {
+    Rust,
+    /// TypeScript and JavaScript
+    Script,
+    Go,
+}
+
+/// One source line with string contents removed and comments split off
+#[derive(Debug, Default)]
+struct SourceLine {
+    code: String,
+    comment: String,
+}
+
+static RUST_ITEM: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r#"^(?:pub(?:\s*\([^)]*\))?\s+)?(?:(?:default|const|async|unsafe|extern(?:\s*"")?)\s+)*(fn|struct|enum|union|trait|type|mod|const|static|macro_rules!)\s*(?:mut\s+)?([A-Za-z_][A-Za-z0-9_]*)"#,
+    )
+    .unwrap()
+});
+static RUST_IMPL: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^(?:unsafe\s+)?impl\b").unwrap());
+
+static SCRIPT_ITEM: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"^(?:export\s+)?(?:default\s+)?(?:declare\s+)?(?:abstract\s+)?(?:async\s+)?(?:(function)\s*\*?\s*|(class|interface|const\s+enum|enum|type|namespace|module)\s+)([A-Za-z_$][\w$]*)",
+    )
+    .unwrap()
+});
+static SCRIPT_BINDING: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"^(?:export\s+)?(?:declare\s+)?(const|let|var)\s+([A-Za-z_$][\w$]*)\s*(?::[^=]*)?=\s*(.*)$",
+    )
+    .unwrap()
+});
+static SCRIPT_METHOD: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"^(?:(?:public|private|protected|static|readonly|async|override|abstract|get|set)\s+)*\*?\s*(#?[A-Za-z_$][\w$]*)\s*\??\s*(?:<[^>]*>)?\s*\(",
+    )
+    .unwrap()
+});
+static SCRIPT_ARROW_FIELD: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"^(?:(?:public|private|protected|static|readonly)\s+)*(#?[A-Za-z_$][\w$]*)\s*(?::[^=]*)?=\s*(?:async\s*)?(?:\([^)]*\)|[A-Za-z_$][\w$]*)\s*(?::[^=]*)?=>",
+    )
+    .unwrap()
+});
+/// Words that look like a method name in `word(` but start statements
+const SCRIPT_KEYWORDS: &[&str] = &[
+    "if", "for", "while", "switch", "catch", "return", "function", "new", "await", "typeof",
+    "else", "do", "with", "throw", "yield", "delete", "void", "super", "import", "export",
+];
+
+static GO_FUNC: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^func\s*(?:\(([^)]*)\))?\s*([A-Za-z_]\w*)").unwrap());
+static GO_TYPE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^type\s+([A-Za-z_]\w*)(?:\[[^\]]*\])?\s*(?:=\s*)?(struct|interface)?\b").unwrap()
+});
+static GO_VALUE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^(const|var)\s+([A-Za-z_]\w*)").unwrap());
+
+/// A declaration matched on one line
+struct Declaration {
+    kind: SymbolKind,
+    name: String,
+    explicit_parent: Option<String>,
+    scope_name: Option<String>,
+    member_only: bool,
+}
+
+impl Declaration {
+    fn new(kind: SymbolKind, name: &str) -> Self {
+        Self {
+            kind,
+            name: name.to_string(),
+            explicit_parent: None,
+            scope_name: None,
+            member_only: false,
+        }
+    }
+}
+
+fn extract_braced(content: &str, syntax: Syntax) -> Vec<CodeSymbol> {
+    let lines = lex(content, syntax);
+
+    // All code as one buffer so spans can cross lines
+    let mut text: Vec<char> = Vec::with_capacity(content.len());
+    let mut line_starts = Vec::with_capacity(lines.len());
+    for line in &lines {
+        line_starts.push(text.len());
+        text.extend(line.code.chars());
+        text.push('\n');
+    }
+    let line_of = |offset: usize| line_starts.partition_point(|&start| start <= offset) - 1;
+
+    let mut depth = 0usize;
+    let mut line_depths = Vec::with_capacity(lines.len());
+    for line in &lines {
+        line_depths.push(depth);
+        for c in line.code.chars() {
+            match c {
+                '{' => depth += 1,
+                '}' => depth = depth.saturating_sub(1),
+                _ => {}
+            }
+        }
+    }
+
+    let mut raw = Vec::new();
+    for (index, line) in lines.iter().enumerate() {
+        let code = line.code.trim();
+        if code.is_empty() {
+            continue;
+        }
+        let Some(declaration) = parse_declaration(code, syntax) else {
+            continue;
+        };
+
+        let indent = line.code.chars().take_while(|c| c.is_whitespace()).count();
+        let start = line_starts[index] + indent;
+        let (signature_end, end) = scan_declaration(&text, start, syntax);
+        let signature: String = text[start..signature_end].iter().collect();
+
+        raw.push(RawSymbol {
+            kind: declaration.kind,
+            scope_name: declaration
+                .scope_name
+                .unwrap_or_else(|| declaration.name.clone()),
+            name: declaration.name,
+            start_line: index,
+            end_line: line_of(end).max(index),
+            signature: truncate_chars(&collapse_whitespace(&signature), MAX_SIGNATURE_CHARS),
+            doc: doc_comment_before(&lines, index, syntax),
+            explicit_parent: declaration.explicit_parent,
+            member_only: declaration.member_only,
+            depth: Some(line_depths[index]),
+        });
+        if raw.len() == MAX_CANDIDATES_PER_FILE {
+            break;
+        }
+    }
+
+    resolve_scopes(raw)
+}
+
+fn parse_declaration(code: &str, syntax: Syntax) -> Option<Declaration> {
+    match syntax {
+        Syntax::Rust => parse_rust(code),
+        Syntax::Script => parse_script(code),
+        Syntax::Go => parse_go(code),
+    }
+}
+
+fn parse_rust(code: &str) -> Option<Declaration> {
+    if let Some(caps) = RUST_ITEM.captures(code) {
+        let kind = match &caps[1] {
+            "fn" => SymbolKind::Function,
+            "struct" | "union" => SymbolKind::Struct,
+            "enum" => SymbolKind::Enum,
+            "trait" => SymbolKind::Trait,
+            "type" => SymbolKind::TypeAlias,
+            "mod" => SymbolKind::Module,
+            "const" | "static" => SymbolKind::Const,
+            _ => SymbolKind::Macro,
+        };
+        return Some(Declaration::new(kind, &caps[2]));
+    }
+
+    let matched = RUST_IMPL.find(code)?;
+    let mut rest = code[matched.end()..].trim_start();
+    if rest.starts_with('<') {
+        rest = &rest[closing_angle(rest)?..];
+    }
+    let header = rest
+        .split('{')
+        .next()
+        .unwrap_or(rest)
+        .split(" where")
+        .next()
+        .unwrap_or(rest);
+    let header = collapse_whitespace(header);
+    if header.is_empty() {
+        return None;
+    }
+
+    let self_type = header.rsplit(" for ").next().unwrap_or(&header);
+    let mut declaration = Declaration::new(SymbolKind::Impl, &header);
+    declaration.scope_name = Some(base_type_name(self_type));
+    Some(declaration)
+}
+
+fn parse_script(code: &str) -> Option<Declaration> {
+    if let Some(caps) = SCRIPT_ITEM.captures(code) {
+        let name = &caps[3];
+        let kind = match caps.get(1).or_else(|| caps.get(2)).map(|m| m.as_str()) {
+            Some("function") => SymbolKind::Function,
+            Some("class") => SymbolKind::Class,
+            Some("interface") => SymbolKind::Interface,
+            Some("type") => {
+                // `type` is also an ordinary identifier
+                let after = code[caps.get(0)?.end()..].trim_start();
+                if !after.starts_with('=') && !after.starts_with('<') {
+                    return None;
+                }
+                SymbolKind::TypeAlias
+            }
+            Some("namespace") | Some("module") => SymbolKind::Module,
+            _ => SymbolKind::Enum,
+        };
+        return Some(Declaration::new(kind, name));
+    }
+
+    if let Some(caps) = SCRIPT_BINDING.captures(code) {
+        let value = caps[3].trim_start();
+        let kind = if value.starts_with("function")
+            || value.starts_with("async")
+            || value.contains("=>")
+        {
+            SymbolKind::Function
+        } else if &caps[1] == "const" {
+            SymbolKind::Const
+        } else {
+            return None;
+        };
+        return Some(Declaration::new(kind, &caps[2]));
+    }
+
+    let caps = SCRIPT_METHOD
+        .captures(code)
+        .or_else(|| SCRIPT_ARROW_FIELD.captures(code))?;
+    let name = &caps[1];
+    if SCRIPT_KEYWORDS.contains(&name) {
+        return None;
+    }
+    let mut declaration = Declaration::new(SymbolKind::Method, name);
+    declaration.member_only = true;
+    Some(declaration)
+}
+
+fn parse_go(code: &str) -> Option<Declaration> {
+    if let Some(caps) = GO_FUNC.captures(code) {
+        return Some(match caps.get(1) {
+            Some(receiver) => {
+                let receiver_type = receiver.as_str().split_whitespace().last()?;
+                let mut declaration = Declaration::new(SymbolKind::Method, &caps[2]);
+                declaration.explicit_parent = Some(base_type_name(receiver_type));
+                declaration
+            }
+            None => Declaration::new(SymbolKind::Function, &caps[2]),
+        });
+    }
+    if let Some(caps) = GO_TYPE.captures(code) {
+        let kind = match caps.get(2).map(|m| m.as_str()) {
+            Some("struct") => SymbolKind::Struct,
+            Some("interface") => SymbolKind::Interface,
+            _ => SymbolKind::TypeAlias,
+        };
+        return Some(Declaration::new(kind, &caps[1]));
+    }
+    let caps = GO_VALUE.captures(code)?;
+    let kind = if &caps[1] == "const" {
+        SymbolKind::Const
+    } else {
+        SymbolKind::Variable
+    };
+    Some(Declaration::new(kind, &caps[2]))
+}
+
+/// `&mut crate::Foo<T>` / `*Foo[T]` -> `Foo`
+fn base_type_name(type_text: &str) -> String {
+    let mut name = type_text.trim();
+    for prefix in ["&", "*", "mut ", "dyn "] {
+        name = name.trim_start_matches(prefix).trim_start();
+    }
+    let name = name.split(['<', '[']).next().unwrap_or(name);
+    name.rsplit("::").next().unwrap_or(name).trim().to_string()
+}
+
+/// Byte offset just past the `>` closing a leading `<`
+fn closing_angle(text: &str) -> Option<usize> {
+    let mut depth = 0usize;
+    for (offset, c) in text.char_indices() {
+        match c {
+            '<' => depth += 1,
+            '>' => {
+                depth = depth.checked_sub(1)?;
+                if depth == 0 {
+                    return Some(offset + 1);
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Find where a declaration's signature ends and where the declaration ends
+///
+/// The signature runs to the first `{` or `;` outside parentheses and
+/// brackets; a `{` opens the body, which ends at its matching brace. Outside
+/// Rust a line break also ends a declaration unless the line is obviously
+/// continued. Returns char offsets into `text`.
+fn scan_declaration(text: &[char], start: usize, syntax: Syntax) -> (usize, usize) {
+    let mut nesting = 0i32;
+    for (i, &c) in text.iter().enumerate().skip(start) {
+        match c {
+            '(' | '[' => nesting += 1,
+            ')' | ']' => nesting -= 1,
+            '{' if nesting <= 0 => return (i, matching_brace(text, i)),
+            ';' if nesting <= 0 => return (i, i),
+            '\n' if nesting <= 0 && syntax != Syntax::Rust && !line_continues(&text[start..i]) => {
+                return (i, i)
+            }
+            _ => {}
+        }
+    }
+    (text.len(), text.len().saturating_sub(1))
+}
+
+fn line_continues(text: &[char]) -> bool {
+    text.iter()
+        .rev()
+        .find(|c| !c.is_whitespace())
+        .is_some_and(|c| "=,(:|&>.?+-*".contains(*c))
+}
+
+fn matching_brace(text: &[char], open: usize) -> usize {
+    let mut depth = 0usize;
+    for (i, &c) in text.iter().enumerate().skip(open) {
+        match c {
+            '{' => depth += 1,
+            '}' => {
+                depth -= 1;
+                if depth == 0 {
+                    return i;
+                }
+            }
+            _ => {}
+        }
+    }
+    text.len().saturating_sub(1)
+}
+
+/// The doc comment directly above a declaration, skipping attributes and decorators
+///
+/// Rust only counts `///` and `/** */` comments.
+fn doc_comment_before(lines: &[SourceLine], line: usize, syntax: Syntax) -> Option<String> {
+    let mut comments = Vec::new();
+    for previous in lines[..line].iter().rev() {
+        let code = previous.code.trim();
+        let comment = previous.comment.trim();
+        if code.is_empty() && comment.is_empty() {
+            break;
+        }
+        if !code.is_empty() {
+            if code.starts_with("#[") || code.starts_with('@') {
+                continue;
+            }
+            break;
+        }
+        comments.push(comment);
+    }
+    comments.reverse();
+
+    if syntax == Syntax::Rust
+        && !comments
+            .first()
+            .is_some_and(|c| c.starts_with("///") || c.starts_with("/**"))
+    {
+        return None;
+    }
+
+    let text = comments
+        .iter()
+        .map(|comment| {
+            let mut text = *comment;
+            for marker in ["///", "//!", "//", "/**", "/*"] {
+                if let Some(rest) = text.strip_prefix(marker) {
+                    text = rest;
+                    break;
+                }
+            }
+            let text = text.trim_end().trim_end_matches("*/").trim();
+            text.strip_prefix('*').unwrap_or(text).trim()
+        })
+        .filter(|text| !text.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ");
+    (!text.is_empty()).then(|| truncate_chars(&text, MAX_DOC_CHARS))
+}
+
+/// Split source into lines of code (string contents removed) and comment text
+fn lex(content: &str, syntax: Syntax) -> Vec<SourceLine> {
+    #[derive(Clone, Copy)]
+    enum State {
+        Code,
+        LineComment,
+        /// Nesting depth (Rust block comments nest)
+        BlockComment(u32),
+        Str(char),
+        /// Rust raw string with this many `#`s
+        RawStr(usize),
+    }
+
+    let chars: Vec<char> = content.chars().collect();
+    let mut lines = vec![SourceLine::default()];
+    let mut state = State::Code;
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        let next = chars.get(i + 1).copied();
+        if c == '\n' {
+            if matches!(state, State::LineComment) {
+                state = State::Code;
+            }
+            lines.push(SourceLine::default());
+            i += 1;
+            continue;
+        }
+
+        let line = lines.last_mut().expect("lines is never empty");
+        let mut step = 1;
+        match state {
+            State::Code => match c {
+                '/' if next == Some('/') => {
+                    state = State::LineComment;
+                    line.comment.push_str("//");
+                    step = 2;
+                }
+                '/' if next == Some('*') => {
+                    state = State::BlockComment(1);
+                    line.comment.push_str("/*");
+                    step = 2;
+                }
+                '"' => {
+                    state = State::Str(c);
+                    line.code.push(c);
+                }
+                '\'' | '`' if syntax != Syntax::Rust => {
+                    state = State::Str(c);
+                    line.code.push(c);
+                }
+                '\'' => match rust_char_literal_len(&chars[i..]) {
+                    // A char literal such as '{' must not count as a brace
+                    Some(len) => {
+                        line.code.push_str("' '");
+                        step = len;
+                    }
+                    // Lifetime
+                    None => line.code.push(c),
+                },
+                'r' if syntax == Syntax::Rust && (i == 0 || !is_ident_char(chars[i - 1])) => {
+                    let hashes = chars[i + 1..].iter().take_while(|&&h| h == '#').count();
+                    if chars.get(i + 1 + hashes) == Some(&'"') {
+                        state = State::RawStr(hashes);
+                        line.code.push_str("r\"");
+                        step = 2 + hashes;
+                    } else {
+                        line.code.push(c);
+                    }
+                }
+                _ => line.code.push(c),
+            },
+            State::LineComment => line.comment.push(c),
+            State::BlockComment(depth) => {
+                if c == '*' && next == Some('/') {
+                    line.comment.push_str("*/");
+                    state = if depth == 1 {
+                        State::Code
+                    } else {
+                        State::BlockComment(depth - 1)
+                    };
+                    step = 2;
+                } else if syntax == Syntax::Rust && c == '/' && next == Some('*') {
+                    line.comment.push_str("/*");
+                    state = State::BlockComment(depth + 1);
+                    step = 2;
+                } else {
+                    line.comment.push(c);
+                }
+            }
+            State::Str(quote) => {
+                let raw = syntax == Syntax::Go && quote == '`';
+                if c == '\\' && !raw {
+                    // Leave an escaped line break to the newline handling above
+                    step = if next == Some('\n') { 1 } else { 2 };
+                } else if c == quote {
+                    line.code.push(quote);
+                    state = State::Code;
+                }
+            }
+            State::RawStr(hashes) => {
+                if c == '"' && chars[i + 1..].iter().take(hashes).all(|&h| h == '#') {
+                    line.code.push('"');
+                    state = State::Code;
+                    step = 1 + hashes;
+                }
+            }
+        }
+        i += step;
+    }
+    lines
+}
+
+/// Length of a Rust char literal starting at `chars[0] == '\''`, or None for a lifetime
+fn rust_char_literal_len(chars: &[char]) -> Option<usize> {
+    if chars.get(1) == Some(&'\\') {
+        // Escapes: '\n', '\'', '\u{1F600}'
+        let close = chars.iter().skip(3).take(10).position(|&c| c == '\'')?;
+        return Some(close + 4);
+    }
+    (chars.get(2) == Some(&'\'') && chars.get(1) != Some(&'\'')).then_some(3)
+}
+
+fn is_ident_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+// =============================================================================
+// PYTHON
+// =============================================================================
+
+static PYTHON_DEF: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^(?:async\s+)?(def|class)\s+([A-Za-z_]\w*)").unwrap());
+
+/// One Python source line with string contents and comments removed
+#[derive(Debug, Default)]
+struct PythonLine {
+    code: String,
+    /// The line starts inside a multi-line string, so its indentation means nothing
+    in_string: bool,
+}
+
+fn extract_python(content: &str) -> Vec<CodeSymbol> {
+    let raw_lines: Vec<&str> = content.split('\n').collect();
+    let lines = lex_python(content);
+    let indent_of = |line: &PythonLine| line.code.chars().take_while(|c| c.is_whitespace()).count();
+
+    let mut raw = Vec::new();
+    for (index, line) in lines.iter().enumerate() {
+        if line.in_string {
+            continue;
+        }
+        let Some(caps) = PYTHON_DEF.captures(line.code.trim()) else {
+            continue;
+        };
+        let kind = if &caps[1] == "class" {
+            SymbolKind::Class
+        } else {
+            SymbolKind::Function
+        };
+        let indent = indent_of(line);
+
+        // The header ends at the first `:` outside brackets
+        let mut header = String::new();
+        let mut header_end = index;
+        let mut inline_body = false;
+        let mut nesting = 0i32;
+        'header: for (offset, header_line) in lines[index..].iter().enumerate() {
+            header_end = index + offset;
+            for (position, c) in header_line.code.char_indices() {
+                match c {
+                    '(' | '[' | '{' => nesting += 1,
+                    ')' | ']' | '}' => nesting -= 1,
+                    ':' if nesting <= 0 => {
+                        header.push_str(&header_line.code[..position]);
+                        inline_body = !header_line.code[position + 1..].trim().is_empty();
+                        break 'header;
+                    }
+                    _ => {}
+                }
+            }
+            header.push_str(&header_line.code);
+            header.push(' ');
+        }
+
+        // The body is everything indented deeper than the header
+        let mut end_line = header_end;
+        if !inline_body {
+            for (offset, body_line) in lines[header_end + 1..].iter().enumerate() {
+                if !body_line.in_string {
+                    if body_line.code.trim().is_empty() {
+                        continue;
+                    }
+                    if indent_of(body_line) <= indent {
+                        break;
+                    }
+                }
+                end_line = header_end + 1 + offset;
+            }
+        }
+
+        raw.push(RawSymbol {
+            kind,
+            name: caps[2].to_string(),
+            start_line: index,
+            end_line,
+            signature: truncate_chars(&collapse_whitespace(&header), MAX_SIGNATURE_CHARS),
+            doc: (!inline_body)
+                .then(|| docstring_after(&raw_lines, header_end))
+                .flatten(),
+            explicit_parent: None,
+            scope_name: caps[2].to_string(),
+            member_only: false,
+            depth: None,
+        });
+        if raw.len() == MAX_CANDIDATES_PER_FILE {
+            break;
+        }
+    }
+
+    resolve_scopes(raw)
+}
+
+/// The docstring opening the body that follows `header_end`
+fn docstring_after(raw_lines: &[&str], header_end: usize) -> Option<String> {
+    let (offset, first) = raw_lines
+        .get(header_end + 1..)?
+        .iter()
+        .enumerate()
+        .find(|(_, line)| !line.trim().is_empty())?;
+    let first = first
+        .trim()
+        .trim_start_matches(['r', 'R', 'u', 'U', 'b', 'B']);
+
+    let delimiter = ["\"\"\"", "'''", "\"", "'"]
+        .into_iter()
+        .find(|d| first.starts_with(d))?;
+    let first = &first[delimiter.len()..];
+
+    let mut parts = Vec::new();
+    if let Some(close) = first.find(delimiter) {
+        parts.push(&first[..close]);
+    } else if delimiter.len() == 3 {
+        parts.push(first);
+        for line in &raw_lines[header_end + 2 + offset..] {
+            if let Some(close) = line.find(delimiter) {
+                parts.push(&line[..close]);
+                break;
+            }
+            parts.push(line);
+        }
+    }
+
+    let text = parts
+        .iter()
+        .map(|part| part.trim())
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ");
+    (!text.is_empty()).then(|| truncate_chars(&text, MAX_DOC_CHARS))
+}
+
+/// Split Python source into lines with strings blanked and comments dropped
+fn lex_python(content: &str) -> Vec<PythonLine> {
+    let mut lines = vec![PythonLine::default()];
+    // Open string: quote char and whether it is triple-quoted
+    let mut string: Option<(char, bool)> = None;
+    let mut comment = false;
+    let chars: Vec<char> = content.chars().collect();
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        if c == '\n' {
+            comment = false;
+            if matches!(string, Some((_, false))) {
+                // Unterminated single-line string
+                string = None;
+            }
+            lines.push(PythonLine {
+                code: String::new(),
+                in_string: string.is_some(),
+            });
+            i += 1;
+            continue;
+        }
+
+        let line = lines.last_mut().expect("lines is never empty");
+        let triple =
+            |quote: char| chars.get(i + 1) == Some(&quote) && chars.get(i + 2) == Some(&quote);
+        let mut step = 1;
+        match string {
+            _ if comment => {}
+            None => match c {
+                '#' => comment = true,
+                '"' | '\'' => {
+                    let is_triple = triple(c);
+                    string = Some((c, is_triple));
+                    line.code.push(c);
+                    if is_triple {
+                        step = 3;
+                    }
+                }
+                _ => line.code.push(c),
+            },
+            Some((quote, is_triple)) => {
+                if c == '\\' {
+                    step = if chars.get(i + 1) == Some(&'\n') {
+                        1
+                    } else {
+                        2
+                    };
+                } else if c == quote && (!is_triple || triple(c)) {
+                    string = None;
+                    line.code.push(quote);
+                    if is_triple {
+                        step = 3;
+                    }
+                }
+            }
+        }
+        i += step;
+    }
+    lines
+}
+
+// =============================================================================
+// HELPERS
+// =============================================================================
+
+fn collapse_whitespace(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+fn truncate_chars(text: &str, max_chars: usize) -> String {
+    match text.char_indices().nth(max_chars) {
+        Some((end, _)) => format!("{}...", &text[..end]),
+        None => text.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn find<'a>(symbols: &'a [CodeSymbol], name: &str) -> &'a CodeSymbol {
+        symbols
+            .iter()
+            .find(|s| s.name == name)
+            .unwrap_or_else(|| panic!("no symbol {name} in {symbols:#?}"))
+    }
+
+    #[test]
+    fn test_rust_symbols() {
+        let source = r#"//! Module docs
+
+/// A configuration.
+#[derive(Debug)]
+pub struct Config {
+    name: String,
+}
+
+impl Config {
+    /// Build one.
+    pub fn new(name: &str) -> Self {
+        let brace = '{';
+        let text = "fn not_a_symbol() {";
+        Self { name: name.to_string() }
+    }
+
+    fn private_helper(&self) {}
+}
+
+impl<T: Into<String>> From<T> for Config {
+    fn from(value: T) -> Self {
+        Config::new(&value.into())
+    }
+}
+
+trait Named {
+    fn name(&self) -> &str;
+}
+
+const LIMIT: usize = 3;
+"#;
+        let symbols = extract_symbols(source, &FileType::Rust);
+
+        let config = find(&symbols, "Config");
+        assert_eq!(config.kind, SymbolKind::Struct);
+        assert_eq!((config.start_line, config.end_line), (5, 7));
+        assert_eq!(config.doc.as_deref(), Some("A configuration."));
+
+        let new = find(&symbols, "new");
+        assert_eq!(new.kind, SymbolKind::Method);
+        assert_eq!(new.parent.as_deref(), Some("Config"));
+        assert_eq!((new.start_line, new.end_line), (11, 15));
+        assert_eq!(new.signature, "pub fn new(name: &str) -> Self");
+        assert_eq!(new.doc.as_deref(), Some("Build one."));
+
+        assert_eq!(find(&symbols, "private_helper").kind, SymbolKind::Method);
+        assert!(!symbols.iter().any(|s| s.name == "not_a_symbol"));
+        assert!(!symbols.iter().any(|s| s.name == "brace"));
+
+        let from_impl = find(&symbols, "From<T> for Config");
+        assert_eq!(from_impl.kind, SymbolKind::Impl);
+        assert_eq!(find(&symbols, "from").parent.as_deref(), Some("Config"));
+
+        let name = find(&symbols, "name");
+        assert_eq!((name.kind, name.end_line), (SymbolKind::Method, 27));
+        assert_eq!(name.parent.as_deref(), Some("Named"));
+        assert_eq!(find(&symbols, "LIMIT").kind, SymbolKind::Const);
+    }
+
+    #[test]
+    fn test_python_symbols() {
+        let source = r#"
+class DataProcessor(Base):
+    """Processes data.
+
+    Longer description.
+    """
+
+    def process(self, data,
+                strict=False):
+        text = """
+def not_a_symbol():
+"""
+        return data
+
+    async def fetch(self): pass
+
+def helper(x):
+    # def commented_out():
+    return x
+"#;
+        let symbols = extract_symbols(source, &FileType::Python);
+        assert_eq!(symbols.len(), 4, "{symbols:#?}");
+
+        let class = find(&symbols, "DataProcessor");
+        assert_eq!(
+            (class.kind, class.start_line, class.end_line),
+            (SymbolKind::Class, 2, 15)
+        );
+        assert_eq!(
+            class.doc.as_deref(),
+            Some("Processes data. Longer description.")
+        );
+
+        let process = find(&symbols, "process");
+        assert_eq!(process.kind, SymbolKind::Method);
+        assert_eq!(process.parent.as_deref(), Some("DataProcessor"));
+        assert_eq!((process.start_line, process.end_line), (8, 13));
+        assert_eq!(process.signature, "def process(self, data, strict=False)");
+
+        assert_eq!(find(&symbols, "fetch").end_line, 15);
+        let helper = find(&symbols, "helper");
+        assert_eq!(
+            (helper.kind, helper.parent.as_deref()),
+            (SymbolKind::Function, None)
+        );
+    }
+
+    #[test]
+    fn test_typescript_symbols() {
+        let source = r#"
+/** Manages users. */
+export class UserService {
+    private users = new Map();
+
+    constructor() {
+        this.users.clear();
+    }
+
+    public async addUser(user: User): Promise<void> {
+        if (user.id) {
+            validate(user);
+        }
+    }
+
+    handle = (event: Event) => {
+        console.log(event);
+    };
+}
+
+export interface User {
+    id: string;
+    rename(name: string): void;
+}
+
+export const validate = (user: User) => user.id.length > 0;
+const LIMIT = 10;
+type Id = string;
+"#;
+        let symbols = extract_symbols(source, &FileType::TypeScript);
+
+        let class = find(&symbols, "UserService");
+        assert_eq!((class.kind, class.end_line), (SymbolKind::Class, 19));
+        assert_eq!(class.doc.as_deref(), Some("Manages users."));
+
+        for method in ["constructor", "addUser", "handle"] {
+            let symbol = find(&symbols, method);
+            assert_eq!(symbol.kind, SymbolKind::Method, "{method}");
+            assert_eq!(symbol.parent.as_deref(), Some("UserService"));
+        }
+        assert_eq!(find(&symbols, "addUser").end_line, 14);
+        assert_eq!(find(&symbols, "rename").parent.as_deref(), Some("User"));
+        assert!(!symbols
+            .iter()
+            .any(|s| s.name == "validate" && s.parent.is_some()));
+        assert_eq!(find(&symbols, "validate").kind, SymbolKind::Function);
+        assert_eq!(find(&symbols, "LIMIT").kind, SymbolKind::Const);
+        assert_eq!(find(&symbols, "Id").kind, SymbolKind::TypeAlias);
+    }
+
+    #[test]
+    fn test_go_symbols() {
+        let source = r#"package main
+
+// Server serves requests.
+type Server struct {
+    Port int
+}
+
+// Start runs the server.
+func (s *Server) Start() error {
+    go func() {
+        run()
+    }()
+    return nil
+}
+
+func NewServer(port int) *Server {
+    const retries = 3
+    return &Server{Port: port}
+}
+
+type Handler interface {
+    Handle(req Request) Response
+}
+"#;
+        let symbols = extract_symbols(source, &FileType::Go);
+
+        let server = find(&symbols, "Server");
+        assert_eq!(
+            (server.kind, server.start_line, server.end_line),
+            (SymbolKind::Struct, 4, 6)
+        );
+        assert_eq!(server.doc.as_deref(), Some("Server serves requests."));
+
+        let start = find(&symbols, "Start");
+        assert_eq!(start.kind, SymbolKind::Method);
+        assert_eq!(start.parent.as_deref(), Some("Server"));
+        assert_eq!((start.start_line, start.end_line), (9, 14));
+
+        assert_eq!(find(&symbols, "NewServer").kind, SymbolKind::Function);
+        assert!(!symbols.iter().any(|s| s.name == "retries"));
+        assert_eq!(find(&symbols, "Handler").kind, SymbolKind::Interface);
+    }
+
+    #[test]
+    fn test_lexical_score() {
+        let symbols = extract_symbols(
+            "impl Config {\n    fn load_file(&self) {}\n}\n",
+            &FileType::Rust,
+        );
+        let load = find(&symbols, "load_file");
+        assert_eq!(load.lexical_score("load_file"), 1.0);
+        assert_eq!(load.lexical_score("config.load_file"), 1.0);
+        assert_eq!(load.lexical_score("load"), 0.7);
+        assert_eq!(load.lexical_score("&self"), 0.5);
+        assert_eq!(load.lexical_score("unrelated"), 0.0);
+    }
+}
@@ -14,11 +14,11 @@
 use anyhow::Result;
 use chrono::{DateTime, Datelike, Duration, NaiveDate, Utc, Weekday};
 use rocksdb::{IteratorMode, DB};
-use rust_stemmers::{Algorithm, Stemmer};
 use serde::{Deserialize, Serialize};
 use std::collections::HashSet;
 use std::sync::Arc;
 
+use super::language::{stem_candidates, Language, TextStemmer};
 use super::types::MemoryId;
 
 /// Type of temporal event
@@ -181,16 +181,26 @@ impl TemporalFactStore {
     }
 
     /// Find facts by event keyword
+    ///
+    /// The keyword is stemmed in its detected language and in English, so
+    /// facts indexed before language detection are still found.
     pub fn find_by_event(
         &self,
         user_id: &str,
         event: &str,
         limit: usize,
     ) -> Result<Vec<TemporalFact>> {
-        let stemmer = Stemmer::create(Algorithm::English);
-        let stem = stemmer.stem(&event.to_lowercase()).to_string();
-        let prefix = format!("temporal_by_event:{}:{}:", user_id, stem);
-        self.find_by_prefix(&prefix, user_id, limit)
+        let event = event.to_lowercase();
+        let mut facts: Vec<TemporalFact> = Vec::new();
+        for stem in stem_candidates(&event, Language::detect(&event)) {
+            let prefix = format!("temporal_by_event:{}:{}:", user_id, stem);
+            for fact in self.find_by_prefix(&prefix, user_id, limit)? {
+                if facts.len() < limit && !facts.iter().any(|f| f.id == fact.id) {
+                    facts.push(fact);
+                }
+            }
+        }
+        Ok(facts)
     }
 
     /// Find facts matching entity AND event
@@ -205,10 +215,12 @@ impl TemporalFactStore {
         let entity_facts = self.find_by_entity(user_id, entity, 100)?;
 
         // Filter by event keywords
-        let stemmer = Stemmer::create(Algorithm::English);
         let event_stems: HashSet<String> = event_keywords
             .iter()
-            .map(|kw| stemmer.stem(&kw.to_lowercase()).to_string())
+            .flat_map(|kw| {
+                let kw = kw.to_lowercase();
+                stem_candidates(&kw, Language::detect(&kw))
+            })
             .collect();
 
         let mut matching: Vec<TemporalFact> = entity_facts
@@ -309,7 +321,7 @@ pub fn extract_temporal_facts(
     entities: &[String],
 ) -> Vec<TemporalFact> {
     let mut facts = Vec::new();
-    let stemmer = Stemmer::create(Algorithm::English);
+    let stemmer = Language::detect(content).stemmer();
 
     // Split into sentences
     let sentences: Vec<&str> = content
@@ -355,7 +367,7 @@ fn extract_planning_fact(
     entity: &str,
     memory_id: &MemoryId,
     conversation_date: DateTime<Utc>,
-    stemmer: &Stemmer,
+    stemmer: &TextStemmer,
 ) -> Option<TemporalFact> {
     let sentence_lower = sentence.to_lowercase();
 
@@ -392,7 +404,7 @@ fn extract_planning_fact(
     let event_stems: Vec<String> = event
         .split_whitespace()
         .filter(|w| w.len() > 2)
-        .map(|w| stemmer.stem(&w.to_lowercase()).to_string())
+        .map(|w| stemmer.stem(&w.to_lowercase()))
         .collect();
 
     Some(TemporalFact {
@@ -420,7 +432,7 @@ fn extract_occurred_fact(
     entity: &str,
     memory_id: &MemoryId,
     conversation_date: DateTime<Utc>,
-    stemmer: &Stemmer,
+    stemmer: &TextStemmer,
 ) -> Option<TemporalFact> {
     let sentence_lower = sentence.to_lowercase();
 
@@ -469,7 +481,7 @@ fn extract_occurred_fact(
     let event_stems: Vec<String> = event
         .split_whitespace()
         .filter(|w| w.len() > 2)
-        .map(|w| stemmer.stem(&w.to_lowercase()).to_string())
+        .map(|w| stemmer.stem(&w.to_lowercase()))
         .collect();
 
     Some(TemporalFact {
@@ -497,7 +509,7 @@ fn extract_historical_fact(
     entity: &str,
     memory_id: &MemoryId,
     conversation_date: DateTime<Utc>,
-    stemmer: &Stemmer,
+    stemmer: &TextStemmer,
 ) -> Option<TemporalFact> {
     let sentence_lower = sentence.to_lowercase();
 
@@ -520,7 +532,7 @@ fn extract_historical_fact(
     let event_stems: Vec<String> = event
         .split_whitespace()
         .filter(|w| w.len() > 2)
-        .map(|w| stemmer.stem(&w.to_lowercase()).to_string())
+        .map(|w| stemmer.stem(&w.to_lowercase()))
         .collect();
 
     Some(TemporalFact {
@@ -9,7 +9,7 @@
 use chrono::{DateTime, Datelike, Utc};
 use std::sync::OnceLock;
 
-use super::todos::{ProjectStats, UserTodoStats};
+use super::todos::{ProjectStats, TodoDependencyGraph, UserTodoStats};
 
 // Static regex for context extraction (compiled once)
 static CONTEXT_REGEX: OnceLock<regex::Regex> = OnceLock::new();
@@ -17,7 +17,7 @@ static CONTEXT_REGEX: OnceLock<regex::Regex> = OnceLock::new();
 fn get_context_regex() -> &'static regex::Regex {
     CONTEXT_REGEX.get_or_init(|| regex::Regex::new(r"@(\w+)").unwrap())
 }
-use super::types::{Project, ProjectStatus, Todo, TodoStatus};
+use super::types::{Project, ProjectStatus, Todo, TodoId, TodoStatus};
 
 /// Width of formatted output
 const LINE_WIDTH: usize = 70;
@@ -61,6 +61,15 @@ pub fn format_todo_line(todo: &Todo, project_name: Option<&str>, show_meta: bool
             }
         }
 
+        if !todo.blocked_by.is_empty() && !todo.status.is_finished() {
+            let count = todo.blocked_by.len();
+            meta.push(format!(
+                "Depends on {} todo{}",
+                count,
+                if count == 1 { "" } else { "s" }
+            ));
+        }
+
         if !meta.is_empty() {
             line.push_str(&format!("\n                  {}", meta.join(" · ")));
         }
@@ -296,7 +305,7 @@ pub fn format_todo_created(todo: &Todo, project_name: Option<&str>) -> String {
 }
 
 /// Format completed todo confirmation
-pub fn format_todo_completed(todo: &Todo, next: Option<&Todo>) -> String {
+pub fn format_todo_completed(todo: &Todo, next: Option<&Todo>, unblocked: &[Todo]) -> String {
     let duration = todo
         .completed_at
         .and_then(|completed| {
@@ -337,6 +346,68 @@ pub fn format_todo_completed(todo: &Todo, next: Option<&Todo>) -> String {
         ));
     }
 
+    if !unblocked.is_empty() {
+        let ids: Vec<String> = unblocked.iter().map(|t| t.short_id()).collect();
+        output.push_str(&format!("\n\n  → Unblocked: {}", ids.join(", ")));
+    }
+
+    output
+}
+
+/// Format a project's dependency graph as blocker → dependent lines
+pub fn format_dependency_graph(project: &Project, graph: &TodoDependencyGraph) -> String {
+    let mut output = format!("{} · Dependencies\n", project.name);
+    output.push_str(&"─".repeat(LINE_WIDTH));
+    output.push('\n');
+
+    if graph.edges.is_empty() {
+        output.push_str("  No dependencies between todos.\n");
+        return output;
+    }
+
+    let label = |id: &TodoId| {
+        graph
+            .todos
+            .iter()
+            .find(|t| t.id == *id)
+            .map(|t| format!("{} {}", t.status.icon(), t.short_id()))
+            .unwrap_or_else(|| id.short())
+    };
+    for edge in &graph.edges {
+        output.push_str(&format!(
+            "  {}  →  {}\n",
+            label(&edge.blocker),
+            label(&edge.dependent)
+        ));
+    }
+
+    output
+}
+
+/// Format a critical path, first todo to start at the top
+pub fn format_critical_path(project: &Project, path: &[Todo]) -> String {
+    let mut output = format!(
+        "{} · Critical path{:>width$} steps\n",
+        project.name,
+        path.len(),
+        width = LINE_WIDTH.saturating_sub(project.name.len() + 16)
+    );
+    output.push_str(&"─".repeat(LINE_WIDTH));
+    output.push('\n');
+
+    if path.is_empty() {
+        output.push_str("  No open todos.\n");
+        return output;
+    }
+
+    for (i, todo) in path.iter().enumerate() {
+        if i > 0 {
+            output.push_str("      ↓\n");
+        }
+        output.push_str(&format_todo_line(todo, None, false));
+        output.push('\n');
+    }
+
     output
 }
 
@@ -8,14 +8,16 @@
 //! - Project grouping
 //! - Recurring tasks with automatic next instance creation
 //! - Due date tracking with overdue detection
-//! - Vector embeddings for semantic search (MiniLM-L6-v2)
+//! - Blocked-by dependencies with cycle detection, automatic unblocking
+//!   and critical path analysis
+//! - Vector embeddings for semantic search (configured embedding backend)
 //! - Vamana HNSW index for fast similarity search
 
 use anyhow::{Context, Result};
 use chrono::Utc;
 use parking_lot::RwLock;
-use rocksdb::{Options, WriteBatch, DB};
-use std::collections::HashMap;
+use rocksdb::{IteratorMode, Options, WriteBatch, DB};
+use std::collections::{HashMap, HashSet};
 use std::path::Path;
 use std::sync::Arc;
 use uuid::Uuid;
@@ -24,11 +26,10 @@ use super::types::{
     Project, ProjectId, ProjectStatus, Todo, TodoComment, TodoCommentId, TodoCommentType, TodoId,
     TodoStatus,
 };
+use crate::embeddings::minilm::MINILM_DIMENSION;
+use crate::embeddings::{Embedder, EmbeddingFingerprint};
 use crate::vector_db::{VamanaConfig, VamanaIndex};
 
-/// Embedding dimension (MiniLM-L6-v2)
-const EMBEDDING_DIM: usize = 384;
-
 /// Migrate unpadded `due:{ts}:{uid}:{id}` keys to zero-padded `due:{:020}:{uid}:{id}` format.
 ///
 /// Prior versions wrote bare timestamps (e.g. `due:1739404800:user:uuid`), which break
@@ -85,6 +86,10 @@ pub struct TodoStore {
     storage_path: std::path::PathBuf,
     /// Mutex for atomic sequence number allocation (prevents TOCTOU race)
     seq_mutex: parking_lot::Mutex<()>,
+    /// Embedder that produced the indexed vectors (used for re-embedding)
+    embedder: Option<Arc<dyn Embedder>>,
+    /// Vector dimension of the per-user indices
+    dimension: usize,
 }
 
 impl TodoStore {
@@ -122,15 +127,92 @@ impl TodoStore {
             vector_indices: RwLock::new(HashMap::new()),
             storage_path: todos_path,
             seq_mutex: parking_lot::Mutex::new(()),
+            embedder: None,
+            dimension: MINILM_DIMENSION,
         })
     }
 
+    /// Use the given embedder's dimension for vector indices and re-embedding
+    pub fn with_embedder(mut self, embedder: Arc<dyn Embedder>) -> Self {
+        self.dimension = embedder.dimension();
+        self.embedder = Some(embedder);
+        self
+    }
+
+    /// Re-embed all todos if the indices were built by a different model
+    ///
+    /// Call after `load_vector_indices()`. Returns the number of todos re-embedded.
+    pub fn sync_embedding_model(&self) -> Result<usize> {
+        let Some(embedder) = self.embedder.clone() else {
+            return Ok(0);
+        };
+        let fingerprint = EmbeddingFingerprint::of(embedder.as_ref());
+        let has_data = || !self.vector_indices.read().is_empty();
+        if !fingerprint.requires_reembed(&self.storage_path, has_data)? {
+            fingerprint.save(&self.storage_path)?;
+            return Ok(0);
+        }
+
+        let count = self.reindex_all(true)?;
+        fingerprint.save(&self.storage_path)?;
+
+        tracing::info!(
+            todos = count,
+            model = %fingerprint.model_id,
+            "Re-embedded todos for new embedding model"
+        );
+        Ok(count)
+    }
+
+    /// Rebuild the per-user vector indices from the stored todos
+    ///
+    /// Needed after the todo databases are replaced underneath the store
+    /// (online restore). Stored embeddings are reused where present.
+    pub fn rebuild_vector_indices(&self) -> Result<usize> {
+        self.reindex_all(false)
+    }
+
+    /// Index every stored todo from scratch, encoding with the embedder when
+    /// `reencode` is set or a todo has no usable stored embedding
+    fn reindex_all(&self, reencode: bool) -> Result<usize> {
+        self.vector_indices.write().clear();
+        let mut count = 0;
+        for item in self.todo_db.iterator(IteratorMode::Start) {
+            let (key, value) = item?;
+            let Ok(mut todo) = serde_json::from_slice::<Todo>(&value) else {
+                continue;
+            };
+            let stored = todo
+                .embedding
+                .take()
+                .filter(|e| !reencode && e.len() == self.dimension);
+            let embedding = match (stored, &self.embedder) {
+                (Some(embedding), _) => embedding,
+                (None, Some(embedder)) => embedder.encode(&todo.embedding_text())?,
+                (None, None) => continue,
+            };
+            let vector_id = self.index_todo_embedding(&todo.user_id, &todo.id, &embedding)?;
+            self.store_vector_id_mapping(&todo.user_id, vector_id, &todo.id)?;
+            todo.embedding = Some(embedding);
+            self.todo_db.put(&key, serde_json::to_vec(&todo)?)?;
+            count += 1;
+        }
+
+        // Indices of users without todos would otherwise reload with stale vectors
+        let vectors_path = self.storage_path.join("vectors");
+        if vectors_path.exists() {
+            std::fs::remove_dir_all(&vectors_path)?;
+        }
+        self.save_vector_indices()?;
+        Ok(count)
+    }
+
     /// Get or create a Vamana vector index for a user
     fn get_or_create_index(&self, user_id: &str) -> Result<()> {
         let mut indices = self.vector_indices.write();
         if !indices.contains_key(user_id) {
             let config = VamanaConfig {
-                dimension: EMBEDDING_DIM,
+                dimension: self.dimension,
                 max_degree: 32,
                 search_list_size: 75,
                 alpha: 1.2,
@@ -237,7 +319,7 @@ impl TodoStore {
 
                 // Create a new index and load from disk
                 let config = VamanaConfig {
-                    dimension: EMBEDDING_DIM,
+                    dimension: self.dimension,
                     ..Default::default()
                 };
                 let mut index = VamanaIndex::new(config)?;
@@ -254,15 +336,20 @@ impl TodoStore {
     // SEQUENCE NUMBER MANAGEMENT
     // =========================================================================
 
-    /// Get the next sequence number for a project (or user if no project) and increment the counter
+    /// Index key of the sequence counter for a user's project (or standalone todos)
     /// Key format: "seq:{user_id}:{project_id}" or "seq:{user_id}:_standalone_" for todos without project
+    fn seq_key(user_id: &str, project_id: Option<&ProjectId>) -> String {
+        match project_id {
+            Some(pid) => format!("seq:{}:{}", user_id, pid.0),
+            None => format!("seq:{}:_standalone_", user_id),
+        }
+    }
+
+    /// Get the next sequence number for a project (or user if no project) and increment the counter
     fn next_seq_num(&self, user_id: &str, project_id: Option<&ProjectId>) -> Result<u32> {
         // Hold mutex to prevent TOCTOU race on concurrent seq_num allocation
         let _lock = self.seq_mutex.lock();
-        let key = match project_id {
-            Some(pid) => format!("seq:{}:{}", user_id, pid.0),
-            None => format!("seq:{}:_standalone_", user_id),
-        };
+        let key = Self::seq_key(user_id, project_id);
         let current = match self.index_db.get(key.as_bytes())? {
             Some(data) => {
                 if data.len() >= 4 {
@@ -334,6 +421,43 @@ impl TodoStore {
         Ok(todo_to_store)
     }
 
+    /// Import an exported todo, preserving its ID and sequence number
+    ///
+    /// Raises the sequence counter past the imported number so later todos
+    /// don't reuse it, and indexes the todo for semantic search (reusing the
+    /// stored embedding when it matches the current model's dimension).
+    pub fn import_todo(&self, mut todo: Todo) -> Result<Todo> {
+        if todo.seq_num > 0 {
+            let _lock = self.seq_mutex.lock();
+            let key = Self::seq_key(&todo.user_id, todo.project_id.as_ref());
+            let current = self
+                .index_db
+                .get(key.as_bytes())?
+                .filter(|data| data.len() >= 4)
+                .map_or(0, |data| {
+                    u32::from_le_bytes([data[0], data[1], data[2], data[3]])
+                });
+            if todo.seq_num > current {
+                self.index_db
+                    .put(key.as_bytes(), todo.seq_num.to_le_bytes())?;
+            }
+        }
+
+        let stored = todo.embedding.take().filter(|e| e.len() == self.dimension);
+        let embedding = match (stored, &self.embedder) {
+            (Some(embedding), _) => Some(embedding),
+            (None, Some(embedder)) => Some(embedder.encode(&todo.embedding_text())?),
+            (None, None) => None,
+        };
+        if let Some(embedding) = embedding {
+            let vector_id = self.index_todo_embedding(&todo.user_id, &todo.id, &embedding)?;
+            self.store_vector_id_mapping(&todo.user_id, vector_id, &todo.id)?;
+            todo.embedding = Some(embedding);
+        }
+
+        self.store_todo(&todo)
+    }
+
     /// Update todo indices
     fn update_todo_indices(&self, todo: &Todo) -> Result<()> {
         let mut batch = WriteBatch::default();
@@ -380,6 +504,12 @@ impl TodoStore {
             batch.put(parent_key.as_bytes(), todo.user_id.as_bytes());
         }
 
+        // Reverse dependency index (blocker -> dependents)
+        for blocker_id in &todo.blocked_by {
+            let blocks_key = format!("blocks:{}:{}", blocker_id.0, id_str);
+            batch.put(blocks_key.as_bytes(), todo.user_id.as_bytes());
+        }
+
         self.index_db
             .write(batch)
             .context("Failed to update todo indices")?;
@@ -426,6 +556,11 @@ impl TodoStore {
             batch.delete(parent_key.as_bytes());
         }
 
+        for blocker_id in &todo.blocked_by {
+            let blocks_key = format!("blocks:{}:{}", blocker_id.0, id_str);
+            batch.delete(blocks_key.as_bytes());
+        }
+
         self.index_db.write(batch)?;
         Ok(())
     }
@@ -530,6 +665,11 @@ impl TodoStore {
             // Cascade delete subtasks to prevent orphans
             let subtasks = self.list_subtasks(todo_id)?;
             for subtask in &subtasks {
+                self.release_dependents(user_id, subtask, true)?;
+                // Re-read: releasing a sibling's dependents may have rewritten it
+                let subtask = self
+                    .get_todo(&subtask.user_id, &subtask.id)?
+                    .unwrap_or_else(|| subtask.clone());
                 self.remove_todo_indices(&subtask)?;
                 let subtask_key = format!("{}:{}", subtask.user_id, subtask.id.0);
                 self.todo_db.delete(subtask_key.as_bytes())?;
@@ -540,6 +680,8 @@ impl TodoStore {
                 );
             }
 
+            self.release_dependents(user_id, &todo, true)?;
+            let todo = self.get_todo(user_id, todo_id)?.unwrap_or(todo);
             self.remove_todo_indices(&todo)?;
             self.todo_db.delete(key.as_bytes())?;
             tracing::debug!(
@@ -582,6 +724,300 @@ impl TodoStore {
         }
     }
 
+    // =========================================================================
+    // TODO DEPENDENCIES
+    // =========================================================================
+
+    /// List todos that declare `todo_id` in their `blocked_by`
+    pub fn list_dependents(&self, user_id: &str, todo_id: &TodoId) -> Result<Vec<Todo>> {
+        let prefix = format!("blocks:{}:", todo_id.0);
+        let mut todos = Vec::new();
+
+        let iter = self.index_db.prefix_iterator(prefix.as_bytes());
+
+        for item in iter {
+            let (key, value) = item?;
+            let key_str = String::from_utf8_lossy(&key);
+
+            if !key_str.starts_with(&prefix) {
+                break;
+            }
+            if value.as_ref() != user_id.as_bytes() {
+                continue;
+            }
+
+            let todo_id_str = key_str.strip_prefix(&prefix).unwrap_or("");
+            if let Ok(uuid) = Uuid::parse_str(todo_id_str) {
+                if let Some(todo) = self.get_todo(user_id, &TodoId(uuid))? {
+                    todos.push(todo);
+                }
+            }
+        }
+
+        Ok(todos)
+    }
+
+    /// Blockers of `todo` that are not yet Done or Cancelled
+    ///
+    /// Blockers that no longer exist are ignored.
+    pub fn open_blockers(&self, todo: &Todo) -> Result<Vec<Todo>> {
+        let mut blockers = Vec::new();
+        for blocker_id in &todo.blocked_by {
+            if let Some(blocker) = self.get_todo(&todo.user_id, blocker_id)? {
+                if !blocker.status.is_finished() {
+                    blockers.push(blocker);
+                }
+            }
+        }
+        Ok(blockers)
+    }
+
+    /// Check whether saving `pending` would introduce a dependency cycle
+    ///
+    /// `pending` holds todos whose `blocked_by` is about to change; they
+    /// override the stored versions. Returns the cycle as a chain of IDs
+    /// where each todo is blocked by the next, starting and ending with the
+    /// same todo.
+    pub fn find_dependency_cycle(
+        &self,
+        user_id: &str,
+        pending: &[&Todo],
+    ) -> Result<Option<Vec<TodoId>>> {
+        let mut edges: HashMap<TodoId, Vec<TodoId>> = self
+            .list_todos_for_user(user_id, None)?
+            .into_iter()
+            .map(|t| (t.id, t.blocked_by))
+            .collect();
+        for todo in pending {
+            edges.insert(todo.id.clone(), todo.blocked_by.clone());
+        }
+
+        // Iterative DFS; a node met again while still on the path closes a cycle
+        let mut finished: HashSet<TodoId> = HashSet::new();
+        for start in pending {
+            if finished.contains(&start.id) {
+                continue;
+            }
+            let mut path: Vec<TodoId> = vec![start.id.clone()];
+            let mut cursors: Vec<usize> = vec![0];
+            while let Some(node) = path.last().cloned() {
+                let cursor = cursors.last_mut().expect("cursor per path node");
+                let next = edges.get(&node).and_then(|deps| deps.get(*cursor)).cloned();
+                *cursor += 1;
+                match next {
+                    Some(dep) => {
+                        if let Some(pos) = path.iter().position(|id| *id == dep) {
+                            let mut cycle = path[pos..].to_vec();
+                            cycle.push(dep);
+                            return Ok(Some(cycle));
+                        }
+                        if !finished.contains(&dep) {
+                            path.push(dep);
+                            cursors.push(0);
+                        }
+                    }
+                    None => {
+                        finished.insert(node);
+                        path.pop();
+                        cursors.pop();
+                    }
+                }
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// Re-evaluate the dependents of a todo that was just completed or cancelled
+    ///
+    /// Each dependent gets an activity entry. Dependents with no open blockers
+    /// left move from Blocked back to Todo (unless they are also blocked on
+    /// something outside the todo list) and are returned.
+    pub fn unblock_dependents(&self, user_id: &str, finished: &Todo) -> Result<Vec<Todo>> {
+        self.release_dependents(user_id, finished, false)
+    }
+
+    /// Shared by completion and deletion; `detach` also drops the link
+    fn release_dependents(&self, user_id: &str, blocker: &Todo, detach: bool) -> Result<Vec<Todo>> {
+        let event = if detach {
+            "deleted"
+        } else if blocker.status == TodoStatus::Cancelled {
+            "cancelled"
+        } else {
+            "completed"
+        };
+
+        let mut unblocked = Vec::new();
+        for mut dependent in self.list_dependents(user_id, &blocker.id)? {
+            if detach {
+                dependent.blocked_by.retain(|id| *id != blocker.id);
+            }
+            if dependent.status.is_finished() {
+                if detach {
+                    self.update_todo(&dependent)?;
+                }
+                continue;
+            }
+
+            let waiting_on: Vec<String> = self
+                .open_blockers(&dependent)?
+                .iter()
+                .filter(|t| t.id != blocker.id)
+                .map(|t| t.short_id())
+                .collect();
+
+            dependent.sync_blocked_status(!waiting_on.is_empty());
+            if waiting_on.is_empty() {
+                dependent.add_activity(format!("Unblocked: {} {}", blocker.short_id(), event));
+                unblocked.push(dependent.clone());
+            } else {
+                dependent.add_activity(format!(
+                    "{} {}, still waiting on {}",
+                    blocker.short_id(),
+                    event,
+                    waiting_on.join(", ")
+                ));
+            }
+            self.update_todo(&dependent)?;
+        }
+
+        Ok(unblocked)
+    }
+
+    /// Dependency subgraph of a project
+    ///
+    /// Includes the project's todos plus any todos outside the project that
+    /// block or depend on them.
+    pub fn dependency_graph(
+        &self,
+        user_id: &str,
+        project_id: &ProjectId,
+    ) -> Result<TodoDependencyGraph> {
+        let mut todos = self.list_todos_by_project(user_id, project_id)?;
+        let in_project: HashSet<TodoId> = todos.iter().map(|t| t.id.clone()).collect();
+        let mut seen = in_project.clone();
+        let mut edges = Vec::new();
+
+        let mut linked = Vec::new();
+        for todo in &todos {
+            for blocker_id in &todo.blocked_by {
+                if let Some(blocker) = self.get_todo(user_id, blocker_id)? {
+                    edges.push(DependencyEdge {
+                        blocker: blocker.id.clone(),
+                        dependent: todo.id.clone(),
+                    });
+                    if seen.insert(blocker.id.clone()) {
+                        linked.push(blocker);
+                    }
+                }
+            }
+            // Edges to in-project dependents come from their own `blocked_by`
+            for dependent in self.list_dependents(user_id, &todo.id)? {
+                if in_project.contains(&dependent.id) {
+                    continue;
+                }
+                edges.push(DependencyEdge {
+                    blocker: todo.id.clone(),
+                    dependent: dependent.id.clone(),
+                });
+                if seen.insert(dependent.id.clone()) {
+                    linked.push(dependent);
+                }
+            }
+        }
+        todos.extend(linked);
+
+        Ok(TodoDependencyGraph { todos, edges })
+    }
+
+    /// Longest chain of unfinished todos in a project's dependency graph
+    ///
+    /// Ordered from the first todo to start to the last one it unblocks.
+    /// Ties go to the chain whose first todo sorts first by priority and due date.
+    pub fn critical_path(&self, user_id: &str, project_id: &ProjectId) -> Result<Vec<Todo>> {
+        let graph = self.dependency_graph(user_id, project_id)?;
+        let mut open: Vec<Todo> = graph
+            .todos
+            .into_iter()
+            .filter(|t| !t.status.is_finished())
+            .collect();
+        open.sort_by(actionable_order);
+
+        let index: HashMap<TodoId, usize> = open
+            .iter()
+            .enumerate()
+            .map(|(i, t)| (t.id.clone(), i))
+            .collect();
+        let mut dependents: Vec<Vec<usize>> = vec![Vec::new(); open.len()];
+        for edge in &graph.edges {
+            if let (Some(&from), Some(&to)) = (index.get(&edge.blocker), index.get(&edge.dependent))
+            {
+                dependents[from].push(to);
+            }
+        }
+
+        // Longest path starting at each node (the graph is kept acyclic on write)
+        let mut longest: Vec<Option<(usize, Option<usize>)>> = vec![None; open.len()];
+        fn visit(
+            node: usize,
+            dependents: &[Vec<usize>],
+            longest: &mut [Option<(usize, Option<usize>)>],
+            on_path: &mut HashSet<usize>,
+        ) -> usize {
+            if let Some((len, _)) = longest[node] {
+                return len;
+            }
+            on_path.insert(node);
+            let mut best = (1, None);
+            for &next in &dependents[node] {
+                if on_path.contains(&next) {
+                    continue;
+                }
+                let len = visit(next, dependents, longest, on_path) + 1;
+                if len > best.0 {
+                    best = (len, Some(next));
+                }
+            }
+            on_path.remove(&node);
+            longest[node] = Some(best);
+            best.0
+        }
+
+        let mut on_path = HashSet::new();
+        let mut start = None;
+        let mut best_len = 0;
+        for node in 0..open.len() {
+            let len = visit(node, &dependents, &mut longest, &mut on_path);
+            if len > best_len {
+                best_len = len;
+                start = Some(node);
+            }
+        }
+
+        let mut chain = Vec::with_capacity(best_len);
+        let mut cursor = start;
+        while let Some(node) = cursor {
+            chain.push(open[node].clone());
+            cursor = longest[node].and_then(|(_, next)| next);
+        }
+        Ok(chain)
+    }
+
+    /// Todos that can be worked on now: Todo or InProgress with no open
+    /// blockers, sorted by priority then due date
+    pub fn list_actionable(&self, user_id: &str) -> Result<Vec<Todo>> {
+        let mut todos = Vec::new();
+        for todo in
+            self.list_todos_for_user(user_id, Some(&[TodoStatus::Todo, TodoStatus::InProgress]))?
+        {
+            if self.open_blockers(&todo)?.is_empty() {
+                todos.push(todo);
+            }
+        }
+        todos.sort_by(actionable_order);
+        Ok(todos)
+    }
+
     // =========================================================================
     // TODO COMMENTS
     // =========================================================================
@@ -1286,6 +1722,34 @@ pub struct UserTodoStats {
     pub projects: usize,
 }
 
+/// A `blocker` must finish before `dependent` can start
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct DependencyEdge {
+    pub blocker: TodoId,
+    pub dependent: TodoId,
+}
+
+/// Todos and blocked-by edges around a project
+#[derive(Debug, Clone, Default, serde::Serialize)]
+pub struct TodoDependencyGraph {
+    pub todos: Vec<Todo>,
+    pub edges: Vec<DependencyEdge>,
+}
+
+/// Priority first, then earliest due date (undated last), then oldest
+fn actionable_order(a: &Todo, b: &Todo) -> std::cmp::Ordering {
+    a.priority
+        .value()
+        .cmp(&b.priority.value())
+        .then_with(|| match (&a.due_date, &b.due_date) {
+            (Some(a_due), Some(b_due)) => a_due.cmp(b_due),
+            (Some(_), None) => std::cmp::Ordering::Less,
+            (None, Some(_)) => std::cmp::Ordering::Greater,
+            (None, None) => std::cmp::Ordering::Equal,
+        })
+        .then_with(|| a.created_at.cmp(&b.created_at))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1411,6 +1875,140 @@ mod tests {
         assert!(completed.completed_at.is_some());
     }
 
+    #[test]
+    fn test_dependency_cycle_detection() {
+        let (store, _temp) = setup_store();
+
+        let a = Todo::new("test_user".to_string(), "Design schema".to_string());
+        let mut b = Todo::new("test_user".to_string(), "Write migration".to_string());
+        b.blocked_by = vec![a.id.clone()];
+        let mut c = Todo::new("test_user".to_string(), "Deploy".to_string());
+        c.blocked_by = vec![b.id.clone()];
+        for todo in [&a, &b, &c] {
+            store.store_todo(todo).unwrap();
+        }
+
+        assert!(store
+            .find_dependency_cycle("test_user", &[&c])
+            .unwrap()
+            .is_none());
+
+        // A blocked by C closes A -> C -> B -> A
+        let mut a_blocked = a.clone();
+        a_blocked.blocked_by = vec![c.id.clone()];
+        let cycle = store
+            .find_dependency_cycle("test_user", &[&a_blocked])
+            .unwrap()
+            .expect("cycle");
+        assert_eq!(cycle, vec![a.id.clone(), c.id.clone(), b.id, a.id]);
+    }
+
+    #[test]
+    fn test_complete_unblocks_dependents() {
+        let (store, _temp) = setup_store();
+
+        let a = store
+            .store_todo(&Todo::new("test_user".to_string(), "Review".to_string()))
+            .unwrap();
+        let b = store
+            .store_todo(&Todo::new("test_user".to_string(), "Sign-off".to_string()))
+            .unwrap();
+        let mut c = Todo::new("test_user".to_string(), "Release".to_string());
+        c.blocked_by = vec![a.id.clone(), b.id.clone()];
+        c.status = TodoStatus::Blocked;
+        store.store_todo(&c).unwrap();
+
+        assert_eq!(store.list_dependents("test_user", &a.id).unwrap().len(), 1);
+        let actionable: Vec<TodoId> = store
+            .list_actionable("test_user")
+            .unwrap()
+            .into_iter()
+            .map(|t| t.id)
+            .collect();
+        assert_eq!(actionable.len(), 2);
+        assert!(!actionable.contains(&c.id));
+
+        // First blocker: still waiting on the second
+        let (done_a, _) = store.complete_todo("test_user", &a.id).unwrap().unwrap();
+        assert!(store
+            .unblock_dependents("test_user", &done_a)
+            .unwrap()
+            .is_empty());
+        let waiting = store.get_todo("test_user", &c.id).unwrap().unwrap();
+        assert_eq!(waiting.status, TodoStatus::Blocked);
+        assert!(waiting
+            .comments
+            .last()
+            .unwrap()
+            .content
+            .contains("still waiting"));
+
+        // Second blocker: released
+        let (done_b, _) = store.complete_todo("test_user", &b.id).unwrap().unwrap();
+        let unblocked = store.unblock_dependents("test_user", &done_b).unwrap();
+        assert_eq!(unblocked.len(), 1);
+        let released = store.get_todo("test_user", &c.id).unwrap().unwrap();
+        assert_eq!(released.status, TodoStatus::Todo);
+        assert_eq!(
+            released.comments.last().unwrap().comment_type,
+            TodoCommentType::Activity
+        );
+        assert_eq!(
+            store
+                .list_todos_for_user("test_user", Some(&[TodoStatus::Blocked]))
+                .unwrap()
+                .len(),
+            0
+        );
+    }
+
+    #[test]
+    fn test_critical_path_and_delete_detaches() {
+        let (store, _temp) = setup_store();
+        let project = Project::new("test_user".to_string(), "Launch".to_string());
+        store.store_project(&project).unwrap();
+
+        let in_project = |content: &str, blocked_by: Vec<TodoId>| {
+            let mut todo = Todo::new("test_user".to_string(), content.to_string());
+            todo.project_id = Some(project.id.clone());
+            todo.blocked_by = blocked_by;
+            store.store_todo(&todo).unwrap()
+        };
+        let spec = in_project("Spec", vec![]);
+        let build = in_project("Build", vec![spec.id.clone()]);
+        let docs = in_project("Docs", vec![spec.id.clone()]);
+        let ship = in_project("Ship", vec![build.id.clone(), docs.id.clone()]);
+        let test = in_project("Test", vec![build.id.clone()]);
+        store
+            .update_todo(&{
+                let mut ship = ship.clone();
+                ship.blocked_by.push(test.id.clone());
+                ship
+            })
+            .unwrap();
+
+        let graph = store.dependency_graph("test_user", &project.id).unwrap();
+        assert_eq!(graph.todos.len(), 5);
+        assert_eq!(graph.edges.len(), 6);
+
+        let path: Vec<String> = store
+            .critical_path("test_user", &project.id)
+            .unwrap()
+            .into_iter()
+            .map(|t| t.content)
+            .collect();
+        assert_eq!(path, vec!["Spec", "Build", "Test", "Ship"]);
+
+        // Deleting a blocker drops the link from its dependents
+        store.delete_todo("test_user", &test.id).unwrap();
+        let ship = store.get_todo("test_user", &ship.id).unwrap().unwrap();
+        assert!(!ship.blocked_by.contains(&test.id));
+        assert!(store
+            .list_dependents("test_user", &test.id)
+            .unwrap()
+            .is_empty());
+    }
+
     #[test]
     fn test_recurring_todo() {
         let (store, _temp) = setup_store();
@@ -1465,4 +2063,50 @@ mod tests {
         assert_eq!(in_progress.len(), 1);
         assert_eq!(in_progress[0].content, "Task 1");
     }
+
+    /// Deterministic embedder with a configurable dimension
+    struct LengthEmbedder(usize);
+
+    impl Embedder for LengthEmbedder {
+        fn encode(&self, text: &str) -> Result<Vec<f32>> {
+            let mut v = vec![0.1; self.0];
+            v[0] = text.len() as f32;
+            Ok(v)
+        }
+
+        fn dimension(&self) -> usize {
+            self.0
+        }
+    }
+
+    #[test]
+    fn test_reembed_on_model_change() {
+        let temp_dir = TempDir::new().unwrap();
+        {
+            let store = TodoStore::new(temp_dir.path()).unwrap();
+            let todo = Todo::new("test_user".to_string(), "Write report".to_string());
+            store.store_todo(&todo).unwrap();
+            let vector_id = store
+                .index_todo_embedding("test_user", &todo.id, &[0.5; MINILM_DIMENSION])
+                .unwrap();
+            store
+                .store_vector_id_mapping("test_user", vector_id, &todo.id)
+                .unwrap();
+            store.save_vector_indices().unwrap();
+        }
+
+        let store = TodoStore::new(temp_dir.path())
+            .unwrap()
+            .with_embedder(Arc::new(LengthEmbedder(8)));
+        store.load_vector_indices().unwrap();
+        assert_eq!(store.sync_embedding_model().unwrap(), 1);
+        // Fingerprint recorded: nothing to do on the next start
+        assert_eq!(store.sync_embedding_model().unwrap(), 0);
+
+        let query = LengthEmbedder(8).encode("Write report  ").unwrap();
+        let results = store.search_similar("test_user", &query, 5).unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].0.content, "Write report");
+        assert_eq!(results[0].0.embedding.as_ref().unwrap().len(), 8);
+    }
 }
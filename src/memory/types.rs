@@ -2,10 +2,12 @@
 
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::sync::Arc;
 use uuid::Uuid;
 
+use super::language::Language;
+
 use crate::constants::{
     DEFAULT_MAX_RESULTS, IMPORTANCE_FLOOR, RECENCY_FULL_DAYS, RECENCY_HIGH_DAYS,
     RECENCY_HIGH_WEIGHT, RECENCY_LOW_WEIGHT, RECENCY_MEDIUM_DAYS, RECENCY_MEDIUM_WEIGHT,
@@ -788,6 +790,27 @@ pub struct Experience {
     /// Pre-computed by handler to avoid redundant content parsing in downstream passes
     #[serde(default)]
     pub cooccurrence_pairs: Vec<(String, String)>,
+
+    /// Detected content language (drives stemming, stopwords and BM25 analyzer)
+    /// Populated during remember/upsert; None for memories stored before detection
+    #[serde(default)]
+    pub language: Option<Language>,
+}
+
+impl Experience {
+    /// Language of the content: the stored detection, or detected on the fly
+    pub fn detected_language(&self) -> Language {
+        self.language
+            .unwrap_or_else(|| Language::detect(&self.content))
+    }
+
+    /// Map frame of `local_position`: the `frame_id` metadata entry, or "map"
+    pub fn local_frame(&self) -> &str {
+        self.metadata
+            .get(LOCAL_FRAME_METADATA_KEY)
+            .map(String::as_str)
+            .unwrap_or(DEFAULT_LOCAL_FRAME)
+    }
 }
 
 impl Default for Experience {
@@ -836,6 +859,7 @@ impl Default for Experience {
             temporal_refs: Vec::new(),
             ner_entities: Vec::new(),
             cooccurrence_pairs: Vec::new(),
+            language: None,
         }
     }
 }
@@ -1706,6 +1730,129 @@ impl GeoFilter {
     }
 }
 
+// ============================================================================
+// Local-frame spatial filtering (indoor robots, map coordinates)
+// ============================================================================
+
+/// Metadata key naming the map frame a memory's `local_position` is expressed in
+pub const LOCAL_FRAME_METADATA_KEY: &str = "frame_id";
+
+/// Frame assumed for `local_position` when the memory does not name one
+pub const DEFAULT_LOCAL_FRAME: &str = "map";
+
+/// Euclidean distance between two local-frame positions in meters
+pub fn local_distance(a: [f32; 3], b: [f32; 3]) -> f32 {
+    ((a[0] - b[0]).powi(2) + (a[1] - b[1]).powi(2) + (a[2] - b[2]).powi(2)).sqrt()
+}
+
+/// Region of a robot's map frame to search
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum LocalRegion {
+    /// Positions within `radius_meters` of `center`
+    Radius {
+        center: [f32; 3],
+        radius_meters: f32,
+    },
+    /// Positions inside the axis-aligned box spanned by `min` and `max`
+    BoundingBox { min: [f32; 3], max: [f32; 3] },
+    /// The `k` positions closest to `center`
+    Nearest { center: [f32; 3], k: usize },
+}
+
+/// Heading cone: headings within `tolerance_degrees` of `heading` (degrees, 0-360)
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct HeadingCone {
+    pub heading: f32,
+    pub tolerance_degrees: f32,
+}
+
+impl HeadingCone {
+    pub fn new(heading: f32, tolerance_degrees: f32) -> Self {
+        Self {
+            heading,
+            tolerance_degrees,
+        }
+    }
+
+    /// Check if a heading falls inside the cone, wrapping around 360 degrees
+    pub fn contains(&self, heading: f32) -> bool {
+        let diff = (heading - self.heading).rem_euclid(360.0);
+        diff.min(360.0 - diff) <= self.tolerance_degrees
+    }
+}
+
+/// Spatial filter for `local_position` in a robot's map frame
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LocalSpatialFilter {
+    /// Map frame the region is expressed in (see [`LOCAL_FRAME_METADATA_KEY`])
+    pub frame: String,
+    pub region: LocalRegion,
+    /// Only match memories whose heading falls inside this cone
+    #[serde(default)]
+    pub heading_cone: Option<HeadingCone>,
+}
+
+impl LocalSpatialFilter {
+    pub fn new(frame: impl Into<String>, region: LocalRegion) -> Self {
+        Self {
+            frame: frame.into(),
+            region,
+            heading_cone: None,
+        }
+    }
+
+    pub fn with_heading_cone(mut self, cone: HeadingCone) -> Self {
+        self.heading_cone = Some(cone);
+        self
+    }
+
+    /// Point results are ranked by distance from (the box center for bounding boxes)
+    pub fn center(&self) -> [f32; 3] {
+        match self.region {
+            LocalRegion::Radius { center, .. } | LocalRegion::Nearest { center, .. } => center,
+            LocalRegion::BoundingBox { min, max } => [
+                (min[0] + max[0]) / 2.0,
+                (min[1] + max[1]) / 2.0,
+                (min[2] + max[2]) / 2.0,
+            ],
+        }
+    }
+
+    /// Check if a position and heading fall inside the region and heading cone
+    ///
+    /// `Nearest` has no fixed extent, so any position matches here; the
+    /// k-limit is applied by [`super::storage::MemoryStorage::search_local`].
+    pub fn contains(&self, position: [f32; 3], heading: Option<f32>) -> bool {
+        if let Some(cone) = &self.heading_cone {
+            if !heading.is_some_and(|h| cone.contains(h)) {
+                return false;
+            }
+        }
+        match self.region {
+            LocalRegion::Radius {
+                center,
+                radius_meters,
+            } => local_distance(center, position) <= radius_meters,
+            LocalRegion::BoundingBox { min, max } => {
+                (0..3).all(|i| position[i] >= min[i] && position[i] <= max[i])
+            }
+            LocalRegion::Nearest { .. } => true,
+        }
+    }
+
+    /// Check if a memory was recorded in this frame and inside the region
+    pub fn matches(&self, experience: &Experience) -> bool {
+        match experience.local_position {
+            Some(position) => {
+                experience.local_frame() == self.frame
+                    && self.contains(position, experience.heading)
+            }
+            None => false,
+        }
+    }
+}
+
 // ============================================================================
 // Geohash utilities for efficient spatial indexing
 // ============================================================================
@@ -1937,11 +2084,21 @@ pub struct Query {
     pub mission_id: Option<String>,
     /// Spatial filter (geo_location within radius)
     pub geo_filter: Option<GeoFilter>,
+    /// Spatial filter in the robot's map frame (local_position)
+    pub local_filter: Option<LocalSpatialFilter>,
     /// Filter by action type
     pub action_type: Option<String>,
     /// Filter by reward range (min, max) for RL-style queries
     pub reward_range: Option<(f32, f32)>,
 
+    // === Multi-Agent Filters ===
+    /// Filter by the agent that created the memory
+    pub agent_id: Option<String>,
+    /// Filter by agent run
+    pub run_id: Option<String>,
+    /// Filter by the actor the agent acted for
+    pub actor_id: Option<String>,
+
     // === Decision & Learning Filters ===
     /// Filter by outcome type: success, failure, partial, aborted, timeout
     pub outcome_type: Option<String>,
@@ -1972,6 +2129,12 @@ pub struct Query {
     /// This prevents episode bleeding where unrelated memories mix in results
     pub episode_id: Option<String>,
 
+    // === Structured Filter ===
+    /// Metadata filter built from storage search criteria
+    /// Semantic retrieval pushes this into the vector index traversal, so only
+    /// matching memories compete for the `max_results` slots.
+    pub filter: Option<super::storage::SearchCriteria>,
+
     // === Result Control ===
     pub max_results: usize,
     pub retrieval_mode: RetrievalMode,
@@ -2043,8 +2206,12 @@ impl Default for Query {
             robot_id: None,
             mission_id: None,
             geo_filter: None,
+            local_filter: None,
             action_type: None,
             reward_range: None,
+            agent_id: None,
+            run_id: None,
+            actor_id: None,
             outcome_type: None,
             failures_only: false,
             anomalies_only: false,
@@ -2055,6 +2222,7 @@ impl Default for Query {
             confidence_range: None,
             prospective_signals: None,
             episode_id: None,
+            filter: None,
             max_results: DEFAULT_MAX_RESULTS,
             retrieval_mode: RetrievalMode::Hybrid,
             offset: 0,
@@ -2143,6 +2311,13 @@ impl Query {
             }
         }
 
+        // Local-frame filter (map coordinates)
+        if let Some(local_filter) = &self.local_filter {
+            if !local_filter.matches(&memory.experience) {
+                return false;
+            }
+        }
+
         // Action type filter
         if let Some(action_type) = &self.action_type {
             if memory.experience.action_type.as_ref() != Some(action_type) {
@@ -2162,6 +2337,18 @@ impl Query {
             }
         }
 
+        // === Multi-Agent Filters ===
+
+        if self.agent_id.is_some() && memory.agent_id != self.agent_id {
+            return false;
+        }
+        if self.run_id.is_some() && memory.run_id != self.run_id {
+            return false;
+        }
+        if self.actor_id.is_some() && memory.actor_id != self.actor_id {
+            return false;
+        }
+
         // === Decision & Learning Filters ===
 
         // Outcome type filter
@@ -2231,9 +2418,35 @@ impl Query {
             }
         }
 
+        // Structured filter (exact check; index lookups are bucketed)
+        if let Some(filter) = &self.filter {
+            if !filter.matches(memory) {
+                return false;
+            }
+        }
+
         true
     }
 
+    /// The structured filter to resolve against the storage indices
+    ///
+    /// Combines `filter` with the agent/run/actor filters, which are indexed,
+    /// so scoped recalls only rank memories from that agent, run or actor.
+    pub fn index_filter(&self) -> Option<super::storage::SearchCriteria> {
+        use super::storage::SearchCriteria;
+
+        let mut criteria: Vec<SearchCriteria> = self.filter.iter().cloned().collect();
+        criteria.extend(self.agent_id.clone().map(SearchCriteria::ByAgent));
+        criteria.extend(self.run_id.clone().map(SearchCriteria::ByRun));
+        criteria.extend(self.actor_id.clone().map(SearchCriteria::ByActor));
+
+        match criteria.len() {
+            0 => None,
+            1 => criteria.pop(),
+            _ => Some(SearchCriteria::Combined(criteria)),
+        }
+    }
+
     /// Create a builder for Query
     pub fn builder() -> QueryBuilder {
         QueryBuilder::default()
@@ -2288,6 +2501,27 @@ impl QueryBuilder {
         self
     }
 
+    /// Restrict results to a region of the robot's map frame
+    pub fn local_filter(mut self, filter: LocalSpatialFilter) -> Self {
+        self.query.local_filter = Some(filter);
+        self
+    }
+
+    pub fn agent_id(mut self, id: impl Into<String>) -> Self {
+        self.query.agent_id = Some(id.into());
+        self
+    }
+
+    pub fn run_id(mut self, id: impl Into<String>) -> Self {
+        self.query.run_id = Some(id.into());
+        self
+    }
+
+    pub fn actor_id(mut self, id: impl Into<String>) -> Self {
+        self.query.actor_id = Some(id.into());
+        self
+    }
+
     pub fn failures_only(mut self, only: bool) -> Self {
         self.query.failures_only = only;
         self
@@ -2327,6 +2561,12 @@ impl QueryBuilder {
         self
     }
 
+    /// Restrict results to memories matching structured search criteria
+    pub fn filter(mut self, criteria: super::storage::SearchCriteria) -> Self {
+        self.query.filter = Some(criteria);
+        self
+    }
+
     pub fn build(self) -> Query {
         self.query
     }
@@ -2363,6 +2603,10 @@ pub enum ForgetCriteria {
     },
     /// Delete memories of a specific type
     ByType(ExperienceType),
+    /// Delete every memory created by an agent
+    ByAgent(String),
+    /// Delete every memory from one agent run
+    ByRun(String),
     /// Delete ALL memories for a user (GDPR compliance - right to erasure)
     All,
 }
@@ -2773,6 +3017,17 @@ pub struct MemoryStats {
     /// Knowledge graph relationship count
     #[serde(default)]
     pub graph_edges: usize,
+    /// Memory and run counts per agent_id
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub agents: BTreeMap<String, AgentStats>,
+}
+
+/// Per-agent breakdown in [`MemoryStats`]
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AgentStats {
+    pub memory_count: usize,
+    /// Distinct run_ids among the agent's memories
+    pub run_count: usize,
 }
 
 /// Report from index integrity verification
@@ -2890,6 +3145,11 @@ pub enum ProspectiveTrigger {
         #[serde(default = "default_context_threshold")]
         threshold: f32,
     },
+    /// Trigger at `at`, then re-arm at the next occurrence of `recurrence`
+    Recurring {
+        at: DateTime<Utc>,
+        recurrence: Recurrence,
+    },
 }
 
 fn default_context_threshold() -> f32 {
@@ -2907,6 +3167,7 @@ impl ProspectiveTrigger {
                 now >= due_at
             }
             ProspectiveTrigger::OnContext { .. } => false, // Context triggers are checked separately
+            ProspectiveTrigger::Recurring { at, .. } => now >= *at,
         }
     }
 
@@ -2918,6 +3179,7 @@ impl ProspectiveTrigger {
                 Some(*from + chrono::Duration::seconds(*seconds as i64))
             }
             ProspectiveTrigger::OnContext { .. } => None,
+            ProspectiveTrigger::Recurring { at, .. } => Some(*at),
         }
     }
 
@@ -3040,6 +3302,21 @@ impl ProspectiveTask {
         self.triggered_at = Some(Utc::now());
     }
 
+    /// Move a recurring trigger to its next occurrence after now and set the
+    /// task pending again. Returns false for one-shot triggers and for
+    /// series that have ended (the task keeps its current status).
+    pub fn rearm(&mut self) -> bool {
+        if let ProspectiveTrigger::Recurring { at, recurrence } = &mut self.trigger {
+            // Skip occurrences missed while the reminder wasn't polled
+            if let Some(next) = recurrence.next_after((*at).max(Utc::now())) {
+                *at = next;
+                self.status = ProspectiveTaskStatus::Pending;
+                return true;
+            }
+        }
+        false
+    }
+
     /// Mark as dismissed
     pub fn mark_dismissed(&mut self) {
         self.status = ProspectiveTaskStatus::Dismissed;
@@ -3127,6 +3404,11 @@ pub enum TodoStatus {
 }
 
 impl TodoStatus {
+    /// Done and Cancelled todos no longer hold up their dependents
+    pub fn is_finished(&self) -> bool {
+        matches!(self, TodoStatus::Done | TodoStatus::Cancelled)
+    }
+
     /// Get the status icon (Linear-style)
     pub fn icon(&self) -> &'static str {
         match self {
@@ -3218,10 +3500,24 @@ pub enum Recurrence {
     Monthly { day: u8 },
     /// Every N days
     EveryNDays { n: u32 },
+    /// RFC 5545 RRULE anchored at `dtstart`, expanded in `tzid` local time
+    /// (UTC when unset); see [`crate::memory::rrule`]
+    #[serde(rename = "rrule")]
+    RRule {
+        rule: String,
+        dtstart: DateTime<Utc>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        tzid: Option<String>,
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        exdates: Vec<DateTime<Utc>>,
+    },
 }
 
 impl Recurrence {
     /// Calculate the next due date from a given date
+    ///
+    /// Returns `from` unchanged for an RRULE series that has ended; use
+    /// [`Recurrence::next_after`] to tell the two apart.
     pub fn next_occurrence(&self, from: DateTime<Utc>) -> DateTime<Utc> {
         use chrono::{Datelike, Duration};
 
@@ -3257,6 +3553,59 @@ impl Recurrence {
                 next.with_day(target_day).unwrap_or(next)
             }
             Recurrence::EveryNDays { n } => from + Duration::days(*n as i64),
+            Recurrence::RRule { .. } => self.next_after(from).unwrap_or(from),
+        }
+    }
+
+    /// Next occurrence strictly after `after`, or None once an RRULE series
+    /// has run out (COUNT/UNTIL) or its rule no longer parses
+    pub fn next_after(&self, after: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        match self {
+            Recurrence::RRule { .. } => self.recurrence_set()?.next_after(after),
+            _ => Some(self.next_occurrence(after)),
+        }
+    }
+
+    /// Expandable series for an RRULE recurrence
+    pub fn recurrence_set(&self) -> Option<crate::memory::rrule::RecurrenceSet> {
+        use crate::memory::rrule::{parse_tzid, RecurrenceSet};
+
+        match self {
+            Recurrence::RRule {
+                rule,
+                dtstart,
+                tzid,
+                exdates,
+            } => {
+                let tz = match tzid {
+                    Some(name) => parse_tzid(name).ok()?,
+                    None => chrono_tz::Tz::UTC,
+                };
+                let mut set = RecurrenceSet::new(rule.parse().ok()?, *dtstart, tz);
+                set.exdates = exdates.clone();
+                Some(set)
+            }
+            _ => None,
+        }
+    }
+
+    /// Equivalent RRULE value (for iCalendar export)
+    pub fn to_rrule(&self) -> String {
+        const DAYS: [&str; 7] = ["SU", "MO", "TU", "WE", "TH", "FR", "SA"];
+
+        match self {
+            Recurrence::Daily => "FREQ=DAILY".to_string(),
+            Recurrence::Weekly { days } if days.is_empty() => "FREQ=WEEKLY".to_string(),
+            Recurrence::Weekly { days } => format!(
+                "FREQ=WEEKLY;BYDAY={}",
+                days.iter()
+                    .filter_map(|d| DAYS.get(*d as usize).copied())
+                    .collect::<Vec<_>>()
+                    .join(",")
+            ),
+            Recurrence::Monthly { day } => format!("FREQ=MONTHLY;BYMONTHDAY={}", (*day).min(28)),
+            Recurrence::EveryNDays { n } => format!("FREQ=DAILY;INTERVAL={}", (*n).max(1)),
+            Recurrence::RRule { rule, .. } => rule.clone(),
         }
     }
 }
@@ -3405,6 +3754,10 @@ pub struct Todo {
     /// Who/what this is blocked on (when status=Blocked)
     pub blocked_on: Option<String>,
 
+    /// Todos that must be finished before this one can start
+    #[serde(default)]
+    pub blocked_by: Vec<TodoId>,
+
     /// Additional notes
     pub notes: Option<String>,
 
@@ -3456,6 +3809,7 @@ impl Todo {
             due_date: None,
             recurrence: None,
             blocked_on: None,
+            blocked_by: Vec::new(),
             notes: None,
             created_at: now,
             updated_at: now,
@@ -3467,6 +3821,16 @@ impl Todo {
         }
     }
 
+    /// Text embedded for semantic todo search
+    pub fn embedding_text(&self) -> String {
+        format!(
+            "{} {} {}",
+            self.content,
+            self.notes.as_deref().unwrap_or(""),
+            self.tags.join(" ")
+        )
+    }
+
     /// Get the user-facing short ID (BOLT-1, MEM-2, SHO-3, etc.)
     /// Uses project prefix if available, otherwise "SHO" for standalone todos
     pub fn short_id(&self) -> String {
@@ -3519,11 +3883,29 @@ impl Todo {
         self.updated_at = Utc::now();
     }
 
+    /// Move between Blocked and Todo to match whether any blocker is still open
+    ///
+    /// Only Todo/InProgress todos become Blocked, and a todo also waiting on
+    /// something outside the todo list (`blocked_on`) stays Blocked.
+    /// Returns whether the status changed.
+    pub fn sync_blocked_status(&mut self, has_open_blockers: bool) -> bool {
+        let next = match self.status {
+            TodoStatus::Todo | TodoStatus::InProgress if has_open_blockers => TodoStatus::Blocked,
+            TodoStatus::Blocked if !has_open_blockers && self.blocked_on.is_none() => {
+                TodoStatus::Todo
+            }
+            _ => return false,
+        };
+        self.status = next;
+        self.updated_at = Utc::now();
+        true
+    }
+
     /// Create next recurrence if applicable
     pub fn create_next_recurrence(&self) -> Option<Todo> {
-        self.recurrence.as_ref().map(|r| {
+        self.recurrence.as_ref().and_then(|r| {
             let base_date = self.due_date.unwrap_or_else(Utc::now);
-            let next_due = r.next_occurrence(base_date);
+            let next_due = r.next_after(base_date)?;
 
             let mut next = self.clone();
             next.id = TodoId::new();
@@ -3533,7 +3915,7 @@ impl Todo {
             next.created_at = Utc::now();
             next.updated_at = Utc::now();
             next.comments = Vec::new(); // Fresh comments for new recurrence
-            next
+            Some(next)
         })
     }
 
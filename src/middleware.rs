@@ -4,16 +4,29 @@
 //! - Request ID generation and propagation
 //! - HTTP latency and count metrics
 //! - Path normalization to prevent cardinality explosion
+//! - Rate limiting and CORS layers that can be swapped on config reload
 
 use axum::{
-    extract::Request,
+    body::Body,
+    extract::{Request, State},
     http::{header::HeaderValue, StatusCode},
     middleware::Next,
     response::Response,
 };
+use governor::{clock::QuantaInstant, middleware::NoOpMiddleware};
+use parking_lot::RwLock;
+use std::convert::Infallible;
+use std::sync::Arc;
 use std::time::Instant;
+use tower::{Layer, ServiceExt};
+use tower_governor::{
+    governor::GovernorConfigBuilder, key_extractor::PeerIpKeyExtractor, GovernorLayer,
+};
+use tower_http::cors::CorsLayer;
 use uuid::Uuid;
 
+use crate::config::ServerConfig;
+
 /// Request ID extension for correlation across logs and errors
 #[derive(Debug, Clone)]
 pub struct RequestId(pub String);
@@ -142,6 +155,89 @@ pub async fn track_metrics(req: Request, next: Next) -> Result<Response, StatusC
     Ok(response)
 }
 
+type IpGovernorLayer = GovernorLayer<PeerIpKeyExtractor, NoOpMiddleware<QuantaInstant>, Body>;
+
+/// Rate limiter and CORS policy that are rebuilt when the config is reloaded
+///
+/// `tower_governor` and `tower_http` layers are immutable once built, so the
+/// current ones live behind a lock and are applied per request by
+/// [`reloadable_rate_limit`] and [`reloadable_cors`].
+pub struct ReloadableLayers {
+    governor: RwLock<Option<IpGovernorLayer>>,
+    cors: RwLock<CorsLayer>,
+}
+
+impl ReloadableLayers {
+    pub fn new(config: &ServerConfig) -> Self {
+        Self {
+            governor: RwLock::new(build_governor(config)),
+            cors: RwLock::new(config.cors.to_layer()),
+        }
+    }
+
+    /// Rebuild both layers from `config`
+    pub fn apply(&self, config: &ServerConfig) {
+        *self.governor.write() = build_governor(config);
+        *self.cors.write() = config.cors.to_layer();
+    }
+
+    pub fn rate_limit_enabled(&self) -> bool {
+        self.governor.read().is_some()
+    }
+}
+
+/// Build the per-IP rate limiter (0 requests/sec = disabled, for localhost/embedded use)
+fn build_governor(config: &ServerConfig) -> Option<IpGovernorLayer> {
+    if config.rate_limit_per_second == 0 {
+        return None;
+    }
+    match GovernorConfigBuilder::default()
+        .per_second(config.rate_limit_per_second)
+        .burst_size(config.rate_limit_burst)
+        .finish()
+    {
+        Some(governor_conf) => Some(GovernorLayer::new(governor_conf)),
+        None => {
+            tracing::error!(
+                "Invalid rate limit (per_second={}, burst={}), rate limiting disabled",
+                config.rate_limit_per_second,
+                config.rate_limit_burst
+            );
+            None
+        }
+    }
+}
+
+/// Middleware applying the current rate limiter, if any
+pub async fn reloadable_rate_limit(
+    State(layers): State<Arc<ReloadableLayers>>,
+    req: Request,
+    next: Next,
+) -> Response {
+    let governor = layers.governor.read().clone();
+    match governor {
+        Some(governor) => unwrap_infallible(governor.layer(next).oneshot(req).await),
+        None => next.run(req).await,
+    }
+}
+
+/// Middleware applying the current CORS policy
+pub async fn reloadable_cors(
+    State(layers): State<Arc<ReloadableLayers>>,
+    req: Request,
+    next: Next,
+) -> Response {
+    let cors = layers.cors.read().clone();
+    unwrap_infallible(cors.layer(next).oneshot(req).await)
+}
+
+fn unwrap_infallible(result: Result<Response, Infallible>) -> Response {
+    match result {
+        Ok(response) => response,
+        Err(never) => match never {},
+    }
+}
+
 /// Normalize path to prevent metric cardinality explosion
 /// /api/users/user123/memories -> /api/users/{id}/memories
 fn normalize_path(path: &str) -> String {
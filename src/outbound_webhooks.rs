@@ -0,0 +1,1220 @@
+//! Outbound Webhooks - per-user subscriptions with signed, retried delivery
+//!
+//! Users register HTTP endpoints for a subset of server events (memory
+//! created/forgotten, todo completed, reminder triggered, consolidation
+//! report). Each matching event becomes a delivery that is POSTed as JSON and
+//! signed with HMAC-SHA256 over the raw body, in the same format GitHub uses:
+//! `X-Shodh-Signature-256: sha256=<hex>`. Receivers check it with
+//! [`verify_signature`].
+//!
+//! Failed deliveries are retried with exponential backoff. After
+//! [`MAX_DELIVERY_ATTEMPTS`] failures a delivery moves to the dead-letter list,
+//! where it can be inspected and re-queued.
+//!
+//! Endpoints must resolve to public addresses: loopback, private, link-local
+//! (including cloud metadata) and other internal ranges are refused when a
+//! subscription is created and again when each delivery connects, unless the
+//! host is listed in `SHODH_WEBHOOK_ALLOWED_HOSTS`. Redirects are not
+//! followed, and only the status code of a failed response is recorded.
+//!
+//! Storage layout (RocksDB at `{base_path}/webhooks`):
+//! - `sub:{user_id}:{subscription_id}`        → JSON [`WebhookSubscription`]
+//! - `delivery:{delivery_id}`                 → JSON [`WebhookDelivery`]
+//! - `queue:{due_nanos:020}:{delivery_id}`    → empty (retry queue, due-time order)
+//! - `log:{user_id}:{created_nanos:020}:{id}` → empty (delivery log index)
+//! - `dead:{user_id}:{delivery_id}`           → empty (dead-letter index)
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use hmac::{Hmac, Mac};
+use rand::RngCore;
+use rocksdb::{Direction, IteratorMode, Options, WriteBatch, DB};
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use std::collections::HashSet;
+use std::net::{IpAddr, SocketAddr, ToSocketAddrs};
+use std::path::Path;
+use std::sync::Arc;
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Signature header sent with every delivery
+pub const SIGNATURE_HEADER: &str = "X-Shodh-Signature-256";
+
+/// Event type header sent with every delivery
+pub const EVENT_HEADER: &str = "X-Shodh-Event";
+
+/// Delivery ID header (stable across retries, for receiver-side dedup)
+pub const DELIVERY_HEADER: &str = "X-Shodh-Delivery";
+
+/// Attempts before a delivery is dead-lettered
+pub const MAX_DELIVERY_ATTEMPTS: u32 = 8;
+
+/// First retry delay; doubles per attempt up to [`MAX_RETRY_DELAY_SECS`]
+const BASE_RETRY_DELAY_SECS: i64 = 10;
+
+/// Retry delay cap (1 hour)
+const MAX_RETRY_DELAY_SECS: i64 = 3600;
+
+/// Per-request timeout for deliveries
+const DELIVERY_TIMEOUT_SECS: u64 = 10;
+
+/// Deliveries sent per dispatch pass
+const DISPATCH_BATCH_SIZE: usize = 32;
+
+/// Delivery log entries kept per user; older ones are pruned on insert
+const MAX_LOG_ENTRIES_PER_USER: usize = 1000;
+
+/// Longest error message kept on a failed attempt
+const MAX_ERROR_LEN: usize = 500;
+
+/// Events a subscription can ask for
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WebhookEventType {
+    MemoryCreated,
+    MemoryForgotten,
+    TodoCompleted,
+    ReminderTriggered,
+    ConsolidationReport,
+}
+
+impl WebhookEventType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::MemoryCreated => "memory_created",
+            Self::MemoryForgotten => "memory_forgotten",
+            Self::TodoCompleted => "todo_completed",
+            Self::ReminderTriggered => "reminder_triggered",
+            Self::ConsolidationReport => "consolidation_report",
+        }
+    }
+
+    /// Map a broadcast `MemoryEvent::event_type` to a webhook event
+    pub fn from_memory_event(event_type: &str) -> Option<Self> {
+        match event_type {
+            "CREATE" => Some(Self::MemoryCreated),
+            "DELETE" => Some(Self::MemoryForgotten),
+            "TODO_COMPLETE" => Some(Self::TodoCompleted),
+            "REMINDER_TRIGGERED" => Some(Self::ReminderTriggered),
+            _ => None,
+        }
+    }
+}
+
+impl std::fmt::Display for WebhookEventType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// A registered webhook endpoint
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WebhookSubscription {
+    pub id: String,
+    pub user_id: String,
+    /// `http://` or `https://` endpoint receiving POSTs
+    pub url: String,
+    /// Events delivered to this endpoint. Empty means all events.
+    #[serde(default)]
+    pub events: Vec<WebhookEventType>,
+    #[serde(default)]
+    pub description: Option<String>,
+    /// HMAC signing secret. Only returned when the subscription is created.
+    #[serde(skip_serializing_if = "String::is_empty", default)]
+    pub secret: String,
+    pub created_at: DateTime<Utc>,
+}
+
+impl WebhookSubscription {
+    pub fn wants(&self, event: WebhookEventType) -> bool {
+        self.events.is_empty() || self.events.contains(&event)
+    }
+
+    /// Copy safe to return from listings (secret stripped)
+    pub fn redacted(&self) -> Self {
+        Self {
+            secret: String::new(),
+            ..self.clone()
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DeliveryStatus {
+    /// Queued for its first attempt or a retry
+    Pending,
+    Delivered,
+    /// Gave up after [`MAX_DELIVERY_ATTEMPTS`]; listed as a dead letter
+    Failed,
+}
+
+/// One event sent (or to be sent) to one subscription
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WebhookDelivery {
+    pub id: String,
+    pub subscription_id: String,
+    pub user_id: String,
+    pub url: String,
+    pub event_type: WebhookEventType,
+    /// JSON body POSTed to the endpoint
+    pub payload: serde_json::Value,
+    pub status: DeliveryStatus,
+    pub attempts: u32,
+    pub created_at: DateTime<Utc>,
+    pub next_attempt_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub last_attempt_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub last_status_code: Option<u16>,
+    #[serde(default)]
+    pub last_error: Option<String>,
+    #[serde(default)]
+    pub delivered_at: Option<DateTime<Utc>>,
+}
+
+/// Whether an address belongs to a loopback, private, link-local or other
+/// non-public range a webhook must not reach
+fn is_internal_ip(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(v4) => {
+            let [a, b, c, _] = v4.octets();
+            v4.is_loopback()
+                || v4.is_private()
+                || v4.is_link_local() // includes 169.254.169.254 cloud metadata
+                || v4.is_unspecified()
+                || v4.is_broadcast()
+                || v4.is_multicast()
+                || v4.is_documentation()
+                || a == 0
+                || (a == 100 && (64..128).contains(&b)) // carrier-grade NAT
+                || (a == 192 && b == 0 && c == 0) // IETF protocol assignments
+                || (a == 198 && (b == 18 || b == 19)) // benchmarking
+                || a >= 240 // reserved
+        }
+        IpAddr::V6(v6) => {
+            // IPv4-mapped and IPv4-compatible forms (also covers ::1 and ::)
+            if let Some(v4) = v6.to_ipv4() {
+                return is_internal_ip(IpAddr::V4(v4));
+            }
+            let segments = v6.segments();
+            // NAT64 addresses embed an IPv4 address in the low 32 bits
+            if segments[0] == 0x64 && segments[1] == 0xff9b {
+                let low = (u32::from(segments[6]) << 16) | u32::from(segments[7]);
+                return is_internal_ip(IpAddr::V4(low.into()));
+            }
+            v6.is_multicast()
+                || (segments[0] & 0xfe00) == 0xfc00 // unique local
+                || (segments[0] & 0xffc0) == 0xfe80 // link-local
+        }
+    }
+}
+
+/// Which endpoints deliveries may reach
+///
+/// Hosts resolving to internal addresses are refused unless listed in the
+/// allowlist (hostnames or IP literals, matched against the URL host).
+#[derive(Debug, Clone, Default)]
+pub struct WebhookTargetPolicy {
+    allowed_hosts: HashSet<String>,
+}
+
+impl WebhookTargetPolicy {
+    pub fn new(allowed_hosts: impl IntoIterator<Item = String>) -> Self {
+        Self {
+            allowed_hosts: allowed_hosts
+                .into_iter()
+                .map(|h| normalize_host(&h))
+                .filter(|h| !h.is_empty())
+                .collect(),
+        }
+    }
+
+    fn is_allowed(&self, host: &str) -> bool {
+        self.allowed_hosts.contains(&normalize_host(host))
+    }
+
+    /// Reject `host` if any of its addresses is internal (or it has none)
+    fn check_addrs(&self, host: &str, addrs: &[SocketAddr]) -> Result<()> {
+        if self.is_allowed(host) {
+            return Ok(());
+        }
+        if addrs.is_empty() {
+            anyhow::bail!("Webhook host {host} does not resolve");
+        }
+        if let Some(addr) = addrs.iter().find(|a| is_internal_ip(a.ip())) {
+            anyhow::bail!(
+                "Webhook host {host} resolves to internal address {}; \
+                 add it to SHODH_WEBHOOK_ALLOWED_HOSTS to allow it",
+                addr.ip()
+            );
+        }
+        Ok(())
+    }
+
+    /// Check an IP-literal host without DNS; hostnames pass through
+    ///
+    /// Deliveries check hostnames in [`PublicResolver`] instead, which sees
+    /// the addresses the connection actually uses.
+    fn check_literal(&self, url: &reqwest::Url) -> Result<()> {
+        let host = url.host_str().unwrap_or_default();
+        match normalize_host(host).parse::<IpAddr>() {
+            Ok(ip) => self.check_addrs(host, &[SocketAddr::new(ip, 0)]),
+            Err(_) => Ok(()),
+        }
+    }
+
+    /// Validate a subscription URL: scheme, then every resolved address
+    ///
+    /// Blocks on DNS for hostnames.
+    fn check_url(&self, url: &reqwest::Url) -> Result<()> {
+        if !matches!(url.scheme(), "http" | "https") {
+            anyhow::bail!("Webhook URL must use http or https");
+        }
+        let host = url
+            .host_str()
+            .filter(|h| !h.is_empty())
+            .context("Webhook URL has no host")?;
+        if normalize_host(host).parse::<IpAddr>().is_ok() {
+            return self.check_literal(url);
+        }
+        if self.is_allowed(host) {
+            return Ok(());
+        }
+        let port = url.port_or_known_default().unwrap_or(80);
+        let addrs: Vec<SocketAddr> = (host, port)
+            .to_socket_addrs()
+            .with_context(|| format!("Webhook host {host} does not resolve"))?
+            .collect();
+        self.check_addrs(host, &addrs)
+    }
+}
+
+/// Lowercase a host and strip IPv6 brackets so `[::1]` matches `::1`
+fn normalize_host(host: &str) -> String {
+    host.trim()
+        .trim_start_matches('[')
+        .trim_end_matches(']')
+        .to_ascii_lowercase()
+}
+
+/// DNS resolver for deliveries that refuses internal addresses
+///
+/// Checking the addresses the connection will use (not just those seen at
+/// subscribe time) stops a hostname from being re-pointed at an internal
+/// service after it was registered.
+struct PublicResolver {
+    policy: WebhookTargetPolicy,
+}
+
+impl reqwest::dns::Resolve for PublicResolver {
+    fn resolve(&self, name: reqwest::dns::Name) -> reqwest::dns::Resolving {
+        let policy = self.policy.clone();
+        Box::pin(async move {
+            let host = name.as_str();
+            let addrs: Vec<SocketAddr> = tokio::net::lookup_host((host, 0)).await?.collect();
+            policy.check_addrs(host, &addrs)?;
+            Ok(Box::new(addrs.into_iter()) as reqwest::dns::Addrs)
+        })
+    }
+}
+
+/// Compute the signature header value for a body
+pub fn sign_payload(secret: &str, body: &[u8]) -> String {
+    let mut mac =
+        HmacSha256::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any length");
+    mac.update(body);
+    format!("sha256={}", hex::encode(mac.finalize().into_bytes()))
+}
+
+/// Verify a delivery signature (`sha256=<hex>`) against the raw body
+pub fn verify_signature(secret: &str, body: &[u8], signature: &str) -> Result<bool> {
+    let mut mac =
+        HmacSha256::new_from_slice(secret.as_bytes()).context("Invalid webhook secret")?;
+    mac.update(body);
+
+    let expected_sig = signature.strip_prefix("sha256=").unwrap_or(signature);
+    let expected_bytes = hex::decode(expected_sig).context("Invalid signature format")?;
+
+    Ok(mac.verify_slice(&expected_bytes).is_ok())
+}
+
+/// Delay before retry number `attempts` (1-based)
+fn retry_delay(attempts: u32) -> chrono::Duration {
+    let exp = attempts.saturating_sub(1).min(16);
+    let secs = (BASE_RETRY_DELAY_SECS << exp).min(MAX_RETRY_DELAY_SECS);
+    chrono::Duration::seconds(secs)
+}
+
+fn generate_secret() -> String {
+    let mut bytes = [0u8; 32];
+    rand::thread_rng().fill_bytes(&mut bytes);
+    format!("whsec_{}", hex::encode(bytes))
+}
+
+fn nanos(t: DateTime<Utc>) -> i64 {
+    t.timestamp_nanos_opt().unwrap_or(0)
+}
+
+fn queue_key(due: DateTime<Utc>, delivery_id: &str) -> String {
+    format!("queue:{:020}:{delivery_id}", nanos(due))
+}
+
+fn log_key(delivery: &WebhookDelivery) -> String {
+    format!(
+        "log:{}:{:020}:{}",
+        delivery.user_id,
+        nanos(delivery.created_at),
+        delivery.id
+    )
+}
+
+fn dead_key(user_id: &str, delivery_id: &str) -> String {
+    format!("dead:{user_id}:{delivery_id}")
+}
+
+/// Storage for subscriptions, the retry queue and the delivery log
+pub struct WebhookStore {
+    db: Arc<DB>,
+    /// Wakes the dispatcher when new deliveries are queued
+    notify: tokio::sync::Notify,
+    /// Endpoints subscriptions and deliveries may target
+    target_policy: WebhookTargetPolicy,
+}
+
+impl WebhookStore {
+    /// Open (or create) the store under `{storage_path}/webhooks`
+    pub fn new(storage_path: &Path) -> Result<Self> {
+        let path = storage_path.join("webhooks");
+        std::fs::create_dir_all(&path)?;
+
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        opts.set_compression_type(rocksdb::DBCompressionType::Lz4);
+        opts.set_max_write_buffer_number(2);
+        opts.set_write_buffer_size(4 * 1024 * 1024);
+
+        let db = Arc::new(DB::open(&opts, &path).context("Failed to open webhook store")?);
+
+        Ok(Self {
+            db,
+            notify: tokio::sync::Notify::new(),
+            target_policy: WebhookTargetPolicy::default(),
+        })
+    }
+
+    /// Allow endpoints on these hosts even when they resolve to internal
+    /// addresses (e.g. a receiver running next to the server)
+    pub fn with_allowed_hosts(mut self, hosts: impl IntoIterator<Item = String>) -> Self {
+        self.target_policy = WebhookTargetPolicy::new(hosts);
+        self
+    }
+
+    /// Flush to disk (graceful shutdown)
+    pub fn flush(&self) -> Result<()> {
+        self.db
+            .flush()
+            .map_err(|e| anyhow::anyhow!("Failed to flush webhooks db: {e}"))
+    }
+
+    /// Database reference for comprehensive backups
+    pub fn database(&self) -> &Arc<DB> {
+        &self.db
+    }
+
+    // =========================================================================
+    // SUBSCRIPTIONS
+    // =========================================================================
+
+    /// Register an endpoint. A signing secret is generated when none is given;
+    /// the returned record is the only place it is exposed.
+    ///
+    /// Resolves the URL's host (blocking) and rejects internal addresses
+    /// that are not allowlisted.
+    pub fn subscribe(
+        &self,
+        user_id: &str,
+        url: &str,
+        events: Vec<WebhookEventType>,
+        description: Option<String>,
+        secret: Option<String>,
+    ) -> Result<WebhookSubscription> {
+        let parsed = reqwest::Url::parse(url).context("Invalid webhook URL")?;
+        self.target_policy.check_url(&parsed)?;
+
+        let subscription = WebhookSubscription {
+            id: format!("wh_{}", uuid::Uuid::new_v4().simple()),
+            user_id: user_id.to_string(),
+            url: url.to_string(),
+            events,
+            description,
+            secret: secret
+                .filter(|s| !s.is_empty())
+                .unwrap_or_else(generate_secret),
+            created_at: Utc::now(),
+        };
+        self.db
+            .put(
+                format!("sub:{user_id}:{}", subscription.id).as_bytes(),
+                serde_json::to_vec(&subscription)?,
+            )
+            .context("Failed to persist webhook subscription")?;
+
+        tracing::info!(
+            user_id = %user_id,
+            subscription_id = %subscription.id,
+            "Created webhook subscription"
+        );
+        Ok(subscription)
+    }
+
+    /// List a user's subscriptions (with secrets), oldest first
+    pub fn subscriptions(&self, user_id: &str) -> Result<Vec<WebhookSubscription>> {
+        let prefix = format!("sub:{user_id}:");
+        let mut subs = Vec::new();
+        for item in self.db.prefix_iterator(prefix.as_bytes()) {
+            let (key, value) = item?;
+            if !key.starts_with(prefix.as_bytes()) {
+                break;
+            }
+            match serde_json::from_slice::<WebhookSubscription>(&value) {
+                Ok(sub) => subs.push(sub),
+                Err(e) => tracing::warn!("Skipping corrupt webhook subscription: {}", e),
+            }
+        }
+        subs.sort_by_key(|s| s.created_at);
+        Ok(subs)
+    }
+
+    /// Remove a subscription. Already queued deliveries are still attempted.
+    pub fn unsubscribe(&self, user_id: &str, subscription_id: &str) -> Result<bool> {
+        let key = format!("sub:{user_id}:{subscription_id}");
+        if self.db.get(key.as_bytes())?.is_none() {
+            return Ok(false);
+        }
+        self.db.delete(key.as_bytes())?;
+        tracing::info!(
+            user_id = %user_id,
+            subscription_id = %subscription_id,
+            "Deleted webhook subscription"
+        );
+        Ok(true)
+    }
+
+    /// Whether any of the user's subscriptions wants `event`
+    pub fn has_subscribers(&self, user_id: &str, event: WebhookEventType) -> bool {
+        self.subscriptions(user_id)
+            .map(|subs| subs.iter().any(|s| s.wants(event)))
+            .unwrap_or(false)
+    }
+
+    // =========================================================================
+    // QUEUE
+    // =========================================================================
+
+    /// Queue `data` for every subscription of `user_id` that wants `event`.
+    ///
+    /// Returns the number of deliveries queued.
+    pub fn enqueue(
+        &self,
+        user_id: &str,
+        event: WebhookEventType,
+        data: serde_json::Value,
+    ) -> Result<usize> {
+        let subs = self.subscriptions(user_id)?;
+        let now = Utc::now();
+        let mut batch = WriteBatch::default();
+        let mut queued = 0;
+
+        for sub in subs.iter().filter(|s| s.wants(event)) {
+            let id = format!("dlv_{}", uuid::Uuid::new_v4().simple());
+            let delivery = WebhookDelivery {
+                payload: serde_json::json!({
+                    "id": id,
+                    "event": event,
+                    "user_id": user_id,
+                    "created_at": now,
+                    "data": data,
+                }),
+                id,
+                subscription_id: sub.id.clone(),
+                user_id: user_id.to_string(),
+                url: sub.url.clone(),
+                event_type: event,
+                status: DeliveryStatus::Pending,
+                attempts: 0,
+                created_at: now,
+                next_attempt_at: Some(now),
+                last_attempt_at: None,
+                last_status_code: None,
+                last_error: None,
+                delivered_at: None,
+            };
+            batch.put(
+                format!("delivery:{}", delivery.id).as_bytes(),
+                serde_json::to_vec(&delivery)?,
+            );
+            batch.put(queue_key(now, &delivery.id).as_bytes(), b"");
+            batch.put(log_key(&delivery).as_bytes(), b"");
+            queued += 1;
+        }
+
+        if queued > 0 {
+            self.db
+                .write(batch)
+                .context("Failed to queue webhook deliveries")?;
+            self.prune_log(user_id)?;
+            self.notify.notify_one();
+        }
+        Ok(queued)
+    }
+
+    /// Deliveries whose next attempt is due, earliest first
+    pub fn due_deliveries(&self, now: DateTime<Utc>, limit: usize) -> Result<Vec<WebhookDelivery>> {
+        let cutoff = format!("queue:{:020}", nanos(now));
+        let mut due = Vec::new();
+        for item in self.db.prefix_iterator(b"queue:") {
+            let (key, _) = item?;
+            if !key.starts_with(b"queue:") || key.as_ref() > cutoff.as_bytes() || due.len() >= limit
+            {
+                break;
+            }
+            let key = String::from_utf8_lossy(&key);
+            let Some(delivery_id) = key.rsplit(':').next() else {
+                continue;
+            };
+            match self.get_delivery(delivery_id)? {
+                Some(delivery) => due.push(delivery),
+                // Orphaned queue entry - drop it
+                None => self.db.delete(key.as_bytes())?,
+            }
+        }
+        Ok(due)
+    }
+
+    pub fn get_delivery(&self, delivery_id: &str) -> Result<Option<WebhookDelivery>> {
+        match self.db.get(format!("delivery:{delivery_id}").as_bytes())? {
+            Some(bytes) => Ok(Some(
+                serde_json::from_slice(&bytes).context("Corrupt webhook delivery")?,
+            )),
+            None => Ok(None),
+        }
+    }
+
+    /// Record a successful attempt and take the delivery off the queue
+    pub fn record_success(&self, delivery: &WebhookDelivery, status_code: u16) -> Result<()> {
+        let mut updated = delivery.clone();
+        let now = Utc::now();
+        updated.attempts += 1;
+        updated.status = DeliveryStatus::Delivered;
+        updated.last_attempt_at = Some(now);
+        updated.last_status_code = Some(status_code);
+        updated.last_error = None;
+        updated.delivered_at = Some(now);
+        updated.next_attempt_at = None;
+
+        let mut batch = WriteBatch::default();
+        if let Some(due) = delivery.next_attempt_at {
+            batch.delete(queue_key(due, &delivery.id).as_bytes());
+        }
+        batch.put(
+            format!("delivery:{}", updated.id).as_bytes(),
+            serde_json::to_vec(&updated)?,
+        );
+        self.db
+            .write(batch)
+            .context("Failed to record webhook delivery")?;
+        Ok(())
+    }
+
+    /// Record a failed attempt: reschedule with backoff, or dead-letter the
+    /// delivery once it has used up its attempts
+    pub fn record_failure(
+        &self,
+        delivery: &WebhookDelivery,
+        status_code: Option<u16>,
+        error: &str,
+    ) -> Result<WebhookDelivery> {
+        let mut updated = delivery.clone();
+        let now = Utc::now();
+        updated.attempts += 1;
+        updated.last_attempt_at = Some(now);
+        updated.last_status_code = status_code;
+        updated.last_error = Some(error.chars().take(MAX_ERROR_LEN).collect());
+
+        let mut batch = WriteBatch::default();
+        if let Some(due) = delivery.next_attempt_at {
+            batch.delete(queue_key(due, &delivery.id).as_bytes());
+        }
+        if updated.attempts >= MAX_DELIVERY_ATTEMPTS {
+            updated.status = DeliveryStatus::Failed;
+            updated.next_attempt_at = None;
+            batch.put(dead_key(&updated.user_id, &updated.id).as_bytes(), b"");
+            tracing::warn!(
+                delivery_id = %updated.id,
+                url = %updated.url,
+                "Webhook delivery dead-lettered after {} attempts",
+                updated.attempts
+            );
+        } else {
+            let next = now + retry_delay(updated.attempts);
+            updated.next_attempt_at = Some(next);
+            batch.put(queue_key(next, &updated.id).as_bytes(), b"");
+        }
+        batch.put(
+            format!("delivery:{}", updated.id).as_bytes(),
+            serde_json::to_vec(&updated)?,
+        );
+        self.db
+            .write(batch)
+            .context("Failed to record webhook delivery")?;
+        Ok(updated)
+    }
+
+    // =========================================================================
+    // DELIVERY LOG & DEAD LETTERS
+    // =========================================================================
+
+    /// Most recent deliveries for a user, newest first
+    pub fn delivery_log(
+        &self,
+        user_id: &str,
+        subscription_id: Option<&str>,
+        limit: usize,
+    ) -> Result<Vec<WebhookDelivery>> {
+        let prefix = format!("log:{user_id}:");
+        // Seek just past the prefix and walk backwards for newest-first order
+        let upper = format!("log:{user_id};");
+        let iter = self
+            .db
+            .iterator(IteratorMode::From(upper.as_bytes(), Direction::Reverse));
+
+        let mut deliveries = Vec::new();
+        for item in iter {
+            let (key, _) = item?;
+            if !key.starts_with(prefix.as_bytes()) {
+                if key.as_ref() < prefix.as_bytes() {
+                    break;
+                }
+                continue;
+            }
+            let key = String::from_utf8_lossy(&key);
+            let Some(delivery_id) = key.rsplit(':').next() else {
+                continue;
+            };
+            if let Some(delivery) = self.get_delivery(delivery_id)? {
+                if subscription_id.is_none_or(|s| s == delivery.subscription_id) {
+                    deliveries.push(delivery);
+                    if deliveries.len() >= limit {
+                        break;
+                    }
+                }
+            }
+        }
+        Ok(deliveries)
+    }
+
+    /// Dead-lettered deliveries for a user
+    pub fn dead_letters(&self, user_id: &str) -> Result<Vec<WebhookDelivery>> {
+        let prefix = format!("dead:{user_id}:");
+        let mut deliveries = Vec::new();
+        for item in self.db.prefix_iterator(prefix.as_bytes()) {
+            let (key, _) = item?;
+            if !key.starts_with(prefix.as_bytes()) {
+                break;
+            }
+            let delivery_id = String::from_utf8_lossy(&key[prefix.len()..]).to_string();
+            if let Some(delivery) = self.get_delivery(&delivery_id)? {
+                deliveries.push(delivery);
+            }
+        }
+        deliveries.sort_by_key(|d| std::cmp::Reverse(d.created_at));
+        Ok(deliveries)
+    }
+
+    /// Move a dead letter back onto the queue with a fresh attempt budget
+    pub fn retry_dead_letter(
+        &self,
+        user_id: &str,
+        delivery_id: &str,
+    ) -> Result<Option<WebhookDelivery>> {
+        let dead = dead_key(user_id, delivery_id);
+        if self.db.get(dead.as_bytes())?.is_none() {
+            return Ok(None);
+        }
+        let Some(mut delivery) = self.get_delivery(delivery_id)? else {
+            return Ok(None);
+        };
+
+        let now = Utc::now();
+        delivery.status = DeliveryStatus::Pending;
+        delivery.attempts = 0;
+        delivery.next_attempt_at = Some(now);
+
+        let mut batch = WriteBatch::default();
+        batch.delete(dead.as_bytes());
+        batch.put(queue_key(now, delivery_id).as_bytes(), b"");
+        batch.put(
+            format!("delivery:{delivery_id}").as_bytes(),
+            serde_json::to_vec(&delivery)?,
+        );
+        self.db
+            .write(batch)
+            .context("Failed to re-queue dead letter")?;
+        self.notify.notify_one();
+        Ok(Some(delivery))
+    }
+
+    /// Drop the oldest finished log entries beyond [`MAX_LOG_ENTRIES_PER_USER`].
+    /// Pending and dead-lettered deliveries are kept.
+    fn prune_log(&self, user_id: &str) -> Result<()> {
+        let prefix = format!("log:{user_id}:");
+        let keys: Vec<Box<[u8]>> = self
+            .db
+            .prefix_iterator(prefix.as_bytes())
+            .map_while(|item| item.ok())
+            .take_while(|(key, _)| key.starts_with(prefix.as_bytes()))
+            .map(|(key, _)| key)
+            .collect();
+        let excess = keys.len().saturating_sub(MAX_LOG_ENTRIES_PER_USER);
+        if excess == 0 {
+            return Ok(());
+        }
+
+        let mut batch = WriteBatch::default();
+        for key in keys.iter().take(excess) {
+            let key_str = String::from_utf8_lossy(key);
+            let Some(delivery_id) = key_str.rsplit(':').next() else {
+                continue;
+            };
+            let finished = self
+                .get_delivery(delivery_id)?
+                .is_none_or(|d| d.status == DeliveryStatus::Delivered);
+            if finished {
+                batch.delete(key);
+                batch.delete(format!("delivery:{delivery_id}").as_bytes());
+            }
+        }
+        self.db.write(batch)?;
+        Ok(())
+    }
+
+    /// Wait until new deliveries are queued (or a retry is requested)
+    pub async fn notified(&self) {
+        self.notify.notified().await
+    }
+}
+
+/// Sends queued deliveries over HTTP
+pub struct WebhookDispatcher {
+    store: Arc<WebhookStore>,
+    client: reqwest::Client,
+}
+
+impl WebhookDispatcher {
+    pub fn new(store: Arc<WebhookStore>) -> Result<Self> {
+        let resolver = PublicResolver {
+            policy: store.target_policy.clone(),
+        };
+        let client = reqwest::Client::builder()
+            .timeout(std::time::Duration::from_secs(DELIVERY_TIMEOUT_SECS))
+            .user_agent(concat!("shodh-memory-webhooks/", env!("CARGO_PKG_VERSION")))
+            .dns_resolver(Arc::new(resolver))
+            // A redirect could point at an internal IP literal, which skips the resolver
+            .redirect(reqwest::redirect::Policy::none())
+            .build()
+            .context("Failed to build webhook HTTP client")?;
+        Ok(Self { store, client })
+    }
+
+    /// Attempt every due delivery once. Returns the number attempted.
+    pub async fn dispatch_due(&self) -> Result<usize> {
+        let store = Arc::clone(&self.store);
+        let due = tokio::task::spawn_blocking(move || {
+            store.due_deliveries(Utc::now(), DISPATCH_BATCH_SIZE)
+        })
+        .await
+        .context("Webhook queue scan panicked")??;
+
+        let attempted = due.len();
+        futures::future::join_all(due.iter().map(|d| self.attempt(d))).await;
+        Ok(attempted)
+    }
+
+    async fn attempt(&self, delivery: &WebhookDelivery) {
+        // Sign with the current secret; a deleted subscription dead-letters
+        // its remaining deliveries on their next attempt
+        let secret = self
+            .store
+            .subscriptions(&delivery.user_id)
+            .ok()
+            .and_then(|subs| subs.into_iter().find(|s| s.id == delivery.subscription_id))
+            .map(|s| s.secret);
+        let Some(secret) = secret else {
+            let mut exhausted = delivery.clone();
+            exhausted.attempts = MAX_DELIVERY_ATTEMPTS.saturating_sub(1);
+            if let Err(e) = self
+                .store
+                .record_failure(&exhausted, None, "subscription deleted")
+            {
+                tracing::warn!("Failed to record webhook failure: {}", e);
+            }
+            return;
+        };
+
+        let literal_check = reqwest::Url::parse(&delivery.url)
+            .map_err(anyhow::Error::from)
+            .and_then(|url| self.store.target_policy.check_literal(&url));
+        if let Err(e) = literal_check {
+            let _ = self.store.record_failure(delivery, None, &format!("{e:#}"));
+            return;
+        }
+
+        let body = match serde_json::to_vec(&delivery.payload) {
+            Ok(body) => body,
+            Err(e) => {
+                tracing::error!("Unserializable webhook payload {}: {}", delivery.id, e);
+                let _ = self.store.record_failure(delivery, None, &e.to_string());
+                return;
+            }
+        };
+
+        let result = self
+            .client
+            .post(&delivery.url)
+            .header("Content-Type", "application/json")
+            .header(SIGNATURE_HEADER, sign_payload(&secret, &body))
+            .header(EVENT_HEADER, delivery.event_type.as_str())
+            .header(DELIVERY_HEADER, &delivery.id)
+            .body(body)
+            .send()
+            .await;
+
+        let recorded = match result {
+            Ok(resp) if resp.status().is_success() => {
+                self.store.record_success(delivery, resp.status().as_u16())
+            }
+            // The response body is not kept: the log is readable over the API
+            Ok(resp) => {
+                let status = resp.status().as_u16();
+                self.store
+                    .record_failure(delivery, Some(status), &format!("HTTP {status}"))
+                    .map(|_| ())
+            }
+            Err(e) => self
+                .store
+                .record_failure(delivery, None, &format!("{:#}", anyhow::Error::from(e)))
+                .map(|_| ()),
+        };
+        if let Err(e) = recorded {
+            tracing::warn!("Failed to record webhook delivery {}: {}", delivery.id, e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::dns::Resolve;
+    use tempfile::TempDir;
+
+    /// Store whose test endpoints skip DNS: example.com is allowlisted
+    fn store() -> (WebhookStore, TempDir) {
+        let dir = TempDir::new().unwrap();
+        let store = WebhookStore::new(dir.path())
+            .unwrap()
+            .with_allowed_hosts(["example.com".to_string()]);
+        (store, dir)
+    }
+
+    /// Local HTTP receiver answering every POST to /hook with `status`
+    async fn receiver(
+        status: axum::http::StatusCode,
+    ) -> (
+        SocketAddr,
+        tokio::sync::mpsc::UnboundedReceiver<(axum::http::HeaderMap, axum::body::Bytes)>,
+    ) {
+        use axum::{body::Bytes, http::HeaderMap, routing::post, Router};
+
+        let (tx, rx) = tokio::sync::mpsc::unbounded_channel::<(HeaderMap, Bytes)>();
+        let app = Router::new().route(
+            "/hook",
+            post(move |headers: HeaderMap, body: Bytes| {
+                let tx = tx.clone();
+                async move {
+                    let _ = tx.send((headers, body));
+                    (status, "internal details the caller must not see")
+                }
+            }),
+        );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await });
+        (addr, rx)
+    }
+
+    #[test]
+    fn signature_roundtrip() {
+        let body = br#"{"event":"memory_created"}"#;
+        let sig = sign_payload("whsec_test", body);
+        assert!(sig.starts_with("sha256="));
+        assert!(verify_signature("whsec_test", body, &sig).unwrap());
+        assert!(!verify_signature("whsec_other", body, &sig).unwrap());
+        assert!(!verify_signature("whsec_test", b"tampered", &sig).unwrap());
+    }
+
+    #[test]
+    fn retry_delay_backs_off_and_caps() {
+        assert_eq!(retry_delay(1).num_seconds(), 10);
+        assert_eq!(retry_delay(2).num_seconds(), 20);
+        assert_eq!(retry_delay(4).num_seconds(), 80);
+        assert_eq!(retry_delay(30).num_seconds(), MAX_RETRY_DELAY_SECS);
+    }
+
+    #[test]
+    fn enqueue_respects_event_filter() {
+        let (store, _dir) = store();
+        store
+            .subscribe(
+                "alice",
+                "https://example.com/hook",
+                vec![WebhookEventType::TodoCompleted],
+                None,
+                None,
+            )
+            .unwrap();
+        store
+            .subscribe("alice", "https://example.com/all", vec![], None, None)
+            .unwrap();
+
+        let queued = store
+            .enqueue(
+                "alice",
+                WebhookEventType::MemoryCreated,
+                serde_json::json!({}),
+            )
+            .unwrap();
+        assert_eq!(queued, 1, "only the catch-all subscription matches");
+        let queued = store
+            .enqueue(
+                "alice",
+                WebhookEventType::TodoCompleted,
+                serde_json::json!({}),
+            )
+            .unwrap();
+        assert_eq!(queued, 2);
+        let queued = store
+            .enqueue(
+                "bob",
+                WebhookEventType::TodoCompleted,
+                serde_json::json!({}),
+            )
+            .unwrap();
+        assert_eq!(queued, 0);
+
+        assert_eq!(store.due_deliveries(Utc::now(), 10).unwrap().len(), 3);
+        assert_eq!(store.delivery_log("alice", None, 10).unwrap().len(), 3);
+    }
+
+    #[test]
+    fn rejects_non_http_urls() {
+        let (store, _dir) = store();
+        assert!(store
+            .subscribe("alice", "file:///etc/passwd", vec![], None, None)
+            .is_err());
+        assert!(store
+            .subscribe("alice", "not a url", vec![], None, None)
+            .is_err());
+    }
+
+    #[test]
+    fn internal_addresses_are_detected() {
+        for ip in [
+            "127.0.0.1",
+            "10.1.2.3",
+            "172.16.0.1",
+            "192.168.1.1",
+            "169.254.169.254",
+            "100.64.0.1",
+            "0.0.0.0",
+            "::1",
+            "::",
+            "fd00:ec2::254",
+            "fe80::1",
+            "::ffff:10.0.0.1",
+            "64:ff9b::7f00:1",
+        ] {
+            assert!(
+                is_internal_ip(ip.parse().unwrap()),
+                "{ip} should be internal"
+            );
+        }
+        for ip in ["93.184.216.34", "8.8.8.8", "2606:4700::1111"] {
+            assert!(
+                !is_internal_ip(ip.parse().unwrap()),
+                "{ip} should be public"
+            );
+        }
+    }
+
+    #[test]
+    fn rejects_internal_targets_unless_allowlisted() {
+        let dir = TempDir::new().unwrap();
+        let store = WebhookStore::new(dir.path()).unwrap();
+        for url in [
+            "http://169.254.169.254/latest/meta-data/",
+            "http://10.0.0.5:8080/hook",
+            "http://[::1]/hook",
+            "http://[::ffff:127.0.0.1]/hook",
+            "http://localhost:3030/api/keys",
+        ] {
+            let err = store
+                .subscribe("alice", url, vec![], None, None)
+                .expect_err(url);
+            assert!(err.to_string().contains("internal address"), "{url}: {err}");
+        }
+        drop(store);
+
+        let store = WebhookStore::new(dir.path())
+            .unwrap()
+            .with_allowed_hosts(["LOCALHOST".to_string(), "[::1]".to_string()]);
+        assert!(store
+            .subscribe("alice", "http://localhost:9000/hook", vec![], None, None)
+            .is_ok());
+        assert!(store
+            .subscribe("alice", "http://[::1]:9000/hook", vec![], None, None)
+            .is_ok());
+        assert!(store
+            .subscribe("alice", "http://127.0.0.1:9000/hook", vec![], None, None)
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn delivery_resolver_refuses_internal_addresses() {
+        let resolver = PublicResolver {
+            policy: WebhookTargetPolicy::default(),
+        };
+        let err = match resolver.resolve("localhost".parse().unwrap()).await {
+            Ok(_) => panic!("localhost resolved for delivery"),
+            Err(e) => e,
+        };
+        assert!(err.to_string().contains("internal address"), "{err}");
+
+        let allowed = PublicResolver {
+            policy: WebhookTargetPolicy::new(["localhost".to_string()]),
+        };
+        assert!(allowed.resolve("localhost".parse().unwrap()).await.is_ok());
+    }
+
+    #[test]
+    fn failures_back_off_then_dead_letter() {
+        let (store, _dir) = store();
+        store
+            .subscribe("alice", "https://example.com/hook", vec![], None, None)
+            .unwrap();
+        store
+            .enqueue(
+                "alice",
+                WebhookEventType::MemoryForgotten,
+                serde_json::json!({}),
+            )
+            .unwrap();
+
+        let mut delivery = store.due_deliveries(Utc::now(), 10).unwrap().remove(0);
+        delivery = store.record_failure(&delivery, Some(500), "boom").unwrap();
+        assert_eq!(delivery.status, DeliveryStatus::Pending);
+        assert!(
+            store.due_deliveries(Utc::now(), 10).unwrap().is_empty(),
+            "retry is scheduled in the future"
+        );
+
+        while delivery.status == DeliveryStatus::Pending {
+            delivery = store.record_failure(&delivery, None, "refused").unwrap();
+        }
+        assert_eq!(delivery.attempts, MAX_DELIVERY_ATTEMPTS);
+        assert_eq!(delivery.status, DeliveryStatus::Failed);
+        assert_eq!(store.dead_letters("alice").unwrap().len(), 1);
+
+        let retried = store
+            .retry_dead_letter("alice", &delivery.id)
+            .unwrap()
+            .unwrap();
+        assert_eq!(retried.status, DeliveryStatus::Pending);
+        assert!(store.dead_letters("alice").unwrap().is_empty());
+        assert_eq!(store.due_deliveries(Utc::now(), 10).unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn dispatcher_delivers_signed_payload() {
+        let (addr, mut rx) = receiver(axum::http::StatusCode::OK).await;
+
+        let dir = TempDir::new().unwrap();
+        let store = Arc::new(
+            WebhookStore::new(dir.path())
+                .unwrap()
+                .with_allowed_hosts(["127.0.0.1".to_string()]),
+        );
+        let sub = store
+            .subscribe(
+                "alice",
+                &format!("http://{addr}/hook"),
+                vec![],
+                None,
+                Some("whsec_fixed".to_string()),
+            )
+            .unwrap();
+        store
+            .enqueue(
+                "alice",
+                WebhookEventType::TodoCompleted,
+                serde_json::json!({"todo_id": "SHO-1"}),
+            )
+            .unwrap();
+
+        let dispatcher = WebhookDispatcher::new(Arc::clone(&store)).unwrap();
+        assert_eq!(dispatcher.dispatch_due().await.unwrap(), 1);
+
+        let (headers, body) = rx.recv().await.unwrap();
+        let signature = headers[SIGNATURE_HEADER].to_str().unwrap();
+        assert!(verify_signature(&sub.secret, &body, signature).unwrap());
+        assert_eq!(headers[EVENT_HEADER], "todo_completed");
+        let payload: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(payload["data"]["todo_id"], "SHO-1");
+
+        let log = store.delivery_log("alice", Some(&sub.id), 10).unwrap();
+        assert_eq!(log[0].status, DeliveryStatus::Delivered);
+        assert!(store.due_deliveries(Utc::now(), 10).unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn failed_delivery_records_status_only() {
+        let (addr, mut rx) = receiver(axum::http::StatusCode::INTERNAL_SERVER_ERROR).await;
+
+        let dir = TempDir::new().unwrap();
+        let store = Arc::new(
+            WebhookStore::new(dir.path())
+                .unwrap()
+                .with_allowed_hosts(["127.0.0.1".to_string()]),
+        );
+        let sub = store
+            .subscribe("alice", &format!("http://{addr}/hook"), vec![], None, None)
+            .unwrap();
+        store
+            .enqueue(
+                "alice",
+                WebhookEventType::MemoryCreated,
+                serde_json::json!({}),
+            )
+            .unwrap();
+
+        let dispatcher = WebhookDispatcher::new(Arc::clone(&store)).unwrap();
+        assert_eq!(dispatcher.dispatch_due().await.unwrap(), 1);
+        rx.recv().await.unwrap();
+
+        let log = store.delivery_log("alice", Some(&sub.id), 10).unwrap();
+        assert_eq!(log[0].status, DeliveryStatus::Pending);
+        assert_eq!(log[0].last_status_code, Some(500));
+        assert_eq!(log[0].last_error.as_deref(), Some("HTTP 500"));
+    }
+}
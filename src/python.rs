@@ -13,8 +13,10 @@ use pyo3::prelude::*;
 use std::collections::HashMap;
 use std::path::PathBuf;
 
+use crate::constants::DEFAULT_HEADING_TOLERANCE_DEGREES;
 use crate::memory::types::{
-    Experience, ExperienceType, ForgetCriteria, GeoFilter, Memory, MemoryId,
+    Experience, ExperienceType, ForgetCriteria, GeoFilter, HeadingCone, LocalRegion,
+    LocalSpatialFilter, Memory, MemoryId, LOCAL_FRAME_METADATA_KEY,
 };
 use crate::memory::{MemoryConfig, MemorySystem, Query, RetrievalMode};
 use chrono::{DateTime, Utc};
@@ -142,6 +144,104 @@ impl PyGeoFilter {
     }
 }
 
+// ============================================================================
+// LocalFilter - Spatial query filter in the robot's map frame
+// ============================================================================
+
+/// Filter memories by local position: radius, bounding box or k-nearest
+#[pyclass(name = "LocalFilter")]
+#[derive(Clone, Debug)]
+pub struct PyLocalFilter {
+    inner: LocalSpatialFilter,
+}
+
+#[pymethods]
+impl PyLocalFilter {
+    /// Memories within radius_meters of center
+    #[staticmethod]
+    #[pyo3(signature = (center, radius_meters, frame="map"))]
+    fn radius(center: &PyPosition, radius_meters: f32, frame: &str) -> PyResult<Self> {
+        if radius_meters.is_nan() || radius_meters <= 0.0 {
+            return Err(PyValueError::new_err("radius_meters must be positive"));
+        }
+        Ok(PyLocalFilter {
+            inner: LocalSpatialFilter::new(
+                frame,
+                LocalRegion::Radius {
+                    center: [center.x, center.y, center.z],
+                    radius_meters,
+                },
+            ),
+        })
+    }
+
+    /// Memories inside the axis-aligned box spanned by min and max
+    #[staticmethod]
+    #[pyo3(signature = (min, max, frame="map"))]
+    fn bbox(min: &PyPosition, max: &PyPosition, frame: &str) -> PyResult<Self> {
+        if min.x > max.x || min.y > max.y || min.z > max.z {
+            return Err(PyValueError::new_err("min must not exceed max"));
+        }
+        Ok(PyLocalFilter {
+            inner: LocalSpatialFilter::new(
+                frame,
+                LocalRegion::BoundingBox {
+                    min: [min.x, min.y, min.z],
+                    max: [max.x, max.y, max.z],
+                },
+            ),
+        })
+    }
+
+    /// The k memories closest to center
+    #[staticmethod]
+    #[pyo3(signature = (center, k, frame="map"))]
+    fn nearest(center: &PyPosition, k: usize, frame: &str) -> PyResult<Self> {
+        if k == 0 {
+            return Err(PyValueError::new_err("k must be positive"));
+        }
+        Ok(PyLocalFilter {
+            inner: LocalSpatialFilter::new(
+                frame,
+                LocalRegion::Nearest {
+                    center: [center.x, center.y, center.z],
+                    k,
+                },
+            ),
+        })
+    }
+
+    /// Copy of this filter that only matches memories facing within
+    /// tolerance_degrees of heading (default 45)
+    #[pyo3(signature = (heading, tolerance_degrees=None))]
+    fn with_heading(&self, heading: f32, tolerance_degrees: Option<f32>) -> PyResult<Self> {
+        let tolerance = tolerance_degrees.unwrap_or(DEFAULT_HEADING_TOLERANCE_DEGREES);
+        if !(0.0..=180.0).contains(&tolerance) {
+            return Err(PyValueError::new_err(
+                "tolerance_degrees must be between 0 and 180",
+            ));
+        }
+        Ok(PyLocalFilter {
+            inner: self
+                .inner
+                .clone()
+                .with_heading_cone(HeadingCone::new(heading, tolerance)),
+        })
+    }
+
+    #[getter]
+    fn frame(&self) -> String {
+        self.inner.frame.clone()
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "LocalFilter(frame={}, region={:?}, heading_cone={:?})",
+            self.inner.frame, self.inner.region, self.inner.heading_cone
+        )
+    }
+}
+
 // ============================================================================
 // DecisionContext - For action-outcome learning
 // ============================================================================
@@ -663,20 +763,34 @@ impl PyMemorySystem {
     }
 
     /// Record a waypoint event
-    #[pyo3(signature = (waypoint_id, status="reached", position=None, geo_location=None))]
+    ///
+    /// `frame` names the map frame of `position` (default "map"), so the waypoint
+    /// can be found again with a LocalFilter in that frame.
+    #[pyo3(signature = (
+        waypoint_id,
+        status="reached",
+        position=None,
+        geo_location=None,
+        heading=None,
+        frame=None
+    ))]
     fn record_waypoint(
         &mut self,
         waypoint_id: String,
         status: &str,
         position: Option<&PyPosition>,
         geo_location: Option<&PyGeoLocation>,
+        heading: Option<f32>,
+        frame: Option<String>,
     ) -> PyResult<String> {
+        let metadata =
+            frame.map(|frame| HashMap::from([(LOCAL_FRAME_METADATA_KEY.to_string(), frame)]));
         self.remember(
             format!("Waypoint {}: {}", waypoint_id, status),
             "task",
             position,
             geo_location,
-            None,
+            heading,
             Some("navigation".to_string()),
             None,
             None,
@@ -691,7 +805,7 @@ impl PyMemorySystem {
             None,
             Some(vec!["waypoint".to_string(), "navigation".to_string()]),
             Some(vec!["waypoint".to_string(), waypoint_id]),
-            None,
+            metadata,
         )
     }
 
@@ -716,7 +830,8 @@ impl PyMemorySystem {
         pattern_id=None,
         terrain_type=None,
         min_confidence=None,
-        max_confidence=None
+        max_confidence=None,
+        local_filter=None
     ))]
     #[allow(clippy::too_many_arguments)]
     fn recall(
@@ -737,7 +852,14 @@ impl PyMemorySystem {
         terrain_type: Option<String>,
         min_confidence: Option<f32>,
         max_confidence: Option<f32>,
+        local_filter: Option<&PyLocalFilter>,
     ) -> PyResult<Vec<HashMap<String, PyObject>>> {
+        if local_filter.is_some() && self.robot_id.is_none() {
+            return Err(PyValueError::new_err(
+                "local_filter requires a MemorySystem created with robot_id",
+            ));
+        }
+
         let retrieval_mode = match mode.to_lowercase().as_str() {
             "semantic" | "similarity" => RetrievalMode::Similarity,
             "temporal" => RetrievalMode::Temporal,
@@ -773,8 +895,12 @@ impl PyMemorySystem {
             mission_id,
             geo_filter: geo_filter
                 .map(|f| GeoFilter::new(f.latitude, f.longitude, f.radius_meters)),
+            local_filter: local_filter.map(|f| f.inner.clone()),
             action_type,
             reward_range: None,
+            agent_id: None,
+            run_id: None,
+            actor_id: None,
             outcome_type,
             failures_only,
             anomalies_only,
@@ -785,6 +911,7 @@ impl PyMemorySystem {
             confidence_range,
             prospective_signals: None,
             episode_id: None,
+            filter: None,
             max_results: limit,
             retrieval_mode,
             offset: 0,
@@ -834,6 +961,7 @@ impl PyMemorySystem {
             None,
             None,
             None,
+            None,
         )
     }
 
@@ -862,6 +990,7 @@ impl PyMemorySystem {
             None,
             None,
             None,
+            None,
         )
     }
 
@@ -890,6 +1019,7 @@ impl PyMemorySystem {
             None,
             None,
             None,
+            None,
         )
     }
 
@@ -917,6 +1047,7 @@ impl PyMemorySystem {
             None,
             None,
             None,
+            None,
         )
     }
 
@@ -1807,8 +1938,12 @@ impl PyMemorySystem {
             robot_id: self.robot_id.clone(),
             mission_id: None,
             geo_filter: None,
+            local_filter: None,
             action_type: None,
             reward_range: None,
+            agent_id: None,
+            run_id: None,
+            actor_id: None,
             outcome_type: None,
             failures_only: false,
             anomalies_only: false,
@@ -1819,6 +1954,7 @@ impl PyMemorySystem {
             confidence_range: None,
             prospective_signals: None,
             episode_id: None,
+            filter: None,
             max_results: max_results * 2, // Get more for filtering
             retrieval_mode: RetrievalMode::Hybrid,
             offset: 0,
@@ -2210,6 +2346,7 @@ fn shodh_memory(m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_class::<PyPosition>()?;
     m.add_class::<PyGeoLocation>()?;
     m.add_class::<PyGeoFilter>()?;
+    m.add_class::<PyLocalFilter>()?;
 
     // Decision & Learning types
     m.add_class::<PyDecisionContext>()?;
@@ -2224,6 +2361,7 @@ fn shodh_memory(m: &Bound<'_, PyModule>) -> PyResult<()> {
                        Features:\n\
                        - Position(x, y, z) for local coordinates\n\
                        - GeoLocation(lat, lon, alt) for GPS\n\
+                       - LocalFilter for radius, box and k-nearest queries in a map frame\n\
                        - DecisionContext for action-outcome learning\n\
                        - Outcome for decision results\n\
                        - Environment for weather, terrain, lighting\n\
@@ -4,6 +4,7 @@
 //! Provides better temporal reasoning and entity extraction than rule-based.
 
 use super::parser_trait::*;
+use crate::memory::Language;
 use chrono::{DateTime, NaiveDate, Utc};
 use parking_lot::Mutex;
 use serde::{Deserialize, Serialize};
@@ -415,11 +416,10 @@ fn extract_json(output: &str) -> String {
     }
 }
 
-/// Simple stemming using rust_stemmers
+/// Stem a word with the stemmer of its detected language
 fn stem_word(word: &str) -> String {
-    use rust_stemmers::{Algorithm, Stemmer};
-    let stemmer = Stemmer::create(Algorithm::English);
-    stemmer.stem(&word.to_lowercase()).to_string()
+    let word = word.to_lowercase();
+    Language::detect(&word).stemmer().stem(&word)
 }
 
 /// Parse entity type string
@@ -0,0 +1,444 @@
+//! Shared Memory Spaces - team memory that several users can belong to
+//!
+//! A space is a named memory store with its own `MemorySystem` and
+//! `GraphMemory`, kept under `{base_path}/spaces/{space_id}` and loaded through
+//! the same per-user cache as personal stores (keyed by [`storage_id`]). Users
+//! join a space as readers, writers or owners: readers can recall from it,
+//! writers can also `remember` into it, and owners manage membership.
+//!
+//! Recall across the caller's personal store and any number of spaces is
+//! merged with Reciprocal Rank Fusion ([`fuse_space_results`]), so each store
+//! contributes by rank rather than by raw score, which isn't comparable
+//! between independently indexed stores.
+//!
+//! Storage layout (RocksDB at `{base_path}/spaces/.registry`):
+//! - `space:{space_id}`            → JSON [`Space`] (includes the member map)
+//! - `member:{user_id}:{space_id}` → JSON [`SpaceRole`] (per-user index)
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use rocksdb::{Options, WriteBatch, DB};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+use std::sync::Arc;
+
+use crate::memory::{MemoryId, MemorySystem, Query, RRFusion, SharedMemory};
+
+/// Most spaces a single recall may fan out to
+pub const MAX_SPACES_PER_QUERY: usize = 8;
+
+/// RRF constant for cross-store fusion (the standard value from the RRF paper)
+pub const SPACE_RRF_K: f32 = 60.0;
+
+/// Directory under the server base path holding every space
+const SPACES_DIR: &str = "spaces";
+
+/// Cache/storage key for a space's memory system.
+///
+/// Contains a `/`, which user ids can never contain, so a space store can't be
+/// reached (or shadowed) through a personal `user_id`.
+pub fn storage_id(space_id: &str) -> String {
+    format!("{SPACES_DIR}/{space_id}")
+}
+
+/// Access level of a space member, ordered from least to most privileged
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SpaceRole {
+    /// Recall only
+    Read,
+    /// Recall and remember
+    Write,
+    /// Write plus membership management and deletion
+    Owner,
+}
+
+impl SpaceRole {
+    pub fn can_write(&self) -> bool {
+        *self >= Self::Write
+    }
+
+    pub fn can_manage(&self) -> bool {
+        *self == Self::Owner
+    }
+}
+
+/// A shared memory space
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Space {
+    pub id: String,
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    pub created_by: String,
+    pub created_at: DateTime<Utc>,
+    pub members: BTreeMap<String, SpaceRole>,
+}
+
+impl Space {
+    pub fn role_of(&self, user_id: &str) -> Option<SpaceRole> {
+        self.members.get(user_id).copied()
+    }
+
+    fn owner_count(&self) -> usize {
+        self.members.values().filter(|r| r.can_manage()).count()
+    }
+}
+
+/// Space registry: definitions and memberships
+pub struct SpaceStore {
+    db: Arc<DB>,
+    /// Serializes read-modify-write updates of a space record
+    write_lock: parking_lot::Mutex<()>,
+}
+
+impl SpaceStore {
+    /// Open (or create) the registry under `{storage_path}/spaces/.registry`
+    pub fn new(storage_path: &Path) -> Result<Self> {
+        let path = storage_path.join(SPACES_DIR).join(".registry");
+        std::fs::create_dir_all(&path)?;
+
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        opts.set_compression_type(rocksdb::DBCompressionType::Lz4);
+        opts.set_max_write_buffer_number(2);
+        opts.set_write_buffer_size(4 * 1024 * 1024);
+
+        let db = Arc::new(DB::open(&opts, &path).context("Failed to open space registry")?);
+
+        Ok(Self {
+            db,
+            write_lock: parking_lot::Mutex::new(()),
+        })
+    }
+
+    /// Flush to disk (graceful shutdown)
+    pub fn flush(&self) -> Result<()> {
+        self.db
+            .flush()
+            .map_err(|e| anyhow::anyhow!("Failed to flush space registry: {e}"))
+    }
+
+    /// Database reference for comprehensive backups
+    pub fn database(&self) -> &Arc<DB> {
+        &self.db
+    }
+
+    /// Create a space owned by `owner`. Fails if the id is taken.
+    pub fn create(
+        &self,
+        space_id: &str,
+        name: Option<String>,
+        description: Option<String>,
+        owner: &str,
+    ) -> Result<Space> {
+        let _guard = self.write_lock.lock();
+        if self.get(space_id)?.is_some() {
+            anyhow::bail!("Space '{space_id}' already exists");
+        }
+
+        let space = Space {
+            id: space_id.to_string(),
+            name: name
+                .filter(|n| !n.trim().is_empty())
+                .unwrap_or_else(|| space_id.to_string()),
+            description,
+            created_by: owner.to_string(),
+            created_at: Utc::now(),
+            members: BTreeMap::from([(owner.to_string(), SpaceRole::Owner)]),
+        };
+        self.write_space(&space, &[(owner, Some(SpaceRole::Owner))])?;
+
+        tracing::info!(space_id = %space_id, owner = %owner, "Created shared space");
+        Ok(space)
+    }
+
+    /// Look up a space by id
+    pub fn get(&self, space_id: &str) -> Result<Option<Space>> {
+        match self.db.get(format!("space:{space_id}").as_bytes())? {
+            Some(bytes) => Ok(Some(
+                serde_json::from_slice(&bytes).context("Corrupt space record")?,
+            )),
+            None => Ok(None),
+        }
+    }
+
+    /// A user's role in a space, or `None` if the space doesn't exist or they
+    /// aren't a member
+    pub fn role_of(&self, space_id: &str, user_id: &str) -> Result<Option<SpaceRole>> {
+        match self
+            .db
+            .get(format!("member:{user_id}:{space_id}").as_bytes())?
+        {
+            Some(bytes) => Ok(Some(serde_json::from_slice(&bytes)?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Spaces a user belongs to, with their role, ordered by space id
+    pub fn spaces_for_user(&self, user_id: &str) -> Result<Vec<(Space, SpaceRole)>> {
+        let prefix = format!("member:{user_id}:");
+        let mut spaces = Vec::new();
+        for item in self.db.prefix_iterator(prefix.as_bytes()) {
+            let (key, value) = item?;
+            if !key.starts_with(prefix.as_bytes()) {
+                break;
+            }
+            let space_id = String::from_utf8_lossy(&key[prefix.len()..]).to_string();
+            let role: SpaceRole = serde_json::from_slice(&value)?;
+            if let Some(space) = self.get(&space_id)? {
+                spaces.push((space, role));
+            }
+        }
+        Ok(spaces)
+    }
+
+    /// Add a member or change their role.
+    ///
+    /// Demoting the last owner is refused so a space can't become unmanageable.
+    pub fn set_member(&self, space_id: &str, user_id: &str, role: SpaceRole) -> Result<Space> {
+        let _guard = self.write_lock.lock();
+        let mut space = self
+            .get(space_id)?
+            .with_context(|| format!("Space '{space_id}' not found"))?;
+
+        if space.role_of(user_id) == Some(SpaceRole::Owner)
+            && role != SpaceRole::Owner
+            && space.owner_count() == 1
+        {
+            anyhow::bail!("Cannot demote the last owner of space '{space_id}'");
+        }
+
+        space.members.insert(user_id.to_string(), role);
+        self.write_space(&space, &[(user_id, Some(role))])?;
+        Ok(space)
+    }
+
+    /// Remove a member. Returns `false` if they weren't one.
+    ///
+    /// Removing the last owner is refused; delete the space instead.
+    pub fn remove_member(&self, space_id: &str, user_id: &str) -> Result<bool> {
+        let _guard = self.write_lock.lock();
+        let mut space = self
+            .get(space_id)?
+            .with_context(|| format!("Space '{space_id}' not found"))?;
+
+        match space.role_of(user_id) {
+            None => return Ok(false),
+            Some(SpaceRole::Owner) if space.owner_count() == 1 => {
+                anyhow::bail!("Cannot remove the last owner of space '{space_id}'");
+            }
+            Some(_) => {}
+        }
+
+        space.members.remove(user_id);
+        self.write_space(&space, &[(user_id, None)])?;
+        Ok(true)
+    }
+
+    /// Delete a space record and its membership index. The caller is
+    /// responsible for removing the space's memory data.
+    pub fn delete(&self, space_id: &str) -> Result<Option<Space>> {
+        let _guard = self.write_lock.lock();
+        let Some(space) = self.get(space_id)? else {
+            return Ok(None);
+        };
+
+        let mut batch = WriteBatch::default();
+        batch.delete(format!("space:{space_id}").as_bytes());
+        for user_id in space.members.keys() {
+            batch.delete(format!("member:{user_id}:{space_id}").as_bytes());
+        }
+        self.db
+            .write(batch)
+            .context("Failed to delete space record")?;
+
+        tracing::info!(space_id = %space_id, "Deleted shared space");
+        Ok(Some(space))
+    }
+
+    /// Persist a space record together with membership index changes
+    /// (`None` removes the index entry) in one atomic batch
+    fn write_space(&self, space: &Space, changes: &[(&str, Option<SpaceRole>)]) -> Result<()> {
+        let mut batch = WriteBatch::default();
+        batch.put(
+            format!("space:{}", space.id).as_bytes(),
+            serde_json::to_vec(space)?,
+        );
+        for (user_id, role) in changes {
+            let key = format!("member:{user_id}:{}", space.id);
+            match role {
+                Some(role) => batch.put(key.as_bytes(), serde_json::to_vec(role)?),
+                None => batch.delete(key.as_bytes()),
+            }
+        }
+        self.db
+            .write(batch)
+            .context("Failed to persist space record")
+    }
+}
+
+/// A recalled memory tagged with the store it came from
+#[derive(Debug, Clone)]
+pub struct SpaceHit {
+    pub memory: SharedMemory,
+    /// Space id, or `None` for the caller's personal store
+    pub space: Option<String>,
+    /// Fused RRF score
+    pub score: f32,
+}
+
+/// Merge per-store recall results with Reciprocal Rank Fusion.
+///
+/// Each input list is one store's results in rank order. Stores are weighted
+/// equally. A memory appearing in several lists (only possible for imported
+/// copies sharing an id) keeps the first store it was seen in.
+pub fn fuse_space_results(
+    lists: Vec<(Option<String>, Vec<SharedMemory>)>,
+    k: f32,
+) -> Vec<SpaceHit> {
+    let mut origin: HashMap<MemoryId, (SharedMemory, Option<String>)> = HashMap::new();
+    let mut ranked_lists = Vec::with_capacity(lists.len());
+
+    for (space, memories) in lists {
+        let mut ranked = Vec::with_capacity(memories.len());
+        for memory in memories {
+            ranked.push((memory.id.clone(), memory.get_score().unwrap_or(0.0)));
+            origin
+                .entry(memory.id.clone())
+                .or_insert_with(|| (memory, space.clone()));
+        }
+        ranked_lists.push(ranked);
+    }
+
+    let rrf = RRFusion::new(k, vec![1.0; ranked_lists.len()]);
+    rrf.fuse(ranked_lists)
+        .into_iter()
+        .filter_map(|(id, score)| {
+            origin.remove(&id).map(|(memory, space)| SpaceHit {
+                memory,
+                space,
+                score,
+            })
+        })
+        .collect()
+}
+
+/// Recall from the caller's personal store and each of `spaces`.
+///
+/// With no spaces this is a plain personal recall, scores untouched. Otherwise
+/// every store is queried independently (each under its own storage id, so
+/// temporal fact lookups hit the right store) and the results are fused with
+/// [`fuse_space_results`], truncated to `query.max_results`. A store whose
+/// recall fails contributes nothing rather than failing the whole request.
+pub fn recall_across(
+    personal: &parking_lot::RwLock<MemorySystem>,
+    spaces: &[(String, Arc<parking_lot::RwLock<MemorySystem>>)],
+    query: &Query,
+) -> Vec<SpaceHit> {
+    let personal_results = personal.read().recall(query).unwrap_or_else(|e| {
+        tracing::debug!("Personal recall failed: {}", e);
+        Vec::new()
+    });
+    if spaces.is_empty() {
+        return personal_results
+            .into_iter()
+            .map(|memory| SpaceHit {
+                score: memory.get_score().unwrap_or(0.0),
+                memory,
+                space: None,
+            })
+            .collect();
+    }
+
+    let mut lists = vec![(None, personal_results)];
+    for (space_id, memory) in spaces {
+        let space_query = Query {
+            user_id: Some(storage_id(space_id)),
+            ..query.clone()
+        };
+        let results = memory.read().recall(&space_query).unwrap_or_else(|e| {
+            tracing::debug!(space_id = %space_id, "Space recall failed: {}", e);
+            Vec::new()
+        });
+        lists.push((Some(space_id.clone()), results));
+    }
+
+    let mut hits = fuse_space_results(lists, SPACE_RRF_K);
+    hits.truncate(query.max_results);
+    hits
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::memory::{Experience, Memory};
+    use tempfile::TempDir;
+
+    fn store() -> (SpaceStore, TempDir) {
+        let dir = TempDir::new().unwrap();
+        (SpaceStore::new(dir.path()).unwrap(), dir)
+    }
+
+    fn memory(content: &str) -> SharedMemory {
+        let experience = Experience {
+            content: content.to_string(),
+            ..Default::default()
+        };
+        Arc::new(Memory::new(
+            MemoryId(uuid::Uuid::new_v4()),
+            experience,
+            0.5,
+            None,
+            None,
+            None,
+            None,
+        ))
+    }
+
+    #[test]
+    fn membership_lifecycle() {
+        let (store, _dir) = store();
+        store.create("team", None, None, "alice").unwrap();
+        assert!(store.create("team", None, None, "bob").is_err());
+
+        store.set_member("team", "bob", SpaceRole::Read).unwrap();
+        assert_eq!(store.role_of("team", "bob").unwrap(), Some(SpaceRole::Read));
+        assert_eq!(store.spaces_for_user("bob").unwrap().len(), 1);
+
+        // The last owner can't leave or be demoted
+        assert!(store.remove_member("team", "alice").is_err());
+        assert!(store.set_member("team", "alice", SpaceRole::Write).is_err());
+
+        assert!(store.remove_member("team", "bob").unwrap());
+        assert!(!store.remove_member("team", "bob").unwrap());
+        assert!(store.spaces_for_user("bob").unwrap().is_empty());
+
+        let deleted = store.delete("team").unwrap().unwrap();
+        assert_eq!(deleted.members.len(), 1);
+        assert!(store.get("team").unwrap().is_none());
+        assert!(store.role_of("team", "alice").unwrap().is_none());
+    }
+
+    #[test]
+    fn fusion_interleaves_stores_by_rank() {
+        let personal = vec![memory("p1"), memory("p2")];
+        let shared = vec![memory("s1")];
+        let hits = fuse_space_results(
+            vec![(None, personal), (Some("team".to_string()), shared)],
+            60.0,
+        );
+
+        assert_eq!(hits.len(), 3);
+        // Both rank-1 results outscore the personal rank-2 result
+        assert_eq!(hits[2].memory.experience.content, "p2");
+        assert!(hits[2].space.is_none());
+        let s1 = hits
+            .iter()
+            .find(|h| h.memory.experience.content == "s1")
+            .unwrap();
+        assert_eq!(s1.space.as_deref(), Some("team"));
+        assert!(hits[0].score > hits[2].score);
+    }
+}
@@ -1242,7 +1242,7 @@ impl StreamingMemoryExtractor {
             let guard = memory_for_embed.read();
             guard
                 .compute_embedding(&content_for_embed)
-                .unwrap_or_else(|_| vec![0.0; 384])
+                .unwrap_or_else(|_| vec![0.0; guard.get_embedder().dimension()])
         })
         .await
         .ok()?;
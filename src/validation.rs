@@ -6,6 +6,7 @@ use regex::Regex;
 
 /// Maximum lengths for security
 pub const MAX_USER_ID_LENGTH: usize = 128;
+pub const MAX_SPACE_ID_LENGTH: usize = 64;
 pub const MAX_CONTENT_LENGTH: usize = 50_000; // 50KB
 pub const MAX_PATTERN_LENGTH: usize = 256; // Max regex pattern length
 pub const MAX_ENTITY_LENGTH: usize = 256; // Max entity name length
@@ -14,6 +15,23 @@ pub const MAX_METADATA_SIZE: usize = 10_000; // Max metadata JSON size (10KB)
 #[allow(unused)] // Public API - available for validation
 pub const MAX_ENTITIES_PER_MEMORY: usize = 50; // Max entities per memory
 
+/// Shared store directories that sit next to the per-user directories under
+/// the server base path; a user with one of these ids would collide with them
+pub const RESERVED_USER_IDS: &[&str] = &[
+    "ab_tests",
+    "api_keys",
+    "audit_logs",
+    "backups",
+    "feedback",
+    "files",
+    "prospective",
+    "semantic_facts",
+    "sessions",
+    "spaces",
+    "todos",
+    "webhooks",
+];
+
 /// Validate user_id
 pub fn validate_user_id(user_id: &str) -> Result<()> {
     if user_id.is_empty() {
@@ -50,6 +68,15 @@ pub fn validate_user_id(user_id: &str) -> Result<()> {
         return Err(anyhow!("user_id cannot start or end with a dot"));
     }
 
+    // Reject names of shared store directories (case-insensitively, for
+    // case-insensitive filesystems)
+    if RESERVED_USER_IDS
+        .iter()
+        .any(|reserved| user_id.eq_ignore_ascii_case(reserved))
+    {
+        return Err(anyhow!("user_id is reserved: {}", user_id));
+    }
+
     // Reject absolute paths — PathBuf::join with an absolute path ignores the base
     if std::path::Path::new(user_id).is_absolute() {
         return Err(anyhow!("user_id cannot be an absolute path"));
@@ -76,6 +103,39 @@ pub fn validate_user_id(user_id: &str) -> Result<()> {
     Ok(())
 }
 
+/// Validate a shared space id
+///
+/// Stricter than user ids: space ids name a directory under `spaces/`, so they
+/// are limited to lowercase ASCII letters, digits, `-` and `_`.
+pub fn validate_space_id(space_id: &str) -> Result<()> {
+    if space_id.is_empty() {
+        return Err(anyhow!("space_id cannot be empty"));
+    }
+
+    if space_id.len() > MAX_SPACE_ID_LENGTH {
+        return Err(anyhow!(
+            "space_id too long: {} chars (max: {})",
+            space_id.len(),
+            MAX_SPACE_ID_LENGTH
+        ));
+    }
+
+    if !space_id
+        .chars()
+        .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-' || c == '_')
+    {
+        return Err(anyhow!(
+            "space_id contains invalid characters (allowed: a-z, 0-9, -, _)"
+        ));
+    }
+
+    if !space_id.starts_with(|c: char| c.is_ascii_alphanumeric()) {
+        return Err(anyhow!("space_id must start with a letter or digit"));
+    }
+
+    Ok(())
+}
+
 /// Validate memory_id (UUID format)
 pub fn validate_memory_id(memory_id: &str) -> Result<uuid::Uuid> {
     uuid::Uuid::parse_str(memory_id).map_err(|e| anyhow!("Invalid memory_id UUID format: {e}"))
@@ -358,6 +418,27 @@ mod tests {
         assert!(validate_user_id(&"a".repeat(200)).is_err()); // too long
     }
 
+    #[test]
+    fn test_reserved_user_ids() {
+        assert!(validate_user_id("spaces").is_err());
+        assert!(validate_user_id("Spaces").is_err());
+        assert!(validate_user_id("backups").is_err());
+        assert!(validate_user_id("webhooks").is_err());
+        assert!(validate_user_id("spaces-team").is_ok());
+    }
+
+    #[test]
+    fn test_space_id() {
+        assert!(validate_space_id("team-alpha").is_ok());
+        assert!(validate_space_id("proj_42").is_ok());
+        assert!(validate_space_id("").is_err());
+        assert!(validate_space_id("Team").is_err()); // uppercase
+        assert!(validate_space_id("_registry").is_err()); // leading underscore
+        assert!(validate_space_id(".registry").is_err()); // leading dot
+        assert!(validate_space_id("a/b").is_err());
+        assert!(validate_space_id(&"a".repeat(65)).is_err());
+    }
+
     #[test]
     fn test_path_traversal_prevention() {
         assert!(validate_user_id("user..admin").is_err()); // path traversal
@@ -46,6 +46,7 @@ pub use spann::{SpannConfig, SpannIndex};
 pub use vamana::{DistanceMetric, VamanaConfig, VamanaIndex, REBUILD_THRESHOLD};
 
 use anyhow::Result;
+use std::collections::HashSet;
 use std::path::Path;
 
 /// Threshold for auto-selecting SPANN over Vamana
@@ -169,6 +170,22 @@ impl VectorIndexBackend {
         }
     }
 
+    /// Search for k nearest neighbors among an allow-list of vector IDs
+    ///
+    /// The filter is applied inside the index traversal, so selective filters
+    /// still return k results when that many allowed vectors exist.
+    pub fn search_filtered(
+        &self,
+        query: &[f32],
+        k: usize,
+        allowed: &HashSet<u32>,
+    ) -> Result<Vec<(u32, f32)>> {
+        match self {
+            Self::Vamana(idx) => idx.search_filtered(query, k, allowed),
+            Self::Spann(idx) => idx.search_filtered(query, k, allowed),
+        }
+    }
+
     /// Number of vectors in the index
     pub fn len(&self) -> usize {
         match self {
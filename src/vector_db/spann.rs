@@ -63,7 +63,7 @@ use anyhow::{anyhow, Result};
 use memmap2::{Mmap, MmapMut};
 use parking_lot::RwLock;
 use rand::seq::SliceRandom;
-use std::collections::BinaryHeap;
+use std::collections::{BinaryHeap, HashSet};
 use std::fs::{File, OpenOptions};
 use std::path::Path;
 use std::sync::atomic::{AtomicUsize, Ordering};
@@ -558,12 +558,38 @@ impl SpannIndex {
 
     /// Search for k nearest neighbors
     pub fn search(&self, query: &[f32], k: usize) -> Result<Vec<(u32, f32)>> {
+        self.probe_search(query, k, None)
+    }
+
+    /// Search for k nearest neighbors among an allow-list of vector IDs
+    ///
+    /// Posting entries outside `allowed` are skipped while scanning, and
+    /// partitions beyond `num_probes` are probed in centroid order until k
+    /// allowed vectors are found, so selective filters still fill the result.
+    pub fn search_filtered(
+        &self,
+        query: &[f32],
+        k: usize,
+        allowed: &HashSet<u32>,
+    ) -> Result<Vec<(u32, f32)>> {
+        if allowed.is_empty() {
+            return Ok(Vec::new());
+        }
+        self.probe_search(query, k, Some(allowed))
+    }
+
+    fn probe_search(
+        &self,
+        query: &[f32],
+        k: usize,
+        allowed: Option<&HashSet<u32>>,
+    ) -> Result<Vec<(u32, f32)>> {
         let centroids = self.centroids.read();
         if centroids.is_empty() {
             return Ok(Vec::new());
         }
 
-        // Step 1: Find top-nprobes nearest partitions
+        // Step 1: Rank partitions by centroid distance
         let mut partition_distances: Vec<(usize, f32)> = centroids
             .iter()
             .enumerate()
@@ -572,11 +598,6 @@ impl SpannIndex {
 
         partition_distances
             .sort_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(std::cmp::Ordering::Equal));
-        let probe_partitions: Vec<usize> = partition_distances
-            .iter()
-            .take(self.config.num_probes)
-            .map(|(i, _)| *i)
-            .collect();
 
         // Step 2: Search in selected partitions
         let quantizer = self.quantizer.read();
@@ -584,76 +605,61 @@ impl SpannIndex {
 
         // Build distance table for PQ (required for SPANN search)
         let distance_table = if let Some(ref pq) = *quantizer {
-            Some(pq.build_distance_table(query)?)
+            pq.build_distance_table(query)?
         } else {
             anyhow::bail!(
                 "SPANN search requires PQ quantizer but use_pq is disabled. \
                  PostingEntry stores only PQ codes, not original vectors."
             );
         };
+        let pq = quantizer.as_ref().unwrap();
 
         // Collect candidates from all probed partitions
         // Use max-heap to keep k smallest distances: pop() removes largest (worst) match
         let mut heap: BinaryHeap<(ordered_float::OrderedFloat<f32>, u32)> = BinaryHeap::new();
-
-        if !partitions.is_empty() {
-            // In-memory search (before save or after full load)
-            for &partition_id in &probe_partitions {
-                if partition_id >= partitions.len() {
-                    continue;
-                }
-
-                for entry in &partitions[partition_id].entries {
-                    let dist = if let (Some(ref table), Some(ref codes)) =
-                        (&distance_table, &entry.pq_codes)
-                    {
-                        quantizer
-                            .as_ref()
-                            .unwrap()
-                            .distance_with_table(table, codes)
-                    } else {
-                        // Would need original vectors - skip for now
+        let scan =
+            |entries: &[PostingEntry],
+             heap: &mut BinaryHeap<(ordered_float::OrderedFloat<f32>, u32)>| {
+                for entry in entries {
+                    if allowed.is_some_and(|ids| !ids.contains(&entry.vector_id)) {
+                        continue;
+                    }
+                    // Entries without PQ codes would need original vectors - skip for now
+                    let Some(ref codes) = entry.pq_codes else {
                         continue;
                     };
+                    let dist = pq.distance_with_table(&distance_table, codes);
 
                     heap.push((ordered_float::OrderedFloat(dist), entry.vector_id));
                     if heap.len() > k {
                         heap.pop(); // Removes largest distance (worst match)
                     }
                 }
+            };
+
+        // Unfiltered search probes exactly `num_probes` partitions; a filtered
+        // search keeps probing until it has k allowed results.
+        let mut found = 0;
+        let mmap_guard = self.mmap.read();
+        let pq_subvectors = self.config.dimension / 8; // PQ subvector count
+
+        for (probed, &(partition_id, _)) in partition_distances.iter().enumerate() {
+            if probed >= self.config.num_probes && (allowed.is_none() || found >= k) {
+                break;
             }
-        } else {
-            // Disk-based search (after load_from_file)
-            let mmap_guard = self.mmap.read();
-            if let Some(ref mmap) = *mmap_guard {
-                let pq_subvectors = if quantizer.is_some() {
-                    self.config.dimension / 8 // PQ subvector count
-                } else {
-                    0
-                };
-
-                for &partition_id in &probe_partitions {
-                    let entries = self.read_posting_list(mmap, partition_id, pq_subvectors)?;
-
-                    for entry in entries {
-                        let dist = if let (Some(ref table), Some(ref codes)) =
-                            (&distance_table, &entry.pq_codes)
-                        {
-                            quantizer
-                                .as_ref()
-                                .unwrap()
-                                .distance_with_table(table, codes)
-                        } else {
-                            continue;
-                        };
-
-                        heap.push((ordered_float::OrderedFloat(dist), entry.vector_id));
-                        if heap.len() > k {
-                            heap.pop(); // Removes largest distance (worst match)
-                        }
-                    }
+
+            if !partitions.is_empty() {
+                // In-memory search (before save or after full load)
+                if partition_id >= partitions.len() {
+                    continue;
                 }
+                scan(&partitions[partition_id].entries, &mut heap);
+            } else if let Some(ref mmap) = *mmap_guard {
+                // Disk-based search (after load_from_file)
+                let entries = self.read_posting_list(mmap, partition_id, pq_subvectors)?;
+                scan(&entries, &mut heap);
             }
+            found = heap.len();
         }
 
         // Convert heap to sorted results (smallest distance first)
@@ -1072,6 +1078,29 @@ mod tests {
             .collect()
     }
 
+    #[test]
+    fn test_spann_search_filtered_probes_until_k() {
+        let vectors = generate_random_vectors(1000, 384);
+
+        let config = SpannConfig {
+            dimension: 384,
+            use_pq: true,
+            num_probes: 2,
+            ..Default::default()
+        };
+
+        let mut index = SpannIndex::new(config);
+        index.build(vectors.clone()).unwrap();
+
+        // 2% of vectors: two probed partitions rarely hold 10 of them
+        let allowed: HashSet<u32> = (0..1000).filter(|id| id % 50 == 0).collect();
+        let results = index.search_filtered(&vectors[0], 10, &allowed).unwrap();
+
+        assert_eq!(results.len(), 10);
+        assert!(results.iter().all(|(id, _)| allowed.contains(id)));
+        assert_eq!(results[0].0, 0, "query vector is allowed and nearest");
+    }
+
     #[test]
     fn test_spann_build_and_search() {
         let vectors = generate_random_vectors(1000, 384);
@@ -21,8 +21,7 @@
 //! ```
 
 use super::distance_inline::{
-    cosine_similarity_inline, dot_product_inline, euclidean_squared_inline,
-    normalized_distance_inline,
+    cosine_similarity_inline, euclidean_squared_inline, normalized_distance_inline,
 };
 use anyhow::{anyhow, Result};
 use memmap2::MmapMut;
@@ -778,6 +777,169 @@ impl VamanaIndex {
         Ok(results)
     }
 
+    /// Search for k nearest neighbors among an allow-list of vector IDs
+    ///
+    /// The filter is applied during traversal rather than to a top-k result:
+    /// disallowed nodes are still expanded for navigation but never returned,
+    /// and the search keeps going past the usual beam cutoff until k allowed
+    /// nodes are found (or the reachable graph is exhausted). When the
+    /// allow-list is small relative to the index, an exact scan over it is
+    /// cheaper than walking the graph and is used instead.
+    pub fn search_filtered(
+        &self,
+        query: &[f32],
+        k: usize,
+        allowed: &HashSet<u32>,
+    ) -> Result<Vec<(u32, f32)>> {
+        let num_vectors = self.num_vectors.load(std::sync::atomic::Ordering::Acquire);
+        if num_vectors == 0 || k == 0 || allowed.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let deleted = self.deleted_ids.read();
+        let live: Vec<u32> = allowed
+            .iter()
+            .copied()
+            .filter(|id| (*id as usize) < num_vectors && !deleted.contains(id))
+            .collect();
+        if live.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        // A graph walk visits roughly k * n / |allowed| nodes before it has k
+        // matches, so scanning the allow-list directly wins once |allowed|² <= k * n.
+        if live.len().saturating_mul(live.len()) <= k.saturating_mul(num_vectors)
+            || self.graph.read().is_empty()
+        {
+            return self.exact_search(query, k, &live);
+        }
+
+        let entry = *self.medoid.read();
+        let candidates = self.filtered_greedy_search(query, k, entry, |id| {
+            allowed.contains(&id) && !deleted.contains(&id)
+        })?;
+
+        // Allowed nodes unreachable from the medoid (e.g. after heavy deletion)
+        if candidates.len() < k.min(live.len()) {
+            return self.exact_search(query, k, &live);
+        }
+
+        Ok(candidates.into_iter().map(|c| (c.id, c.distance)).collect())
+    }
+
+    /// Brute-force k nearest neighbors over an explicit set of vector IDs
+    fn exact_search(&self, query: &[f32], k: usize, ids: &[u32]) -> Result<Vec<(u32, f32)>> {
+        let storage = self.vectors.read();
+        let mut heap: BinaryHeap<SearchCandidate> = BinaryHeap::with_capacity(k + 1);
+
+        for &id in ids {
+            let slice = Self::get_slice_from_storage(&storage, id)?;
+            heap.push(SearchCandidate {
+                id,
+                distance: self.distance(query, slice),
+            });
+            if heap.len() > k {
+                heap.pop();
+            }
+        }
+
+        Ok(heap
+            .into_sorted_vec()
+            .into_iter()
+            .map(|c| (c.id, c.distance))
+            .collect())
+    }
+
+    /// Greedy search that only returns nodes accepted by `filter`
+    ///
+    /// Navigation uses a beam of `max(k, search_list_size)` over all nodes, as in
+    /// [`greedy_search`](Self::greedy_search). Until k accepted nodes have been
+    /// found, every unvisited neighbor is queued regardless of the beam bound,
+    /// which degrades gracefully into a best-first scan for selective filters.
+    fn filtered_greedy_search<F>(
+        &self,
+        query: &[f32],
+        k: usize,
+        entry: u32,
+        filter: F,
+    ) -> Result<Vec<SearchCandidate>>
+    where
+        F: Fn(u32) -> bool,
+    {
+        let graph = self.graph.read();
+        let storage = self.vectors.read();
+        let beam_width = k.max(self.config.search_list_size);
+
+        let mut visited = HashSet::new();
+        let mut candidates = BinaryHeap::new();
+        let mut beam = BinaryHeap::new();
+        let mut found = BinaryHeap::new();
+
+        let entry_slice = Self::get_slice_from_storage(&storage, entry)?;
+        let entry_candidate = SearchCandidate {
+            id: entry,
+            distance: self.distance(query, entry_slice),
+        };
+        candidates.push(Reverse(entry_candidate.clone()));
+        beam.push(entry_candidate.clone());
+        if filter(entry) {
+            found.push(entry_candidate);
+        }
+        visited.insert(entry);
+
+        while let Some(Reverse(current)) = candidates.pop() {
+            let satisfied = found.len() >= k;
+            if satisfied
+                && beam
+                    .peek()
+                    .map(|worst: &SearchCandidate| current.distance > worst.distance)
+                    .unwrap_or(false)
+            {
+                break;
+            }
+
+            if (current.id as usize) >= graph.len() {
+                continue;
+            }
+
+            for &neighbor_id in &graph[current.id as usize].neighbors {
+                if !visited.insert(neighbor_id) {
+                    continue;
+                }
+
+                let neighbor_slice = Self::get_slice_from_storage(&storage, neighbor_id)?;
+                let candidate = SearchCandidate {
+                    id: neighbor_id,
+                    distance: self.distance(query, neighbor_slice),
+                };
+
+                if filter(neighbor_id) {
+                    found.push(candidate.clone());
+                    if found.len() > k {
+                        found.pop();
+                    }
+                }
+
+                let within_beam = beam.len() < beam_width
+                    || beam
+                        .peek()
+                        .map(|worst| candidate.distance < worst.distance)
+                        .unwrap_or(true);
+                if within_beam {
+                    beam.push(candidate.clone());
+                    if beam.len() > beam_width {
+                        beam.pop();
+                    }
+                }
+                if within_beam || !satisfied {
+                    candidates.push(Reverse(candidate));
+                }
+            }
+        }
+
+        Ok(found.into_sorted_vec())
+    }
+
     /// Mark a vector as deleted (soft delete)
     /// The vector remains in the graph but is excluded from search results.
     /// It will be physically removed on the next rebuild.
@@ -1686,6 +1848,62 @@ mod tests {
         assert_eq!(repaired, 0);
     }
 
+    #[test]
+    fn test_search_filtered_fills_k_from_allowed_set() {
+        use rand::{Rng, SeedableRng};
+
+        let mut rng = rand::rngs::StdRng::seed_from_u64(7);
+        let vectors: Vec<Vec<f32>> = (0..400)
+            .map(|_| {
+                let v: Vec<f32> = (0..16).map(|_| rng.gen::<f32>() - 0.5).collect();
+                let norm = v.iter().map(|x| x * x).sum::<f32>().sqrt();
+                v.into_iter().map(|x| x / norm).collect()
+            })
+            .collect();
+
+        let mut index = VamanaIndex::new(VamanaConfig {
+            dimension: 16,
+            max_degree: 16,
+            search_list_size: 32,
+            use_mmap: false,
+            ..Default::default()
+        })
+        .unwrap();
+        index.build(vectors.clone()).unwrap();
+        index.mark_deleted(3);
+
+        let query = &vectors[0];
+        // 25% allowed walks the graph; 5% allowed takes the exact-scan path
+        for modulus in [4u32, 20] {
+            let allowed: HashSet<u32> = (0..400).filter(|id| id % modulus == 3).collect();
+            let results = index.search_filtered(query, 10, &allowed).unwrap();
+
+            assert_eq!(results.len(), 10, "modulus {modulus}");
+            assert!(results
+                .iter()
+                .all(|(id, _)| allowed.contains(id) && *id != 3));
+            assert!(results.windows(2).all(|w| w[0].1 <= w[1].1));
+
+            // Nearest allowed vector must be found
+            let best = allowed
+                .iter()
+                .filter(|id| **id != 3)
+                .min_by(|a, b| {
+                    let da = normalized_distance_inline(query, &vectors[**a as usize]);
+                    let db = normalized_distance_inline(query, &vectors[**b as usize]);
+                    da.partial_cmp(&db).unwrap()
+                })
+                .copied()
+                .unwrap();
+            assert_eq!(results[0].0, best, "modulus {modulus}");
+        }
+
+        assert!(index
+            .search_filtered(query, 10, &HashSet::new())
+            .unwrap()
+            .is_empty());
+    }
+
     #[test]
     fn test_estimate_recall() {
         let mut index = VamanaIndex::new(VamanaConfig {
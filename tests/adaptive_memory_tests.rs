@@ -509,6 +509,9 @@ fn test_semantic_fact_structure() {
         created_at: Utc::now(),
         last_reinforced: Utc::now(),
         fact_type: FactType::Preference,
+        valid_from: None,
+        valid_to: None,
+        superseded_by: None,
     };
 
     assert_eq!(fact.id, "fact_001");
@@ -532,6 +535,9 @@ fn test_reinforce_fact_increases_confidence() {
         created_at: Utc::now(),
         last_reinforced: Utc::now() - Duration::days(1),
         fact_type: FactType::Pattern,
+        valid_from: None,
+        valid_to: None,
+        superseded_by: None,
     };
 
     let initial_confidence = fact.confidence;
@@ -563,6 +569,9 @@ fn test_reinforce_fact_adds_source() {
         created_at: Utc::now(),
         last_reinforced: Utc::now(),
         fact_type: FactType::Pattern,
+        valid_from: None,
+        valid_to: None,
+        superseded_by: None,
     };
 
     let memory = create_memory("Evidence", ExperienceType::Learning, vec!["test"], 0.5);
@@ -586,6 +595,9 @@ fn test_should_decay_fact_old_unreinforced() {
         created_at: Utc::now() - Duration::days(100),
         last_reinforced: Utc::now() - Duration::days(100),
         fact_type: FactType::Pattern,
+        valid_from: None,
+        valid_to: None,
+        superseded_by: None,
     };
 
     assert!(consolidator.should_decay_fact(&old_fact));
@@ -605,6 +617,9 @@ fn test_should_not_decay_high_confidence_fact() {
         created_at: Utc::now() - Duration::days(30),
         last_reinforced: Utc::now() - Duration::days(30),
         fact_type: FactType::Definition,
+        valid_from: None,
+        valid_to: None,
+        superseded_by: None,
     };
 
     assert!(!consolidator.should_decay_fact(&strong_fact));
@@ -0,0 +1,186 @@
+//! Graph Analytics Tests
+//!
+//! Tests for structural analytics over the knowledge graph:
+//! - Weighted PageRank centrality stored on entities
+//! - Louvain communities and topic clusters
+//! - Bridge entities between communities
+//! - Invalidated edges and isolated entities
+
+use chrono::Utc;
+use shodh_memory::graph_memory::{
+    EdgeTier, EntityLabel, EntityNode, GraphMemory, LtpStatus, RelationType, RelationshipEdge,
+};
+use shodh_memory::uuid::Uuid;
+use std::collections::HashMap;
+use tempfile::TempDir;
+
+fn setup_graph_memory() -> (GraphMemory, TempDir) {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let graph = GraphMemory::new(temp_dir.path()).expect("Failed to create graph memory");
+    (graph, temp_dir)
+}
+
+fn add_entity(graph: &GraphMemory, name: &str) -> Uuid {
+    graph
+        .add_entity(EntityNode {
+            uuid: Uuid::new_v4(),
+            name: name.to_string(),
+            labels: vec![EntityLabel::Technology],
+            created_at: Utc::now(),
+            last_seen_at: Utc::now(),
+            mention_count: 1,
+            summary: String::new(),
+            attributes: HashMap::new(),
+            name_embedding: None,
+            salience: 0.5,
+            is_proper_noun: true,
+            aliases: Vec::new(),
+            centrality: 0.0,
+            community: None,
+        })
+        .expect("Failed to add entity")
+}
+
+fn connect(graph: &GraphMemory, from: Uuid, to: Uuid, strength: f32) -> Uuid {
+    graph
+        .add_relationship(RelationshipEdge {
+            uuid: Uuid::new_v4(),
+            from_entity: from,
+            to_entity: to,
+            relation_type: RelationType::RelatedTo,
+            strength,
+            created_at: Utc::now(),
+            valid_at: Utc::now(),
+            invalidated_at: None,
+            source_episode_id: None,
+            context: String::new(),
+            last_activated: Utc::now(),
+            activation_count: 0,
+            ltp_status: LtpStatus::None,
+            tier: EdgeTier::L2Episodic,
+            activation_timestamps: None,
+            entity_confidence: None,
+        })
+        .expect("Failed to add relationship")
+}
+
+fn community_of(graph: &GraphMemory, uuid: &Uuid) -> Option<u32> {
+    graph.get_entity(uuid).unwrap().unwrap().community
+}
+
+#[test]
+fn test_analytics_finds_communities_and_bridges() {
+    let (graph, _temp_dir) = setup_graph_memory();
+
+    // Two tight triangles, joined only through Docker
+    let rust = add_entity(&graph, "Rust");
+    let tokio = add_entity(&graph, "Tokio");
+    let cargo = add_entity(&graph, "Cargo");
+    let python = add_entity(&graph, "Python");
+    let django = add_entity(&graph, "Django");
+    let pandas = add_entity(&graph, "Pandas");
+    let docker = add_entity(&graph, "Docker");
+    for (a, b) in [(rust, tokio), (tokio, cargo), (rust, cargo)] {
+        connect(&graph, a, b, 0.9);
+    }
+    for (a, b) in [(python, django), (django, pandas), (python, pandas)] {
+        connect(&graph, a, b, 0.9);
+    }
+    connect(&graph, docker, tokio, 0.5);
+    connect(&graph, docker, django, 0.5);
+
+    let report = graph.run_analytics().expect("Analytics failed");
+
+    assert_eq!(report.entity_count, 7);
+    assert_eq!(report.edge_count, 8);
+    assert_eq!(report.community_count, 2);
+    assert!(report.modularity > 0.2);
+
+    let rust_community = community_of(&graph, &rust);
+    let python_community = community_of(&graph, &python);
+    assert!(rust_community.is_some());
+    assert_eq!(community_of(&graph, &tokio), rust_community);
+    assert_eq!(community_of(&graph, &cargo), rust_community);
+    assert_eq!(community_of(&graph, &django), python_community);
+    assert_eq!(community_of(&graph, &pandas), python_community);
+    assert_ne!(rust_community, python_community);
+
+    // Topic clusters cover both triangles
+    assert_eq!(report.communities.len(), 2);
+    assert!(report.communities.iter().all(|c| c.size >= 3));
+    assert!(report.communities.iter().all(|c| !c.topic.is_empty()));
+
+    // Docker splits its edges evenly between the two communities
+    let bridge = report
+        .bridges
+        .iter()
+        .find(|b| b.uuid == docker)
+        .expect("Docker should be a bridge");
+    assert!((bridge.participation - 0.5).abs() < 0.01);
+    assert_eq!(bridge.communities.len(), 2);
+    assert!(report.bridges.iter().all(|b| b.uuid != rust));
+
+    assert!(graph.last_analytics().is_some());
+}
+
+#[test]
+fn test_centrality_ranks_hubs_and_feeds_salience() {
+    let (graph, _temp_dir) = setup_graph_memory();
+
+    let hub = add_entity(&graph, "Kubernetes");
+    let leaves: Vec<Uuid> = ["Helm", "Istio", "Envoy", "Prometheus"]
+        .iter()
+        .map(|name| add_entity(&graph, name))
+        .collect();
+    for leaf in &leaves {
+        connect(&graph, hub, *leaf, 0.8);
+    }
+
+    let report = graph.run_analytics().expect("Analytics failed");
+    assert_eq!(report.central_entities[0].uuid, hub);
+    assert!((report.central_entities[0].centrality - 1.0).abs() < 1e-6);
+
+    let hub_node = graph.get_entity(&hub).unwrap().unwrap();
+    let leaf_node = graph.get_entity(&leaves[0]).unwrap().unwrap();
+    assert!((hub_node.centrality - 1.0).abs() < 1e-6);
+    assert!(leaf_node.centrality < hub_node.centrality);
+    assert!(hub_node.structural_salience() > leaf_node.structural_salience());
+
+    // A new mention keeps the structural scores
+    add_entity(&graph, "Kubernetes");
+    let mentioned = graph.get_entity(&hub).unwrap().unwrap();
+    assert_eq!(mentioned.mention_count, 2);
+    assert_eq!(mentioned.centrality, hub_node.centrality);
+    assert_eq!(mentioned.community, hub_node.community);
+}
+
+#[test]
+fn test_isolated_and_invalidated_edges_have_no_community() {
+    let (graph, _temp_dir) = setup_graph_memory();
+
+    let redis = add_entity(&graph, "Redis");
+    let memcached = add_entity(&graph, "Memcached");
+    let lonely = add_entity(&graph, "Fortran");
+    let edge = connect(&graph, redis, memcached, 0.7);
+
+    graph.run_analytics().expect("Analytics failed");
+    assert!(community_of(&graph, &redis).is_some());
+    assert_eq!(community_of(&graph, &lonely), None);
+
+    graph.invalidate_relationship(&edge).unwrap();
+    let report = graph.run_analytics().expect("Analytics failed");
+    assert_eq!(report.edge_count, 0);
+    assert_eq!(community_of(&graph, &redis), None);
+    assert!(report.communities.is_empty());
+}
+
+#[test]
+fn test_analytics_on_empty_graph() {
+    let (graph, _temp_dir) = setup_graph_memory();
+
+    let report = graph.run_analytics().expect("Analytics failed");
+    assert_eq!(report.entity_count, 0);
+    assert_eq!(report.community_count, 0);
+    assert!(report.central_entities.is_empty());
+    assert!(report.bridges.is_empty());
+}
@@ -51,6 +51,9 @@ fn create_entity_from_ner(ner: &NeuralNer, text: &str) -> Vec<EntityNode> {
             name_embedding: None,
             salience: entity.confidence,
             is_proper_noun: true,
+            aliases: Vec::new(),
+            centrality: 0.0,
+            community: None,
         })
         .collect()
 }
@@ -81,6 +84,9 @@ fn create_entity(
         name_embedding: None,
         salience,
         is_proper_noun: is_proper,
+        aliases: Vec::new(),
+        centrality: 0.0,
+        community: None,
     }
 }
 
@@ -1345,3 +1351,324 @@ fn test_relationship_with_effective_strength() {
         effective_edge.strength
     );
 }
+
+// =============================================================================
+// ENTITY RESOLUTION: ALIASES, MERGE AND UNDO
+// =============================================================================
+
+fn create_episode(entity_refs: Vec<Uuid>) -> EpisodicNode {
+    EpisodicNode {
+        uuid: Uuid::new_v4(),
+        name: "Resolution episode".to_string(),
+        content: "Entities mentioned together".to_string(),
+        source: EpisodeSource::Message,
+        created_at: Utc::now(),
+        valid_at: Utc::now(),
+        entity_refs,
+        metadata: HashMap::new(),
+    }
+}
+
+#[test]
+fn test_alias_resolves_to_entity() {
+    let (graph, _temp_dir) = setup_graph_memory();
+
+    let id = graph
+        .add_entity(create_entity(
+            "PostgreSQL",
+            Some(EntityLabel::Technology),
+            true,
+            0.7,
+        ))
+        .expect("Failed to add entity");
+    graph
+        .add_alias(&id, "Postgres")
+        .expect("Failed to add alias");
+
+    let found = graph
+        .find_entity_by_name("postgres")
+        .expect("Lookup failed")
+        .expect("Alias should resolve");
+    assert_eq!(found.uuid, id);
+    assert_eq!(found.aliases, vec!["Postgres".to_string()]);
+
+    // A later mention under the alias updates the same node
+    let again = graph
+        .add_entity(create_entity(
+            "Postgres",
+            Some(EntityLabel::Technology),
+            true,
+            0.7,
+        ))
+        .expect("Failed to add entity");
+    assert_eq!(again, id);
+    assert_eq!(graph.get_stats().expect("Stats failed").entity_count, 1);
+
+    graph
+        .remove_alias(&id, "postgres")
+        .expect("Failed to remove alias");
+    let found = graph.get_entity(&id).expect("Get failed").expect("Exists");
+    assert!(found.aliases.is_empty());
+
+    // Once removed, the name no longer resolves to the entity
+    let separate = graph
+        .add_entity(create_entity(
+            "Postgres",
+            Some(EntityLabel::Technology),
+            true,
+            0.7,
+        ))
+        .expect("Failed to add entity");
+    assert_ne!(separate, id);
+}
+
+#[test]
+fn test_alias_naming_another_entity_is_rejected() {
+    let (graph, _temp_dir) = setup_graph_memory();
+
+    let rust = graph
+        .add_entity(create_entity(
+            "Rust",
+            Some(EntityLabel::Technology),
+            true,
+            0.7,
+        ))
+        .expect("Failed");
+    graph
+        .add_entity(create_entity(
+            "Go",
+            Some(EntityLabel::Technology),
+            true,
+            0.7,
+        ))
+        .expect("Failed");
+
+    assert!(graph.add_alias(&rust, "go").is_err());
+}
+
+#[test]
+fn test_merge_entities_rewires_edges_and_episodes() {
+    let (graph, _temp_dir) = setup_graph_memory();
+
+    let pg = graph
+        .add_entity(create_entity(
+            "PostgreSQL",
+            Some(EntityLabel::Technology),
+            true,
+            0.7,
+        ))
+        .expect("Failed");
+    let dup = graph
+        .add_entity(create_entity(
+            "PG Database",
+            Some(EntityLabel::Technology),
+            true,
+            0.6,
+        ))
+        .expect("Failed");
+    let shared = graph
+        .add_entity(create_entity("Backend", None, false, 0.5))
+        .expect("Failed");
+    let only_dup = graph
+        .add_entity(create_entity("Migrations", None, false, 0.5))
+        .expect("Failed");
+
+    graph
+        .add_relationship(create_relationship(
+            pg,
+            shared,
+            RelationType::RelatedTo,
+            0.3,
+        ))
+        .expect("Failed");
+    graph
+        .add_relationship(create_relationship(
+            dup,
+            shared,
+            RelationType::RelatedTo,
+            0.2,
+        ))
+        .expect("Failed");
+    let rewired = graph
+        .add_relationship(create_relationship(
+            only_dup,
+            dup,
+            RelationType::RelatedTo,
+            0.4,
+        ))
+        .expect("Failed");
+    graph
+        .add_relationship(create_relationship(pg, dup, RelationType::RelatedTo, 0.5))
+        .expect("Failed");
+    let episode = graph
+        .add_episode(create_episode(vec![dup, only_dup]))
+        .expect("Failed");
+
+    let record = graph.merge_entities(&pg, &dup, None).expect("Merge failed");
+    assert_eq!(record.rewired_edges.len(), 1);
+    assert_eq!(record.combined_edges.len(), 1);
+    assert_eq!(record.dropped_edges.len(), 1);
+
+    assert!(graph.get_entity(&dup).expect("Get failed").is_none());
+    let survivor = graph.get_entity(&pg).expect("Get failed").expect("Exists");
+    assert_eq!(survivor.mention_count, 2);
+    assert_eq!(survivor.aliases, vec!["PG Database".to_string()]);
+    assert_eq!(
+        graph
+            .find_entity_by_name("PG Database")
+            .expect("Lookup failed")
+            .map(|entity| entity.uuid),
+        Some(pg)
+    );
+
+    // Parallel edges to the same neighbour are combined, strengths summed
+    let combined = graph
+        .find_relationship_between(&pg, &shared)
+        .expect("Lookup failed")
+        .expect("Edge exists");
+    assert!((combined.strength - 0.5).abs() < 0.001);
+
+    // The duplicate's own edge now points at the survivor
+    let edge = graph
+        .get_relationship(&rewired)
+        .expect("Get failed")
+        .expect("Exists");
+    assert_eq!(edge.to_entity, pg);
+    assert!(graph
+        .get_entity_relationships(&dup)
+        .expect("Failed")
+        .is_empty());
+
+    let episodes = graph.get_episodes_by_entity(&pg).expect("Failed");
+    assert_eq!(episodes.len(), 1);
+    assert_eq!(episodes[0].uuid, episode);
+    assert!(episodes[0].entity_refs.contains(&pg));
+    assert!(!episodes[0].entity_refs.contains(&dup));
+    assert_eq!(graph.get_stats().expect("Stats failed").entity_count, 3);
+}
+
+#[test]
+fn test_undo_entity_merge_restores_graph() {
+    let (graph, _temp_dir) = setup_graph_memory();
+
+    let pg = graph
+        .add_entity(create_entity(
+            "PostgreSQL",
+            Some(EntityLabel::Technology),
+            true,
+            0.7,
+        ))
+        .expect("Failed");
+    let dup = graph
+        .add_entity(create_entity(
+            "PG Database",
+            Some(EntityLabel::Product),
+            true,
+            0.6,
+        ))
+        .expect("Failed");
+    let shared = graph
+        .add_entity(create_entity("Backend", None, false, 0.5))
+        .expect("Failed");
+    graph
+        .add_relationship(create_relationship(
+            pg,
+            shared,
+            RelationType::RelatedTo,
+            0.3,
+        ))
+        .expect("Failed");
+    let dup_edge = graph
+        .add_relationship(create_relationship(
+            dup,
+            shared,
+            RelationType::RelatedTo,
+            0.2,
+        ))
+        .expect("Failed");
+    graph
+        .add_relationship(create_relationship(pg, dup, RelationType::RelatedTo, 0.5))
+        .expect("Failed");
+    graph
+        .add_episode(create_episode(vec![dup]))
+        .expect("Failed");
+
+    let record = graph.merge_entities(&pg, &dup, None).expect("Merge failed");
+    assert_eq!(graph.list_entity_merges().expect("Failed").len(), 1);
+
+    graph.undo_entity_merge(&record.id).expect("Undo failed");
+    assert!(graph.list_entity_merges().expect("Failed").is_empty());
+
+    let restored = graph
+        .get_entity(&dup)
+        .expect("Get failed")
+        .expect("Restored");
+    assert_eq!(restored.name, "PG Database");
+    let survivor = graph.get_entity(&pg).expect("Get failed").expect("Exists");
+    assert_eq!(survivor.mention_count, 1);
+    assert!(survivor.aliases.is_empty());
+    assert_eq!(survivor.labels, vec![EntityLabel::Technology]);
+    assert_eq!(
+        graph
+            .find_entity_by_name("PG Database")
+            .expect("Lookup failed")
+            .map(|entity| entity.uuid),
+        Some(dup)
+    );
+
+    let survivor_edge = graph
+        .find_relationship_between(&pg, &shared)
+        .expect("Lookup failed")
+        .expect("Edge exists");
+    assert!((survivor_edge.strength - 0.3).abs() < 0.001);
+    let dup_restored = graph
+        .get_relationship(&dup_edge)
+        .expect("Get failed")
+        .expect("Edge restored");
+    assert_eq!(dup_restored.from_entity, dup);
+    assert!(graph
+        .find_relationship_between(&pg, &dup)
+        .expect("Lookup failed")
+        .is_some());
+    assert_eq!(graph.get_episodes_by_entity(&dup).expect("Failed").len(), 1);
+    assert!(graph
+        .get_episodes_by_entity(&pg)
+        .expect("Failed")
+        .is_empty());
+}
+
+#[test]
+fn test_resolve_entities_merges_confident_pairs() {
+    use shodh_memory::graph_memory::EntityResolverConfig;
+
+    let (graph, _temp_dir) = setup_graph_memory();
+
+    // cosine([1, 0], [0.8, 0.6]) = 0.8: a candidate, but below the default
+    // threshold, so add_entity keeps them apart
+    let mut pg = create_entity("PostgreSQL", Some(EntityLabel::Technology), true, 0.7);
+    pg.name_embedding = Some(vec![1.0, 0.0]);
+    let mut dup = create_entity("Postgres DB", Some(EntityLabel::Technology), true, 0.6);
+    dup.name_embedding = Some(vec![0.8, 0.6]);
+    let mut person = create_entity("Paul Postgres", Some(EntityLabel::Person), true, 0.6);
+    person.name_embedding = Some(vec![0.9, 0.436]);
+
+    let pg = graph.add_entity(pg).expect("Failed");
+    let dup = graph.add_entity(dup).expect("Failed");
+    graph.add_entity(person).expect("Failed");
+    assert_ne!(pg, dup);
+
+    let config = EntityResolverConfig {
+        min_confidence: 0.75,
+        ..Default::default()
+    };
+    let (candidates, merges) = graph.resolve_entities(&config, 10, true).expect("Failed");
+    assert!(merges.is_empty());
+    // The Person never pairs with a Technology
+    assert_eq!(candidates.len(), 1);
+    assert_eq!(candidates[0].survivor, pg);
+    assert_eq!(candidates[0].duplicate, dup);
+
+    let (_, merges) = graph.resolve_entities(&config, 10, false).expect("Failed");
+    assert_eq!(merges.len(), 1);
+    assert!(graph.get_entity(&dup).expect("Get failed").is_none());
+}
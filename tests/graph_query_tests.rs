@@ -0,0 +1,296 @@
+//! Graph Query Tests
+//!
+//! Tests for the Cypher-like query language over the knowledge graph:
+//! - Label, name and attribute predicates
+//! - Variable-length relationship patterns
+//! - Edge tier and strength filters
+//! - Invalidated edge handling
+//! - Projections, ordering and limits
+
+use chrono::Utc;
+use shodh_memory::graph_memory::{
+    EdgeTier, EntityLabel, EntityNode, GraphMemory, LtpStatus, RelationType, RelationshipEdge,
+};
+use shodh_memory::graph_query::GraphQuery;
+use shodh_memory::uuid::Uuid;
+use std::collections::HashMap;
+use tempfile::TempDir;
+
+struct Fixture {
+    graph: GraphMemory,
+    _temp_dir: TempDir,
+    alice: Uuid,
+    acme: Uuid,
+    carol_job: Uuid,
+}
+
+fn entity(name: &str, label: EntityLabel, salience: f32) -> EntityNode {
+    EntityNode {
+        uuid: Uuid::new_v4(),
+        name: name.to_string(),
+        labels: vec![label],
+        created_at: Utc::now(),
+        last_seen_at: Utc::now(),
+        mention_count: 1,
+        summary: String::new(),
+        attributes: HashMap::new(),
+        name_embedding: None,
+        salience,
+        is_proper_noun: true,
+        aliases: Vec::new(),
+        centrality: 0.0,
+        community: None,
+    }
+}
+
+fn edge(
+    from: Uuid,
+    to: Uuid,
+    relation_type: RelationType,
+    strength: f32,
+    tier: EdgeTier,
+) -> RelationshipEdge {
+    RelationshipEdge {
+        uuid: Uuid::new_v4(),
+        from_entity: from,
+        to_entity: to,
+        relation_type,
+        strength,
+        created_at: Utc::now(),
+        valid_at: Utc::now(),
+        invalidated_at: None,
+        source_episode_id: None,
+        context: String::new(),
+        last_activated: Utc::now(),
+        activation_count: 0,
+        ltp_status: LtpStatus::None,
+        tier,
+        activation_timestamps: None,
+        entity_confidence: None,
+    }
+}
+
+/// Alice and Bob work at Acme; Carol used to work at Globex.
+/// Alice knows Bob, who knows Carol.
+fn setup() -> Fixture {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let graph = GraphMemory::new(temp_dir.path()).expect("Failed to create graph memory");
+
+    let mut alice_node = entity("Alice", EntityLabel::Person, 0.9);
+    alice_node
+        .attributes
+        .insert("role".to_string(), "engineer".to_string());
+    let alice = graph.add_entity(alice_node).unwrap();
+    let bob = graph
+        .add_entity(entity("Bob", EntityLabel::Person, 0.4))
+        .unwrap();
+    let carol = graph
+        .add_entity(entity("Carol", EntityLabel::Person, 0.6))
+        .unwrap();
+    let acme = graph
+        .add_entity(entity("Acme", EntityLabel::Organization, 0.7))
+        .unwrap();
+    let globex = graph
+        .add_entity(entity("Globex", EntityLabel::Organization, 0.5))
+        .unwrap();
+
+    graph
+        .add_relationship(edge(
+            alice,
+            acme,
+            RelationType::WorksAt,
+            0.9,
+            EdgeTier::L3Semantic,
+        ))
+        .unwrap();
+    graph
+        .add_relationship(edge(
+            bob,
+            acme,
+            RelationType::WorksAt,
+            0.3,
+            EdgeTier::L1Working,
+        ))
+        .unwrap();
+    let carol_job = graph
+        .add_relationship(edge(
+            carol,
+            globex,
+            RelationType::WorksAt,
+            0.8,
+            EdgeTier::L2Episodic,
+        ))
+        .unwrap();
+    graph.invalidate_relationship(&carol_job).unwrap();
+    graph
+        .add_relationship(edge(
+            alice,
+            bob,
+            RelationType::Knows,
+            0.6,
+            EdgeTier::L2Episodic,
+        ))
+        .unwrap();
+    graph
+        .add_relationship(edge(
+            bob,
+            carol,
+            RelationType::Knows,
+            0.6,
+            EdgeTier::L2Episodic,
+        ))
+        .unwrap();
+
+    Fixture {
+        graph,
+        _temp_dir: temp_dir,
+        alice,
+        acme,
+        carol_job,
+    }
+}
+
+fn run(fixture: &Fixture, query: &str) -> Vec<serde_json::Value> {
+    GraphQuery::parse(query)
+        .unwrap_or_else(|e| panic!("query failed to parse: {e}"))
+        .execute(&fixture.graph)
+        .expect("query failed")
+        .rows
+}
+
+fn column(rows: &[serde_json::Value], name: &str) -> Vec<String> {
+    rows.iter()
+        .map(|row| row[name].as_str().unwrap_or_default().to_string())
+        .collect()
+}
+
+#[test]
+fn test_match_by_label_and_name() {
+    let fixture = setup();
+
+    let rows = run(
+        &fixture,
+        "MATCH (p:Person)-[:WORKS_AT]->(o:Organization {name: 'acme'}) \
+         RETURN p.name AS person, o.uuid AS org ORDER BY person",
+    );
+    assert_eq!(column(&rows, "person"), vec!["Alice", "Bob"]);
+    assert_eq!(rows[0]["org"], fixture.acme.to_string());
+}
+
+#[test]
+fn test_where_attribute_and_comparisons() {
+    let fixture = setup();
+
+    let rows = run(
+        &fixture,
+        "MATCH (p:Person) WHERE p.role = 'engineer' OR p.salience < 0.5 \
+         RETURN p.name ORDER BY p.salience DESC",
+    );
+    assert_eq!(column(&rows, "p.name"), vec!["Alice", "Bob"]);
+
+    let rows = run(
+        &fixture,
+        "MATCH (p:Person) WHERE p.name IN ['Carol', 'Dave'] AND p.role IS NULL RETURN p.name",
+    );
+    assert_eq!(column(&rows, "p.name"), vec!["Carol"]);
+}
+
+#[test]
+fn test_variable_length_paths() {
+    let fixture = setup();
+
+    let rows = run(
+        &fixture,
+        "MATCH (a:Person {name: 'Alice'})-[:Knows*1..2]->(b) RETURN b.name ORDER BY b.name",
+    );
+    assert_eq!(column(&rows, "b.name"), vec!["Bob", "Carol"]);
+
+    let rows = run(
+        &fixture,
+        "MATCH (a {name: 'Alice'})-[:Knows*2]->(b) RETURN b.name",
+    );
+    assert_eq!(column(&rows, "b.name"), vec!["Carol"]);
+
+    // Either direction, returning the path itself
+    let rows = run(
+        &fixture,
+        "MATCH (c {name: 'Carol'})-[path:Knows*..3]-(a {name: 'Alice'}) RETURN path",
+    );
+    assert_eq!(rows.len(), 1);
+    assert_eq!(rows[0]["path"].as_array().unwrap().len(), 2);
+}
+
+#[test]
+fn test_edge_tier_and_strength_filters() {
+    let fixture = setup();
+
+    let rows = run(
+        &fixture,
+        "MATCH (p)-[r:WorksAt]->(o) WHERE r.tier = 'L3' RETURN p.name",
+    );
+    assert_eq!(column(&rows, "p.name"), vec!["Alice"]);
+
+    let rows = run(
+        &fixture,
+        "MATCH (p)-[:WorksAt {min_strength: 0.5}]->(o {name: 'Acme'}) RETURN p.name",
+    );
+    assert_eq!(column(&rows, "p.name"), vec!["Alice"]);
+
+    let rows = run(
+        &fixture,
+        "MATCH (p)-[r {tier: ['L1', 'L2']}]->(o:Organization) RETURN p.name",
+    );
+    assert_eq!(column(&rows, "p.name"), vec!["Bob"]);
+}
+
+#[test]
+fn test_invalidated_edges_are_skipped_unless_asked_for() {
+    let fixture = setup();
+
+    let rows = run(&fixture, "MATCH (p)-[:WorksAt]->(o) RETURN DISTINCT o.name");
+    assert_eq!(column(&rows, "o.name"), vec!["Acme"]);
+
+    let rows = run(
+        &fixture,
+        "MATCH (p)-[r:WorksAt]->(o) WHERE r.invalidated_at IS NOT NULL RETURN p.name, r.uuid",
+    );
+    assert_eq!(column(&rows, "p.name"), vec!["Carol"]);
+    assert_eq!(rows[0]["r.uuid"], fixture.carol_job.to_string());
+}
+
+#[test]
+fn test_anchor_by_uuid_and_return_star() {
+    let fixture = setup();
+
+    let query = format!(
+        "MATCH (a)-[r:WorksAt]->(o) WHERE a.uuid = '{}' RETURN *",
+        fixture.alice
+    );
+    let parsed = GraphQuery::parse(&query).unwrap();
+    assert!(parsed.explain()[0].contains("by uuid"));
+
+    let result = parsed.execute(&fixture.graph).unwrap();
+    assert_eq!(result.columns, vec!["a", "r", "o"]);
+    assert_eq!(result.stats.anchors, 1);
+    assert_eq!(result.rows.len(), 1);
+    assert_eq!(result.rows[0]["o"]["name"], "Acme");
+    assert_eq!(result.rows[0]["r"]["tier"], "L3");
+    assert!(result.rows[0]["a"].get("name_embedding").is_none());
+}
+
+#[test]
+fn test_limit_and_repeated_variables() {
+    let fixture = setup();
+
+    let rows = run(&fixture, "MATCH (p:Person) RETURN p.name LIMIT 2");
+    assert_eq!(rows.len(), 2);
+
+    // Colleagues: two people sharing an employer, never paired with themselves
+    let rows = run(
+        &fixture,
+        "MATCH (a:Person)-[:WorksAt]->(o)<-[:WorksAt]-(b:Person) \
+         RETURN a.name, b.name ORDER BY a.name",
+    );
+    assert_eq!(column(&rows, "a.name"), vec!["Alice", "Bob"]);
+    assert_eq!(column(&rows, "b.name"), vec!["Bob", "Alice"]);
+}
@@ -22,6 +22,7 @@ use tower::ServiceExt;
 use shodh_memory::{
     config::ServerConfig,
     handlers::{build_protected_routes, build_public_routes, MultiUserMemoryManager},
+    mcp_params::tool_description,
 };
 
 // ═══════════════════════════════════════════════════════════════════════
@@ -43,32 +44,39 @@ fn init_env() {
 /// Self-contained test harness with a fresh temp directory and RocksDB.
 struct Harness {
     mgr: Arc<MultiUserMemoryManager>,
-    _dir: TempDir,
+    dir: TempDir,
 }
 
 impl Harness {
     fn new() -> Self {
+        Self::with_config(|_| {})
+    }
+
+    fn with_config(configure: impl FnOnce(&mut ServerConfig)) -> Self {
         init_env();
         let dir = TempDir::new().expect("create temp dir");
-        let cfg = ServerConfig {
+        let mut cfg = ServerConfig {
             storage_path: dir.path().to_path_buf(),
             backup_enabled: false,
             ..ServerConfig::default()
         };
+        configure(&mut cfg);
         let mgr = MultiUserMemoryManager::new(dir.path().to_path_buf(), cfg)
             .expect("create MultiUserMemoryManager");
         Self {
             mgr: Arc::new(mgr),
-            _dir: dir,
+            dir,
         }
     }
 
     fn app(&self) -> Router {
         // Mirror main.rs: auth middleware only wraps protected routes.
         let public = build_public_routes(self.mgr.clone());
-        let protected = build_protected_routes(self.mgr.clone()).layer(axum::middleware::from_fn(
-            shodh_memory::auth::auth_middleware,
-        ));
+        let protected =
+            build_protected_routes(self.mgr.clone()).layer(axum::middleware::from_fn_with_state(
+                self.mgr.api_key_store.clone(),
+                shodh_memory::auth::auth_middleware,
+            ));
         Router::new().merge(public).merge(protected)
     }
 }
@@ -330,6 +338,29 @@ async fn session_stats() {
     assert!(status.is_success());
 }
 
+#[tokio::test]
+async fn get_session_hides_other_users_sessions() {
+    let h = Harness::new();
+    let sid = h.mgr.session_store().start_session("alice");
+
+    let (status, body) = json_of(
+        h.app(),
+        authed_get(&format!("/api/sessions/{}?user_id=alice", sid.0)),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "owner lookup failed: {body}");
+    assert_eq!(body["session"]["user_id"], "alice");
+
+    // A session owned by someone else is indistinguishable from a missing one
+    let (status, body) = json_of(
+        h.app(),
+        authed_get(&format!("/api/sessions/{}?user_id=bob", sid.0)),
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND, "{body}");
+    assert_eq!(body["code"], "SESSION_NOT_FOUND");
+}
+
 // ═══════════════════════════════════════════════════════════════════════
 // remember.rs
 // ═══════════════════════════════════════════════════════════════════════
@@ -434,6 +465,77 @@ async fn recall_empty_state() {
     assert!(status.is_success(), "recall returned {status}: {body}");
 }
 
+#[tokio::test]
+async fn recall_with_filter_returns_only_matching() {
+    let h = Harness::new();
+    let mut tagged_ids = Vec::new();
+    for i in 0..12 {
+        let tags = if i % 4 == 0 {
+            vec!["deploy"]
+        } else {
+            vec!["misc"]
+        };
+        let (status, body) = json_of(
+            h.app(),
+            authed_post(
+                "/api/remember",
+                json!({
+                    "user_id": "test-user",
+                    "content": format!("Service rollout note number {i} about the release pipeline."),
+                    "tags": tags,
+                }),
+            ),
+        )
+        .await;
+        assert!(status.is_success(), "remember returned {status}: {body}");
+        if i % 4 == 0 {
+            tagged_ids.push(body["id"].as_str().unwrap().to_string());
+        }
+    }
+
+    let (status, body) = json_of(
+        h.app(),
+        authed_post(
+            "/api/recall",
+            json!({
+                "user_id": "test-user",
+                "query": "release pipeline rollout",
+                "limit": 3,
+                "filter": { "tags": ["deploy"] }
+            }),
+        ),
+    )
+    .await;
+    assert!(status.is_success(), "recall returned {status}: {body}");
+    let memories = body["memories"].as_array().unwrap();
+    assert_eq!(
+        memories.len(),
+        3,
+        "selective filter should still fill limit: {body}"
+    );
+    for m in memories {
+        assert!(tagged_ids.contains(&m["id"].as_str().unwrap().to_string()));
+    }
+}
+
+#[tokio::test]
+async fn recall_rejects_invalid_filter() {
+    let h = Harness::new();
+    let (status, _) = json_of(
+        h.app(),
+        authed_post(
+            "/api/recall",
+            json!({
+                "user_id": "test-user",
+                "query": "anything",
+                "filter": { "min_importance": 0.9, "max_importance": 0.1 }
+            }),
+        ),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
 #[tokio::test]
 async fn context_summary_empty() {
     let h = Harness::new();
@@ -678,6 +780,31 @@ async fn facts_by_entity_empty() {
     assert!(status.is_success());
 }
 
+#[tokio::test]
+async fn list_facts_as_of() {
+    let h = Harness::new();
+    let (status, body) = json_of(
+        h.app(),
+        authed_post(
+            "/api/facts/list",
+            json!({"user_id": "test-user", "as_of": "2024-06-01T00:00:00Z"}),
+        ),
+    )
+    .await;
+    assert!(status.is_success());
+    assert_eq!(body["total"], 0);
+
+    let (status, _) = json_of(
+        h.app(),
+        authed_post(
+            "/api/facts/list",
+            json!({"user_id": "test-user", "as_of": "last tuesday"}),
+        ),
+    )
+    .await;
+    assert!(status.is_client_error());
+}
+
 #[tokio::test]
 async fn facts_stats() {
     let h = Harness::new();
@@ -739,6 +866,86 @@ async fn lineage_stats_empty() {
     assert!(status.is_success());
 }
 
+#[tokio::test]
+async fn lineage_post_mortem_for_completed_todo() {
+    let h = Harness::new();
+
+    let (status, body) = json_of(
+        h.app(),
+        authed_post(
+            "/api/todos/add",
+            json!({"user_id": "test-user", "content": "Fix login timeout"}),
+        ),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "create todo: {body}");
+    let todo_id = body["todo"]["id"].as_str().unwrap().to_string();
+    let tag = format!("todo:SHO-{}", body["todo"]["seq_num"]);
+    let endpoint = format!("/api/lineage/postmortem/{todo_id}");
+
+    // Nothing to review while the todo is open
+    let (status, body) = json_of(
+        h.app(),
+        authed_post(&endpoint, json!({"user_id": "test-user"})),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST, "open todo: {body}");
+
+    let learning = "Session cookies expired after five minutes in staging.";
+    let (status, body) = json_of(
+        h.app(),
+        authed_post(
+            "/api/remember",
+            json!({
+                "user_id": "test-user",
+                "content": learning,
+                "memory_type": "Learning",
+                "tags": [tag]
+            }),
+        ),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "remember: {body}");
+
+    let (status, _) = json_of(
+        h.app(),
+        authed_post(
+            &format!("/api/todos/{todo_id}/complete"),
+            json!({"user_id": "test-user"}),
+        ),
+    )
+    .await;
+    assert!(status.is_success());
+
+    let (status, body) = json_of(
+        h.app(),
+        authed_post(
+            &endpoint,
+            json!({"user_id": "test-user", "regenerate": true}),
+        ),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "regenerate: {body}");
+    assert_eq!(body["subject_type"], "todo");
+    assert_eq!(body["regenerated"], true);
+    let learnings = body["post_mortem"]["learnings"].as_array().unwrap();
+    assert!(learnings.iter().any(|l| l == learning), "{body}");
+    assert!(body["post_mortem"]["memory_id"].is_string());
+
+    let (status, body) = json_of(
+        h.app(),
+        authed_post(
+            &endpoint,
+            json!({"user_id": "test-user", "format": "markdown"}),
+        ),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "markdown: {body}");
+    assert_eq!(body["regenerated"], false);
+    assert!(body["markdown"].as_str().unwrap().contains("## Learnings"));
+    assert!(body.get("post_mortem").is_none());
+}
+
 // ═══════════════════════════════════════════════════════════════════════
 // graph.rs
 // ═══════════════════════════════════════════════════════════════════════
@@ -878,6 +1085,74 @@ async fn todo_lifecycle() {
     assert!(status.is_success(), "delete todo returned {status}");
 }
 
+#[tokio::test]
+async fn todo_dependencies() {
+    let h = Harness::new();
+    let add = |content: &str, extra: serde_json::Value| {
+        let mut body = json!({"user_id": "test-user", "content": content, "project": "Launch"});
+        body.as_object_mut()
+            .unwrap()
+            .extend(extra.as_object().unwrap().clone());
+        authed_post("/api/todos/add", body)
+    };
+
+    let (_, spec) = json_of(h.app(), add("Write spec", json!({}))).await;
+    let spec_id = spec["todo"]["id"].as_str().unwrap().to_string();
+    let (status, build) = json_of(
+        h.app(),
+        add("Build feature", json!({"blocked_by": [spec_id]})),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "create dependent: {build}");
+    assert_eq!(build["todo"]["status"], "blocked");
+    let build_id = build["todo"]["id"].as_str().unwrap().to_string();
+
+    // Spec blocked by its own dependent would be a cycle
+    let (status, body) = json_of(
+        h.app(),
+        authed_post(
+            &format!("/api/todos/{spec_id}/update"),
+            json!({"user_id": "test-user", "blocked_by": [build_id]}),
+        ),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST, "cycle: {body}");
+
+    let (status, body) = json_of(
+        h.app(),
+        authed_post("/api/todos/next", json!({"user_id": "test-user"})),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let next: Vec<&str> = body["todos"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|t| t["id"].as_str().unwrap())
+        .collect();
+    assert_eq!(next, vec![spec_id.as_str()]);
+
+    let (status, body) = json_of(
+        h.app(),
+        authed_get("/api/projects/Launch/critical-path?user_id=test-user"),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "critical path: {body}");
+    assert_eq!(body["count"], 2);
+
+    let (status, body) = json_of(
+        h.app(),
+        authed_post(
+            &format!("/api/todos/{spec_id}/complete"),
+            json!({"user_id": "test-user"}),
+        ),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["unblocked"][0]["id"], build_id.as_str());
+    assert_eq!(body["unblocked"][0]["status"], "todo");
+}
+
 #[tokio::test]
 async fn list_todos_empty() {
     let h = Harness::new();
@@ -928,6 +1203,64 @@ async fn project_lifecycle() {
     assert!(status.is_success());
 }
 
+#[tokio::test]
+async fn codebase_watch_lifecycle() {
+    let h = Harness::new();
+    let code = TempDir::new().unwrap();
+    std::fs::write(code.path().join("main.rs"), "fn main() {}\n").unwrap();
+
+    let (status, body) = json_of(
+        h.app(),
+        authed_post(
+            "/api/projects",
+            json!({"user_id": "test-user", "name": "Watched"}),
+        ),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "create project: {body}");
+
+    // No indexed codebase to default to
+    let status = status_of(
+        h.app(),
+        authed_post(
+            "/api/projects/Watched/watch",
+            json!({"user_id": "test-user"}),
+        ),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let (status, body) = json_of(
+        h.app(),
+        authed_post(
+            "/api/projects/Watched/watch",
+            json!({
+                "user_id": "test-user",
+                "codebase_path": code.path().to_string_lossy(),
+                "polling": true
+            }),
+        ),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "watch: {body}");
+    assert_eq!(body["backend"], "polling");
+
+    let (status, body) = json_of(h.app(), authed_get("/api/files/watches?user_id=test-user")).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["total"], 1);
+
+    let (status, body) = json_of(
+        h.app(),
+        authed_delete("/api/projects/Watched/watch?user_id=test-user"),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["stopped"], true);
+
+    let (_, body) = json_of(h.app(), authed_get("/api/files/watches?user_id=test-user")).await;
+    assert_eq!(body["total"], 0);
+}
+
 #[tokio::test]
 async fn list_reminders_empty() {
     let h = Harness::new();
@@ -1032,6 +1365,18 @@ async fn rebuild_index_empty() {
     assert!(status.is_success());
 }
 
+#[tokio::test]
+async fn reembed_memories_empty() {
+    let h = Harness::new();
+    let (status, body) = json_of(
+        h.app(),
+        authed_post("/api/index/reembed", json!({"user_id": "test-user"})),
+    )
+    .await;
+    assert!(status.is_success());
+    assert_eq!(body["reembedded"], 0);
+}
+
 #[tokio::test]
 async fn create_backup() {
     let h = Harness::new();
@@ -1194,45 +1539,1041 @@ async fn linear_sync_no_integration() {
 }
 
 // ═══════════════════════════════════════════════════════════════════════
-// End-to-end: remember → recall cycle
+// SCOPED API KEYS (tenant isolation + roles)
 // ═══════════════════════════════════════════════════════════════════════
 
-#[tokio::test]
-async fn remember_then_list() {
-    let h = Harness::new();
+fn keyed_post(uri: &str, key: &str, body: serde_json::Value) -> Request<Body> {
+    Request::builder()
+        .method(Method::POST)
+        .uri(uri)
+        .header("content-type", "application/json")
+        .header("x-api-key", key)
+        .body(Body::from(serde_json::to_vec(&body).unwrap()))
+        .unwrap()
+}
 
-    // Store a memory
-    let (status, _) = json_of(
+fn keyed_get(uri: &str, key: &str) -> Request<Body> {
+    Request::builder()
+        .method(Method::GET)
+        .uri(uri)
+        .header("x-api-key", key)
+        .body(Body::empty())
+        .unwrap()
+}
+
+async fn issue_key(h: &Harness, role: &str, users: &[&str]) -> String {
+    let (status, body) = json_of(
         h.app(),
         authed_post(
-            "/api/remember",
-            json!({
-                "user_id": "e2e-user",
-                "content": "Melbourne has unpredictable weather.",
-                "tags": ["weather", "melbourne"]
-            }),
+            "/api/keys",
+            json!({"name": "scoped", "role": role, "allowed_user_ids": users}),
         ),
     )
     .await;
-    assert_eq!(status, StatusCode::OK);
+    assert_eq!(status, StatusCode::OK, "create key failed: {body}");
+    body["key"].as_str().unwrap().to_string()
+}
 
-    // List memories — should have at least one
-    let (status, body) = json_of(h.app(), authed_get("/api/list/e2e-user")).await;
-    assert_eq!(status, StatusCode::OK);
-    let memories = body.as_array().or(body["memories"].as_array());
-    assert!(
-        memories.map(|m| !m.is_empty()).unwrap_or(false),
-        "should see stored memory in list: {body}"
+#[tokio::test]
+async fn scoped_key_rejects_cross_tenant_requests() {
+    let h = Harness::new();
+    let key = issue_key(&h, "writer", &["alice"]).await;
+
+    let own = keyed_post(
+        "/api/recall",
+        &key,
+        json!({"user_id": "alice", "query": "anything"}),
     );
+    assert_eq!(status_of(h.app(), own).await, StatusCode::OK);
 
-    // User stats should show 1 memory
-    let (status, body) = json_of(h.app(), authed_get("/api/users/e2e-user/stats")).await;
-    assert_eq!(status, StatusCode::OK);
-    let count = body["total_memories"]
-        .as_u64()
-        .or(body["memory_count"].as_u64());
-    assert!(
-        count.unwrap_or(0) >= 1,
-        "stats should show at least 1 memory: {body}"
+    let other = keyed_post(
+        "/api/recall",
+        &key,
+        json!({"user_id": "bob", "query": "anything"}),
     );
+    assert_eq!(status_of(h.app(), other).await, StatusCode::FORBIDDEN);
+
+    let other_path = keyed_get("/api/users/bob/stats", &key);
+    assert_eq!(status_of(h.app(), other_path).await, StatusCode::FORBIDDEN);
+
+    let export = keyed_post("/api/export/mif", &key, json!({"user_id": "bob"}));
+    assert_eq!(status_of(h.app(), export).await, StatusCode::FORBIDDEN);
+}
+
+#[tokio::test]
+async fn scoped_key_body_check_covers_json_suffix_types() {
+    let h = Harness::new();
+    let key = issue_key(&h, "writer", &["alice"]).await;
+
+    for content_type in ["application/vnd.x+json", "Application/JSON; charset=utf-8"] {
+        let mut req = keyed_post(
+            "/api/remember",
+            &key,
+            json!({"user_id": "bob", "content": "written into another tenant"}),
+        );
+        req.headers_mut()
+            .insert("content-type", content_type.parse().unwrap());
+        assert_eq!(
+            status_of(h.app(), req).await,
+            StatusCode::FORBIDDEN,
+            "{content_type} bypassed the tenant check"
+        );
+    }
+}
+
+#[tokio::test]
+async fn read_only_key_cannot_write() {
+    let h = Harness::new();
+    let key = issue_key(&h, "read_only", &["alice"]).await;
+
+    let write = keyed_post(
+        "/api/remember",
+        &key,
+        json!({"user_id": "alice", "content": "should be rejected"}),
+    );
+    assert_eq!(status_of(h.app(), write).await, StatusCode::FORBIDDEN);
+
+    let clear = keyed_post("/api/memories/clear", &key, json!({"user_id": "alice"}));
+    assert_eq!(status_of(h.app(), clear).await, StatusCode::FORBIDDEN);
+}
+
+#[tokio::test]
+async fn non_admin_key_cannot_manage_keys() {
+    let h = Harness::new();
+    let key = issue_key(&h, "writer", &["*"]).await;
+    assert_eq!(
+        status_of(h.app(), keyed_get("/api/keys", &key)).await,
+        StatusCode::FORBIDDEN
+    );
+}
+
+#[tokio::test]
+async fn scoped_admin_key_cannot_manage_keys() {
+    let h = Harness::new();
+    let scoped_admin = issue_key(&h, "admin", &["alice"]).await;
+    let (_, victim) = json_of(
+        h.app(),
+        authed_post(
+            "/api/keys",
+            json!({"name": "bob-writer", "role": "writer", "allowed_user_ids": ["bob"]}),
+        ),
+    )
+    .await;
+    let victim_id = victim["record"]["id"].as_str().unwrap().to_string();
+
+    let escalate = keyed_post(
+        "/api/keys",
+        &scoped_admin,
+        json!({"name": "escalated", "role": "admin", "allowed_user_ids": ["*"]}),
+    );
+    assert_eq!(status_of(h.app(), escalate).await, StatusCode::FORBIDDEN);
+
+    let own_scope = keyed_post(
+        "/api/keys",
+        &scoped_admin,
+        json!({"name": "alice-reader", "role": "read_only", "allowed_user_ids": ["alice"]}),
+    );
+    assert_eq!(status_of(h.app(), own_scope).await, StatusCode::FORBIDDEN);
+
+    for action in ["rotate", "revoke"] {
+        let uri = format!("/api/keys/{victim_id}/{action}");
+        let req = keyed_post(&uri, &scoped_admin, json!({}));
+        assert_eq!(status_of(h.app(), req).await, StatusCode::FORBIDDEN);
+    }
+    assert_eq!(
+        status_of(h.app(), keyed_get("/api/keys", &scoped_admin)).await,
+        StatusCode::FORBIDDEN
+    );
+
+    // The other tenant's key is untouched
+    let bob_key = victim["key"].as_str().unwrap();
+    assert_eq!(
+        status_of(h.app(), keyed_get("/api/users/bob/stats", bob_key)).await,
+        StatusCode::OK
+    );
+}
+
+#[tokio::test]
+async fn revoked_and_rotated_keys_stop_working() {
+    let h = Harness::new();
+    let (_, created) = json_of(
+        h.app(),
+        authed_post(
+            "/api/keys",
+            json!({"name": "rotating", "role": "read_only", "allowed_user_ids": ["alice"]}),
+        ),
+    )
+    .await;
+    let key_id = created["record"]["id"].as_str().unwrap().to_string();
+    let old_key = created["key"].as_str().unwrap().to_string();
+
+    let (status, rotated) = json_of(
+        h.app(),
+        authed_post(&format!("/api/keys/{key_id}/rotate"), json!({})),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let new_key = rotated["key"].as_str().unwrap().to_string();
+
+    let stats = |k: &str| keyed_get("/api/users/alice/stats", k);
+    assert_eq!(
+        status_of(h.app(), stats(&old_key)).await,
+        StatusCode::UNAUTHORIZED
+    );
+    assert_eq!(status_of(h.app(), stats(&new_key)).await, StatusCode::OK);
+
+    let (status, _) = json_of(
+        h.app(),
+        authed_post(&format!("/api/keys/{key_id}/revoke"), json!({})),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(
+        status_of(h.app(), stats(&new_key)).await,
+        StatusCode::UNAUTHORIZED
+    );
+}
+
+/// Open the context monitor socket with `key` and return the reply to a
+/// handshake for `user_id`
+async fn context_monitor_handshake(
+    addr: std::net::SocketAddr,
+    key: &str,
+    user_id: &str,
+) -> serde_json::Value {
+    use futures::{SinkExt, StreamExt};
+    use tokio_tungstenite::tungstenite::{client::IntoClientRequest, Message};
+
+    let mut request = format!("ws://{addr}/api/context/monitor")
+        .into_client_request()
+        .unwrap();
+    request
+        .headers_mut()
+        .insert("x-api-key", key.parse().unwrap());
+    let (mut socket, _) = tokio_tungstenite::connect_async(request).await.unwrap();
+
+    let handshake = json!({ "user_id": user_id }).to_string();
+    socket.send(Message::Text(handshake.into())).await.unwrap();
+    let reply = tokio::time::timeout(std::time::Duration::from_secs(10), socket.next())
+        .await
+        .expect("context monitor reply timed out")
+        .expect("context monitor closed without replying")
+        .unwrap();
+    serde_json::from_str(reply.to_text().unwrap()).unwrap()
+}
+
+#[tokio::test]
+async fn scoped_key_cannot_monitor_another_users_context() {
+    let h = Harness::new();
+    let key = issue_key(&h, "read_only", &["alice"]).await;
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let app = h.app();
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+    let reply = context_monitor_handshake(addr, &key, "bob").await;
+    assert_eq!(reply["type"], "error", "{reply}");
+    assert_eq!(reply["code"], "USER_NOT_PERMITTED", "{reply}");
+
+    let reply = context_monitor_handshake(addr, &key, "alice").await;
+    assert_eq!(reply["type"], "ack", "{reply}");
+}
+
+// ═══════════════════════════════════════════════════════════════════════
+// End-to-end: remember → recall cycle
+// ═══════════════════════════════════════════════════════════════════════
+
+#[tokio::test]
+async fn remember_then_list() {
+    let h = Harness::new();
+
+    // Store a memory
+    let (status, _) = json_of(
+        h.app(),
+        authed_post(
+            "/api/remember",
+            json!({
+                "user_id": "e2e-user",
+                "content": "Melbourne has unpredictable weather.",
+                "tags": ["weather", "melbourne"]
+            }),
+        ),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+
+    // List memories — should have at least one
+    let (status, body) = json_of(h.app(), authed_get("/api/list/e2e-user")).await;
+    assert_eq!(status, StatusCode::OK);
+    let memories = body.as_array().or(body["memories"].as_array());
+    assert!(
+        memories.map(|m| !m.is_empty()).unwrap_or(false),
+        "should see stored memory in list: {body}"
+    );
+
+    // User stats should show 1 memory
+    let (status, body) = json_of(h.app(), authed_get("/api/users/e2e-user/stats")).await;
+    assert_eq!(status, StatusCode::OK);
+    let count = body["total_memories"]
+        .as_u64()
+        .or(body["memory_count"].as_u64());
+    assert!(
+        count.unwrap_or(0) >= 1,
+        "stats should show at least 1 memory: {body}"
+    );
+}
+
+// ═══════════════════════════════════════════════════════════════════════
+// MCP (STREAMABLE HTTP)
+// ═══════════════════════════════════════════════════════════════════════
+
+fn mcp_post(uri: &str, key: &str, session: Option<&str>, body: serde_json::Value) -> Request<Body> {
+    let mut builder = Request::builder()
+        .method(Method::POST)
+        .uri(uri)
+        .header("content-type", "application/json")
+        .header("accept", "application/json, text/event-stream")
+        .header("x-api-key", key);
+    if let Some(session) = session {
+        builder = builder.header("mcp-session-id", session);
+    }
+    builder
+        .body(Body::from(serde_json::to_vec(&body).unwrap()))
+        .unwrap()
+}
+
+/// Read the first JSON-RPC message from an SSE response body
+async fn mcp_message(resp: axum::response::Response) -> serde_json::Value {
+    let mut body = resp.into_body();
+    let mut buf = String::new();
+    loop {
+        let frame = tokio::time::timeout(std::time::Duration::from_secs(10), body.frame())
+            .await
+            .expect("MCP response timed out")
+            .expect("MCP stream ended")
+            .unwrap();
+        if let Ok(data) = frame.into_data() {
+            buf.push_str(&String::from_utf8_lossy(&data));
+        }
+        if let Some(line) = buf.lines().find(|l| l.starts_with("data:") && l.len() > 5) {
+            return serde_json::from_str(line["data:".len()..].trim()).unwrap();
+        }
+    }
+}
+
+/// Initialize an MCP session on `uri`, returning the session ID
+async fn mcp_session(app: &Router, uri: &str, key: &str) -> String {
+    let init = json!({
+        "jsonrpc": "2.0", "id": 1, "method": "initialize",
+        "params": {
+            "protocolVersion": "2025-03-26",
+            "capabilities": {},
+            "clientInfo": {"name": "handler-tests", "version": "0"}
+        }
+    });
+    let resp = app
+        .clone()
+        .oneshot(mcp_post(uri, key, None, init))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    let session = resp.headers()["mcp-session-id"]
+        .to_str()
+        .unwrap()
+        .to_string();
+    let init_result = mcp_message(resp).await;
+    assert!(
+        init_result["result"]["serverInfo"].is_object(),
+        "{init_result}"
+    );
+
+    let initialized = json!({"jsonrpc": "2.0", "method": "notifications/initialized"});
+    let resp = app
+        .clone()
+        .oneshot(mcp_post(uri, key, Some(&session), initialized))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::ACCEPTED);
+    session
+}
+
+async fn mcp_call(
+    app: &Router,
+    uri: &str,
+    key: &str,
+    session: &str,
+    tool: &str,
+    arguments: serde_json::Value,
+) -> serde_json::Value {
+    let call = json!({
+        "jsonrpc": "2.0", "id": 2, "method": "tools/call",
+        "params": {"name": tool, "arguments": arguments}
+    });
+    let resp = app
+        .clone()
+        .oneshot(mcp_post(uri, key, Some(session), call))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    mcp_message(resp).await
+}
+
+#[tokio::test]
+async fn mcp_streamable_http_remember_and_list_todos() {
+    let h = Harness::new();
+    let app = h.app();
+    let uri = "/mcp?user_id=mcp-user";
+    let session = mcp_session(&app, uri, TEST_KEY).await;
+
+    let stored = mcp_call(
+        &app,
+        uri,
+        TEST_KEY,
+        &session,
+        "remember",
+        json!({"content": "The deploy pipeline runs on Fridays", "tags": ["deploy"]}),
+    )
+    .await;
+    assert_eq!(stored["result"]["isError"], json!(false), "{stored}");
+
+    let added = mcp_call(
+        &app,
+        uri,
+        TEST_KEY,
+        &session,
+        "add_todo",
+        json!({"content": "Review deploy pipeline"}),
+    )
+    .await;
+    assert_eq!(added["result"]["isError"], json!(false), "{added}");
+
+    let listed = mcp_call(&app, uri, TEST_KEY, &session, "list_todos", json!({})).await;
+    let text = listed["result"]["content"][0]["text"].as_str().unwrap();
+    assert!(text.contains("Review deploy pipeline"), "{text}");
+
+    // Writes went straight into the manager for the URL's user
+    let (status, body) = json_of(app.clone(), authed_get("/api/users/mcp-user/stats")).await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(body["total_memories"].as_u64().unwrap_or(0) >= 1, "{body}");
+}
+
+#[tokio::test]
+async fn mcp_tools_enforce_key_role_and_scope() {
+    let h = Harness::new();
+    let app = h.app();
+    let key = issue_key(&h, "read_only", &["alice"]).await;
+
+    // Single-user key: user_id comes from the key when the URL has none
+    let session = mcp_session(&app, "/mcp", &key).await;
+    let recalled = mcp_call(
+        &app,
+        "/mcp",
+        &key,
+        &session,
+        "recall",
+        json!({"query": "x"}),
+    )
+    .await;
+    assert!(recalled["result"].is_object(), "{recalled}");
+
+    let write = mcp_call(
+        &app,
+        "/mcp",
+        &key,
+        &session,
+        "remember",
+        json!({"content": "should be rejected"}),
+    )
+    .await;
+    assert!(
+        write["error"].is_object(),
+        "read-only key wrote via MCP: {write}"
+    );
+
+    // Cross-tenant user on the URL is rejected by the auth middleware
+    let resp = app
+        .clone()
+        .oneshot(mcp_post("/mcp?user_id=bob", &key, None, json!({})))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+}
+
+#[tokio::test]
+async fn mcp_tools_share_descriptions_and_rest_errors() {
+    let h = Harness::new();
+    let app = h.app();
+    let uri = "/mcp?user_id=mcp-user";
+    let session = mcp_session(&app, uri, TEST_KEY).await;
+
+    let list = json!({"jsonrpc": "2.0", "id": 2, "method": "tools/list"});
+    let resp = app
+        .clone()
+        .oneshot(mcp_post(uri, TEST_KEY, Some(&session), list))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    let listed = mcp_message(resp).await;
+    let tools = listed["result"]["tools"].as_array().unwrap();
+    assert_eq!(tools.len(), 46, "{listed}");
+    for tool in tools {
+        let name = tool["name"].as_str().unwrap();
+        let expected = tool_description(name).expect(name);
+        assert_eq!(tool["description"], json!(expected), "{name}");
+    }
+
+    // REST client errors come back as invalid params carrying the REST code
+    let missing = mcp_call(
+        &app,
+        uri,
+        TEST_KEY,
+        &session,
+        "get_todo",
+        json!({"todo_id": "NOPE-404"}),
+    )
+    .await;
+    assert!(missing["error"]["data"]["code"].is_string(), "{missing}");
+}
+
+// ═══════════════════════════════════════════════════════════════════════
+// OUTBOUND WEBHOOKS
+// ═══════════════════════════════════════════════════════════════════════
+
+#[tokio::test]
+async fn webhook_subscription_lifecycle_and_delivery_log() {
+    let h = Harness::new();
+
+    // Internal targets are refused (SSRF)
+    for url in ["http://127.0.0.1:9/hook", "http://169.254.169.254/latest"] {
+        let (status, body) = json_of(
+            h.app(),
+            authed_post("/api/webhooks", json!({"user_id": "hook-user", "url": url})),
+        )
+        .await;
+        assert_eq!(status, StatusCode::BAD_REQUEST, "{url}: {body}");
+    }
+
+    let (status, body) = json_of(
+        h.app(),
+        authed_post(
+            "/api/webhooks",
+            json!({
+                "user_id": "hook-user",
+                "url": "http://93.184.216.34:9/hook",
+                "events": ["memory_created"]
+            }),
+        ),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    let webhook_id = body["id"].as_str().unwrap().to_string();
+    assert!(body["secret"].as_str().unwrap().starts_with("whsec_"));
+
+    let (status, body) = json_of(h.app(), authed_get("/api/webhooks?user_id=hook-user")).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["count"], 1);
+    assert!(
+        body["webhooks"][0].get("secret").is_none(),
+        "secret leaked: {body}"
+    );
+
+    let (status, _) = json_of(
+        h.app(),
+        authed_post(
+            "/api/remember",
+            json!({"user_id": "hook-user", "content": "Webhooks fire on new memories."}),
+        ),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+
+    let (status, body) = json_of(
+        h.app(),
+        authed_get("/api/webhooks/deliveries?user_id=hook-user"),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["count"], 1, "{body}");
+    assert_eq!(body["deliveries"][0]["event_type"], "memory_created");
+    assert_eq!(body["deliveries"][0]["status"], "pending");
+
+    let (status, _) = json_of(
+        h.app(),
+        authed_post(
+            "/api/webhooks",
+            json!({"user_id": "hook-user", "url": "ftp://example.com"}),
+        ),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let uri = format!("/api/webhooks/{webhook_id}?user_id=hook-user");
+    let (status, _) = json_of(h.app(), authed_delete(&uri)).await;
+    assert_eq!(status, StatusCode::OK);
+    let (status, _) = json_of(h.app(), authed_delete(&uri)).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn backup_restore_and_point_in_time_recovery() {
+    let h = Harness::new();
+    let remember = |content: &str| {
+        authed_post(
+            "/api/remember",
+            json!({"user_id": "restore-user", "content": content}),
+        )
+    };
+    let memory_count = |h: &Harness| {
+        let app = h.app();
+        async move {
+            let (status, body) = json_of(
+                app,
+                authed_post("/api/memories", json!({"user_id": "restore-user"})),
+            )
+            .await;
+            assert_eq!(status, StatusCode::OK, "{body}");
+            body["memories"].as_array().unwrap().len()
+        }
+    };
+
+    let (status, _) = json_of(h.app(), remember("Rust ownership prevents data races.")).await;
+    assert_eq!(status, StatusCode::OK);
+    let (status, body) = json_of(
+        h.app(),
+        authed_post("/api/backup/create", json!({"user_id": "restore-user"})),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["success"], true, "{body}");
+    let (status, _) = json_of(h.app(), remember("Tokio schedules tasks cooperatively.")).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(memory_count(&h).await, 2);
+
+    // Plain restore goes back to the backup point
+    let (status, body) = json_of(
+        h.app(),
+        authed_post("/api/backup/restore", json!({"user_id": "restore-user"})),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    assert!(body["restore"]["restored_stores"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .any(|s| s == "graph_entities"));
+    assert_eq!(memory_count(&h).await, 1);
+
+    // PITR: keep a write archived before the target, drop one made after it
+    let (status, _) = json_of(h.app(), remember("RocksDB keeps a write-ahead log.")).await;
+    assert_eq!(status, StatusCode::OK);
+    let mgr = h.mgr.clone();
+    tokio::task::spawn_blocking(move || mgr.archive_wal_all_users())
+        .await
+        .unwrap();
+    let target_time = chrono::Utc::now();
+    tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+    let (status, _) = json_of(h.app(), remember("This memory is after the target.")).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(memory_count(&h).await, 3);
+
+    let (status, body) = json_of(
+        h.app(),
+        authed_post(
+            "/api/backup/restore",
+            json!({"user_id": "restore-user", "target_time": target_time}),
+        ),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    assert!(body["restore"]["replayed_batches"].as_u64().unwrap() > 0);
+    assert_eq!(memory_count(&h).await, 2);
+
+    // No backup exists before this point
+    let (status, _) = json_of(
+        h.app(),
+        authed_post(
+            "/api/backup/restore",
+            json!({"user_id": "restore-user", "target_time": "2001-01-01T00:00:00Z"}),
+        ),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn evicted_users_have_their_wal_archived() {
+    let h = Harness::with_config(|cfg| cfg.max_users_in_memory = 1);
+    let remember = |content: &str| {
+        authed_post(
+            "/api/remember",
+            json!({"user_id": "evicted-user", "content": content}),
+        )
+    };
+
+    let (status, _) = json_of(h.app(), remember("Written before the backup.")).await;
+    assert_eq!(status, StatusCode::OK);
+    let (status, _) = json_of(
+        h.app(),
+        authed_post("/api/backup/create", json!({"user_id": "evicted-user"})),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let (status, _) = json_of(h.app(), remember("Written after the backup.")).await;
+    assert_eq!(status, StatusCode::OK);
+
+    // Offer another user until the cache admits it over the evicted one.
+    // The store is opened once: a rejected entry is dropped lazily, and
+    // reopening it before then would trip over its own RocksDB lock.
+    let mgr = h.mgr.clone();
+    tokio::task::spawn_blocking(move || {
+        let other = mgr.get_user_memory("other-user").unwrap();
+        for _ in 0..50 {
+            for _ in 0..4 {
+                mgr.user_memories.get("other-user");
+            }
+            mgr.user_memories
+                .insert("other-user".to_string(), other.clone());
+            mgr.user_memories.run_pending_tasks();
+            if !mgr.user_memories.contains_key("evicted-user") {
+                return;
+            }
+        }
+        panic!("evicted-user was never evicted");
+    })
+    .await
+    .unwrap();
+
+    let wal_dir = h
+        .dir
+        .path()
+        .join("backups/evicted-user/wal")
+        .join(shodh_memory::backup::MAIN_STORE);
+    let segments = std::fs::read_dir(&wal_dir)
+        .expect("WAL archived on eviction")
+        .flatten()
+        .filter(|e| e.path().extension().is_some_and(|ext| ext == "wal"))
+        .count();
+    assert!(segments > 0);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn streaming_export_import_roundtrip() {
+    let h = Harness::new();
+    for content in [
+        "Parquet stores data column by column.",
+        "JSON lines are easy to stream.",
+    ] {
+        let (status, _) = json_of(
+            h.app(),
+            authed_post(
+                "/api/remember",
+                json!({"user_id": "export-src", "content": content}),
+            ),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+    }
+    let (status, _) = json_of(
+        h.app(),
+        authed_post(
+            "/api/todos/add",
+            json!({"user_id": "export-src", "content": "Ship the exporter"}),
+        ),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+
+    let export = |body: serde_json::Value| {
+        let app = h.app();
+        async move {
+            let resp = app
+                .oneshot(authed_post("/api/export/stream", body))
+                .await
+                .unwrap();
+            assert_eq!(resp.status(), StatusCode::OK);
+            resp.into_body().collect().await.unwrap().to_bytes()
+        }
+    };
+    let import = |user: &str, format: &str, bytes: axum::body::Bytes| {
+        let app = h.app();
+        let req = Request::builder()
+            .method(Method::POST)
+            .uri(format!("/api/import/stream?user_id={user}&format={format}"))
+            .header("x-api-key", TEST_KEY)
+            .body(Body::from(bytes))
+            .unwrap();
+        async move { json_of(app, req).await }
+    };
+    let counts = |user: &'static str| {
+        let app = h.app();
+        async move {
+            let (_, memories) = json_of(
+                app.clone(),
+                authed_post("/api/memories", json!({"user_id": user})),
+            )
+            .await;
+            let (_, todos) =
+                json_of(app, authed_post("/api/todos", json!({"user_id": user}))).await;
+            (
+                memories["memories"].as_array().unwrap().len(),
+                todos["todos"].as_array().unwrap().len(),
+            )
+        }
+    };
+
+    // Creating a todo also records a memory in the background
+    for _ in 0..100 {
+        if counts("export-src").await.0 == 3 {
+            break;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+    }
+    assert_eq!(counts("export-src").await, (3, 1));
+
+    // JSONL: every record carries its store and a resumable cursor
+    let jsonl = export(json!({"user_id": "export-src"})).await;
+    let lines: Vec<serde_json::Value> = jsonl
+        .split(|b| *b == b'\n')
+        .filter(|l| !l.is_empty())
+        .map(|l| serde_json::from_slice(l).unwrap())
+        .collect();
+    assert_eq!(lines.iter().filter(|l| l["store"] == "memories").count(), 3);
+    assert_eq!(lines.iter().filter(|l| l["store"] == "todos").count(), 1);
+
+    let stores = json!(["memories", "todos"]);
+    let first = export(json!({"user_id": "export-src", "stores": stores, "limit": 1})).await;
+    let first: serde_json::Value =
+        serde_json::from_slice(first.split(|b| *b == b'\n').next().unwrap()).unwrap();
+    let rest = export(json!({
+        "user_id": "export-src",
+        "stores": stores,
+        "cursor": first["cursor"]
+    }))
+    .await;
+    let rest: Vec<serde_json::Value> = rest
+        .split(|b| *b == b'\n')
+        .filter(|l| !l.is_empty())
+        .map(|l| serde_json::from_slice(l).unwrap())
+        .collect();
+    assert_eq!(rest.len(), 3);
+    assert!(rest.iter().all(|r| r["id"] != first["id"]));
+
+    let (status, body) = import("export-jsonl", "jsonl", jsonl).await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    assert_eq!(body["report"]["failed"], 0, "{body}");
+    assert_eq!(counts("export-jsonl").await, (3, 1));
+
+    // Parquet round trip, then a re-import is idempotent
+    let parquet = export(json!({"user_id": "export-src", "format": "parquet"})).await;
+    assert_eq!(&parquet[..4], b"PAR1");
+    let (status, body) = import("export-parquet", "parquet", parquet.clone()).await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    assert_eq!(body["report"]["imported"]["memories"], 3, "{body}");
+    let (status, _) = import("export-parquet", "parquet", parquet).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(counts("export-parquet").await, (3, 1));
+
+    let (status, _) = import("export-parquet", "parquet", "not parquet".into()).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn shared_space_membership_and_fanout_recall() {
+    let h = Harness::new();
+    let remember = |user: &str, content: &str, space: Option<&str>| {
+        authed_post(
+            "/api/remember",
+            json!({"user_id": user, "content": content, "space": space}),
+        )
+    };
+
+    let (status, body) = json_of(
+        h.app(),
+        authed_post(
+            "/api/spaces",
+            json!({"user_id": "alice", "space_id": "team", "name": "Team"}),
+        ),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    assert_eq!(body["members"]["alice"], "owner");
+
+    // Non-members can't see the space; readers can't write to it
+    let status = status_of(h.app(), remember("bob", "Unauthorized note.", Some("team"))).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    let (status, _) = json_of(
+        h.app(),
+        authed_post(
+            "/api/spaces/team/members",
+            json!({"user_id": "alice", "member_id": "bob", "role": "read"}),
+        ),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let status = status_of(h.app(), remember("bob", "Read-only note.", Some("team"))).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    let (status, body) = json_of(
+        h.app(),
+        remember(
+            "alice",
+            "The deploy pipeline for the payments service runs every Friday.",
+            Some("team"),
+        ),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    assert_eq!(body["space"], "team");
+    let space_memory_id = body["id"].as_str().unwrap().to_string();
+    let (status, _) = json_of(
+        h.app(),
+        remember(
+            "bob",
+            "The deploy dashboard for payments shows pipeline failures in red.",
+            None,
+        ),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+
+    let recall = |spaces: serde_json::Value| {
+        authed_post(
+            "/api/recall",
+            json!({"user_id": "bob", "query": "payments deploy pipeline", "spaces": spaces}),
+        )
+    };
+    let (status, body) = json_of(h.app(), recall(json!(["team"]))).await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    let memories = body["memories"].as_array().unwrap();
+    let from_space = memories
+        .iter()
+        .find(|m| m["id"] == space_memory_id.as_str())
+        .expect("space memory should be recalled");
+    assert_eq!(from_space["space"], "team");
+    assert!(memories.iter().any(|m| m.get("space").is_none()));
+
+    let (status, body) = json_of(h.app(), recall(json!([]))).await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(body["memories"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .all(|m| m["id"] != space_memory_id.as_str()));
+    let status = status_of(h.app(), recall(json!(["other"]))).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    let (status, body) = json_of(h.app(), authed_get("/api/spaces?user_id=bob")).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["count"], 1);
+    assert_eq!(body["spaces"][0]["role"], "read");
+
+    let status = status_of(h.app(), authed_delete("/api/spaces/team?user_id=bob")).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    let status = status_of(h.app(), authed_delete("/api/spaces/team?user_id=alice")).await;
+    assert_eq!(status, StatusCode::OK);
+    let status = status_of(h.app(), recall(json!(["team"]))).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn user_id_cannot_shadow_the_spaces_directory() {
+    let h = Harness::new();
+    for user_id in ["spaces", "SPACES"] {
+        let (status, _) = json_of(
+            h.app(),
+            authed_post(
+                "/api/remember",
+                json!({"user_id": user_id, "content": "Lands in the spaces directory."}),
+            ),
+        )
+        .await;
+        assert_eq!(status, StatusCode::BAD_REQUEST, "{user_id}");
+    }
+}
+
+#[tokio::test]
+async fn agent_scoped_recall_forget_and_stats() {
+    let h = Harness::new();
+    for (agent, run, content) in [
+        (
+            "planner",
+            "run-1",
+            "Planner chose to shard the orders table by region.",
+        ),
+        (
+            "planner",
+            "run-2",
+            "Planner chose to cache the orders table in Redis.",
+        ),
+        (
+            "coder",
+            "run-2",
+            "Coder migrated the orders table to the new schema.",
+        ),
+    ] {
+        let (status, body) = json_of(
+            h.app(),
+            authed_post(
+                "/api/remember",
+                json!({"user_id": "ops", "content": content, "agent_id": agent, "run_id": run}),
+            ),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK, "{body}");
+    }
+
+    let (status, body) = json_of(
+        h.app(),
+        authed_post(
+            "/api/recall",
+            json!({
+                "user_id": "ops",
+                "query": "orders table",
+                "filter": {"agent_id": "planner", "run_id": "run-2"}
+            }),
+        ),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    let memories = body["memories"].as_array().unwrap();
+    assert_eq!(memories.len(), 1);
+    assert!(memories[0]["experience"]["content"]
+        .as_str()
+        .unwrap()
+        .contains("Redis"));
+
+    let (status, body) = json_of(h.app(), authed_get("/api/list/ops?agent_id=planner")).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["total"], 2);
+
+    let (status, body) = json_of(h.app(), authed_get("/api/users/ops/stats")).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["agents"]["planner"]["memory_count"], 2);
+    assert_eq!(body["agents"]["planner"]["run_count"], 2);
+
+    // Dropping a failed run leaves other runs alone
+    let (status, body) = json_of(
+        h.app(),
+        authed_post(
+            "/api/forget/agent",
+            json!({"user_id": "ops", "run_id": "run-2"}),
+        ),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    assert_eq!(body["deleted_count"], 2);
+    let (_, body) = json_of(h.app(), authed_get("/api/users/ops/stats")).await;
+    assert_eq!(body["agents"]["planner"]["memory_count"], 1);
+    assert!(body["agents"].get("coder").is_none());
+
+    let status = status_of(
+        h.app(),
+        authed_post(
+            "/api/forget/agent",
+            json!({"user_id": "ops", "agent_id": "planner", "run_id": "run-1"}),
+        ),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
 }
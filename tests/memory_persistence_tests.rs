@@ -16,6 +16,7 @@ use tempfile::TempDir;
 use uuid::Uuid;
 
 use shodh_memory::embeddings::ner::{NerConfig, NeuralNer};
+use shodh_memory::embeddings::Embedder;
 use shodh_memory::memory::{
     retrieval::RetrievalOutcome,
     types::{Experience, ExperienceType, Query},
@@ -116,6 +117,78 @@ fn test_memory_survives_system_restart() {
     }
 }
 
+/// Bag-of-words hashing embedder with a non-MiniLM dimension
+struct HashingEmbedder;
+
+impl Embedder for HashingEmbedder {
+    fn encode(&self, text: &str) -> anyhow::Result<Vec<f32>> {
+        let mut v = vec![0.0; 64];
+        for word in text.to_lowercase().split_whitespace() {
+            let bucket = word.bytes().fold(7usize, |h, b| h * 31 + b as usize) % 64;
+            v[bucket] += 1.0;
+        }
+        let norm = v.iter().map(|x| x * x).sum::<f32>().sqrt().max(1e-6);
+        Ok(v.into_iter().map(|x| x / norm).collect())
+    }
+
+    fn dimension(&self) -> usize {
+        64
+    }
+
+    fn model_id(&self) -> String {
+        "test-hashing-64".to_string()
+    }
+}
+
+#[test]
+fn test_embedding_model_change_requires_explicit_reembed() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let config = create_test_config(&temp_dir);
+
+    let memory_id;
+    {
+        let system = MemorySystem::new(config.clone()).expect("Failed to create system");
+        let exp = create_experience("Deploy pipeline uses blue green rollout", vec![]);
+        memory_id = system.remember(exp, None).expect("Failed to record");
+    }
+
+    // Restart with a 64-dim model: opening only reports the mismatch
+    let system = MemorySystem::with_embedder(config.clone(), Arc::new(HashingEmbedder))
+        .expect("Failed to reopen with new embedder");
+    assert!(system.requires_reembed().unwrap());
+    let stored = system.get_memory(&memory_id).expect("Memory should load");
+    assert_eq!(
+        stored.experience.embeddings.as_ref().map(Vec::len),
+        Some(384),
+        "Opening must not re-embed the store"
+    );
+
+    assert_eq!(system.reembed().expect("Failed to re-embed"), 1);
+    assert!(!system.requires_reembed().unwrap());
+
+    let query = Query {
+        query_text: Some("blue green rollout".to_string()),
+        max_results: 5,
+        ..Default::default()
+    };
+    let results = system.recall(&query).expect("Failed to retrieve");
+    let found = results
+        .iter()
+        .find(|m| m.id == memory_id)
+        .expect("Memory should be searchable after re-embedding");
+    assert_eq!(
+        found.experience.embeddings.as_ref().map(Vec::len),
+        Some(64),
+        "Stored embedding should come from the new model"
+    );
+    drop(system);
+
+    // The new fingerprint is recorded, so the next open is clean
+    let system =
+        MemorySystem::with_embedder(config, Arc::new(HashingEmbedder)).expect("Failed to reopen");
+    assert!(!system.requires_reembed().unwrap());
+}
+
 #[test]
 fn test_importance_changes_survive_restart() {
     let temp_dir = TempDir::new().expect("Failed to create temp dir");
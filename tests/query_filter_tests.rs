@@ -8,8 +8,10 @@ use std::collections::HashMap;
 use std::sync::Arc;
 
 use shodh_memory::embeddings::ner::{NerConfig, NeuralNer};
+use shodh_memory::memory::storage::SearchCriteria;
 use shodh_memory::memory::types::{
-    Experience, ExperienceType, GeoFilter, Memory, MemoryId, Query, RetrievalMode,
+    Experience, ExperienceType, GeoFilter, HeadingCone, LocalRegion, LocalSpatialFilter, Memory,
+    MemoryId, Query, RetrievalMode, LOCAL_FRAME_METADATA_KEY,
 };
 use shodh_memory::uuid::Uuid;
 
@@ -408,6 +410,98 @@ fn test_geo_filter_memory_no_location() {
     );
 }
 
+// ============================================================================
+// LOCAL-FRAME FILTER TESTS
+// ============================================================================
+
+fn create_local_memory(position: Option<[f32; 3]>, heading: Option<f32>, frame: &str) -> Memory {
+    let experience = Experience {
+        content: "waypoint".to_string(),
+        robot_id: Some("amr_01".to_string()),
+        local_position: position,
+        heading,
+        metadata: HashMap::from([(LOCAL_FRAME_METADATA_KEY.to_string(), frame.to_string())]),
+        ..Default::default()
+    };
+    Memory::new(
+        MemoryId(Uuid::new_v4()),
+        experience,
+        0.5,
+        None,
+        None,
+        None,
+        None,
+    )
+}
+
+#[test]
+fn test_local_filter_radius_and_frame() {
+    let memory = create_local_memory(Some([3.0, 4.0, 0.0]), None, "warehouse");
+    let within = |radius_meters: f32, frame: &str| Query {
+        local_filter: Some(LocalSpatialFilter::new(
+            frame,
+            LocalRegion::Radius {
+                center: [0.0, 0.0, 0.0],
+                radius_meters,
+            },
+        )),
+        ..Default::default()
+    };
+
+    assert!(within(5.0, "warehouse").matches(&memory));
+    assert!(
+        !within(4.9, "warehouse").matches(&memory),
+        "Memory 5m away should not match a 4.9m radius"
+    );
+    assert!(
+        !within(5.0, "map").matches(&memory),
+        "Memory in another frame should not match"
+    );
+}
+
+#[test]
+fn test_local_filter_bbox_and_heading_cone() {
+    let memory = create_local_memory(Some([1.0, 1.0, 0.5]), Some(350.0), "map");
+    let bbox = LocalSpatialFilter::new(
+        "map",
+        LocalRegion::BoundingBox {
+            min: [0.0, 0.0, 0.0],
+            max: [2.0, 2.0, 1.0],
+        },
+    );
+    let query = |filter: LocalSpatialFilter| Query {
+        local_filter: Some(filter),
+        ..Default::default()
+    };
+
+    assert!(query(bbox.clone()).matches(&memory));
+    // 350 is 20 degrees from 10, across the 0/360 wrap
+    assert!(query(bbox.clone().with_heading_cone(HeadingCone::new(10.0, 30.0))).matches(&memory));
+    assert!(!query(bbox.clone().with_heading_cone(HeadingCone::new(90.0, 30.0))).matches(&memory));
+
+    let no_heading = create_local_memory(Some([1.0, 1.0, 0.5]), None, "map");
+    assert!(!query(bbox.with_heading_cone(HeadingCone::new(10.0, 30.0))).matches(&no_heading));
+}
+
+#[test]
+fn test_local_filter_memory_no_position() {
+    let memory = create_local_memory(None, None, "map");
+    let query = Query {
+        local_filter: Some(LocalSpatialFilter::new(
+            "map",
+            LocalRegion::Nearest {
+                center: [0.0, 0.0, 0.0],
+                k: 5,
+            },
+        )),
+        ..Default::default()
+    };
+    assert!(
+        !query.matches(&memory),
+        "Memory without local_position should not match local filter"
+    );
+}
+
 // ============================================================================
 // ACTION TYPE FILTER TESTS
 // ============================================================================
@@ -833,6 +927,97 @@ fn test_combined_one_fails() {
     );
 }
 
+// ============================================================================
+// STRUCTURED FILTER (SearchCriteria) TESTS
+// ============================================================================
+
+#[test]
+fn test_filter_tags_case_insensitive() {
+    let mut memory = create_test_memory("Tagged memory", 0.5);
+    memory.experience.tags = vec!["Deploy".to_string()];
+
+    let query = Query::builder()
+        .filter(SearchCriteria::ByTags(vec!["deploy".to_string()]))
+        .build();
+    assert!(query.matches(&memory));
+
+    let query = Query::builder()
+        .filter(SearchCriteria::ByTags(vec!["rollback".to_string()]))
+        .build();
+    assert!(!query.matches(&memory));
+}
+
+#[test]
+fn test_filter_date_is_exact_not_day_bucketed() {
+    let memory = create_test_memory("Timed memory", 0.5);
+    let created = memory.created_at;
+
+    // Same calendar day as the index bucket, but after the memory was created
+    let query = Query::builder()
+        .filter(SearchCriteria::ByDate {
+            start: created + Duration::seconds(1),
+            end: created + Duration::hours(1),
+        })
+        .build();
+    assert!(!query.matches(&memory));
+
+    let query = Query::builder()
+        .filter(SearchCriteria::ByDate {
+            start: created - Duration::seconds(1),
+            end: created + Duration::seconds(1),
+        })
+        .build();
+    assert!(query.matches(&memory));
+}
+
+#[test]
+fn test_filter_combined_requires_all() {
+    let memory = create_robotics_memory(
+        "Robot picked up box",
+        Some("robot_001"),
+        Some("mission_alpha"),
+        Some([37.7749, -122.4194, 0.0]),
+        Some("pick"),
+        Some(0.8),
+        None,
+    );
+
+    let matching = SearchCriteria::Combined(vec![
+        SearchCriteria::ByRobot("robot_001".to_string()),
+        SearchCriteria::ByType(ExperienceType::Task),
+        SearchCriteria::ByReward { min: 0.5, max: 1.0 },
+        SearchCriteria::ByLocation {
+            lat: 37.7749,
+            lon: -122.4194,
+            radius_meters: 100.0,
+        },
+    ]);
+    assert!(Query::builder().filter(matching).build().matches(&memory));
+
+    let one_fails = SearchCriteria::Combined(vec![
+        SearchCriteria::ByRobot("robot_001".to_string()),
+        SearchCriteria::ByMission("mission_beta".to_string()),
+    ]);
+    assert!(!Query::builder().filter(one_fails).build().matches(&memory));
+}
+
+#[test]
+fn test_filter_hierarchy() {
+    let parent = create_test_memory("Parent", 0.5);
+    let mut child = create_test_memory("Child", 0.5);
+    child.parent_id = Some(parent.id.clone());
+
+    let roots = Query::builder().filter(SearchCriteria::RootsOnly).build();
+    assert!(roots.matches(&parent));
+    assert!(!roots.matches(&child));
+
+    let children = Query::builder()
+        .filter(SearchCriteria::ByParent(parent.id.clone()))
+        .build();
+    assert!(children.matches(&child));
+    assert!(!children.matches(&parent));
+}
+
 // ============================================================================
 // QUERY BUILDER TESTS
 // ============================================================================
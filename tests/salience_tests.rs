@@ -62,6 +62,9 @@ fn create_entity(
         name_embedding: None,
         salience: base_salience,
         is_proper_noun: is_proper,
+        aliases: Vec::new(),
+        centrality: 0.0,
+        community: None,
     }
 }
 
@@ -51,6 +51,9 @@ fn create_entity_from_ner(
         name_embedding: None,
         salience,
         is_proper_noun: is_proper,
+        aliases: Vec::new(),
+        centrality: 0.0,
+        community: None,
     }
 }
 
@@ -80,6 +83,9 @@ fn create_entity(
         name_embedding: None,
         salience,
         is_proper_noun: is_proper,
+        aliases: Vec::new(),
+        centrality: 0.0,
+        community: None,
     }
 }
 
@@ -114,6 +114,8 @@ struct TodoApiItem {
     contexts: Vec<String>,
     due_date: Option<String>,
     blocked_on: Option<String>,
+    #[serde(default)]
+    blocked_by: Vec<String>,
     created_at: String,
     #[serde(default)]
     parent_id: Option<String>,
@@ -304,6 +306,7 @@ impl MemoryStream {
                     contexts: t.contexts,
                     due_date: t.due_date.and_then(|d| d.parse().ok()),
                     blocked_on: t.blocked_on,
+                    blocked_by: t.blocked_by,
                     created_at: t.created_at.parse().unwrap_or_else(|_| Utc::now()),
                     parent_id: t.parent_id,
                     seq_num: t.seq_num,
@@ -662,6 +665,7 @@ impl MemoryStream {
                     contexts: t.contexts,
                     due_date: t.due_date.and_then(|d| d.parse().ok()),
                     blocked_on: t.blocked_on,
+                    blocked_by: t.blocked_by,
                     created_at: t.created_at.parse().unwrap_or_else(|_| Utc::now()),
                     parent_id: t.parent_id,
                     seq_num: t.seq_num,
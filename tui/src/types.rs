@@ -872,6 +872,10 @@ impl MemoryEvent {
             "TODO_DELETE" => Color::Red,
             "FEEDBACK_PROCESSED" => Color::Rgb(200, 180, 255), // Pastel purple
             "PROACTIVE_CONTEXT" => Color::Rgb(180, 220, 255),  // Light blue
+            "FILE_INDEXED" => Color::Rgb(180, 230, 180),       // Pastel green
+            "FILE_UPDATED" => Color::Yellow,
+            "FILE_RENAMED" => Color::Rgb(180, 200, 255), // Pastel blue
+            "FILE_REMOVED" => Color::Red,
             _ => Color::White,
         }
     }
@@ -894,6 +898,10 @@ impl MemoryEvent {
             "TODO_DELETE" => "☒",
             "FEEDBACK_PROCESSED" => "⟲",
             "PROACTIVE_CONTEXT" => "◉",
+            "FILE_INDEXED" => "+",
+            "FILE_UPDATED" => "~",
+            "FILE_RENAMED" => "→",
+            "FILE_REMOVED" => "-",
             _ => "•",
         }
     }
@@ -1286,6 +1294,9 @@ pub struct TuiTodo {
     pub contexts: Vec<String>,
     pub due_date: Option<DateTime<Utc>>,
     pub blocked_on: Option<String>,
+    /// IDs of todos that must be finished before this one
+    #[serde(default)]
+    pub blocked_by: Vec<String>,
     pub created_at: DateTime<Utc>,
     /// Parent todo ID (for subtasks)
     #[serde(default)]
@@ -2565,6 +2576,44 @@ impl AppState {
             .collect()
     }
 
+    /// Blockers of a todo that aren't Done or Cancelled yet
+    pub fn open_blockers(&self, todo: &TuiTodo) -> Vec<&TuiTodo> {
+        todo.blocked_by
+            .iter()
+            .filter_map(|id| self.todos.iter().find(|t| &t.id == id))
+            .filter(|t| !matches!(t.status, TuiTodoStatus::Done | TuiTodoStatus::Cancelled))
+            .collect()
+    }
+
+    /// Todos that can be started now: Todo/InProgress with no open
+    /// blockers, by priority then due date (same order as `/api/todos/next`)
+    pub fn next_actionable_todos(&self) -> Vec<&TuiTodo> {
+        let rank = |p: &TuiPriority| match p {
+            TuiPriority::Urgent => 0,
+            TuiPriority::High => 1,
+            TuiPriority::Medium => 2,
+            TuiPriority::Low => 3,
+        };
+        let mut todos: Vec<&TuiTodo> = self
+            .todos
+            .iter()
+            .filter(|t| matches!(t.status, TuiTodoStatus::Todo | TuiTodoStatus::InProgress))
+            .filter(|t| self.open_blockers(t).is_empty())
+            .collect();
+        todos.sort_by(|a, b| {
+            rank(&a.priority)
+                .cmp(&rank(&b.priority))
+                .then_with(|| match (a.due_date, b.due_date) {
+                    (Some(a_due), Some(b_due)) => a_due.cmp(&b_due),
+                    (Some(_), None) => std::cmp::Ordering::Less,
+                    (None, Some(_)) => std::cmp::Ordering::Greater,
+                    (None, None) => std::cmp::Ordering::Equal,
+                })
+                .then_with(|| a.created_at.cmp(&b.created_at))
+        });
+        todos
+    }
+
     /// Get overdue/due today/blocked counts for attention bar
     pub fn attention_counts(&self) -> (usize, usize, usize) {
         let overdue = self.todos.iter().filter(|t| t.is_overdue()).count();
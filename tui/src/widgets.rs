@@ -1775,12 +1775,8 @@ fn render_status_ribbon(f: &mut Frame, area: Rect, state: &AppState) {
             ));
         }
     } else {
-        // Idle state - suggest next action
-        let next_todo: Option<&TuiTodo> = state
-            .todos
-            .iter()
-            .filter(|t| t.status == TuiTodoStatus::Todo)
-            .next();
+        // Idle state - suggest the next unblocked todo
+        let next_todo: Option<&TuiTodo> = state.next_actionable_todos().first().copied();
 
         spans.push(Span::styled(
             " READY ",
@@ -3003,6 +2999,19 @@ fn render_detail_left_column(f: &mut Frame, area: Rect, todo: &TuiTodo, state: &
         ]));
     }
 
+    // Waiting row (unfinished todos this one depends on)
+    let open_blockers = state.open_blockers(todo);
+    if !open_blockers.is_empty() {
+        let ids: Vec<String> = open_blockers.iter().map(|t| t.short_id()).collect();
+        lines.push(Line::from(vec![
+            Span::styled(
+                format!(" {:label_width$}", "Waits on"),
+                Style::default().fg(TEXT_DISABLED),
+            ),
+            Span::styled(format!("⊘ {}", ids.join(", ")), Style::default().fg(MAROON)),
+        ]));
+    }
+
     // Calculate available space for notes
     let metadata_lines = lines.len();
     let available_for_notes = (area.height as usize).saturating_sub(metadata_lines + 2);